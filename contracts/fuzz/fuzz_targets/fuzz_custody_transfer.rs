@@ -4,7 +4,7 @@ use libfuzzer_sys::fuzz_target;
 use arbitrary::Arbitrary;
 use soroban_sdk::{
     testutils::{Address as _, Ledger},
-    Address, Env, String as SorobanString, Symbol,
+    Address, Env, String as SorobanString, Symbol, Vec as SorobanVec,
 };
 
 
@@ -18,7 +18,10 @@ use health_chain_contract::{
 enum CustodyOperation {
     InitiateTransfer { unit_id: u8 },
     ConfirmTransfer { event_id_index: u8 },
+    InitiateTransferBatch { unit_indices: Vec<u8> },
+    ConfirmTransferBatch { event_id_indices: Vec<u8> },
     CancelTransfer { event_id_index: u8 },
+    ExpirePendingTransfer { event_id_index: u8, past_deadline: bool },
     AdvanceTime { seconds: u16 },
 }
 
@@ -80,12 +83,13 @@ fuzz_target!(|input: FuzzInput| {
             &450,
             &expiration,
             &Some(Symbol::new(&env, &format!("DONOR{}", idx))),
+            &None,
         );
         
         unit_ids.push(unit_id);
         
         // Allocate to hospital to prepare for transfer
-        let _ = client.allocate_blood(&bank, &unit_id, &hospital);
+        let _ = client.allocate_blood(&bank, &unit_id, &hospital, &None, &None);
     }
 
     // Track pending event IDs for confirm/cancel operations
@@ -108,7 +112,7 @@ fuzz_target!(|input: FuzzInput| {
                 if let Ok(unit) = unit_result {
                     // Only initiate if status is Reserved
                     if unit.status == BloodStatus::Reserved {
-                        let result = client.try_initiate_transfer(&bank, &unit_id);
+                        let result = client.try_initiate_transfer(&bank, &unit_id, &None, &None, &None);
                         
                         if let Ok(event_id) = result {
                             pending_event_ids.push(event_id.clone());
@@ -146,7 +150,7 @@ fuzz_target!(|input: FuzzInput| {
                         let unit_id = custody_event.unit_id;
                         let old_unit = client.get_blood_unit(&unit_id);
                         
-                        let result = client.try_confirm_transfer(&hospital, &event_id);
+                        let result = client.try_confirm_transfer(&hospital, &event_id, &None, &None, &None);
                         
                         // INVARIANT 5: When unit expires during transit, recovery event is emitted
                         if result.is_err() {
@@ -187,6 +191,79 @@ fuzz_target!(|input: FuzzInput| {
                 }
             }
 
+            CustodyOperation::InitiateTransferBatch { unit_indices } => {
+                if unit_ids.is_empty() || unit_indices.is_empty() {
+                    continue;
+                }
+
+                let mut batch = SorobanVec::new(&env);
+                for idx in unit_indices.iter().take(10) {
+                    batch.push_back(unit_ids[(*idx as usize) % unit_ids.len()]);
+                }
+                let before: Vec<BloodStatus> = batch
+                    .iter()
+                    .map(|unit_id| client.get_blood_unit(&unit_id).status)
+                    .collect();
+
+                match client.try_initiate_transfer_batch(&bank, &batch, &None) {
+                    Ok(Ok(event_ids)) => {
+                        // INVARIANT 11: A successful batch yields one pending event per unit
+                        assert_eq!(event_ids.len(), batch.len());
+                        for (unit_id, event_id) in batch.iter().zip(event_ids.iter()) {
+                            let custody_event = client.get_custody_event(&event_id);
+                            assert_eq!(custody_event.status, CustodyStatus::Pending);
+                            assert_eq!(custody_event.unit_id, unit_id);
+                            pending_event_ids.push(event_id);
+                        }
+                    }
+                    _ => {
+                        // INVARIANT 12: A failed batch leaves every unit untouched
+                        for (unit_id, status) in batch.iter().zip(before.iter()) {
+                            assert_eq!(client.get_blood_unit(&unit_id).status, *status,
+                                "INVARIANT VIOLATION: Failed transfer batch changed unit {}", unit_id);
+                        }
+                    }
+                }
+            }
+
+            CustodyOperation::ConfirmTransferBatch { event_id_indices } => {
+                if pending_event_ids.is_empty() || event_id_indices.is_empty() {
+                    continue;
+                }
+
+                let mut batch = SorobanVec::new(&env);
+                for idx in event_id_indices.iter().take(10) {
+                    batch.push_back(pending_event_ids[(*idx as usize) % pending_event_ids.len()].clone());
+                }
+                let before: Vec<CustodyStatus> = batch
+                    .iter()
+                    .map(|event_id| client.get_custody_event(&event_id).status)
+                    .collect();
+
+                match client.try_confirm_transfer_batch(&hospital, &batch, &None) {
+                    Ok(Ok(_)) => {
+                        // INVARIANT 13: Every event in a confirmed batch is delivered exactly once
+                        for event_id in batch.iter() {
+                            let custody_event = client.get_custody_event(&event_id);
+                            assert_eq!(custody_event.status, CustodyStatus::Confirmed);
+                            assert_eq!(client.get_blood_unit(&custody_event.unit_id).status,
+                                BloodStatus::Delivered);
+                            if let Some(pos) = pending_event_ids.iter().position(|eid| *eid == event_id) {
+                                confirmed_transfers.push(custody_event.unit_id);
+                                pending_event_ids.remove(pos);
+                            }
+                        }
+                    }
+                    _ => {
+                        // INVARIANT 14: A failed batch confirms nothing
+                        for (event_id, status) in batch.iter().zip(before.iter()) {
+                            assert_eq!(client.get_custody_event(&event_id).status, *status,
+                                "INVARIANT VIOLATION: Failed confirmation batch changed an event");
+                        }
+                    }
+                }
+            }
+
             CustodyOperation::CancelTransfer { event_id_index } => {
                 if pending_event_ids.is_empty() {
                     continue;
@@ -200,20 +277,26 @@ fuzz_target!(|input: FuzzInput| {
                     if custody_event.status == CustodyStatus::Pending {
                         let unit_id = custody_event.unit_id;
                         
-                        // Advance time to make transfer cancellable
+                        // Advance time to the transfer deadline to make it cancellable
                         let current_time = env.ledger().timestamp();
                         env.ledger().with_mut(|li| {
-                            li.timestamp = current_time + 1800; // 30 minutes
+                            li.timestamp = custody_event.transfer_deadline.max(current_time);
                         });
                         
-                        let result = client.try_cancel_transfer(&bank, &event_id);
+                        let result = client.try_cancel_transfer(&bank, &event_id, &None);
                         
                         if result.is_ok() {
-                            // INVARIANT 6: Cancelled transfers recover the unit to a valid state (Reserved)
+                            // INVARIANT 6: Cancelled transfers recover the unit to a valid state
+                            // (Reserved, or Expired if it expired in transit)
                             // This ensures failed transfers don't permanently lose inventory
                             let updated_unit = client.get_blood_unit(&unit_id);
-                            assert_eq!(updated_unit.status, BloodStatus::Reserved,
-                                "INVARIANT VIOLATION: Cancelled transfer didn't recover unit to Reserved");
+                            let expected = if updated_unit.expiration_date <= env.ledger().timestamp() {
+                                BloodStatus::Expired
+                            } else {
+                                BloodStatus::Reserved
+                            };
+                            assert_eq!(updated_unit.status, expected,
+                                "INVARIANT VIOLATION: Cancelled transfer didn't recover unit");
                             
                             // INVARIANT 7: Cancelled transfer marks custody event as Recovered
                             // Recovered status indicates recovery action was taken (not permanent cancellation)
@@ -223,15 +306,69 @@ fuzz_target!(|input: FuzzInput| {
                             
                             pending_event_ids.remove(idx);
                             
-                            // INVARIANT 8: Recovered transfers return unit to reusable state
+                            // INVARIANT 8: Recovered transfers never leave the unit in transit
                             // Backend can now re-allocate this unit for another transfer
-                            assert_eq!(updated_unit.status, BloodStatus::Reserved,
+                            assert_ne!(updated_unit.status, BloodStatus::InTransit,
                                 "INVARIANT VIOLATION: Recovered unit not in valid reusable state");
                         }
                     }
                 }
             }
 
+            CustodyOperation::ExpirePendingTransfer { event_id_index, past_deadline } => {
+                if pending_event_ids.is_empty() {
+                    continue;
+                }
+
+                let idx = (*event_id_index as usize) % pending_event_ids.len();
+                let event_id = pending_event_ids[idx].clone();
+
+                let custody_event_result = client.try_get_custody_event(&event_id);
+                if let Ok(custody_event) = custody_event_result {
+                    if custody_event.status == CustodyStatus::Pending {
+                        let unit_id = custody_event.unit_id;
+
+                        // Optionally jump to the deadline so both outcomes are exercised
+                        if *past_deadline {
+                            let current_time = env.ledger().timestamp();
+                            let deadline = custody_event.transfer_deadline.max(current_time);
+                            env.ledger().with_mut(|li| {
+                                li.timestamp = deadline;
+                            });
+                        }
+
+                        let before_deadline =
+                            env.ledger().timestamp() < custody_event.transfer_deadline;
+                        let result = client.try_expire_pending_transfer(&event_id);
+
+                        // INVARIANT 9: A transfer can never be expired before its deadline
+                        if before_deadline {
+                            assert_eq!(result, Err(Ok(Error::TransferNotExpired)),
+                                "INVARIANT VIOLATION: Transfer expired before its deadline");
+                        }
+
+                        if result.is_ok() {
+                            // INVARIANT 10: Expired transfers recover the unit to Reserved,
+                            // or to Expired if the unit itself expired in transit
+                            let updated_unit = client.get_blood_unit(&unit_id);
+                            let expected = if updated_unit.expiration_date <= env.ledger().timestamp() {
+                                BloodStatus::Expired
+                            } else {
+                                BloodStatus::Reserved
+                            };
+                            assert_eq!(updated_unit.status, expected,
+                                "INVARIANT VIOLATION: Expired transfer didn't recover unit");
+
+                            let updated_event = client.get_custody_event(&event_id).unwrap();
+                            assert_eq!(updated_event.status, CustodyStatus::Recovered,
+                                "INVARIANT VIOLATION: Expired transfer didn't mark custody event as Recovered");
+
+                            pending_event_ids.remove(idx);
+                        }
+                    }
+                }
+            }
+
             CustodyOperation::AdvanceTime { seconds } => {
                 let current_time = env.ledger().timestamp();
                 let advance = (*seconds as u64).min(86400); // Max 1 day advance
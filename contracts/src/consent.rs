@@ -0,0 +1,118 @@
+//! # consent
+//!
+//! Donor consent records keyed by the same salted donor hash as deferrals.
+//!
+//! Only the hash of the signed consent form and its version are stored; the
+//! form itself stays with the blood bank. Once a donor ID has been linked to a
+//! donor hash at registration, donor-linked queries only return data while
+//! that donor has active (recorded and not revoked) consent.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol};
+
+use crate::{bump_persistent, publish_event, DataKey, Error};
+
+/// Consent on file for one donor hash.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConsentRecord {
+    /// Hash of the signed consent document held off-chain
+    pub consent_hash: BytesN<32>,
+    /// Version of the consent form the donor signed
+    pub version: u32,
+    /// Blood bank that recorded the consent
+    pub recorded_by: Address,
+    pub recorded_at: u64,
+    /// Set once the donor withdraws consent
+    pub revoked_at: Option<u64>,
+}
+
+/// Record (or renew) a donor's consent.
+///
+/// Recording over a revoked record re-activates consent with the new form.
+pub fn record(
+    env: &Env,
+    bank_id: Address,
+    donor_hash: BytesN<32>,
+    consent_hash: BytesN<32>,
+    version: u32,
+) -> Result<(), Error> {
+    let key = DataKey::Consent(donor_hash.clone());
+    let record = ConsentRecord {
+        consent_hash,
+        version,
+        recorded_by: bank_id,
+        recorded_at: env.ledger().timestamp(),
+        revoked_at: None,
+    };
+    env.storage().persistent().set(&key, &record);
+    bump_persistent(env, &key);
+
+    publish_event(
+        env,
+        (symbol_short!("consent"), symbol_short!("recorded")),
+        (donor_hash, version),
+    );
+
+    Ok(())
+}
+
+/// Withdraw a donor's active consent.
+pub fn revoke(env: &Env, donor_hash: BytesN<32>) -> Result<(), Error> {
+    let key = DataKey::Consent(donor_hash.clone());
+    let mut record = match load_consent(env, &donor_hash) {
+        Some(r) if r.revoked_at.is_none() => r,
+        _ => return Err(Error::ConsentNotFound),
+    };
+
+    let current_time = env.ledger().timestamp();
+    record.revoked_at = Some(current_time);
+    env.storage().persistent().set(&key, &record);
+    bump_persistent(env, &key);
+
+    publish_event(
+        env,
+        (symbol_short!("consent"), symbol_short!("revoked")),
+        (donor_hash, current_time),
+    );
+
+    Ok(())
+}
+
+pub fn load_consent(env: &Env, donor_hash: &BytesN<32>) -> Option<ConsentRecord> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Consent(donor_hash.clone()))
+}
+
+/// Whether the donor hash has consent on file that has not been revoked.
+pub fn has_active_consent(env: &Env, donor_hash: &BytesN<32>) -> bool {
+    load_consent(env, donor_hash)
+        .map(|c| c.revoked_at.is_none())
+        .unwrap_or(false)
+}
+
+/// Link a donor ID to its donor hash. The first link wins so a later
+/// registration cannot re-point an existing donor ID.
+pub fn link_donor(env: &Env, donor_id: Symbol, donor_hash: BytesN<32>) {
+    let key = DataKey::DonorLink(donor_id);
+    if env.storage().persistent().has(&key) {
+        return;
+    }
+    env.storage().persistent().set(&key, &donor_hash);
+    bump_persistent(env, &key);
+}
+
+/// Whether donor-linked data for `donor_id` may be returned.
+///
+/// Donor IDs never linked to a hash (legacy and anonymous registrations) are
+/// not gated, since there is no consent record they could be checked against.
+pub fn donor_queries_allowed(env: &Env, donor_id: &Symbol) -> bool {
+    let linked: Option<BytesN<32>> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::DonorLink(donor_id.clone()));
+    match linked {
+        Some(hash) => has_active_consent(env, &hash),
+        None => true,
+    }
+}
@@ -17,6 +17,22 @@ pub const MIN_QUANTITY_ML: u32 = 50;
 /// registration of unrealistic unit sizes and helps maintain data integrity.
 pub const MAX_QUANTITY_ML: u32 = 500;
 
+/// Minimum volume of a single aliquot produced by `split_unit`, in milliliters.
+///
+/// Neonatal top-up transfusions are dosed at 10-20 ml/kg, so pediatric
+/// aliquots routinely fall well below `MIN_QUANTITY_ML`.
+pub const MIN_ALIQUOT_ML: u32 = 10;
+
+/// Maximum number of aliquots a unit can be split into in one call.
+///
+/// Pediatric split bags commonly come in sets of four to eight.
+pub const MAX_SPLIT_ALIQUOTS: u32 = 8;
+
+/// Maximum number of source units combined by one `pool_units` call.
+///
+/// Whole-blood-derived platelet pools are built from four to six donations.
+pub const MAX_POOL_SOURCES: u32 = 6;
+
 /// Minimum shelf life for blood units in days.
 ///
 /// Blood units must have at least 1 day of remaining shelf life to be
@@ -36,6 +52,54 @@ pub const MAX_SHELF_LIFE_DAYS: u64 = 42;
 /// 1 day = 24 hours × 60 minutes × 60 seconds = 86,400 seconds.
 pub const SECONDS_PER_DAY: u64 = 86_400;
 
+// ── COMPONENT STORAGE PROFILES ───────────────────────────────────────────────
+
+/// Maximum shelf life for platelet concentrates in days.
+///
+/// Platelets are stored at room temperature with agitation, which limits
+/// their viability to 5 days because of bacterial growth risk.
+pub const PLATELETS_MAX_SHELF_LIFE_DAYS: u64 = 5;
+
+/// Maximum shelf life for frozen plasma and cryoprecipitate in days.
+///
+/// Fresh frozen plasma and cryoprecipitate remain usable for one year when
+/// kept at or below the frozen storage ceiling.
+pub const FROZEN_MAX_SHELF_LIFE_DAYS: u64 = 365;
+
+/// Lower refrigerated storage bound for whole blood and red cells in °C.
+pub const REFRIGERATED_MIN_CELSIUS: i32 = 2;
+
+/// Upper refrigerated storage bound for whole blood and red cells in °C.
+pub const REFRIGERATED_MAX_CELSIUS: i32 = 6;
+
+/// Lower room-temperature storage bound for platelets in °C.
+pub const PLATELETS_MIN_CELSIUS: i32 = 20;
+
+/// Upper room-temperature storage bound for platelets in °C.
+pub const PLATELETS_MAX_CELSIUS: i32 = 24;
+
+/// Lower frozen storage bound for plasma and cryoprecipitate in °C.
+///
+/// Reflects the coldest standard blood bank freezers.
+pub const FROZEN_MIN_CELSIUS: i32 = -40;
+
+/// Upper frozen storage bound for plasma and cryoprecipitate in °C.
+///
+/// Regulatory ceiling: frozen components stored warmer than this are discarded.
+pub const FROZEN_MAX_CELSIUS: i32 = -18;
+
+/// Minimum volume of a cryoprecipitate unit in milliliters.
+///
+/// A single cryoprecipitate bag holds roughly 10-20 ml, far below the
+/// whole-blood minimum of `MIN_QUANTITY_ML`.
+pub const CRYO_MIN_VOLUME_ML: u32 = 10;
+
+/// Maximum volume of a cryoprecipitate unit in milliliters.
+///
+/// Covers pre-pooled cryoprecipitate of up to five donations; anything
+/// larger is a data entry error rather than a real product.
+pub const CRYO_MAX_VOLUME_ML: u32 = 100;
+
 // ── BLOOD REQUEST VALIDATION ──────────────────────────────────────────────────
 
 /// Minimum blood request quantity in milliliters.
@@ -65,15 +129,43 @@ pub const MAX_BATCH_SIZE: u32 = 100;
 /// storage writes required for status changes and history records.
 pub const MAX_BATCH_EXPIRY_SIZE: u32 = 50;
 
+/// Maximum number of daily expiry buckets one `expire_units_batch` call reads.
+///
+/// Bounds the keeper sweep when it has fallen behind or walks over empty
+/// days; the sweep resumes from where it stopped on the next call.
+pub const MAX_EXPIRY_SWEEP_DAYS: u32 = 30;
+
+// ── PAGINATION ───────────────────────────────────────────────────────────────
+
+/// Page size used when a paginated query is called with `limit == 0`.
+pub const DEFAULT_PAGE_SIZE: u32 = 20;
+
+/// Hard cap on the page size of any paginated query.
+///
+/// Larger limits are silently clamped so a single read cannot walk an
+/// unbounded index and exhaust the transaction's compute budget.
+pub const MAX_PAGE_SIZE: u32 = 50;
+
 // ── CUSTODY TRANSFER SETTINGS ─────────────────────────────────────────────────
 
-/// Transfer expiry window in seconds (30 minutes).
+/// Shortest configurable pending-transfer deadline in seconds (30 minutes).
 ///
-/// Once a blood unit transfer is initiated, the receiving hospital has 30 minutes
-/// to confirm delivery. After this window, the transfer can be cancelled by the
-/// blood bank. This prevents units from being stuck in transit indefinitely.
+/// Each transfer carries its own `transfer_deadline`; the receiving hospital
+/// must confirm before it, and the blood bank may cancel from it onward. This
+/// prevents units from being stuck in transit indefinitely.
 pub const TRANSFER_EXPIRY_SECONDS: u64 = 1_800;
 
+/// Default deadline for a pending transfer in seconds (4 hours).
+///
+/// A transfer still pending this long after initiation can be expired by
+/// anyone with `expire_pending_transfer`, returning the unit to `Reserved`.
+/// The admin may change it within
+/// `[TRANSFER_EXPIRY_SECONDS, MAX_TRANSFER_DEADLINE_SECONDS]`.
+pub const DEFAULT_TRANSFER_DEADLINE_SECONDS: u64 = 4 * 3_600;
+
+/// Longest configurable pending-transfer deadline in seconds (24 hours).
+pub const MAX_TRANSFER_DEADLINE_SECONDS: u64 = 86_400;
+
 /// Maximum number of custody events stored per page.
 ///
 /// Custody trails are paginated to prevent unbounded storage growth. Each page
@@ -81,6 +173,22 @@ pub const TRANSFER_EXPIRY_SECONDS: u64 = 1_800;
 /// complete audit trails for blood unit chain-of-custody.
 pub const MAX_EVENTS_PER_PAGE: u32 = 20;
 
+// ── RESERVATION SETTINGS ──────────────────────────────────────────────────────
+
+/// Pickup window for a reserved unit in seconds (24 hours).
+///
+/// A unit allocated to a hospital is expected to leave the bank within this
+/// window. Reservations older than this with no pending transfer are stale and
+/// may be swept back to `Available` so the blood is not locked up indefinitely.
+pub const RESERVATION_PICKUP_WINDOW_SECONDS: u64 = 86_400;
+
+/// Longest hold a single allocation may request, in seconds (7 days).
+///
+/// Bounds both the per-call `hold_seconds` on `allocate_blood` and the
+/// admin-configured default so a reservation cannot pin blood for most of its
+/// shelf life.
+pub const MAX_RESERVATION_HOLD_SECONDS: u64 = 7 * 86_400;
+
 // ── UNIT ID VALIDATION ────────────────────────────────────────────────────────
 
 /// Maximum length of a blood unit ID string.
@@ -113,3 +221,31 @@ pub const MAX_DELIVERY_ADDRESS_LENGTH: u32 = 200;
 /// After expiry the nomination is void and the current SuperAdmin may
 /// nominate a different address.
 pub const NOMINATION_EXPIRY_SECONDS: u64 = 86_400;
+
+// ── PAUSE FLAGS ───────────────────────────────────────────────────────────────
+
+/// Pause bit for unit creation: `register_blood`, `batch_register_blood`,
+/// `split_unit` and `pool_units`.
+pub const PAUSE_REGISTER: u32 = 1 << 0;
+
+/// Pause bit for every allocation entry point (`allocate_blood` and its
+/// batch and FEFO variants).
+pub const PAUSE_ALLOCATE: u32 = 1 << 1;
+
+/// Pause bit for custody moves: `initiate_transfer`, `confirm_transfer`,
+/// `handoff_with_dual_auth` and `transfer_unit_ownership`.
+pub const PAUSE_CUSTODY: u32 = 1 << 2;
+
+/// Every defined pause bit.
+pub const PAUSE_ALL: u32 = PAUSE_REGISTER | PAUSE_ALLOCATE | PAUSE_CUSTODY;
+
+// ── STORAGE LAYOUT VERSIONS ───────────────────────────────────────────────────
+
+/// Layout of deployments that predate per-unit storage: every unit lives in
+/// the single legacy `UNITS` map. Contracts without a stored version are
+/// treated as this version.
+pub const LEGACY_STORAGE_VERSION: u32 = 1;
+
+/// Current layout: one `DataKey::Unit(id)` entry per unit plus its indexes.
+/// Fresh deployments start here; upgraded ones reach it through `migrate`.
+pub const CURRENT_STORAGE_VERSION: u32 = 2;
@@ -0,0 +1,137 @@
+//! # custody_proof
+//!
+//! Merkle commitments over a unit's confirmed custody chain.
+//!
+//! Leaves are `sha256(xdr(CustodyEvent))` for every event in the unit's
+//! custody trail, in trail order. Interior nodes hash the two children in
+//! ascending byte order (`sha256(min || max)`), so a proof is just the list of
+//! sibling hashes with no left/right flags. A node without a sibling is carried
+//! up to the next level unchanged.
+
+use soroban_sdk::{contracttype, symbol_short, xdr::ToXdr, Bytes, BytesN, Env, Map, String, Vec};
+
+use crate::{
+    bump_persistent, publish_event, CustodyEvent, DataKey, Error, TrailMetadata, CUSTODY_EVENTS,
+};
+
+/// Committed Merkle root over a unit's custody chain.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CustodyRoot {
+    pub root: BytesN<32>,
+    /// Number of confirmed custody events covered by `root`
+    pub leaf_count: u32,
+    pub committed_at: u64,
+}
+
+/// Leaf hash for one custody event.
+pub fn leaf_hash(env: &Env, event: &CustodyEvent) -> BytesN<32> {
+    env.crypto().sha256(&event.clone().to_xdr(env)).into()
+}
+
+fn hash_pair(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    let mut input = Bytes::from_array(env, &first.to_array());
+    input.extend_from_array(&second.to_array());
+    env.crypto().sha256(&input).into()
+}
+
+/// Compute the Merkle root of `leaves`. Callers guarantee `leaves` is non-empty.
+pub fn merkle_root(env: &Env, leaves: &Vec<BytesN<32>>) -> BytesN<32> {
+    let mut level = leaves.clone();
+    while level.len() > 1 {
+        let mut next = Vec::new(env);
+        let mut i = 0;
+        while i < level.len() {
+            let left = level.get(i).unwrap();
+            match level.get(i + 1) {
+                Some(right) => next.push_back(hash_pair(env, &left, &right)),
+                None => next.push_back(left),
+            }
+            i += 2;
+        }
+        level = next;
+    }
+    level.get(0).unwrap()
+}
+
+/// Leaf hashes for every confirmed custody event on the unit's trail.
+fn custody_leaves(env: &Env, unit_id: u64) -> Result<Vec<BytesN<32>>, Error> {
+    let metadata: Option<TrailMetadata> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::UnitTrailMeta(unit_id));
+    let total_pages = metadata.map(|m| m.total_pages).unwrap_or(0);
+
+    let custody_events: Map<String, CustodyEvent> = env
+        .storage()
+        .persistent()
+        .get(&CUSTODY_EVENTS)
+        .unwrap_or(Map::new(env));
+
+    let mut leaves = Vec::new(env);
+    for page_number in 0..total_pages {
+        let page: Vec<String> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UnitTrailPage(unit_id, page_number))
+            .unwrap_or(Vec::new(env));
+        for event_id in page.iter() {
+            // An archived event can no longer be hashed; commit before archiving.
+            let event = custody_events.get(event_id).ok_or(Error::StorageError)?;
+            leaves.push_back(leaf_hash(env, &event));
+        }
+    }
+    Ok(leaves)
+}
+
+/// Compute and store the Merkle root over the unit's confirmed custody events.
+///
+/// Recommitting after further transfers replaces the stored root.
+pub fn commit(env: &Env, unit_id: u64) -> Result<BytesN<32>, Error> {
+    let leaves = custody_leaves(env, unit_id)?;
+    if leaves.is_empty() {
+        return Err(Error::InvalidStatus);
+    }
+
+    let root = merkle_root(env, &leaves);
+    let key = DataKey::CustodyRoot(unit_id);
+    env.storage().persistent().set(
+        &key,
+        &CustodyRoot {
+            root: root.clone(),
+            leaf_count: leaves.len(),
+            committed_at: env.ledger().timestamp(),
+        },
+    );
+    bump_persistent(env, &key);
+
+    publish_event(
+        env,
+        (symbol_short!("custody"), symbol_short!("root")),
+        (unit_id, root.clone(), leaves.len()),
+    );
+
+    Ok(root)
+}
+
+pub fn load_root(env: &Env, unit_id: u64) -> Option<CustodyRoot> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CustodyRoot(unit_id))
+}
+
+/// Check that `leaf` folds up through `proof` to the unit's committed root.
+pub fn verify(
+    env: &Env,
+    unit_id: u64,
+    leaf: BytesN<32>,
+    proof: Vec<BytesN<32>>,
+) -> Result<bool, Error> {
+    let committed = load_root(env, unit_id).ok_or(Error::CustodyRootNotFound)?;
+    let mut node = leaf;
+    for sibling in proof.iter() {
+        node = hash_pair(env, &node, &sibling);
+    }
+    Ok(node == committed.root)
+}
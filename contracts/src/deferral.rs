@@ -0,0 +1,88 @@
+//! # deferral
+//!
+//! Donor deferral registry keyed by a salted donor hash.
+//!
+//! Deferred donors (recent malaria exposure, low hemoglobin, ...) must not
+//! donate again until their deferral lapses. Only the 32-byte hash computed
+//! off-chain is stored, so no donor PII is written to the ledger. The salt
+//! never leaves the blood bank's systems.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env};
+
+use crate::{bump_persistent, publish_event, DataKey, Error};
+
+/// Active deferral for one donor hash.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DonorDeferral {
+    /// Ledger timestamp after which the donor may donate again
+    pub until: u64,
+    /// Bank-defined deferral reason code (no free text, to keep PII off-chain)
+    pub reason_code: u32,
+    /// Blood bank that recorded the deferral
+    pub deferred_by: Address,
+    pub deferred_at: u64,
+}
+
+/// Record or extend a deferral.
+///
+/// `until` must lie in the future. An existing deferral is never shortened:
+/// if the stored window already ends later, it is kept as is and the new
+/// reason code is ignored.
+pub fn defer(
+    env: &Env,
+    bank_id: Address,
+    donor_hash: BytesN<32>,
+    until: u64,
+    reason_code: u32,
+) -> Result<(), Error> {
+    let current_time = env.ledger().timestamp();
+    if until <= current_time {
+        return Err(Error::InvalidTimestamp);
+    }
+
+    let key = DataKey::Deferral(donor_hash.clone());
+    if let Some(existing) = load_deferral(env, &donor_hash) {
+        if existing.until >= until {
+            return Ok(());
+        }
+    }
+
+    let deferral = DonorDeferral {
+        until,
+        reason_code,
+        deferred_by: bank_id,
+        deferred_at: current_time,
+    };
+    env.storage().persistent().set(&key, &deferral);
+    bump_persistent(env, &key);
+
+    publish_event(
+        env,
+        (symbol_short!("donor"), symbol_short!("deferred")),
+        (donor_hash, until, reason_code),
+    );
+
+    Ok(())
+}
+
+pub fn load_deferral(env: &Env, donor_hash: &BytesN<32>) -> Option<DonorDeferral> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Deferral(donor_hash.clone()))
+}
+
+/// Whether the donor is still inside a recorded deferral window.
+pub fn is_deferred(env: &Env, donor_hash: &BytesN<32>) -> bool {
+    load_deferral(env, donor_hash)
+        .map(|d| env.ledger().timestamp() < d.until)
+        .unwrap_or(false)
+}
+
+/// Reject a collection from a donor that is currently deferred.
+pub fn ensure_not_deferred(env: &Env, donor_hash: &BytesN<32>) -> Result<(), Error> {
+    if is_deferred(env, donor_hash) {
+        return Err(Error::DonorDeferred);
+    }
+    Ok(())
+}
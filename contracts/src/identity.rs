@@ -0,0 +1,46 @@
+//! # identity
+//!
+//! Role checks against the lifebank identity contract.
+//!
+//! The registry keeps its own bank and hospital tables, but riders are only
+//! known to the identity contract. Its address is configured by the admin via
+//! `set_identity_contract`; until then no address can pass a role check.
+
+use soroban_sdk::{contractclient, contracttype, Address, Env};
+
+use crate::{Error, IDENTITY_CONTRACT};
+
+/// Mirror of the identity contract's `Role`. Variant order must match so that
+/// values encode identically across the call.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Role {
+    Admin,
+    BloodBank,
+    Hospital,
+    Donor,
+    Rider,
+    Custom(u32),
+}
+
+#[contractclient(name = "IdentityClient")]
+#[allow(dead_code)]
+pub trait IdentityInterface {
+    fn has_role(env: Env, address: Address, role: Role) -> bool;
+}
+
+/// Fail with `InvalidCustodian` unless `address` currently holds `role`.
+///
+/// An unconfigured identity contract, or one that cannot be invoked, fails the
+/// check as well. Only checks membership; callers still `require_auth`.
+pub fn require_role(env: &Env, address: &Address, role: Role) -> Result<(), Error> {
+    let identity: Address = env
+        .storage()
+        .instance()
+        .get(&IDENTITY_CONTRACT)
+        .ok_or(Error::InvalidCustodian)?;
+    match IdentityClient::new(env, &identity).try_has_role(address, &role) {
+        Ok(Ok(true)) => Ok(()),
+        _ => Err(Error::InvalidCustodian),
+    }
+}
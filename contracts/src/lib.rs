@@ -1,16 +1,21 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, vec, Address, Bytes, Env,
-    Map, String, Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, events::Topics, symbol_short, vec,
+    Address, Bytes, BytesN, Env, IntoVal, Map, String, Symbol, Val, Vec,
 };
 
+pub mod consent;
 pub mod constants;
+pub mod custody_proof;
+pub mod deferral;
+pub mod identity;
 pub mod payments;
 use crate::payments::*;
 
 pub mod registry_read;
 pub mod registry_write;
 pub mod storage_lifecycle;
+pub mod temperature;
 #[cfg(test)]
 mod test_payments;
 #[cfg(test)]
@@ -23,23 +28,38 @@ mod test_storage_layout;
 /// Events identify their payload schema by appending `symbol_short!("v1")` as
 /// the final topic. Backend/indexer consumers must treat events without this
 /// marker as legacy and must not silently decode future version markers.
+/// When an event namespace is configured (see `set_event_namespace`) it is
+/// prepended as the first topic; the version marker stays last.
 pub const EVENT_SCHEMA_VERSION: u32 = 1;
 
 /// Error types for blood registration and transfer
+///
+/// Every contract entry point reports failure through this enum; codes are
+/// stable and must never be renumbered, so callers (the fuzz harness,
+/// cross-contract callers, the backend) can match on them.
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
 pub enum Error {
+    /// Caller is not the admin, a registered blood bank, or the owner of the record.
     Unauthorized = 1,
+    /// Volume or count is outside the accepted range.
     InvalidQuantity = 2,
+    /// Expiration is outside the component's shelf-life window.
     InvalidExpiration = 3,
+    /// The address, lot or record is already registered.
     DuplicateRegistration = 4,
     StorageError = 5,
+    /// The unit or record is not in a status that allows this operation.
     InvalidStatus = 6,
     UnitNotFound = 7,
+    /// The unit is past its expiration date.
     UnitExpired = 8,
+    /// Caller is not a registered hospital.
     UnauthorizedHospital = 9,
+    /// The requested state transition is not allowed.
     InvalidTransition = 10,
+    /// The unit is already reserved or listed twice in a batch.
     AlreadyAllocated = 11,
     BatchSizeExceeded = 12,
     DuplicateRequest = 13,
@@ -77,6 +97,46 @@ pub enum Error {
     DeliveryAddressTooLong = 34,
     /// Requested page number exceeds the total number of available pages.
     PageNotFound = 35,
+    /// Expiration timestamp is not in the future and backdated registration is disabled.
+    AlreadyExpired = 36,
+    /// No recovery address was registered at initialization.
+    RecoveryNotConfigured = 37,
+    /// Allocation target is not an active registered hospital.
+    HospitalNotRegistered = 38,
+    /// Screening is required and the unit has not passed every required lab test.
+    ScreeningIncomplete = 39,
+    /// Fewer eligible Available units exist than were requested.
+    InsufficientInventory = 40,
+    /// Reservation hold is zero or longer than `MAX_RESERVATION_HOLD_SECONDS`.
+    InvalidHoldDuration = 41,
+    /// No compatible, in-window crossmatch is on record for the receiving hospital.
+    CrossmatchRequired = 42,
+    /// A caller-supplied timestamp is out of range (e.g. in the future).
+    InvalidTimestamp = 43,
+    /// The donor hash is inside an active deferral window.
+    DonorDeferred = 44,
+    /// No collection lot has been created under this ID.
+    LotNotFound = 45,
+    /// The collection lot is closed and accepts no further units.
+    LotClosed = 46,
+    /// The unit's ABO/Rh type cannot be transfused to the recipient's type.
+    IncompatibleBloodType = 47,
+    /// `initialize` was called on a contract that already has an admin.
+    AlreadyInitialized = 48,
+    /// `migrate` was called with a version other than the stored layout version.
+    VersionMismatch = 49,
+    /// The entry point's operation class is paused by the admin.
+    ContractPaused = 50,
+    /// Pause flags are empty or contain bits outside `PAUSE_ALL`.
+    InvalidPauseFlags = 51,
+    /// No active consent is on file for the donor hash.
+    ConsentNotFound = 52,
+    /// Transfer deadline is shorter than the confirmation window or over the maximum.
+    InvalidTransferDeadline = 53,
+    /// No custody Merkle root has been committed for the unit.
+    CustodyRootNotFound = 54,
+    /// Hop recipient is neither the recipient hospital nor a rider in the identity contract.
+    InvalidCustodian = 55,
 }
 
 // Alias for issue/docs terminology.
@@ -93,6 +153,67 @@ pub enum BloodComponent {
     Cryoprecipitate,
 }
 
+impl BloodComponent {
+    /// Whether this component passes an optional component filter
+    /// (`None` matches every component).
+    pub fn matches(&self, filter: Option<BloodComponent>) -> bool {
+        match filter {
+            Some(wanted) => wanted == *self,
+            None => true,
+        }
+    }
+
+    /// Default storage profile (shelf life and temperature range) for this component.
+    pub fn profile(&self) -> ComponentProfile {
+        match self {
+            BloodComponent::WholeBlood | BloodComponent::RedBloodCells => ComponentProfile {
+                max_shelf_life_days: MAX_SHELF_LIFE_DAYS,
+                min_temp_celsius: REFRIGERATED_MIN_CELSIUS,
+                max_temp_celsius: REFRIGERATED_MAX_CELSIUS,
+                min_volume_ml: MIN_QUANTITY_ML,
+                max_volume_ml: MAX_QUANTITY_ML,
+            },
+            BloodComponent::Platelets => ComponentProfile {
+                max_shelf_life_days: PLATELETS_MAX_SHELF_LIFE_DAYS,
+                min_temp_celsius: PLATELETS_MIN_CELSIUS,
+                max_temp_celsius: PLATELETS_MAX_CELSIUS,
+                min_volume_ml: MIN_QUANTITY_ML,
+                max_volume_ml: MAX_QUANTITY_ML,
+            },
+            BloodComponent::Plasma => ComponentProfile {
+                max_shelf_life_days: FROZEN_MAX_SHELF_LIFE_DAYS,
+                min_temp_celsius: FROZEN_MIN_CELSIUS,
+                max_temp_celsius: FROZEN_MAX_CELSIUS,
+                min_volume_ml: MIN_QUANTITY_ML,
+                max_volume_ml: MAX_QUANTITY_ML,
+            },
+            BloodComponent::Cryoprecipitate => ComponentProfile {
+                max_shelf_life_days: FROZEN_MAX_SHELF_LIFE_DAYS,
+                min_temp_celsius: FROZEN_MIN_CELSIUS,
+                max_temp_celsius: FROZEN_MAX_CELSIUS,
+                min_volume_ml: CRYO_MIN_VOLUME_ML,
+                max_volume_ml: CRYO_MAX_VOLUME_ML,
+            },
+        }
+    }
+}
+
+/// Storage defaults for a blood component.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComponentProfile {
+    /// Longest shelf life accepted at registration, in days
+    pub max_shelf_life_days: u64,
+    /// Lowest acceptable storage temperature, in °C
+    pub min_temp_celsius: i32,
+    /// Highest acceptable storage temperature, in °C
+    pub max_temp_celsius: i32,
+    /// Smallest volume accepted at registration, in ml
+    pub min_volume_ml: u32,
+    /// Largest volume accepted at registration, in ml
+    pub max_volume_ml: u32,
+}
+
 /// Blood type enumeration
 #[contracttype]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -107,6 +228,31 @@ pub enum BloodType {
     ONegative,
 }
 
+/// Red cell ABO/Rh compatibility: whether `donor` blood can be transfused to
+/// a `recipient` of the given type.
+///
+/// The donor may not carry an A, B or RhD antigen the recipient lacks, so
+/// O-negative can go to anyone and AB-positive can receive from anyone.
+pub fn is_compatible(donor: BloodType, recipient: BloodType) -> bool {
+    // (A antigen, B antigen, RhD antigen)
+    fn antigens(blood_type: BloodType) -> (bool, bool, bool) {
+        match blood_type {
+            BloodType::APositive => (true, false, true),
+            BloodType::ANegative => (true, false, false),
+            BloodType::BPositive => (false, true, true),
+            BloodType::BNegative => (false, true, false),
+            BloodType::ABPositive => (true, true, true),
+            BloodType::ABNegative => (true, true, false),
+            BloodType::OPositive => (false, false, true),
+            BloodType::ONegative => (false, false, false),
+        }
+    }
+
+    let (donor_a, donor_b, donor_rh) = antigens(donor);
+    let (recipient_a, recipient_b, recipient_rh) = antigens(recipient);
+    (!donor_a || recipient_a) && (!donor_b || recipient_b) && (!donor_rh || recipient_rh)
+}
+
 /// Blood status enumeration
 #[contracttype]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -118,6 +264,13 @@ pub enum BloodStatus {
     Quarantined,
     Expired,
     Discarded,
+    Recalled,
+    /// Divided into aliquots by `split_unit`; the children carry the inventory
+    Split,
+    /// Combined into a pooled product by `pool_units`
+    Pooled,
+    /// Refused on arrival by `reject_transfer`; on its way back to the sender
+    ReturnPending,
 }
 
 /// Quarantine reason categories for explicit on-chain lifecycle records.
@@ -141,6 +294,91 @@ pub enum QuarantineDisposition {
     Discard,
 }
 
+/// Latest crossmatch a hospital recorded for a unit allocated to it.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CrossmatchRecord {
+    pub hospital: Address,
+    /// Hash of the patient identifier; the identifier itself stays off-chain
+    pub patient_hash: BytesN<32>,
+    pub compatible: bool,
+    /// When the sample was tested, as reported by the hospital
+    pub tested_at: u64,
+    pub recorded_at: u64,
+}
+
+/// Why a unit was taken out of inventory through `discard_unit`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DiscardReason {
+    Expired,
+    TemperatureBreach,
+    Damaged,
+    Recalled,
+    QcFailure,
+}
+
+/// Why a donor's or a collection lot's units were recalled.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RecallReason {
+    DonorTestPositive,
+    DonorDeferral,
+    LotContamination,
+    ManufacturerNotice,
+    Other,
+}
+
+/// Serology screening tests a lab can record against a blood unit.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TestType {
+    Hiv,
+    HepatitisB,
+    HepatitisC,
+    Syphilis,
+}
+
+/// Processing steps a blood bank can apply to a unit after collection.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ProcessingType {
+    Irradiation,
+    Leukoreduction,
+    Washing,
+}
+
+/// One entry in a unit's append-only processing log.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProcessingStep {
+    pub step: ProcessingType,
+    pub performed_by: Address,
+    /// When the step was performed, as reported by the bank
+    pub performed_at: u64,
+    pub recorded_at: u64,
+}
+
+/// Tests a unit must pass before allocation while screening is required.
+pub const REQUIRED_TESTS: [TestType; 4] = [
+    TestType::Hiv,
+    TestType::HepatitisB,
+    TestType::HepatitisC,
+    TestType::Syphilis,
+];
+
+/// Latest lab result recorded for one test on a unit.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestResult {
+    pub test_type: TestType,
+    /// Hash of the full lab report, kept off-chain
+    pub result_hash: BytesN<32>,
+    pub passed: bool,
+    pub lab: Address,
+    pub recorded_at: u64,
+}
+
 /// Withdrawal reason enumeration
 #[contracttype]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -189,6 +427,19 @@ pub struct BloodUnit {
     pub allocation_timestamp: Option<u64>,
     pub transfer_timestamp: Option<u64>,
     pub delivery_timestamp: Option<u64>,
+    /// Collection lot the unit was tagged with via `assign_lot`
+    pub lot_id: Option<Symbol>,
+}
+
+/// Collection lot (one donation drive) created by a blood bank.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lot {
+    pub id: Symbol,
+    pub bank_id: Address,
+    pub created_at: u64,
+    /// Set by `close_lot`; a closed lot accepts no further units
+    pub closed_at: Option<u64>,
 }
 
 /// Transfer record
@@ -229,6 +480,21 @@ pub struct QuarantineLifecycleEvent {
     pub timestamp: u64,
 }
 
+/// Discard event carrying enough context for analytics to bucket wastage by
+/// bank, blood type and reason without loading the unit.
+#[contracttype]
+#[derive(Clone)]
+pub struct DiscardEvent {
+    pub blood_unit_id: u64,
+    pub bank_id: Address,
+    pub blood_type: BloodType,
+    pub quantity: u32,
+    pub old_status: BloodStatus,
+    pub reason: DiscardReason,
+    pub actor: Address,
+    pub timestamp: u64,
+}
+
 /// Custody event for chain-of-custody tracking
 #[contracttype]
 #[derive(Clone)]
@@ -237,9 +503,23 @@ pub struct CustodyEvent {
     pub unit_id: u64,
     pub from_custodian: Address,
     pub to_custodian: Address,
+    /// Capacity the sender held the unit in: the bank, or a rider on a later hop
+    pub from_kind: CustodianKind,
+    /// Capacity the receiver takes the unit in: a rider, or the recipient hospital
+    pub to_kind: CustodianKind,
     pub initiated_at: u64,
+    /// Once passed, a still-pending transfer may be expired by anyone
+    pub transfer_deadline: u64,
     pub ledger_sequence: u32,
     pub status: CustodyStatus,
+    /// Hash of a handoff document (photo, signed waybill) kept off-chain
+    pub doc_hash: Option<BytesN<32>>,
+    /// Short free-form handoff reference
+    pub note: Option<Symbol>,
+    /// Salted geohash of where the sender handed the unit off
+    pub location_hash: Option<BytesN<32>>,
+    /// Salted geohash of where the transfer was confirmed or cancelled
+    pub closing_location_hash: Option<BytesN<32>>,
 }
 
 /// Custody status enumeration
@@ -256,6 +536,59 @@ pub enum CustodyStatus {
     Cancelled,
     /// Transfer failed due to unit expiry during transit (recovery action)
     Recovered,
+    /// Unit refused by the receiving hospital on arrival
+    Rejected,
+}
+
+/// Capacity in which an address currently holds a blood unit.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CustodianKind {
+    /// Unit is stored at its registering blood bank
+    Bank,
+    /// Unit is in transit with the assigned rider
+    Rider,
+    /// Unit has been delivered to the recipient hospital
+    Hospital,
+}
+
+/// How a confirmed delivery ended.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeliveryOutcome {
+    /// Cold chain intact; the unit is Delivered and available for transfusion
+    Delivered,
+    /// The temperature contract reported a breach; the unit is Quarantined instead
+    DeliveredWithExcursion,
+}
+
+/// Why a hospital refused a unit on arrival.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RejectionReason {
+    /// Leaking or otherwise damaged bag
+    Damaged,
+    /// Label does not match the allocated unit
+    LabelMismatch,
+    /// Visible signs of temperature abuse
+    TemperatureExcursion,
+    /// Unit is not the product that was requested
+    WrongProduct,
+    Other,
+}
+
+/// Emitted by `reject_transfer`. `payment_id` lets the payments flow refund the payer.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransferRejectedEvent {
+    pub custody_event_id: String,
+    pub unit_id: u64,
+    pub hospital: Address,
+    /// Custodian the unit is returned to
+    pub returned_to: Address,
+    pub reason: RejectionReason,
+    pub payment_id: Option<u64>,
+    pub rejected_at: u64,
 }
 
 /// Transfer recovery event for explicit tracking of failed/recovered transfers.
@@ -271,7 +604,8 @@ pub struct TransferRecoveryEvent {
     pub unit_id: u64,
     /// Actor initiating or detecting the recovery
     pub actor: Address,
-    /// Reason for recovery: 0 = unit_expired_during_transit, 1 = transfer_cancelled, 2 = other
+    /// Reason for recovery: 0 = unit_expired_during_transit, 1 = transfer_cancelled, 2 = other,
+    /// 3 = transfer_deadline_passed
     pub recovery_reason: u32,
     /// Previous custody status before recovery
     pub previous_custody_status: CustodyStatus,
@@ -281,6 +615,8 @@ pub struct TransferRecoveryEvent {
     pub unit_status_after_recovery: BloodStatus,
     /// Timestamp when recovery occurred
     pub recovery_timestamp: u64,
+    /// Salted geohash of where the recovering actor reported the unit, if given
+    pub location_hash: Option<BytesN<32>>,
 }
 
 /// Request status enumeration
@@ -479,6 +815,18 @@ pub struct DisputeAutoRefundedEvent {
     pub refunded_at: u64,
 }
 
+/// Event emitted when a payment is refunded because its custody transfer timed out.
+#[contracttype]
+#[derive(Clone)]
+pub struct PaymentTimeoutRefundedEvent {
+    pub payment_id: u64,
+    pub unit_id: u64,
+    pub custody_event_id: String,
+    pub refunded_to: Address,
+    pub amount: i128,
+    pub refunded_at: u64,
+}
+
 /// Storage key literals (compile-time guarded for `symbol_short!` compatibility).
 const _: () = assert!("UNITS".len() <= 9);
 const _: () = assert!("NEXT_ID".len() <= 9);
@@ -501,6 +849,12 @@ const _: () = assert!("MSIG_CFG".len() <= 9);
 const _: () = assert!("PEND_APR".len() <= 9);
 const _: () = assert!("ESC_ACCS".len() <= 9);
 const _: () = assert!("INV_CTRL".len() <= 9);
+const _: () = assert!("ALLOW_BKD".len() <= 9);
+const _: () = assert!("CUST_CNT".len() <= 9);
+const _: () = assert!("EVT_NS".len() <= 9);
+const _: () = assert!("EXP_CUR".len() <= 9);
+const _: () = assert!("LABS".len() <= 9);
+const _: () = assert!("SCRN_REQ".len() <= 9);
 
 /// Storage keys (single source of truth)
 pub(crate) const BLOOD_UNITS: Symbol = symbol_short!("UNITS");
@@ -524,6 +878,36 @@ pub(crate) const MULTISIG_CONFIG: Symbol = symbol_short!("MSIG_CFG");
 pub(crate) const PENDING_APPROVALS: Symbol = symbol_short!("PEND_APR");
 pub(crate) const ESCROW_ACCOUNTS: Symbol = symbol_short!("ESC_ACCS");
 pub(crate) const INVENTORY_CONTRACT: Symbol = symbol_short!("INV_CTRL");
+pub(crate) const ALLOW_BACKDATED: Symbol = symbol_short!("ALLOW_BKD");
+pub(crate) const CUSTODY_COUNTS: Symbol = symbol_short!("CUST_CNT");
+pub(crate) const EVENT_NAMESPACE: Symbol = symbol_short!("EVT_NS");
+pub(crate) const EXPIRY_CURSOR: Symbol = symbol_short!("EXP_CUR");
+pub(crate) const LABS: Symbol = symbol_short!("LABS");
+pub(crate) const SCREENING_REQUIRED: Symbol = symbol_short!("SCRN_REQ");
+
+const _: () = assert!("HOLD_DUR".len() <= 9);
+pub(crate) const HOLD_DURATION: Symbol = symbol_short!("HOLD_DUR");
+
+const _: () = assert!("XM_WINDOW".len() <= 9);
+pub(crate) const CROSSMATCH_WINDOW: Symbol = symbol_short!("XM_WINDOW");
+
+const _: () = assert!("STOR_VER".len() <= 9);
+pub(crate) const STORAGE_VERSION: Symbol = symbol_short!("STOR_VER");
+
+const _: () = assert!("PAUSED".len() <= 9);
+pub(crate) const PAUSED: Symbol = symbol_short!("PAUSED");
+
+const _: () = assert!("XFER_DL".len() <= 9);
+pub(crate) const TRANSFER_DEADLINE: Symbol = symbol_short!("XFER_DL");
+
+const _: () = assert!("IDENTITY".len() <= 9);
+pub(crate) const IDENTITY_CONTRACT: Symbol = symbol_short!("IDENTITY");
+
+const _: () = assert!("TEMP_CTRL".len() <= 9);
+pub(crate) const TEMPERATURE_CONTRACT: Symbol = symbol_short!("TEMP_CTRL");
+
+const _: () = assert!("DUAL_AUTH".len() <= 9);
+pub(crate) const DUAL_AUTH_REQUIRED: Symbol = symbol_short!("DUAL_AUTH");
 
 /// Storage key enumeration for composite keys
 #[contracttype]
@@ -547,6 +931,52 @@ pub enum DataKey {
     UnitTrailMeta(u64),
     /// Pending SuperAdmin nomination
     PendingNominee,
+    /// Recovery address registered at initialization for emergency admin rotation
+    RecoveryAddress,
+    /// Payment held against a unit's delivery: unit_id -> payment_id
+    UnitPayment(u64),
+    /// Rider carrying an in-transit unit: unit_id -> Address (cleared when the transfer ends)
+    UnitRider(u64),
+    /// Reservation timing for a unit: unit_id -> ReservationWindow
+    ReservationWindow(u64),
+    /// Running average reservation duration for a bank: bank_id -> ReservationAverage
+    AvgReservationTime(Address),
+    /// Blood unit record: unit_id -> BloodUnit (replaces the legacy `UNITS` map)
+    Unit(u64),
+    /// Available units index: (bank_id, blood_type) -> Vec<u64> of units in Available status, sorted by expiry
+    AvailableByType(Address, BloodType),
+    /// Lab results for a unit: unit_id -> Vec<TestResult> (latest result per test type)
+    UnitTests(u64),
+    /// Crossmatch for a unit: unit_id -> CrossmatchRecord (latest only)
+    Crossmatch(u64),
+    /// Processing log for a unit: unit_id -> Vec<ProcessingStep> (append-only)
+    UnitProcessing(u64),
+    /// Donor deferral: salted donor hash -> DonorDeferral
+    Deferral(BytesN<32>),
+    /// Committed custody Merkle root: unit_id -> CustodyRoot (latest commit only)
+    CustodyRoot(u64),
+    /// Donor consent: salted donor hash -> ConsentRecord
+    Consent(BytesN<32>),
+    /// Donor ID -> salted donor hash, written the first time both are registered together
+    DonorLink(Symbol),
+    /// Collection lot record: lot_id -> Lot
+    Lot(Symbol),
+    /// Collection lot index: lot_id -> Vec<u64> of units tagged with that lot
+    LotUnits(Symbol),
+    /// Lots created by a bank: bank_id -> Vec<Symbol>
+    BankLots(Address),
+    /// Aliquot lineage: child unit_id -> parent unit_id it was split from
+    UnitParent(u64),
+    /// Aliquot lineage: parent unit_id -> Vec<u64> of child unit IDs
+    UnitChildren(u64),
+    /// Pooled product lineage: pooled unit_id -> Vec<u64> of source unit IDs
+    PoolSources(u64),
+    /// Pooled product lineage: source unit_id -> pooled unit_id it went into
+    PooledInto(u64),
+    /// Expiry index: day number (expiration_date / SECONDS_PER_DAY) -> Vec<u64> of units expiring that day
+    ExpiryBucket(u64),
+    /// Inventory counters per bank and blood type: (bank_id, blood_type) -> TypeSummary
+    TypeSummary(Address, BloodType),
 }
 
 /// Metadata for paginated custody trail
@@ -557,19 +987,119 @@ pub struct TrailMetadata {
     pub total_pages: u32,
 }
 
+/// One page of blood units from a paginated listing.
+///
+/// `#[contracttype]` structs cannot be generic, so each listing has its own
+/// page type with the same `items` / `next_cursor` shape.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnitPage {
+    pub items: Vec<BloodUnit>,
+    /// Cursor to pass for the following page; `None` once the listing is exhausted
+    pub next_cursor: Option<u64>,
+}
+
+/// One page of custody event IDs from a unit's trail.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrailPage {
+    pub items: Vec<String>,
+    /// Cursor to pass for the following page; `None` once the trail is exhausted
+    pub next_cursor: Option<u64>,
+}
+
+/// One page of full custody events for a unit, in creation order.
+#[contracttype]
+#[derive(Clone)]
+pub struct CustodyEventPage {
+    pub items: Vec<CustodyEvent>,
+    /// Number of custody events recorded for the unit across all pages
+    pub total: u32,
+}
+
+/// One page of registered blood bank addresses.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BankPage {
+    pub items: Vec<Address>,
+    /// Cursor to pass for the following page; `None` once the registry is exhausted
+    pub next_cursor: Option<u64>,
+}
+
+/// When a unit was reserved and, once the reservation was confirmed or
+/// released, when it resolved.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReservationWindow {
+    pub reserved_at: u64,
+    /// Deadline after which `release_expired_reservations` may free the unit
+    pub reserved_until: u64,
+    pub resolved_at: Option<u64>,
+}
+
+/// Running average of resolved reservation durations for a bank.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReservationAverage {
+    /// Number of resolved reservations folded into the average
+    pub samples: u32,
+    /// Mean time between reservation and resolution, in seconds
+    pub average_seconds: u64,
+}
+
+/// Incrementally maintained inventory counters for one bank and blood type.
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TypeSummary {
+    pub available: u32,
+    pub reserved: u32,
+    pub delivered: u32,
+    pub expired: u32,
+    /// Combined volume (ml) of the units counted in `available`
+    pub total_volume_ml: u64,
+}
+
+/// First unit that stopped an `allocate_blood_batch` call.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BatchAllocationFailure {
+    /// Position of the unit in the submitted list
+    pub index: u32,
+    pub unit_id: u64,
+    /// [`Error`] code the unit would have failed single allocation with
+    pub error_code: u32,
+}
+
+/// Result of an all-or-nothing batch allocation.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BatchAllocationOutcome {
+    /// Every unit was reserved; IDs in submission order
+    Allocated(Vec<u64>),
+    /// Nothing was reserved because of this unit
+    Rejected(BatchAllocationFailure),
+}
+
 // Re-export storage lifecycle types for external consumers
 pub use storage_lifecycle::{
-    archive_custody_events, archive_unit_history, bump_all_registries, bump_rent_for_unit,
-    get_archived_custody_summary, get_archived_history_summary, is_custody_archived,
-    is_history_archived, ArchiveKey, ArchivedCustodySummary, ArchivedHistorySummary,
+    archive_custody_events, archive_unit_history, bump_all_registries, bump_persistent,
+    bump_rent_for_unit, get_archived_custody_summary, get_archived_history_summary,
+    is_custody_archived, is_history_archived, ArchiveKey, ArchivedCustodySummary,
+    ArchivedHistorySummary, ArchivedUnit,
 };
 
 // Re-export constants for internal use
 pub(crate) use constants::{
-    HEX_HASH_LENGTH, MAX_BATCH_EXPIRY_SIZE, MAX_BATCH_SIZE, MAX_DELIVERY_ADDRESS_LENGTH,
-    MAX_EVENTS_PER_PAGE, MAX_QUANTITY_ML, MAX_REQUEST_ML, MAX_SHELF_LIFE_DAYS, MAX_UNIT_ID_LENGTH,
-    MIN_QUANTITY_ML, MIN_REQUEST_ML, MIN_SHELF_LIFE_DAYS, NOMINATION_EXPIRY_SECONDS,
-    SECONDS_PER_DAY, TRANSFER_EXPIRY_SECONDS,
+    CRYO_MAX_VOLUME_ML, CRYO_MIN_VOLUME_ML, CURRENT_STORAGE_VERSION, DEFAULT_PAGE_SIZE,
+    DEFAULT_TRANSFER_DEADLINE_SECONDS, FROZEN_MAX_CELSIUS, FROZEN_MAX_SHELF_LIFE_DAYS,
+    FROZEN_MIN_CELSIUS, HEX_HASH_LENGTH, LEGACY_STORAGE_VERSION, MAX_BATCH_EXPIRY_SIZE,
+    MAX_BATCH_SIZE, MAX_DELIVERY_ADDRESS_LENGTH, MAX_EVENTS_PER_PAGE, MAX_PAGE_SIZE,
+    MAX_QUANTITY_ML, MAX_REQUEST_ML, MAX_RESERVATION_HOLD_SECONDS, MAX_SHELF_LIFE_DAYS,
+    MAX_TRANSFER_DEADLINE_SECONDS, MAX_UNIT_ID_LENGTH, MIN_QUANTITY_ML, MIN_REQUEST_ML,
+    MIN_SHELF_LIFE_DAYS, NOMINATION_EXPIRY_SECONDS, PLATELETS_MAX_CELSIUS,
+    PLATELETS_MAX_SHELF_LIFE_DAYS, PLATELETS_MIN_CELSIUS, REFRIGERATED_MAX_CELSIUS,
+    REFRIGERATED_MIN_CELSIUS, RESERVATION_PICKUP_WINDOW_SECONDS, SECONDS_PER_DAY,
+    TRANSFER_EXPIRY_SECONDS,
 };
 
 /// Pending SuperAdmin nomination entry.
@@ -638,10 +1168,41 @@ pub struct HealthChainContract;
 #[contractimpl]
 impl HealthChainContract {
     /// Initialize the contract with admin
-    pub fn initialize(env: Env, admin: Address) -> Symbol {
+    pub fn initialize(env: Env, admin: Address) -> Result<Symbol, Error> {
+        admin.require_auth();
+        if env.storage().instance().has(&ADMIN) {
+            return Err(Error::AlreadyInitialized);
+        }
+        env.storage().instance().set(&ADMIN, &admin);
+        env.storage()
+            .instance()
+            .set(&STORAGE_VERSION, &CURRENT_STORAGE_VERSION);
+        Ok(symbol_short!("init"))
+    }
+
+    /// Initialize the contract with admin and a recovery address.
+    ///
+    /// The recovery address is the only party able to call
+    /// `emergency_reinitialize` if the admin key is lost. Fails with
+    /// `AlreadyInitialized` once an admin is set, so the recovery address
+    /// cannot be replaced by calling this again.
+    pub fn initialize_with_recovery(
+        env: Env,
+        admin: Address,
+        recovery: Address,
+    ) -> Result<Symbol, Error> {
         admin.require_auth();
+        if env.storage().instance().has(&ADMIN) {
+            return Err(Error::AlreadyInitialized);
+        }
         env.storage().instance().set(&ADMIN, &admin);
-        symbol_short!("init")
+        env.storage()
+            .instance()
+            .set(&DataKey::RecoveryAddress, &recovery);
+        env.storage()
+            .instance()
+            .set(&STORAGE_VERSION, &CURRENT_STORAGE_VERSION);
+        Ok(symbol_short!("init"))
     }
 
     /// Get contract version
@@ -703,7 +1264,8 @@ impl HealthChainContract {
         banks.set(bank_id.clone(), LifecycleState::Active);
         env.storage().persistent().set(&BLOOD_BANKS, &banks);
 
-        env.events().publish(
+        publish_event(
+            &env,
             (symbol_short!("bank"), symbol_short!("state")),
             ActorStateChangeEvent {
                 entity_id: bank_id.clone(),
@@ -715,7 +1277,7 @@ impl HealthChainContract {
             },
         );
 
-        env.events().publish((symbol_short!("bank"), symbol_short!("reg")), bank_id);
+        publish_event(&env, (symbol_short!("bank"), symbol_short!("reg")), bank_id);
 
         Ok(())
     }
@@ -742,7 +1304,8 @@ impl HealthChainContract {
         hospitals.set(hospital_id.clone(), LifecycleState::Active);
         env.storage().persistent().set(&HOSPITALS, &hospitals);
 
-        env.events().publish(
+        publish_event(
+            &env,
             (symbol_short!("hospital"), symbol_short!("state")),
             ActorStateChangeEvent {
                 entity_id: hospital_id.clone(),
@@ -754,7 +1317,38 @@ impl HealthChainContract {
             },
         );
 
-        env.events().publish((symbol_short!("hospital"), symbol_short!("reg")), hospital_id);
+        publish_event(
+            &env,
+            (symbol_short!("hospital"), symbol_short!("reg")),
+            hospital_id,
+        );
+
+        Ok(())
+    }
+
+    /// Register a testing lab allowed to record screening results (admin only)
+    pub fn register_lab(env: Env, lab_id: Address) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+
+        let mut labs: Map<Address, LifecycleState> = env
+            .storage()
+            .persistent()
+            .get(&LABS)
+            .unwrap_or(Map::new(&env));
+
+        if labs.get(lab_id.clone()).is_some() {
+            return Err(Error::DuplicateRegistration);
+        }
+
+        labs.set(lab_id.clone(), LifecycleState::Active);
+        env.storage().persistent().set(&LABS, &labs);
+
+        publish_event(&env, (symbol_short!("lab"), symbol_short!("reg")), lab_id);
 
         Ok(())
     }
@@ -788,7 +1382,8 @@ impl HealthChainContract {
         banks.set(bank_id.clone(), LifecycleState::Active);
         env.storage().persistent().set(&BLOOD_BANKS, &banks);
 
-        env.events().publish(
+        publish_event(
+            &env,
             (symbol_short!("bank"), symbol_short!("state")),
             ActorStateChangeEvent {
                 entity_id: bank_id.clone(),
@@ -833,7 +1428,8 @@ impl HealthChainContract {
         banks.set(bank_id.clone(), LifecycleState::Inactive);
         env.storage().persistent().set(&BLOOD_BANKS, &banks);
 
-        env.events().publish(
+        publish_event(
+            &env,
             (symbol_short!("bank"), symbol_short!("state")),
             ActorStateChangeEvent {
                 entity_id: bank_id.clone(),
@@ -877,7 +1473,8 @@ impl HealthChainContract {
         hospitals.set(hospital_id.clone(), LifecycleState::Active);
         env.storage().persistent().set(&HOSPITALS, &hospitals);
 
-        env.events().publish(
+        publish_event(
+            &env,
             (symbol_short!("hospital"), symbol_short!("state")),
             ActorStateChangeEvent {
                 entity_id: hospital_id.clone(),
@@ -922,7 +1519,8 @@ impl HealthChainContract {
         hospitals.set(hospital_id.clone(), LifecycleState::Inactive);
         env.storage().persistent().set(&HOSPITALS, &hospitals);
 
-        env.events().publish(
+        publish_event(
+            &env,
             (symbol_short!("hospital"), symbol_short!("state")),
             ActorStateChangeEvent {
                 entity_id: hospital_id.clone(),
@@ -951,6 +1549,13 @@ impl HealthChainContract {
         banks.get(bank_id).unwrap_or(LifecycleState::Inactive)
     }
 
+    /// Page through registered blood bank addresses, in registry order.
+    ///
+    /// Delegates to [`registry_read::list_blood_banks`].
+    pub fn list_blood_banks(env: Env, cursor: u64, limit: u32) -> BankPage {
+        registry_read::list_blood_banks(&env, cursor, limit)
+    }
+
     /// Get the lifecycle state of an address registered as a hospital.
     pub fn get_hospital_state(
         env: Env,
@@ -989,6 +1594,9 @@ impl HealthChainContract {
 
     /// Register blood donation into inventory.
     ///
+    /// When `donor_hash` is supplied the collection is rejected with
+    /// `DonorDeferred` while that donor is inside a deferral window.
+    ///
     /// Delegates to [`registry_write::register_unit`].
     pub fn register_blood(
         env: Env,
@@ -998,23 +1606,35 @@ impl HealthChainContract {
         quantity_ml: u32,
         expiration_timestamp: u64,
         donor_id: Option<Symbol>,
+        donor_hash: Option<BytesN<32>>,
     ) -> Result<u64, Error> {
         // Authenticate and verify blood bank
         bank_id.require_auth();
+        ensure_not_paused(&env, PAUSE_REGISTER)?;
 
         if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
             return Err(Error::Unauthorized);
         }
 
-        registry_write::register_unit(
+        if let Some(hash) = donor_hash.clone() {
+            deferral::ensure_not_deferred(&env, &hash)?;
+        }
+
+        let unit_id = registry_write::register_unit(
             &env,
             bank_id,
             blood_type,
             component,
             quantity_ml,
             expiration_timestamp,
-            donor_id,
-        )
+            donor_id.clone(),
+        )?;
+
+        if let (Some(id), Some(hash)) = (donor_id, donor_hash) {
+            consent::link_donor(&env, id, hash);
+        }
+
+        Ok(unit_id)
     }
 
     /// Batch register multiple blood units in a single transaction.
@@ -1024,6 +1644,7 @@ impl HealthChainContract {
         units: Vec<(BloodType, BloodComponent, u32, u64, Option<Symbol>)>,
     ) -> Result<Vec<u64>, Error> {
         bank_id.require_auth();
+        ensure_not_paused(&env, PAUSE_REGISTER)?;
 
         if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
             return Err(Error::Unauthorized);
@@ -1052,6 +1673,72 @@ impl HealthChainContract {
         Ok(registered_ids)
     }
 
+    /// Defer a donor, identified only by a salted hash, until `until`.
+    ///
+    /// Delegates to [`deferral::defer`].
+    pub fn defer_donor(
+        env: Env,
+        bank_id: Address,
+        donor_hash: BytesN<32>,
+        until: u64,
+        reason_code: u32,
+    ) -> Result<(), Error> {
+        bank_id.require_auth();
+
+        if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
+            return Err(Error::Unauthorized);
+        }
+
+        deferral::defer(&env, bank_id, donor_hash, until, reason_code)
+    }
+
+    /// Whether the donor hash is currently inside a deferral window.
+    pub fn is_deferred(env: Env, donor_hash: BytesN<32>) -> bool {
+        deferral::is_deferred(&env, &donor_hash)
+    }
+
+    /// Stored deferral for a donor hash, including lapsed ones.
+    pub fn get_donor_deferral(env: Env, donor_hash: BytesN<32>) -> Option<deferral::DonorDeferral> {
+        deferral::load_deferral(&env, &donor_hash)
+    }
+
+    /// Record the donor's signed consent form (hash and version only).
+    ///
+    /// Delegates to [`consent::record`].
+    pub fn record_consent(
+        env: Env,
+        bank_id: Address,
+        donor_hash: BytesN<32>,
+        consent_hash: BytesN<32>,
+        version: u32,
+    ) -> Result<(), Error> {
+        bank_id.require_auth();
+
+        if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
+            return Err(Error::Unauthorized);
+        }
+
+        consent::record(&env, bank_id, donor_hash, consent_hash, version)
+    }
+
+    /// Withdraw a donor's consent. Donor-linked queries return nothing afterwards.
+    ///
+    /// Delegates to [`consent::revoke`].
+    pub fn revoke_consent(env: Env, bank_id: Address, donor_hash: BytesN<32>) -> Result<(), Error> {
+        bank_id.require_auth();
+
+        if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
+            return Err(Error::Unauthorized);
+        }
+
+        consent::revoke(&env, donor_hash)
+    }
+
+    /// Stored consent for a donor hash, including revoked ones.
+    pub fn get_consent(env: Env, donor_hash: BytesN<32>) -> Option<consent::ConsentRecord> {
+        consent::load_consent(&env, &donor_hash)
+    }
+
     /// Check if an address is an authorized blood bank
     pub fn is_blood_bank(env: Env, bank_id: Address) -> bool {
         let banks: Map<Address, LifecycleState> = env
@@ -1067,33 +1754,43 @@ impl HealthChainContract {
     }
 
     /// Allocate blood unit to a hospital
+    ///
+    /// The reservation is held for `hold_seconds`, or the configured default
+    /// hold when `None`; once it lapses without a pending transfer,
+    /// `release_expired_reservations` returns the unit to Available.
+    ///
+    /// When `recipient_type` is supplied the unit must be ABO/Rh compatible
+    /// with it (see [`is_compatible`]), otherwise `IncompatibleBloodType`.
     pub fn allocate_blood(
         env: Env,
         bank_id: Address,
         unit_id: u64,
         hospital: Address,
+        hold_seconds: Option<u64>,
+        recipient_type: Option<BloodType>,
     ) -> Result<(), Error> {
         bank_id.require_auth();
+        ensure_not_paused(&env, PAUSE_ALLOCATE)?;
+
+        let hold_seconds = match hold_seconds {
+            Some(hold) => validate_hold_seconds(hold)?,
+            None => default_hold_seconds(&env),
+        };
 
         if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
             return Err(Error::Unauthorized);
         }
 
+        // Never reserve blood for an address outside the active hospital set
         if !Self::is_hospital(env.clone(), hospital.clone()) {
-            return Err(Error::UnauthorizedHospital);
+            return Err(Error::HospitalNotRegistered);
         }
 
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
-
-        let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+        let mut unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
 
         // Re-validate hospital status immediately before the storage write (fix #946 TOCTOU)
         if !Self::is_hospital(env.clone(), hospital.clone()) {
-            return Err(Error::UnauthorizedHospital);
+            return Err(Error::HospitalNotRegistered);
         }
 
         // --- NEW: REQUIREMENT #67 GUARD ---
@@ -1111,16 +1808,22 @@ impl HealthChainContract {
             return Err(Error::InvalidStatus);
         }
 
+        if !screening_cleared(&env, unit_id) {
+            return Err(Error::ScreeningIncomplete);
+        }
+
+        check_compatible(&unit, recipient_type)?;
+
         let old_status = unit.status;
         unit.status = BloodStatus::Reserved;
         unit.recipient_hospital = Some(hospital.clone());
         unit.allocation_timestamp = Some(current_time);
 
-        units.set(unit_id, unit.clone());
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
+        save_unit(&env, &unit);
 
         // Maintain status index
         reindex_status(&env, unit_id, old_status, BloodStatus::Reserved);
+        start_reservation(&env, unit_id, current_time, hold_seconds);
 
         // Maintain hospital units index
         index_hospital_unit(&env, &hospital, unit_id);
@@ -1133,7 +1836,8 @@ impl HealthChainContract {
             bank_id.clone(),
         );
 
-        env.events().publish(
+        publish_event(
+            &env,
             (
                 symbol_short!("blood"),
                 symbol_short!("allocate"),
@@ -1146,13 +1850,18 @@ impl HealthChainContract {
     }
 
     /// Batch allocate blood units
+    ///
+    /// When `recipient_type` is supplied every unit must be ABO/Rh compatible
+    /// with it.
     pub fn batch_allocate_blood(
         env: Env,
         bank_id: Address,
         unit_ids: Vec<u64>,
         hospital: Address,
+        recipient_type: Option<BloodType>,
     ) -> Result<Vec<u64>, Error> {
         bank_id.require_auth();
+        ensure_not_paused(&env, PAUSE_ALLOCATE)?;
 
         // Check batch size
         if unit_ids.len() > MAX_BATCH_SIZE {
@@ -1166,22 +1875,17 @@ impl HealthChainContract {
 
         // Verify hospital is registered
         if !Self::is_hospital(env.clone(), hospital.clone()) {
-            return Err(Error::UnauthorizedHospital);
+            return Err(Error::HospitalNotRegistered);
         }
 
         let mut allocated = vec![&env];
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
 
         let current_time = env.ledger().timestamp();
 
         // Process all units
         for i in 0..unit_ids.len() {
             let unit_id = unit_ids.get(i).unwrap();
-            let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+            let unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
 
             // Check if expired
             if unit.expiration_date <= current_time {
@@ -1193,46 +1897,149 @@ impl HealthChainContract {
                 return Err(Error::InvalidStatus);
             }
 
-            // Record old status for event
-            let old_status = unit.status;
-
-            // Update unit
-            unit.status = BloodStatus::Reserved;
-            unit.recipient_hospital = Some(hospital.clone());
-            unit.allocation_timestamp = Some(current_time);
+            if !screening_cleared(&env, unit_id) {
+                return Err(Error::ScreeningIncomplete);
+            }
 
-            units.set(unit_id, unit.clone());
+            check_compatible(&unit, recipient_type)?;
 
-            // Maintain status index
-            reindex_status(&env, unit_id, old_status, BloodStatus::Reserved);
+            reserve_unit(&env, unit, &bank_id, &hospital, current_time);
+            allocated.push_back(unit_id);
+        }
 
-            // Maintain hospital units index
-            index_hospital_unit(&env, &hospital, unit_id);
+        Ok(allocated)
+    }
 
-            // Record status change
-            record_status_change(
-                &env,
-                unit_id,
-                old_status,
-                BloodStatus::Reserved,
-                bank_id.clone(),
-            );
+    /// Reserve every unit in `unit_ids` for `hospital`, or none of them.
+    ///
+    /// All units are validated before any is written. If one would fail
+    /// single allocation (unknown, another bank's, expired, not Available,
+    /// screening incomplete, incompatible with `recipient_type`, or listed
+    /// twice) the call returns `BatchAllocationOutcome::Rejected` naming the
+    /// first such unit and leaves every unit untouched. Reservations use the
+    /// default hold duration.
+    pub fn allocate_blood_batch(
+        env: Env,
+        bank_id: Address,
+        unit_ids: Vec<u64>,
+        hospital: Address,
+        recipient_type: Option<BloodType>,
+    ) -> Result<BatchAllocationOutcome, Error> {
+        bank_id.require_auth();
+        ensure_not_paused(&env, PAUSE_ALLOCATE)?;
 
-            // Emit event
-            env.events().publish(
-                (
-                    symbol_short!("blood"),
-                    symbol_short!("allocate"),
-                    symbol_short!("v1"),
-                ),
-                (unit_id, hospital.clone(), current_time),
-            );
+        if unit_ids.is_empty() {
+            return Err(Error::InvalidQuantity);
+        }
+        if unit_ids.len() > MAX_BATCH_SIZE {
+            return Err(Error::BatchSizeExceeded);
+        }
 
-            allocated.push_back(unit_id);
+        if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
+            return Err(Error::Unauthorized);
+        }
+        if !Self::is_hospital(env.clone(), hospital.clone()) {
+            return Err(Error::HospitalNotRegistered);
+        }
+
+        let current_time = env.ledger().timestamp();
+        let mut units: Vec<BloodUnit> = Vec::new(&env);
+        for (index, unit_id) in unit_ids.iter().enumerate() {
+            let checked = match load_unit(&env, unit_id) {
+                Some(_) if unit_ids.first_index_of(unit_id) != Some(index as u32) => {
+                    Err(Error::AlreadyAllocated)
+                }
+                Some(unit) => {
+                    check_allocatable(&env, &unit, &bank_id, recipient_type, current_time)
+                        .map(|_| unit)
+                }
+                None => Err(Error::UnitNotFound),
+            };
+            match checked {
+                Ok(unit) => units.push_back(unit),
+                Err(error) => {
+                    return Ok(BatchAllocationOutcome::Rejected(BatchAllocationFailure {
+                        index: index as u32,
+                        unit_id,
+                        error_code: error as u32,
+                    }));
+                }
+            }
+        }
+
+        for unit in units.iter() {
+            reserve_unit(&env, unit, &bank_id, &hospital, current_time);
+        }
+
+        Ok(BatchAllocationOutcome::Allocated(unit_ids))
+    }
+
+    /// Reserve the `count` earliest-expiring Available units of `blood_type`
+    /// held by `bank_id` for `hospital` (first-expiring-first-out).
+    ///
+    /// Walks the expiry-ordered `AvailableByType` index, skipping units that
+    /// are already past their date or have not cleared screening. Either all
+    /// `count` units are reserved or none are: `InsufficientInventory` is
+    /// returned when fewer eligible units exist. Returns the reserved IDs in
+    /// expiry order.
+    pub fn allocate_fefo(
+        env: Env,
+        bank_id: Address,
+        blood_type: BloodType,
+        count: u32,
+        hospital: Address,
+    ) -> Result<Vec<u64>, Error> {
+        bank_id.require_auth();
+        ensure_not_paused(&env, PAUSE_ALLOCATE)?;
+
+        if count == 0 {
+            return Err(Error::InvalidQuantity);
+        }
+        if count > MAX_BATCH_SIZE {
+            return Err(Error::BatchSizeExceeded);
+        }
+
+        if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
+            return Err(Error::Unauthorized);
+        }
+        if !Self::is_hospital(env.clone(), hospital.clone()) {
+            return Err(Error::HospitalNotRegistered);
+        }
+
+        let current_time = env.ledger().timestamp();
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AvailableByType(bank_id.clone(), blood_type))
+            .unwrap_or(Vec::new(&env));
+
+        // Select first, then reserve, so the index is not mutated mid-walk
+        let mut selected: Vec<BloodUnit> = Vec::new(&env);
+        for unit_id in ids.iter() {
+            if selected.len() >= count {
+                break;
+            }
+            let unit = match load_unit(&env, unit_id) {
+                Some(unit) => unit,
+                None => continue,
+            };
+            if unit.status != BloodStatus::Available
+                || unit.expiration_date <= current_time
+                || !screening_cleared(&env, unit_id)
+            {
+                continue;
+            }
+            selected.push_back(unit);
+        }
+        if selected.len() < count {
+            return Err(Error::InsufficientInventory);
         }
 
-        // Save all changes
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
+        let mut allocated = Vec::new(&env);
+        for unit in selected.iter() {
+            allocated.push_back(unit.id);
+            reserve_unit(&env, unit, &bank_id, &hospital, current_time);
+        }
 
         Ok(allocated)
     }
@@ -1247,13 +2054,7 @@ impl HealthChainContract {
         }
 
         // Get blood unit
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
-
-        let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+        let mut unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
 
         // Verify caller is the current custodian of this specific unit
         if unit.bank_id != bank_id {
@@ -1274,11 +2075,11 @@ impl HealthChainContract {
         unit.recipient_hospital = None;
         unit.allocation_timestamp = None;
 
-        units.set(unit_id, unit.clone());
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
+        save_unit(&env, &unit);
 
         // Maintain status index
         reindex_status(&env, unit_id, old_status, BloodStatus::Available);
+        resolve_reservation(&env, unit_id, &bank_id, env.ledger().timestamp());
 
         // Remove from hospital units index (allocation is being cancelled)
         if let Some(ref hosp) = hospital_id {
@@ -1295,7 +2096,8 @@ impl HealthChainContract {
         );
 
         // Emit event
-        env.events().publish(
+        publish_event(
+            &env,
             (
                 symbol_short!("blood"),
                 symbol_short!("cancel"),
@@ -1307,27 +2109,304 @@ impl HealthChainContract {
         Ok(())
     }
 
+    /// Release stale reservations held by `bank_id` back to `Available`.
+    ///
+    /// A unit is eligible when it belongs to the bank, is `Reserved`, was
+    /// allocated at least `RESERVATION_PICKUP_WINDOW_SECONDS` ago and has no
+    /// pending custody transfer. Ineligible or unknown IDs are skipped.
+    /// Returns the number of units released. At most `MAX_BATCH_SIZE` IDs per call.
+    pub fn release_stale_reservations_batch(
+        env: Env,
+        bank_id: Address,
+        unit_ids: Vec<u64>,
+    ) -> Result<u32, Error> {
+        bank_id.require_auth();
+
+        if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
+            return Err(Error::Unauthorized);
+        }
+
+        if unit_ids.len() > MAX_BATCH_SIZE {
+            return Err(Error::BatchSizeExceeded);
+        }
+
+        let current_time = env.ledger().timestamp();
+        let mut released: u32 = 0;
+
+        for unit_id in unit_ids.iter() {
+            let unit = match load_unit(&env, unit_id) {
+                Some(unit) => unit,
+                None => continue,
+            };
+
+            if unit.bank_id != bank_id || unit.status != BloodStatus::Reserved {
+                continue;
+            }
+
+            let allocated_at = match unit.allocation_timestamp {
+                Some(ts) => ts,
+                None => continue,
+            };
+            if current_time < allocated_at.saturating_add(RESERVATION_PICKUP_WINDOW_SECONDS) {
+                continue;
+            }
+
+            if env
+                .storage()
+                .persistent()
+                .has(&DataKey::UnitCustodyIndex(unit_id))
+            {
+                continue;
+            }
+
+            release_reservation(&env, unit, &bank_id, current_time);
+            released += 1;
+        }
+
+        Ok(released)
+    }
+
+    /// Keeper entry point: release up to `limit` reservations whose
+    /// `reserved_until` deadline has passed back to Available.
+    ///
+    /// Callable by anyone. Units with a pending custody transfer are left
+    /// alone. `limit` is capped at `MAX_BATCH_SIZE`; returns the number of
+    /// units released.
+    pub fn release_expired_reservations(env: Env, limit: u32) -> u32 {
+        let limit = limit.min(MAX_BATCH_SIZE);
+        let current_time = env.ledger().timestamp();
+        let keeper = env.current_contract_address();
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::StatusUnits(BloodStatus::Reserved))
+            .unwrap_or(Vec::new(&env));
+
+        let mut released: u32 = 0;
+        for unit_id in ids.iter() {
+            if released >= limit {
+                break;
+            }
+            let unit = match load_unit(&env, unit_id) {
+                Some(unit) if unit.status == BloodStatus::Reserved => unit,
+                _ => continue,
+            };
+            let window: ReservationWindow = match env
+                .storage()
+                .persistent()
+                .get(&DataKey::ReservationWindow(unit_id))
+            {
+                Some(window) => window,
+                None => continue,
+            };
+            if current_time < window.reserved_until {
+                continue;
+            }
+            if env
+                .storage()
+                .persistent()
+                .has(&DataKey::UnitCustodyIndex(unit_id))
+            {
+                continue;
+            }
+
+            release_reservation(&env, unit, &keeper, current_time);
+            released += 1;
+        }
+
+        released
+    }
+
+    /// Move an unclaimed unit from `from_bank`'s inventory to `to_bank`'s.
+    ///
+    /// Both banks must sign. The unit must belong to `from_bank` and be
+    /// Available or Quarantined; reserved or moving units stay with their bank.
+    /// Rewrites `bank_id` and moves the unit between the banks' BankUnits,
+    /// DonorUnits and AvailableByType indexes and TypeSummary counters.
+    pub fn transfer_unit_ownership(
+        env: Env,
+        from_bank: Address,
+        to_bank: Address,
+        unit_id: u64,
+    ) -> Result<(), Error> {
+        from_bank.require_auth();
+        to_bank.require_auth();
+        ensure_not_paused(&env, PAUSE_CUSTODY)?;
+
+        if from_bank == to_bank {
+            return Err(Error::InvalidTransition);
+        }
+        if !Self::is_blood_bank(env.clone(), from_bank.clone())
+            || !Self::is_blood_bank(env.clone(), to_bank.clone())
+        {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
+        if unit.bank_id != from_bank {
+            return Err(Error::NotCurrentCustodian);
+        }
+        if unit.status != BloodStatus::Available && unit.status != BloodStatus::Quarantined {
+            return Err(Error::InvalidStatus);
+        }
+
+        // Leave the old bank's indexes and counters
+        deindex_bank_unit(&env, &from_bank, unit_id);
+        deindex_donor_unit(&env, &from_bank, &unit.donor_id, unit_id);
+        if unit.status == BloodStatus::Available {
+            deindex_available_unit(&env, &from_bank, unit.blood_type, unit_id);
+        }
+        adjust_type_summary(&env, &unit, Some(unit.status), None);
+
+        unit.bank_id = to_bank.clone();
+        save_unit(&env, &unit);
+
+        // Join the new bank's
+        index_bank_unit(&env, &to_bank, unit_id);
+        let key = DataKey::DonorUnits(to_bank.clone(), unit.donor_id.clone());
+        let mut donor_ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        donor_ids.push_back(unit_id);
+        env.storage().persistent().set(&key, &donor_ids);
+        bump_persistent(&env, &key);
+        if unit.status == BloodStatus::Available {
+            index_available_unit(
+                &env,
+                &to_bank,
+                unit.blood_type,
+                unit_id,
+                unit.expiration_date,
+            );
+        }
+        adjust_type_summary(&env, &unit, None, Some(unit.status));
+
+        publish_event(
+            &env,
+            (
+                symbol_short!("blood"),
+                symbol_short!("ownership"),
+                symbol_short!("v1"),
+            ),
+            (unit_id, from_bank, to_bank, env.ledger().timestamp()),
+        );
+
+        Ok(())
+    }
+
+    /// Split one of `bank_id`'s Available units into pediatric aliquots.
+    ///
+    /// Delegates to [`registry_write::split_unit`].
+    pub fn split_unit(
+        env: Env,
+        bank_id: Address,
+        unit_id: u64,
+        volumes: Vec<u32>,
+    ) -> Result<Vec<u64>, Error> {
+        bank_id.require_auth();
+        ensure_not_paused(&env, PAUSE_REGISTER)?;
+
+        if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
+            return Err(Error::Unauthorized);
+        }
+
+        registry_write::split_unit(&env, bank_id, unit_id, volumes)
+    }
+
+    /// Combine several of `bank_id`'s Available units into one pooled product.
+    ///
+    /// Delegates to [`registry_write::pool_units`].
+    pub fn pool_units(env: Env, bank_id: Address, unit_ids: Vec<u64>) -> Result<u64, Error> {
+        bank_id.require_auth();
+        ensure_not_paused(&env, PAUSE_REGISTER)?;
+
+        if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
+            return Err(Error::Unauthorized);
+        }
+
+        registry_write::pool_units(&env, bank_id, unit_ids)
+    }
+
     /// Initiate blood transfer
     /// Creates a custody event with deterministically derived event_id
-    pub fn initiate_transfer(env: Env, bank_id: Address, unit_id: u64) -> Result<String, Error> {
+    /// The optional `doc_hash` and `note` are stored on the event as the sender's handoff reference
+    /// `location_hash` is a salted geohash of the handoff point; coordinates never go on-chain
+    pub fn initiate_transfer(
+        env: Env,
+        bank_id: Address,
+        unit_id: u64,
+        doc_hash: Option<BytesN<32>>,
+        note: Option<Symbol>,
+        location_hash: Option<BytesN<32>>,
+    ) -> Result<String, Error> {
         // CUSTODIAN AUTHORIZATION: Verify caller is authenticated and authorized actor
         bank_id.require_auth();
+        ensure_not_paused(&env, PAUSE_CUSTODY)?;
 
         if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
             return Err(Error::Unauthorized);
         }
 
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
+        Self::start_transfer(&env, &bank_id, unit_id, doc_hash, note, location_hash)
+    }
+
+    /// Initiate transfers for every unit in `unit_ids` (one delivery run).
+    ///
+    /// Each unit goes through the same checks as `initiate_transfer`. The batch
+    /// is atomic: if any unit fails, the error is returned and no transfer in
+    /// the batch is recorded. `location_hash` is recorded on every event.
+    /// Returns the custody event IDs in input order.
+    pub fn initiate_transfer_batch(
+        env: Env,
+        bank_id: Address,
+        unit_ids: Vec<u64>,
+        location_hash: Option<BytesN<32>>,
+    ) -> Result<Vec<String>, Error> {
+        bank_id.require_auth();
+        ensure_not_paused(&env, PAUSE_CUSTODY)?;
+
+        if unit_ids.is_empty() {
+            return Err(Error::InvalidQuantity);
+        }
+        if unit_ids.len() > MAX_BATCH_SIZE {
+            return Err(Error::BatchSizeExceeded);
+        }
 
-        let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+        if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut event_ids = Vec::new(&env);
+        for unit_id in unit_ids.iter() {
+            event_ids.push_back(Self::start_transfer(
+                &env,
+                &bank_id,
+                unit_id,
+                None,
+                None,
+                location_hash.clone(),
+            )?);
+        }
+
+        Ok(event_ids)
+    }
+
+    /// Move one Reserved unit into transit; the caller has already been authorized.
+    fn start_transfer(
+        env: &Env,
+        bank_id: &Address,
+        unit_id: u64,
+        doc_hash: Option<BytesN<32>>,
+        note: Option<Symbol>,
+        location_hash: Option<BytesN<32>>,
+    ) -> Result<String, Error> {
+        let mut unit = load_unit(env, unit_id).ok_or(Error::UnitNotFound)?;
 
         // INVARIANT: Only the current custodian (unit.bank_id) can initiate a transfer
         // This ensures that only actors with actual possession can move the unit
-        if unit.bank_id != bank_id {
+        if unit.bank_id != *bank_id {
             return Err(Error::NotCurrentCustodian);
         }
 
@@ -1350,7 +2429,7 @@ impl HealthChainContract {
         let to_custodian = unit.recipient_hospital.clone().ok_or(Error::StorageError)?;
 
         // Derive deterministic event_id
-        let event_id = Self::derive_event_id(&env, unit_id, &bank_id, &to_custodian);
+        let event_id = Self::derive_event_id(env, unit_id, bank_id, &to_custodian);
 
         // Validate event_id length (should always be HEX_HASH_LENGTH, but check for safety)
         if event_id.len() > MAX_UNIT_ID_LENGTH {
@@ -1363,59 +2442,39 @@ impl HealthChainContract {
             unit_id,
             from_custodian: bank_id.clone(),
             to_custodian: to_custodian.clone(),
+            from_kind: CustodianKind::Bank,
+            to_kind: CustodianKind::Hospital,
             initiated_at: current_time,
+            transfer_deadline: current_time.saturating_add(default_transfer_deadline(env)),
             ledger_sequence: env.ledger().sequence(),
             status: CustodyStatus::Pending,
+            doc_hash,
+            note,
+            location_hash,
+            closing_location_hash: None,
         };
 
-        // Store custody event
-        let mut custody_events: Map<String, CustodyEvent> = env
-            .storage()
-            .persistent()
-            .get(&CUSTODY_EVENTS)
-            .unwrap_or(Map::new(&env));
+        store_pending_custody_event(env, &custody_event);
 
-        custody_events.set(event_id.clone(), custody_event.clone());
-        env.storage()
-            .persistent()
-            .set(&CUSTODY_EVENTS, &custody_events);
+        let old_status = unit.status;
+        unit.status = BloodStatus::InTransit;
+        unit.transfer_timestamp = Some(current_time);
 
-        // Maintain UnitCustodyIndex so confirm_delivery can find the pending event in O(1)
-        let index_key = DataKey::UnitCustodyIndex(unit_id);
-        env.storage().persistent().set(&index_key, &event_id);
-
-        // Maintain per-unit custody events list so archive_custody_events can find all events
-        // for this unit in O(k) (k = events per unit) instead of scanning the full CUSTODY_EVENTS map
-        let unit_events_key = DataKey::UnitCustodyEvents(unit_id);
-        let mut unit_event_ids: Vec<String> = env
-            .storage()
-            .persistent()
-            .get(&unit_events_key)
-            .unwrap_or(Vec::new(&env));
-        unit_event_ids.push_back(event_id.clone());
-        env.storage()
-            .persistent()
-            .set(&unit_events_key, &unit_event_ids);
-
-        let old_status = unit.status;
-        unit.status = BloodStatus::InTransit;
-        unit.transfer_timestamp = Some(current_time);
-
-        units.set(unit_id, unit.clone());
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
+        save_unit(env, &unit);
 
         // Maintain status index
-        reindex_status(&env, unit_id, old_status, BloodStatus::InTransit);
+        reindex_status(env, unit_id, old_status, BloodStatus::InTransit);
 
         record_status_change(
-            &env,
+            env,
             unit_id,
             old_status,
             BloodStatus::InTransit,
             bank_id.clone(),
         );
 
-        env.events().publish(
+        publish_event(
+            env,
             (
                 symbol_short!("custody"),
                 symbol_short!("initiate"),
@@ -1427,199 +2486,707 @@ impl HealthChainContract {
         Ok(event_id)
     }
 
-    /// Confirm blood delivery
+    /// Record the rider carrying an in-transit unit (originating bank only).
     ///
-    /// This is kept for backwards-compatibility and delegates to `confirm_transfer`.
-    /// Note: This function looks up the pending custody event by unit_id via the
-    /// UnitCustodyIndex — O(1) instead of an O(n) scan over all custody events.
-    pub fn confirm_delivery(env: Env, hospital: Address, unit_id: u64) -> Result<(), Error> {
-        // Look up the pending event_id via the per-unit custody index (O(1))
-        let index_key = DataKey::UnitCustodyIndex(unit_id);
-        let event_id: String = env
-            .storage()
-            .persistent()
-            .get(&index_key)
-            .ok_or(Error::UnitNotFound)?;
+    /// The assignment is cleared when the transfer is confirmed or cancelled.
+    pub fn assign_transfer_rider(
+        env: Env,
+        bank_id: Address,
+        unit_id: u64,
+        rider: Address,
+    ) -> Result<(), Error> {
+        bank_id.require_auth();
+
+        if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
+            return Err(Error::Unauthorized);
+        }
+
+        let unit = registry_read::get_unit(&env, unit_id)?;
+        if unit.bank_id != bank_id {
+            return Err(Error::NotCurrentCustodian);
+        }
+        if unit.status != BloodStatus::InTransit {
+            return Err(Error::InvalidStatus);
+        }
 
-        Self::confirm_transfer(env, hospital, event_id)
+        env.storage()
+            .persistent()
+            .set(&DataKey::UnitRider(unit_id), &rider);
+        Ok(())
     }
 
-    /// Confirm an in-transit transfer using the derived event_id.
+    /// Hand a unit to the next custodian on a multi-hop delivery.
     ///
-    /// Must be confirmed strictly before `initiated_at + TRANSFER_EXPIRY_SECONDS`.
-    /// Callers must compute the same hash (unit_id + from + to + ledger_sequence) to reference the transfer.
-    pub fn confirm_transfer(env: Env, hospital: Address, event_id: String) -> Result<(), Error> {
-        // Validate event_id length
+    /// The first hop is sent by the registering bank from `Reserved` and moves
+    /// the unit `InTransit`; later hops are sent by the rider currently holding
+    /// it. `to` must be the unit's recipient hospital or hold the `Rider` role
+    /// in the identity contract. A unit may have only one pending hop at a time.
+    /// Riders accept with `confirm_hop`; the hospital with `confirm_transfer`.
+    /// `doc_hash` and `note` work as on `initiate_transfer` and `confirm_transfer`.
+    /// Returns the custody event id.
+    pub fn initiate_hop(
+        env: Env,
+        from: Address,
+        unit_id: u64,
+        to: Address,
+        doc_hash: Option<BytesN<32>>,
+        note: Option<Symbol>,
+    ) -> Result<String, Error> {
+        from.require_auth();
+        ensure_not_paused(&env, PAUSE_CUSTODY)?;
+
+        let mut unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
+        let current_time = env.ledger().timestamp();
+        if unit.status == BloodStatus::Expired || unit.expiration_date <= current_time {
+            return Err(Error::UnitExpired);
+        }
+
+        // INVARIANT: at most one pending transfer per unit, across all hops
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::UnitCustodyIndex(unit_id))
+        {
+            return Err(Error::InvalidStatus);
+        }
+
+        let from_kind = match unit.status {
+            BloodStatus::Reserved => {
+                if unit.bank_id != from || !Self::is_blood_bank(env.clone(), from.clone()) {
+                    return Err(Error::NotCurrentCustodian);
+                }
+                CustodianKind::Bank
+            }
+            BloodStatus::InTransit => {
+                let rider: Option<Address> =
+                    env.storage().persistent().get(&DataKey::UnitRider(unit_id));
+                if rider != Some(from.clone()) {
+                    return Err(Error::NotCurrentCustodian);
+                }
+                CustodianKind::Rider
+            }
+            _ => return Err(Error::InvalidStatus),
+        };
+
+        let recipient = unit.recipient_hospital.clone().ok_or(Error::StorageError)?;
+        let to_kind = if to == recipient {
+            if !Self::is_hospital(env.clone(), to.clone()) {
+                return Err(Error::UnauthorizedHospital);
+            }
+            CustodianKind::Hospital
+        } else {
+            if to == from {
+                return Err(Error::InvalidCustodian);
+            }
+            identity::require_role(&env, &to, identity::Role::Rider)?;
+            CustodianKind::Rider
+        };
+
+        let event_id = Self::derive_event_id(&env, unit_id, &from, &to);
         if event_id.len() > MAX_UNIT_ID_LENGTH {
             return Err(Error::UnitIdTooLong);
         }
 
-        // CUSTODIAN AUTHORIZATION: Verify caller is authenticated and authorized actor
-        hospital.require_auth();
+        let custody_event = CustodyEvent {
+            event_id: event_id.clone(),
+            unit_id,
+            from_custodian: from.clone(),
+            to_custodian: to,
+            from_kind,
+            to_kind,
+            initiated_at: current_time,
+            transfer_deadline: current_time.saturating_add(default_transfer_deadline(&env)),
+            ledger_sequence: env.ledger().sequence(),
+            status: CustodyStatus::Pending,
+            doc_hash,
+            note,
+            location_hash: None,
+            closing_location_hash: None,
+        };
+        store_pending_custody_event(&env, &custody_event);
 
-        // Verify hospital is registered and authorized
-        if !Self::is_hospital(env.clone(), hospital.clone()) {
-            return Err(Error::UnauthorizedHospital);
+        if from_kind == CustodianKind::Bank {
+            let old_status = unit.status;
+            unit.status = BloodStatus::InTransit;
+            unit.transfer_timestamp = Some(current_time);
+            save_unit(&env, &unit);
+            reindex_status(&env, unit_id, old_status, BloodStatus::InTransit);
+            record_status_change(&env, unit_id, old_status, BloodStatus::InTransit, from);
         }
 
-        // Get custody event
+        publish_event(
+            &env,
+            (
+                symbol_short!("custody"),
+                symbol_short!("hop"),
+                symbol_short!("v1"),
+            ),
+            custody_event,
+        );
+
+        Ok(event_id)
+    }
+
+    /// Accept a pending hop as the receiving rider.
+    ///
+    /// Must happen strictly before the hop's `transfer_deadline`.
+    /// While dual authorization is on, the sending custodian must also sign.
+    /// The rider becomes the unit's current custodian and the hop is appended
+    /// to the custody trail; the unit stays `InTransit`.
+    pub fn confirm_hop(
+        env: Env,
+        rider: Address,
+        event_id: String,
+        doc_hash: Option<BytesN<32>>,
+        note: Option<Symbol>,
+    ) -> Result<(), Error> {
+        if event_id.len() > MAX_UNIT_ID_LENGTH {
+            return Err(Error::UnitIdTooLong);
+        }
+
+        rider.require_auth();
+        ensure_not_paused(&env, PAUSE_CUSTODY)?;
+
         let mut custody_events: Map<String, CustodyEvent> = env
             .storage()
             .persistent()
             .get(&CUSTODY_EVENTS)
             .unwrap_or(Map::new(&env));
-
         let mut custody_event = custody_events
             .get(event_id.clone())
             .ok_or(Error::UnitNotFound)?;
 
-        // Verify the event's designated recipient is a registered hospital
-        if !Self::is_hospital(env.clone(), custody_event.to_custodian.clone()) {
-            return Err(Error::UnauthorizedHospital);
-        }
-
-        // INVARIANT: Only the designated recipient (to_custodian) can confirm the transfer
-        // This ensures units can only be received by the intended hospital
-        if custody_event.to_custodian != hospital {
+        if custody_event.to_custodian != rider || custody_event.to_kind != CustodianKind::Rider {
             return Err(Error::Unauthorized);
         }
-
-        // INVARIANT: Custody event must be in Pending status (not already confirmed/recovered)
         if custody_event.status != CustodyStatus::Pending {
             return Err(Error::InvalidStatus);
         }
+        if dual_auth_required(&env) {
+            custody_event.from_custodian.require_auth();
+        }
 
         let unit_id = custody_event.unit_id;
-
-        // Get blood unit
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
-
-        let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
-
-        // INVARIANT: Unit must be in InTransit status (transferred but not yet confirmed)
+        let unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
         if unit.status != BloodStatus::InTransit {
             return Err(Error::InvalidStatus);
         }
 
-        let initiated_at = custody_event.initiated_at;
         let current_time = env.ledger().timestamp();
-
-        // EXPIRY ENFORCEMENT: Transfer window must not be expired (30-minute limit)
-        // At/after boundary is considered expired to ensure clean cutoffs
-        if current_time >= initiated_at.saturating_add(TRANSFER_EXPIRY_SECONDS) {
+        if current_time >= custody_event.transfer_deadline {
             return Err(Error::TransferExpired);
         }
 
-        let old_status = unit.status;
+        custody_event.status = CustodyStatus::Confirmed;
+        attach_handoff_reference(&mut custody_event, doc_hash, note);
+        custody_events.set(event_id.clone(), custody_event.clone());
+        env.storage()
+            .persistent()
+            .set(&CUSTODY_EVENTS, &custody_events);
+        record_custody_transition(&env, Some(CustodyStatus::Pending), CustodyStatus::Confirmed);
 
-        // RECOVERY PATH: Check if blood unit expired during transit
-        // If unit expiration passed while in transit, mark as recovered with explicit event
-        if unit.expiration_date <= current_time {
-            unit.status = BloodStatus::Expired;
-            units.set(unit_id, unit.clone());
-            env.storage().persistent().set(&BLOOD_UNITS, &units);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::UnitCustodyIndex(unit_id));
+        env.storage()
+            .persistent()
+            .set(&DataKey::UnitRider(unit_id), &rider);
 
-            // Maintain status index
-            reindex_status(&env, unit_id, old_status, BloodStatus::Expired);
+        append_to_custody_trail(&env, unit_id, event_id);
 
-            // Update custody event to Recovered status to indicate recovery action
-            custody_event.status = CustodyStatus::Recovered;
-            custody_events.set(event_id.clone(), custody_event.clone());
-            env.storage()
-                .persistent()
-                .set(&CUSTODY_EVENTS, &custody_events);
+        publish_event(
+            &env,
+            (
+                symbol_short!("custody"),
+                symbol_short!("hop_conf"),
+                symbol_short!("v1"),
+            ),
+            custody_event,
+        );
 
-            // Clear UnitCustodyIndex — transfer is no longer pending
-            env.storage()
-                .persistent()
-                .remove(&DataKey::UnitCustodyIndex(unit_id));
+        Ok(())
+    }
 
-            record_status_change(
-                &env,
-                unit_id,
-                old_status,
-                BloodStatus::Expired,
-                hospital.clone(),
-            );
+    /// Who physically holds a unit right now, and in what capacity.
+    ///
+    /// Delivered units are held by their recipient hospital; in-transit and
+    /// returning units by the assigned rider. Every other status — including a
+    /// moving unit with no rider recorded — resolves to the registering bank.
+    pub fn get_current_custodian(
+        env: Env,
+        unit_id: u64,
+    ) -> Result<(Address, CustodianKind), Error> {
+        let unit = registry_read::get_unit(&env, unit_id)?;
 
-            // Emit explicit recovery event for backend projection consistency
-            env.events().publish(
-                (
-                    symbol_short!("custody"),
-                    symbol_short!("recover"),
-                    symbol_short!("v1"),
-                ),
-                TransferRecoveryEvent {
-                    custody_event_id: event_id,
-                    unit_id,
-                    actor: hospital.clone(),
-                    recovery_reason: 0, // 0 = unit_expired_during_transit
-                    previous_custody_status: CustodyStatus::Pending,
-                    new_custody_status: CustodyStatus::Recovered,
-                    unit_status_after_recovery: BloodStatus::Expired,
-                    recovery_timestamp: current_time,
-                },
-            );
+        match unit.status {
+            BloodStatus::Delivered => {
+                let hospital = unit.recipient_hospital.ok_or(Error::StorageError)?;
+                Ok((hospital, CustodianKind::Hospital))
+            }
+            BloodStatus::InTransit | BloodStatus::ReturnPending => {
+                match env
+                    .storage()
+                    .persistent()
+                    .get::<DataKey, Address>(&DataKey::UnitRider(unit_id))
+                {
+                    Some(rider) => Ok((rider, CustodianKind::Rider)),
+                    None => Ok((unit.bank_id, CustodianKind::Bank)),
+                }
+            }
+            _ => Ok((unit.bank_id, CustodianKind::Bank)),
+        }
+    }
+
+    /// Hand a reserved unit from its bank straight to the recipient hospital.
+    ///
+    /// Both custodians must sign the same invocation: the bank releases and the
+    /// hospital accepts atomically, so neither side can claim custody alone.
+    /// Records an already-confirmed custody event and marks the unit Delivered.
+    /// Returns the custody event id.
+    pub fn handoff_with_dual_auth(
+        env: Env,
+        from: Address,
+        to: Address,
+        unit_id: u64,
+    ) -> Result<String, Error> {
+        // CUSTODIAN AUTHORIZATION: outgoing and incoming custodians sign together
+        from.require_auth();
+        to.require_auth();
+        ensure_not_paused(&env, PAUSE_CUSTODY)?;
+
+        if !Self::is_blood_bank(env.clone(), from.clone()) {
+            return Err(Error::Unauthorized);
+        }
+        if !Self::is_hospital(env.clone(), to.clone()) {
+            return Err(Error::UnauthorizedHospital);
+        }
+
+        let mut unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
+
+        if unit.bank_id != from {
+            return Err(Error::NotCurrentCustodian);
+        }
 
+        let current_time = env.ledger().timestamp();
+        if unit.status == BloodStatus::Expired || unit.expiration_date <= current_time {
             return Err(Error::UnitExpired);
         }
 
-        // Update custody event status
-        custody_event.status = CustodyStatus::Confirmed;
+        if unit.status != BloodStatus::Reserved {
+            return Err(Error::InvalidStatus);
+        }
+
+        // INVARIANT: the unit can only be handed to the hospital it was allocated to
+        if unit.recipient_hospital != Some(to.clone()) {
+            return Err(Error::Unauthorized);
+        }
+
+        // Same crossmatch rule as confirm_transfer; the handoff is a delivery too
+        if !crossmatch_cleared(&env, unit_id, &to, current_time) {
+            return Err(Error::CrossmatchRequired);
+        }
+
+        let event_id = Self::derive_event_id(&env, unit_id, &from, &to);
+        let custody_event = CustodyEvent {
+            event_id: event_id.clone(),
+            unit_id,
+            from_custodian: from.clone(),
+            to_custodian: to.clone(),
+            from_kind: CustodianKind::Bank,
+            to_kind: CustodianKind::Hospital,
+            initiated_at: current_time,
+            transfer_deadline: current_time,
+            ledger_sequence: env.ledger().sequence(),
+            status: CustodyStatus::Confirmed,
+            doc_hash: None,
+            note: None,
+            location_hash: None,
+            closing_location_hash: None,
+        };
+
+        let mut custody_events: Map<String, CustodyEvent> = env
+            .storage()
+            .persistent()
+            .get(&CUSTODY_EVENTS)
+            .unwrap_or(Map::new(&env));
         custody_events.set(event_id.clone(), custody_event.clone());
         env.storage()
             .persistent()
             .set(&CUSTODY_EVENTS, &custody_events);
+        record_custody_transition(&env, None, CustodyStatus::Confirmed);
 
-        // Clear UnitCustodyIndex — transfer is no longer pending
+        let unit_events_key = DataKey::UnitCustodyEvents(unit_id);
+        let mut unit_event_ids: Vec<String> = env
+            .storage()
+            .persistent()
+            .get(&unit_events_key)
+            .unwrap_or(Vec::new(&env));
+        unit_event_ids.push_back(event_id.clone());
         env.storage()
             .persistent()
-            .remove(&DataKey::UnitCustodyIndex(unit_id));
+            .set(&unit_events_key, &unit_event_ids);
+        bump_persistent(&env, &unit_events_key);
+        bump_persistent(&env, &CUSTODY_EVENTS);
 
-        // Append to custody trail (paginated)
         append_to_custody_trail(&env, unit_id, event_id.clone());
 
-        // Update unit
+        let old_status = unit.status;
         unit.status = BloodStatus::Delivered;
+        unit.transfer_timestamp = Some(current_time);
         unit.delivery_timestamp = Some(current_time);
 
-        units.set(unit_id, unit.clone());
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
+        save_unit(&env, &unit);
 
-        // Maintain status index
         reindex_status(&env, unit_id, old_status, BloodStatus::Delivered);
+        deindex_bank_unit(&env, &unit.bank_id, unit_id);
+        resolve_reservation(&env, unit_id, &from, current_time);
 
-        // Record status change
         record_status_change(
             &env,
             unit_id,
             old_status,
             BloodStatus::Delivered,
-            hospital.clone(),
+            to.clone(),
         );
 
-        // Emit event
-        env.events().publish(
+        publish_event(
+            &env,
             (
                 symbol_short!("custody"),
-                symbol_short!("confirm"),
+                symbol_short!("handoff"),
                 symbol_short!("v1"),
             ),
             custody_event,
         );
 
-        Ok(())
+        Ok(event_id)
     }
 
-    /// Cancel an in-transit transfer using the derived event_id.
+    /// Confirm blood delivery
     ///
-    /// Transfer is cancellable at/after `initiated_at + TRANSFER_EXPIRY_SECONDS`.
+    /// This is kept for backwards-compatibility and delegates to `confirm_transfer`.
+    /// Note: This function looks up the pending custody event by unit_id via the
+    /// UnitCustodyIndex — O(1) instead of an O(n) scan over all custody events.
+    pub fn confirm_delivery(
+        env: Env,
+        hospital: Address,
+        unit_id: u64,
+        doc_hash: Option<BytesN<32>>,
+        note: Option<Symbol>,
+        location_hash: Option<BytesN<32>>,
+    ) -> Result<DeliveryOutcome, Error> {
+        // Look up the pending event_id via the per-unit custody index (O(1))
+        let index_key = DataKey::UnitCustodyIndex(unit_id);
+        let event_id: String = env
+            .storage()
+            .persistent()
+            .get(&index_key)
+            .ok_or(Error::UnitNotFound)?;
+
+        Self::confirm_transfer(env, hospital, event_id, doc_hash, note, location_hash)
+    }
+
+    /// Confirm an in-transit transfer using the derived event_id.
+    ///
+    /// Must be confirmed strictly before the event's `transfer_deadline`.
+    /// Callers must compute the same hash (unit_id + from + to + ledger_sequence) to reference the transfer.
+    ///
+    /// A `doc_hash` or `note` supplied here replaces the sender's on the custody event;
+    /// `location_hash` is kept alongside the sender's as the closing location.
+    /// While dual authorization is on, the outgoing custodian must sign the same call.
+    ///
+    /// If the configured temperature contract reports a breach for the unit, the
+    /// handover still completes but the unit is quarantined at the hospital and
+    /// `DeliveredWithExcursion` is returned.
+    pub fn confirm_transfer(
+        env: Env,
+        hospital: Address,
+        event_id: String,
+        doc_hash: Option<BytesN<32>>,
+        note: Option<Symbol>,
+        location_hash: Option<BytesN<32>>,
+    ) -> Result<DeliveryOutcome, Error> {
+        // Validate event_id length
+        if event_id.len() > MAX_UNIT_ID_LENGTH {
+            return Err(Error::UnitIdTooLong);
+        }
+
+        // CUSTODIAN AUTHORIZATION: Verify caller is authenticated and authorized actor
+        hospital.require_auth();
+        ensure_not_paused(&env, PAUSE_CUSTODY)?;
+
+        // Verify hospital is registered and authorized
+        if !Self::is_hospital(env.clone(), hospital.clone()) {
+            return Err(Error::UnauthorizedHospital);
+        }
+
+        Self::complete_transfer(&env, &hospital, event_id, doc_hash, note, location_hash)
+    }
+
+    /// Confirm every pending transfer in `event_ids` (one delivery run).
+    ///
+    /// Each event goes through the same checks as `confirm_transfer`. The batch
+    /// is atomic: if any event fails — including a unit found expired in
+    /// transit — the error is returned and no confirmation in the batch is
+    /// recorded. `location_hash` is recorded on every event as its closing
+    /// location. Returns the delivery outcomes in input order.
+    pub fn confirm_transfer_batch(
+        env: Env,
+        hospital: Address,
+        event_ids: Vec<String>,
+        location_hash: Option<BytesN<32>>,
+    ) -> Result<Vec<DeliveryOutcome>, Error> {
+        if event_ids.is_empty() {
+            return Err(Error::InvalidQuantity);
+        }
+        if event_ids.len() > MAX_BATCH_SIZE {
+            return Err(Error::BatchSizeExceeded);
+        }
+        if event_ids
+            .iter()
+            .any(|event_id| event_id.len() > MAX_UNIT_ID_LENGTH)
+        {
+            return Err(Error::UnitIdTooLong);
+        }
+
+        hospital.require_auth();
+        ensure_not_paused(&env, PAUSE_CUSTODY)?;
+
+        if !Self::is_hospital(env.clone(), hospital.clone()) {
+            return Err(Error::UnauthorizedHospital);
+        }
+
+        let mut outcomes = Vec::new(&env);
+        for event_id in event_ids.iter() {
+            outcomes.push_back(Self::complete_transfer(
+                &env,
+                &hospital,
+                event_id,
+                None,
+                None,
+                location_hash.clone(),
+            )?);
+        }
+
+        Ok(outcomes)
+    }
+
+    /// Hand one pending transfer over to `hospital`; the caller has already been authorized.
+    fn complete_transfer(
+        env: &Env,
+        hospital: &Address,
+        event_id: String,
+        doc_hash: Option<BytesN<32>>,
+        note: Option<Symbol>,
+        location_hash: Option<BytesN<32>>,
+    ) -> Result<DeliveryOutcome, Error> {
+        // Get custody event
+        let mut custody_events: Map<String, CustodyEvent> = env
+            .storage()
+            .persistent()
+            .get(&CUSTODY_EVENTS)
+            .unwrap_or(Map::new(env));
+
+        let mut custody_event = custody_events
+            .get(event_id.clone())
+            .ok_or(Error::UnitNotFound)?;
+
+        // Verify the event's designated recipient is a registered hospital
+        if !Self::is_hospital(env.clone(), custody_event.to_custodian.clone()) {
+            return Err(Error::UnauthorizedHospital);
+        }
+
+        // INVARIANT: Only the designated recipient (to_custodian) can confirm the transfer
+        // This ensures units can only be received by the intended hospital
+        if custody_event.to_custodian != *hospital {
+            return Err(Error::Unauthorized);
+        }
+
+        // INVARIANT: Custody event must be in Pending status (not already confirmed/recovered)
+        if custody_event.status != CustodyStatus::Pending {
+            return Err(Error::InvalidStatus);
+        }
+
+        // DUAL AUTHORIZATION: the outgoing custodian co-signs the handover
+        if dual_auth_required(env) {
+            custody_event.from_custodian.require_auth();
+        }
+
+        let unit_id = custody_event.unit_id;
+
+        // Get blood unit
+        let mut unit = load_unit(env, unit_id).ok_or(Error::UnitNotFound)?;
+
+        // INVARIANT: Unit must be in InTransit status (transferred but not yet confirmed)
+        if unit.status != BloodStatus::InTransit {
+            return Err(Error::InvalidStatus);
+        }
+
+        let current_time = env.ledger().timestamp();
+
+        // EXPIRY ENFORCEMENT: Transfer window must not be past the event's deadline
+        // At/after boundary is considered expired to ensure clean cutoffs
+        if current_time >= custody_event.transfer_deadline {
+            return Err(Error::TransferExpired);
+        }
+
+        let old_status = unit.status;
+
+        // RECOVERY PATH: Check if blood unit expired during transit
+        // If unit expiration passed while in transit, mark as recovered with explicit event
+        if unit.expiration_date <= current_time {
+            unit.status = BloodStatus::Expired;
+            save_unit(env, &unit);
+
+            // Maintain status and bank indexes
+            reindex_status(env, unit_id, old_status, BloodStatus::Expired);
+            deindex_bank_unit(env, &unit.bank_id, unit_id);
+
+            // Update custody event to Recovered status to indicate recovery action
+            custody_event.status = CustodyStatus::Recovered;
+            custody_event.closing_location_hash = location_hash.clone();
+            custody_events.set(event_id.clone(), custody_event.clone());
+            env.storage()
+                .persistent()
+                .set(&CUSTODY_EVENTS, &custody_events);
+            record_custody_transition(env, Some(CustodyStatus::Pending), CustodyStatus::Recovered);
+
+            // Clear UnitCustodyIndex — transfer is no longer pending
+            env.storage()
+                .persistent()
+                .remove(&DataKey::UnitCustodyIndex(unit_id));
+            env.storage()
+                .persistent()
+                .remove(&DataKey::UnitRider(unit_id));
+
+            record_status_change(
+                env,
+                unit_id,
+                old_status,
+                BloodStatus::Expired,
+                hospital.clone(),
+            );
+
+            // Emit explicit recovery event for backend projection consistency
+            publish_event(
+                env,
+                (
+                    symbol_short!("custody"),
+                    symbol_short!("recover"),
+                    symbol_short!("v1"),
+                ),
+                TransferRecoveryEvent {
+                    custody_event_id: event_id,
+                    unit_id,
+                    actor: hospital.clone(),
+                    recovery_reason: 0, // 0 = unit_expired_during_transit
+                    previous_custody_status: CustodyStatus::Pending,
+                    new_custody_status: CustodyStatus::Recovered,
+                    unit_status_after_recovery: BloodStatus::Expired,
+                    recovery_timestamp: current_time,
+                    location_hash,
+                },
+            );
+
+            return Err(Error::UnitExpired);
+        }
+
+        // COMPATIBILITY: while a crossmatch window is configured, the receiving
+        // hospital must hold a recent compatible crossmatch for this unit
+        if !crossmatch_cleared(env, unit_id, hospital, current_time) {
+            return Err(Error::CrossmatchRequired);
+        }
+
+        // Update custody event status
+        custody_event.status = CustodyStatus::Confirmed;
+        attach_handoff_reference(&mut custody_event, doc_hash, note);
+        custody_event.closing_location_hash = location_hash;
+        custody_events.set(event_id.clone(), custody_event.clone());
+        env.storage()
+            .persistent()
+            .set(&CUSTODY_EVENTS, &custody_events);
+        record_custody_transition(env, Some(CustodyStatus::Pending), CustodyStatus::Confirmed);
+
+        // Clear UnitCustodyIndex — transfer is no longer pending
+        env.storage()
+            .persistent()
+            .remove(&DataKey::UnitCustodyIndex(unit_id));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::UnitRider(unit_id));
+
+        // Append to custody trail (paginated)
+        append_to_custody_trail(env, unit_id, event_id.clone());
+
+        // COLD CHAIN: a unit breached in transit is handed over but not released
+        let (outcome, new_status) = if temperature::has_active_breach(env, unit_id) {
+            (
+                DeliveryOutcome::DeliveredWithExcursion,
+                BloodStatus::Quarantined,
+            )
+        } else {
+            (DeliveryOutcome::Delivered, BloodStatus::Delivered)
+        };
+
+        // Update unit
+        unit.status = new_status;
+        unit.delivery_timestamp = Some(current_time);
+
+        save_unit(env, &unit);
+
+        // Maintain status and bank indexes; a quarantined unit stays on its bank's
+        // books until the quarantine is finalized
+        reindex_status(env, unit_id, old_status, new_status);
+        if new_status == BloodStatus::Delivered {
+            deindex_bank_unit(env, &unit.bank_id, unit_id);
+        }
+        resolve_reservation(env, unit_id, &unit.bank_id, current_time);
+
+        // Record status change
+        record_status_change(env, unit_id, old_status, new_status, hospital.clone());
+
+        // Emit event
+        publish_event(
+            env,
+            (
+                symbol_short!("custody"),
+                symbol_short!("confirm"),
+                symbol_short!("v1"),
+            ),
+            custody_event,
+        );
+
+        if outcome == DeliveryOutcome::DeliveredWithExcursion {
+            publish_event(
+                env,
+                (symbol_short!("quar"), symbol_short!("place")),
+                QuarantineLifecycleEvent {
+                    blood_unit_id: unit_id,
+                    old_status,
+                    new_status,
+                    actor: hospital.clone(),
+                    reason: QuarantineReason::TemperatureBreach,
+                    disposition_code: 0,
+                    timestamp: current_time,
+                },
+            );
+        }
+
+        Ok(outcome)
+    }
+
+    /// Cancel an in-transit transfer using the derived event_id.
+    ///
+    /// Transfer is cancellable at/after the event's `transfer_deadline`.
+    /// The unit returns to `Reserved`, or to `Expired` if it expired in transit.
     /// Callers must compute the same hash (unit_id + from + to + ledger_sequence) to reference the transfer.
-    pub fn cancel_transfer(env: Env, bank_id: Address, event_id: String) -> Result<(), Error> {
+    /// `location_hash` records where the unit was when the transfer was called off.
+    pub fn cancel_transfer(
+        env: Env,
+        bank_id: Address,
+        event_id: String,
+        location_hash: Option<BytesN<32>>,
+    ) -> Result<(), Error> {
         // Validate event_id length
         if event_id.len() > MAX_UNIT_ID_LENGTH {
             return Err(Error::UnitIdTooLong);
@@ -1656,63 +3223,37 @@ impl HealthChainContract {
 
         let unit_id = custody_event.unit_id;
 
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
-
-        let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+        let mut unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
 
         // RECOVERY PATH: Unit must be in transit to be cancelled/recovered
         if unit.status != BloodStatus::InTransit {
             return Err(Error::InvalidStatus);
         }
 
-        let initiated_at = custody_event.initiated_at;
         let current_time = env.ledger().timestamp();
 
-        // EXPIRY ENFORCEMENT: Transfer must be expired (at least 30 minutes old) to be cancelled
+        // EXPIRY ENFORCEMENT: Transfer must be past its deadline to be cancelled
         // This prevents cancellation within the confirmation window and ensures fair delivery times
-        if current_time < initiated_at.saturating_add(TRANSFER_EXPIRY_SECONDS) {
+        if current_time < custody_event.transfer_deadline {
             return Err(Error::TransferNotExpired);
         }
 
         // RECOVERY ACTION: Update custody event status to Recovered
         custody_event.status = CustodyStatus::Recovered;
+        custody_event.closing_location_hash = location_hash.clone();
         custody_events.set(event_id.clone(), custody_event.clone());
         env.storage()
             .persistent()
             .set(&CUSTODY_EVENTS, &custody_events);
-
-        // Clear UnitCustodyIndex — transfer is no longer pending
-        env.storage()
-            .persistent()
-            .remove(&DataKey::UnitCustodyIndex(unit_id));
-
-        let old_status = unit.status;
+        record_custody_transition(&env, Some(CustodyStatus::Pending), CustodyStatus::Recovered);
 
         // Revert back to Reserved state; keep recipient_hospital + allocation_timestamp.
-        unit.status = BloodStatus::Reserved;
-        unit.transfer_timestamp = None;
-
-        units.set(unit_id, unit.clone());
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
-
-        // Maintain status index
-        reindex_status(&env, unit_id, old_status, BloodStatus::Reserved);
-
-        // Record status change
-        record_status_change(
-            &env,
-            unit_id,
-            old_status,
-            BloodStatus::Reserved,
-            bank_id.clone(),
-        );
+        let unit_status_after_recovery =
+            recover_abandoned_transfer(&env, &mut unit, bank_id.clone(), current_time);
 
         // Emit explicit recovery event for transfer cancellation/rollback
-        env.events().publish(
+        publish_event(
+            &env,
             (
                 symbol_short!("custody"),
                 symbol_short!("recover"),
@@ -1725,13 +3266,15 @@ impl HealthChainContract {
                 recovery_reason: 1, // 1 = transfer_cancelled (rollback after expiry)
                 previous_custody_status: CustodyStatus::Pending,
                 new_custody_status: CustodyStatus::Recovered,
-                unit_status_after_recovery: BloodStatus::Reserved,
+                unit_status_after_recovery,
                 recovery_timestamp: current_time,
+                location_hash,
             },
         );
 
         // Emit legacy event for backward compatibility
-        env.events().publish(
+        publish_event(
+            &env,
             (
                 symbol_short!("blood"),
                 symbol_short!("tr_cancel"),
@@ -1747,31 +3290,209 @@ impl HealthChainContract {
         Ok(())
     }
 
-    /// Withdraw blood unit (mark as used/discarded)
-    pub fn withdraw_blood(
-        env: Env,
-        caller: Address,
-        unit_id: u64,
-        reason: WithdrawalReason,
-    ) -> Result<(), Error> {
-        caller.require_auth();
-
-        // Verify caller is authorized (blood bank or hospital)
-        let is_bank = Self::is_blood_bank(env.clone(), caller.clone());
-        let is_hosp = Self::is_hospital(env.clone(), caller.clone());
-
-        if !is_bank && !is_hosp {
-            return Err(Error::Unauthorized);
+    /// Expire a pending transfer whose `transfer_deadline` has passed.
+    ///
+    /// Callable by anyone so a transfer that is never confirmed or cancelled
+    /// cannot strand the unit in transit. The custody event becomes `Recovered`
+    /// and the unit returns to `Reserved`, exactly as with `cancel_transfer`.
+    /// A hop sent by a rider instead leaves the unit in transit with that rider.
+    /// Either way a unit that expired in transit is marked `Expired`.
+    pub fn expire_pending_transfer(env: Env, event_id: String) -> Result<(), Error> {
+        if event_id.len() > MAX_UNIT_ID_LENGTH {
+            return Err(Error::UnitIdTooLong);
         }
 
-        // Get blood unit
-        let mut units: Map<u64, BloodUnit> = env
+        let mut custody_events: Map<String, CustodyEvent> = env
             .storage()
             .persistent()
-            .get(&BLOOD_UNITS)
+            .get(&CUSTODY_EVENTS)
+            .unwrap_or(Map::new(&env));
+        let mut custody_event = custody_events
+            .get(event_id.clone())
+            .ok_or(Error::UnitNotFound)?;
+        if custody_event.status != CustodyStatus::Pending {
+            return Err(Error::InvalidStatus);
+        }
+
+        let current_time = env.ledger().timestamp();
+        if current_time < custody_event.transfer_deadline {
+            return Err(Error::TransferNotExpired);
+        }
+
+        let unit_id = custody_event.unit_id;
+        let mut unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
+        if unit.status != BloodStatus::InTransit {
+            return Err(Error::InvalidStatus);
+        }
+
+        custody_event.status = CustodyStatus::Recovered;
+        custody_events.set(event_id.clone(), custody_event.clone());
+        env.storage()
+            .persistent()
+            .set(&CUSTODY_EVENTS, &custody_events);
+        record_custody_transition(&env, Some(CustodyStatus::Pending), CustodyStatus::Recovered);
+
+        // Nobody authenticated, so the contract itself is recorded as the actor
+        let actor = env.current_contract_address();
+        let unit_status_after_recovery = if custody_event.from_kind == CustodianKind::Rider
+            && unit.expiration_date > current_time
+        {
+            // A later hop never left the sending rider, who keeps the unit in transit
+            env.storage()
+                .persistent()
+                .remove(&DataKey::UnitCustodyIndex(unit_id));
+            BloodStatus::InTransit
+        } else {
+            recover_abandoned_transfer(&env, &mut unit, actor.clone(), current_time)
+        };
+
+        publish_event(
+            &env,
+            (
+                symbol_short!("custody"),
+                symbol_short!("recover"),
+                symbol_short!("v1"),
+            ),
+            TransferRecoveryEvent {
+                custody_event_id: event_id,
+                unit_id,
+                actor,
+                recovery_reason: 3, // 3 = transfer_deadline_passed
+                previous_custody_status: CustodyStatus::Pending,
+                new_custody_status: CustodyStatus::Recovered,
+                unit_status_after_recovery,
+                recovery_timestamp: current_time,
+                location_hash: None,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Refuse a unit on arrival (leaking bag, label mismatch, ...).
+    ///
+    /// Only the designated receiving hospital may reject a pending transfer. The
+    /// custody event becomes `Rejected`, custody goes back to the sender and the
+    /// unit moves to `ReturnPending`, from where its bank can quarantine or
+    /// discard it. The emitted event carries the unit's payment, if any, so the
+    /// payer can be refunded.
+    pub fn reject_transfer(
+        env: Env,
+        hospital: Address,
+        event_id: String,
+        reason: RejectionReason,
+    ) -> Result<(), Error> {
+        if event_id.len() > MAX_UNIT_ID_LENGTH {
+            return Err(Error::UnitIdTooLong);
+        }
+
+        hospital.require_auth();
+
+        if !Self::is_hospital(env.clone(), hospital.clone()) {
+            return Err(Error::UnauthorizedHospital);
+        }
+
+        let mut custody_events: Map<String, CustodyEvent> = env
+            .storage()
+            .persistent()
+            .get(&CUSTODY_EVENTS)
             .unwrap_or(Map::new(&env));
+        let mut custody_event = custody_events
+            .get(event_id.clone())
+            .ok_or(Error::UnitNotFound)?;
+
+        if custody_event.to_custodian != hospital {
+            return Err(Error::Unauthorized);
+        }
+        if custody_event.status != CustodyStatus::Pending {
+            return Err(Error::InvalidStatus);
+        }
+
+        let unit_id = custody_event.unit_id;
+        let mut unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
+        if unit.status != BloodStatus::InTransit {
+            return Err(Error::InvalidStatus);
+        }
+
+        let current_time = env.ledger().timestamp();
+
+        custody_event.status = CustodyStatus::Rejected;
+        custody_events.set(event_id.clone(), custody_event.clone());
+        env.storage()
+            .persistent()
+            .set(&CUSTODY_EVENTS, &custody_events);
+        record_custody_transition(&env, Some(CustodyStatus::Pending), CustodyStatus::Rejected);
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::UnitCustodyIndex(unit_id));
+        // A rider who sent the unit keeps it; otherwise it goes straight back to the bank
+        if custody_event.from_kind != CustodianKind::Rider {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::UnitRider(unit_id));
+        }
+
+        let old_status = unit.status;
+        unit.status = BloodStatus::ReturnPending;
+        unit.recipient_hospital = None;
+        unit.allocation_timestamp = None;
+        unit.transfer_timestamp = None;
+        save_unit(&env, &unit);
+
+        reindex_status(&env, unit_id, old_status, BloodStatus::ReturnPending);
+        resolve_reservation(&env, unit_id, &unit.bank_id, current_time);
+        record_status_change(
+            &env,
+            unit_id,
+            old_status,
+            BloodStatus::ReturnPending,
+            hospital.clone(),
+        );
+
+        publish_event(
+            &env,
+            (
+                symbol_short!("custody"),
+                symbol_short!("reject"),
+                symbol_short!("v1"),
+            ),
+            TransferRejectedEvent {
+                custody_event_id: event_id,
+                unit_id,
+                hospital,
+                returned_to: custody_event.from_custodian,
+                reason,
+                payment_id: env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::UnitPayment(unit_id)),
+                rejected_at: current_time,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw blood unit (mark as used/discarded)
+    pub fn withdraw_blood(
+        env: Env,
+        caller: Address,
+        unit_id: u64,
+        reason: WithdrawalReason,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+
+        // Verify caller is authorized (blood bank or hospital)
+        let is_bank = Self::is_blood_bank(env.clone(), caller.clone());
+        let is_hosp = Self::is_hospital(env.clone(), caller.clone());
+
+        if !is_bank && !is_hosp {
+            return Err(Error::Unauthorized);
+        }
 
-        let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+        // Get blood unit
+        let mut unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
 
         let old_status = unit.status;
         let current_time = env.ledger().timestamp();
@@ -1779,8 +3500,7 @@ impl HealthChainContract {
         // Update unit
         unit.status = BloodStatus::Discarded;
 
-        units.set(unit_id, unit.clone());
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
+        save_unit(&env, &unit);
 
         // Maintain status index
         reindex_status(&env, unit_id, old_status, BloodStatus::Discarded);
@@ -1795,7 +3515,8 @@ impl HealthChainContract {
         );
 
         // Emit event
-        env.events().publish(
+        publish_event(
+            &env,
             (
                 symbol_short!("blood"),
                 symbol_short!("withdraw"),
@@ -1807,6 +3528,90 @@ impl HealthChainContract {
         Ok(())
     }
 
+    /// Discard a unit with a typed reason.
+    ///
+    /// The registering bank may discard its units while they are Available,
+    /// Reserved, Quarantined, Expired, Recalled or ReturnPending; the recipient hospital may
+    /// discard a unit delivered to it. In-transit units must have their
+    /// transfer cancelled first. `DiscardReason::Expired` requires the unit to
+    /// be past its expiration date and `DiscardReason::Recalled` requires a
+    /// Recalled unit; otherwise `InvalidStatus` is returned.
+    pub fn discard_unit(
+        env: Env,
+        caller: Address,
+        unit_id: u64,
+        reason: DiscardReason,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+
+        let mut unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
+        let old_status = unit.status;
+        let current_time = env.ledger().timestamp();
+
+        let permitted = match old_status {
+            BloodStatus::Available
+            | BloodStatus::Reserved
+            | BloodStatus::Quarantined
+            | BloodStatus::Expired
+            | BloodStatus::Recalled
+            | BloodStatus::ReturnPending => caller == unit.bank_id,
+            BloodStatus::Delivered => unit.recipient_hospital.as_ref() == Some(&caller),
+            BloodStatus::InTransit
+            | BloodStatus::Discarded
+            | BloodStatus::Split
+            | BloodStatus::Pooled => {
+                return Err(Error::InvalidStatus);
+            }
+        };
+        if !permitted {
+            return Err(Error::Unauthorized);
+        }
+
+        let reason_matches = match reason {
+            DiscardReason::Expired => {
+                old_status == BloodStatus::Expired || unit.expiration_date <= current_time
+            }
+            DiscardReason::Recalled => old_status == BloodStatus::Recalled,
+            _ => true,
+        };
+        if !reason_matches {
+            return Err(Error::InvalidStatus);
+        }
+
+        unit.status = BloodStatus::Discarded;
+        save_unit(&env, &unit);
+
+        reindex_status(&env, unit_id, old_status, BloodStatus::Discarded);
+        record_status_change(
+            &env,
+            unit_id,
+            old_status,
+            BloodStatus::Discarded,
+            caller.clone(),
+        );
+
+        publish_event(
+            &env,
+            (
+                symbol_short!("blood"),
+                symbol_short!("discard"),
+                symbol_short!("v1"),
+            ),
+            DiscardEvent {
+                blood_unit_id: unit_id,
+                bank_id: unit.bank_id,
+                blood_type: unit.blood_type,
+                quantity: unit.quantity,
+                old_status,
+                reason,
+                actor: caller,
+                timestamp: current_time,
+            },
+        );
+
+        Ok(())
+    }
+
     /// Place a blood unit into explicit quarantine state.
     pub fn quarantine_blood(
         env: Env,
@@ -1822,16 +3627,16 @@ impl HealthChainContract {
             return Err(Error::Unauthorized);
         }
 
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
-
-        let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+        let mut unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
         let old_status = unit.status;
 
-        if old_status == BloodStatus::Quarantined {
+        if matches!(
+            old_status,
+            BloodStatus::Quarantined
+                | BloodStatus::Recalled
+                | BloodStatus::Split
+                | BloodStatus::Pooled
+        ) {
             return Err(Error::InvalidStatus);
         }
 
@@ -1841,8 +3646,7 @@ impl HealthChainContract {
         }
 
         unit.status = BloodStatus::Quarantined;
-        units.set(unit_id, unit.clone());
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
+        save_unit(&env, &unit);
 
         // Maintain status index
         reindex_status(&env, unit_id, old_status, BloodStatus::Quarantined);
@@ -1865,7 +3669,8 @@ impl HealthChainContract {
             timestamp: current_time,
         };
 
-        env.events().publish(
+        publish_event(
+            &env,
             (symbol_short!("quar"), symbol_short!("place")),
             quarantine_event,
         );
@@ -1889,13 +3694,7 @@ impl HealthChainContract {
             return Err(Error::Unauthorized);
         }
 
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
-
-        let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+        let mut unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
         let old_status = unit.status;
         if old_status != BloodStatus::Quarantined {
             return Err(Error::InvalidStatus);
@@ -1907,8 +3706,7 @@ impl HealthChainContract {
         };
 
         unit.status = new_status;
-        units.set(unit_id, unit.clone());
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
+        save_unit(&env, &unit);
 
         // Maintain status index
         reindex_status(&env, unit_id, old_status, new_status);
@@ -1928,7 +3726,8 @@ impl HealthChainContract {
             timestamp: env.ledger().timestamp(),
         };
 
-        env.events().publish(
+        publish_event(
+            &env,
             (symbol_short!("quar"), symbol_short!("final")),
             quarantine_event,
         );
@@ -1936,515 +3735,396 @@ impl HealthChainContract {
         Ok(())
     }
 
-    // ── READ ──────────────────────────────────────────────────────────────────
-
-    /// Get blood unit by ID.
-    ///
-    /// Delegates to [`registry_read::get_unit`].
-    pub fn get_blood_unit(env: Env, unit_id: u64) -> Result<BloodUnit, Error> {
-        registry_read::get_unit(&env, unit_id)
-    }
-
-    /// Get blood status.
+    /// Record a lab screening result against a blood unit (registered labs only).
     ///
-    /// Delegates to [`registry_read::get_unit`].
-    pub fn get_blood_status(env: Env, unit_id: u64) -> Result<BloodStatus, Error> {
-        let unit = registry_read::get_unit(&env, unit_id)?;
-        Ok(unit.status)
-    }
+    /// A later result for the same `test_type` replaces the earlier one, so a
+    /// retest can clear a unit. While screening is required, `allocate_blood`
+    /// rejects units without a passing result for every test in [`REQUIRED_TESTS`].
+    pub fn record_test_result(
+        env: Env,
+        lab: Address,
+        unit_id: u64,
+        test_type: TestType,
+        result_hash: BytesN<32>,
+        passed: bool,
+    ) -> Result<(), Error> {
+        lab.require_auth();
 
-    /// Check whether a blood unit's expiration date has passed.
-    ///
-    /// Delegates to [`registry_read::is_expired`].
-    pub fn is_expired(env: Env, unit_id: u64) -> Result<bool, Error> {
-        registry_read::is_expired(&env, unit_id)
-    }
+        if !Self::is_lab(env.clone(), lab.clone()) {
+            return Err(Error::Unauthorized);
+        }
 
-    /// Return all blood units donated by the given donor.
-    ///
-    /// Delegates to [`registry_read::get_units_by_donor`].
-    pub fn get_units_by_donor(env: Env, donor_id: Symbol) -> Vec<BloodUnit> {
-        registry_read::get_units_by_donor(&env, donor_id)
-    }
+        let unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
+        if storage_lifecycle::is_terminal_status(unit.status) {
+            return Err(Error::InvalidStatus);
+        }
 
-    /// Query blood units by status
-    pub fn query_by_status(env: Env, status: BloodStatus, max_results: u32) -> Vec<BloodUnit> {
-        // Use the StatusUnits secondary index — O(k) where k = units with this status.
-        let key = DataKey::StatusUnits(status);
-        let ids: Vec<u64> = env
+        let key = DataKey::UnitTests(unit_id);
+        let results: Vec<TestResult> = env
             .storage()
             .persistent()
             .get(&key)
             .unwrap_or(Vec::new(&env));
-
-        let units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
-
-        let mut results = vec![&env];
-        let limit = if max_results == 0 { u32::MAX } else { max_results };
-
-        for id in ids.iter() {
-            if results.len() >= limit {
-                break;
-            }
-            if let Some(unit) = units.get(id) {
-                results.push_back(unit);
+        let mut updated = Vec::new(&env);
+        for result in results.iter() {
+            if result.test_type != test_type {
+                updated.push_back(result);
             }
         }
+        updated.push_back(TestResult {
+            test_type,
+            result_hash,
+            passed,
+            lab: lab.clone(),
+            recorded_at: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&key, &updated);
+        bump_persistent(&env, &key);
 
-        results
+        publish_event(
+            &env,
+            (symbol_short!("lab"), symbol_short!("result")),
+            (unit_id, test_type, passed, lab),
+        );
+
+        Ok(())
     }
 
-    /// Query blood units by hospital
-    pub fn query_by_hospital(env: Env, hospital: Address, max_results: u32) -> Vec<BloodUnit> {
-        // Use the HospitalUnits secondary index — O(k) where k = units for this hospital.
-        let key = DataKey::HospitalUnits(hospital);
-        let ids: Vec<u64> = env
-            .storage()
+    /// Latest recorded lab result per test type for a unit.
+    pub fn get_test_results(env: Env, unit_id: u64) -> Vec<TestResult> {
+        env.storage()
             .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(&env));
+            .get(&DataKey::UnitTests(unit_id))
+            .unwrap_or(Vec::new(&env))
+    }
 
-        let units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
+    /// Record the outcome of crossmatching a unit against a patient sample.
+    ///
+    /// Only the hospital the unit is allocated to may record, and only while
+    /// the unit is Reserved or InTransit. `timestamp` is when the sample was
+    /// tested and cannot lie in the future. A later crossmatch replaces the
+    /// earlier one.
+    pub fn record_crossmatch(
+        env: Env,
+        hospital: Address,
+        unit_id: u64,
+        patient_hash: BytesN<32>,
+        compatible: bool,
+        timestamp: u64,
+    ) -> Result<(), Error> {
+        hospital.require_auth();
 
-        let mut results = vec![&env];
-        let limit = if max_results == 0 { u32::MAX } else { max_results };
+        if !Self::is_hospital(env.clone(), hospital.clone()) {
+            return Err(Error::UnauthorizedHospital);
+        }
 
-        for id in ids.iter() {
-            if results.len() >= limit {
-                break;
-            }
-            if let Some(unit) = units.get(id) {
-                results.push_back(unit);
-            }
+        let unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
+        if unit.recipient_hospital.as_ref() != Some(&hospital) {
+            return Err(Error::Unauthorized);
+        }
+        if unit.status != BloodStatus::Reserved && unit.status != BloodStatus::InTransit {
+            return Err(Error::InvalidStatus);
         }
 
-        results
-    }
-}
+        let current_time = env.ledger().timestamp();
+        if timestamp > current_time {
+            return Err(Error::InvalidTimestamp);
+        }
 
-// ── INDEX HELPERS (Internal) ──
+        let record = CrossmatchRecord {
+            hospital,
+            patient_hash,
+            compatible,
+            tested_at: timestamp,
+            recorded_at: current_time,
+        };
+        let key = DataKey::Crossmatch(unit_id);
+        env.storage().persistent().set(&key, &record);
+        bump_persistent(&env, &key);
 
-/// Append `unit_id` to the BankUnits index for `bank_id`.
-pub(crate) fn index_bank_unit(env: &Env, bank_id: &Address, unit_id: u64) {
-    let key = DataKey::BankUnits(bank_id.clone());
-    let mut ids: Vec<u64> = env
-        .storage()
-        .persistent()
-        .get(&key)
-        .unwrap_or(Vec::new(env));
-    ids.push_back(unit_id);
-    env.storage().persistent().set(&key, &ids);
-}
+        publish_event(
+            &env,
+            (symbol_short!("xmatch"), symbol_short!("record")),
+            (unit_id, record),
+        );
 
-/// Append `unit_id` to the HospitalUnits index for `hospital_id`.
-/// Call when a unit is allocated to a hospital.
-pub(crate) fn index_hospital_unit(env: &Env, hospital_id: &Address, unit_id: u64) {
-    let key = DataKey::HospitalUnits(hospital_id.clone());
-    let mut ids: Vec<u64> = env
-        .storage()
-        .persistent()
-        .get(&key)
-        .unwrap_or(Vec::new(env));
-    ids.push_back(unit_id);
-    env.storage().persistent().set(&key, &ids);
-}
+        Ok(())
+    }
 
-/// Remove `unit_id` from the HospitalUnits index for `hospital_id`.
-/// Call when an allocation is cancelled and the unit returns to inventory.
-pub(crate) fn deindex_hospital_unit(env: &Env, hospital_id: &Address, unit_id: u64) {
-    let key = DataKey::HospitalUnits(hospital_id.clone());
-    let ids: Vec<u64> = env
-        .storage()
-        .persistent()
-        .get(&key)
-        .unwrap_or(Vec::new(env));
-    let mut filtered = Vec::new(env);
-    for id in ids.iter() {
-        if id != unit_id {
-            filtered.push_back(id);
-        }
+    /// Latest crossmatch recorded for a unit, if any.
+    pub fn get_crossmatch(env: Env, unit_id: u64) -> Option<CrossmatchRecord> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Crossmatch(unit_id))
     }
-    env.storage().persistent().set(&key, &filtered);
-}
 
-/// Append `unit_id` to the DonorUnits index for `(bank_id, donor_id)` and the
-/// global sentinel index `(ZERO_ADDR, donor_id)` used by cross-bank donor queries.
-pub(crate) fn index_donor_unit(env: &Env, bank_id: &Address, donor_id: &Symbol, unit_id: u64) {
-    // Per-bank index
-    let key = DataKey::DonorUnits(bank_id.clone(), donor_id.clone());
-    let mut ids: Vec<u64> = env
-        .storage()
-        .persistent()
-        .get(&key)
-        .unwrap_or(Vec::new(env));
-    ids.push_back(unit_id);
-    env.storage().persistent().set(&key, &ids);
+    /// Append a processing step (irradiation, leukoreduction, washing) to a
+    /// unit's log.
+    ///
+    /// Only the owning bank may record, and only while it still holds the unit
+    /// (Available, Reserved or Quarantined). `performed_at` must fall between
+    /// registration and now. Each step can be recorded once per unit.
+    pub fn add_processing_step(
+        env: Env,
+        bank_id: Address,
+        unit_id: u64,
+        step: ProcessingType,
+        performed_at: u64,
+    ) -> Result<(), Error> {
+        bank_id.require_auth();
 
-    // Global cross-bank index (sentinel zero-address)
-    let sentinel = Address::from_contract_id(env, &soroban_sdk::BytesN::from_array(env, &[0u8; 32]));
-    let global_key = DataKey::DonorUnits(sentinel, donor_id.clone());
-    let mut global_ids: Vec<u64> = env
-        .storage()
-        .persistent()
-        .get(&global_key)
-        .unwrap_or(Vec::new(env));
-    global_ids.push_back(unit_id);
-    env.storage().persistent().set(&global_key, &global_ids);
-}
+        if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
+            return Err(Error::Unauthorized);
+        }
 
-/// Move `unit_id` from the `old_status` bucket to the `new_status` bucket.
-/// No-op when `old_status == new_status`.
-pub(crate) fn reindex_status(env: &Env, unit_id: u64, old_status: BloodStatus, new_status: BloodStatus) {
-    if old_status == new_status {
-        return;
-    }
-    // Remove from old bucket
-    let old_key = DataKey::StatusUnits(old_status);
-    let mut old_ids: Vec<u64> = env
-        .storage()
-        .persistent()
-        .get(&old_key)
-        .unwrap_or(Vec::new(env));
-    let mut filtered = Vec::new(env);
-    for id in old_ids.iter() {
-        if id != unit_id {
-            filtered.push_back(id);
+        let unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
+        if unit.bank_id != bank_id {
+            return Err(Error::Unauthorized);
+        }
+        match unit.status {
+            BloodStatus::Available | BloodStatus::Reserved | BloodStatus::Quarantined => {}
+            _ => return Err(Error::InvalidStatus),
         }
-    }
-    env.storage().persistent().set(&old_key, &filtered);
 
-    // Add to new bucket
-    let new_key = DataKey::StatusUnits(new_status);
-    let mut new_ids: Vec<u64> = env
-        .storage()
-        .persistent()
-        .get(&new_key)
-        .unwrap_or(Vec::new(env));
-    new_ids.push_back(unit_id);
-    env.storage().persistent().set(&new_key, &new_ids);
-}
+        let current_time = env.ledger().timestamp();
+        if performed_at > current_time || performed_at < unit.registration_timestamp {
+            return Err(Error::InvalidTimestamp);
+        }
 
-// ── SHARED HELPERS (Internal) ──
+        let key = DataKey::UnitProcessing(unit_id);
+        let mut steps: Vec<ProcessingStep> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        if steps.iter().any(|s| s.step == step) {
+            return Err(Error::DuplicateRequest);
+        }
+        steps.push_back(ProcessingStep {
+            step,
+            performed_by: bank_id,
+            performed_at,
+            recorded_at: current_time,
+        });
+        env.storage().persistent().set(&key, &steps);
+        bump_persistent(&env, &key);
 
-pub(crate) fn get_next_id(env: &Env) -> u64 {
-    let id: u64 = env.storage().persistent().get(&NEXT_ID).unwrap_or(1);
-    env.storage().persistent().set(&NEXT_ID, &(id + 1));
-    id
-}
+        publish_event(
+            &env,
+            (symbol_short!("blood"), symbol_short!("process")),
+            (unit_id, step, performed_at),
+        );
 
-pub(crate) fn get_next_request_id(env: &Env) -> u64 {
-    let id: u64 = env
-        .storage()
-        .persistent()
-        .get(&NEXT_REQUEST_ID)
-        .unwrap_or(1);
-    env.storage().persistent().set(&NEXT_REQUEST_ID, &(id + 1));
-    id
-}
+        Ok(())
+    }
 
-pub(crate) fn record_status_change(
-    env: &Env,
-    unit_id: u64,
-    old_status: BloodStatus,
-    new_status: BloodStatus,
-    actor: Address,
-) {
-    let history_key = (HISTORY, unit_id);
-    let mut history: Vec<StatusChangeEvent> = env
-        .storage()
-        .persistent()
-        .get(&history_key)
-        .unwrap_or(Vec::new(env));
+    /// Whether `step` has been recorded for the unit.
+    pub fn has_processing(env: Env, unit_id: u64, step: ProcessingType) -> bool {
+        Self::get_processing_steps(env, unit_id)
+            .iter()
+            .any(|s| s.step == step)
+    }
 
-    let event = StatusChangeEvent {
-        blood_unit_id: unit_id,
-        old_status,
-        new_status,
-        actor,
-        timestamp: env.ledger().timestamp(),
-    };
+    /// Full processing log for a unit, in the order the steps were recorded.
+    pub fn get_processing_steps(env: Env, unit_id: u64) -> Vec<ProcessingStep> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::UnitProcessing(unit_id))
+            .unwrap_or(Vec::new(&env))
+    }
 
-    history.push_back(event.clone());
-    env.storage().persistent().set(&history_key, &history);
+    /// Open a collection lot for one donation drive.
+    pub fn create_lot(env: Env, bank_id: Address, lot_id: Symbol) -> Result<(), Error> {
+        bank_id.require_auth();
 
-    // Also emit event
-    env.events().publish(
-        (
-            symbol_short!("status"),
-            symbol_short!("change"),
-            symbol_short!("v1"),
-        ),
-        event,
-    );
-}
+        if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
+            return Err(Error::Unauthorized);
+        }
 
-pub(crate) fn record_request_status_change(
-    env: &Env,
-    request_id: u64,
-    old_status: RequestStatus,
-    new_status: RequestStatus,
-    actor: Address,
-    reason: Option<String>,
-) {
-    let event = RequestStatusChangeEvent {
-        request_id,
-        old_status,
-        new_status,
-        actor,
-        timestamp: env.ledger().timestamp(),
-        reason,
-    };
+        let key = DataKey::Lot(lot_id.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(Error::DuplicateRegistration);
+        }
 
-    env.events().publish(
-        (
-            symbol_short!("request"),
-            symbol_short!("status"),
-            symbol_short!("v1"),
-        ),
-        event,
-    );
-}
+        let lot = Lot {
+            id: lot_id.clone(),
+            bank_id: bank_id.clone(),
+            created_at: env.ledger().timestamp(),
+            closed_at: None,
+        };
+        env.storage().persistent().set(&key, &lot);
+        bump_persistent(&env, &key);
 
-/// Append a custody event_id to the paginated trail for a unit
-pub(crate) fn append_to_custody_trail(env: &Env, unit_id: u64, event_id: String) {
-    // Get or create metadata
-    let meta_key = DataKey::UnitTrailMeta(unit_id);
-    let mut metadata: TrailMetadata =
-        env.storage()
+        let bank_key = DataKey::BankLots(bank_id.clone());
+        let mut lots: Vec<Symbol> = env
+            .storage()
             .persistent()
-            .get(&meta_key)
-            .unwrap_or(TrailMetadata {
-                total_events: 0,
-                total_pages: 0,
-            });
+            .get(&bank_key)
+            .unwrap_or(Vec::new(&env));
+        lots.push_back(lot_id.clone());
+        env.storage().persistent().set(&bank_key, &lots);
+        bump_persistent(&env, &bank_key);
 
-    // Calculate which page this event belongs to
-    let page_number = metadata.total_events / MAX_EVENTS_PER_PAGE;
-    let page_key = DataKey::UnitTrailPage(unit_id, page_number);
-
-    // Get or create the page
-    let mut page: Vec<String> = env
-        .storage()
-        .persistent()
-        .get(&page_key)
-        .unwrap_or(Vec::new(env));
-
-    // Append event_id to the page
-    page.push_back(event_id);
-
-    // Save the page
-    env.storage().persistent().set(&page_key, &page);
+        publish_event(
+            &env,
+            (symbol_short!("lot"), symbol_short!("create")),
+            (lot_id, bank_id),
+        );
 
-    // Update metadata
-    metadata.total_events = metadata.total_events.saturating_add(1);
-    if page.len() == 1 {
-        // New page was created
-        metadata.total_pages = metadata.total_pages.saturating_add(1);
+        Ok(())
     }
 
-    env.storage().persistent().set(&meta_key, &metadata);
-}
-
-#[contractimpl]
-impl HealthChainContract {
-    /// Get transfer history for a blood unit
-    pub fn get_transfer_history(env: Env, unit_id: u64) -> Vec<StatusChangeEvent> {
-        let history_key = (HISTORY, unit_id);
-        env.storage()
-            .persistent()
-            .get(&history_key)
-            .unwrap_or(Vec::new(&env))
-    }
+    /// Close a collection lot once its drive is over (creating bank only).
+    ///
+    /// Units already tagged stay in the lot and remain recallable.
+    pub fn close_lot(env: Env, bank_id: Address, lot_id: Symbol) -> Result<(), Error> {
+        bank_id.require_auth();
 
-    /// Check if an address is an authorized hospital
-    pub fn is_hospital(env: Env, hospital_id: Address) -> bool {
-        let hospitals: Map<Address, LifecycleState> = env
+        let key = DataKey::Lot(lot_id.clone());
+        let mut lot: Lot = env
             .storage()
             .persistent()
-            .get(&HOSPITALS)
-            .unwrap_or(Map::new(&env));
-
-        hospitals
-            .get(hospital_id)
-            .unwrap_or(LifecycleState::Inactive)
-            == LifecycleState::Active
-    }
-
-    /// Helper: Derive deterministic event_id for custody transfers
-    /// Uses SHA256 hash of: unit_id + from_custodian + to_custodian + ledger_sequence
-    fn derive_event_id(
-        env: &Env,
-        unit_id: u64,
-        from_custodian: &Address,
-        to_custodian: &Address,
-    ) -> String {
-        use soroban_sdk::{Bytes, BytesN};
-
-        let ledger_sequence = env.ledger().sequence();
-
-        // Create input bytes for hashing
-        let mut input = Bytes::new(env);
-
-        // Add unit_id (8 bytes)
-        for byte in unit_id.to_be_bytes().iter() {
-            input.push_back(*byte);
-        }
-
-        // Add from_custodian as Val (8 bytes)
-        let from_val_u64: u64 = from_custodian.to_val().get_payload();
-        for byte in from_val_u64.to_be_bytes().iter() {
-            input.push_back(*byte);
-        }
-
-        // Add to_custodian as Val (8 bytes)
-        let to_val_u64: u64 = to_custodian.to_val().get_payload();
-        for byte in to_val_u64.to_be_bytes().iter() {
-            input.push_back(*byte);
+            .get(&key)
+            .ok_or(Error::LotNotFound)?;
+        if lot.bank_id != bank_id {
+            return Err(Error::Unauthorized);
         }
-
-        // Add ledger_sequence (4 bytes)
-        for byte in ledger_sequence.to_be_bytes().iter() {
-            input.push_back(*byte);
+        if lot.closed_at.is_some() {
+            return Err(Error::LotClosed);
         }
 
-        // Compute SHA256 hash
-        let hash: BytesN<32> = env.crypto().sha256(&input).into();
-
-        // Convert hash to hex string
-        let hex_chars = b"0123456789abcdef";
-        let mut hex_array = [0u8; HEX_HASH_LENGTH];
+        lot.closed_at = Some(env.ledger().timestamp());
+        env.storage().persistent().set(&key, &lot);
+        bump_persistent(&env, &key);
 
-        for i in 0..32u32 {
-            let byte = hash.get(i).unwrap();
-            let high = (byte >> 4) & 0x0f;
-            let low = byte & 0x0f;
-            hex_array[(i * 2) as usize] = hex_chars[high as usize];
-            hex_array[(i * 2 + 1) as usize] = hex_chars[low as usize];
-        }
+        publish_event(&env, (symbol_short!("lot"), symbol_short!("close")), lot_id);
 
-        String::from_bytes(env, &hex_array)
+        Ok(())
     }
 
-    /// Public function to compute event_id for a given transfer
-    /// Callers can use this to compute the event_id needed for confirm_transfer and cancel_transfer
-    pub fn compute_event_id(
+    /// Tag one of `bank_id`'s units with an open collection lot the bank
+    /// created, so it can later be recalled through [`Self::recall_by_lot`].
+    /// A unit belongs to at most one lot.
+    pub fn assign_lot(
         env: Env,
+        bank_id: Address,
         unit_id: u64,
-        from_custodian: Address,
-        to_custodian: Address,
-        ledger_sequence: u32,
-    ) -> String {
-        use soroban_sdk::{Bytes, BytesN};
-
-        // Create input bytes for hashing
-        let mut input = Bytes::new(&env);
+        lot_id: Symbol,
+    ) -> Result<(), Error> {
+        bank_id.require_auth();
 
-        // Add unit_id (8 bytes)
-        for byte in unit_id.to_be_bytes().iter() {
-            input.push_back(*byte);
+        if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
+            return Err(Error::Unauthorized);
         }
 
-        // Add from_custodian as Val (8 bytes)
-        let from_val_u64: u64 = from_custodian.to_val().get_payload();
-        for byte in from_val_u64.to_be_bytes().iter() {
-            input.push_back(*byte);
+        let lot = Self::get_lot(env.clone(), lot_id.clone()).ok_or(Error::LotNotFound)?;
+        if lot.bank_id != bank_id {
+            return Err(Error::Unauthorized);
         }
-
-        // Add to_custodian as Val (8 bytes)
-        let to_val_u64: u64 = to_custodian.to_val().get_payload();
-        for byte in to_val_u64.to_be_bytes().iter() {
-            input.push_back(*byte);
+        if lot.closed_at.is_some() {
+            return Err(Error::LotClosed);
         }
 
-        // Add ledger_sequence (4 bytes)
-        for byte in ledger_sequence.to_be_bytes().iter() {
-            input.push_back(*byte);
+        let mut unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
+        if unit.bank_id != bank_id {
+            return Err(Error::Unauthorized);
+        }
+        if unit.lot_id.is_some() {
+            return Err(Error::DuplicateRegistration);
         }
 
-        // Compute SHA256 hash
-        let hash: BytesN<32> = env.crypto().sha256(&input).into();
+        let key = DataKey::LotUnits(lot_id.clone());
+        let mut ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        ids.push_back(unit_id);
+        env.storage().persistent().set(&key, &ids);
+        bump_persistent(&env, &key);
 
-        // Convert hash to hex string
-        let hex_chars = b"0123456789abcdef";
-        let mut hex_array = [0u8; HEX_HASH_LENGTH];
+        unit.lot_id = Some(lot_id.clone());
+        save_unit(&env, &unit);
 
-        for i in 0..32u32 {
-            let byte = hash.get(i).unwrap();
-            let high = (byte >> 4) & 0x0f;
-            let low = byte & 0x0f;
-            hex_array[(i * 2) as usize] = hex_chars[high as usize];
-            hex_array[(i * 2 + 1) as usize] = hex_chars[low as usize];
-        }
+        publish_event(
+            &env,
+            (symbol_short!("lot"), symbol_short!("assign")),
+            (unit_id, lot_id),
+        );
 
-        String::from_bytes(&env, &hex_array)
+        Ok(())
     }
 
-    /// Get custody event by event_id
-    pub fn get_custody_event(env: Env, event_id: String) -> Result<CustodyEvent, Error> {
-        let custody_events: Map<String, CustodyEvent> = env
-            .storage()
+    /// Return the IDs of every unit tagged with `lot_id`, in tagging order.
+    pub fn get_units_by_lot(env: Env, lot_id: Symbol) -> Vec<u64> {
+        env.storage()
             .persistent()
-            .get(&CUSTODY_EVENTS)
-            .unwrap_or(Map::new(&env));
+            .get(&DataKey::LotUnits(lot_id))
+            .unwrap_or(Vec::new(&env))
+    }
 
-        custody_events.get(event_id).ok_or(Error::UnitNotFound)
+    /// Lot record, if the lot has been created.
+    pub fn get_lot(env: Env, lot_id: Symbol) -> Option<Lot> {
+        env.storage().persistent().get(&DataKey::Lot(lot_id))
     }
 
-    /// Get custody trail for a blood unit with pagination
-    /// Returns all confirmed custody event IDs for the specified page
-    pub fn get_custody_trail(
-        env: Env,
-        unit_id: u64,
-        page_number: u32,
-    ) -> Result<Vec<String>, Error> {
-        let meta_key = DataKey::UnitTrailMeta(unit_id);
-        let metadata: TrailMetadata = env
-            .storage()
+    /// Lots created by `bank_id`, in creation order.
+    pub fn get_bank_lots(env: Env, bank_id: Address) -> Vec<Symbol> {
+        env.storage()
             .persistent()
-            .get(&meta_key)
-            .unwrap_or(TrailMetadata {
-                total_events: 0,
-                total_pages: 0,
-            });
+            .get(&DataKey::BankLots(bank_id))
+            .unwrap_or(Vec::new(&env))
+    }
 
-        if metadata.total_pages > 0 && page_number >= metadata.total_pages {
-            return Err(Error::PageNotFound);
+    /// Units of a lot currently in `status`, e.g. everything still Available
+    /// from a drive under audit.
+    pub fn get_lot_units_by_status(env: Env, lot_id: Symbol, status: BloodStatus) -> Vec<u64> {
+        let mut matching = Vec::new(&env);
+        for unit_id in Self::get_units_by_lot(env.clone(), lot_id).iter() {
+            if let Some(unit) = load_unit(&env, unit_id) {
+                if unit.status == status {
+                    matching.push_back(unit_id);
+                }
+            }
         }
+        matching
+    }
 
-        let page_key = DataKey::UnitTrailPage(unit_id, page_number);
+    /// Recall every unit `bank_id` collected from `donor_id`.
+    ///
+    /// Units already Discarded (which covers units withdrawn as used), Expired
+    /// or Recalled are left as they are. Returns the number of units recalled.
+    pub fn recall_by_donor(
+        env: Env,
+        bank_id: Address,
+        donor_id: Symbol,
+        reason: RecallReason,
+    ) -> Result<u32, Error> {
+        bank_id.require_auth();
 
-        let page: Vec<String> = env
+        if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
+            return Err(Error::Unauthorized);
+        }
+
+        let ids: Vec<u64> = env
             .storage()
             .persistent()
-            .get(&page_key)
+            .get(&DataKey::DonorUnits(bank_id.clone(), donor_id))
             .unwrap_or(Vec::new(&env));
 
-        Ok(page)
-    }
+        let mut recalled: u32 = 0;
+        for unit_id in ids.iter() {
+            recalled += recall_unit(&env, unit_id, &bank_id, reason);
+        }
 
-    /// Get custody trail metadata for a blood unit
-    pub fn get_custody_trail_metadata(env: Env, unit_id: u64) -> TrailMetadata {
-        let meta_key = DataKey::UnitTrailMeta(unit_id);
-        env.storage()
-            .persistent()
-            .get(&meta_key)
-            .unwrap_or(TrailMetadata {
-                total_events: 0,
-                total_pages: 0,
-            })
+        Ok(recalled)
     }
 
-    /// Migrate existing unbounded custody trail to paginated format (admin only)
-    /// This is a one-time migration function for units that may have old trail data
-    pub fn migrate_trail_index(env: Env, unit_id: u64) -> Result<(), Error> {
+    /// Recall every unit tagged with `lot_id` (admin only).
+    ///
+    /// Lots can span banks, so this is gated on the contract admin rather than
+    /// a single bank. Skips the same statuses as [`Self::recall_by_donor`].
+    pub fn recall_by_lot(env: Env, lot_id: Symbol, reason: RecallReason) -> Result<u32, Error> {
         let admin: Address = env
             .storage()
             .instance()
@@ -2452,1214 +4132,1844 @@ impl HealthChainContract {
             .ok_or(Error::Unauthorized)?;
         admin.require_auth();
 
-        // Check if already migrated
-        let meta_key = DataKey::UnitTrailMeta(unit_id);
-        if env.storage().persistent().has(&meta_key) {
-            // Already migrated, nothing to do
-            return Ok(());
+        let ids = Self::get_units_by_lot(env.clone(), lot_id);
+
+        let mut recalled: u32 = 0;
+        for unit_id in ids.iter() {
+            recalled += recall_unit(&env, unit_id, &admin, reason);
         }
 
-        // For this implementation, we assume there's no legacy unbounded Vec to migrate
-        // If there was a legacy storage key like DataKey::UnitTrail(unit_id) -> Vec<String>,
-        // we would:
-        // 1. Load the old Vec
-        // 2. Split it into pages of MAX_EVENTS_PER_PAGE
-        // 3. Store each page with DataKey::UnitTrailPage(unit_id, page_number)
-        // 4. Create and store metadata
-        // 5. Delete the old storage entry
+        Ok(recalled)
+    }
 
-        // Since we're implementing this fresh, we just initialize empty metadata
-        let metadata = TrailMetadata {
-            total_events: 0,
-            total_pages: 0,
-        };
-        env.storage().persistent().set(&meta_key, &metadata);
+    // ── READ ──────────────────────────────────────────────────────────────────
 
-        Ok(())
+    /// Get blood unit by ID.
+    ///
+    /// Delegates to [`registry_read::get_unit`].
+    pub fn get_blood_unit(env: Env, unit_id: u64) -> Result<BloodUnit, Error> {
+        registry_read::get_unit(&env, unit_id)
     }
 
-    /// Create a blood request (hospital only)
-    pub fn create_request(
-        env: Env,
-        hospital_id: Address,
-        blood_type: BloodType,
-        quantity_ml: u32,
-        urgency: UrgencyLevel,
-        required_by: u64,
-        delivery_address: String,
-    ) -> Result<u64, Error> {
-        hospital_id.require_auth();
+    /// Get blood status.
+    ///
+    /// Delegates to [`registry_read::get_unit`].
+    pub fn get_blood_status(env: Env, unit_id: u64) -> Result<BloodStatus, Error> {
+        let unit = registry_read::get_unit(&env, unit_id)?;
+        Ok(unit.status)
+    }
 
-        if !Self::is_hospital(env.clone(), hospital_id.clone()) {
-            return Err(Error::Unauthorized);
-        }
+    /// Check whether a blood unit's expiration date has passed.
+    ///
+    /// Delegates to [`registry_read::is_expired`].
+    pub fn is_expired(env: Env, unit_id: u64) -> Result<bool, Error> {
+        registry_read::is_expired(&env, unit_id)
+    }
 
-        if !(MIN_REQUEST_ML..=MAX_REQUEST_ML).contains(&quantity_ml) {
-            return Err(Error::InvalidQuantity);
-        }
+    /// Return all blood units donated by the given donor.
+    ///
+    /// Delegates to [`registry_read::get_units_by_donor`].
+    pub fn get_units_by_donor(env: Env, donor_id: Symbol) -> Vec<BloodUnit> {
+        registry_read::get_units_by_donor(&env, donor_id)
+    }
 
-        if delivery_address.is_empty() {
-            return Err(Error::InvalidDeliveryAddress);
-        }
+    /// Return the unit an aliquot was split from.
+    ///
+    /// Delegates to [`registry_read::get_parent_unit`].
+    pub fn get_parent_unit(env: Env, unit_id: u64) -> Option<u64> {
+        registry_read::get_parent_unit(&env, unit_id)
+    }
 
-        if delivery_address.len() > MAX_DELIVERY_ADDRESS_LENGTH {
-            return Err(Error::DeliveryAddressTooLong);
-        }
+    /// Return the aliquots a unit was split into.
+    ///
+    /// Delegates to [`registry_read::get_child_units`].
+    pub fn get_child_units(env: Env, unit_id: u64) -> Vec<u64> {
+        registry_read::get_child_units(&env, unit_id)
+    }
 
-        let current_time = env.ledger().timestamp();
-        if required_by <= current_time {
-            return Err(Error::InvalidRequiredBy);
-        }
+    /// Return the source units a pooled product was made from.
+    ///
+    /// Delegates to [`registry_read::get_pool_sources`].
+    pub fn get_pool_sources(env: Env, unit_id: u64) -> Vec<u64> {
+        registry_read::get_pool_sources(&env, unit_id)
+    }
 
-        // Normalize dedup semantics by excluding free-form delivery address text:
-        // equivalent logical requests should map to one key even if address case/spacing differs.
-        let request_key = RequestKey {
-            hospital_id: hospital_id.clone(),
-            blood_type,
-            quantity_ml,
-            urgency,
-            required_by,
-        };
+    /// Return the pooled product a source unit went into.
+    ///
+    /// Delegates to [`registry_read::get_pooled_into`].
+    pub fn get_pooled_into(env: Env, unit_id: u64) -> Option<u64> {
+        registry_read::get_pooled_into(&env, unit_id)
+    }
 
-        let mut request_keys: Map<RequestKey, u64> = env
+    /// Return the units one bank collected from `donor_id`.
+    ///
+    /// Delegates to [`registry_read::get_units_by_bank_donor`].
+    pub fn get_units_by_bank_donor(env: Env, bank_id: Address, donor_id: Symbol) -> Vec<BloodUnit> {
+        registry_read::get_units_by_bank_donor(&env, bank_id, donor_id)
+    }
+
+    /// Query blood units by status
+    pub fn query_by_status(env: Env, status: BloodStatus, max_results: u32) -> Vec<BloodUnit> {
+        // Use the StatusUnits secondary index — O(k) where k = units with this status.
+        let key = DataKey::StatusUnits(status);
+        let ids: Vec<u64> = env
             .storage()
             .persistent()
-            .get(&REQUEST_KEYS)
-            .unwrap_or(Map::new(&env));
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
 
-        if request_keys.get(request_key.clone()).is_some() {
-            return Err(Error::DuplicateRequest);
-        }
+        let mut results = vec![&env];
+        let limit = clamp_limit(max_results);
 
-        let request_id = get_next_request_id(&env);
+        for id in ids.iter() {
+            if results.len() >= limit {
+                break;
+            }
+            if let Some(unit) = load_unit(&env, id) {
+                results.push_back(unit);
+            }
+        }
 
-        let request = BloodRequest {
-            id: request_id,
-            hospital_id: hospital_id.clone(),
-            blood_type,
-            quantity_ml,
-            urgency,
-            required_by,
-            delivery_address: delivery_address.clone(),
-            created_at: current_time,
-            status: RequestStatus::Pending,
-            fulfilled_quantity_ml: 0,
-            fulfillment_timestamp: None,
-            reserved_unit_ids: vec![&env],
-        };
+        results
+    }
 
-        let mut requests: Map<u64, BloodRequest> = env
+    /// Query blood units by hospital
+    pub fn query_by_hospital(env: Env, hospital: Address, max_results: u32) -> Vec<BloodUnit> {
+        // Use the HospitalUnits secondary index — O(k) where k = units for this hospital.
+        let key = DataKey::HospitalUnits(hospital);
+        let ids: Vec<u64> = env
             .storage()
             .persistent()
-            .get(&REQUESTS)
-            .unwrap_or(Map::new(&env));
-
-        requests.set(request_id, request);
-        env.storage().persistent().set(&REQUESTS, &requests);
-
-        request_keys.set(request_key, request_id);
-        env.storage().persistent().set(&REQUEST_KEYS, &request_keys);
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
 
-        let event = RequestCreatedEvent {
-            request_id,
-            hospital_id,
-            blood_type,
-            quantity_ml,
-            urgency,
-            required_by,
-            delivery_address,
-            created_at: current_time,
-        };
+        let mut results = vec![&env];
+        let limit = clamp_limit(max_results);
 
-        env.events().publish(
-            (
-                symbol_short!("blood"),
-                symbol_short!("request"),
-                symbol_short!("v1"),
-            ),
-            event,
-        );
+        for id in ids.iter() {
+            if results.len() >= limit {
+                break;
+            }
+            if let Some(unit) = load_unit(&env, id) {
+                results.push_back(unit);
+            }
+        }
 
-        Ok(request_id)
+        results
     }
+}
 
-    /// Create a payment for a request and persist its escrow account with release conditions.
-    pub fn create_payment(
-        env: Env,
-        request_id: u64,
-        payer: Address,
-        payee: Address,
-        amount: i128,
-        asset: Address,
-        fee_payload: FeeStructure,
-        backend_auth: Address,
-    ) -> Result<u64, Error> {
-        payer.require_auth();
-        backend_auth.require_auth();
+// ── INDEX HELPERS (Internal) ──
 
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&ADMIN)
-            .ok_or(Error::Unauthorized)?;
+/// Append `unit_id` to the BankUnits index for `bank_id`.
+pub(crate) fn index_bank_unit(env: &Env, bank_id: &Address, unit_id: u64) {
+    let key = DataKey::BankUnits(bank_id.clone());
+    let mut ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    ids.push_back(unit_id);
+    env.storage().persistent().set(&key, &ids);
+    bump_persistent(env, &key);
+}
 
-        if backend_auth != admin {
-            return Err(Error::Unauthorized);
+/// Remove `unit_id` from the BankUnits index for `bank_id`.
+/// Call when a unit leaves the bank's inventory (delivered or expired).
+pub(crate) fn deindex_bank_unit(env: &Env, bank_id: &Address, unit_id: u64) {
+    let key = DataKey::BankUnits(bank_id.clone());
+    let ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    let mut filtered = Vec::new(env);
+    for id in ids.iter() {
+        if id != unit_id {
+            filtered.push_back(id);
         }
+    }
+    env.storage().persistent().set(&key, &filtered);
+}
 
-        if let Err(_) = fee_payload.validate() {
-            return Err(Error::InvalidFeePayload);
+/// Append `unit_id` to the HospitalUnits index for `hospital_id`.
+/// Call when a unit is allocated to a hospital.
+pub(crate) fn index_hospital_unit(env: &Env, hospital_id: &Address, unit_id: u64) {
+    let key = DataKey::HospitalUnits(hospital_id.clone());
+    let mut ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    ids.push_back(unit_id);
+    env.storage().persistent().set(&key, &ids);
+    bump_persistent(env, &key);
+}
+
+/// Remove `unit_id` from the HospitalUnits index for `hospital_id`.
+/// Call when an allocation is cancelled and the unit returns to inventory.
+pub(crate) fn deindex_hospital_unit(env: &Env, hospital_id: &Address, unit_id: u64) {
+    let key = DataKey::HospitalUnits(hospital_id.clone());
+    let ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    let mut filtered = Vec::new(env);
+    for id in ids.iter() {
+        if id != unit_id {
+            filtered.push_back(id);
         }
+    }
+    env.storage().persistent().set(&key, &filtered);
+}
 
-        let mut payments: Map<u64, Payment> = env
-            .storage()
-            .persistent()
-            .get(&PAYMENTS)
-            .unwrap_or(Map::new(&env));
+/// Append `unit_id` to the DonorUnits index for `(bank_id, donor_id)` and the
+/// global sentinel index `(ZERO_ADDR, donor_id)` used by cross-bank donor queries.
+pub(crate) fn index_donor_unit(env: &Env, bank_id: &Address, donor_id: &Symbol, unit_id: u64) {
+    // Per-bank index
+    let key = DataKey::DonorUnits(bank_id.clone(), donor_id.clone());
+    let mut ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    ids.push_back(unit_id);
+    env.storage().persistent().set(&key, &ids);
+    bump_persistent(env, &key);
 
-        let payment_id = env
-            .storage()
-            .instance()
-            .get(&NEXT_PAYMENT_ID)
-            .unwrap_or(1u64);
+    // Global cross-bank index (sentinel zero-address)
+    let sentinel = Address::from_contract_id(env, &soroban_sdk::BytesN::from_array(env, &[0u8; 32]));
+    let global_key = DataKey::DonorUnits(sentinel, donor_id.clone());
+    let mut global_ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&global_key)
+        .unwrap_or(Vec::new(env));
+    global_ids.push_back(unit_id);
+    env.storage().persistent().set(&global_key, &global_ids);
+}
 
-        let payment = Payment {
-            id: payment_id,
-            request_id,
-            payer,
-            payee,
-            amount,
-            asset,
-            fee_structure: fee_payload,
-            status: PaymentStatus::Pending,
-            escrow_released_at: None,
-        };
-
-        if let Err(_) = payment.validate() {
-            return Err(Error::StorageError);
+/// Remove `unit_id` from the per-bank DonorUnits index for `(bank_id, donor_id)`.
+///
+/// The global sentinel index is left alone: the donor and unit are unchanged,
+/// only the holding bank is.
+pub(crate) fn deindex_donor_unit(env: &Env, bank_id: &Address, donor_id: &Symbol, unit_id: u64) {
+    let key = DataKey::DonorUnits(bank_id.clone(), donor_id.clone());
+    let ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    let mut filtered = Vec::new(env);
+    for id in ids.iter() {
+        if id != unit_id {
+            filtered.push_back(id);
         }
-
-        // Persist escrow account with default release conditions at payment setup time.
-        // Callers may update conditions via set_escrow_conditions before release.
-        let escrow = EscrowAccount {
-            payment_id,
-            locked_amount: amount,
-            release_conditions: ReleaseConditions {
-                medical_records_verified: false,
-                min_timestamp: 0,
-                authorized_approver: None,
-            },
-        };
-        let mut escrow_accounts: Map<u64, EscrowAccount> = env
-            .storage()
-            .persistent()
-            .get(&ESCROW_ACCOUNTS)
-            .unwrap_or(Map::new(&env));
-        escrow_accounts.set(payment_id, escrow);
-        env.storage()
-            .persistent()
-            .set(&ESCROW_ACCOUNTS, &escrow_accounts);
-
-        payments.set(payment_id, payment);
-        env.storage().persistent().set(&PAYMENTS, &payments);
-        env.storage()
-            .instance()
-            .set(&NEXT_PAYMENT_ID, &(payment_id + 1));
-
-        Ok(payment_id)
     }
+    env.storage().persistent().set(&key, &filtered);
+}
 
-    /// Update the release conditions for an escrowed payment (admin only).
-    pub fn set_escrow_conditions(
-        env: Env,
-        payment_id: u64,
-        medical_records_verified: bool,
-        min_timestamp: u64,
-        authorized_approver: Option<Address>,
-    ) -> Result<(), Error> {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&ADMIN)
-            .ok_or(Error::Unauthorized)?;
-        admin.require_auth();
-
-        let mut escrow_accounts: Map<u64, EscrowAccount> = env
-            .storage()
-            .persistent()
-            .get(&ESCROW_ACCOUNTS)
-            .ok_or(Error::PaymentNotFound)?;
+/// Move `unit_id` from the `old_status` bucket to the `new_status` bucket.
+/// No-op when `old_status == new_status`.
+pub(crate) fn reindex_status(env: &Env, unit_id: u64, old_status: BloodStatus, new_status: BloodStatus) {
+    if old_status == new_status {
+        return;
+    }
+    // Remove from old bucket
+    let old_key = DataKey::StatusUnits(old_status);
+    let mut old_ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&old_key)
+        .unwrap_or(Vec::new(env));
+    let mut filtered = Vec::new(env);
+    for id in old_ids.iter() {
+        if id != unit_id {
+            filtered.push_back(id);
+        }
+    }
+    env.storage().persistent().set(&old_key, &filtered);
 
-        let mut escrow = escrow_accounts
-            .get(payment_id)
-            .ok_or(Error::PaymentNotFound)?;
-        escrow.release_conditions = ReleaseConditions {
-            medical_records_verified,
-            min_timestamp,
-            authorized_approver,
-        };
-        escrow_accounts.set(payment_id, escrow);
-        env.storage()
-            .persistent()
-            .set(&ESCROW_ACCOUNTS, &escrow_accounts);
-        Ok(())
+    // Add to new bucket
+    let new_key = DataKey::StatusUnits(new_status);
+    let mut new_ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&new_key)
+        .unwrap_or(Vec::new(env));
+    new_ids.push_back(unit_id);
+    env.storage().persistent().set(&new_key, &new_ids);
+    bump_persistent(env, &new_key);
+
+    // Keep the per-bank Available-by-type index and inventory counters in step
+    if let Some(unit) = load_unit(env, unit_id) {
+        if old_status == BloodStatus::Available {
+            deindex_available_unit(env, &unit.bank_id, unit.blood_type, unit_id);
+        } else if new_status == BloodStatus::Available {
+            index_available_unit(
+                env,
+                &unit.bank_id,
+                unit.blood_type,
+                unit_id,
+                unit.expiration_date,
+            );
+        }
+        adjust_type_summary(env, &unit, Some(old_status), Some(new_status));
     }
+}
 
-    /// Configure the dispute timeout window in seconds (admin only).
-    pub fn set_dispute_timeout(env: Env, timeout_secs: u64) -> Result<(), Error> {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&ADMIN)
-            .ok_or(Error::Unauthorized)?;
-        admin.require_auth();
+/// Move `unit` from the `old_status` counter to the `new_status` counter in
+/// its bank's [`TypeSummary`]. Pass `None` as `old_status` for a unit entering
+/// the bank's inventory and as `new_status` for one leaving it.
+///
+/// Statuses without a counter (InTransit, Quarantined, Discarded, Recalled,
+/// Split, Pooled) are ignored; decrements saturate so units from before the counters
+/// existed cannot underflow them.
+pub(crate) fn adjust_type_summary(
+    env: &Env,
+    unit: &BloodUnit,
+    old_status: Option<BloodStatus>,
+    new_status: Option<BloodStatus>,
+) {
+    let key = DataKey::TypeSummary(unit.bank_id.clone(), unit.blood_type);
+    let mut summary: TypeSummary = env.storage().persistent().get(&key).unwrap_or_default();
+    let volume = unit.quantity as u64;
 
-        env.storage()
-            .instance()
-            .set(&DISPUTE_TIMEOUT, &timeout_secs);
-        Ok(())
+    match old_status {
+        Some(BloodStatus::Available) => {
+            summary.available = summary.available.saturating_sub(1);
+            summary.total_volume_ml = summary.total_volume_ml.saturating_sub(volume);
+        }
+        Some(BloodStatus::Reserved) => summary.reserved = summary.reserved.saturating_sub(1),
+        Some(BloodStatus::Delivered) => summary.delivered = summary.delivered.saturating_sub(1),
+        Some(BloodStatus::Expired) => summary.expired = summary.expired.saturating_sub(1),
+        _ => {}
+    }
+    match new_status {
+        Some(BloodStatus::Available) => {
+            summary.available += 1;
+            summary.total_volume_ml += volume;
+        }
+        Some(BloodStatus::Reserved) => summary.reserved += 1,
+        Some(BloodStatus::Delivered) => summary.delivered += 1,
+        Some(BloodStatus::Expired) => summary.expired += 1,
+        _ => {}
     }
 
-    /// Configure M-of-N multisig signers for high-value escrow releases.
-    pub fn configure_multisig(
-        env: Env,
-        signers: Vec<Address>,
-        threshold: u32,
-    ) -> Result<(), Error> {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&ADMIN)
-            .ok_or(Error::Unauthorized)?;
-        admin.require_auth();
+    env.storage().persistent().set(&key, &summary);
+    bump_persistent(env, &key);
+}
 
-        let config = MultiSigConfig { signers, threshold };
-        config
-            .validate()
-            .map_err(|_| Error::InvalidMultiSigConfig)?;
+/// Insert `unit_id` into the AvailableByType index for `(bank_id, blood_type)`.
+///
+/// The index is kept sorted by expiration date (ties in insertion order) so
+/// FEFO allocation can take units from the front. New units usually expire
+/// last, so the backwards scan normally stops after a single comparison.
+pub(crate) fn index_available_unit(
+    env: &Env,
+    bank_id: &Address,
+    blood_type: BloodType,
+    unit_id: u64,
+    expiration_date: u64,
+) {
+    let key = DataKey::AvailableByType(bank_id.clone(), blood_type);
+    let mut ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    let mut position = ids.len();
+    while position > 0 {
+        let expires_at = load_unit(env, ids.get(position - 1).unwrap())
+            .map(|unit| unit.expiration_date)
+            .unwrap_or(0);
+        if expires_at <= expiration_date {
+            break;
+        }
+        position -= 1;
+    }
+    ids.insert(position, unit_id);
+    env.storage().persistent().set(&key, &ids);
+    bump_persistent(env, &key);
+}
 
-        env.storage().persistent().set(&MULTISIG_CONFIG, &config);
-        Ok(())
+/// Remove `unit_id` from the AvailableByType index for `(bank_id, blood_type)`.
+pub(crate) fn deindex_available_unit(
+    env: &Env,
+    bank_id: &Address,
+    blood_type: BloodType,
+    unit_id: u64,
+) {
+    let key = DataKey::AvailableByType(bank_id.clone(), blood_type);
+    let ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    let mut filtered = Vec::new(env);
+    for id in ids.iter() {
+        if id != unit_id {
+            filtered.push_back(id);
+        }
     }
+    env.storage().persistent().set(&key, &filtered);
+}
 
-    /// Read the active dispute timeout, falling back to the default 72h window.
-    pub fn get_dispute_timeout(env: Env) -> u64 {
-        env.storage()
-            .instance()
-            .get(&DISPUTE_TIMEOUT)
-            .unwrap_or(DEFAULT_DISPUTE_TIMEOUT_SECS)
+/// Append `unit_id` to the expiry bucket for the day it expires.
+///
+/// Pulls the `EXPIRY_CURSOR` back when the bucket is earlier than the next
+/// day the keeper sweep would visit (e.g. backdated units).
+pub(crate) fn index_expiry_unit(env: &Env, unit_id: u64, expiration_date: u64) {
+    let day = expiration_date / SECONDS_PER_DAY;
+    let key = DataKey::ExpiryBucket(day);
+    let mut ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    ids.push_back(unit_id);
+    env.storage().persistent().set(&key, &ids);
+    bump_persistent(env, &key);
+
+    let cursor: Option<u64> = env.storage().instance().get(&EXPIRY_CURSOR);
+    if !matches!(cursor, Some(next) if next <= day) {
+        env.storage().instance().set(&EXPIRY_CURSOR, &day);
     }
+}
 
-    /// Read aggregate payment stats for auto-refunded disputes.
-    pub fn get_payment_stats(env: Env) -> PaymentStats {
-        env.storage()
-            .persistent()
-            .get(&PAYMENT_STATS)
-            .unwrap_or(PaymentStats::new())
+/// Whether `unit_id` may be allocated under the current screening policy.
+///
+/// Always `true` while screening is not required; otherwise every test in
+/// [`REQUIRED_TESTS`] must have a passing latest result.
+pub(crate) fn screening_cleared(env: &Env, unit_id: u64) -> bool {
+    let required: bool = env
+        .storage()
+        .instance()
+        .get(&SCREENING_REQUIRED)
+        .unwrap_or(false);
+    if !required {
+        return true;
     }
 
-    /// Propose an escrow release.
-    ///
-    /// Escrow conditions (medical records, min timestamp, optional approver) are
-    /// evaluated first for every payment.  Multisig approval is additive — it is
-    /// required on top of the escrow conditions for high-value payments, not
-    /// instead of them.
-    pub fn propose_release(env: Env, payment_id: u64, approver: Address) -> Result<bool, Error> {
-        approver.require_auth();
+    let results: Vec<TestResult> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::UnitTests(unit_id))
+        .unwrap_or(Vec::new(env));
+    REQUIRED_TESTS.iter().all(|test_type| {
+        results
+            .iter()
+            .any(|result| result.test_type == *test_type && result.passed)
+    })
+}
 
-        let mut payments: Map<u64, Payment> = env
-            .storage()
-            .persistent()
-            .get(&PAYMENTS)
-            .ok_or(Error::PaymentNotFound)?;
+/// Whether `hospital` may take delivery of `unit_id` under the crossmatch policy.
+///
+/// Always `true` while no crossmatch window is configured; otherwise the
+/// unit's latest crossmatch must come from `hospital`, be compatible, and have
+/// been tested no more than the window before `current_time`.
+pub(crate) fn crossmatch_cleared(
+    env: &Env,
+    unit_id: u64,
+    hospital: &Address,
+    current_time: u64,
+) -> bool {
+    let window: u64 = match env.storage().instance().get(&CROSSMATCH_WINDOW) {
+        Some(window) => window,
+        None => return true,
+    };
 
-        let mut payment = payments.get(payment_id).ok_or(Error::PaymentNotFound)?;
-        if !payment.can_transition_to(PaymentStatus::Completed) {
-            return Err(Error::InvalidPaymentStatus);
+    match env
+        .storage()
+        .persistent()
+        .get::<DataKey, CrossmatchRecord>(&DataKey::Crossmatch(unit_id))
+    {
+        Some(record) => {
+            record.compatible
+                && record.hospital == *hospital
+                && current_time <= record.tested_at.saturating_add(window)
         }
+        None => false,
+    }
+}
 
-        // Enforce escrow release conditions before any payout path.
-        let escrow_accounts: Map<u64, EscrowAccount> = env
+/// Flip a single unit to Recalled and emit its recall event.
+///
+/// A Split unit is not recalled itself; the recall passes on to its
+/// aliquots instead, and a Pooled source passes it on to the pooled product.
+/// Units that no longer exist or are already Discarded, Expired or Recalled
+/// are left untouched. Returns the number of units recalled.
+pub(crate) fn recall_unit(env: &Env, unit_id: u64, actor: &Address, reason: RecallReason) -> u32 {
+    let mut unit = match load_unit(env, unit_id) {
+        Some(unit) => unit,
+        None => return 0,
+    };
+    let old_status = unit.status;
+    if old_status == BloodStatus::Split {
+        let children: Vec<u64> = env
             .storage()
             .persistent()
-            .get(&ESCROW_ACCOUNTS)
-            .unwrap_or(Map::new(&env));
-        let escrow = escrow_accounts
-            .get(payment_id)
-            .ok_or(Error::PaymentNotFound)?;
-        let current_timestamp = env.ledger().timestamp();
-        if !escrow.can_release(current_timestamp, Some(&approver)) {
-            return Err(Error::EscrowNotReleasable);
+            .get(&DataKey::UnitChildren(unit_id))
+            .unwrap_or(Vec::new(env));
+        let mut recalled: u32 = 0;
+        for child_id in children.iter() {
+            recalled += recall_unit(env, child_id, actor, reason);
         }
-
-        let mut pending_approvals: Map<u64, PendingApproval> = env
+        return recalled;
+    }
+    if old_status == BloodStatus::Pooled {
+        return match env
             .storage()
             .persistent()
-            .get(&PENDING_APPROVALS)
-            .unwrap_or(Map::new(&env));
+            .get::<DataKey, u64>(&DataKey::PooledInto(unit_id))
+        {
+            Some(pooled_id) => recall_unit(env, pooled_id, actor, reason),
+            None => 0,
+        };
+    }
+    if matches!(
+        old_status,
+        BloodStatus::Discarded | BloodStatus::Expired | BloodStatus::Recalled
+    ) {
+        return 0;
+    }
 
-        if payment.amount < HIGH_VALUE_THRESHOLD {
-            let admin: Address = env
-                .storage()
-                .instance()
-                .get(&ADMIN)
-                .ok_or(Error::Unauthorized)?;
-            if approver != admin {
-                return Err(Error::Unauthorized);
-            }
+    unit.status = BloodStatus::Recalled;
+    save_unit(env, &unit);
 
-            payment.status = PaymentStatus::Completed;
-            payment.escrow_released_at = Some(current_timestamp);
-            payments.set(payment_id, payment);
-            env.storage().persistent().set(&PAYMENTS, &payments);
-            pending_approvals.remove(payment_id);
-            env.storage()
-                .persistent()
-                .set(&PENDING_APPROVALS, &pending_approvals);
-            return Ok(true);
-        }
+    reindex_status(env, unit_id, old_status, BloodStatus::Recalled);
+    record_status_change(
+        env,
+        unit_id,
+        old_status,
+        BloodStatus::Recalled,
+        actor.clone(),
+    );
 
-        let config: MultiSigConfig = env
-            .storage()
-            .persistent()
-            .get(&MULTISIG_CONFIG)
-            .ok_or(Error::InvalidMultiSigConfig)?;
-        config
-            .validate()
-            .map_err(|_| Error::InvalidMultiSigConfig)?;
+    publish_event(
+        env,
+        (
+            symbol_short!("blood"),
+            symbol_short!("recall"),
+            symbol_short!("v1"),
+        ),
+        (unit_id, reason, env.ledger().timestamp()),
+    );
 
-        if !config.is_signer(&approver) {
-            return Err(Error::Unauthorized);
+    1
+}
+
+/// Walk up to `limit` units out of the legacy `UNITS` map into per-unit
+/// `DataKey::Unit` entries and their indexes. Marks the storage layout
+/// current once the map is drained.
+pub(crate) fn migrate_legacy_units(env: &Env, limit: u32) -> u32 {
+    let mut legacy: Map<u64, BloodUnit> = match env.storage().persistent().get(&BLOOD_UNITS) {
+        Some(units) => units,
+        None => {
+            mark_storage_current(env);
+            return 0;
         }
+    };
 
-        let mut approval = pending_approvals
-            .get(payment_id)
-            .unwrap_or(PendingApproval::new(&env, payment_id));
+    let limit = clamp_limit(limit);
+    let mut migrated: u32 = 0;
+    for id in legacy.keys().iter() {
+        if migrated >= limit {
+            break;
+        }
+        if let Some(unit) = legacy.get(id) {
+            if load_unit(env, id).is_none() {
+                save_unit(env, &unit);
+                if unit.status == BloodStatus::Available {
+                    index_available_unit(
+                        env,
+                        &unit.bank_id,
+                        unit.blood_type,
+                        id,
+                        unit.expiration_date,
+                    );
+                }
+                adjust_type_summary(env, &unit, None, Some(unit.status));
+                if !matches!(
+                    unit.status,
+                    BloodStatus::Expired
+                        | BloodStatus::Delivered
+                        | BloodStatus::Discarded
+                        | BloodStatus::Recalled
+                        | BloodStatus::Split
+                        | BloodStatus::Pooled
+                ) {
+                    index_expiry_unit(env, id, unit.expiration_date);
+                }
+            }
+        }
+        legacy.remove(id);
+        migrated += 1;
+    }
 
-        approval
-            .register_vote(approver)
-            .map_err(|_| Error::DuplicateApproval)?;
+    if legacy.is_empty() {
+        env.storage().persistent().remove(&BLOOD_UNITS);
+        mark_storage_current(env);
+    } else {
+        env.storage().persistent().set(&BLOOD_UNITS, &legacy);
+    }
 
-        if approval.has_reached_threshold(config.threshold) {
-            approval.executed = true;
-            payment.status = PaymentStatus::Completed;
-            payment.escrow_released_at = Some(current_timestamp);
-            payments.set(payment_id, payment);
-            env.storage().persistent().set(&PAYMENTS, &payments);
-        }
+    migrated
+}
 
-        pending_approvals.set(payment_id, approval.clone());
-        env.storage()
-            .persistent()
-            .set(&PENDING_APPROVALS, &pending_approvals);
+/// Record that every legacy structure has been migrated.
+fn mark_storage_current(env: &Env) {
+    let stored: Option<u32> = env.storage().instance().get(&STORAGE_VERSION);
+    if stored == Some(CURRENT_STORAGE_VERSION) {
+        return;
+    }
+    env.storage()
+        .instance()
+        .set(&STORAGE_VERSION, &CURRENT_STORAGE_VERSION);
+    publish_event(
+        env,
+        (symbol_short!("storage"), symbol_short!("migrated")),
+        CURRENT_STORAGE_VERSION,
+    );
+}
 
-        Ok(approval.executed)
+/// Fail with `ContractPaused` while `flag` is set in the admin pause mask.
+pub(crate) fn ensure_not_paused(env: &Env, flag: u32) -> Result<(), Error> {
+    let paused: u32 = env.storage().instance().get(&PAUSED).unwrap_or(0);
+    if paused & flag != 0 {
+        return Err(Error::ContractPaused);
     }
+    Ok(())
+}
 
-    /// Raise a dispute for a payment
-    pub fn raise_dispute(
-        env: Env,
-        payment_id: u64,
-        raised_by: Address,
-        reason: String,
-        evidence_digest: Bytes,
-        evidence_ref_chunks: Vec<String>,
-    ) -> Result<u64, Error> {
-        raised_by.require_auth();
+/// Checks `allocate_blood_batch` applies to each unit before reserving any.
+pub(crate) fn check_allocatable(
+    env: &Env,
+    unit: &BloodUnit,
+    bank_id: &Address,
+    recipient_type: Option<BloodType>,
+    current_time: u64,
+) -> Result<(), Error> {
+    if unit.bank_id != *bank_id {
+        return Err(Error::Unauthorized);
+    }
+    if unit.status == BloodStatus::Expired || unit.expiration_date <= current_time {
+        return Err(Error::UnitExpired);
+    }
+    if unit.status != BloodStatus::Available {
+        return Err(Error::InvalidStatus);
+    }
+    if !screening_cleared(env, unit.id) {
+        return Err(Error::ScreeningIncomplete);
+    }
+    check_compatible(unit, recipient_type)
+}
 
-        // evidence_ref_chunks reconstruction against evidence_digest is off-chain only;
-        // enforce a valid SHA-256 digest size to reject trivially invalid submissions.
-        if evidence_digest.len() != 32 {
-            return Err(Error::InvalidFeePayload);
+/// Reject a unit that cannot be transfused to `recipient_type`; allocations
+/// without a recipient type (stock moves) are not checked.
+pub(crate) fn check_compatible(
+    unit: &BloodUnit,
+    recipient_type: Option<BloodType>,
+) -> Result<(), Error> {
+    match recipient_type {
+        Some(recipient) if !is_compatible(unit.blood_type, recipient) => {
+            Err(Error::IncompatibleBloodType)
         }
+        _ => Ok(()),
+    }
+}
 
-        let mut payments: Map<u64, Payment> = env
-            .storage()
-            .persistent()
-            .get(&PAYMENTS)
-            .ok_or(Error::PaymentNotFound)?;
+/// Move an Available unit to Reserved for `hospital`, keeping the status,
+/// reservation and hospital indexes, history and events in step.
+pub(crate) fn reserve_unit(
+    env: &Env,
+    mut unit: BloodUnit,
+    bank_id: &Address,
+    hospital: &Address,
+    current_time: u64,
+) {
+    let unit_id = unit.id;
+    let old_status = unit.status;
 
-        let mut payment = payments.get(payment_id).ok_or(Error::PaymentNotFound)?;
+    unit.status = BloodStatus::Reserved;
+    unit.recipient_hospital = Some(hospital.clone());
+    unit.allocation_timestamp = Some(current_time);
 
-        if !payment.can_transition_to(PaymentStatus::Disputed) {
-            return Err(Error::InvalidTransition);
-        }
+    save_unit(env, &unit);
 
-        let dispute_id = env
-            .storage()
-            .instance()
-            .get(&NEXT_DISPUTE_ID)
-            .unwrap_or(1u64);
+    // Maintain status index
+    reindex_status(env, unit_id, old_status, BloodStatus::Reserved);
+    start_reservation(env, unit_id, current_time, default_hold_seconds(env));
 
-        let dispute = Dispute {
-            id: dispute_id,
-            payment_id,
-            raised_by: raised_by.clone(),
-            status: DisputeStatus::Open,
-            reason: reason.clone(),
-            evidence_digest: evidence_digest.clone(),
-            evidence_ref_chunks: evidence_ref_chunks.clone(),
-            raised_at: env.ledger().timestamp(),
-            resolved_at: None,
-        };
-        let dispute_deadline = env.ledger().timestamp() + Self::get_dispute_timeout(env.clone());
-        let metadata = DisputeMetadata {
-            dispute_id,
-            dispute_deadline,
-        };
+    // Maintain hospital units index
+    index_hospital_unit(env, hospital, unit_id);
 
-        payment.status = PaymentStatus::Disputed;
-        payments.set(payment_id, payment.clone());
-        env.storage().persistent().set(&PAYMENTS, &payments);
+    record_status_change(
+        env,
+        unit_id,
+        old_status,
+        BloodStatus::Reserved,
+        bank_id.clone(),
+    );
 
-        let mut disputes: Map<u64, Dispute> = env
-            .storage()
-            .persistent()
-            .get(&DISPUTES)
-            .unwrap_or(Map::new(&env));
+    publish_event(
+        env,
+        (
+            symbol_short!("blood"),
+            symbol_short!("allocate"),
+            symbol_short!("v1"),
+        ),
+        (unit_id, hospital.clone(), current_time),
+    );
+}
 
-        disputes.set(dispute_id, dispute);
-        env.storage().persistent().set(&DISPUTES, &disputes);
-        let mut dispute_metadata: Map<u64, DisputeMetadata> = env
-            .storage()
-            .persistent()
-            .get(&DISPUTE_METADATA)
-            .unwrap_or(Map::new(&env));
-        dispute_metadata.set(dispute_id, metadata);
-        env.storage()
-            .persistent()
-            .set(&DISPUTE_METADATA, &dispute_metadata);
-        env.storage()
-            .instance()
-            .set(&NEXT_DISPUTE_ID, &(dispute_id + 1));
+// ── UNIT STORAGE (Internal) ──
 
-        // Update Request Status if possible
-        let mut requests: Map<u64, BloodRequest> = env
-            .storage()
-            .persistent()
-            .get(&REQUESTS)
-            .unwrap_or(Map::new(&env));
+/// Load a blood unit from its `DataKey::Unit` entry.
+pub(crate) fn load_unit(env: &Env, unit_id: u64) -> Option<BloodUnit> {
+    env.storage().persistent().get(&DataKey::Unit(unit_id))
+}
 
-        if let Some(mut request) = requests.get(payment.request_id) {
-            request.status = RequestStatus::Disputed;
-            requests.set(payment.request_id, request);
-            env.storage().persistent().set(&REQUESTS, &requests);
-        }
+/// Persist a blood unit under its own `DataKey::Unit` entry.
+pub(crate) fn save_unit(env: &Env, unit: &BloodUnit) {
+    env.storage().persistent().set(&DataKey::Unit(unit.id), unit);
+    bump_persistent(env, &DataKey::Unit(unit.id));
+}
 
-        // Emit DisputeRaisedEvent
-        env.events().publish(
-            (
-                symbol_short!("dispute"),
-                symbol_short!("raised"),
-                symbol_short!("v1"),
-            ),
-            DisputeRaisedEvent {
-                dispute_id,
-                payment_id,
-                raised_by,
-                reason,
-                evidence_digest,
-                timestamp: env.ledger().timestamp(),
-            },
-        );
+/// One past the highest allocated unit ID. Full inventory scans walk
+/// `1..next_unit_id(env)` and skip IDs with no entry.
+pub(crate) fn next_unit_id(env: &Env) -> u64 {
+    env.storage().persistent().get(&NEXT_ID).unwrap_or(1)
+}
 
-        Ok(dispute_id)
-    }
+// ── SHARED HELPERS (Internal) ──
 
-    /// Resolve a dispute (admin only)
-    pub fn resolve_dispute(
-        env: Env,
-        dispute_id: u64,
-        resolution: DisputeStatus,
-    ) -> Result<(), Error> {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&ADMIN)
-            .ok_or(Error::Unauthorized)?;
-        admin.require_auth();
+pub(crate) fn get_next_id(env: &Env) -> u64 {
+    let id: u64 = env.storage().persistent().get(&NEXT_ID).unwrap_or(1);
+    env.storage().persistent().set(&NEXT_ID, &(id + 1));
+    bump_persistent(env, &NEXT_ID);
+    id
+}
 
-        let mut disputes: Map<u64, Dispute> = env
-            .storage()
-            .persistent()
-            .get(&DISPUTES)
-            .ok_or(Error::DisputeNotFound)?;
+pub(crate) fn get_next_request_id(env: &Env) -> u64 {
+    let id: u64 = env
+        .storage()
+        .persistent()
+        .get(&NEXT_REQUEST_ID)
+        .unwrap_or(1);
+    env.storage().persistent().set(&NEXT_REQUEST_ID, &(id + 1));
+    id
+}
 
-        let mut dispute = disputes.get(dispute_id).ok_or(Error::DisputeNotFound)?;
+/// Normalise a caller-supplied page size: zero means `DEFAULT_PAGE_SIZE`, and
+/// anything above `MAX_PAGE_SIZE` is clamped to it.
+pub(crate) fn clamp_limit(limit: u32) -> u32 {
+    if limit == 0 {
+        DEFAULT_PAGE_SIZE
+    } else {
+        limit.min(MAX_PAGE_SIZE)
+    }
+}
 
-        if dispute.status != DisputeStatus::Open {
-            return Err(Error::InvalidDisputeStatus);
+/// Publish a contract event, prepending the admin-configured namespace (if any)
+/// as the first topic so one indexer can tell deployments apart.
+pub(crate) fn publish_event<T, D>(env: &Env, topics: T, data: D)
+where
+    T: Topics,
+    D: IntoVal<Env, Val>,
+{
+    match env
+        .storage()
+        .instance()
+        .get::<Symbol, Symbol>(&EVENT_NAMESPACE)
+    {
+        Some(namespace) => {
+            let mut prefixed: Vec<Val> = vec![env, namespace.into_val(env)];
+            prefixed.append(&topics.into_val(env));
+            env.events().publish(prefixed, data);
         }
+        None => env.events().publish(topics, data),
+    }
+}
 
-        let mut payments: Map<u64, Payment> = env
-            .storage()
-            .persistent()
-            .get(&PAYMENTS)
-            .ok_or(Error::PaymentNotFound)?;
+pub(crate) fn record_status_change(
+    env: &Env,
+    unit_id: u64,
+    old_status: BloodStatus,
+    new_status: BloodStatus,
+    actor: Address,
+) {
+    let history_key = (HISTORY, unit_id);
+    let mut history: Vec<StatusChangeEvent> = env
+        .storage()
+        .persistent()
+        .get(&history_key)
+        .unwrap_or(Vec::new(env));
 
-        let mut payment = payments
-            .get(dispute.payment_id)
-            .ok_or(Error::PaymentNotFound)?;
+    let event = StatusChangeEvent {
+        blood_unit_id: unit_id,
+        old_status,
+        new_status,
+        actor,
+        timestamp: env.ledger().timestamp(),
+    };
 
-        dispute.status = resolution;
-        dispute.resolved_at = Some(env.ledger().timestamp());
-        disputes.set(dispute_id, dispute.clone());
-        env.storage().persistent().set(&DISPUTES, &disputes);
+    history.push_back(event.clone());
+    env.storage().persistent().set(&history_key, &history);
+    bump_persistent(env, &history_key);
 
-        payment.status = PaymentStatus::Resolved;
+    // Also emit event
+    publish_event(
+        env,
+        (
+            symbol_short!("status"),
+            symbol_short!("change"),
+            symbol_short!("v1"),
+        ),
+        event,
+    );
+}
 
-        // Handle funds based on resolution
-        match resolution {
-            DisputeStatus::ResolvedInFavorOfPayer => {
-                payment.status = PaymentStatus::Refunded;
-            }
-            DisputeStatus::ResolvedInFavorOfPayee => {
-                payment.status = PaymentStatus::Completed;
-            }
-            _ => {}
-        }
+pub(crate) fn record_request_status_change(
+    env: &Env,
+    request_id: u64,
+    old_status: RequestStatus,
+    new_status: RequestStatus,
+    actor: Address,
+    reason: Option<String>,
+) {
+    let event = RequestStatusChangeEvent {
+        request_id,
+        old_status,
+        new_status,
+        actor,
+        timestamp: env.ledger().timestamp(),
+        reason,
+    };
 
-        payments.set(dispute.payment_id, payment.clone());
-        env.storage().persistent().set(&PAYMENTS, &payments);
+    publish_event(
+        env,
+        (
+            symbol_short!("request"),
+            symbol_short!("status"),
+            symbol_short!("v1"),
+        ),
+        event,
+    );
+}
 
-        // Update Request Status
-        let mut requests: Map<u64, BloodRequest> = env
-            .storage()
-            .persistent()
-            .get(&REQUESTS)
-            .unwrap_or(Map::new(&env));
+/// Whether confirmations must also carry the outgoing custodian's signature.
+pub(crate) fn dual_auth_required(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DUAL_AUTH_REQUIRED)
+        .unwrap_or(false)
+}
 
-        if let Some(mut request) = requests.get(payment.request_id) {
-            request.status = RequestStatus::Resolved;
-            requests.set(payment.request_id, request);
-            env.storage().persistent().set(&REQUESTS, &requests);
-        }
+/// Record the receiver's handoff reference on confirmation. Values left as
+/// `None` keep whatever the sender attached at initiation.
+pub(crate) fn attach_handoff_reference(
+    custody_event: &mut CustodyEvent,
+    doc_hash: Option<BytesN<32>>,
+    note: Option<Symbol>,
+) {
+    if doc_hash.is_some() {
+        custody_event.doc_hash = doc_hash;
+    }
+    if note.is_some() {
+        custody_event.note = note;
+    }
+}
 
-        // Emit DisputeResolvedEvent
-        env.events().publish(
-            (
-                symbol_short!("dispute"),
-                symbol_short!("resolved"),
-                symbol_short!("v1"),
-            ),
-            DisputeResolvedEvent {
-                dispute_id,
-                payment_id: dispute.payment_id,
-                status: resolution,
-                resolved_at: env.ledger().timestamp(),
-            },
-        );
+/// Persist a newly initiated Pending custody event and its per-unit indexes.
+///
+/// Sets `UnitCustodyIndex` so the unit can have at most one pending transfer,
+/// and appends to `UnitCustodyEvents` so archival finds every event in O(k).
+pub(crate) fn store_pending_custody_event(env: &Env, custody_event: &CustodyEvent) {
+    let mut custody_events: Map<String, CustodyEvent> = env
+        .storage()
+        .persistent()
+        .get(&CUSTODY_EVENTS)
+        .unwrap_or(Map::new(env));
 
-        Ok(())
-    }
+    custody_events.set(custody_event.event_id.clone(), custody_event.clone());
+    env.storage()
+        .persistent()
+        .set(&CUSTODY_EVENTS, &custody_events);
 
-    /// Permissionless cleanup for disputes that exceeded their arbitration deadline.
-    pub fn process_expired_disputes(env: Env) -> Result<u32, Error> {
-        let current_time = env.ledger().timestamp();
-        let mut disputes: Map<u64, Dispute> = env
-            .storage()
-            .persistent()
-            .get(&DISPUTES)
-            .unwrap_or(Map::new(&env));
-        let dispute_metadata: Map<u64, DisputeMetadata> = env
-            .storage()
-            .persistent()
-            .get(&DISPUTE_METADATA)
-            .unwrap_or(Map::new(&env));
-        let mut payments: Map<u64, Payment> = env
-            .storage()
-            .persistent()
-            .get(&PAYMENTS)
-            .unwrap_or(Map::new(&env));
-        let mut stats = Self::get_payment_stats(env.clone());
-        let mut processed = 0u32;
+    record_custody_transition(env, None, CustodyStatus::Pending);
 
-        for dispute_id in disputes.keys() {
-            let mut dispute = disputes.get(dispute_id).unwrap();
-            if dispute.status != DisputeStatus::Open {
-                continue;
-            }
+    // Maintain UnitCustodyIndex so confirm_delivery can find the pending event in O(1)
+    let index_key = DataKey::UnitCustodyIndex(custody_event.unit_id);
+    env.storage()
+        .persistent()
+        .set(&index_key, &custody_event.event_id);
 
-            let metadata = match dispute_metadata.get(dispute_id) {
-                Some(metadata) => metadata,
-                None => continue,
-            };
+    // Maintain per-unit custody events list so archive_custody_events can find all events
+    // for this unit in O(k) (k = events per unit) instead of scanning the full CUSTODY_EVENTS map
+    let unit_events_key = DataKey::UnitCustodyEvents(custody_event.unit_id);
+    let mut unit_event_ids: Vec<String> = env
+        .storage()
+        .persistent()
+        .get(&unit_events_key)
+        .unwrap_or(Vec::new(env));
+    unit_event_ids.push_back(custody_event.event_id.clone());
+    env.storage()
+        .persistent()
+        .set(&unit_events_key, &unit_event_ids);
+    bump_persistent(env, &unit_events_key);
+    bump_persistent(env, &CUSTODY_EVENTS);
+}
 
-            if current_time <= metadata.dispute_deadline {
-                continue;
-            }
+/// Append a custody event_id to the paginated trail for a unit
+pub(crate) fn append_to_custody_trail(env: &Env, unit_id: u64, event_id: String) {
+    // Get or create metadata
+    let meta_key = DataKey::UnitTrailMeta(unit_id);
+    let mut metadata: TrailMetadata =
+        env.storage()
+            .persistent()
+            .get(&meta_key)
+            .unwrap_or(TrailMetadata {
+                total_events: 0,
+                total_pages: 0,
+            });
 
-            let mut payment = match payments.get(dispute.payment_id) {
-                Some(payment) => payment,
-                None => continue,
-            };
+    // Calculate which page this event belongs to
+    let page_number = metadata.total_events / MAX_EVENTS_PER_PAGE;
+    let page_key = DataKey::UnitTrailPage(unit_id, page_number);
 
-            if payment.status != PaymentStatus::Disputed {
-                continue;
-            }
+    // Get or create the page
+    let mut page: Vec<String> = env
+        .storage()
+        .persistent()
+        .get(&page_key)
+        .unwrap_or(Vec::new(env));
 
-            payment.status = PaymentStatus::Refunded;
-            payment.escrow_released_at = Some(current_time);
-            payments.set(dispute.payment_id, payment.clone());
+    // Append event_id to the page
+    page.push_back(event_id);
 
-            dispute.status = DisputeStatus::ResolvedInFavorOfPayer;
-            dispute.resolved_at = Some(current_time);
-            disputes.set(dispute_id, dispute.clone());
+    // Save the page
+    env.storage().persistent().set(&page_key, &page);
+    bump_persistent(env, &page_key);
 
-            stats.count_auto_refunded += 1;
-            stats.total_auto_refunded += payment.amount;
-            processed += 1;
+    // Update metadata
+    metadata.total_events = metadata.total_events.saturating_add(1);
+    if page.len() == 1 {
+        // New page was created
+        metadata.total_pages = metadata.total_pages.saturating_add(1);
+    }
 
-            env.events().publish(
-                (
-                    symbol_short!("dispute"),
-                    symbol_short!("refunded"),
-                    symbol_short!("v1"),
-                ),
-                DisputeAutoRefundedEvent {
-                    case_id: dispute_id,
-                    payment_id: payment.id,
-                    refunded_to: payment.payer,
-                    amount: payment.amount,
-                    refunded_at: current_time,
-                },
-            );
-        }
+    env.storage().persistent().set(&meta_key, &metadata);
+    bump_persistent(env, &meta_key);
+}
 
-        env.storage().persistent().set(&DISPUTES, &disputes);
-        env.storage().persistent().set(&PAYMENTS, &payments);
-        env.storage().persistent().set(&PAYMENT_STATS, &stats);
+/// Move one custody event between the aggregate `(pending, confirmed, cancelled)`
+/// counters kept under `CUSTODY_COUNTS`.
+///
+/// `from` is `None` for a freshly initiated event. `Recovered`, `Cancelled` and
+/// `Rejected` all count as cancelled: the transfer ended without a delivery.
+pub(crate) fn record_custody_transition(env: &Env, from: Option<CustodyStatus>, to: CustodyStatus) {
+    let (mut pending, mut confirmed, mut cancelled): (u32, u32, u32) = env
+        .storage()
+        .instance()
+        .get(&CUSTODY_COUNTS)
+        .unwrap_or((0, 0, 0));
 
-        Ok(processed)
+    match from {
+        Some(CustodyStatus::Pending) => pending = pending.saturating_sub(1),
+        Some(CustodyStatus::Confirmed) => confirmed = confirmed.saturating_sub(1),
+        Some(CustodyStatus::Cancelled)
+        | Some(CustodyStatus::Recovered)
+        | Some(CustodyStatus::Rejected) => cancelled = cancelled.saturating_sub(1),
+        None => {}
+    }
+    match to {
+        CustodyStatus::Pending => pending = pending.saturating_add(1),
+        CustodyStatus::Confirmed => confirmed = confirmed.saturating_add(1),
+        CustodyStatus::Cancelled | CustodyStatus::Recovered | CustodyStatus::Rejected => {
+            cancelled = cancelled.saturating_add(1)
+        }
     }
 
-    /// Update request status
-    pub fn update_request_status(
-        env: Env,
-        request_id: u64,
-        new_status: RequestStatus,
-    ) -> Result<(), Error> {
-        let mut requests: Map<u64, BloodRequest> = env
-            .storage()
-            .persistent()
-            .get(&REQUESTS)
-            .unwrap_or(Map::new(&env));
+    env.storage()
+        .instance()
+        .set(&CUSTODY_COUNTS, &(pending, confirmed, cancelled));
+}
 
-        let mut request = requests.get(request_id).ok_or(Error::UnitNotFound)?;
+/// Open a fresh reservation window for `unit_id` held for `hold_seconds`,
+/// discarding any earlier one.
+pub(crate) fn start_reservation(env: &Env, unit_id: u64, reserved_at: u64, hold_seconds: u64) {
+    env.storage().persistent().set(
+        &DataKey::ReservationWindow(unit_id),
+        &ReservationWindow {
+            reserved_at,
+            reserved_until: reserved_at.saturating_add(hold_seconds),
+            resolved_at: None,
+        },
+    );
+}
 
-        let caller = env.current_contract_address();
+/// Hold applied to allocations that do not ask for one: the admin-configured
+/// `HOLD_DURATION`, falling back to `RESERVATION_PICKUP_WINDOW_SECONDS`.
+pub(crate) fn default_hold_seconds(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&HOLD_DURATION)
+        .unwrap_or(RESERVATION_PICKUP_WINDOW_SECONDS)
+}
 
-        // Validate status transition
-        if !Self::is_valid_status_transition(&request.status, &new_status) {
-            return Err(Error::InvalidTransition);
-        }
+/// Pending-transfer deadline applied by `initiate_transfer`: the admin-configured
+/// `TRANSFER_DEADLINE`, falling back to `DEFAULT_TRANSFER_DEADLINE_SECONDS`.
+pub(crate) fn default_transfer_deadline(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&TRANSFER_DEADLINE)
+        .unwrap_or(DEFAULT_TRANSFER_DEADLINE_SECONDS)
+}
 
-        let old_status = request.status;
-        request.status = new_status;
+/// Return an in-transit unit whose pending transfer was rolled back to `Reserved`.
+///
+/// Clears the pending-transfer index and any rider, keeps `recipient_hospital`
+/// and `allocation_timestamp`, and records the status change against `actor`.
+pub(crate) fn revert_transfer_to_reserved(env: &Env, unit: &mut BloodUnit, actor: Address) {
+    let unit_id = unit.id;
+    env.storage()
+        .persistent()
+        .remove(&DataKey::UnitCustodyIndex(unit_id));
+    env.storage()
+        .persistent()
+        .remove(&DataKey::UnitRider(unit_id));
 
-        requests.set(request_id, request);
-        env.storage().persistent().set(&REQUESTS, &requests);
+    let old_status = unit.status;
+    unit.status = BloodStatus::Reserved;
+    unit.transfer_timestamp = None;
+    save_unit(env, unit);
 
-        // Record and emit status change
-        record_request_status_change(&env, request_id, old_status, new_status, caller, None);
+    reindex_status(env, unit_id, old_status, BloodStatus::Reserved);
+    record_status_change(env, unit_id, old_status, BloodStatus::Reserved, actor);
+}
 
-        Ok(())
+/// Close out an abandoned transfer: a unit that expired while in transit is
+/// marked `Expired` and dropped from its bank's stock, any other unit goes
+/// back to `Reserved`. Returns the status the unit ends up in.
+pub(crate) fn recover_abandoned_transfer(
+    env: &Env,
+    unit: &mut BloodUnit,
+    actor: Address,
+    current_time: u64,
+) -> BloodStatus {
+    if unit.expiration_date > current_time {
+        revert_transfer_to_reserved(env, unit, actor);
+        return BloodStatus::Reserved;
     }
 
-    /// Approve a pending request and reserve matching units for it.
-    pub fn approve_request(
-        env: Env,
-        bank_id: Address,
-        request_id: u64,
-        unit_ids: Vec<u64>,
-    ) -> Result<(), Error> {
-        bank_id.require_auth();
+    let unit_id = unit.id;
+    env.storage()
+        .persistent()
+        .remove(&DataKey::UnitCustodyIndex(unit_id));
+    env.storage()
+        .persistent()
+        .remove(&DataKey::UnitRider(unit_id));
 
-        if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
-            return Err(Error::Unauthorized);
-        }
+    let old_status = unit.status;
+    unit.status = BloodStatus::Expired;
+    save_unit(env, unit);
 
-        let mut requests: Map<u64, BloodRequest> = env
-            .storage()
-            .persistent()
-            .get(&REQUESTS)
-            .unwrap_or(Map::new(&env));
+    reindex_status(env, unit_id, old_status, BloodStatus::Expired);
+    deindex_bank_unit(env, &unit.bank_id, unit_id);
+    record_status_change(env, unit_id, old_status, BloodStatus::Expired, actor);
+    BloodStatus::Expired
+}
 
-        let mut request = requests.get(request_id).ok_or(Error::UnitNotFound)?;
+/// Reject holds of zero or longer than `MAX_RESERVATION_HOLD_SECONDS`.
+pub(crate) fn validate_hold_seconds(hold_seconds: u64) -> Result<u64, Error> {
+    if hold_seconds == 0 || hold_seconds > MAX_RESERVATION_HOLD_SECONDS {
+        return Err(Error::InvalidHoldDuration);
+    }
+    Ok(hold_seconds)
+}
 
-        if request.status != RequestStatus::Pending {
-            return Err(Error::InvalidStatus);
-        }
+/// Return a Reserved unit to Available, closing its reservation window and
+/// dropping it from the recipient hospital's index.
+pub(crate) fn release_reservation(
+    env: &Env,
+    mut unit: BloodUnit,
+    actor: &Address,
+    released_at: u64,
+) {
+    let unit_id = unit.id;
+    let hospital_id = unit.recipient_hospital.clone();
+    unit.status = BloodStatus::Available;
+    unit.recipient_hospital = None;
+    unit.allocation_timestamp = None;
+    save_unit(env, &unit);
+
+    reindex_status(env, unit_id, BloodStatus::Reserved, BloodStatus::Available);
+    resolve_reservation(env, unit_id, &unit.bank_id, released_at);
+    if let Some(ref hosp) = hospital_id {
+        deindex_hospital_unit(env, hosp, unit_id);
+    }
+
+    record_status_change(
+        env,
+        unit_id,
+        BloodStatus::Reserved,
+        BloodStatus::Available,
+        actor.clone(),
+    );
 
-        let mut units: Map<u64, BloodUnit> = env
+    publish_event(
+        env,
+        (
+            symbol_short!("blood"),
+            symbol_short!("cancel"),
+            symbol_short!("v1"),
+        ),
+        unit_id,
+    );
+}
+
+/// Close the open reservation window for `unit_id` and fold its duration into
+/// the bank's running average. Does nothing if no window is open.
+pub(crate) fn resolve_reservation(env: &Env, unit_id: u64, bank_id: &Address, resolved_at: u64) {
+    let key = DataKey::ReservationWindow(unit_id);
+    let mut window: ReservationWindow = match env.storage().persistent().get(&key) {
+        Some(window) => window,
+        None => return,
+    };
+    if window.resolved_at.is_some() {
+        return;
+    }
+    window.resolved_at = Some(resolved_at);
+    env.storage().persistent().set(&key, &window);
+    bump_persistent(env, &key);
+
+    let duration = resolved_at.saturating_sub(window.reserved_at);
+    let avg_key = DataKey::AvgReservationTime(bank_id.clone());
+    let mut average: ReservationAverage =
+        env.storage()
+            .persistent()
+            .get(&avg_key)
+            .unwrap_or(ReservationAverage {
+                samples: 0,
+                average_seconds: 0,
+            });
+    let total = (average.average_seconds as u128) * (average.samples as u128) + duration as u128;
+    average.samples = average.samples.saturating_add(1);
+    average.average_seconds = (total / average.samples as u128) as u64;
+    env.storage().persistent().set(&avg_key, &average);
+}
+
+#[contractimpl]
+impl HealthChainContract {
+    /// Get transfer history for a blood unit
+    pub fn get_transfer_history(env: Env, unit_id: u64) -> Vec<StatusChangeEvent> {
+        let history_key = (HISTORY, unit_id);
+        env.storage()
+            .persistent()
+            .get(&history_key)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Check if an address is an authorized hospital
+    pub fn is_hospital(env: Env, hospital_id: Address) -> bool {
+        let hospitals: Map<Address, LifecycleState> = env
             .storage()
             .persistent()
-            .get(&BLOOD_UNITS)
+            .get(&HOSPITALS)
             .unwrap_or(Map::new(&env));
 
-        let current_time = env.ledger().timestamp();
-        let mut total_quantity: u32 = 0;
+        hospitals
+            .get(hospital_id)
+            .unwrap_or(LifecycleState::Inactive)
+            == LifecycleState::Active
+    }
 
-        for i in 0..unit_ids.len() {
-            let unit_id = unit_ids.get(i).unwrap();
-            let unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+    /// Check if an address is a registered testing lab
+    pub fn is_lab(env: Env, lab_id: Address) -> bool {
+        let labs: Map<Address, LifecycleState> = env
+            .storage()
+            .persistent()
+            .get(&LABS)
+            .unwrap_or(Map::new(&env));
 
-            if unit.blood_type != request.blood_type {
-                return Err(Error::InvalidStatus);
-            }
+        labs.get(lab_id).unwrap_or(LifecycleState::Inactive) == LifecycleState::Active
+    }
 
-            if unit.status != BloodStatus::Available {
-                return Err(Error::InvalidStatus);
-            }
+    /// Helper: Derive deterministic event_id for custody transfers
+    /// Uses SHA256 hash of: unit_id + from_custodian + to_custodian + ledger_sequence
+    fn derive_event_id(
+        env: &Env,
+        unit_id: u64,
+        from_custodian: &Address,
+        to_custodian: &Address,
+    ) -> String {
+        use soroban_sdk::{Bytes, BytesN};
 
-            if unit.expiration_date <= current_time {
-                return Err(Error::UnitExpired);
-            }
+        let ledger_sequence = env.ledger().sequence();
 
-            total_quantity = total_quantity
-                .checked_add(unit.quantity)
-                .ok_or(Error::ArithmeticError)?;
+        // Create input bytes for hashing
+        let mut input = Bytes::new(env);
+
+        // Add unit_id (8 bytes)
+        for byte in unit_id.to_be_bytes().iter() {
+            input.push_back(*byte);
         }
 
-        // Reserve units to the requesting hospital.
-        for i in 0..unit_ids.len() {
-            let unit_id = unit_ids.get(i).unwrap();
-            let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
-            let old_status = unit.status;
+        // Add from_custodian as Val (8 bytes)
+        let from_val_u64: u64 = from_custodian.to_val().get_payload();
+        for byte in from_val_u64.to_be_bytes().iter() {
+            input.push_back(*byte);
+        }
 
-            unit.status = BloodStatus::Reserved;
-            unit.recipient_hospital = Some(request.hospital_id.clone());
-            unit.allocation_timestamp = Some(current_time);
+        // Add to_custodian as Val (8 bytes)
+        let to_val_u64: u64 = to_custodian.to_val().get_payload();
+        for byte in to_val_u64.to_be_bytes().iter() {
+            input.push_back(*byte);
+        }
 
-            units.set(unit_id, unit);
+        // Add ledger_sequence (4 bytes)
+        for byte in ledger_sequence.to_be_bytes().iter() {
+            input.push_back(*byte);
+        }
 
-            // Maintain status index
-            reindex_status(&env, unit_id, old_status, BloodStatus::Reserved);
+        // Compute SHA256 hash
+        let hash: BytesN<32> = env.crypto().sha256(&input).into();
 
-            record_status_change(
-                &env,
-                unit_id,
-                old_status,
-                BloodStatus::Reserved,
-                bank_id.clone(),
-            );
+        // Convert hash to hex string
+        let hex_chars = b"0123456789abcdef";
+        let mut hex_array = [0u8; HEX_HASH_LENGTH];
 
-            env.events().publish(
-                (
-                    symbol_short!("blood"),
-                    symbol_short!("allocate"),
-                    symbol_short!("v1"),
-                ),
-                (unit_id, request.hospital_id.clone(), current_time),
-            );
+        for i in 0..32u32 {
+            let byte = hash.get(i).unwrap();
+            let high = (byte >> 4) & 0x0f;
+            let low = byte & 0x0f;
+            hex_array[(i * 2) as usize] = hex_chars[high as usize];
+            hex_array[(i * 2 + 1) as usize] = hex_chars[low as usize];
         }
 
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
+        String::from_bytes(env, &hex_array)
+    }
 
-        let old_status = request.status;
-        request.reserved_unit_ids = unit_ids.clone();
-        request.fulfilled_quantity_ml = total_quantity;
-        request.status = if total_quantity >= request.quantity_ml {
-            RequestStatus::Approved
-        } else {
-            RequestStatus::InProgress
-        };
+    /// Public function to compute event_id for a given transfer
+    /// Callers can use this to compute the event_id needed for confirm_transfer and cancel_transfer
+    pub fn compute_event_id(
+        env: Env,
+        unit_id: u64,
+        from_custodian: Address,
+        to_custodian: Address,
+        ledger_sequence: u32,
+    ) -> String {
+        use soroban_sdk::{Bytes, BytesN};
 
-        requests.set(request_id, request.clone());
-        env.storage().persistent().set(&REQUESTS, &requests);
+        // Create input bytes for hashing
+        let mut input = Bytes::new(&env);
 
-        record_request_status_change(
-            &env,
-            request_id,
-            old_status,
-            request.status,
-            bank_id.clone(),
-            None,
-        );
+        // Add unit_id (8 bytes)
+        for byte in unit_id.to_be_bytes().iter() {
+            input.push_back(*byte);
+        }
 
-        env.events().publish(
-            (
-                symbol_short!("request"),
-                symbol_short!("approve"),
-                symbol_short!("v1"),
-            ),
-            RequestApprovedEvent {
-                request_id,
-                blood_bank: bank_id,
-                assigned_unit_ids: unit_ids,
-                total_quantity_ml: total_quantity,
-                fulfillment_percentage: Self::calculate_fulfillment_percentage(
-                    request.quantity_ml,
-                    total_quantity,
-                )?,
-                status: request.status,
-            },
-        );
+        // Add from_custodian as Val (8 bytes)
+        let from_val_u64: u64 = from_custodian.to_val().get_payload();
+        for byte in from_val_u64.to_be_bytes().iter() {
+            input.push_back(*byte);
+        }
 
-        Ok(())
+        // Add to_custodian as Val (8 bytes)
+        let to_val_u64: u64 = to_custodian.to_val().get_payload();
+        for byte in to_val_u64.to_be_bytes().iter() {
+            input.push_back(*byte);
+        }
+
+        // Add ledger_sequence (4 bytes)
+        for byte in ledger_sequence.to_be_bytes().iter() {
+            input.push_back(*byte);
+        }
+
+        // Compute SHA256 hash
+        let hash: BytesN<32> = env.crypto().sha256(&input).into();
+
+        // Convert hash to hex string
+        let hex_chars = b"0123456789abcdef";
+        let mut hex_array = [0u8; HEX_HASH_LENGTH];
+
+        for i in 0..32u32 {
+            let byte = hash.get(i).unwrap();
+            let high = (byte >> 4) & 0x0f;
+            let low = byte & 0x0f;
+            hex_array[(i * 2) as usize] = hex_chars[high as usize];
+            hex_array[(i * 2 + 1) as usize] = hex_chars[low as usize];
+        }
+
+        String::from_bytes(&env, &hex_array)
     }
 
-    /// Cancel blood request
-    pub fn cancel_request(env: Env, request_id: u64, reason: String) -> Result<(), Error> {
-        let mut requests: Map<u64, BloodRequest> = env
+    /// Get custody event by event_id
+    pub fn get_custody_event(env: Env, event_id: String) -> Result<CustodyEvent, Error> {
+        let custody_events: Map<String, CustodyEvent> = env
             .storage()
             .persistent()
-            .get(&REQUESTS)
+            .get(&CUSTODY_EVENTS)
             .unwrap_or(Map::new(&env));
 
-        let mut request = requests.get(request_id).ok_or(Error::UnitNotFound)?;
-
-        // Authorization: only hospital that created the request or blood bank can cancel
-        let caller = env.current_contract_address();
-        let is_hospital =
-            HealthChainContract::is_hospital(env.clone(), request.hospital_id.clone());
-        let is_bank = HealthChainContract::is_blood_bank(env.clone(), caller.clone());
+        custody_events.get(event_id).ok_or(Error::UnitNotFound)
+    }
 
-        if !is_hospital && !is_bank {
-            return Err(Error::Unauthorized);
-        }
+    /// Get custody trail for a blood unit with pagination
+    /// Returns all confirmed custody event IDs for the specified page
+    pub fn get_custody_trail(
+        env: Env,
+        unit_id: u64,
+        page_number: u32,
+    ) -> Result<Vec<String>, Error> {
+        let meta_key = DataKey::UnitTrailMeta(unit_id);
+        let metadata: TrailMetadata =
+            env.storage()
+                .persistent()
+                .get(&meta_key)
+                .unwrap_or(TrailMetadata {
+                    total_events: 0,
+                    total_pages: 0,
+                });
 
-        // Can only cancel if Pending, Approved, or InProgress
-        if request.status == RequestStatus::Fulfilled || request.status == RequestStatus::Cancelled
-        {
-            return Err(Error::InvalidStatus);
+        if metadata.total_pages > 0 && page_number >= metadata.total_pages {
+            return Err(Error::PageNotFound);
         }
 
-        let old_status = request.status;
-        request.status = RequestStatus::Cancelled;
-
-        // Capture released unit IDs before clearing the vector
-        let released_unit_ids = request.reserved_unit_ids.clone();
+        let page_key = DataKey::UnitTrailPage(unit_id, page_number);
 
-        // Release reserved units
-        let mut units: Map<u64, BloodUnit> = env
+        let page: Vec<String> = env
             .storage()
             .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
+            .get(&page_key)
+            .unwrap_or(Vec::new(&env));
 
-        for i in 0..request.reserved_unit_ids.len() {
-            let unit_id = request.reserved_unit_ids.get(i).unwrap();
-            if let Some(mut unit) = units.get(unit_id) {
-                if unit.status == BloodStatus::Reserved {
-                    let old_unit_status = unit.status;
-                    unit.status = BloodStatus::Available;
-                    unit.recipient_hospital = None;
-                    unit.allocation_timestamp = None;
-                    units.set(unit_id, unit);
-                    // Maintain status index
-                    reindex_status(&env, unit_id, old_unit_status, BloodStatus::Available);
+        Ok(page)
+    }
+
+    /// Page through a unit's custody trail independent of storage page size.
+    ///
+    /// `cursor` is the zero-based position of the first event to return; pass
+    /// the previous page's `next_cursor` to continue. `limit` is clamped by
+    /// [`clamp_limit`].
+    pub fn get_custody_trail_page(env: Env, unit_id: u64, cursor: u64, limit: u32) -> TrailPage {
+        let metadata = Self::get_custody_trail_metadata(env.clone(), unit_id);
+        let total = metadata.total_events as u64;
+        let limit = clamp_limit(limit) as u64;
+
+        let per_page = MAX_EVENTS_PER_PAGE as u64;
+        let mut items = Vec::new(&env);
+        let mut position = cursor;
+        let mut page_number = (cursor / per_page) as u32;
+        let mut offset = (cursor % per_page) as u32;
+        while position < total && (items.len() as u64) < limit {
+            let page: Vec<String> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::UnitTrailPage(unit_id, page_number))
+                .unwrap_or(Vec::new(&env));
+            if offset >= page.len() {
+                break;
+            }
+            for event_id in page.iter().skip(offset as usize) {
+                if (items.len() as u64) >= limit {
+                    break;
                 }
+                items.push_back(event_id);
+                position += 1;
             }
+            page_number += 1;
+            offset = 0;
         }
 
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
-        request.reserved_unit_ids = vec![&env];
+        TrailPage {
+            items,
+            next_cursor: (position < total).then_some(position),
+        }
+    }
 
-        requests.set(request_id, request);
-        env.storage().persistent().set(&REQUESTS, &requests);
+    /// Page through the full custody events recorded for a unit.
+    ///
+    /// Reads the per-unit `UnitCustodyEvents` index, so every transfer is
+    /// included whatever its status. `offset` is the zero-based position of the
+    /// first event; `limit` is clamped by [`clamp_limit`]. Use `total` to work
+    /// out how many pages remain.
+    pub fn get_custody_events(env: Env, unit_id: u64, offset: u32, limit: u32) -> CustodyEventPage {
+        let event_ids: Vec<String> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UnitCustodyEvents(unit_id))
+            .unwrap_or(Vec::new(&env));
+        let custody_events: Map<String, CustodyEvent> = env
+            .storage()
+            .persistent()
+            .get(&CUSTODY_EVENTS)
+            .unwrap_or(Map::new(&env));
 
-        let current_time = env.ledger().timestamp();
+        let total = event_ids.len();
+        let end = offset.saturating_add(clamp_limit(limit)).min(total);
+        let mut items = Vec::new(&env);
+        for i in offset..end {
+            if let Some(event) = custody_events.get(event_ids.get(i).unwrap()) {
+                items.push_back(event);
+            }
+        }
 
-        // Record and emit status change (for backward compatibility)
-        record_request_status_change(
-            &env,
-            request_id,
-            old_status,
-            RequestStatus::Cancelled,
-            caller.clone(),
-            Some(reason.clone()),
-        );
+        CustodyEventPage { items, total }
+    }
 
-        // Emit dedicated cancellation event with explicit unit release information
-        env.events().publish(
-            (
-                symbol_short!("request"),
-                symbol_short!("cancel"),
-                symbol_short!("v1"),
-            ),
-            RequestCancellationEvent {
-                request_id,
-                actor: caller,
-                cancellation_reason: reason,
-                reason_code: CancellationReason::ExplicitCancellation,
-                released_unit_ids,
-                cancellation_timestamp: current_time,
-            },
-        );
+    /// Commit a Merkle root over the unit's confirmed custody events.
+    ///
+    /// Callable by anyone: the root is derived purely from on-chain events.
+    /// Fails with `InvalidStatus` while the unit has no confirmed transfers.
+    ///
+    /// Delegates to [`custody_proof::commit`].
+    pub fn commit_custody_root(env: Env, unit_id: u64) -> Result<BytesN<32>, Error> {
+        load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
+        custody_proof::commit(&env, unit_id)
+    }
 
-        Ok(())
+    /// Latest committed custody root for a unit.
+    pub fn get_custody_root(env: Env, unit_id: u64) -> Option<custody_proof::CustodyRoot> {
+        custody_proof::load_root(&env, unit_id)
     }
 
-    /// Fulfill blood request
-    pub fn fulfill_request(
+    /// Check a custody event's leaf hash against the unit's committed root.
+    ///
+    /// Delegates to [`custody_proof::verify`].
+    pub fn verify_custody_proof(
         env: Env,
-        bank_id: Address,
-        request_id: u64,
-        unit_ids: Vec<u64>,
-    ) -> Result<(), Error> {
-        bank_id.require_auth();
+        unit_id: u64,
+        leaf_hash: BytesN<32>,
+        proof: Vec<BytesN<32>>,
+    ) -> Result<bool, Error> {
+        custody_proof::verify(&env, unit_id, leaf_hash, proof)
+    }
 
-        let mut requests: Map<u64, BloodRequest> = env
-            .storage()
+    /// Get aggregate custody event counts as `(pending, confirmed, cancelled)`.
+    ///
+    /// Counters are maintained incrementally on every custody transition, so
+    /// this is O(1) regardless of how many events exist.
+    pub fn get_custody_event_counts(env: Env) -> (u32, u32, u32) {
+        env.storage()
+            .instance()
+            .get(&CUSTODY_COUNTS)
+            .unwrap_or((0, 0, 0))
+    }
+
+    /// Get custody trail metadata for a blood unit
+    pub fn get_custody_trail_metadata(env: Env, unit_id: u64) -> TrailMetadata {
+        let meta_key = DataKey::UnitTrailMeta(unit_id);
+        env.storage()
             .persistent()
-            .get(&REQUESTS)
-            .unwrap_or(Map::new(&env));
+            .get(&meta_key)
+            .unwrap_or(TrailMetadata {
+                total_events: 0,
+                total_pages: 0,
+            })
+    }
 
-        let mut request = requests.get(request_id).ok_or(Error::UnitNotFound)?;
+    /// Migrate existing unbounded custody trail to paginated format (admin only)
+    /// This is a one-time migration function for units that may have old trail data
+    pub fn migrate_trail_index(env: Env, unit_id: u64) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
 
-        if !HealthChainContract::is_blood_bank(env.clone(), bank_id.clone()) {
-            return Err(Error::Unauthorized);
+        // Check if already migrated
+        let meta_key = DataKey::UnitTrailMeta(unit_id);
+        if env.storage().persistent().has(&meta_key) {
+            // Already migrated, nothing to do
+            return Ok(());
         }
 
-        // Can only fulfill if Approved or InProgress
-        if request.status != RequestStatus::Approved && request.status != RequestStatus::InProgress
-        {
-            return Err(Error::InvalidStatus);
-        }
+        // For this implementation, we assume there's no legacy unbounded Vec to migrate
+        // If there was a legacy storage key like DataKey::UnitTrail(unit_id) -> Vec<String>,
+        // we would:
+        // 1. Load the old Vec
+        // 2. Split it into pages of MAX_EVENTS_PER_PAGE
+        // 3. Store each page with DataKey::UnitTrailPage(unit_id, page_number)
+        // 4. Create and store metadata
+        // 5. Delete the old storage entry
 
-        if request.reserved_unit_ids.len() > 0 && request.reserved_unit_ids != unit_ids {
-            return Err(Error::InvalidStatus);
-        }
+        // Since we're implementing this fresh, we just initialize empty metadata
+        let metadata = TrailMetadata {
+            total_events: 0,
+            total_pages: 0,
+        };
+        env.storage().persistent().set(&meta_key, &metadata);
 
-        // Validate delivery quantity before mutating any unit or request state.
-        let mut units: Map<u64, BloodUnit> = env
+        Ok(())
+    }
+
+    /// Move up to `limit` units from the legacy `UNITS` map into per-unit
+    /// `DataKey::Unit` entries (admin only).
+    ///
+    /// Returns the number of units moved; call repeatedly until it returns 0.
+    /// The legacy key is removed once the map is empty. An existing
+    /// `DataKey::Unit` entry is never overwritten by the legacy copy.
+    pub fn migrate_units(env: Env, limit: u32) -> Result<u32, Error> {
+        let admin: Address = env
             .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
 
-        let mut delivered_quantity: u32 = 0;
+        Ok(migrate_legacy_units(&env, limit))
+    }
 
-        for i in 0..unit_ids.len() {
-            let unit_id = unit_ids.get(i).unwrap();
-            let unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+    /// Replace the contract's code with the uploaded `new_wasm_hash` (admin only).
+    ///
+    /// Stored data is untouched; if the new code expects a newer storage
+    /// layout, follow up with [`Self::migrate`].
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
 
-            // Verify unit is reserved for this hospital
-            if unit.recipient_hospital != Some(request.hospital_id.clone()) {
-                return Err(Error::Unauthorized);
-            }
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
 
-            if unit.status != BloodStatus::Reserved && unit.status != BloodStatus::InTransit {
-                return Err(Error::InvalidStatus);
-            }
+        publish_event(
+            &env,
+            (symbol_short!("contract"), symbol_short!("upgrade")),
+            new_wasm_hash,
+        );
 
-            delivered_quantity = delivered_quantity
-                .checked_add(unit.quantity)
-                .ok_or(Error::ArithmeticError)?;
+        Ok(())
+    }
+
+    /// Advance stored data from layout `from_version` by one bounded batch
+    /// (admin only).
+    ///
+    /// `from_version` must equal [`Self::get_storage_version`], so a stale or
+    /// replayed call cannot re-run a finished step. From
+    /// `LEGACY_STORAGE_VERSION`, up to `limit` units are walked out of the
+    /// legacy `UNITS` map; the stored version moves to
+    /// `CURRENT_STORAGE_VERSION` once the map is drained. Returns the number
+    /// of entries moved; call repeatedly until the version advances.
+    pub fn migrate(env: Env, from_version: u32, limit: u32) -> Result<u32, Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+
+        if from_version != Self::get_storage_version(env.clone()) {
+            return Err(Error::VersionMismatch);
         }
 
-        if delivered_quantity > request.quantity_ml {
-            return Err(Error::InvalidQuantity);
+        match from_version {
+            LEGACY_STORAGE_VERSION => Ok(migrate_legacy_units(&env, limit)),
+            _ => Err(Error::VersionMismatch),
         }
+    }
 
-        for i in 0..unit_ids.len() {
-            let unit_id = unit_ids.get(i).unwrap();
-            let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+    /// Storage layout version of this deployment. Contracts that predate
+    /// versioning report `LEGACY_STORAGE_VERSION`.
+    pub fn get_storage_version(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&STORAGE_VERSION)
+            .unwrap_or(LEGACY_STORAGE_VERSION)
+    }
 
-            // Update to delivered
-            let old_status = unit.status;
-            unit.status = BloodStatus::Delivered;
-            let current_time = env.ledger().timestamp();
-            unit.delivery_timestamp = Some(current_time);
+    /// Create a blood request (hospital only)
+    pub fn create_request(
+        env: Env,
+        hospital_id: Address,
+        blood_type: BloodType,
+        quantity_ml: u32,
+        urgency: UrgencyLevel,
+        required_by: u64,
+        delivery_address: String,
+    ) -> Result<u64, Error> {
+        hospital_id.require_auth();
 
-            units.set(unit_id, unit.clone());
+        if !Self::is_hospital(env.clone(), hospital_id.clone()) {
+            return Err(Error::Unauthorized);
+        }
 
-            // Maintain status index
-            reindex_status(&env, unit_id, old_status, BloodStatus::Delivered);
+        if !(MIN_REQUEST_ML..=MAX_REQUEST_ML).contains(&quantity_ml) {
+            return Err(Error::InvalidQuantity);
+        }
 
-            // Record blood unit status change
-            record_status_change(
-                &env,
-                unit_id,
-                old_status,
-                BloodStatus::Delivered,
-                bank_id.clone(),
-            );
+        if delivery_address.is_empty() {
+            return Err(Error::InvalidDeliveryAddress);
         }
 
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
+        if delivery_address.len() > MAX_DELIVERY_ADDRESS_LENGTH {
+            return Err(Error::DeliveryAddressTooLong);
+        }
 
-        // Update request
-        let old_status = request.status;
-        request.status = if delivered_quantity == request.quantity_ml {
-            RequestStatus::Fulfilled
-        } else {
-            RequestStatus::InProgress
-        };
-        request.fulfilled_quantity_ml = delivered_quantity;
-        request.fulfillment_timestamp = if request.status == RequestStatus::Fulfilled {
-            Some(env.ledger().timestamp())
-        } else {
-            None
+        let current_time = env.ledger().timestamp();
+        if required_by <= current_time {
+            return Err(Error::InvalidRequiredBy);
+        }
+
+        // Normalize dedup semantics by excluding free-form delivery address text:
+        // equivalent logical requests should map to one key even if address case/spacing differs.
+        let request_key = RequestKey {
+            hospital_id: hospital_id.clone(),
+            blood_type,
+            quantity_ml,
+            urgency,
+            required_by,
         };
-        request.reserved_unit_ids = unit_ids.clone();
 
-        requests.set(request_id, request.clone());
-        env.storage().persistent().set(&REQUESTS, &requests);
+        let mut request_keys: Map<RequestKey, u64> = env
+            .storage()
+            .persistent()
+            .get(&REQUEST_KEYS)
+            .unwrap_or(Map::new(&env));
 
-        if old_status != request.status {
-            record_request_status_change(
-                &env,
-                request_id,
-                old_status,
-                request.status,
-                bank_id.clone(),
-                None,
-            );
+        if request_keys.get(request_key.clone()).is_some() {
+            return Err(Error::DuplicateRequest);
         }
 
-        env.events().publish(
-            (
-                symbol_short!("request"),
-                symbol_short!("fulfill"),
-                symbol_short!("v1"),
-            ),
-            RequestFulfilledEvent {
-                request_id,
-                blood_bank: bank_id,
-                delivered_unit_ids: unit_ids,
-                delivered_quantity_ml: delivered_quantity,
-                fulfilled_at: env.ledger().timestamp(),
-            },
-        );
-
-        Ok(())
-    }
+        let request_id = get_next_request_id(&env);
 
-    /// Helper: Validate status transitions
-    fn is_valid_status_transition(old_status: &RequestStatus, new_status: &RequestStatus) -> bool {
-        match (old_status, new_status) {
-            // From Pending
-            (RequestStatus::Pending, RequestStatus::Approved) => true,
-            (RequestStatus::Pending, RequestStatus::Rejected) => true,
-            (RequestStatus::Pending, RequestStatus::Cancelled) => true,
+        let request = BloodRequest {
+            id: request_id,
+            hospital_id: hospital_id.clone(),
+            blood_type,
+            quantity_ml,
+            urgency,
+            required_by,
+            delivery_address: delivery_address.clone(),
+            created_at: current_time,
+            status: RequestStatus::Pending,
+            fulfilled_quantity_ml: 0,
+            fulfillment_timestamp: None,
+            reserved_unit_ids: vec![&env],
+        };
 
-            // From Approved
-            (RequestStatus::Approved, RequestStatus::InProgress) => true,
-            (RequestStatus::Approved, RequestStatus::Cancelled) => true,
+        let mut requests: Map<u64, BloodRequest> = env
+            .storage()
+            .persistent()
+            .get(&REQUESTS)
+            .unwrap_or(Map::new(&env));
 
-            // From InProgress
-            (RequestStatus::InProgress, RequestStatus::Fulfilled) => true,
-            (RequestStatus::InProgress, RequestStatus::Cancelled) => true,
+        requests.set(request_id, request);
+        env.storage().persistent().set(&REQUESTS, &requests);
 
-            // No transitions from terminal states
-            (RequestStatus::Fulfilled, _) => false,
-            (RequestStatus::Cancelled, _) => false,
-            (RequestStatus::Rejected, _) => false,
+        request_keys.set(request_key, request_id);
+        env.storage().persistent().set(&REQUEST_KEYS, &request_keys);
 
-            // Any other transition is invalid
-            _ => false,
-        }
-    }
+        let event = RequestCreatedEvent {
+            request_id,
+            hospital_id,
+            blood_type,
+            quantity_ml,
+            urgency,
+            required_by,
+            delivery_address,
+            created_at: current_time,
+        };
 
-    fn calculate_fulfillment_percentage(
-        requested_quantity: u32,
-        fulfilled_quantity: u32,
-    ) -> Result<u32, Error> {
-        if requested_quantity == 0 {
-            return Ok(0);
-        }
+        publish_event(
+            &env,
+            (
+                symbol_short!("blood"),
+                symbol_short!("request"),
+                symbol_short!("v1"),
+            ),
+            event,
+        );
 
-        let percentage = fulfilled_quantity
-            .checked_mul(100)
-            .ok_or(Error::ArithmeticError)?
-            / requested_quantity;
-        Ok(percentage.min(100))
+        Ok(request_id)
     }
 
-    // ── SUPER ADMIN TWO-STEP TRANSFER ────────────────────────────────────────────────────
+    /// Create a payment for a request and persist its escrow account with release conditions.
+    pub fn create_payment(
+        env: Env,
+        request_id: u64,
+        payer: Address,
+        payee: Address,
+        amount: i128,
+        asset: Address,
+        fee_payload: FeeStructure,
+        backend_auth: Address,
+    ) -> Result<u64, Error> {
+        payer.require_auth();
+        backend_auth.require_auth();
 
-    /// Nominate a new SuperAdmin (current admin only).
-    ///
-    /// Clears any expired pending nomination before checking for an active one.
-    /// Emits `AdminProposedEvent` on success.
-    pub fn nominate_super_admin(env: Env, nominee: Address) -> Result<(), Error> {
         let admin: Address = env
             .storage()
             .instance()
             .get(&ADMIN)
             .ok_or(Error::Unauthorized)?;
-        admin.require_auth();
 
-        let now = env.ledger().timestamp();
+        if backend_auth != admin {
+            return Err(Error::Unauthorized);
+        }
 
-        // Lazily clear an expired nomination so a new one can be made.
-        if let Some(entry) = env
+        if let Err(_) = fee_payload.validate() {
+            return Err(Error::InvalidFeePayload);
+        }
+
+        let mut payments: Map<u64, Payment> = env
+            .storage()
+            .persistent()
+            .get(&PAYMENTS)
+            .unwrap_or(Map::new(&env));
+
+        let payment_id = env
             .storage()
             .instance()
-            .get::<DataKey, NominationEntry>(&DataKey::PendingNominee)
-        {
-            let expired = now > entry.nominated_at.saturating_add(NOMINATION_EXPIRY_SECONDS);
-            if !expired {
-                return Err(Error::NominationPending);
-            }
-            env.storage().instance().remove(&DataKey::PendingNominee);
+            .get(&NEXT_PAYMENT_ID)
+            .unwrap_or(1u64);
+
+        let payment = Payment {
+            id: payment_id,
+            request_id,
+            payer,
+            payee,
+            amount,
+            asset,
+            fee_structure: fee_payload,
+            status: PaymentStatus::Pending,
+            escrow_released_at: None,
+        };
+
+        if let Err(_) = payment.validate() {
+            return Err(Error::StorageError);
         }
 
-        env.storage().instance().set(
-            &DataKey::PendingNominee,
-            &NominationEntry {
-                nominee: nominee.clone(),
-                nominated_at: now,
+        // Persist escrow account with default release conditions at payment setup time.
+        // Callers may update conditions via set_escrow_conditions before release.
+        let escrow = EscrowAccount {
+            payment_id,
+            locked_amount: amount,
+            release_conditions: ReleaseConditions {
+                medical_records_verified: false,
+                min_timestamp: 0,
+                authorized_approver: None,
             },
-        );
+        };
+        let mut escrow_accounts: Map<u64, EscrowAccount> = env
+            .storage()
+            .persistent()
+            .get(&ESCROW_ACCOUNTS)
+            .unwrap_or(Map::new(&env));
+        escrow_accounts.set(payment_id, escrow);
+        env.storage()
+            .persistent()
+            .set(&ESCROW_ACCOUNTS, &escrow_accounts);
 
-        env.events().publish(
-            (symbol_short!("admin"), symbol_short!("proposed")),
-            AdminProposedEvent {
-                current_admin: admin,
-                proposed_admin: nominee,
-                nominated_at: now,
-                expires_at: now.saturating_add(NOMINATION_EXPIRY_SECONDS),
-            },
-        );
+        payments.set(payment_id, payment);
+        env.storage().persistent().set(&PAYMENTS, &payments);
+        env.storage()
+            .instance()
+            .set(&NEXT_PAYMENT_ID, &(payment_id + 1));
 
-        Ok(())
+        Ok(payment_id)
     }
 
-    /// Accept a pending SuperAdmin nomination (nominee only).
-    ///
-    /// Fails with `NominationExpired` if the 24-hour window has passed.
-    /// Emits `AdminTransferredEvent` on success.
-    pub fn accept_super_admin(env: Env) -> Result<(), Error> {
-        let entry: NominationEntry = env
+    /// Update the release conditions for an escrowed payment (admin only).
+    pub fn set_escrow_conditions(
+        env: Env,
+        payment_id: u64,
+        medical_records_verified: bool,
+        min_timestamp: u64,
+        authorized_approver: Option<Address>,
+    ) -> Result<(), Error> {
+        let admin: Address = env
             .storage()
             .instance()
-            .get(&DataKey::PendingNominee)
+            .get(&ADMIN)
             .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
 
-        entry.nominee.require_auth();
+        let mut escrow_accounts: Map<u64, EscrowAccount> = env
+            .storage()
+            .persistent()
+            .get(&ESCROW_ACCOUNTS)
+            .ok_or(Error::PaymentNotFound)?;
 
-        let now = env.ledger().timestamp();
-        if now > entry.nominated_at.saturating_add(NOMINATION_EXPIRY_SECONDS) {
-            return Err(Error::NominationExpired);
-        }
+        let mut escrow = escrow_accounts
+            .get(payment_id)
+            .ok_or(Error::PaymentNotFound)?;
+        escrow.release_conditions = ReleaseConditions {
+            medical_records_verified,
+            min_timestamp,
+            authorized_approver,
+        };
+        escrow_accounts.set(payment_id, escrow);
+        env.storage()
+            .persistent()
+            .set(&ESCROW_ACCOUNTS, &escrow_accounts);
+        Ok(())
+    }
 
-        let previous_admin: Address = env
+    /// Configure the dispute timeout window in seconds (admin only).
+    pub fn set_dispute_timeout(env: Env, timeout_secs: u64) -> Result<(), Error> {
+        let admin: Address = env
             .storage()
             .instance()
             .get(&ADMIN)
             .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
 
-        env.storage().instance().set(&ADMIN, &entry.nominee);
-        env.storage().instance().remove(&DataKey::PendingNominee);
+        env.storage()
+            .instance()
+            .set(&DISPUTE_TIMEOUT, &timeout_secs);
+        Ok(())
+    }
 
-        env.events().publish(
-            (symbol_short!("admin"), symbol_short!("xfer")),
-            AdminTransferredEvent {
-                previous_admin,
-                new_admin: entry.nominee,
-                transferred_at: now,
-            },
-        );
+    /// Configure M-of-N multisig signers for high-value escrow releases.
+    pub fn configure_multisig(
+        env: Env,
+        signers: Vec<Address>,
+        threshold: u32,
+    ) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+
+        let config = MultiSigConfig { signers, threshold };
+        config
+            .validate()
+            .map_err(|_| Error::InvalidMultiSigConfig)?;
 
+        env.storage().persistent().set(&MULTISIG_CONFIG, &config);
         Ok(())
     }
 
-    /// Cancel a pending nomination (current admin only).
-    /// Emits `AdminNominationCancelledEvent` if a nomination was present.
-    pub fn cancel_nomination(env: Env) -> Result<(), Error> {
+    /// Read the active dispute timeout, falling back to the default 72h window.
+    pub fn get_dispute_timeout(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DISPUTE_TIMEOUT)
+            .unwrap_or(DEFAULT_DISPUTE_TIMEOUT_SECS)
+    }
+
+    /// Read aggregate payment stats for auto-refunded disputes.
+    pub fn get_payment_stats(env: Env) -> PaymentStats {
+        env.storage()
+            .persistent()
+            .get(&PAYMENT_STATS)
+            .unwrap_or(PaymentStats::new())
+    }
+
+    /// Link a payment to the blood unit whose delivery it pays for (admin only).
+    ///
+    /// The link lets `claim_timed_out_payment` find the payment from the unit's
+    /// custody state.
+    pub fn link_unit_payment(env: Env, unit_id: u64, payment_id: u64) -> Result<(), Error> {
         let admin: Address = env
             .storage()
             .instance()
@@ -3667,2035 +5977,6189 @@ impl HealthChainContract {
             .ok_or(Error::Unauthorized)?;
         admin.require_auth();
 
-        if let Some(entry) = env
-            .storage()
-            .instance()
-            .get::<DataKey, NominationEntry>(&DataKey::PendingNominee)
-        {
-            env.storage().instance().remove(&DataKey::PendingNominee);
-            env.events().publish(
-                (symbol_short!("admin"), symbol_short!("nom_cxl")),
-                AdminNominationCancelledEvent {
-                    cancelled_by: admin,
-                    cancelled_nominee: entry.nominee,
-                    cancelled_at: env.ledger().timestamp(),
-                },
-            );
-        }
+        let payments: Map<u64, Payment> = env
+            .storage()
+            .persistent()
+            .get(&PAYMENTS)
+            .ok_or(Error::PaymentNotFound)?;
+        if !payments.contains_key(payment_id) {
+            return Err(Error::PaymentNotFound);
+        }
+        registry_read::get_unit(&env, unit_id)?;
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::UnitPayment(unit_id), &payment_id);
+        Ok(())
+    }
+
+    /// Refund the payer of a unit's payment once its custody transfer has timed out.
+    ///
+    /// Delegates to [`payments::claim_timed_out_payment`].
+    pub fn claim_timed_out_payment(env: Env, unit_id: u64) -> Result<(), Error> {
+        payments::claim_timed_out_payment(&env, unit_id)
+    }
+
+    /// Propose an escrow release.
+    ///
+    /// Escrow conditions (medical records, min timestamp, optional approver) are
+    /// evaluated first for every payment.  Multisig approval is additive — it is
+    /// required on top of the escrow conditions for high-value payments, not
+    /// instead of them.
+    pub fn propose_release(env: Env, payment_id: u64, approver: Address) -> Result<bool, Error> {
+        approver.require_auth();
+
+        let mut payments: Map<u64, Payment> = env
+            .storage()
+            .persistent()
+            .get(&PAYMENTS)
+            .ok_or(Error::PaymentNotFound)?;
+
+        let mut payment = payments.get(payment_id).ok_or(Error::PaymentNotFound)?;
+        if !payment.can_transition_to(PaymentStatus::Completed) {
+            return Err(Error::InvalidPaymentStatus);
+        }
+
+        // Enforce escrow release conditions before any payout path.
+        let escrow_accounts: Map<u64, EscrowAccount> = env
+            .storage()
+            .persistent()
+            .get(&ESCROW_ACCOUNTS)
+            .unwrap_or(Map::new(&env));
+        let escrow = escrow_accounts
+            .get(payment_id)
+            .ok_or(Error::PaymentNotFound)?;
+        let current_timestamp = env.ledger().timestamp();
+        if !escrow.can_release(current_timestamp, Some(&approver)) {
+            return Err(Error::EscrowNotReleasable);
+        }
+
+        let mut pending_approvals: Map<u64, PendingApproval> = env
+            .storage()
+            .persistent()
+            .get(&PENDING_APPROVALS)
+            .unwrap_or(Map::new(&env));
+
+        if payment.amount < HIGH_VALUE_THRESHOLD {
+            let admin: Address = env
+                .storage()
+                .instance()
+                .get(&ADMIN)
+                .ok_or(Error::Unauthorized)?;
+            if approver != admin {
+                return Err(Error::Unauthorized);
+            }
+
+            payment.status = PaymentStatus::Completed;
+            payment.escrow_released_at = Some(current_timestamp);
+            payments.set(payment_id, payment);
+            env.storage().persistent().set(&PAYMENTS, &payments);
+            pending_approvals.remove(payment_id);
+            env.storage()
+                .persistent()
+                .set(&PENDING_APPROVALS, &pending_approvals);
+            return Ok(true);
+        }
+
+        let config: MultiSigConfig = env
+            .storage()
+            .persistent()
+            .get(&MULTISIG_CONFIG)
+            .ok_or(Error::InvalidMultiSigConfig)?;
+        config
+            .validate()
+            .map_err(|_| Error::InvalidMultiSigConfig)?;
+
+        if !config.is_signer(&approver) {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut approval = pending_approvals
+            .get(payment_id)
+            .unwrap_or(PendingApproval::new(&env, payment_id));
+
+        approval
+            .register_vote(approver)
+            .map_err(|_| Error::DuplicateApproval)?;
+
+        if approval.has_reached_threshold(config.threshold) {
+            approval.executed = true;
+            payment.status = PaymentStatus::Completed;
+            payment.escrow_released_at = Some(current_timestamp);
+            payments.set(payment_id, payment);
+            env.storage().persistent().set(&PAYMENTS, &payments);
+        }
+
+        pending_approvals.set(payment_id, approval.clone());
+        env.storage()
+            .persistent()
+            .set(&PENDING_APPROVALS, &pending_approvals);
+
+        Ok(approval.executed)
+    }
+
+    /// Raise a dispute for a payment
+    pub fn raise_dispute(
+        env: Env,
+        payment_id: u64,
+        raised_by: Address,
+        reason: String,
+        evidence_digest: Bytes,
+        evidence_ref_chunks: Vec<String>,
+    ) -> Result<u64, Error> {
+        raised_by.require_auth();
+
+        // evidence_ref_chunks reconstruction against evidence_digest is off-chain only;
+        // enforce a valid SHA-256 digest size to reject trivially invalid submissions.
+        if evidence_digest.len() != 32 {
+            return Err(Error::InvalidFeePayload);
+        }
+
+        let mut payments: Map<u64, Payment> = env
+            .storage()
+            .persistent()
+            .get(&PAYMENTS)
+            .ok_or(Error::PaymentNotFound)?;
+
+        let mut payment = payments.get(payment_id).ok_or(Error::PaymentNotFound)?;
+
+        if !payment.can_transition_to(PaymentStatus::Disputed) {
+            return Err(Error::InvalidTransition);
+        }
+
+        let dispute_id = env
+            .storage()
+            .instance()
+            .get(&NEXT_DISPUTE_ID)
+            .unwrap_or(1u64);
+
+        let dispute = Dispute {
+            id: dispute_id,
+            payment_id,
+            raised_by: raised_by.clone(),
+            status: DisputeStatus::Open,
+            reason: reason.clone(),
+            evidence_digest: evidence_digest.clone(),
+            evidence_ref_chunks: evidence_ref_chunks.clone(),
+            raised_at: env.ledger().timestamp(),
+            resolved_at: None,
+        };
+        let dispute_deadline = env.ledger().timestamp() + Self::get_dispute_timeout(env.clone());
+        let metadata = DisputeMetadata {
+            dispute_id,
+            dispute_deadline,
+        };
+
+        payment.status = PaymentStatus::Disputed;
+        payments.set(payment_id, payment.clone());
+        env.storage().persistent().set(&PAYMENTS, &payments);
+
+        let mut disputes: Map<u64, Dispute> = env
+            .storage()
+            .persistent()
+            .get(&DISPUTES)
+            .unwrap_or(Map::new(&env));
+
+        disputes.set(dispute_id, dispute);
+        env.storage().persistent().set(&DISPUTES, &disputes);
+        let mut dispute_metadata: Map<u64, DisputeMetadata> = env
+            .storage()
+            .persistent()
+            .get(&DISPUTE_METADATA)
+            .unwrap_or(Map::new(&env));
+        dispute_metadata.set(dispute_id, metadata);
+        env.storage()
+            .persistent()
+            .set(&DISPUTE_METADATA, &dispute_metadata);
+        env.storage()
+            .instance()
+            .set(&NEXT_DISPUTE_ID, &(dispute_id + 1));
+
+        // Update Request Status if possible
+        let mut requests: Map<u64, BloodRequest> = env
+            .storage()
+            .persistent()
+            .get(&REQUESTS)
+            .unwrap_or(Map::new(&env));
+
+        if let Some(mut request) = requests.get(payment.request_id) {
+            request.status = RequestStatus::Disputed;
+            requests.set(payment.request_id, request);
+            env.storage().persistent().set(&REQUESTS, &requests);
+        }
+
+        // Emit DisputeRaisedEvent
+        publish_event(
+            &env,
+            (
+                symbol_short!("dispute"),
+                symbol_short!("raised"),
+                symbol_short!("v1"),
+            ),
+            DisputeRaisedEvent {
+                dispute_id,
+                payment_id,
+                raised_by,
+                reason,
+                evidence_digest,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(dispute_id)
+    }
+
+    /// Resolve a dispute (admin only)
+    pub fn resolve_dispute(
+        env: Env,
+        dispute_id: u64,
+        resolution: DisputeStatus,
+    ) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+
+        let mut disputes: Map<u64, Dispute> = env
+            .storage()
+            .persistent()
+            .get(&DISPUTES)
+            .ok_or(Error::DisputeNotFound)?;
+
+        let mut dispute = disputes.get(dispute_id).ok_or(Error::DisputeNotFound)?;
+
+        if dispute.status != DisputeStatus::Open {
+            return Err(Error::InvalidDisputeStatus);
+        }
+
+        let mut payments: Map<u64, Payment> = env
+            .storage()
+            .persistent()
+            .get(&PAYMENTS)
+            .ok_or(Error::PaymentNotFound)?;
+
+        let mut payment = payments
+            .get(dispute.payment_id)
+            .ok_or(Error::PaymentNotFound)?;
+
+        dispute.status = resolution;
+        dispute.resolved_at = Some(env.ledger().timestamp());
+        disputes.set(dispute_id, dispute.clone());
+        env.storage().persistent().set(&DISPUTES, &disputes);
+
+        payment.status = PaymentStatus::Resolved;
+
+        // Handle funds based on resolution
+        match resolution {
+            DisputeStatus::ResolvedInFavorOfPayer => {
+                payment.status = PaymentStatus::Refunded;
+            }
+            DisputeStatus::ResolvedInFavorOfPayee => {
+                payment.status = PaymentStatus::Completed;
+            }
+            _ => {}
+        }
+
+        payments.set(dispute.payment_id, payment.clone());
+        env.storage().persistent().set(&PAYMENTS, &payments);
+
+        // Update Request Status
+        let mut requests: Map<u64, BloodRequest> = env
+            .storage()
+            .persistent()
+            .get(&REQUESTS)
+            .unwrap_or(Map::new(&env));
+
+        if let Some(mut request) = requests.get(payment.request_id) {
+            request.status = RequestStatus::Resolved;
+            requests.set(payment.request_id, request);
+            env.storage().persistent().set(&REQUESTS, &requests);
+        }
+
+        // Emit DisputeResolvedEvent
+        publish_event(
+            &env,
+            (
+                symbol_short!("dispute"),
+                symbol_short!("resolved"),
+                symbol_short!("v1"),
+            ),
+            DisputeResolvedEvent {
+                dispute_id,
+                payment_id: dispute.payment_id,
+                status: resolution,
+                resolved_at: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Permissionless cleanup for disputes that exceeded their arbitration deadline.
+    pub fn process_expired_disputes(env: Env) -> Result<u32, Error> {
+        let current_time = env.ledger().timestamp();
+        let mut disputes: Map<u64, Dispute> = env
+            .storage()
+            .persistent()
+            .get(&DISPUTES)
+            .unwrap_or(Map::new(&env));
+        let dispute_metadata: Map<u64, DisputeMetadata> = env
+            .storage()
+            .persistent()
+            .get(&DISPUTE_METADATA)
+            .unwrap_or(Map::new(&env));
+        let mut payments: Map<u64, Payment> = env
+            .storage()
+            .persistent()
+            .get(&PAYMENTS)
+            .unwrap_or(Map::new(&env));
+        let mut stats = Self::get_payment_stats(env.clone());
+        let mut processed = 0u32;
+
+        for dispute_id in disputes.keys() {
+            let mut dispute = disputes.get(dispute_id).unwrap();
+            if dispute.status != DisputeStatus::Open {
+                continue;
+            }
+
+            let metadata = match dispute_metadata.get(dispute_id) {
+                Some(metadata) => metadata,
+                None => continue,
+            };
+
+            if current_time <= metadata.dispute_deadline {
+                continue;
+            }
+
+            let mut payment = match payments.get(dispute.payment_id) {
+                Some(payment) => payment,
+                None => continue,
+            };
+
+            if payment.status != PaymentStatus::Disputed {
+                continue;
+            }
+
+            payment.status = PaymentStatus::Refunded;
+            payment.escrow_released_at = Some(current_time);
+            payments.set(dispute.payment_id, payment.clone());
+
+            dispute.status = DisputeStatus::ResolvedInFavorOfPayer;
+            dispute.resolved_at = Some(current_time);
+            disputes.set(dispute_id, dispute.clone());
+
+            stats.count_auto_refunded += 1;
+            stats.total_auto_refunded += payment.amount;
+            processed += 1;
+
+            publish_event(
+                &env,
+                (
+                    symbol_short!("dispute"),
+                    symbol_short!("refunded"),
+                    symbol_short!("v1"),
+                ),
+                DisputeAutoRefundedEvent {
+                    case_id: dispute_id,
+                    payment_id: payment.id,
+                    refunded_to: payment.payer,
+                    amount: payment.amount,
+                    refunded_at: current_time,
+                },
+            );
+        }
+
+        env.storage().persistent().set(&DISPUTES, &disputes);
+        env.storage().persistent().set(&PAYMENTS, &payments);
+        env.storage().persistent().set(&PAYMENT_STATS, &stats);
+
+        Ok(processed)
+    }
+
+    /// Update request status
+    pub fn update_request_status(
+        env: Env,
+        request_id: u64,
+        new_status: RequestStatus,
+    ) -> Result<(), Error> {
+        let mut requests: Map<u64, BloodRequest> = env
+            .storage()
+            .persistent()
+            .get(&REQUESTS)
+            .unwrap_or(Map::new(&env));
+
+        let mut request = requests.get(request_id).ok_or(Error::UnitNotFound)?;
+
+        let caller = env.current_contract_address();
+
+        // Validate status transition
+        if !Self::is_valid_status_transition(&request.status, &new_status) {
+            return Err(Error::InvalidTransition);
+        }
+
+        let old_status = request.status;
+        request.status = new_status;
+
+        requests.set(request_id, request);
+        env.storage().persistent().set(&REQUESTS, &requests);
+
+        // Record and emit status change
+        record_request_status_change(&env, request_id, old_status, new_status, caller, None);
+
+        Ok(())
+    }
+
+    /// Approve a pending request and reserve matching units for it.
+    pub fn approve_request(
+        env: Env,
+        bank_id: Address,
+        request_id: u64,
+        unit_ids: Vec<u64>,
+    ) -> Result<(), Error> {
+        bank_id.require_auth();
+
+        if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut requests: Map<u64, BloodRequest> = env
+            .storage()
+            .persistent()
+            .get(&REQUESTS)
+            .unwrap_or(Map::new(&env));
+
+        let mut request = requests.get(request_id).ok_or(Error::UnitNotFound)?;
+
+        if request.status != RequestStatus::Pending {
+            return Err(Error::InvalidStatus);
+        }
+
+        let current_time = env.ledger().timestamp();
+        let mut total_quantity: u32 = 0;
+
+        for i in 0..unit_ids.len() {
+            let unit_id = unit_ids.get(i).unwrap();
+            let unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
+
+            if unit.blood_type != request.blood_type {
+                return Err(Error::InvalidStatus);
+            }
+
+            if unit.status != BloodStatus::Available {
+                return Err(Error::InvalidStatus);
+            }
+
+            if unit.expiration_date <= current_time {
+                return Err(Error::UnitExpired);
+            }
+
+            total_quantity = total_quantity
+                .checked_add(unit.quantity)
+                .ok_or(Error::ArithmeticError)?;
+        }
+
+        // Reserve units to the requesting hospital.
+        for i in 0..unit_ids.len() {
+            let unit_id = unit_ids.get(i).unwrap();
+            let mut unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
+            let old_status = unit.status;
+
+            unit.status = BloodStatus::Reserved;
+            unit.recipient_hospital = Some(request.hospital_id.clone());
+            unit.allocation_timestamp = Some(current_time);
+
+            save_unit(&env, &unit);
+
+            // Maintain status index
+            reindex_status(&env, unit_id, old_status, BloodStatus::Reserved);
+            start_reservation(&env, unit_id, current_time, default_hold_seconds(&env));
+
+            record_status_change(
+                &env,
+                unit_id,
+                old_status,
+                BloodStatus::Reserved,
+                bank_id.clone(),
+            );
+
+            publish_event(
+                &env,
+                (
+                    symbol_short!("blood"),
+                    symbol_short!("allocate"),
+                    symbol_short!("v1"),
+                ),
+                (unit_id, request.hospital_id.clone(), current_time),
+            );
+        }
+
+        let old_status = request.status;
+        request.reserved_unit_ids = unit_ids.clone();
+        request.fulfilled_quantity_ml = total_quantity;
+        request.status = if total_quantity >= request.quantity_ml {
+            RequestStatus::Approved
+        } else {
+            RequestStatus::InProgress
+        };
+
+        requests.set(request_id, request.clone());
+        env.storage().persistent().set(&REQUESTS, &requests);
+
+        record_request_status_change(
+            &env,
+            request_id,
+            old_status,
+            request.status,
+            bank_id.clone(),
+            None,
+        );
+
+        publish_event(
+            &env,
+            (
+                symbol_short!("request"),
+                symbol_short!("approve"),
+                symbol_short!("v1"),
+            ),
+            RequestApprovedEvent {
+                request_id,
+                blood_bank: bank_id,
+                assigned_unit_ids: unit_ids,
+                total_quantity_ml: total_quantity,
+                fulfillment_percentage: Self::calculate_fulfillment_percentage(
+                    request.quantity_ml,
+                    total_quantity,
+                )?,
+                status: request.status,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Cancel blood request
+    pub fn cancel_request(env: Env, request_id: u64, reason: String) -> Result<(), Error> {
+        let mut requests: Map<u64, BloodRequest> = env
+            .storage()
+            .persistent()
+            .get(&REQUESTS)
+            .unwrap_or(Map::new(&env));
+
+        let mut request = requests.get(request_id).ok_or(Error::UnitNotFound)?;
+
+        // Authorization: only hospital that created the request or blood bank can cancel
+        let caller = env.current_contract_address();
+        let is_hospital =
+            HealthChainContract::is_hospital(env.clone(), request.hospital_id.clone());
+        let is_bank = HealthChainContract::is_blood_bank(env.clone(), caller.clone());
+
+        if !is_hospital && !is_bank {
+            return Err(Error::Unauthorized);
+        }
+
+        // Can only cancel if Pending, Approved, or InProgress
+        if request.status == RequestStatus::Fulfilled || request.status == RequestStatus::Cancelled
+        {
+            return Err(Error::InvalidStatus);
+        }
+
+        let old_status = request.status;
+        request.status = RequestStatus::Cancelled;
+
+        // Capture released unit IDs before clearing the vector
+        let released_unit_ids = request.reserved_unit_ids.clone();
+
+        // Release reserved units
+        for i in 0..request.reserved_unit_ids.len() {
+            let unit_id = request.reserved_unit_ids.get(i).unwrap();
+            if let Some(mut unit) = load_unit(&env, unit_id) {
+                if unit.status == BloodStatus::Reserved {
+                    let old_unit_status = unit.status;
+                    unit.status = BloodStatus::Available;
+                    unit.recipient_hospital = None;
+                    unit.allocation_timestamp = None;
+                    let unit_bank = unit.bank_id.clone();
+                    save_unit(&env, &unit);
+                    // Maintain status index
+                    reindex_status(&env, unit_id, old_unit_status, BloodStatus::Available);
+                    resolve_reservation(&env, unit_id, &unit_bank, env.ledger().timestamp());
+                }
+            }
+        }
+
+        request.reserved_unit_ids = vec![&env];
+
+        requests.set(request_id, request);
+        env.storage().persistent().set(&REQUESTS, &requests);
+
+        let current_time = env.ledger().timestamp();
+
+        // Record and emit status change (for backward compatibility)
+        record_request_status_change(
+            &env,
+            request_id,
+            old_status,
+            RequestStatus::Cancelled,
+            caller.clone(),
+            Some(reason.clone()),
+        );
+
+        // Emit dedicated cancellation event with explicit unit release information
+        publish_event(
+            &env,
+            (
+                symbol_short!("request"),
+                symbol_short!("cancel"),
+                symbol_short!("v1"),
+            ),
+            RequestCancellationEvent {
+                request_id,
+                actor: caller,
+                cancellation_reason: reason,
+                reason_code: CancellationReason::ExplicitCancellation,
+                released_unit_ids,
+                cancellation_timestamp: current_time,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Fulfill blood request
+    pub fn fulfill_request(
+        env: Env,
+        bank_id: Address,
+        request_id: u64,
+        unit_ids: Vec<u64>,
+    ) -> Result<(), Error> {
+        bank_id.require_auth();
+
+        let mut requests: Map<u64, BloodRequest> = env
+            .storage()
+            .persistent()
+            .get(&REQUESTS)
+            .unwrap_or(Map::new(&env));
+
+        let mut request = requests.get(request_id).ok_or(Error::UnitNotFound)?;
+
+        if !HealthChainContract::is_blood_bank(env.clone(), bank_id.clone()) {
+            return Err(Error::Unauthorized);
+        }
+
+        // Can only fulfill if Approved or InProgress
+        if request.status != RequestStatus::Approved && request.status != RequestStatus::InProgress
+        {
+            return Err(Error::InvalidStatus);
+        }
+
+        if request.reserved_unit_ids.len() > 0 && request.reserved_unit_ids != unit_ids {
+            return Err(Error::InvalidStatus);
+        }
+
+        // Validate delivery quantity before mutating any unit or request state.
+        let mut delivered_quantity: u32 = 0;
+
+        for i in 0..unit_ids.len() {
+            let unit_id = unit_ids.get(i).unwrap();
+            let unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
+
+            // Verify unit is reserved for this hospital
+            if unit.recipient_hospital != Some(request.hospital_id.clone()) {
+                return Err(Error::Unauthorized);
+            }
+
+            if unit.status != BloodStatus::Reserved && unit.status != BloodStatus::InTransit {
+                return Err(Error::InvalidStatus);
+            }
+
+            delivered_quantity = delivered_quantity
+                .checked_add(unit.quantity)
+                .ok_or(Error::ArithmeticError)?;
+        }
+
+        if delivered_quantity > request.quantity_ml {
+            return Err(Error::InvalidQuantity);
+        }
+
+        for i in 0..unit_ids.len() {
+            let unit_id = unit_ids.get(i).unwrap();
+            let mut unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
+
+            // Update to delivered
+            let old_status = unit.status;
+            unit.status = BloodStatus::Delivered;
+            let current_time = env.ledger().timestamp();
+            unit.delivery_timestamp = Some(current_time);
+
+            save_unit(&env, &unit);
+
+            // Maintain status and bank indexes
+            reindex_status(&env, unit_id, old_status, BloodStatus::Delivered);
+            deindex_bank_unit(&env, &unit.bank_id, unit_id);
+            resolve_reservation(&env, unit_id, &unit.bank_id, current_time);
+
+            // Record blood unit status change
+            record_status_change(
+                &env,
+                unit_id,
+                old_status,
+                BloodStatus::Delivered,
+                bank_id.clone(),
+            );
+        }
+
+        // Update request
+        let old_status = request.status;
+        request.status = if delivered_quantity == request.quantity_ml {
+            RequestStatus::Fulfilled
+        } else {
+            RequestStatus::InProgress
+        };
+        request.fulfilled_quantity_ml = delivered_quantity;
+        request.fulfillment_timestamp = if request.status == RequestStatus::Fulfilled {
+            Some(env.ledger().timestamp())
+        } else {
+            None
+        };
+        request.reserved_unit_ids = unit_ids.clone();
+
+        requests.set(request_id, request.clone());
+        env.storage().persistent().set(&REQUESTS, &requests);
+
+        if old_status != request.status {
+            record_request_status_change(
+                &env,
+                request_id,
+                old_status,
+                request.status,
+                bank_id.clone(),
+                None,
+            );
+        }
+
+        publish_event(
+            &env,
+            (
+                symbol_short!("request"),
+                symbol_short!("fulfill"),
+                symbol_short!("v1"),
+            ),
+            RequestFulfilledEvent {
+                request_id,
+                blood_bank: bank_id,
+                delivered_unit_ids: unit_ids,
+                delivered_quantity_ml: delivered_quantity,
+                fulfilled_at: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Helper: Validate status transitions
+    fn is_valid_status_transition(old_status: &RequestStatus, new_status: &RequestStatus) -> bool {
+        match (old_status, new_status) {
+            // From Pending
+            (RequestStatus::Pending, RequestStatus::Approved) => true,
+            (RequestStatus::Pending, RequestStatus::Rejected) => true,
+            (RequestStatus::Pending, RequestStatus::Cancelled) => true,
+
+            // From Approved
+            (RequestStatus::Approved, RequestStatus::InProgress) => true,
+            (RequestStatus::Approved, RequestStatus::Cancelled) => true,
+
+            // From InProgress
+            (RequestStatus::InProgress, RequestStatus::Fulfilled) => true,
+            (RequestStatus::InProgress, RequestStatus::Cancelled) => true,
+
+            // No transitions from terminal states
+            (RequestStatus::Fulfilled, _) => false,
+            (RequestStatus::Cancelled, _) => false,
+            (RequestStatus::Rejected, _) => false,
+
+            // Any other transition is invalid
+            _ => false,
+        }
+    }
+
+    fn calculate_fulfillment_percentage(
+        requested_quantity: u32,
+        fulfilled_quantity: u32,
+    ) -> Result<u32, Error> {
+        if requested_quantity == 0 {
+            return Ok(0);
+        }
+
+        let percentage = fulfilled_quantity
+            .checked_mul(100)
+            .ok_or(Error::ArithmeticError)?
+            / requested_quantity;
+        Ok(percentage.min(100))
+    }
+
+    // ── SUPER ADMIN TWO-STEP TRANSFER ────────────────────────────────────────────────────
+
+    /// Nominate a new SuperAdmin (current admin only).
+    ///
+    /// Clears any expired pending nomination before checking for an active one.
+    /// Emits `AdminProposedEvent` on success.
+    pub fn nominate_super_admin(env: Env, nominee: Address) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+
+        let now = env.ledger().timestamp();
+
+        // Lazily clear an expired nomination so a new one can be made.
+        if let Some(entry) = env
+            .storage()
+            .instance()
+            .get::<DataKey, NominationEntry>(&DataKey::PendingNominee)
+        {
+            let expired = now > entry.nominated_at.saturating_add(NOMINATION_EXPIRY_SECONDS);
+            if !expired {
+                return Err(Error::NominationPending);
+            }
+            env.storage().instance().remove(&DataKey::PendingNominee);
+        }
+
+        env.storage().instance().set(
+            &DataKey::PendingNominee,
+            &NominationEntry {
+                nominee: nominee.clone(),
+                nominated_at: now,
+            },
+        );
+
+        publish_event(
+            &env,
+            (symbol_short!("admin"), symbol_short!("proposed")),
+            AdminProposedEvent {
+                current_admin: admin,
+                proposed_admin: nominee,
+                nominated_at: now,
+                expires_at: now.saturating_add(NOMINATION_EXPIRY_SECONDS),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Accept a pending SuperAdmin nomination (nominee only).
+    ///
+    /// Fails with `NominationExpired` if the 24-hour window has passed.
+    /// Emits `AdminTransferredEvent` on success.
+    pub fn accept_super_admin(env: Env) -> Result<(), Error> {
+        let entry: NominationEntry = env
+            .storage()
+            .instance()
+            .get(&DataKey::PendingNominee)
+            .ok_or(Error::Unauthorized)?;
+
+        entry.nominee.require_auth();
+
+        let now = env.ledger().timestamp();
+        if now > entry.nominated_at.saturating_add(NOMINATION_EXPIRY_SECONDS) {
+            return Err(Error::NominationExpired);
+        }
+
+        let previous_admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+
+        env.storage().instance().set(&ADMIN, &entry.nominee);
+        env.storage().instance().remove(&DataKey::PendingNominee);
+
+        publish_event(
+            &env,
+            (symbol_short!("admin"), symbol_short!("xfer")),
+            AdminTransferredEvent {
+                previous_admin,
+                new_admin: entry.nominee,
+                transferred_at: now,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Cancel a pending nomination (current admin only).
+    /// Emits `AdminNominationCancelledEvent` if a nomination was present.
+    pub fn cancel_nomination(env: Env) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+
+        if let Some(entry) = env
+            .storage()
+            .instance()
+            .get::<DataKey, NominationEntry>(&DataKey::PendingNominee)
+        {
+            env.storage().instance().remove(&DataKey::PendingNominee);
+            publish_event(
+                &env,
+                (symbol_short!("admin"), symbol_short!("nom_cxl")),
+                AdminNominationCancelledEvent {
+                    cancelled_by: admin,
+                    cancelled_nominee: entry.nominee,
+                    cancelled_at: env.ledger().timestamp(),
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Propose a new admin (canonical alias for `nominate_super_admin`).
+    ///
+    /// Requires auth from the current admin. Emits `AdminProposedEvent`.
+    pub fn propose_admin(env: Env, proposed: Address) -> Result<(), Error> {
+        Self::nominate_super_admin(env, proposed)
+    }
+
+    /// Accept a pending admin proposal (canonical alias for `accept_super_admin`).
+    ///
+    /// Requires auth from the proposed admin. Emits `AdminTransferredEvent`.
+    pub fn accept_admin(env: Env) -> Result<(), Error> {
+        Self::accept_super_admin(env)
+    }
+
+    /// Rotate the admin without the current admin's signature (recovery only).
+    ///
+    /// `proof` must equal the recovery address registered via
+    /// `initialize_with_recovery` and must authorize the call. Any pending
+    /// nomination is discarded. Fails with `RecoveryNotConfigured` if no
+    /// recovery address was ever set. Emits `AdminTransferredEvent`.
+    pub fn emergency_reinitialize(
+        env: Env,
+        new_admin: Address,
+        proof: Address,
+    ) -> Result<(), Error> {
+        let recovery: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::RecoveryAddress)
+            .ok_or(Error::RecoveryNotConfigured)?;
+        if proof != recovery {
+            return Err(Error::Unauthorized);
+        }
+        proof.require_auth();
+
+        let previous_admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+
+        env.storage().instance().set(&ADMIN, &new_admin);
+        env.storage().instance().remove(&DataKey::PendingNominee);
+
+        publish_event(
+            &env,
+            (symbol_short!("admin"), symbol_short!("recover")),
+            AdminTransferredEvent {
+                previous_admin,
+                new_admin,
+                transferred_at: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+        /// Authorize an inventory contract for cross-contract synchronization (admin only).
+    ///
+    /// The authorized inventory contract may call `inventory_reserve_unit` and
+    /// `inventory_release_unit` to keep its reservation state consistent with
+    /// the registry's canonical unit status.
+    pub fn set_inventory_contract(
+        env: Env,
+        admin: Address,
+        inventory_contract_id: Address,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&INVENTORY_CONTRACT, &inventory_contract_id);
+        Ok(())
+    }
+
+    /// Check deliveries against the temperature contract's breach records (admin only).
+    pub fn set_temperature_contract(
+        env: Env,
+        admin: Address,
+        temperature_contract_id: Address,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&TEMPERATURE_CONTRACT, &temperature_contract_id);
+        Ok(())
+    }
+
+    /// Point role checks for multi-hop custody at the identity contract (admin only).
+    pub fn set_identity_contract(
+        env: Env,
+        admin: Address,
+        identity_contract_id: Address,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&IDENTITY_CONTRACT, &identity_contract_id);
+        Ok(())
+    }
+
+    /// Enable or disable registration of already-expired units (admin only).
+    ///
+    /// Intended for data-migration scenarios where historical units are
+    /// imported with their original expiration timestamps.  Off by default.
+    pub fn set_allow_backdated(env: Env, admin: Address, allow: bool) -> Result<(), Error> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+        env.storage().instance().set(&ALLOW_BACKDATED, &allow);
+        Ok(())
+    }
+
+    /// Returns `true` if backdated registration is currently enabled.
+    pub fn is_backdating_allowed(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&ALLOW_BACKDATED)
+            .unwrap_or(false)
+    }
+
+    /// Pause the operation classes in `flags` (admin only).
+    ///
+    /// `flags` is a bitmask of `PAUSE_REGISTER`, `PAUSE_ALLOCATE` and
+    /// `PAUSE_CUSTODY` and is OR-ed into the current mask. Paused entry points
+    /// fail with `ContractPaused`; reads, `cancel_transfer` and reservation
+    /// release stay live so in-flight units can still be recovered.
+    pub fn pause(env: Env, admin: Address, flags: u32) -> Result<(), Error> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+        if flags == 0 || flags & !PAUSE_ALL != 0 {
+            return Err(Error::InvalidPauseFlags);
+        }
+
+        let paused = Self::get_paused_flags(env.clone()) | flags;
+        env.storage().instance().set(&PAUSED, &paused);
+
+        publish_event(
+            &env,
+            (symbol_short!("admin"), symbol_short!("pause")),
+            paused,
+        );
+
+        Ok(())
+    }
+
+    /// Clear the operation classes in `flags` from the pause mask (admin only).
+    pub fn unpause(env: Env, admin: Address, flags: u32) -> Result<(), Error> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+        if flags == 0 || flags & !PAUSE_ALL != 0 {
+            return Err(Error::InvalidPauseFlags);
+        }
+
+        let paused = Self::get_paused_flags(env.clone()) & !flags;
+        if paused == 0 {
+            env.storage().instance().remove(&PAUSED);
+        } else {
+            env.storage().instance().set(&PAUSED, &paused);
+        }
+
+        publish_event(
+            &env,
+            (symbol_short!("admin"), symbol_short!("unpause")),
+            paused,
+        );
+
+        Ok(())
+    }
+
+    /// Current pause bitmask; 0 when nothing is paused.
+    pub fn get_paused_flags(env: Env) -> u32 {
+        env.storage().instance().get(&PAUSED).unwrap_or(0)
+    }
+
+    /// Require the outgoing custodian to co-sign every `confirm_transfer` and
+    /// `confirm_hop`, so neither side can record a handover alone (admin only).
+    /// Off by default.
+    pub fn set_dual_auth_required(env: Env, admin: Address, required: bool) -> Result<(), Error> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+        env.storage().instance().set(&DUAL_AUTH_REQUIRED, &required);
+        Ok(())
+    }
+
+    /// Returns `true` if confirmations need both custodians' signatures.
+    pub fn is_dual_auth_required(env: Env) -> bool {
+        dual_auth_required(&env)
+    }
+
+    /// Require passing lab results for every test in [`REQUIRED_TESTS`] before
+    /// a unit can be allocated (admin only). Off by default.
+    pub fn set_screening_required(env: Env, admin: Address, required: bool) -> Result<(), Error> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+        env.storage().instance().set(&SCREENING_REQUIRED, &required);
+        Ok(())
+    }
+
+    /// Returns `true` if allocation currently requires cleared lab screening.
+    pub fn is_screening_required(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&SCREENING_REQUIRED)
+            .unwrap_or(false)
+    }
+
+    /// Set the reservation hold used when `allocate_blood` is not given one
+    /// (admin only). Must be non-zero and at most `MAX_RESERVATION_HOLD_SECONDS`.
+    pub fn set_default_hold_duration(
+        env: Env,
+        admin: Address,
+        hold_seconds: u64,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+        let hold_seconds = validate_hold_seconds(hold_seconds)?;
+        env.storage().instance().set(&HOLD_DURATION, &hold_seconds);
+        Ok(())
+    }
+
+    /// Reservation hold applied when an allocation does not specify one.
+    pub fn get_default_hold_duration(env: Env) -> u64 {
+        default_hold_seconds(&env)
+    }
+
+    /// Set how long a newly initiated transfer may stay pending before anyone
+    /// can expire it (admin only). Must lie within
+    /// `[TRANSFER_EXPIRY_SECONDS, MAX_TRANSFER_DEADLINE_SECONDS]`; transfers
+    /// already in flight keep the deadline they were created with.
+    pub fn set_transfer_deadline(
+        env: Env,
+        admin: Address,
+        deadline_seconds: u64,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+        if !(TRANSFER_EXPIRY_SECONDS..=MAX_TRANSFER_DEADLINE_SECONDS).contains(&deadline_seconds) {
+            return Err(Error::InvalidTransferDeadline);
+        }
+        env.storage()
+            .instance()
+            .set(&TRANSFER_DEADLINE, &deadline_seconds);
+        Ok(())
+    }
+
+    /// Pending-transfer deadline applied by `initiate_transfer`.
+    pub fn get_transfer_deadline(env: Env) -> u64 {
+        default_transfer_deadline(&env)
+    }
+
+    /// Require a compatible crossmatch tested within the last `window_seconds`
+    /// before a hospital can confirm a transfer (admin only). Zero switches the
+    /// requirement off, which is the default.
+    pub fn set_crossmatch_window(
+        env: Env,
+        admin: Address,
+        window_seconds: u64,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+        if window_seconds == 0 {
+            env.storage().instance().remove(&CROSSMATCH_WINDOW);
+        } else {
+            env.storage()
+                .instance()
+                .set(&CROSSMATCH_WINDOW, &window_seconds);
+        }
+        Ok(())
+    }
+
+    /// Crossmatch validity window in seconds; zero when not enforced.
+    pub fn get_crossmatch_window(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&CROSSMATCH_WINDOW)
+            .unwrap_or(0)
+    }
+
+    /// Set the namespace topic prepended to every emitted event (admin only).
+    ///
+    /// Passing an empty symbol clears the namespace and restores the
+    /// unprefixed topic layout.
+    pub fn set_event_namespace(env: Env, admin: Address, namespace: Symbol) -> Result<(), Error> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+        if namespace == Symbol::new(&env, "") {
+            env.storage().instance().remove(&EVENT_NAMESPACE);
+        } else {
+            env.storage().instance().set(&EVENT_NAMESPACE, &namespace);
+        }
+        Ok(())
+    }
+
+    /// Current event namespace; an empty symbol when none is configured.
+    pub fn get_event_namespace(env: Env) -> Symbol {
+        env.storage()
+            .instance()
+            .get(&EVENT_NAMESPACE)
+            .unwrap_or(Symbol::new(&env, ""))
+    }
+
+    /// Check whether a blood unit exists and is in `Available` status.
+    ///
+    /// Intended for cross-contract calls from the inventory contract.
+    /// Returns `false` if the unit does not exist, is not Available, or is expired.
+    pub fn check_unit_available(env: Env, unit_id: u64) -> bool {
+        match load_unit(&env, unit_id) {
+            Some(unit) => {
+                let current_time = env.ledger().timestamp();
+                unit.status == BloodStatus::Available && unit.expiration_date > current_time
+            }
+            None => false,
+        }
+    }
+
+    /// Mark a blood unit as Reserved, called by the authorized inventory contract.
+    ///
+    /// Only the contract stored via `set_inventory_contract` may call this.
+    /// The `bank_id` is the blood bank that owns the unit — its auth is verified
+    /// by the inventory contract before this cross-contract call is made.
+    pub fn inventory_reserve_unit(
+        env: Env,
+        bank_id: Address,
+        unit_id: u64,
+        hospital_id: Address,
+    ) -> Result<(), Error> {
+        let authorized: Address = env
+            .storage()
+            .instance()
+            .get(&INVENTORY_CONTRACT)
+            .ok_or(Error::Unauthorized)?;
+        if env.current_contract_address() != authorized {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
+        if unit.status != BloodStatus::Available {
+            return Err(Error::InvalidStatus);
+        }
+        let current_time = env.ledger().timestamp();
+        if unit.expiration_date <= current_time {
+            return Err(Error::UnitExpired);
+        }
+
+        let old_status = unit.status;
+        unit.status = BloodStatus::Reserved;
+        unit.recipient_hospital = Some(hospital_id.clone());
+        unit.allocation_timestamp = Some(current_time);
+
+        save_unit(&env, &unit);
+
+        reindex_status(&env, unit_id, old_status, BloodStatus::Reserved);
+        start_reservation(&env, unit_id, current_time, default_hold_seconds(&env));
+        index_hospital_unit(&env, &hospital_id, unit_id);
+        record_status_change(&env, unit_id, old_status, BloodStatus::Reserved, bank_id);
+
+        publish_event(
+            &env,
+            (
+                symbol_short!("blood"),
+                symbol_short!("allocate"),
+                symbol_short!("v1"),
+            ),
+            (unit_id, hospital_id, current_time),
+        );
+
+        Ok(())
+    }
+
+    /// Release a previously reserved blood unit back to Available.
+    ///
+    /// Called by the authorized inventory contract when a reservation is
+    /// cancelled or expires.
+    pub fn inventory_release_unit(
+        env: Env,
+        bank_id: Address,
+        unit_id: u64,
+    ) -> Result<(), Error> {
+        let authorized: Address = env
+            .storage()
+            .instance()
+            .get(&INVENTORY_CONTRACT)
+            .ok_or(Error::Unauthorized)?;
+        if env.current_contract_address() != authorized {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
+        if unit.status != BloodStatus::Reserved {
+            return Err(Error::InvalidStatus);
+        }
+
+        let old_status = unit.status;
+        let hospital_id = unit.recipient_hospital.clone();
+
+        unit.status = BloodStatus::Available;
+        unit.recipient_hospital = None;
+        unit.allocation_timestamp = None;
+
+        save_unit(&env, &unit);
+
+        reindex_status(&env, unit_id, old_status, BloodStatus::Available);
+        resolve_reservation(&env, unit_id, &bank_id, env.ledger().timestamp());
+        if let Some(ref hosp) = hospital_id {
+            deindex_hospital_unit(&env, hosp, unit_id);
+        }
+        record_status_change(&env, unit_id, old_status, BloodStatus::Available, bank_id);
+
+        publish_event(
+            &env,
+            (
+                symbol_short!("blood"),
+                symbol_short!("cancel"),
+                symbol_short!("v1"),
+            ),
+            unit_id,
+        );
+
+        Ok(())
+    }
+
+    /// Store a health record hash
+    pub fn store_record(env: Env, patient_id: Symbol, record_hash: Symbol) -> Vec<Symbol> {
+        vec![&env, patient_id, record_hash]
+    }
+
+    /// Retrieve stored record
+    pub fn get_record(_env: Env, patient_id: Symbol) -> Symbol {
+        patient_id
+    }
+
+    /// Verify record access
+    pub fn verify_access(_env: Env, _patient_id: Symbol, _provider_id: Symbol) -> bool {
+        true
+    }
+
+    /// Add a blood unit to inventory (legacy function for testing)
+    pub fn add_blood_unit(
+        env: Env,
+        blood_type: BloodType,
+        quantity: u32,
+        expiration_date: u64,
+        donor_id: Symbol,
+        location: Symbol,
+    ) -> u64 {
+        let id = get_next_id(&env);
+        let current_time = env.ledger().timestamp();
+
+        // Create a default address for legacy function using contract address
+        let default_bank = env.current_contract_address();
+
+        let unit = BloodUnit {
+            id,
+            blood_type,
+            component: BloodComponent::WholeBlood,
+            quantity,
+            expiration_date,
+            donor_id,
+            location,
+            bank_id: default_bank,
+            registration_timestamp: current_time,
+            status: BloodStatus::Available,
+            recipient_hospital: None,
+            allocation_timestamp: None,
+            transfer_timestamp: None,
+            delivery_timestamp: None,
+            lot_id: None,
+        };
+
+        save_unit(&env, &unit);
+
+        id
+    }
+
+    /// Query blood inventory by blood type with filters
+    ///
+    /// Pass `Some(component)` to restrict results to one component.
+    pub fn query_by_blood_type(
+        env: Env,
+        blood_type: BloodType,
+        component: Option<BloodComponent>,
+        min_quantity: u32,
+        max_results: u32,
+    ) -> Vec<BloodUnit> {
+        let current_time = env.ledger().timestamp();
+        let mut results = vec![&env];
+        let mut temp_units = vec![&env];
+
+        // Collect matching units (Available status, non-expired, matching blood type, sufficient quantity)
+        for id in 1..next_unit_id(&env) {
+            let unit = match load_unit(&env, id) {
+                Some(unit) => unit,
+                None => continue,
+            };
+            if unit.blood_type == blood_type
+                && unit.component.matches(component)
+                && unit.status == BloodStatus::Available
+                && unit.quantity >= min_quantity
+                && unit.expiration_date > current_time
+            {
+                temp_units.push_back(unit);
+            }
+        }
+
+        // Sort by expiration date (FIFO - earliest expiration first)
+        let len = temp_units.len();
+        for i in 0..len {
+            for j in 0..len.saturating_sub(i + 1) {
+                let unit_j = temp_units.get(j).unwrap();
+                let unit_j_plus_1 = temp_units.get(j + 1).unwrap();
+
+                if unit_j.expiration_date > unit_j_plus_1.expiration_date {
+                    temp_units.set(j, unit_j_plus_1.clone());
+                    temp_units.set(j + 1, unit_j);
+                }
+            }
+        }
+
+        // Apply pagination
+        let limit = clamp_limit(max_results).min(len);
+        for i in 0..limit {
+            if let Some(unit) = temp_units.get(i) {
+                results.push_back(unit);
+            }
+        }
+
+        results
+    }
+
+    /// Check if sufficient blood quantity is available
+    ///
+    /// Pass `Some(component)` to count only units of that component.
+    pub fn check_availability(
+        env: Env,
+        blood_type: BloodType,
+        component: Option<BloodComponent>,
+        required_quantity: u32,
+    ) -> bool {
+        let current_time = env.ledger().timestamp();
+        let mut total_quantity: u32 = 0;
+
+        // Sum up available quantities for the blood type (Available status and non-expired only)
+        for id in 1..next_unit_id(&env) {
+            let unit = match load_unit(&env, id) {
+                Some(unit) => unit,
+                None => continue,
+            };
+            if unit.blood_type == blood_type
+                && unit.component.matches(component)
+                && unit.status == BloodStatus::Available
+                && unit.expiration_date > current_time
+            {
+                total_quantity = total_quantity.saturating_add(unit.quantity);
+
+                // Early exit if we've found enough
+                if total_quantity >= required_quantity {
+                    return true;
+                }
+            }
+        }
+
+        total_quantity >= required_quantity
+    }
+
+    /// Page through every registered unit in ID order.
+    ///
+    /// Delegates to [`registry_read::list_units`].
+    pub fn list_units(env: Env, cursor: u64, limit: u32) -> UnitPage {
+        registry_read::list_units(&env, cursor, limit)
+    }
+
+    /// Page through the units currently held in a bank's inventory.
+    ///
+    /// Delegates to [`registry_read::get_units_by_bank`].
+    pub fn get_units_by_bank(
+        env: Env,
+        bank_id: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<BloodUnit> {
+        registry_read::get_units_by_bank(&env, bank_id, offset, limit)
+    }
+
+    /// Total available volume (ml) for a bank and blood type, optionally
+    /// restricted to one component.
+    ///
+    /// Only Available, non-expired units count; reserved, in-transit and
+    /// expired units are excluded. Delegates to [`registry_read::get_available_volume`].
+    pub fn get_available_volume(
+        env: Env,
+        bank_id: Address,
+        blood_type: BloodType,
+        component: Option<BloodComponent>,
+    ) -> u32 {
+        registry_read::get_available_volume(&env, bank_id, blood_type, component)
+    }
+
+    /// Per-blood-type inventory counters for a bank.
+    ///
+    /// Delegates to [`registry_read::get_inventory_summary`].
+    pub fn get_inventory_summary(env: Env, bank_id: Address) -> Map<BloodType, TypeSummary> {
+        registry_read::get_inventory_summary(&env, bank_id)
+    }
+
+    /// Number of Available units of `blood_type` (and optionally `component`)
+    /// held by a bank.
+    ///
+    /// Delegates to [`registry_read::get_available_count`].
+    pub fn get_available_count(
+        env: Env,
+        bank_id: Address,
+        blood_type: BloodType,
+        component: Option<BloodComponent>,
+    ) -> u32 {
+        registry_read::get_available_count(&env, bank_id, blood_type, component)
+    }
+
+    /// Available, non-expired units of `blood_type` with at least `min_volume` ml,
+    /// across all registered banks, optionally restricted to one component.
+    ///
+    /// Delegates to [`registry_read::find_available`].
+    pub fn find_available(
+        env: Env,
+        blood_type: BloodType,
+        component: Option<BloodComponent>,
+        min_volume: u32,
+        limit: u32,
+    ) -> Vec<BloodUnit> {
+        registry_read::find_available(&env, blood_type, component, min_volume, limit)
+    }
+
+    /// SHA-256 digest of a bank's unit ids, statuses and expirations.
+    ///
+    /// Delegates to [`registry_read::inventory_checksum`].
+    pub fn inventory_checksum(env: Env, bank_id: Address) -> BytesN<32> {
+        registry_read::inventory_checksum(&env, bank_id)
+    }
+
+    /// Seconds elapsed since a unit was registered.
+    ///
+    /// Delegates to [`registry_read::get_unit_age`].
+    pub fn get_unit_age(env: Env, unit_id: u64) -> Result<u64, Error> {
+        registry_read::get_unit_age(&env, unit_id)
+    }
+
+    /// Page through a bank's units older than `age_seconds`.
+    ///
+    /// Delegates to [`registry_read::get_units_older_than`].
+    pub fn get_units_older_than(
+        env: Env,
+        bank_id: Address,
+        age_seconds: u64,
+        start: u32,
+        limit: u32,
+    ) -> Vec<u64> {
+        registry_read::get_units_older_than(&env, bank_id, age_seconds, start, limit)
+    }
+
+    /// Page through units allocated to a hospital in the window `[from, to)`.
+    ///
+    /// Delegates to [`registry_read::get_allocations_for_hospital`].
+    pub fn get_allocations_for_hospital(
+        env: Env,
+        hospital: Address,
+        from: u64,
+        to: u64,
+        start: u32,
+        limit: u32,
+    ) -> Vec<u64> {
+        registry_read::get_allocations_for_hospital(&env, hospital, from, to, start, limit)
+    }
+
+    /// Seconds a unit spent reserved before it was confirmed or released.
+    ///
+    /// Delegates to [`registry_read::get_reservation_duration`].
+    pub fn get_reservation_duration(env: Env, unit_id: u64) -> Option<u64> {
+        registry_read::get_reservation_duration(&env, unit_id)
+    }
+
+    /// Hold deadline of a unit's open reservation, if any.
+    ///
+    /// Delegates to [`registry_read::get_reserved_until`].
+    pub fn get_reserved_until(env: Env, unit_id: u64) -> Option<u64> {
+        registry_read::get_reserved_until(&env, unit_id)
+    }
+
+    /// Running average reservation duration across a bank's resolved reservations.
+    ///
+    /// Delegates to [`registry_read::get_avg_reservation_time`].
+    pub fn get_avg_reservation_time(env: Env, bank_id: Address) -> ReservationAverage {
+        registry_read::get_avg_reservation_time(&env, bank_id)
+    }
+
+    /// Available units of a given blood type and component (e.g. O+ platelets).
+    ///
+    /// Delegates to [`registry_read::query_by_component`].
+    pub fn query_by_component(
+        env: Env,
+        blood_type: BloodType,
+        component: BloodComponent,
+        max_results: u32,
+    ) -> Vec<BloodUnit> {
+        registry_read::query_by_component(&env, blood_type, component, max_results)
+    }
+
+    /// Shelf life, storage temperature range and accepted volume range for a
+    /// blood component.
+    pub fn get_component_profile(_env: Env, component: BloodComponent) -> ComponentProfile {
+        component.profile()
+    }
+
+    /// Mark a single blood unit as Expired if its expiration time has passed.
+    ///
+    /// Delegates to [`registry_write::expire_unit`].
+    pub fn expire_unit(env: Env, unit_id: u64) -> Result<(), Error> {
+        registry_write::expire_unit(&env, unit_id)
+    }
+
+    /// Try to expire up to 50 units in a single call.
+    ///
+    /// Delegates to [`registry_write::check_and_expire_batch`].
+    pub fn check_and_expire_batch(env: Env, unit_ids: Vec<u64>) -> Result<Vec<u64>, Error> {
+        registry_write::check_and_expire_batch(&env, unit_ids)
+    }
+
+    /// Keeper entry point: expire up to `max_count` past-due units found via
+    /// the expiry index. Callable by anyone; returns the number expired.
+    ///
+    /// Delegates to [`registry_write::expire_units_batch`].
+    pub fn expire_units_batch(env: Env, max_count: u32) -> u32 {
+        registry_write::expire_units_batch(&env, max_count)
+    }
+}
+
+#[contractimpl]
+impl HealthChainContract {
+    /// Register an organization (any address can self-register).
+    pub fn register_organization(env: Env, org_id: Address) -> Result<(), Error> {
+        org_id.require_auth();
+
+        let org_key = OrgKey::Org(org_id.clone());
+        if env.storage().persistent().has(&org_key) {
+            return Err(Error::DuplicateRegistration);
+        }
+
+        let organization = Organization {
+            id: org_id.clone(),
+            verified: false,
+            verified_timestamp: None,
+            state: LifecycleState::Inactive,
+            state_changed_by: None,
+            state_changed_at: None,
+            state_change_reason: None,
+        };
+
+        env.storage().persistent().set(&org_key, &organization);
+
+        publish_event(
+            &env,
+            (
+                symbol_short!("org"),
+                symbol_short!("reg"),
+                symbol_short!("v1"),
+            ),
+            org_id,
+        );
+
+        Ok(())
+    }
+
+    /// Verify an organization (admin only).
+    pub fn verify_organization(env: Env, admin: Address, org_id: Address) -> Result<(), Error> {
+        admin.require_auth();
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        let org_key = OrgKey::Org(org_id.clone());
+        let mut organization: Organization = env
+            .storage()
+            .persistent()
+            .get(&org_key)
+            .ok_or(Error::OrganizationNotFound)?;
+
+        if organization.verified {
+            return Err(Error::AlreadyVerified);
+        }
+
+        let old_state = organization.state;
+        organization.verified = true;
+        organization.verified_timestamp = Some(env.ledger().timestamp());
+        organization.state = LifecycleState::Active;
+        organization.state_changed_by = Some(admin.clone());
+        organization.state_changed_at = Some(env.ledger().timestamp());
+        organization.state_change_reason = Some(String::from_str(&env, "verification"));
+        env.storage().persistent().set(&org_key, &organization);
+
+        let verifier_key = OrgKey::Verifier(org_id.clone());
+        env.storage().persistent().set(&verifier_key, &admin);
+
+        publish_event(
+            &env,
+            (symbol_short!("org"), symbol_short!("state")),
+            ActorStateChangeEvent {
+                entity_id: org_id.clone(),
+                old_state,
+                new_state: LifecycleState::Active,
+                changed_by: admin.clone(),
+                reason: Some(String::from_str(&env, "verification")),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        publish_event(
+            &env,
+            (
+                symbol_short!("org"),
+                symbol_short!("verified"),
+                symbol_short!("v1"),
+            ),
+            (org_id, admin, env.ledger().timestamp()),
+        );
+
+        Ok(())
+    }
+
+    /// Unverify an organization (admin only).
+    pub fn unverify_organization(
+        env: Env,
+        admin: Address,
+        org_id: Address,
+        reason: String,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        let org_key = OrgKey::Org(org_id.clone());
+        let mut organization: Organization = env
+            .storage()
+            .persistent()
+            .get(&org_key)
+            .ok_or(Error::OrganizationNotFound)?;
+
+        let old_state = organization.state;
+        organization.verified = false;
+        organization.verified_timestamp = None;
+        organization.state = LifecycleState::Inactive;
+        organization.state_changed_by = Some(admin.clone());
+        organization.state_changed_at = Some(env.ledger().timestamp());
+        organization.state_change_reason = Some(reason.clone());
+        env.storage().persistent().set(&org_key, &organization);
+
+        let reason_key = OrgKey::UnverifyReason(org_id.clone());
+        env.storage().persistent().set(&reason_key, &reason);
+
+        publish_event(
+            &env,
+            (symbol_short!("org"), symbol_short!("state")),
+            ActorStateChangeEvent {
+                entity_id: org_id.clone(),
+                old_state,
+                new_state: LifecycleState::Inactive,
+                changed_by: admin.clone(),
+                reason: Some(reason.clone()),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        publish_event(
+            &env,
+            (
+                symbol_short!("org"),
+                symbol_short!("unverif"),
+                symbol_short!("v1"),
+            ),
+            (org_id, reason),
+        );
+
+        Ok(())
+    }
+
+    /// Query an organization by address.
+    pub fn get_organization(env: Env, org_id: Address) -> Result<Organization, Error> {
+        let org_key = OrgKey::Org(org_id);
+        env.storage()
+            .persistent()
+            .get(&org_key)
+            .ok_or(Error::OrganizationNotFound)
+    }
+}
+
+#[contractimpl]
+impl HealthChainContract {
+    // ── Storage Lifecycle / Rent Management ───────────────────────────────────
+
+    /// Extend the TTL of all shared registry maps (admin only).
+    ///
+    /// Call this periodically (e.g., monthly) to prevent rent expiry on the
+    /// large persistent maps that are the highest-risk keys for storage fees.
+    pub fn bump_registry_ttl(env: Env) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+        storage_lifecycle::bump_all_registries(&env);
+        Ok(())
+    }
+
+    /// Extend TTLs for one page of units and their index entries (permissionless).
+    ///
+    /// Writes already extend the entries they touch; this lets a keeper keep
+    /// idle units and custody trails alive. Pass 0 first, then the returned
+    /// page until it is `None`. Delegates to [`storage_lifecycle::bump_storage_page`].
+    pub fn bump_storage(env: Env, page: u32) -> Option<u32> {
+        storage_lifecycle::bump_storage_page(&env, page)
+    }
+
+    /// Compact the status-history for a terminal blood unit (permissionless).
+    ///
+    /// Replaces the full `Vec<StatusChangeEvent>` with an `ArchivedHistorySummary`
+    /// once the unit has been in a terminal state for at least 30 days, giving
+    /// off-chain indexers time to ingest all events before on-chain data is pruned.
+    ///
+    /// Returns `true` if archival was performed, `false` if not yet eligible.
+    pub fn archive_history(env: Env, unit_id: u64) -> Result<bool, Error> {
+        storage_lifecycle::archive_unit_history(&env, unit_id)
+    }
+
+    /// Prune finalized custody events for a terminal blood unit (permissionless).
+    ///
+    /// Removes individual `CustodyEvent` entries from the shared `CUSTODY_EVENTS`
+    /// map and stores a compact `ArchivedCustodySummary`. The `UnitTrailPage`
+    /// entries (event_id strings) are preserved for off-chain reconstruction.
+    ///
+    /// Returns `true` if pruning was performed, `false` if not yet eligible.
+    pub fn archive_custody(env: Env, unit_id: u64) -> Result<bool, Error> {
+        storage_lifecycle::archive_custody_events(&env, unit_id)
+    }
+
+    /// Replace a terminal blood unit's record with a compact `ArchivedUnit`
+    /// (permissionless).
+    ///
+    /// Eligible once the unit has been Delivered, Discarded, Expired, Recalled,
+    /// Split or Pooled for at least 30 days. Afterwards `get_blood_unit` returns
+    /// `UnitNotFound` and `get_archived_unit` returns the hash, final status and
+    /// timestamps.
+    ///
+    /// Returns `true` if archival was performed, `false` if not yet eligible.
+    pub fn archive_unit(env: Env, unit_id: u64) -> Result<bool, Error> {
+        storage_lifecycle::archive_unit(&env, unit_id)
+    }
+
+    /// Retrieve the archived record for a unit.
+    ///
+    /// Returns `None` if the unit has not been archived.
+    pub fn get_archived_unit(env: Env, unit_id: u64) -> Option<ArchivedUnit> {
+        storage_lifecycle::get_archived_unit(&env, unit_id)
+    }
+
+    /// Retrieve the archived history summary for a unit.
+    ///
+    /// Returns `None` if the history has not been archived yet (full history
+    /// is still available via `get_transfer_history`).
+    pub fn get_history_summary(env: Env, unit_id: u64) -> Option<ArchivedHistorySummary> {
+        storage_lifecycle::get_archived_history_summary(&env, unit_id)
+    }
+
+    /// Retrieve the archived custody summary for a unit.
+    ///
+    /// Returns `None` if custody events have not been archived yet.
+    pub fn get_custody_summary(env: Env, unit_id: u64) -> Option<ArchivedCustodySummary> {
+        storage_lifecycle::get_archived_custody_summary(&env, unit_id)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::{
+        symbol_short, testutils::Address as _, testutils::Events, testutils::Ledger as _,
+        testutils::MockAuth, testutils::MockAuthInvoke, Address, Env, IntoVal, String, Symbol,
+        TryFromVal,
+    };
+
+    fn setup_contract_with_admin(env: &Env) -> (Address, Address, HealthChainContractClient<'_>) {
+        let admin = Address::generate(env);
+        let contract_id = env.register(HealthChainContract, ());
+        let client = HealthChainContractClient::new(env, &contract_id);
+
+        env.mock_all_auths();
+        client.initialize(&admin);
+
+        (contract_id, admin, client)
+    }
+
+    fn setup_contract_with_hospital<'a>(
+        env: &'a Env,
+    ) -> (Address, Address, Address, HealthChainContractClient<'a>) {
+        let (contract_id, admin, client) = setup_contract_with_admin(env);
+        let hospital = Address::generate(env);
+
+        env.mock_all_auths();
+        client.register_hospital(&hospital);
+
+        env.mock_all_auths();
+
+        (contract_id, admin, hospital, client)
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Adversarial Access Control Tests (Privilege Escalation Attempts)
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1)")]
+    fn test_attack_hospital_spoofs_bank_register_blood_should_fail() {
+        let env = Env::default();
+        let (contract_id, _admin, client) = setup_contract_with_admin(&env);
+
+        // Register a hospital (not a bank)
+        let hospital = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_hospital(&hospital);
+
+        // Hospital attempts to register blood (requires authorized blood bank)
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (7 * 86400);
+
+        env.mock_all_auths();
+        client.register_blood(
+            &hospital,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &expiration,
+            &None,
+            &None,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1)")]
+    fn test_attack_unregistered_hospital_create_request_should_fail() {
+        let env = Env::default();
+        let (contract_id, _admin, client) = setup_contract_with_admin(&env);
+
+        // Unregistered hospital tries to create a request
+        let rogue_hospital = Address::generate(&env);
+        let current_time = env.ledger().timestamp();
+        let required_by = current_time + (2 * 86400);
+        let delivery = String::from_str(&env, "Ward 7B - ICU");
+
+        env.mock_all_auths();
+        client.create_request(
+            &rogue_hospital,
+            &BloodType::APositive,
+            &500,
+            &UrgencyLevel::High,
+            &required_by,
+            &delivery,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1)")]
+    fn test_attack_unregistered_bank_allocates_blood_should_fail() {
+        let env = Env::default();
+        let (contract_id, _admin, client) = setup_contract_with_admin(&env);
+
+        // Create a unit via legacy add_blood_unit (no bank auth required)
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (10 * 86400);
+        let unit_id = client.add_blood_unit(
+            &BloodType::ONegative,
+            &300,
+            &expiration,
+            &symbol_short!("DONOR"),
+            &symbol_short!("BANK"),
+        );
+
+        // Register a hospital to avoid UnauthorizedHospital being triggered later
+        let hospital = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_hospital(&hospital);
+
+        // Unregistered bank attempts to allocate
+        let rogue_bank = Address::generate(&env);
+        env.mock_all_auths();
+        client.allocate_blood(&rogue_bank, &unit_id, &hospital, &None, &None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #8)")]
+    fn test_attack_expired_unit_allocated_should_fail() {
+        let env = Env::default();
+        let (_contract_id, _admin, client) = setup_contract_with_admin(&env);
+
+        // Register an authorized bank and hospital
+        let bank = Address::generate(&env);
+        let hospital = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+        env.mock_all_auths();
+        client.register_hospital(&hospital);
+
+        // Create a unit that will expire shortly
+        let now = env.ledger().timestamp();
+        let expiration = now + 100;
+        let unit_id = client.add_blood_unit(
+            &BloodType::BPositive,
+            &250,
+            &expiration,
+            &symbol_short!("DNR"),
+            &symbol_short!("BANK"),
+        );
+
+        // Advance time past expiration and attempt allocation
+        env.ledger().set_timestamp(expiration + 1);
+        env.mock_all_auths();
+        client.allocate_blood(&bank, &unit_id, &hospital, &None, &None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1)")]
+    fn test_attack_revoked_bank_immediate_reuse_should_fail() {
+        let env = Env::default();
+        let (contract_id, _admin, client) = setup_contract_with_admin(&env);
+
+        // Register bank and create unit
+        let bank = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+        let now = env.ledger().timestamp();
+        let expiration = now + (5 * 86400);
+        let unit_id = client.add_blood_unit(
+            &BloodType::ABNegative,
+            &200,
+            &expiration,
+            &symbol_short!("DNR2"),
+            &symbol_short!("BANK"),
+        );
+
+        // "Revoke" by clearing BANKS map directly
+        env.as_contract(&contract_id, || {
+            let empty_banks = Map::<Address, LifecycleState>::new(&env);
+            env.storage().persistent().set(&BLOOD_BANKS, &empty_banks);
+        });
+
+        // Attempt to register blood using revoked bank (should fail Unauthorized)
+        env.mock_all_auths();
+        client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &100,
+            &expiration,
+            &None,
+            &None,
+        );
+
+        // Attempt to allocate using revoked bank (should also fail Unauthorized)
+        env.mock_all_auths();
+        let hospital = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_hospital(&hospital);
+        client.allocate_blood(&bank, &unit_id, &hospital, &None, &None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #7)")]
+    fn test_attack_wrong_hospital_confirm_delivery_should_fail() {
+        let env = Env::default();
+        let (_contract_id, _admin, client) = setup_contract_with_admin(&env);
+
+        // Register bank and two hospitals
+        let bank = Address::generate(&env);
+        let hospital_a = Address::generate(&env);
+        let hospital_b = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+        env.mock_all_auths();
+        client.register_hospital(&hospital_a);
+        env.mock_all_auths();
+        client.register_hospital(&hospital_b);
+
+        // Create unit and allocate to hospital A
+        let now = env.ledger().timestamp();
+        let expiration = now + (7 * 86400);
+        let unit_id = client.add_blood_unit(
+            &BloodType::APositive,
+            &300,
+            &expiration,
+            &symbol_short!("DNR3"),
+            &symbol_short!("BANK"),
+        );
+        env.mock_all_auths();
+        client.allocate_blood(&bank, &unit_id, &hospital_a, &None, &None);
+
+        // Hospital B attempts to confirm delivery for unit allocated to A
+        env.mock_all_auths();
+        client.confirm_delivery(&hospital_b, &unit_id, &None, &None, &None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1)")]
+    fn test_attack_withdraw_blood_by_unauthorized_address_should_fail() {
+        let env = Env::default();
+        let (_contract_id, _admin, client) = setup_contract_with_admin(&env);
+
+        // Create a unit
+        let now = env.ledger().timestamp();
+        let expiration = now + (10 * 86400);
+        let unit_id = client.add_blood_unit(
+            &BloodType::ONegative,
+            &400,
+            &expiration,
+            &symbol_short!("DNR4"),
+            &symbol_short!("BANK"),
+        );
+
+        // Rogue address (neither bank nor hospital) attempts to withdraw
+        let attacker = Address::generate(&env);
+        env.mock_all_auths();
+        client.withdraw_blood(&attacker, &unit_id, &WithdrawalReason::Other);
+    }
+    #[test]
+    fn test_initialize() {
+        let env = Env::default();
+        let admin = Address::generate(&env);
+        let contract_id = env.register(HealthChainContract, ());
+        let client = HealthChainContractClient::new(&env, &contract_id);
+
+        env.mock_all_auths();
+        let result = client.initialize(&admin);
+        assert_eq!(result, symbol_short!("init"));
+
+        // A second initialization cannot take over the admin role
+        let attacker = Address::generate(&env);
+        let result = client.try_initialize(&attacker);
+        assert_eq!(result, Err(Ok(Error::AlreadyInitialized)));
+        let result = client.try_initialize_with_recovery(&attacker, &attacker);
+        assert_eq!(result, Err(Ok(Error::AlreadyInitialized)));
+        client.set_allow_backdated(&admin, &true);
+    }
+
+    #[test]
+    fn test_register_blood_bank() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+
+        // Verify bank is registered
+        assert_eq!(client.is_blood_bank(&bank), true);
+    }
+
+    #[test]
+    fn test_register_blood_success() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (7 * 86400); // 7 days from now
+
+        let result = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &expiration,
+            &Some(symbol_short!("donor1")),
+            &None,
+        );
+
+        assert_eq!(result, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1)")]
+    fn test_register_blood_unauthorized_bank() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let unauthorized_bank = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (7 * 86400);
+
+        client.register_blood(
+            &unauthorized_bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &expiration,
+            &Some(symbol_short!("donor1")),
+            &None,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #2)")]
+    fn test_register_blood_invalid_quantity_too_low() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (7 * 86400);
+
+        client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &25, // Below minimum
+            &expiration,
+            &Some(symbol_short!("donor1")),
+            &None,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #2)")]
+    fn test_register_blood_invalid_quantity_too_high() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (7 * 86400);
+
+        client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &600, // Above maximum
+            &expiration,
+            &Some(symbol_short!("donor1")),
+            &None,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #36)")]
+    fn test_register_blood_expired_date() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+
+        let expiration = 0; // Already expired
+
+        client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &expiration,
+            &Some(symbol_short!("donor1")),
+            &None,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #3)")]
+    fn test_register_blood_expiration_too_far() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (50 * 86400); // 50 days (exceeds 42 day limit)
+
+        client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &expiration,
+            &Some(symbol_short!("donor1")),
+            &None,
+        );
+    }
+
+    #[test]
+    fn test_register_blood_future_dated_succeeds() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+        env.ledger().set_timestamp(1_000_000);
+
+        let expiration = env.ledger().timestamp() + (7 * 86400);
+        let unit_id = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &expiration,
+            &Some(symbol_short!("donor1")),
+            &None,
+        );
+
+        assert_eq!(client.get_blood_unit(&unit_id).expiration_date, expiration);
+    }
+
+    #[test]
+    fn test_register_blood_past_dated_rejected() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+        env.ledger().set_timestamp(1_000_000);
+
+        let result = client.try_register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &(env.ledger().timestamp() - 86400),
+            &Some(symbol_short!("donor1")),
+            &None,
+        );
+
+        assert_eq!(result, Err(Ok(Error::AlreadyExpired)));
+        assert!(!client.is_backdating_allowed());
+    }
+
+    #[test]
+    fn test_register_blood_past_dated_allowed_with_migration_flag() {
+        let env = Env::default();
+        let (_, admin, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+        env.ledger().set_timestamp(1_000_000);
+
+        client.set_allow_backdated(&admin, &true);
+        assert!(client.is_backdating_allowed());
+
+        let expiration = env.ledger().timestamp() - 86400;
+        let unit_id = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &expiration,
+            &Some(symbol_short!("donor1")),
+            &None,
+        );
+        assert_eq!(client.get_blood_unit(&unit_id).expiration_date, expiration);
+
+        // Turning the flag back off restores the rejection.
+        client.set_allow_backdated(&admin, &false);
+        let result = client.try_register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &expiration,
+            &Some(symbol_short!("donor1")),
+            &None,
+        );
+        assert_eq!(result, Err(Ok(Error::AlreadyExpired)));
+    }
+
+    #[test]
+    fn test_set_allow_backdated_rejects_non_admin() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let intruder = Address::generate(&env);
+
+        env.mock_all_auths();
+        let result = client.try_set_allow_backdated(&intruder, &true);
+
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        assert!(!client.is_backdating_allowed());
+    }
+
+    #[test]
+    fn test_register_blood_without_donor_id() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (7 * 86400);
+
+        let result = client.register_blood(
+            &bank,
+            &BloodType::ABNegative,
+            &BloodComponent::WholeBlood,
+            &350,
+            &expiration,
+            &None, // Anonymous donor
+            &None,
+        );
+
+        assert_eq!(result, 1);
+    }
+
+    #[test]
+    fn test_register_multiple_blood_units() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (7 * 86400);
+
+        // Register first unit
+        let id1 = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &expiration,
+            &Some(symbol_short!("donor1")),
+            &None,
+        );
+
+        // Register second unit
+        let id2 = client.register_blood(
+            &bank,
+            &BloodType::APositive,
+            &BloodComponent::WholeBlood,
+            &400,
+            &expiration,
+            &Some(symbol_short!("donor2")),
+            &None,
+        );
+
+        assert_eq!(id1, 1);
+        assert_eq!(id2, 2);
+    }
+
+    #[test]
+    fn test_register_blood_all_blood_types() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (7 * 86400);
+
+        let blood_types = vec![
+            &env,
+            BloodType::APositive,
+            BloodType::ANegative,
+            BloodType::BPositive,
+            BloodType::BNegative,
+            BloodType::ABPositive,
+            BloodType::ABNegative,
+            BloodType::OPositive,
+            BloodType::ONegative,
+        ];
+
+        for (i, blood_type) in blood_types.iter().enumerate() {
+            let result = client.register_blood(
+                &bank,
+                &blood_type,
+                &BloodComponent::WholeBlood,
+                &450,
+                &expiration,
+                &Some(symbol_short!("donor")),
+                &None,
+            );
+            assert_eq!(result, (i as u64) + 1);
+        }
+    }
+
+    #[test]
+    fn test_register_blood_minimum_valid_quantity() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (7 * 86400);
+
+        let result = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &50, // Minimum valid quantity
+            &expiration,
+            &Some(symbol_short!("donor1")),
+            &None,
+        );
+
+        assert_eq!(result, 1);
+    }
+
+    #[test]
+    fn test_register_blood_maximum_valid_quantity() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (7 * 86400);
+
+        let result = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &500, // Maximum valid quantity
+            &expiration,
+            &Some(symbol_short!("donor1")),
+            &None,
+        );
+
+        assert_eq!(result, 1);
+    }
+
+    #[test]
+    fn test_register_blood_minimum_shelf_life() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (1 * 86400) + 1; // Just over 1 day
+
+        let result = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &expiration,
+            &Some(symbol_short!("donor1")),
+            &None,
+        );
+
+        assert_eq!(result, 1);
+    }
+
+    #[test]
+    fn test_register_blood_maximum_shelf_life() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (42 * 86400); // Exactly 42 days
+
+        let result = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &expiration,
+            &Some(symbol_short!("donor1")),
+            &None,
+        );
+
+        assert_eq!(result, 1);
+    }
+
+    #[test]
+    fn test_multiple_blood_banks() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank1 = Address::generate(&env);
+        let bank2 = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.register_blood_bank(&bank1);
+        client.register_blood_bank(&bank2);
+
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (7 * 86400);
+
+        // Both banks can register blood
+        let id1 = client.register_blood(
+            &bank1,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &expiration,
+            &Some(symbol_short!("donor1")),
+            &None,
+        );
+
+        let id2 = client.register_blood(
+            &bank2,
+            &BloodType::APositive,
+            &BloodComponent::WholeBlood,
+            &400,
+            &expiration,
+            &Some(symbol_short!("donor2")),
+            &None,
+        );
+
+        assert_eq!(id1, 1);
+        assert_eq!(id2, 2);
+    }
+
+    #[test]
+    fn test_store_record() {
+        let env = Env::default();
+        let contract_id = env.register(HealthChainContract, ());
+        let client = HealthChainContractClient::new(&env, &contract_id);
+
+        let patient = symbol_short!("patient1");
+        let hash = symbol_short!("hash123");
+
+        let result = client.store_record(&patient, &hash);
+        assert_eq!(result.len(), 2);
+    }
+
+    #[test]
+    fn test_verify_access() {
+        let env = Env::default();
+        let contract_id = env.register(HealthChainContract, ());
+        let client = HealthChainContractClient::new(&env, &contract_id);
+
+        let patient = symbol_short!("patient1");
+        let provider = symbol_short!("doctor1");
+
+        let has_access = client.verify_access(&patient, &provider);
+        assert_eq!(has_access, true);
+    }
+
+    #[test]
+    fn test_add_blood_unit() {
+        let env = Env::default();
+        let contract_id = env.register(HealthChainContract, ());
+        let client = HealthChainContractClient::new(&env, &contract_id);
+
+        let id = client.add_blood_unit(
+            &BloodType::OPositive,
+            &100,
+            &(env.ledger().timestamp() + 86400 * 30), // 30 days from now
+            &symbol_short!("donor1"),
+            &symbol_short!("loc1"),
+        );
+
+        assert_eq!(id, 1);
+    }
+
+    #[test]
+    fn test_query_by_blood_type_basic() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HealthChainContract, ());
+        let client = HealthChainContractClient::new(&env, &contract_id);
+
+        let current_time = env.ledger().timestamp();
+
+        // Add multiple blood units
+        client.add_blood_unit(
+            &BloodType::OPositive,
+            &100,
+            &(current_time + 86400 * 30),
+            &symbol_short!("donor1"),
+            &symbol_short!("loc1"),
+        );
+
+        client.add_blood_unit(
+            &BloodType::OPositive,
+            &50,
+            &(current_time + 86400 * 15),
+            &symbol_short!("donor2"),
+            &symbol_short!("loc1"),
+        );
+
+        client.add_blood_unit(
+            &BloodType::APositive,
+            &75,
+            &(current_time + 86400 * 20),
+            &symbol_short!("donor3"),
+            &symbol_short!("loc2"),
+        );
+
+        // Query O+ blood
+        let results = client.query_by_blood_type(&BloodType::OPositive, &None, &0, &10);
+        assert_eq!(results.len(), 2);
+    }
+
+    #[test]
+    fn test_query_excludes_expired() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HealthChainContract, ());
+        let client = HealthChainContractClient::new(&env, &contract_id);
+
+        let current_time = env.ledger().timestamp();
+
+        // Add expired unit (expiration = 0, which is before current_time)
+        client.add_blood_unit(
+            &BloodType::OPositive,
+            &100,
+            &0, // Already expired
+            &symbol_short!("donor1"),
+            &symbol_short!("loc1"),
+        );
+
+        // Add valid unit
+        client.add_blood_unit(
+            &BloodType::OPositive,
+            &50,
+            &(current_time + 86400 * 15),
+            &symbol_short!("donor2"),
+            &symbol_short!("loc1"),
+        );
+
+        let results = client.query_by_blood_type(&BloodType::OPositive, &None, &0, &10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results.get(0).unwrap().quantity, 50);
+    }
+
+    #[test]
+    fn test_query_min_quantity_filter() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HealthChainContract, ());
+        let client = HealthChainContractClient::new(&env, &contract_id);
+
+        let current_time = env.ledger().timestamp();
+
+        client.add_blood_unit(
+            &BloodType::OPositive,
+            &100,
+            &(current_time + 86400 * 30),
+            &symbol_short!("donor1"),
+            &symbol_short!("loc1"),
+        );
+
+        client.add_blood_unit(
+            &BloodType::OPositive,
+            &25,
+            &(current_time + 86400 * 15),
+            &symbol_short!("donor2"),
+            &symbol_short!("loc1"),
+        );
+
+        // Query with min_quantity = 50
+        let results = client.query_by_blood_type(&BloodType::OPositive, &None, &50, &10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results.get(0).unwrap().quantity, 100);
+    }
+
+    #[test]
+    fn test_query_fifo_sorting() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HealthChainContract, ());
+        let client = HealthChainContractClient::new(&env, &contract_id);
+
+        let current_time = env.ledger().timestamp();
+
+        // Add units with different expiration dates (not in order)
+        client.add_blood_unit(
+            &BloodType::OPositive,
+            &100,
+            &(current_time + 86400 * 30), // Expires last
+            &symbol_short!("donor1"),
+            &symbol_short!("loc1"),
+        );
+
+        client.add_blood_unit(
+            &BloodType::OPositive,
+            &50,
+            &(current_time + 86400 * 10), // Expires first
+            &symbol_short!("donor2"),
+            &symbol_short!("loc1"),
+        );
+
+        client.add_blood_unit(
+            &BloodType::OPositive,
+            &75,
+            &(current_time + 86400 * 20), // Expires middle
+            &symbol_short!("donor3"),
+            &symbol_short!("loc1"),
+        );
+
+        let results = client.query_by_blood_type(&BloodType::OPositive, &None, &0, &10);
+        assert_eq!(results.len(), 3);
+
+        // Verify FIFO order (earliest expiration first)
+        assert_eq!(results.get(0).unwrap().quantity, 50);
+        assert_eq!(results.get(1).unwrap().quantity, 75);
+        assert_eq!(results.get(2).unwrap().quantity, 100);
+    }
+
+    #[test]
+    fn test_query_pagination() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HealthChainContract, ());
+        let client = HealthChainContractClient::new(&env, &contract_id);
+
+        let current_time = env.ledger().timestamp();
+
+        // Add 5 units
+        for i in 1..=5 {
+            client.add_blood_unit(
+                &BloodType::OPositive,
+                &(i * 10),
+                &(current_time + 86400 * i as u64),
+                &symbol_short!("donor"),
+                &symbol_short!("loc1"),
+            );
+        }
+
+        // Query with max_results = 2
+        let results = client.query_by_blood_type(&BloodType::OPositive, &None, &0, &2);
+        assert_eq!(results.len(), 2);
+
+        // Query with max_results = 0 (falls back to the default page size)
+        let all_results = client.query_by_blood_type(&BloodType::OPositive, &None, &0, &0);
+        assert_eq!(all_results.len(), 5);
+    }
+
+    #[test]
+    fn test_query_no_results() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HealthChainContract, ());
+        let client = HealthChainContractClient::new(&env, &contract_id);
+
+        // Query without adding any units
+        let results = client.query_by_blood_type(&BloodType::OPositive, &None, &0, &10);
+        assert_eq!(results.len(), 0);
+    }
+
+    #[test]
+    fn test_check_availability_sufficient() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HealthChainContract, ());
+        let client = HealthChainContractClient::new(&env, &contract_id);
+
+        let current_time = env.ledger().timestamp();
+
+        client.add_blood_unit(
+            &BloodType::OPositive,
+            &100,
+            &(current_time + 86400 * 30),
+            &symbol_short!("donor1"),
+            &symbol_short!("loc1"),
+        );
+
+        client.add_blood_unit(
+            &BloodType::OPositive,
+            &50,
+            &(current_time + 86400 * 15),
+            &symbol_short!("donor2"),
+            &symbol_short!("loc1"),
+        );
+
+        // Check for 120 units (should be available: 100 + 50 = 150)
+        let available = client.check_availability(&BloodType::OPositive, &None, &120);
+        assert_eq!(available, true);
+    }
+
+    #[test]
+    fn test_check_availability_insufficient() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HealthChainContract, ());
+        let client = HealthChainContractClient::new(&env, &contract_id);
+
+        let current_time = env.ledger().timestamp();
+
+        client.add_blood_unit(
+            &BloodType::OPositive,
+            &100,
+            &(current_time + 86400 * 30),
+            &symbol_short!("donor1"),
+            &symbol_short!("loc1"),
+        );
+
+        // Check for 200 units (only 100 available)
+        let available = client.check_availability(&BloodType::OPositive, &None, &200);
+        assert_eq!(available, false);
+    }
+
+    #[test]
+    fn test_check_availability_excludes_expired() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HealthChainContract, ());
+        let client = HealthChainContractClient::new(&env, &contract_id);
+
+        let current_time = env.ledger().timestamp();
+
+        // Add expired unit (expiration = 0, which is before current_time)
+        client.add_blood_unit(
+            &BloodType::OPositive,
+            &100,
+            &0, // Already expired
+            &symbol_short!("donor1"),
+            &symbol_short!("loc1"),
+        );
+
+        // Add valid unit
+        client.add_blood_unit(
+            &BloodType::OPositive,
+            &50,
+            &(current_time + 86400 * 15),
+            &symbol_short!("donor2"),
+            &symbol_short!("loc1"),
+        );
+
+        // Check for 75 units (only 50 available, expired doesn't count)
+        let available = client.check_availability(&BloodType::OPositive, &None, &75);
+        assert_eq!(available, false);
+
+        // Check for 50 units (should be available)
+        let available = client.check_availability(&BloodType::OPositive, &None, &50);
+        assert_eq!(available, true);
+    }
+
+    #[test]
+    fn test_check_availability_no_inventory() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HealthChainContract, ());
+        let client = HealthChainContractClient::new(&env, &contract_id);
+
+        // Check without adding any units
+        let available = client.check_availability(&BloodType::OPositive, &None, &1);
+        assert_eq!(available, false);
+    }
+
+    #[test]
+    fn test_create_request_success() {
+        let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+
+        env.mock_all_auths();
+        let current_time = env.ledger().timestamp();
+        let required_by = current_time + 3600;
+
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::APositive,
+            &500,
+            &UrgencyLevel::Urgent,
+            &required_by,
+            &String::from_str(&env, "Ward A, City Hospital"),
+        );
+
+        let events = env.events().all();
+        assert_eq!(events.len(), 1);
+
+        assert_eq!(request_id, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1)")]
+    fn test_create_request_unauthorized_hospital() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let hospital = Address::generate(&env);
+
+        env.mock_all_auths();
+        let current_time = env.ledger().timestamp();
+        let required_by = current_time + 3600;
+
+        client.create_request(
+            &hospital,
+            &BloodType::ONegative,
+            &600,
+            &UrgencyLevel::Critical,
+            &required_by,
+            &String::from_str(&env, "ER"),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #2)")]
+    fn test_create_request_invalid_quantity_low() {
+        let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+
+        env.mock_all_auths();
+        let current_time = env.ledger().timestamp();
+        let required_by = current_time + 3600;
+
+        client.create_request(
+            &hospital,
+            &BloodType::OPositive,
+            &10,
+            &UrgencyLevel::Routine,
+            &required_by,
+            &String::from_str(&env, "Ward B"),
+        );
+    }
+
+    #[test]
+    fn test_create_blood_request_success() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let hospital = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.register_hospital(&hospital);
+
+        let required_by = env.ledger().timestamp() + 86400; // Tomorrow
+        let result = client.create_request(
+            &hospital,
+            &BloodType::ABNegative,
+            &500,
+            &UrgencyLevel::High,
+            &required_by,
+            &String::from_str(&env, "Main_Hosp"),
+        );
+
+        assert_eq!(result, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1)")] // Error::Unauthorized
+    fn test_create_request_unauthorized() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let rogue_hospital = Address::generate(&env);
+
+        env.mock_all_auths();
+        // hospital is NOT registered via client.register_hospital()
+
+        client.create_request(
+            &rogue_hospital,
+            &BloodType::OPositive,
+            &400,
+            &UrgencyLevel::Medium,
+            &(env.ledger().timestamp() + 86400),
+            &String::from_str(&env, "Hosp_1"),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #2)")]
+    fn test_create_request_invalid_quantity_high() {
+        let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+
+        env.mock_all_auths();
+        let current_time = env.ledger().timestamp();
+        let required_by = current_time + 3600;
+
+        client.create_request(
+            &hospital,
+            &BloodType::BPositive,
+            &6000,
+            &UrgencyLevel::Routine,
+            &required_by,
+            &String::from_str(&env, "Ward B"),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #2)")] // Error::InvalidQuantity
+    fn test_create_request_invalid_quantity() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let hospital = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.register_hospital(&hospital);
+
+        client.create_request(
+            &hospital,
+            &BloodType::OPositive,
+            &10, // Below MIN_QUANTITY_ML (50)
+            &UrgencyLevel::Low,
+            &(env.ledger().timestamp() + 86400),
+            &String::from_str(&env, "Hosp_1"),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #15)")]
+    fn test_create_request_required_by_in_past() {
+        let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+
+        env.mock_all_auths();
+        let current_time = env.ledger().timestamp();
+        let required_by = current_time;
+
+        client.create_request(
+            &hospital,
+            &BloodType::ABPositive,
+            &200,
+            &UrgencyLevel::Urgent,
+            &required_by,
+            &String::from_str(&env, "Ward C"),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #34)")]
+    fn test_create_request_delivery_address_too_long() {
+        let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+
+        env.mock_all_auths();
+        let current_time = env.ledger().timestamp();
+        let required_by = current_time + 3600;
+
+        // 201 bytes — one byte over MAX_DELIVERY_ADDRESS_LENGTH (200)
+        let addr_bytes = [b'a'; 201];
+        let addr_str = core::str::from_utf8(&addr_bytes).unwrap();
+        let long_addr = String::from_str(&env, addr_str);
+
+        client.create_request(
+            &hospital,
+            &BloodType::OPositive,
+            &200,
+            &UrgencyLevel::High,
+            &required_by,
+            &long_addr,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #14)")]
+    fn test_create_request_empty_delivery_address() {
+        let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+
+        env.mock_all_auths();
+        let current_time = env.ledger().timestamp();
+        let required_by = current_time + 3600;
+
+        client.create_request(
+            &hospital,
+            &BloodType::ABNegative,
+            &200,
+            &UrgencyLevel::Urgent,
+            &required_by,
+            &String::from_str(&env, ""),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #15)")]
+    fn test_create_request_past_date() {
+        let env = Env::default();
+
+        // Set the time to something substantial first
+        env.ledger().with_mut(|li| li.timestamp = 10000);
+
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let hospital = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.register_hospital(&hospital);
+
+        client.create_request(
+            &hospital,
+            &BloodType::OPositive,
+            &200,
+            &UrgencyLevel::High,
+            &5000, // Now this is safely in the past (5000 < 10000)
+            &String::from_str(&env, "Hosp_1"),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #13)")]
+    fn test_create_request_duplicate_request() {
+        let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+
+        env.mock_all_auths();
+        let current_time = env.ledger().timestamp();
+        let required_by = current_time + 7200;
+        let address = String::from_str(&env, "Ward D");
+
+        client.create_request(
+            &hospital,
+            &BloodType::OPositive,
+            &350,
+            &UrgencyLevel::Urgent,
+            &required_by,
+            &address,
+        );
+
+        client.create_request(
+            &hospital,
+            &BloodType::OPositive,
+            &350,
+            &UrgencyLevel::Urgent,
+            &required_by,
+            &address,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #13)")]
+    fn test_create_request_duplicate_request_with_delivery_case_and_spacing_variations() {
+        let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+
+        env.mock_all_auths();
+        let current_time = env.ledger().timestamp();
+        let required_by = current_time + 7200;
+
+        client.create_request(
+            &hospital,
+            &BloodType::APositive,
+            &400,
+            &UrgencyLevel::High,
+            &required_by,
+            &String::from_str(&env, " Ward   7B, ICU "),
+        );
+
+        // Same logical request, delivery text variant only.
+        client.create_request(
+            &hospital,
+            &BloodType::APositive,
+            &400,
+            &UrgencyLevel::High,
+            &required_by,
+            &String::from_str(&env, "ward 7b, icu"),
+        );
+    }
+
+    #[test]
+    fn test_create_request_event_payload() {
+        let env = Env::default();
+        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
+
+        env.mock_all_auths();
+        let current_time = env.ledger().timestamp();
+        let required_by = current_time + 7200;
+        let delivery_address = String::from_str(&env, "Ward E, General Hospital");
+
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::ONegative,
+            &450,
+            &UrgencyLevel::Critical,
+            &required_by,
+            &delivery_address,
+        );
+
+        let events = env.events().all();
+        assert_eq!(events.len(), 1);
+
+        let (event_contract_id, topics, data) = events.get(0).unwrap();
+        assert_eq!(event_contract_id, contract_id);
+        assert_eq!(topics.len(), 3);
+
+        let topic0: Symbol = TryFromVal::try_from_val(&env, &topics.get(0).unwrap()).unwrap();
+        let topic1: Symbol = TryFromVal::try_from_val(&env, &topics.get(1).unwrap()).unwrap();
+        let version_topic: Symbol =
+            TryFromVal::try_from_val(&env, &topics.get(2).unwrap()).unwrap();
+        assert_eq!(topic0, symbol_short!("blood"));
+        assert_eq!(topic1, symbol_short!("request"));
+        assert_eq!(version_topic, symbol_short!("v1"));
+
+        let event: RequestCreatedEvent = TryFromVal::try_from_val(&env, &data).unwrap();
+        assert_eq!(event.request_id, request_id);
+        assert_eq!(event.hospital_id, hospital);
+        assert!(event.blood_type == BloodType::ONegative);
+        assert_eq!(event.quantity_ml, 450);
+        assert!(event.urgency == UrgencyLevel::Critical);
+        assert_eq!(event.required_by, required_by);
+        assert_eq!(event.delivery_address, delivery_address);
+        assert_eq!(event.created_at, current_time);
+    }
+
+    #[test]
+    fn test_create_request_emits_event() {
+        let env = Env::default();
+        let (contract_id, _, client) = setup_contract_with_admin(&env);
+        let hospital = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.register_hospital(&hospital);
+
+        let req_id = client.create_request(
+            &hospital,
+            &BloodType::BPositive,
+            &300,
+            &UrgencyLevel::Critical,
+            &(env.ledger().timestamp() + 3600),
+            &String::from_str(&env, "ER_Room"),
+        );
+
+        // Get the last event
+        let last_event = env.events().all().last().unwrap();
+
+        // 1. Verify the Contract ID
+        assert_eq!(last_event.0, contract_id);
+
+        // 2. Verify the Topics (blood, request, v1)
+        let expected_topics = (
+            symbol_short!("blood"),
+            symbol_short!("request"),
+            symbol_short!("v1"),
+        )
+            .into_val(&env);
+        assert_eq!(last_event.1, expected_topics);
+
+        // 3. Verify the Data (Optional: Deserialize it to be sure)
+        // Fixed: Use RequestCreatedEvent instead of legacy BloodRequestEvent which had missing fields
+        let event_data: RequestCreatedEvent = last_event.2.into_val(&env);
+        assert_eq!(event_data.request_id, req_id);
+        assert_eq!(event_data.hospital_id, hospital);
+    }
+
+    #[test]
+    fn test_event_schema_version_topic_distinguishes_current_from_legacy() {
+        let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+
+        env.mock_all_auths();
+        client.create_request(
+            &hospital,
+            &BloodType::BPositive,
+            &300,
+            &UrgencyLevel::Critical,
+            &(env.ledger().timestamp() + 3600),
+            &String::from_str(&env, "ER_Room"),
+        );
+
+        let (_, topics, _) = env.events().all().last().unwrap();
+        let legacy_topics = (symbol_short!("blood"), symbol_short!("request")).into_val(&env);
+        let current_topics = (
+            symbol_short!("blood"),
+            symbol_short!("request"),
+            symbol_short!("v1"),
+        )
+            .into_val(&env);
+
+        assert_ne!(topics, legacy_topics);
+        assert_eq!(topics, current_topics);
+        assert_eq!(EVENT_SCHEMA_VERSION, 1);
+    }
+
+    #[test]
+    fn test_approve_request_reserves_units_and_updates_request() {
+        let env = Env::default();
+        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
+
+        let bank = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (7 * 86400);
+
+        let unit_id_1 = client.register_blood(
+            &bank,
+            &BloodType::APositive,
+            &BloodComponent::WholeBlood,
+            &300,
+            &expiration,
+            &Some(symbol_short!("donor1")),
+            &None,
+        );
+        let unit_id_2 = client.register_blood(
+            &bank,
+            &BloodType::APositive,
+            &BloodComponent::WholeBlood,
+            &250,
+            &expiration,
+            &Some(symbol_short!("donor2")),
+            &None,
+        );
+
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::APositive,
+            &500,
+            &UrgencyLevel::Urgent,
+            &(current_time + 3600),
+            &String::from_str(&env, "Ward A"),
+        );
+
+        let unit_ids = vec![&env, unit_id_1, unit_id_2];
+        env.mock_all_auths();
+        client.approve_request(&bank, &request_id, &unit_ids);
+
+        let requests: Map<u64, BloodRequest> = env.as_contract(&contract_id, || {
+            env.storage()
+                .persistent()
+                .get(&REQUESTS)
+                .unwrap_or(Map::new(&env))
+        });
+        let request = requests.get(request_id).unwrap();
+
+        assert_eq!(request.status, RequestStatus::Approved);
+        assert_eq!(request.fulfilled_quantity_ml, 550);
+        assert_eq!(request.reserved_unit_ids, unit_ids);
+
+        let unit1 = client.get_blood_unit(&unit_id_1);
+        let unit2 = client.get_blood_unit(&unit_id_2);
+        assert_eq!(unit1.status, BloodStatus::Reserved);
+        assert_eq!(unit2.status, BloodStatus::Reserved);
+        assert_eq!(unit1.recipient_hospital, Some(hospital.clone()));
+        assert_eq!(unit2.recipient_hospital, Some(hospital));
+    }
+
+    #[test]
+    fn test_approve_request_partial_sets_in_progress() {
+        let env = Env::default();
+        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
+
+        let bank = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (7 * 86400);
+
+        let unit_id = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &200,
+            &expiration,
+            &Some(symbol_short!("donor1")),
+            &None,
+        );
+
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::OPositive,
+            &500,
+            &UrgencyLevel::Urgent,
+            &(current_time + 3600),
+            &String::from_str(&env, "Ward B"),
+        );
 
-        Ok(())
-    }
+        let unit_ids = vec![&env, unit_id];
+        env.mock_all_auths();
+        client.approve_request(&bank, &request_id, &unit_ids);
 
-    /// Propose a new admin (canonical alias for `nominate_super_admin`).
-    ///
-    /// Requires auth from the current admin. Emits `AdminProposedEvent`.
-    pub fn propose_admin(env: Env, proposed: Address) -> Result<(), Error> {
-        Self::nominate_super_admin(env, proposed)
-    }
+        let requests: Map<u64, BloodRequest> = env.as_contract(&contract_id, || {
+            env.storage()
+                .persistent()
+                .get(&REQUESTS)
+                .unwrap_or(Map::new(&env))
+        });
+        let request = requests.get(request_id).unwrap();
 
-    /// Accept a pending admin proposal (canonical alias for `accept_super_admin`).
-    ///
-    /// Requires auth from the proposed admin. Emits `AdminTransferredEvent`.
-    pub fn accept_admin(env: Env) -> Result<(), Error> {
-        Self::accept_super_admin(env)
+        assert_eq!(request.status, RequestStatus::InProgress);
+        assert_eq!(request.fulfilled_quantity_ml, 200);
     }
 
-        /// Authorize an inventory contract for cross-contract synchronization (admin only).
-    ///
-    /// The authorized inventory contract may call `inventory_reserve_unit` and
-    /// `inventory_release_unit` to keep its reservation state consistent with
-    /// the registry's canonical unit status.
-    pub fn set_inventory_contract(
-        env: Env,
-        admin: Address,
-        inventory_contract_id: Address,
-    ) -> Result<(), Error> {
-        admin.require_auth();
-        let stored_admin: Address = env
-            .storage()
-            .instance()
-            .get(&ADMIN)
-            .ok_or(Error::Unauthorized)?;
-        if admin != stored_admin {
-            return Err(Error::Unauthorized);
-        }
-        env.storage()
-            .instance()
-            .set(&INVENTORY_CONTRACT, &inventory_contract_id);
-        Ok(())
-    }
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1)")]
+    fn test_approve_request_requires_blood_bank() {
+        let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let non_bank = Address::generate(&env);
 
-    /// Check whether a blood unit exists and is in `Available` status.
-    ///
-    /// Intended for cross-contract calls from the inventory contract.
-    /// Returns `false` if the unit does not exist, is not Available, or is expired.
-    pub fn check_unit_available(env: Env, unit_id: u64) -> bool {
-        let units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::BPositive,
+            &300,
+            &UrgencyLevel::Routine,
+            &(env.ledger().timestamp() + 3600),
+            &String::from_str(&env, "Ward C"),
+        );
 
-        match units.get(unit_id) {
-            Some(unit) => {
-                let current_time = env.ledger().timestamp();
-                unit.status == BloodStatus::Available && unit.expiration_date > current_time
-            }
-            None => false,
-        }
+        let unit_ids = vec![&env, 1u64];
+        env.mock_all_auths();
+        client.approve_request(&non_bank, &request_id, &unit_ids);
     }
 
-    /// Mark a blood unit as Reserved, called by the authorized inventory contract.
-    ///
-    /// Only the contract stored via `set_inventory_contract` may call this.
-    /// The `bank_id` is the blood bank that owns the unit — its auth is verified
-    /// by the inventory contract before this cross-contract call is made.
-    pub fn inventory_reserve_unit(
-        env: Env,
-        bank_id: Address,
-        unit_id: u64,
-        hospital_id: Address,
-    ) -> Result<(), Error> {
-        let authorized: Address = env
-            .storage()
-            .instance()
-            .get(&INVENTORY_CONTRACT)
-            .ok_or(Error::Unauthorized)?;
-        if env.current_contract_address() != authorized {
-            return Err(Error::Unauthorized);
-        }
+    #[test]
+    #[should_panic(expected = "Error(Contract, #28)")] // ArithmeticError
+    fn test_approve_request_fails_on_total_quantity_overflow() {
+        let env = Env::default();
+        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
 
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
+        let bank = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
 
-        let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
-        if unit.status != BloodStatus::Available {
-            return Err(Error::InvalidStatus);
-        }
         let current_time = env.ledger().timestamp();
-        if unit.expiration_date <= current_time {
-            return Err(Error::UnitExpired);
-        }
+        let expiration = current_time + (7 * 86400);
 
-        let old_status = unit.status;
-        unit.status = BloodStatus::Reserved;
-        unit.recipient_hospital = Some(hospital_id.clone());
-        unit.allocation_timestamp = Some(current_time);
+        let unit_id_1 = client.add_blood_unit(
+            &BloodType::APositive,
+            &500,
+            &expiration,
+            &symbol_short!("donor1"),
+            &symbol_short!("bank"),
+        );
+        let unit_id_2 = client.add_blood_unit(
+            &BloodType::APositive,
+            &500,
+            &expiration,
+            &symbol_short!("donor2"),
+            &symbol_short!("bank"),
+        );
 
-        units.set(unit_id, unit);
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
+        env.as_contract(&contract_id, || {
+            let mut unit_1 = load_unit(&env, unit_id_1).unwrap();
+            unit_1.quantity = u32::MAX;
+            save_unit(&env, &unit_1);
 
-        reindex_status(&env, unit_id, old_status, BloodStatus::Reserved);
-        index_hospital_unit(&env, &hospital_id, unit_id);
-        record_status_change(&env, unit_id, old_status, BloodStatus::Reserved, bank_id);
+            let mut unit_2 = load_unit(&env, unit_id_2).unwrap();
+            unit_2.quantity = 1;
+            save_unit(&env, &unit_2);
+        });
 
-        env.events().publish(
-            (
-                symbol_short!("blood"),
-                symbol_short!("allocate"),
-                symbol_short!("v1"),
-            ),
-            (unit_id, hospital_id, current_time),
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::APositive,
+            &500,
+            &UrgencyLevel::Urgent,
+            &(current_time + 3600),
+            &String::from_str(&env, "Ward O1"),
         );
 
-        Ok(())
+        let unit_ids = vec![&env, unit_id_1, unit_id_2];
+        env.mock_all_auths();
+        client.approve_request(&bank, &request_id, &unit_ids);
     }
 
-    /// Release a previously reserved blood unit back to Available.
-    ///
-    /// Called by the authorized inventory contract when a reservation is
-    /// cancelled or expires.
-    pub fn inventory_release_unit(
-        env: Env,
-        bank_id: Address,
-        unit_id: u64,
-    ) -> Result<(), Error> {
-        let authorized: Address = env
-            .storage()
-            .instance()
-            .get(&INVENTORY_CONTRACT)
-            .ok_or(Error::Unauthorized)?;
-        if env.current_contract_address() != authorized {
-            return Err(Error::Unauthorized);
-        }
+    #[test]
+    #[should_panic(expected = "Error(Contract, #28)")] // ArithmeticError
+    fn test_approve_request_fails_on_fulfillment_percentage_overflow() {
+        let env = Env::default();
+        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
 
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
+        let bank = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
 
-        let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
-        if unit.status != BloodStatus::Reserved {
-            return Err(Error::InvalidStatus);
-        }
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (7 * 86400);
 
-        let old_status = unit.status;
-        let hospital_id = unit.recipient_hospital.clone();
+        let unit_id = client.add_blood_unit(
+            &BloodType::OPositive,
+            &500,
+            &expiration,
+            &symbol_short!("donor1"),
+            &symbol_short!("bank"),
+        );
 
-        unit.status = BloodStatus::Available;
-        unit.recipient_hospital = None;
-        unit.allocation_timestamp = None;
+        env.as_contract(&contract_id, || {
+            let mut unit = load_unit(&env, unit_id).unwrap();
+            unit.quantity = u32::MAX;
+            save_unit(&env, &unit);
+        });
 
-        units.set(unit_id, unit);
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::OPositive,
+            &50,
+            &UrgencyLevel::Routine,
+            &(current_time + 3600),
+            &String::from_str(&env, "Ward O2"),
+        );
 
-        reindex_status(&env, unit_id, old_status, BloodStatus::Available);
-        if let Some(ref hosp) = hospital_id {
-            deindex_hospital_unit(&env, hosp, unit_id);
-        }
-        record_status_change(&env, unit_id, old_status, BloodStatus::Available, bank_id);
+        let unit_ids = vec![&env, unit_id];
+        env.mock_all_auths();
+        client.approve_request(&bank, &request_id, &unit_ids);
+    }
 
-        env.events().publish(
-            (
-                symbol_short!("blood"),
-                symbol_short!("cancel"),
-                symbol_short!("v1"),
-            ),
-            unit_id,
-        );
+    // Request Status Management Tests
+
+    #[test]
+    fn test_update_request_status_pending_to_approved() {
+        let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
 
-        Ok(())
-    }
+        env.mock_all_auths();
+        let current_time = env.ledger().timestamp();
+        let required_by = current_time + 3600;
 
-    /// Store a health record hash
-    pub fn store_record(env: Env, patient_id: Symbol, record_hash: Symbol) -> Vec<Symbol> {
-        vec![&env, patient_id, record_hash]
-    }
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::OPositive,
+            &500,
+            &UrgencyLevel::Urgent,
+            &required_by,
+            &String::from_str(&env, "Ward A"),
+        );
 
-    /// Retrieve stored record
-    pub fn get_record(_env: Env, patient_id: Symbol) -> Symbol {
-        patient_id
+        env.mock_all_auths();
+        client.update_request_status(&request_id, &RequestStatus::Approved);
     }
 
-    /// Verify record access
-    pub fn verify_access(_env: Env, _patient_id: Symbol, _provider_id: Symbol) -> bool {
-        true
-    }
+    #[test]
+    fn test_update_request_status_approved_to_in_progress() {
+        let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
 
-    /// Add a blood unit to inventory (legacy function for testing)
-    pub fn add_blood_unit(
-        env: Env,
-        blood_type: BloodType,
-        quantity: u32,
-        expiration_date: u64,
-        donor_id: Symbol,
-        location: Symbol,
-    ) -> u64 {
-        let id = get_next_id(&env);
+        env.mock_all_auths();
         let current_time = env.ledger().timestamp();
+        let required_by = current_time + 3600;
 
-        // Create a default address for legacy function using contract address
-        let default_bank = env.current_contract_address();
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::OPositive,
+            &500,
+            &UrgencyLevel::Urgent,
+            &required_by,
+            &String::from_str(&env, "Ward A"),
+        );
 
-        let unit = BloodUnit {
-            id,
-            blood_type,
-            component: BloodComponent::WholeBlood,
-            quantity,
-            expiration_date,
-            donor_id,
-            location,
-            bank_id: default_bank,
-            registration_timestamp: current_time,
-            status: BloodStatus::Available,
-            recipient_hospital: None,
-            allocation_timestamp: None,
-            transfer_timestamp: None,
-            delivery_timestamp: None,
-        };
+        env.mock_all_auths();
+        client.update_request_status(&request_id, &RequestStatus::Approved);
+        env.mock_all_auths();
+        client.update_request_status(&request_id, &RequestStatus::InProgress);
+    }
 
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
+    #[test]
+    #[should_panic(expected = "Error(Contract, #10)")] // InvalidTransition
+    fn test_update_request_status_invalid_transition_pending_to_fulfilled() {
+        let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
 
-        units.set(id, unit);
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
+        env.mock_all_auths();
+        let current_time = env.ledger().timestamp();
+        let required_by = current_time + 3600;
 
-        id
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::OPositive,
+            &500,
+            &UrgencyLevel::Urgent,
+            &required_by,
+            &String::from_str(&env, "Ward A"),
+        );
+
+        // Try to go directly from Pending to Fulfilled (invalid)
+        client.update_request_status(&request_id, &RequestStatus::Fulfilled);
     }
 
-    /// Query blood inventory by blood type with filters
-    /// Query blood inventory by blood type with filters
-    pub fn query_by_blood_type(
-        env: Env,
-        blood_type: BloodType,
-        min_quantity: u32,
-        max_results: u32,
-    ) -> Vec<BloodUnit> {
-        let units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
+    #[test]
+    #[should_panic(expected = "Error(Contract, #10)")] // InvalidTransition
+    fn test_update_request_status_no_transition_from_fulfilled() {
+        let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
 
+        env.mock_all_auths();
         let current_time = env.ledger().timestamp();
-        let mut results = vec![&env];
-        let mut temp_units = vec![&env];
-
-        // Collect matching units (Available status, non-expired, matching blood type, sufficient quantity)
-        for (_, unit) in units.iter() {
-            if unit.blood_type == blood_type
-                && unit.status == BloodStatus::Available
-                && unit.quantity >= min_quantity
-                && unit.expiration_date > current_time
-            {
-                temp_units.push_back(unit);
-            }
-        }
+        let required_by = current_time + 3600;
 
-        // Sort by expiration date (FIFO - earliest expiration first)
-        let len = temp_units.len();
-        for i in 0..len {
-            for j in 0..len.saturating_sub(i + 1) {
-                let unit_j = temp_units.get(j).unwrap();
-                let unit_j_plus_1 = temp_units.get(j + 1).unwrap();
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::OPositive,
+            &500,
+            &UrgencyLevel::Urgent,
+            &required_by,
+            &String::from_str(&env, "Ward A"),
+        );
 
-                if unit_j.expiration_date > unit_j_plus_1.expiration_date {
-                    temp_units.set(j, unit_j_plus_1.clone());
-                    temp_units.set(j + 1, unit_j);
-                }
-            }
-        }
+        client.update_request_status(&request_id, &RequestStatus::Approved);
+        client.update_request_status(&request_id, &RequestStatus::InProgress);
 
-        // Apply pagination
-        let limit = if max_results == 0 {
-            len
-        } else {
-            max_results.min(len)
-        };
-        for i in 0..limit {
-            if let Some(unit) = temp_units.get(i) {
-                results.push_back(unit);
-            }
-        }
+        // Manually fulfill by creating a dummy fulfilled state
+        // For this test, we'll use cancel and then try to update cancelled
+        client.cancel_request(&request_id, &String::from_str(&env, "Test"));
 
-        results
+        // Try to update from Cancelled (terminal state)
+        client.update_request_status(&request_id, &RequestStatus::Pending);
     }
 
-    /// Check if sufficient blood quantity is available
-    pub fn check_availability(env: Env, blood_type: BloodType, required_quantity: u32) -> bool {
-        let units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
-
-        let current_time = env.ledger().timestamp();
-        let mut total_quantity: u32 = 0;
+    #[test]
+    fn test_cancel_request_releases_reservations() {
+        let env = Env::default();
+        let (_, admin, hospital, client) = setup_contract_with_hospital(&env);
 
-        // Sum up available quantities for the blood type (Available status and non-expired only)
-        for (_, unit) in units.iter() {
-            if unit.blood_type == blood_type
-                && unit.status == BloodStatus::Available
-                && unit.expiration_date > current_time
-            {
-                total_quantity = total_quantity.saturating_add(unit.quantity);
+        // Register a blood bank
+        let bank = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
 
-                // Early exit if we've found enough
-                if total_quantity >= required_quantity {
-                    return true;
-                }
-            }
-        }
+        // Add blood units
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (7 * 86400);
 
-        total_quantity >= required_quantity
-    }
+        let unit_id_1 = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &250,
+            &expiration,
+            &Some(symbol_short!("donor1")),
+            &None,
+        );
 
-    /// Get all blood units registered by a specific bank.
-    ///
-    /// Delegates to [`registry_read::get_units_by_bank`].
-    pub fn get_units_by_bank(env: Env, bank_id: Address) -> Vec<BloodUnit> {
-        registry_read::get_units_by_bank(&env, bank_id)
-    }
+        let unit_id_2 = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &250,
+            &expiration,
+            &Some(symbol_short!("donor2")),
+            &None,
+        );
 
-    /// Mark a single blood unit as Expired if its expiration time has passed.
-    ///
-    /// Delegates to [`registry_write::expire_unit`].
-    pub fn expire_unit(env: Env, unit_id: u64) -> Result<(), Error> {
-        registry_write::expire_unit(&env, unit_id)
-    }
+        // Allocate units to hospital
+        client.allocate_blood(&bank, &unit_id_1, &hospital, &None, &None);
+        client.allocate_blood(&bank, &unit_id_2, &hospital, &None, &None);
 
-    /// Try to expire up to 50 units in a single call.
-    ///
-    /// Delegates to [`registry_write::check_and_expire_batch`].
-    pub fn check_and_expire_batch(env: Env, unit_ids: Vec<u64>) -> Result<Vec<u64>, Error> {
-        registry_write::check_and_expire_batch(&env, unit_ids)
-    }
-}
+        // Verify units are Reserved
+        let unit1 = client.get_blood_unit(&unit_id_1);
+        assert_eq!(unit1.status, BloodStatus::Reserved);
 
-#[contractimpl]
-impl HealthChainContract {
-    /// Register an organization (any address can self-register).
-    pub fn register_organization(env: Env, org_id: Address) -> Result<(), Error> {
-        org_id.require_auth();
+        // Create request
+        let required_by = current_time + 3600;
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::OPositive,
+            &500,
+            &UrgencyLevel::Urgent,
+            &required_by,
+            &String::from_str(&env, "Ward A"),
+        );
 
-        let org_key = OrgKey::Org(org_id.clone());
-        if env.storage().persistent().has(&org_key) {
-            return Err(Error::DuplicateRegistration);
-        }
+        // Cancel the request
+        client.cancel_request(&request_id, &String::from_str(&env, "No longer needed"));
 
-        let organization = Organization {
-            id: org_id.clone(),
-            verified: false,
-            verified_timestamp: None,
-            state: LifecycleState::Inactive,
-            state_changed_by: None,
-            state_changed_at: None,
-            state_change_reason: None,
-        };
+        // Verify units are back to Available (if they were in the reserved_unit_ids)
+        // Note: In our implementation, cancel_request releases units that were in reserved_unit_ids
+        // Since we didn't add them to the request, they should still be Reserved
+        // But the cancel function works correctly for units that ARE in the list
+    }
 
-        env.storage().persistent().set(&org_key, &organization);
+    #[test]
+    #[should_panic(expected = "Error(Contract, #6)")] // InvalidStatus
+    fn test_cancel_request_already_fulfilled() {
+        let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
 
-        env.events().publish(
-            (
-                symbol_short!("org"),
-                symbol_short!("reg"),
-                symbol_short!("v1"),
-            ),
-            org_id,
+        env.mock_all_auths();
+        let current_time = env.ledger().timestamp();
+        let required_by = current_time + 3600;
+
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::OPositive,
+            &500,
+            &UrgencyLevel::Urgent,
+            &required_by,
+            &String::from_str(&env, "Ward A"),
         );
 
-        Ok(())
-    }
+        // Move to Fulfilled
+        client.update_request_status(&request_id, &RequestStatus::Approved);
+        client.update_request_status(&request_id, &RequestStatus::InProgress);
 
-    /// Verify an organization (admin only).
-    pub fn verify_organization(env: Env, admin: Address, org_id: Address) -> Result<(), Error> {
-        admin.require_auth();
+        // We can't actually fulfill without blood bank, so let's just cancel an already cancelled
+        client.cancel_request(&request_id, &String::from_str(&env, "First cancel"));
 
-        let stored_admin: Address = env
-            .storage()
-            .instance()
-            .get(&ADMIN)
-            .ok_or(Error::Unauthorized)?;
-        if admin != stored_admin {
-            return Err(Error::Unauthorized);
-        }
+        // Try to cancel again (should fail because it's already Cancelled)
+        client.cancel_request(&request_id, &String::from_str(&env, "Second cancel"));
+    }
 
-        let org_key = OrgKey::Org(org_id.clone());
-        let mut organization: Organization = env
-            .storage()
-            .persistent()
-            .get(&org_key)
-            .ok_or(Error::OrganizationNotFound)?;
+    #[test]
+    fn test_fulfill_request_updates_inventory() {
+        let env = Env::default();
+        let (_, admin, hospital, client) = setup_contract_with_hospital(&env);
 
-        if organization.verified {
-            return Err(Error::AlreadyVerified);
-        }
+        // Register a blood bank
+        let bank = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
 
-        let old_state = organization.state;
-        organization.verified = true;
-        organization.verified_timestamp = Some(env.ledger().timestamp());
-        organization.state = LifecycleState::Active;
-        organization.state_changed_by = Some(admin.clone());
-        organization.state_changed_at = Some(env.ledger().timestamp());
-        organization.state_change_reason = Some(String::from_str(&env, "verification"));
-        env.storage().persistent().set(&org_key, &organization);
+        // Add blood units
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (7 * 86400);
 
-        let verifier_key = OrgKey::Verifier(org_id.clone());
-        env.storage().persistent().set(&verifier_key, &admin);
+        let unit_id_1 = client.register_blood(
+            &bank,
+            &BloodType::APositive,
+            &BloodComponent::WholeBlood,
+            &250,
+            &expiration,
+            &Some(symbol_short!("donor1")),
+            &None,
+        );
 
-        env.events().publish(
-            (symbol_short!("org"), symbol_short!("state")),
-            ActorStateChangeEvent {
-                entity_id: org_id.clone(),
-                old_state,
-                new_state: LifecycleState::Active,
-                changed_by: admin.clone(),
-                reason: Some(String::from_str(&env, "verification")),
-                timestamp: env.ledger().timestamp(),
-            },
+        let unit_id_2 = client.register_blood(
+            &bank,
+            &BloodType::APositive,
+            &BloodComponent::WholeBlood,
+            &250,
+            &expiration,
+            &Some(symbol_short!("donor2")),
+            &None,
         );
 
-        env.events().publish(
-            (
-                symbol_short!("org"),
-                symbol_short!("verified"),
-                symbol_short!("v1"),
-            ),
-            (org_id, admin, env.ledger().timestamp()),
+        // Allocate units to hospital
+        client.allocate_blood(&bank, &unit_id_1, &hospital, &None, &None);
+        client.allocate_blood(&bank, &unit_id_2, &hospital, &None, &None);
+
+        // Create request
+        let required_by = current_time + 3600;
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::APositive,
+            &500,
+            &UrgencyLevel::Urgent,
+            &required_by,
+            &String::from_str(&env, "Ward B"),
         );
 
-        Ok(())
-    }
+        // Approve and start progress
+        client.update_request_status(&request_id, &RequestStatus::Approved);
 
-    /// Unverify an organization (admin only).
-    pub fn unverify_organization(
-        env: Env,
-        admin: Address,
-        org_id: Address,
-        reason: String,
-    ) -> Result<(), Error> {
-        admin.require_auth();
+        // Fulfill the request
+        let unit_ids = vec![&env, unit_id_1, unit_id_2];
+        env.mock_all_auths();
+        client.fulfill_request(&bank, &request_id, &unit_ids);
 
-        let stored_admin: Address = env
-            .storage()
-            .instance()
-            .get(&ADMIN)
-            .ok_or(Error::Unauthorized)?;
-        if admin != stored_admin {
-            return Err(Error::Unauthorized);
-        }
+        // Verify units are Delivered
+        let unit1 = client.get_blood_unit(&unit_id_1);
+        assert_eq!(unit1.status, BloodStatus::Delivered);
+        assert!(unit1.delivery_timestamp.is_some());
 
-        let org_key = OrgKey::Org(org_id.clone());
-        let mut organization: Organization = env
-            .storage()
-            .persistent()
-            .get(&org_key)
-            .ok_or(Error::OrganizationNotFound)?;
+        let unit2 = client.get_blood_unit(&unit_id_2);
+        assert_eq!(unit2.status, BloodStatus::Delivered);
+        assert!(unit2.delivery_timestamp.is_some());
+    }
 
-        let old_state = organization.state;
-        organization.verified = false;
-        organization.verified_timestamp = None;
-        organization.state = LifecycleState::Inactive;
-        organization.state_changed_by = Some(admin.clone());
-        organization.state_changed_at = Some(env.ledger().timestamp());
-        organization.state_change_reason = Some(reason.clone());
-        env.storage().persistent().set(&org_key, &organization);
+    #[test]
+    fn test_fulfill_request_rejects_over_delivery_before_mutation() {
+        let env = Env::default();
+        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
 
-        let reason_key = OrgKey::UnverifyReason(org_id.clone());
-        env.storage().persistent().set(&reason_key, &reason);
+        let bank = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
 
-        env.events().publish(
-            (symbol_short!("org"), symbol_short!("state")),
-            ActorStateChangeEvent {
-                entity_id: org_id.clone(),
-                old_state,
-                new_state: LifecycleState::Inactive,
-                changed_by: admin.clone(),
-                reason: Some(reason.clone()),
-                timestamp: env.ledger().timestamp(),
-            },
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (7 * 86400);
+
+        let unit_id_1 = client.register_blood(
+            &bank,
+            &BloodType::APositive,
+            &BloodComponent::WholeBlood,
+            &300,
+            &expiration,
+            &Some(symbol_short!("over1")),
+            &None,
+        );
+        let unit_id_2 = client.register_blood(
+            &bank,
+            &BloodType::APositive,
+            &BloodComponent::WholeBlood,
+            &300,
+            &expiration,
+            &Some(symbol_short!("over2")),
+            &None,
         );
 
-        env.events().publish(
-            (
-                symbol_short!("org"),
-                symbol_short!("unverif"),
-                symbol_short!("v1"),
-            ),
-            (org_id, reason),
+        client.allocate_blood(&bank, &unit_id_1, &hospital, &None, &None);
+        client.allocate_blood(&bank, &unit_id_2, &hospital, &None, &None);
+
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::APositive,
+            &500,
+            &UrgencyLevel::Urgent,
+            &(current_time + 3600),
+            &String::from_str(&env, "Ward O"),
         );
+        client.update_request_status(&request_id, &RequestStatus::Approved);
 
-        Ok(())
-    }
+        let unit_ids = vec![&env, unit_id_1, unit_id_2];
+        let result = client.try_fulfill_request(&bank, &request_id, &unit_ids);
+        assert!(matches!(result, Err(Ok(Error::InvalidQuantity))));
 
-    /// Query an organization by address.
-    pub fn get_organization(env: Env, org_id: Address) -> Result<Organization, Error> {
-        let org_key = OrgKey::Org(org_id);
-        env.storage()
-            .persistent()
-            .get(&org_key)
-            .ok_or(Error::OrganizationNotFound)
+        let unit1 = client.get_blood_unit(&unit_id_1);
+        let unit2 = client.get_blood_unit(&unit_id_2);
+        assert_eq!(unit1.status, BloodStatus::Reserved);
+        assert_eq!(unit2.status, BloodStatus::Reserved);
+        assert!(unit1.delivery_timestamp.is_none());
+        assert!(unit2.delivery_timestamp.is_none());
+
+        let request: BloodRequest = env.as_contract(&contract_id, || {
+            let requests: Map<u64, BloodRequest> =
+                env.storage().persistent().get(&REQUESTS).unwrap();
+            requests.get(request_id).unwrap()
+        });
+        assert_eq!(request.status, RequestStatus::Approved);
+        assert_eq!(request.fulfilled_quantity_ml, 0);
+        assert!(request.fulfillment_timestamp.is_none());
     }
-}
 
-#[contractimpl]
-impl HealthChainContract {
-    // ── Storage Lifecycle / Rent Management ───────────────────────────────────
+    #[test]
+    fn test_fulfill_request_exact_delivery_marks_fulfilled() {
+        let env = Env::default();
+        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
 
-    /// Extend the TTL of all shared registry maps (admin only).
-    ///
-    /// Call this periodically (e.g., monthly) to prevent rent expiry on the
-    /// large persistent maps that are the highest-risk keys for storage fees.
-    pub fn bump_registry_ttl(env: Env) -> Result<(), Error> {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&ADMIN)
-            .ok_or(Error::Unauthorized)?;
-        admin.require_auth();
-        storage_lifecycle::bump_all_registries(&env);
-        Ok(())
-    }
+        let bank = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
 
-    /// Compact the status-history for a terminal blood unit (permissionless).
-    ///
-    /// Replaces the full `Vec<StatusChangeEvent>` with an `ArchivedHistorySummary`
-    /// once the unit has been in a terminal state for at least 30 days, giving
-    /// off-chain indexers time to ingest all events before on-chain data is pruned.
-    ///
-    /// Returns `true` if archival was performed, `false` if not yet eligible.
-    pub fn archive_history(env: Env, unit_id: u64) -> Result<bool, Error> {
-        storage_lifecycle::archive_unit_history(&env, unit_id)
-    }
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (7 * 86400);
 
-    /// Prune finalized custody events for a terminal blood unit (permissionless).
-    ///
-    /// Removes individual `CustodyEvent` entries from the shared `CUSTODY_EVENTS`
-    /// map and stores a compact `ArchivedCustodySummary`. The `UnitTrailPage`
-    /// entries (event_id strings) are preserved for off-chain reconstruction.
-    ///
-    /// Returns `true` if pruning was performed, `false` if not yet eligible.
-    pub fn archive_custody(env: Env, unit_id: u64) -> Result<bool, Error> {
-        storage_lifecycle::archive_custody_events(&env, unit_id)
-    }
+        let unit_id_1 = client.register_blood(
+            &bank,
+            &BloodType::ONegative,
+            &BloodComponent::WholeBlood,
+            &250,
+            &expiration,
+            &Some(symbol_short!("exact1")),
+            &None,
+        );
+        let unit_id_2 = client.register_blood(
+            &bank,
+            &BloodType::ONegative,
+            &BloodComponent::WholeBlood,
+            &250,
+            &expiration,
+            &Some(symbol_short!("exact2")),
+            &None,
+        );
 
-    /// Retrieve the archived history summary for a unit.
-    ///
-    /// Returns `None` if the history has not been archived yet (full history
-    /// is still available via `get_transfer_history`).
-    pub fn get_history_summary(env: Env, unit_id: u64) -> Option<ArchivedHistorySummary> {
-        storage_lifecycle::get_archived_history_summary(&env, unit_id)
-    }
+        client.allocate_blood(&bank, &unit_id_1, &hospital, &None, &None);
+        client.allocate_blood(&bank, &unit_id_2, &hospital, &None, &None);
 
-    /// Retrieve the archived custody summary for a unit.
-    ///
-    /// Returns `None` if custody events have not been archived yet.
-    pub fn get_custody_summary(env: Env, unit_id: u64) -> Option<ArchivedCustodySummary> {
-        storage_lifecycle::get_archived_custody_summary(&env, unit_id)
-    }
-}
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::ONegative,
+            &500,
+            &UrgencyLevel::Urgent,
+            &(current_time + 3600),
+            &String::from_str(&env, "Ward E"),
+        );
+        client.update_request_status(&request_id, &RequestStatus::Approved);
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::{
-        symbol_short, testutils::Address as _, testutils::Events, testutils::Ledger as _, Address,
-        Env, IntoVal, String, Symbol, TryFromVal,
-    };
+        let unit_ids = vec![&env, unit_id_1, unit_id_2];
+        client.fulfill_request(&bank, &request_id, &unit_ids);
 
-    fn setup_contract_with_admin(env: &Env) -> (Address, Address, HealthChainContractClient<'_>) {
-        let admin = Address::generate(env);
-        let contract_id = env.register(HealthChainContract, ());
-        let client = HealthChainContractClient::new(env, &contract_id);
+        let request: BloodRequest = env.as_contract(&contract_id, || {
+            let requests: Map<u64, BloodRequest> =
+                env.storage().persistent().get(&REQUESTS).unwrap();
+            requests.get(request_id).unwrap()
+        });
+        assert_eq!(request.status, RequestStatus::Fulfilled);
+        assert_eq!(request.fulfilled_quantity_ml, 500);
+        assert!(request.fulfillment_timestamp.is_some());
+    }
+
+    #[test]
+    fn test_fulfill_request_partial_delivery_remains_in_progress() {
+        let env = Env::default();
+        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
 
+        let bank = Address::generate(&env);
         env.mock_all_auths();
-        client.initialize(&admin);
+        client.register_blood_bank(&bank);
 
-        (contract_id, admin, client)
-    }
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (7 * 86400);
 
-    fn setup_contract_with_hospital<'a>(
-        env: &'a Env,
-    ) -> (Address, Address, Address, HealthChainContractClient<'a>) {
-        let (contract_id, admin, client) = setup_contract_with_admin(env);
-        let hospital = Address::generate(env);
+        let unit_id = client.register_blood(
+            &bank,
+            &BloodType::BPositive,
+            &BloodComponent::WholeBlood,
+            &250,
+            &expiration,
+            &Some(symbol_short!("part1")),
+            &None,
+        );
 
-        env.mock_all_auths();
-        client.register_hospital(&hospital);
+        client.allocate_blood(&bank, &unit_id, &hospital, &None, &None);
 
-        env.mock_all_auths();
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::BPositive,
+            &500,
+            &UrgencyLevel::Urgent,
+            &(current_time + 3600),
+            &String::from_str(&env, "Ward P"),
+        );
+        client.update_request_status(&request_id, &RequestStatus::Approved);
 
-        (contract_id, admin, hospital, client)
-    }
+        let unit_ids = vec![&env, unit_id];
+        client.fulfill_request(&bank, &request_id, &unit_ids);
 
-    // ─────────────────────────────────────────────────────────────────────────────
-    // Adversarial Access Control Tests (Privilege Escalation Attempts)
-    // ─────────────────────────────────────────────────────────────────────────────
+        let unit = client.get_blood_unit(&unit_id);
+        assert_eq!(unit.status, BloodStatus::Delivered);
+        assert!(unit.delivery_timestamp.is_some());
+
+        let request: BloodRequest = env.as_contract(&contract_id, || {
+            let requests: Map<u64, BloodRequest> =
+                env.storage().persistent().get(&REQUESTS).unwrap();
+            requests.get(request_id).unwrap()
+        });
+        assert_eq!(request.status, RequestStatus::InProgress);
+        assert_eq!(request.fulfilled_quantity_ml, 250);
+        assert!(request.fulfillment_timestamp.is_none());
+    }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1)")]
-    fn test_attack_hospital_spoofs_bank_register_blood_should_fail() {
+    #[should_panic(expected = "Error(Contract, #6)")] // InvalidStatus
+    fn test_fulfill_request_invalid_status_pending() {
         let env = Env::default();
-        let (contract_id, _admin, client) = setup_contract_with_admin(&env);
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
 
-        // Register a hospital (not a bank)
-        let hospital = Address::generate(&env);
         env.mock_all_auths();
-        client.register_hospital(&hospital);
+        let bank = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
 
-        // Hospital attempts to register blood (requires authorized blood bank)
         let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
+        let required_by = current_time + 3600;
 
-        env.mock_all_auths();
-        client.register_blood(
+        let request_id = client.create_request(
             &hospital,
             &BloodType::OPositive,
-            &BloodComponent::WholeBlood,
-            &450,
-            &expiration,
-            &None,
+            &500,
+            &UrgencyLevel::Urgent,
+            &required_by,
+            &String::from_str(&env, "Ward A"),
         );
+
+        let unit_ids = vec![&env, 1u64];
+        env.mock_all_auths();
+        client.fulfill_request(&bank, &request_id, &unit_ids);
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1)")]
-    fn test_attack_unregistered_hospital_create_request_should_fail() {
+    #[should_panic(expected = "Error(Contract, #1)")] // Unauthorized
+    fn test_fulfill_request_unauthorized_non_bank() {
         let env = Env::default();
-        let (contract_id, _admin, client) = setup_contract_with_admin(&env);
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
 
-        // Unregistered hospital tries to create a request
-        let rogue_hospital = Address::generate(&env);
+        env.mock_all_auths();
         let current_time = env.ledger().timestamp();
-        let required_by = current_time + (2 * 86400);
-        let delivery = String::from_str(&env, "Ward 7B - ICU");
+        let required_by = current_time + 3600;
 
-        env.mock_all_auths();
-        client.create_request(
-            &rogue_hospital,
-            &BloodType::APositive,
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::OPositive,
             &500,
-            &UrgencyLevel::High,
+            &UrgencyLevel::Urgent,
             &required_by,
-            &delivery,
+            &String::from_str(&env, "Ward A"),
         );
+
+        client.update_request_status(&request_id, &RequestStatus::Approved);
+
+        // Try to fulfill as non-bank (hospital cannot fulfill)
+        let unit_ids = vec![&env, 1u64];
+        env.mock_all_auths();
+        client.fulfill_request(&hospital, &request_id, &unit_ids);
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1)")]
-    fn test_attack_unregistered_bank_allocates_blood_should_fail() {
+    #[should_panic(expected = "Error(Contract, #28)")] // ArithmeticError
+    fn test_fulfill_request_fails_on_delivered_quantity_overflow() {
         let env = Env::default();
-        let (contract_id, _admin, client) = setup_contract_with_admin(&env);
+        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
+
+        let bank = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
 
-        // Create a unit via legacy add_blood_unit (no bank auth required)
         let current_time = env.ledger().timestamp();
-        let expiration = current_time + (10 * 86400);
-        let unit_id = client.add_blood_unit(
-            &BloodType::ONegative,
-            &300,
+        let expiration = current_time + (7 * 86400);
+
+        let unit_id_1 = client.register_blood(
+            &bank,
+            &BloodType::BPositive,
+            &BloodComponent::WholeBlood,
+            &250,
+            &expiration,
+            &Some(symbol_short!("d1")),
+            &None,
+        );
+        let unit_id_2 = client.register_blood(
+            &bank,
+            &BloodType::BPositive,
+            &BloodComponent::WholeBlood,
+            &250,
             &expiration,
-            &symbol_short!("DONOR"),
-            &symbol_short!("BANK"),
+            &Some(symbol_short!("d2")),
+            &None,
         );
 
-        // Register a hospital to avoid UnauthorizedHospital being triggered later
-        let hospital = Address::generate(&env);
+        // Reserve units for the hospital so fulfill_request can proceed past the
+        // recipient_hospital check and reach the arithmetic overflow guard.
+        client.allocate_blood(&bank, &unit_id_1, &hospital, &None, &None);
+        client.allocate_blood(&bank, &unit_id_2, &hospital, &None, &None);
+
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::BPositive,
+            &500,
+            &UrgencyLevel::Urgent,
+            &(current_time + 3600),
+            &String::from_str(&env, "Ward F1"),
+        );
+
+        let unit_ids = vec![&env, unit_id_1, unit_id_2];
         env.mock_all_auths();
-        client.register_hospital(&hospital);
+        client.update_request_status(&request_id, &RequestStatus::Approved);
+
+        env.as_contract(&contract_id, || {
+            let mut unit_1 = load_unit(&env, unit_id_1).unwrap();
+            unit_1.quantity = u32::MAX;
+            save_unit(&env, &unit_1);
+
+            let mut unit_2 = load_unit(&env, unit_id_2).unwrap();
+            unit_2.quantity = 1;
+            save_unit(&env, &unit_2);
+        });
 
-        // Unregistered bank attempts to allocate
-        let rogue_bank = Address::generate(&env);
         env.mock_all_auths();
-        client.allocate_blood(&rogue_bank, &unit_id, &hospital);
+        client.fulfill_request(&bank, &request_id, &unit_ids);
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #8)")]
-    fn test_attack_expired_unit_allocated_should_fail() {
+    fn test_status_transition_pending_to_rejected() {
         let env = Env::default();
-        let (_contract_id, _admin, client) = setup_contract_with_admin(&env);
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
 
-        // Register an authorized bank and hospital
-        let bank = Address::generate(&env);
-        let hospital = Address::generate(&env);
-        env.mock_all_auths();
-        client.register_blood_bank(&bank);
         env.mock_all_auths();
-        client.register_hospital(&hospital);
+        let current_time = env.ledger().timestamp();
+        let required_by = current_time + 3600;
 
-        // Create a unit that will expire shortly
-        let now = env.ledger().timestamp();
-        let expiration = now + 100;
-        let unit_id = client.add_blood_unit(
-            &BloodType::BPositive,
-            &250,
-            &expiration,
-            &symbol_short!("DNR"),
-            &symbol_short!("BANK"),
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::OPositive,
+            &500,
+            &UrgencyLevel::Low,
+            &required_by,
+            &String::from_str(&env, "Ward A"),
         );
 
-        // Advance time past expiration and attempt allocation
-        env.ledger().set_timestamp(expiration + 1);
         env.mock_all_auths();
-        client.allocate_blood(&bank, &unit_id, &hospital);
+        client.update_request_status(&request_id, &RequestStatus::Rejected);
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1)")]
-    fn test_attack_revoked_bank_immediate_reuse_should_fail() {
+    fn test_status_transition_approved_to_cancelled() {
         let env = Env::default();
-        let (contract_id, _admin, client) = setup_contract_with_admin(&env);
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
 
-        // Register bank and create unit
-        let bank = Address::generate(&env);
         env.mock_all_auths();
-        client.register_blood_bank(&bank);
-        let now = env.ledger().timestamp();
-        let expiration = now + (5 * 86400);
-        let unit_id = client.add_blood_unit(
-            &BloodType::ABNegative,
-            &200,
-            &expiration,
-            &symbol_short!("DNR2"),
-            &symbol_short!("BANK"),
-        );
-
-        // "Revoke" by clearing BANKS map directly
-        env.as_contract(&contract_id, || {
-            let empty_banks = Map::<Address, LifecycleState>::new(&env);
-            env.storage().persistent().set(&BLOOD_BANKS, &empty_banks);
-        });
+        let current_time = env.ledger().timestamp();
+        let required_by = current_time + 3600;
 
-        // Attempt to register blood using revoked bank (should fail Unauthorized)
-        env.mock_all_auths();
-        client.register_blood(
-            &bank,
+        let request_id = client.create_request(
+            &hospital,
             &BloodType::OPositive,
-            &BloodComponent::WholeBlood,
-            &100,
-            &expiration,
-            &None,
+            &500,
+            &UrgencyLevel::Urgent,
+            &required_by,
+            &String::from_str(&env, "Ward A"),
         );
 
-        // Attempt to allocate using revoked bank (should also fail Unauthorized)
         env.mock_all_auths();
-        let hospital = Address::generate(&env);
+        client.update_request_status(&request_id, &RequestStatus::Approved);
+
         env.mock_all_auths();
-        client.register_hospital(&hospital);
-        client.allocate_blood(&bank, &unit_id, &hospital);
+        client.cancel_request(&request_id, &String::from_str(&env, "Changed requirements"));
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #7)")]
-    fn test_attack_wrong_hospital_confirm_delivery_should_fail() {
+    fn test_status_transition_in_progress_to_fulfilled() {
         let env = Env::default();
-        let (_contract_id, _admin, client) = setup_contract_with_admin(&env);
+        let (_, _admin, hospital, client) = setup_contract_with_hospital(&env);
 
-        // Register bank and two hospitals
         let bank = Address::generate(&env);
-        let hospital_a = Address::generate(&env);
-        let hospital_b = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
-        env.mock_all_auths();
-        client.register_hospital(&hospital_a);
-        env.mock_all_auths();
-        client.register_hospital(&hospital_b);
 
-        // Create unit and allocate to hospital A
-        let now = env.ledger().timestamp();
-        let expiration = now + (7 * 86400);
-        let unit_id = client.add_blood_unit(
-            &BloodType::APositive,
-            &300,
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (7 * 86400);
+
+        let unit_id = client.register_blood(
+            &bank,
+            &BloodType::BPositive,
+            &BloodComponent::WholeBlood,
+            &500,
             &expiration,
-            &symbol_short!("DNR3"),
-            &symbol_short!("BANK"),
+            &Some(symbol_short!("donor1")),
+            &None,
         );
-        env.mock_all_auths();
-        client.allocate_blood(&bank, &unit_id, &hospital_a);
 
-        // Hospital B attempts to confirm delivery for unit allocated to A
         env.mock_all_auths();
-        client.confirm_delivery(&hospital_b, &unit_id);
-    }
-
-    #[test]
-    #[should_panic(expected = "Error(Contract, #1)")]
-    fn test_attack_withdraw_blood_by_unauthorized_address_should_fail() {
-        let env = Env::default();
-        let (_contract_id, _admin, client) = setup_contract_with_admin(&env);
+        client.allocate_blood(&bank, &unit_id, &hospital, &None, &None);
 
-        // Create a unit
-        let now = env.ledger().timestamp();
-        let expiration = now + (10 * 86400);
-        let unit_id = client.add_blood_unit(
-            &BloodType::ONegative,
-            &400,
-            &expiration,
-            &symbol_short!("DNR4"),
-            &symbol_short!("BANK"),
+        let required_by = current_time + 3600;
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::BPositive,
+            &500,
+            &UrgencyLevel::Critical,
+            &required_by,
+            &String::from_str(&env, "ER"),
         );
 
-        // Rogue address (neither bank nor hospital) attempts to withdraw
-        let attacker = Address::generate(&env);
         env.mock_all_auths();
-        client.withdraw_blood(&attacker, &unit_id, &WithdrawalReason::Other);
-    }
-    #[test]
-    fn test_initialize() {
-        let env = Env::default();
-        let admin = Address::generate(&env);
-        let contract_id = env.register(HealthChainContract, ());
-        let client = HealthChainContractClient::new(&env, &contract_id);
+        client.update_request_status(&request_id, &RequestStatus::Approved);
+        env.mock_all_auths();
+        client.update_request_status(&request_id, &RequestStatus::InProgress);
 
+        let unit_ids = vec![&env, unit_id];
         env.mock_all_auths();
-        let result = client.initialize(&admin);
-        assert_eq!(result, symbol_short!("init"));
+        client.fulfill_request(&bank, &request_id, &unit_ids);
+
+        let unit = client.get_blood_unit(&unit_id);
+        assert_eq!(unit.status, BloodStatus::Delivered);
     }
 
     #[test]
-    fn test_register_blood_bank() {
+    fn test_cancel_request_emits_event_with_reason() {
         let env = Env::default();
-        let (_, _, client) = setup_contract_with_admin(&env);
-        let bank = Address::generate(&env);
+        let (_contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
 
         env.mock_all_auths();
-        client.register_blood_bank(&bank);
+        let current_time = env.ledger().timestamp();
+        let required_by = current_time + 3600;
 
-        // Verify bank is registered
-        assert_eq!(client.is_blood_bank(&bank), true);
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::OPositive,
+            &500,
+            &UrgencyLevel::Urgent,
+            &required_by,
+            &String::from_str(&env, "Ward A"),
+        );
+
+        let cancel_reason = String::from_str(&env, "Patient condition improved");
+        env.mock_all_auths();
+        client.cancel_request(&request_id, &cancel_reason);
     }
 
     #[test]
-    fn test_register_blood_success() {
+    fn test_cancel_request_emits_structured_cancellation_event() {
         let env = Env::default();
-        let (_, _, client) = setup_contract_with_admin(&env);
-        let bank = Address::generate(&env);
+        let (_contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
 
+        // Register blood bank
+        let bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
+        // Create and allocate blood units
         let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400); // 7 days from now
+        let expiration = current_time + (7 * 86400);
 
-        let result = client.register_blood(
+        let unit_id1 = client.register_blood(
             &bank,
             &BloodType::OPositive,
             &BloodComponent::WholeBlood,
             &450,
             &expiration,
             &Some(symbol_short!("donor1")),
+            &None,
         );
 
-        assert_eq!(result, 1);
-    }
+        let unit_id2 = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &expiration,
+            &Some(symbol_short!("donor2")),
+            &None,
+        );
 
-    #[test]
-    #[should_panic(expected = "Error(Contract, #1)")]
-    fn test_register_blood_unauthorized_bank() {
-        let env = Env::default();
-        let (_, _, client) = setup_contract_with_admin(&env);
-        let unauthorized_bank = Address::generate(&env);
+        env.mock_all_auths();
+        client.allocate_blood(&bank, &unit_id1, &hospital, &None, &None);
+
+        env.mock_all_auths();
+        client.allocate_blood(&bank, &unit_id2, &hospital, &None, &None);
+
+        // Create request that reserves the units
+        let required_by = current_time + 3600;
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::OPositive,
+            &900,
+            &UrgencyLevel::Urgent,
+            &required_by,
+            &String::from_str(&env, "Ward A"),
+        );
+
+        // Approve request to move to next state
+        env.mock_all_auths();
+        client.update_request_status(&request_id, &RequestStatus::Approved);
 
+        // Cancel request and verify event is emitted with released units
+        let cancel_reason = String::from_str(&env, "Patient condition improved");
         env.mock_all_auths();
+        client.cancel_request(&request_id, &cancel_reason);
 
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
-
-        client.register_blood(
-            &unauthorized_bank,
-            &BloodType::OPositive,
-            &BloodComponent::WholeBlood,
-            &450,
-            &expiration,
-            &Some(symbol_short!("donor1")),
-        );
+        // ACCEPTANCE: Event emitted with explicit unit release information
+        // Backend consumers can rebuild inventory state from the event:
+        // - Released units returned to Available status
+        // - No need for separate polling queries
+        // - Full audit context in single event
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #2)")]
-    fn test_register_blood_invalid_quantity_too_low() {
+    #[should_panic(expected = "Error(Contract, #7)")] // UnitNotFound (used for request not found)
+    fn test_update_status_nonexistent_request() {
         let env = Env::default();
-        let (_, _, client) = setup_contract_with_admin(&env);
-        let bank = Address::generate(&env);
+        let (_, _, _, client) = setup_contract_with_hospital(&env);
 
         env.mock_all_auths();
-        client.register_blood_bank(&bank);
-
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
 
-        client.register_blood(
-            &bank,
-            &BloodType::OPositive,
-            &BloodComponent::WholeBlood,
-            &25, // Below minimum
-            &expiration,
-            &Some(symbol_short!("donor1")),
-        );
+        // Try to update status of non-existent request
+        client.update_request_status(&999u64, &RequestStatus::Approved);
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #2)")]
-    fn test_register_blood_invalid_quantity_too_high() {
+    #[should_panic(expected = "Error(Contract, #7)")] // UnitNotFound
+    fn test_cancel_nonexistent_request() {
         let env = Env::default();
-        let (_, _, client) = setup_contract_with_admin(&env);
-        let bank = Address::generate(&env);
+        let (_, _, _, client) = setup_contract_with_hospital(&env);
 
         env.mock_all_auths();
-        client.register_blood_bank(&bank);
 
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
-
-        client.register_blood(
-            &bank,
-            &BloodType::OPositive,
-            &BloodComponent::WholeBlood,
-            &600, // Above maximum
-            &expiration,
-            &Some(symbol_short!("donor1")),
-        );
+        // Try to cancel non-existent request
+        client.cancel_request(&999u64, &String::from_str(&env, "Test"));
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #3)")]
-    fn test_register_blood_expired_date() {
+    #[should_panic(expected = "Error(Contract, #7)")] // UnitNotFound
+    fn test_fulfill_nonexistent_request() {
         let env = Env::default();
-        let (_, _, client) = setup_contract_with_admin(&env);
-        let bank = Address::generate(&env);
+        let (_, _, _, client) = setup_contract_with_hospital(&env);
 
+        let bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
-        let expiration = 0; // Already expired
-
-        client.register_blood(
-            &bank,
-            &BloodType::OPositive,
-            &BloodComponent::WholeBlood,
-            &450,
-            &expiration,
-            &Some(symbol_short!("donor1")),
-        );
+        let unit_ids = vec![&env, 1u64];
+        env.mock_all_auths();
+        client.fulfill_request(&bank, &999u64, &unit_ids);
     }
 
+    // ======================================================
+    // Custodian Check Tests (#101)
+    // ======================================================
+
     #[test]
-    #[should_panic(expected = "Error(Contract, #3)")]
-    fn test_register_blood_expiration_too_far() {
+    fn test_initiate_transfer_by_current_custodian_succeeds() {
         let env = Env::default();
-        let (_, _, client) = setup_contract_with_admin(&env);
-        let bank = Address::generate(&env);
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
 
+        let bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
         let current_time = env.ledger().timestamp();
-        let expiration = current_time + (50 * 86400); // 50 days (exceeds 42 day limit)
-
-        client.register_blood(
+        let expiration = current_time + (7 * 86400);
+        let unit_id = client.register_blood(
             &bank,
             &BloodType::OPositive,
             &BloodComponent::WholeBlood,
             &450,
             &expiration,
-            &Some(symbol_short!("donor1")),
+            &None,
+            &None,
         );
+        client.allocate_blood(&bank, &unit_id, &hospital, &None, &None);
+
+        // Current custodian (bank) can initiate transfer
+        let event_id = client.initiate_transfer(&bank, &unit_id, &None, &None, &None);
+        assert!(!event_id.is_empty());
     }
 
     #[test]
-    fn test_register_blood_without_donor_id() {
+    #[should_panic(expected = "Error(Contract, #29)")] // NotCurrentCustodian
+    fn test_initiate_transfer_by_non_custodian_authorized_bank_fails() {
         let env = Env::default();
-        let (_, _, client) = setup_contract_with_admin(&env);
-        let bank = Address::generate(&env);
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
 
+        let bank_a = Address::generate(&env);
+        let bank_b = Address::generate(&env);
         env.mock_all_auths();
-        client.register_blood_bank(&bank);
+        client.register_blood_bank(&bank_a);
+        client.register_blood_bank(&bank_b);
 
         let current_time = env.ledger().timestamp();
         let expiration = current_time + (7 * 86400);
-
-        let result = client.register_blood(
-            &bank,
-            &BloodType::ABNegative,
+        // bank_a registers and allocates the unit — bank_a is the custodian
+        let unit_id = client.register_blood(
+            &bank_a,
+            &BloodType::OPositive,
             &BloodComponent::WholeBlood,
-            &350,
+            &450,
             &expiration,
-            &None, // Anonymous donor
+            &None,
+            &None,
         );
+        client.allocate_blood(&bank_a, &unit_id, &hospital, &None, &None);
 
-        assert_eq!(result, 1);
+        // bank_b is authorized but is NOT the custodian — must fail
+        client.initiate_transfer(&bank_b, &unit_id, &None, &None, &None);
     }
 
     #[test]
-    fn test_register_multiple_blood_units() {
+    #[should_panic(expected = "Error(Contract, #1)")] // Unauthorized
+    fn test_initiate_transfer_by_unregistered_address_fails() {
         let env = Env::default();
-        let (_, _, client) = setup_contract_with_admin(&env);
-        let bank = Address::generate(&env);
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
 
+        let bank = Address::generate(&env);
+        let rogue = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
         let current_time = env.ledger().timestamp();
         let expiration = current_time + (7 * 86400);
-
-        // Register first unit
-        let id1 = client.register_blood(
+        let unit_id = client.register_blood(
             &bank,
             &BloodType::OPositive,
             &BloodComponent::WholeBlood,
             &450,
             &expiration,
-            &Some(symbol_short!("donor1")),
-        );
-
-        // Register second unit
-        let id2 = client.register_blood(
-            &bank,
-            &BloodType::APositive,
-            &BloodComponent::WholeBlood,
-            &400,
-            &expiration,
-            &Some(symbol_short!("donor2")),
+            &None,
+            &None,
         );
+        client.allocate_blood(&bank, &unit_id, &hospital, &None, &None);
 
-        assert_eq!(id1, 1);
-        assert_eq!(id2, 2);
+        // Completely unregistered address — must fail with Unauthorized, not NotCurrentCustodian
+        client.initiate_transfer(&rogue, &unit_id, &None, &None, &None);
     }
 
-    #[test]
-    fn test_register_blood_all_blood_types() {
-        let env = Env::default();
-        let (_, _, client) = setup_contract_with_admin(&env);
-        let bank = Address::generate(&env);
+    // ======================================================
+    // Transfer Expiry Boundary Tests (#105)
+    // ======================================================
 
-        env.mock_all_auths();
-        client.register_blood_bank(&bank);
+    fn setup_in_transit_unit(
+        env: &Env,
+        client: &HealthChainContractClient<'_>,
+        bank: &Address,
+        hospital: &Address,
+        initiated_at: u64,
+    ) -> (u64, String) {
+        // Ensure deterministic time for registration + allocation.
+        env.ledger().set_timestamp(initiated_at.saturating_sub(10));
 
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
+        let expiration = initiated_at + (7 * 86400);
+        let unit_id = client.register_blood(
+            bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &expiration,
+            &Some(symbol_short!("donor")),
+            &None,
+        );
 
-        let blood_types = vec![
-            &env,
-            BloodType::APositive,
-            BloodType::ANegative,
-            BloodType::BPositive,
-            BloodType::BNegative,
-            BloodType::ABPositive,
-            BloodType::ABNegative,
-            BloodType::OPositive,
-            BloodType::ONegative,
-        ];
+        client.allocate_blood(bank, &unit_id, hospital, &None, &None);
 
-        for (i, blood_type) in blood_types.iter().enumerate() {
-            let result = client.register_blood(
-                &bank,
-                &blood_type,
-                &BloodComponent::WholeBlood,
-                &450,
-                &expiration,
-                &Some(symbol_short!("donor")),
-            );
-            assert_eq!(result, (i as u64) + 1);
-        }
+        // Initiate transfer at exact initiated_at.
+        env.ledger().set_timestamp(initiated_at);
+        let event_id = client.initiate_transfer(bank, &unit_id, &None, &None, &None);
+
+        (unit_id, event_id)
     }
 
     #[test]
-    fn test_register_blood_minimum_valid_quantity() {
+    fn test_transfer_cancellable_at_exactly_expiry_boundary_succeeds() {
         let env = Env::default();
-        let (_, _, client) = setup_contract_with_admin(&env);
-        let bank = Address::generate(&env);
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
 
+        // Register a blood bank
+        let bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
+        let initiated_at = 1_000_000u64;
+        let (unit_id, event_id) =
+            setup_in_transit_unit(&env, &client, &bank, &hospital, initiated_at);
 
-        let result = client.register_blood(
-            &bank,
-            &BloodType::OPositive,
-            &BloodComponent::WholeBlood,
-            &50, // Minimum valid quantity
-            &expiration,
-            &Some(symbol_short!("donor1")),
-        );
+        // At the transfer deadline => cancellable
+        env.ledger()
+            .set_timestamp(initiated_at + DEFAULT_TRANSFER_DEADLINE_SECONDS);
+        client.cancel_transfer(&bank, &event_id, &None);
 
-        assert_eq!(result, 1);
+        let unit = client.get_blood_unit(&unit_id);
+        assert_eq!(unit.status, BloodStatus::Reserved);
+        assert_eq!(unit.transfer_timestamp, None);
     }
 
     #[test]
-    fn test_register_blood_maximum_valid_quantity() {
+    fn test_custody_event_counts_track_each_transition() {
         let env = Env::default();
-        let (_, _, client) = setup_contract_with_admin(&env);
-        let bank = Address::generate(&env);
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
 
+        let bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
-
-        let result = client.register_blood(
-            &bank,
-            &BloodType::OPositive,
-            &BloodComponent::WholeBlood,
-            &500, // Maximum valid quantity
-            &expiration,
-            &Some(symbol_short!("donor1")),
-        );
+        assert_eq!(client.get_custody_event_counts(), (0, 0, 0));
 
-        assert_eq!(result, 1);
+        let (_, first_event) = setup_in_transit_unit(&env, &client, &bank, &hospital, 1_000_000);
+        assert_eq!(client.get_custody_event_counts(), (1, 0, 0));
+
+        let (_, second_event) = setup_in_transit_unit(&env, &client, &bank, &hospital, 1_000_100);
+        assert_eq!(client.get_custody_event_counts(), (2, 0, 0));
+
+        // Confirming moves one event from pending to confirmed
+        env.ledger().set_timestamp(1_000_200);
+        client.confirm_transfer(&hospital, &first_event, &None, &None, &None);
+        assert_eq!(client.get_custody_event_counts(), (1, 1, 0));
+
+        // Cancelling after expiry moves the other from pending to cancelled
+        env.ledger()
+            .set_timestamp(1_000_100 + DEFAULT_TRANSFER_DEADLINE_SECONDS);
+        client.cancel_transfer(&bank, &second_event, &None);
+        assert_eq!(client.get_custody_event_counts(), (0, 1, 1));
     }
 
     #[test]
-    fn test_register_blood_minimum_shelf_life() {
+    fn test_custody_event_counts_unchanged_by_failed_transition() {
         let env = Env::default();
-        let (_, _, client) = setup_contract_with_admin(&env);
-        let bank = Address::generate(&env);
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
 
+        let bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (1 * 86400) + 1; // Just over 1 day
+        let initiated_at = 1_000_000u64;
+        let (_, event_id) = setup_in_transit_unit(&env, &client, &bank, &hospital, initiated_at);
 
-        let result = client.register_blood(
-            &bank,
-            &BloodType::OPositive,
-            &BloodComponent::WholeBlood,
-            &450,
-            &expiration,
-            &Some(symbol_short!("donor1")),
-        );
+        // Cancellation inside the confirmation window is rejected
+        let result = client.try_cancel_transfer(&bank, &event_id, &None);
+        assert_eq!(result, Err(Ok(Error::TransferNotExpired)));
+        assert_eq!(client.get_custody_event_counts(), (1, 0, 0));
 
-        assert_eq!(result, 1);
+        // Confirmation after the window is rejected too
+        env.ledger()
+            .set_timestamp(initiated_at + DEFAULT_TRANSFER_DEADLINE_SECONDS);
+        let result = client.try_confirm_transfer(&hospital, &event_id, &None, &None, &None);
+        assert_eq!(result, Err(Ok(Error::TransferExpired)));
+        assert_eq!(client.get_custody_event_counts(), (1, 0, 0));
     }
 
     #[test]
-    fn test_register_blood_maximum_shelf_life() {
+    #[should_panic(expected = "Error(Contract, #17)")]
+    fn test_transfer_not_cancellable_one_second_before_expiry_fails() {
         let env = Env::default();
-        let (_, _, client) = setup_contract_with_admin(&env);
-        let bank = Address::generate(&env);
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
 
+        let bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (42 * 86400); // Exactly 42 days
-
-        let result = client.register_blood(
-            &bank,
-            &BloodType::OPositive,
-            &BloodComponent::WholeBlood,
-            &450,
-            &expiration,
-            &Some(symbol_short!("donor1")),
-        );
+        let initiated_at = 1_000_000u64;
+        let (_, event_id) = setup_in_transit_unit(&env, &client, &bank, &hospital, initiated_at);
 
-        assert_eq!(result, 1);
+        // One second before the deadline => NOT cancellable
+        env.ledger()
+            .set_timestamp(initiated_at + DEFAULT_TRANSFER_DEADLINE_SECONDS - 1);
+        client.cancel_transfer(&bank, &event_id, &None);
     }
 
     #[test]
-    fn test_multiple_blood_banks() {
+    fn test_transfer_cancellable_one_second_after_expiry_succeeds() {
         let env = Env::default();
-        let (_, _, client) = setup_contract_with_admin(&env);
-        let bank1 = Address::generate(&env);
-        let bank2 = Address::generate(&env);
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
 
+        let bank = Address::generate(&env);
         env.mock_all_auths();
-        client.register_blood_bank(&bank1);
-        client.register_blood_bank(&bank2);
-
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
+        client.register_blood_bank(&bank);
 
-        // Both banks can register blood
-        let id1 = client.register_blood(
-            &bank1,
-            &BloodType::OPositive,
-            &BloodComponent::WholeBlood,
-            &450,
-            &expiration,
-            &Some(symbol_short!("donor1")),
-        );
+        let initiated_at = 1_000_000u64;
+        let (unit_id, event_id) =
+            setup_in_transit_unit(&env, &client, &bank, &hospital, initiated_at);
 
-        let id2 = client.register_blood(
-            &bank2,
-            &BloodType::APositive,
-            &BloodComponent::WholeBlood,
-            &400,
-            &expiration,
-            &Some(symbol_short!("donor2")),
-        );
+        // One second past the deadline => cancellable
+        env.ledger()
+            .set_timestamp(initiated_at + DEFAULT_TRANSFER_DEADLINE_SECONDS + 1);
+        client.cancel_transfer(&bank, &event_id, &None);
 
-        assert_eq!(id1, 1);
-        assert_eq!(id2, 2);
+        let unit = client.get_blood_unit(&unit_id);
+        assert_eq!(unit.status, BloodStatus::Reserved);
     }
 
     #[test]
-    fn test_store_record() {
+    fn test_pending_transfer_expires_after_deadline_by_anyone() {
         let env = Env::default();
-        let contract_id = env.register(HealthChainContract, ());
-        let client = HealthChainContractClient::new(&env, &contract_id);
+        let (_, admin, hospital, client) = setup_contract_with_hospital(&env);
 
-        let patient = symbol_short!("patient1");
-        let hash = symbol_short!("hash123");
+        let bank = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
 
-        let result = client.store_record(&patient, &hash);
-        assert_eq!(result.len(), 2);
-    }
+        assert_eq!(
+            client.get_transfer_deadline(),
+            DEFAULT_TRANSFER_DEADLINE_SECONDS
+        );
+        let result = client.try_set_transfer_deadline(&admin, &(TRANSFER_EXPIRY_SECONDS - 1));
+        assert_eq!(result, Err(Ok(Error::InvalidTransferDeadline)));
+        let result = client.try_set_transfer_deadline(&admin, &(MAX_TRANSFER_DEADLINE_SECONDS + 1));
+        assert_eq!(result, Err(Ok(Error::InvalidTransferDeadline)));
+        let result = client.try_set_transfer_deadline(&bank, &7_200);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        client.set_transfer_deadline(&admin, &7_200);
 
-    #[test]
-    fn test_verify_access() {
-        let env = Env::default();
-        let contract_id = env.register(HealthChainContract, ());
-        let client = HealthChainContractClient::new(&env, &contract_id);
+        let initiated_at = 1_000_000u64;
+        let (unit_id, event_id) =
+            setup_in_transit_unit(&env, &client, &bank, &hospital, initiated_at);
+        let event = client.get_custody_event(&event_id);
+        assert_eq!(event.transfer_deadline, initiated_at + 7_200);
 
-        let patient = symbol_short!("patient1");
-        let provider = symbol_short!("doctor1");
+        env.ledger().set_timestamp(initiated_at + 7_199);
+        let result = client.try_expire_pending_transfer(&event_id);
+        assert_eq!(result, Err(Ok(Error::TransferNotExpired)));
 
-        let has_access = client.verify_access(&patient, &provider);
-        assert_eq!(has_access, true);
-    }
+        env.ledger().set_timestamp(initiated_at + 7_200);
+        client.expire_pending_transfer(&event_id);
 
-    #[test]
-    fn test_add_blood_unit() {
-        let env = Env::default();
-        let contract_id = env.register(HealthChainContract, ());
-        let client = HealthChainContractClient::new(&env, &contract_id);
+        let unit = client.get_blood_unit(&unit_id);
+        assert_eq!(unit.status, BloodStatus::Reserved);
+        assert_eq!(unit.recipient_hospital, Some(hospital.clone()));
+        let event = client.get_custody_event(&event_id);
+        assert_eq!(event.status, CustodyStatus::Recovered);
 
-        let id = client.add_blood_unit(
-            &BloodType::OPositive,
-            &100,
-            &(env.ledger().timestamp() + 86400 * 30), // 30 days from now
-            &symbol_short!("donor1"),
-            &symbol_short!("loc1"),
-        );
+        let result = client.try_expire_pending_transfer(&event_id);
+        assert_eq!(result, Err(Ok(Error::InvalidStatus)));
+        let result = client.try_confirm_transfer(&hospital, &event_id, &None, &None, &None);
+        assert_eq!(result, Err(Ok(Error::InvalidStatus)));
 
-        assert_eq!(id, 1);
+        // The unit can be sent again once recovered
+        client.initiate_transfer(&bank, &unit_id, &None, &None, &None);
     }
 
     #[test]
-    fn test_query_by_blood_type_basic() {
+    fn test_expired_transfer_of_expired_unit_marks_unit_expired() {
         let env = Env::default();
-        env.mock_all_auths();
-        let contract_id = env.register(HealthChainContract, ());
-        let client = HealthChainContractClient::new(&env, &contract_id);
-
-        let current_time = env.ledger().timestamp();
+        let (_, admin, hospital, client) = setup_contract_with_hospital(&env);
 
-        // Add multiple blood units
-        client.add_blood_unit(
-            &BloodType::OPositive,
-            &100,
-            &(current_time + 86400 * 30),
-            &symbol_short!("donor1"),
-            &symbol_short!("loc1"),
-        );
+        let bank = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+        client.set_transfer_deadline(&admin, &MAX_TRANSFER_DEADLINE_SECONDS);
 
-        client.add_blood_unit(
+        let initiated_at = 1_000_000u64;
+        env.ledger().set_timestamp(initiated_at - 10);
+        let unit_id = client.register_blood(
+            &bank,
             &BloodType::OPositive,
-            &50,
-            &(current_time + 86400 * 15),
-            &symbol_short!("donor2"),
-            &symbol_short!("loc1"),
+            &BloodComponent::WholeBlood,
+            &450,
+            &(initiated_at - 10 + 86_400),
+            &Some(symbol_short!("donor")),
+            &None,
         );
+        client.allocate_blood(&bank, &unit_id, &hospital, &None, &None);
+        env.ledger().set_timestamp(initiated_at);
+        let event_id = client.initiate_transfer(&bank, &unit_id, &None, &None, &None);
 
-        client.add_blood_unit(
-            &BloodType::APositive,
-            &75,
-            &(current_time + 86400 * 20),
-            &symbol_short!("donor3"),
-            &symbol_short!("loc2"),
-        );
+        // The unit expires before the transfer deadline passes
+        env.ledger()
+            .set_timestamp(initiated_at + MAX_TRANSFER_DEADLINE_SECONDS);
+        client.expire_pending_transfer(&event_id);
 
-        // Query O+ blood
-        let results = client.query_by_blood_type(&BloodType::OPositive, &0, &10);
-        assert_eq!(results.len(), 2);
+        let unit = client.get_blood_unit(&unit_id);
+        assert_eq!(unit.status, BloodStatus::Expired);
+        let event = client.get_custody_event(&event_id);
+        assert_eq!(event.status, CustodyStatus::Recovered);
+        let result = client.try_initiate_transfer(&bank, &unit_id, &None, &None, &None);
+        assert!(result.is_err());
     }
 
     #[test]
-    fn test_query_excludes_expired() {
+    fn test_custody_event_carries_handoff_references() {
+        use soroban_sdk::BytesN;
+
         let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
         env.mock_all_auths();
-        let contract_id = env.register(HealthChainContract, ());
-        let client = HealthChainContractClient::new(&env, &contract_id);
-
-        let current_time = env.ledger().timestamp();
+        client.register_blood_bank(&bank);
 
-        // Add expired unit (expiration = 0, which is before current_time)
-        client.add_blood_unit(
+        env.ledger().set_timestamp(1_000_000);
+        let unit_id = client.register_blood(
+            &bank,
             &BloodType::OPositive,
-            &100,
-            &0, // Already expired
-            &symbol_short!("donor1"),
-            &symbol_short!("loc1"),
+            &BloodComponent::WholeBlood,
+            &450,
+            &(1_000_000 + 7 * 86400),
+            &None,
+            &None,
         );
+        client.allocate_blood(&bank, &unit_id, &hospital, &None, &None);
 
-        // Add valid unit
-        client.add_blood_unit(
-            &BloodType::OPositive,
-            &50,
-            &(current_time + 86400 * 15),
-            &symbol_short!("donor2"),
-            &symbol_short!("loc1"),
+        let photo = BytesN::from_array(&env, &[3u8; 32]);
+        let event_id = client.initiate_transfer(
+            &bank,
+            &unit_id,
+            &Some(photo.clone()),
+            &Some(symbol_short!("cooler7")),
+            &None,
         );
+        let event = client.get_custody_event(&event_id);
+        assert_eq!(event.doc_hash, Some(photo));
+        assert_eq!(event.note, Some(symbol_short!("cooler7")));
 
-        let results = client.query_by_blood_type(&BloodType::OPositive, &0, &10);
-        assert_eq!(results.len(), 1);
-        assert_eq!(results.get(0).unwrap().quantity, 50);
+        // The receiver's waybill replaces the document; the note is kept
+        let waybill = BytesN::from_array(&env, &[4u8; 32]);
+        client.confirm_transfer(&hospital, &event_id, &Some(waybill.clone()), &None, &None);
+        let event = client.get_custody_event(&event_id);
+        assert_eq!(event.doc_hash, Some(waybill));
+        assert_eq!(event.note, Some(symbol_short!("cooler7")));
     }
 
     #[test]
-    fn test_query_min_quantity_filter() {
+    fn test_custody_event_records_location_hashes() {
+        use soroban_sdk::BytesN;
+
         let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
         env.mock_all_auths();
-        let contract_id = env.register(HealthChainContract, ());
-        let client = HealthChainContractClient::new(&env, &contract_id);
-
-        let current_time = env.ledger().timestamp();
+        client.register_blood_bank(&bank);
 
-        client.add_blood_unit(
+        env.ledger().set_timestamp(1_000_000);
+        let unit_id = client.register_blood(
+            &bank,
             &BloodType::OPositive,
-            &100,
-            &(current_time + 86400 * 30),
-            &symbol_short!("donor1"),
-            &symbol_short!("loc1"),
+            &BloodComponent::WholeBlood,
+            &450,
+            &(1_000_000 + 7 * 86400),
+            &None,
+            &None,
         );
+        client.allocate_blood(&bank, &unit_id, &hospital, &None, &None);
 
-        client.add_blood_unit(
-            &BloodType::OPositive,
-            &25,
-            &(current_time + 86400 * 15),
-            &symbol_short!("donor2"),
-            &symbol_short!("loc1"),
-        );
+        let depot = BytesN::from_array(&env, &[5u8; 32]);
+        let event_id =
+            client.initiate_transfer(&bank, &unit_id, &None, &None, &Some(depot.clone()));
+        let event = client.get_custody_event(&event_id);
+        assert_eq!(event.location_hash, Some(depot.clone()));
+        assert_eq!(event.closing_location_hash, None);
 
-        // Query with min_quantity = 50
-        let results = client.query_by_blood_type(&BloodType::OPositive, &50, &10);
-        assert_eq!(results.len(), 1);
-        assert_eq!(results.get(0).unwrap().quantity, 100);
+        // A cancellation records where the unit was called back from
+        let roadside = BytesN::from_array(&env, &[6u8; 32]);
+        env.ledger()
+            .set_timestamp(1_000_000 + DEFAULT_TRANSFER_DEADLINE_SECONDS);
+        client.cancel_transfer(&bank, &event_id, &Some(roadside.clone()));
+        let event = client.get_custody_event(&event_id);
+        assert_eq!(event.location_hash, Some(depot.clone()));
+        assert_eq!(event.closing_location_hash, Some(roadside));
+
+        // The sender's location survives the hospital's on confirmation
+        let ward = BytesN::from_array(&env, &[7u8; 32]);
+        env.ledger().with_mut(|li| li.sequence_number += 1);
+        let event_id =
+            client.initiate_transfer(&bank, &unit_id, &None, &None, &Some(depot.clone()));
+        client.confirm_transfer(&hospital, &event_id, &None, &None, &Some(ward.clone()));
+        let event = client.get_custody_event(&event_id);
+        assert_eq!(event.location_hash, Some(depot));
+        assert_eq!(event.closing_location_hash, Some(ward));
     }
 
     #[test]
-    fn test_query_fifo_sorting() {
+    fn test_rejected_transfer_returns_unit_to_sender() {
         let env = Env::default();
-        env.mock_all_auths();
-        let contract_id = env.register(HealthChainContract, ());
-        let client = HealthChainContractClient::new(&env, &contract_id);
-
-        let current_time = env.ledger().timestamp();
-
-        // Add units with different expiration dates (not in order)
-        client.add_blood_unit(
-            &BloodType::OPositive,
-            &100,
-            &(current_time + 86400 * 30), // Expires last
-            &symbol_short!("donor1"),
-            &symbol_short!("loc1"),
-        );
-
-        client.add_blood_unit(
-            &BloodType::OPositive,
-            &50,
-            &(current_time + 86400 * 10), // Expires first
-            &symbol_short!("donor2"),
-            &symbol_short!("loc1"),
-        );
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
+        let other_hospital = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+        client.register_hospital(&other_hospital);
 
-        client.add_blood_unit(
-            &BloodType::OPositive,
-            &75,
-            &(current_time + 86400 * 20), // Expires middle
-            &symbol_short!("donor3"),
-            &symbol_short!("loc1"),
+        let (unit_id, event_id) = setup_in_transit_unit(&env, &client, &bank, &hospital, 1_000_000);
+
+        let result =
+            client.try_reject_transfer(&other_hospital, &event_id, &RejectionReason::Damaged);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        client.reject_transfer(&hospital, &event_id, &RejectionReason::Damaged);
+
+        let unit = client.get_blood_unit(&unit_id);
+        assert_eq!(unit.status, BloodStatus::ReturnPending);
+        assert_eq!(unit.recipient_hospital, None);
+        assert_eq!(
+            client.get_custody_event(&event_id).status,
+            CustodyStatus::Rejected
+        );
+        assert_eq!(
+            client.get_current_custodian(&unit_id),
+            (bank.clone(), CustodianKind::Bank)
         );
+        assert_eq!(client.get_custody_event_counts(), (0, 0, 1));
 
-        let results = client.query_by_blood_type(&BloodType::OPositive, &0, &10);
-        assert_eq!(results.len(), 3);
+        // Terminal: the refused transfer can be neither confirmed nor rejected again
+        let result = client.try_confirm_transfer(&hospital, &event_id, &None, &None, &None);
+        assert_eq!(result, Err(Ok(Error::InvalidStatus)));
+        let result = client.try_reject_transfer(&hospital, &event_id, &RejectionReason::Other);
+        assert_eq!(result, Err(Ok(Error::InvalidStatus)));
 
-        // Verify FIFO order (earliest expiration first)
-        assert_eq!(results.get(0).unwrap().quantity, 50);
-        assert_eq!(results.get(1).unwrap().quantity, 75);
-        assert_eq!(results.get(2).unwrap().quantity, 100);
+        client.discard_unit(&bank, &unit_id, &DiscardReason::Damaged);
+        assert_eq!(
+            client.get_blood_unit(&unit_id).status,
+            BloodStatus::Discarded
+        );
     }
 
     #[test]
-    fn test_query_pagination() {
+    fn test_transfer_batch_is_atomic() {
         let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
         env.mock_all_auths();
-        let contract_id = env.register(HealthChainContract, ());
-        let client = HealthChainContractClient::new(&env, &contract_id);
-
-        let current_time = env.ledger().timestamp();
+        client.register_blood_bank(&bank);
 
-        // Add 5 units
-        for i in 1..=5 {
-            client.add_blood_unit(
+        let expiration = env.ledger().timestamp() + 7 * 86400;
+        let mut unit_ids = Vec::new(&env);
+        for _ in 0..3 {
+            let unit_id = client.register_blood(
+                &bank,
                 &BloodType::OPositive,
-                &(i * 10),
-                &(current_time + 86400 * i as u64),
-                &symbol_short!("donor"),
-                &symbol_short!("loc1"),
+                &BloodComponent::WholeBlood,
+                &450,
+                &expiration,
+                &Some(symbol_short!("donor")),
+                &None,
+            );
+            unit_ids.push_back(unit_id);
+        }
+        client.allocate_blood(&bank, &unit_ids.get(0).unwrap(), &hospital, &None, &None);
+        client.allocate_blood(&bank, &unit_ids.get(1).unwrap(), &hospital, &None, &None);
+
+        // The third unit was never allocated, so the whole batch is refused
+        let result = client.try_initiate_transfer_batch(&bank, &unit_ids, &None);
+        assert_eq!(result, Err(Ok(Error::InvalidStatus)));
+        for unit_id in unit_ids.iter().take(2) {
+            assert_eq!(
+                client.get_blood_unit(&unit_id).status,
+                BloodStatus::Reserved
             );
         }
+        assert_eq!(client.get_custody_event_counts(), (0, 0, 0));
 
-        // Query with max_results = 2
-        let results = client.query_by_blood_type(&BloodType::OPositive, &0, &2);
-        assert_eq!(results.len(), 2);
+        unit_ids.pop_back();
+        let event_ids = client.initiate_transfer_batch(&bank, &unit_ids, &None);
+        assert_eq!(event_ids.len(), 2);
+        assert_eq!(client.get_custody_event_counts(), (2, 0, 0));
 
-        // Query with max_results = 0 (should return all)
-        let all_results = client.query_by_blood_type(&BloodType::OPositive, &0, &0);
-        assert_eq!(all_results.len(), 5);
+        // A duplicate entry fails on its second confirmation and unwinds the first
+        let mut duplicated = event_ids.clone();
+        duplicated.push_back(event_ids.get(0).unwrap());
+        let result = client.try_confirm_transfer_batch(&hospital, &duplicated, &None);
+        assert_eq!(result, Err(Ok(Error::InvalidStatus)));
+        assert_eq!(client.get_custody_event_counts(), (2, 0, 0));
+
+        let outcomes = client.confirm_transfer_batch(&hospital, &event_ids, &None);
+        assert_eq!(
+            outcomes,
+            vec![&env, DeliveryOutcome::Delivered, DeliveryOutcome::Delivered]
+        );
+        for unit_id in unit_ids.iter() {
+            assert_eq!(
+                client.get_blood_unit(&unit_id).status,
+                BloodStatus::Delivered
+            );
+        }
+        assert_eq!(client.get_custody_event_counts(), (0, 2, 0));
     }
 
     #[test]
-    fn test_query_no_results() {
+    fn test_transfer_confirmation_one_second_before_expiry_succeeds() {
         let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+
+        let bank = Address::generate(&env);
         env.mock_all_auths();
-        let contract_id = env.register(HealthChainContract, ());
-        let client = HealthChainContractClient::new(&env, &contract_id);
+        client.register_blood_bank(&bank);
 
-        // Query without adding any units
-        let results = client.query_by_blood_type(&BloodType::OPositive, &0, &10);
-        assert_eq!(results.len(), 0);
+        let initiated_at = 1_000_000u64;
+        let (unit_id, event_id) =
+            setup_in_transit_unit(&env, &client, &bank, &hospital, initiated_at);
+
+        // One second before the deadline => confirm succeeds
+        env.ledger()
+            .set_timestamp(initiated_at + DEFAULT_TRANSFER_DEADLINE_SECONDS - 1);
+        client.confirm_transfer(&hospital, &event_id, &None, &None, &None);
+
+        let unit = client.get_blood_unit(&unit_id);
+        assert_eq!(unit.status, BloodStatus::Delivered);
     }
 
     #[test]
-    fn test_check_availability_sufficient() {
+    #[should_panic(expected = "Error(Contract, #16)")]
+    fn test_transfer_confirmation_at_expiry_boundary_fails_with_transfer_expired() {
         let env = Env::default();
-        env.mock_all_auths();
-        let contract_id = env.register(HealthChainContract, ());
-        let client = HealthChainContractClient::new(&env, &contract_id);
-
-        let current_time = env.ledger().timestamp();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
 
-        client.add_blood_unit(
-            &BloodType::OPositive,
-            &100,
-            &(current_time + 86400 * 30),
-            &symbol_short!("donor1"),
-            &symbol_short!("loc1"),
-        );
+        let bank = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
 
-        client.add_blood_unit(
-            &BloodType::OPositive,
-            &50,
-            &(current_time + 86400 * 15),
-            &symbol_short!("donor2"),
-            &symbol_short!("loc1"),
-        );
+        let initiated_at = 1_000_000u64;
+        let (_, event_id) = setup_in_transit_unit(&env, &client, &bank, &hospital, initiated_at);
 
-        // Check for 120 units (should be available: 100 + 50 = 150)
-        let available = client.check_availability(&BloodType::OPositive, &120);
-        assert_eq!(available, true);
+        // At the transfer deadline => confirm fails
+        env.ledger()
+            .set_timestamp(initiated_at + DEFAULT_TRANSFER_DEADLINE_SECONDS);
+        client.confirm_transfer(&hospital, &event_id, &None, &None, &None);
     }
 
     #[test]
-    fn test_check_availability_insufficient() {
+    fn test_multiple_transfers_track_expiry_independently() {
         let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+
+        let bank = Address::generate(&env);
         env.mock_all_auths();
-        let contract_id = env.register(HealthChainContract, ());
-        let client = HealthChainContractClient::new(&env, &contract_id);
+        client.register_blood_bank(&bank);
 
-        let current_time = env.ledger().timestamp();
+        let t1 = 1_000_000u64;
+        let t2 = t1 + 100;
 
-        client.add_blood_unit(
-            &BloodType::OPositive,
-            &100,
-            &(current_time + 86400 * 30),
-            &symbol_short!("donor1"),
-            &symbol_short!("loc1"),
-        );
+        let (unit_1, event_id_1) = setup_in_transit_unit(&env, &client, &bank, &hospital, t1);
+        let (unit_2, event_id_2) = setup_in_transit_unit(&env, &client, &bank, &hospital, t2);
 
-        // Check for 200 units (only 100 available)
-        let available = client.check_availability(&BloodType::OPositive, &200);
-        assert_eq!(available, false);
+        // At t1's deadline: transfer #1 expired, transfer #2 still within window.
+        env.ledger().set_timestamp(t1 + DEFAULT_TRANSFER_DEADLINE_SECONDS);
+
+        // Unit 1 can be cancelled.
+        client.cancel_transfer(&bank, &event_id_1, &None);
+
+        // Unit 2 can still be confirmed at the same ledger time.
+        client.confirm_transfer(&hospital, &event_id_2, &None, &None, &None);
+
+        let u1 = client.get_blood_unit(&unit_1);
+        let u2 = client.get_blood_unit(&unit_2);
+
+        assert_eq!(u1.status, BloodStatus::Reserved);
+        assert_eq!(u2.status, BloodStatus::Delivered);
     }
 
     #[test]
-    fn test_check_availability_excludes_expired() {
+    #[should_panic(expected = "Error(Contract, #9)")]
+    fn test_confirm_transfer_rejects_unregistered_to_custodian() {
         let env = Env::default();
+        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
+
+        let bank = Address::generate(&env);
         env.mock_all_auths();
-        let contract_id = env.register(HealthChainContract, ());
-        let client = HealthChainContractClient::new(&env, &contract_id);
+        client.register_blood_bank(&bank);
 
-        let current_time = env.ledger().timestamp();
+        let initiated_at = 1_000_000u64;
+        let (_, event_id) = setup_in_transit_unit(&env, &client, &bank, &hospital, initiated_at);
 
-        // Add expired unit (expiration = 0, which is before current_time)
-        client.add_blood_unit(
-            &BloodType::OPositive,
-            &100,
-            &0, // Already expired
-            &symbol_short!("donor1"),
-            &symbol_short!("loc1"),
-        );
+        let unregistered_hospital = Address::generate(&env);
 
-        // Add valid unit
-        client.add_blood_unit(
-            &BloodType::OPositive,
-            &50,
-            &(current_time + 86400 * 15),
-            &symbol_short!("donor2"),
-            &symbol_short!("loc1"),
-        );
+        // Tamper the custody event in storage to set `to_custodian` to unregistered address
+        env.as_contract(&contract_id, || {
+            let mut custody_events: Map<String, CustodyEvent> = env
+                .storage()
+                .persistent()
+                .get(&CUSTODY_EVENTS)
+                .unwrap();
+            let mut event = custody_events.get(event_id.clone()).unwrap();
+            event.to_custodian = unregistered_hospital.clone();
+            custody_events.set(event_id.clone(), event);
+            env.storage().persistent().set(&CUSTODY_EVENTS, &custody_events);
+        });
 
-        // Check for 75 units (only 50 available, expired doesn't count)
-        let available = client.check_availability(&BloodType::OPositive, &75);
-        assert_eq!(available, false);
+        // Try to confirm with the unregistered hospital. It should panic with UnauthorizedHospital (error code #9)
+        client.confirm_transfer(&unregistered_hospital, &event_id, &None, &None, &None);
+    }
 
-        // Check for 50 units (should be available)
-        let available = client.check_availability(&BloodType::OPositive, &50);
-        assert_eq!(available, true);
+    #[test]
+    fn test_get_units_by_bank_empty() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let empty_bank = Address::generate(&env);
+
+        // This should return an empty Vec and NOT panic
+        let results = client.get_units_by_bank(&empty_bank, &0, &0);
+        assert_eq!(results.len(), 0);
     }
 
     #[test]
-    fn test_check_availability_no_inventory() {
+    fn test_get_units_by_bank_paginates_and_drops_delivered_and_expired() {
         let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
+        let other_bank = Address::generate(&env);
+
         env.mock_all_auths();
-        let contract_id = env.register(HealthChainContract, ());
-        let client = HealthChainContractClient::new(&env, &contract_id);
+        client.register_blood_bank(&bank);
+        client.register_blood_bank(&other_bank);
 
-        // Check without adding any units
-        let available = client.check_availability(&BloodType::OPositive, &1);
-        assert_eq!(available, false);
+        let now = env.ledger().timestamp();
+        let register = |bank: &Address, expiry: u64| {
+            client.register_blood(
+                bank,
+                &BloodType::OPositive,
+                &BloodComponent::WholeBlood,
+                &450,
+                &expiry,
+                &None,
+                &None,
+            )
+        };
+
+        let first = register(&bank, now + 7 * 86400);
+        let second = register(&bank, now + 7 * 86400);
+        let short_lived = register(&bank, now + 86400);
+        register(&other_bank, now + 7 * 86400);
+
+        let ids = |offset: u32, limit: u32| {
+            let mut ids = Vec::new(&env);
+            for unit in client.get_units_by_bank(&bank, &offset, &limit).iter() {
+                ids.push_back(unit.id);
+            }
+            ids
+        };
+
+        assert_eq!(ids(0, 0), vec![&env, first, second, short_lived]);
+        assert_eq!(ids(1, 1), vec![&env, second]);
+        assert_eq!(ids(2, 10), vec![&env, short_lived]);
+        assert_eq!(ids(3, 10).len(), 0);
+
+        // Delivered units leave the bank's inventory
+        client.allocate_blood(&bank, &first, &hospital, &None, &None);
+        client.handoff_with_dual_auth(&bank, &hospital, &first);
+        assert_eq!(ids(0, 0), vec![&env, second, short_lived]);
+
+        // So do expired ones
+        env.ledger().set_timestamp(now + 86400);
+        client.expire_unit(&short_lived);
+        assert_eq!(ids(0, 0), vec![&env, second]);
+
+        assert_eq!(client.get_units_by_bank(&other_bank, &0, &0).len(), 1);
     }
 
     #[test]
-    fn test_create_request_success() {
+    fn test_list_units_and_banks_follow_next_cursor() {
         let env = Env::default();
-        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let (_, _, _, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
+        let other_bank = Address::generate(&env);
 
         env.mock_all_auths();
-        let current_time = env.ledger().timestamp();
-        let required_by = current_time + 3600;
+        client.register_blood_bank(&bank);
+        client.register_blood_bank(&other_bank);
+
+        let expiry = env.ledger().timestamp() + 7 * 86400;
+        let mut registered = Vec::new(&env);
+        for owner in [&bank, &other_bank, &bank] {
+            registered.push_back(client.register_blood(
+                owner,
+                &BloodType::APositive,
+                &BloodComponent::WholeBlood,
+                &450,
+                &expiry,
+                &None,
+                &None,
+            ));
+        }
 
-        let request_id = client.create_request(
-            &hospital,
-            &BloodType::APositive,
-            &500,
-            &UrgencyLevel::Urgent,
-            &required_by,
-            &String::from_str(&env, "Ward A, City Hospital"),
-        );
+        let first = client.list_units(&0, &2);
+        assert_eq!(first.items.len(), 2);
+        assert_eq!(first.items.get(0).unwrap().id, registered.get(0).unwrap());
+        assert_eq!(first.items.get(1).unwrap().id, registered.get(1).unwrap());
+        let cursor = first.next_cursor.unwrap();
 
-        let events = env.events().all();
-        assert_eq!(events.len(), 1);
+        let second = client.list_units(&cursor, &2);
+        assert_eq!(second.items.len(), 1);
+        assert_eq!(second.items.get(0).unwrap().id, registered.get(2).unwrap());
+        assert_eq!(second.next_cursor, None);
 
-        assert_eq!(request_id, 1);
+        let banks = client.list_blood_banks(&0, &1);
+        assert_eq!(banks.items.len(), 1);
+        assert_eq!(banks.next_cursor, Some(1));
+        let rest = client.list_blood_banks(&1, &0);
+        assert_eq!(rest.items.len(), 1);
+        assert_eq!(rest.next_cursor, None);
+        assert_ne!(banks.items.get(0).unwrap(), rest.items.get(0).unwrap());
+        for listed in [banks.items.get(0).unwrap(), rest.items.get(0).unwrap()] {
+            assert!(listed == bank || listed == other_bank);
+        }
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1)")]
-    fn test_create_request_unauthorized_hospital() {
+    fn test_expire_units_batch_sweeps_expiry_index_in_order() {
         let env = Env::default();
-        let (_, _, client) = setup_contract_with_admin(&env);
-        let hospital = Address::generate(&env);
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+
+        let now = env.ledger().timestamp();
+        let register = |expiry: u64| {
+            client.register_blood(
+                &bank,
+                &BloodType::OPositive,
+                &BloodComponent::WholeBlood,
+                &450,
+                &expiry,
+                &None,
+                &None,
+            )
+        };
+
+        let due_first = register(now + SECONDS_PER_DAY);
+        let delivered = register(now + SECONDS_PER_DAY);
+        let later_same_day = register(now + SECONDS_PER_DAY + 3600);
+        let next_week = register(now + 7 * SECONDS_PER_DAY);
+
+        client.allocate_blood(&bank, &delivered, &hospital, &None, &None);
+        client.handoff_with_dual_auth(&bank, &hospital, &delivered);
+
+        // Nothing is due yet
+        assert_eq!(client.expire_units_batch(&10), 0);
+
+        // Delivered units are dropped from the index, not expired
+        env.ledger().set_timestamp(now + SECONDS_PER_DAY + 60);
+        assert_eq!(client.expire_units_batch(&10), 1);
+        assert_eq!(
+            client.get_blood_unit(&due_first).status,
+            BloodStatus::Expired
+        );
+        assert_eq!(
+            client.get_blood_unit(&delivered).status,
+            BloodStatus::Delivered
+        );
+        assert_eq!(
+            client.get_blood_unit(&later_same_day).status,
+            BloodStatus::Available
+        );
 
-        env.mock_all_auths();
-        let current_time = env.ledger().timestamp();
-        let required_by = current_time + 3600;
+        env.ledger().set_timestamp(now + 2 * SECONDS_PER_DAY);
+        assert_eq!(client.expire_units_batch(&10), 1);
+        assert_eq!(
+            client.get_blood_unit(&later_same_day).status,
+            BloodStatus::Expired
+        );
+        assert_eq!(
+            client.get_blood_unit(&next_week).status,
+            BloodStatus::Available
+        );
 
-        client.create_request(
-            &hospital,
-            &BloodType::ONegative,
-            &600,
-            &UrgencyLevel::Critical,
-            &required_by,
-            &String::from_str(&env, "ER"),
+        // max_count bounds the work done per call
+        env.ledger().set_timestamp(now + 7 * SECONDS_PER_DAY);
+        assert_eq!(client.expire_units_batch(&0), 0);
+        assert_eq!(client.expire_units_batch(&1), 1);
+        assert_eq!(
+            client.get_blood_unit(&next_week).status,
+            BloodStatus::Expired
         );
+        assert_eq!(client.expire_units_batch(&10), 0);
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #2)")]
-    fn test_create_request_invalid_quantity_low() {
+    fn test_inventory_summary_counters_follow_status_changes() {
         let env = Env::default();
         let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
 
         env.mock_all_auths();
-        let current_time = env.ledger().timestamp();
-        let required_by = current_time + 3600;
+        client.register_blood_bank(&bank);
 
-        client.create_request(
-            &hospital,
-            &BloodType::OPositive,
-            &10,
-            &UrgencyLevel::Routine,
-            &required_by,
-            &String::from_str(&env, "Ward B"),
+        let now = env.ledger().timestamp();
+        let register = |blood_type: BloodType, quantity: u32, expiry: u64| {
+            client.register_blood(
+                &bank,
+                &blood_type,
+                &BloodComponent::WholeBlood,
+                &quantity,
+                &expiry,
+                &None,
+                &None,
+            )
+        };
+
+        let delivered = register(BloodType::OPositive, 450, now + 7 * SECONDS_PER_DAY);
+        let reserved = register(BloodType::OPositive, 300, now + 7 * SECONDS_PER_DAY);
+        let expiring = register(BloodType::OPositive, 250, now + SECONDS_PER_DAY);
+        register(BloodType::ANegative, 400, now + 7 * SECONDS_PER_DAY);
+
+        let summary = client.get_inventory_summary(&bank);
+        assert_eq!(summary.len(), 2);
+        let o_pos = summary.get(BloodType::OPositive).unwrap();
+        assert_eq!(o_pos.available, 3);
+        assert_eq!(o_pos.total_volume_ml, 1000);
+
+        client.allocate_blood(&bank, &delivered, &hospital, &None, &None);
+        client.handoff_with_dual_auth(&bank, &hospital, &delivered);
+        client.allocate_blood(&bank, &reserved, &hospital, &None, &None);
+        env.ledger().set_timestamp(now + SECONDS_PER_DAY);
+        client.expire_unit(&expiring);
+
+        let o_pos = client
+            .get_inventory_summary(&bank)
+            .get(BloodType::OPositive)
+            .unwrap();
+        assert_eq!(
+            o_pos,
+            TypeSummary {
+                available: 0,
+                reserved: 1,
+                delivered: 1,
+                expired: 1,
+                total_volume_ml: 0,
+            }
         );
+
+        let a_neg = client
+            .get_inventory_summary(&bank)
+            .get(BloodType::ANegative)
+            .unwrap();
+        assert_eq!(a_neg.available, 1);
+        assert_eq!(a_neg.total_volume_ml, 400);
+
+        assert_eq!(client.get_inventory_summary(&hospital).len(), 0);
     }
 
     #[test]
-    fn test_create_blood_request_success() {
+    fn test_get_available_volume_sums_only_available_units() {
         let env = Env::default();
-        let (_, _, client) = setup_contract_with_admin(&env);
-        let hospital = Address::generate(&env);
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
+        let other_bank = Address::generate(&env);
 
         env.mock_all_auths();
-        client.register_hospital(&hospital);
+        client.register_blood_bank(&bank);
+        client.register_blood_bank(&other_bank);
 
-        let required_by = env.ledger().timestamp() + 86400; // Tomorrow
-        let result = client.create_request(
-            &hospital,
-            &BloodType::ABNegative,
-            &500,
-            &UrgencyLevel::High,
-            &required_by,
-            &String::from_str(&env, "Main_Hosp"),
-        );
+        let now = env.ledger().timestamp();
+        let long_expiry = now + (7 * 86400);
+        let short_expiry = now + 86400;
+        let donor = Some(symbol_short!("donor1"));
 
-        assert_eq!(result, 1);
-    }
+        let register = |bank: &Address, blood_type: BloodType, volume: u32, expiry: u64| {
+            client.register_blood(
+                bank,
+                &blood_type,
+                &BloodComponent::WholeBlood,
+                &volume,
+                &expiry,
+                &donor,
+                &None,
+            )
+        };
 
-    #[test]
-    #[should_panic(expected = "Error(Contract, #1)")] // Error::Unauthorized
-    fn test_create_request_unauthorized() {
-        let env = Env::default();
-        let (_, _, client) = setup_contract_with_admin(&env);
-        let rogue_hospital = Address::generate(&env);
+        register(&bank, BloodType::OPositive, 450, long_expiry);
+        register(&bank, BloodType::OPositive, 300, long_expiry);
+        let reserved = register(&bank, BloodType::OPositive, 200, long_expiry);
+        register(&bank, BloodType::OPositive, 100, short_expiry);
+        register(&bank, BloodType::APositive, 250, long_expiry);
+        register(&other_bank, BloodType::OPositive, 400, long_expiry);
 
-        env.mock_all_auths();
-        // hospital is NOT registered via client.register_hospital()
+        let available =
+            |blood_type: BloodType| client.get_available_volume(&bank, &blood_type, &None);
 
-        client.create_request(
-            &rogue_hospital,
-            &BloodType::OPositive,
-            &400,
-            &UrgencyLevel::Medium,
-            &(env.ledger().timestamp() + 86400),
-            &String::from_str(&env, "Hosp_1"),
+        assert_eq!(available(BloodType::OPositive), 1050);
+
+        // Reserved units no longer count
+        client.allocate_blood(&bank, &reserved, &hospital, &None, &None);
+        assert_eq!(available(BloodType::OPositive), 850);
+
+        // Units past expiry are excluded even before being marked Expired
+        env.ledger().set_timestamp(short_expiry);
+        assert_eq!(available(BloodType::OPositive), 750);
+
+        assert_eq!(available(BloodType::APositive), 250);
+        assert_eq!(available(BloodType::BNegative), 0);
+        assert_eq!(
+            client.get_available_volume(&other_bank, &BloodType::OPositive, &None),
+            400
         );
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #2)")]
-    fn test_create_request_invalid_quantity_high() {
+    fn test_available_by_type_index_tracks_status_changes() {
         let env = Env::default();
         let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
+        let other_bank = Address::generate(&env);
 
         env.mock_all_auths();
-        let current_time = env.ledger().timestamp();
-        let required_by = current_time + 3600;
+        client.register_blood_bank(&bank);
+        client.register_blood_bank(&other_bank);
 
-        client.create_request(
-            &hospital,
-            &BloodType::BPositive,
-            &6000,
-            &UrgencyLevel::Routine,
-            &required_by,
-            &String::from_str(&env, "Ward B"),
+        let now = env.ledger().timestamp();
+        let register = |bank: &Address, blood_type: BloodType, volume: u32, expiry: u64| {
+            client.register_blood(
+                bank,
+                &blood_type,
+                &BloodComponent::WholeBlood,
+                &volume,
+                &expiry,
+                &None,
+                &None,
+            )
+        };
+
+        let large = register(&bank, BloodType::ONegative, 450, now + 7 * 86400);
+        let small = register(&bank, BloodType::ONegative, 200, now + 7 * 86400);
+        let short_lived = register(&bank, BloodType::ONegative, 450, now + 86400);
+        let remote = register(&other_bank, BloodType::ONegative, 450, now + 7 * 86400);
+        register(&bank, BloodType::APositive, 450, now + 7 * 86400);
+
+        let count = |bank: &Address| client.get_available_count(bank, &BloodType::ONegative, &None);
+        let found = |min_volume: u32| {
+            let mut ids = Vec::new(&env);
+            for unit in client
+                .find_available(&BloodType::ONegative, &None, &min_volume, &0)
+                .iter()
+            {
+                ids.push_back(unit.id);
+            }
+            ids
+        };
+
+        assert_eq!(count(&bank), 3);
+        assert_eq!(count(&other_bank), 1);
+        assert_eq!(found(0).len(), 4);
+        assert!(!found(300).contains(small));
+        assert_eq!(
+            client
+                .find_available(&BloodType::ONegative, &None, &0, &2)
+                .len(),
+            2
         );
-    }
 
-    #[test]
-    #[should_panic(expected = "Error(Contract, #2)")] // Error::InvalidQuantity
-    fn test_create_request_invalid_quantity() {
-        let env = Env::default();
-        let (_, _, client) = setup_contract_with_admin(&env);
-        let hospital = Address::generate(&env);
+        // Allocation removes the unit; cancelling puts it back
+        client.allocate_blood(&bank, &large, &hospital, &None, &None);
+        assert_eq!(count(&bank), 2);
+        assert!(!found(0).contains(large));
+        client.cancel_allocation(&bank, &large);
+        assert_eq!(count(&bank), 3);
 
-        env.mock_all_auths();
-        client.register_hospital(&hospital);
+        // Delivery removes it for good
+        client.allocate_blood(&bank, &large, &hospital, &None, &None);
+        client.handoff_with_dual_auth(&bank, &hospital, &large);
+        assert_eq!(count(&bank), 2);
 
-        client.create_request(
-            &hospital,
-            &BloodType::OPositive,
-            &10, // Below MIN_QUANTITY_ML (50)
-            &UrgencyLevel::Low,
-            &(env.ledger().timestamp() + 86400),
-            &String::from_str(&env, "Hosp_1"),
-        );
+        // Past-expiry units are skipped by find_available, then dropped on expiry
+        env.ledger().set_timestamp(now + 86400);
+        assert!(!found(0).contains(short_lived));
+        assert_eq!(count(&bank), 2);
+        client.expire_unit(&short_lived);
+        assert_eq!(count(&bank), 1);
+
+        let remaining = found(0);
+        assert_eq!(remaining.len(), 2);
+        assert!(remaining.contains(small) && remaining.contains(remote));
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #15)")]
-    fn test_create_request_required_by_in_past() {
+    fn test_allocate_fefo_reserves_earliest_expiring_units() {
         let env = Env::default();
         let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
 
         env.mock_all_auths();
-        let current_time = env.ledger().timestamp();
-        let required_by = current_time;
+        client.register_blood_bank(&bank);
 
-        client.create_request(
-            &hospital,
-            &BloodType::ABPositive,
-            &200,
-            &UrgencyLevel::Urgent,
-            &required_by,
-            &String::from_str(&env, "Ward C"),
+        let now = env.ledger().timestamp();
+        let register = |expiry: u64| {
+            client.register_blood(
+                &bank,
+                &BloodType::BPositive,
+                &BloodComponent::WholeBlood,
+                &450,
+                &expiry,
+                &None,
+                &None,
+            )
+        };
+
+        // Registered out of expiry order; the index keeps them sorted
+        let day_five = register(now + 5 * 86400);
+        let day_two = register(now + 2 * 86400);
+        let day_nine = register(now + 9 * 86400);
+        let day_two_later = register(now + 2 * 86400);
+        let day_one = register(now + 86400);
+
+        let allocated = client.allocate_fefo(&bank, &BloodType::BPositive, &3, &hospital);
+        assert_eq!(allocated, vec![&env, day_one, day_two, day_two_later]);
+        for unit_id in allocated.iter() {
+            let unit = client.get_blood_unit(&unit_id);
+            assert_eq!(unit.status, BloodStatus::Reserved);
+            assert_eq!(unit.recipient_hospital, Some(hospital.clone()));
+        }
+
+        // All-or-nothing: asking for more than remain reserves nothing
+        let result = client.try_allocate_fefo(&bank, &BloodType::BPositive, &3, &hospital);
+        assert_eq!(result, Err(Ok(Error::InsufficientInventory)));
+        assert_eq!(
+            client.get_blood_unit(&day_five).status,
+            BloodStatus::Available
+        );
+
+        let result = client.try_allocate_fefo(&bank, &BloodType::BPositive, &0, &hospital);
+        assert_eq!(result, Err(Ok(Error::InvalidQuantity)));
+
+        // A released unit slots back in by expiry, ahead of later units
+        client.cancel_allocation(&bank, &day_two);
+        let allocated = client.allocate_fefo(&bank, &BloodType::BPositive, &2, &hospital);
+        assert_eq!(allocated, vec![&env, day_two, day_five]);
+        assert_eq!(
+            client.get_blood_unit(&day_nine).status,
+            BloodStatus::Available
         );
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #34)")]
-    fn test_create_request_delivery_address_too_long() {
+    fn test_allocate_blood_batch_reserves_all_or_names_first_failure() {
         let env = Env::default();
         let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
+        let other_bank = Address::generate(&env);
 
         env.mock_all_auths();
-        let current_time = env.ledger().timestamp();
-        let required_by = current_time + 3600;
+        client.register_blood_bank(&bank);
+        client.register_blood_bank(&other_bank);
 
-        // 201 bytes — one byte over MAX_DELIVERY_ADDRESS_LENGTH (200)
-        let addr_bytes = [b'a'; 201];
-        let addr_str = core::str::from_utf8(&addr_bytes).unwrap();
-        let long_addr = String::from_str(&env, addr_str);
+        let expiry = env.ledger().timestamp() + 7 * 86400;
+        let register = |bank: &Address| {
+            client.register_blood(
+                bank,
+                &BloodType::ONegative,
+                &BloodComponent::WholeBlood,
+                &450,
+                &expiry,
+                &None,
+                &None,
+            )
+        };
+        let first = register(&bank);
+        let second = register(&bank);
+        let third = register(&bank);
+        let foreign = register(&other_bank);
 
-        client.create_request(
+        // A foreign unit mid-list rejects the batch and reserves nothing
+        let outcome = client.allocate_blood_batch(
+            &bank,
+            &vec![&env, first, second, foreign, 999],
             &hospital,
-            &BloodType::OPositive,
-            &200,
-            &UrgencyLevel::High,
-            &required_by,
-            &long_addr,
+            &None,
+        );
+        assert_eq!(
+            outcome,
+            BatchAllocationOutcome::Rejected(BatchAllocationFailure {
+                index: 2,
+                unit_id: foreign,
+                error_code: Error::Unauthorized as u32,
+            })
+        );
+        assert_eq!(client.get_blood_unit(&first).status, BloodStatus::Available);
+        assert_eq!(
+            client.get_blood_unit(&second).status,
+            BloodStatus::Available
+        );
+
+        // Listing a unit twice is caught before anything is written
+        let outcome =
+            client.allocate_blood_batch(&bank, &vec![&env, first, first], &hospital, &None);
+        assert_eq!(
+            outcome,
+            BatchAllocationOutcome::Rejected(BatchAllocationFailure {
+                index: 1,
+                unit_id: first,
+                error_code: Error::AlreadyAllocated as u32,
+            })
+        );
+
+        let ids = vec![&env, first, second, third];
+        let outcome = client.allocate_blood_batch(&bank, &ids, &hospital, &None);
+        assert_eq!(outcome, BatchAllocationOutcome::Allocated(ids.clone()));
+        for unit_id in ids.iter() {
+            assert_eq!(
+                client.get_blood_unit(&unit_id).status,
+                BloodStatus::Reserved
+            );
+        }
+
+        // Already-reserved units now fail with InvalidStatus
+        let outcome = client.allocate_blood_batch(&bank, &vec![&env, third], &hospital, &None);
+        assert_eq!(
+            outcome,
+            BatchAllocationOutcome::Rejected(BatchAllocationFailure {
+                index: 0,
+                unit_id: third,
+                error_code: Error::InvalidStatus as u32,
+            })
         );
+
+        let result = client.try_allocate_blood_batch(&bank, &Vec::new(&env), &hospital, &None);
+        assert_eq!(result, Err(Ok(Error::InvalidQuantity)));
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #14)")]
-    fn test_create_request_empty_delivery_address() {
+    fn test_transfer_unit_ownership_moves_bank_indexes_and_counters() {
         let env = Env::default();
         let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
+        let other_bank = Address::generate(&env);
 
         env.mock_all_auths();
-        let current_time = env.ledger().timestamp();
-        let required_by = current_time + 3600;
+        client.register_blood_bank(&bank);
+        client.register_blood_bank(&other_bank);
 
-        client.create_request(
-            &hospital,
+        let donor = symbol_short!("DONOR7");
+        let unit_id = client.register_blood(
+            &bank,
             &BloodType::ABNegative,
-            &200,
-            &UrgencyLevel::Urgent,
-            &required_by,
-            &String::from_str(&env, ""),
+            &BloodComponent::WholeBlood,
+            &450,
+            &(env.ledger().timestamp() + 7 * 86400),
+            &Some(donor.clone()),
+            &None,
         );
+
+        let result = client.try_transfer_unit_ownership(&other_bank, &bank, &unit_id);
+        assert_eq!(result, Err(Ok(Error::NotCurrentCustodian)));
+
+        client.transfer_unit_ownership(&bank, &other_bank, &unit_id);
+
+        assert_eq!(client.get_blood_unit(&unit_id).bank_id, other_bank);
+        let count =
+            |bank: &Address| client.get_available_count(bank, &BloodType::ABNegative, &None);
+        assert_eq!(count(&bank), 0);
+        assert_eq!(count(&other_bank), 1);
+        assert!(client.get_units_by_bank_donor(&bank, &donor).is_empty());
+        assert_eq!(client.get_units_by_bank_donor(&other_bank, &donor).len(), 1);
+        assert_eq!(client.get_units_by_donor(&donor).len(), 1);
+
+        let old_summary = client
+            .get_inventory_summary(&bank)
+            .get(BloodType::ABNegative)
+            .unwrap();
+        assert_eq!(old_summary.available, 0);
+        assert_eq!(old_summary.total_volume_ml, 0);
+        let new_summary = client
+            .get_inventory_summary(&other_bank)
+            .get(BloodType::ABNegative)
+            .unwrap();
+        assert_eq!(new_summary.available, 1);
+        assert_eq!(new_summary.total_volume_ml, 450);
+
+        // The new owner can allocate it; the old one no longer can
+        let result = client.try_allocate_fefo(&bank, &BloodType::ABNegative, &1, &hospital);
+        assert_eq!(result, Err(Ok(Error::InsufficientInventory)));
+        client.allocate_blood(&other_bank, &unit_id, &hospital, &None, &None);
+
+        // Reserved units stay put
+        let result = client.try_transfer_unit_ownership(&other_bank, &bank, &unit_id);
+        assert_eq!(result, Err(Ok(Error::InvalidStatus)));
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #15)")]
-    fn test_create_request_past_date() {
+    fn test_split_unit_creates_traceable_aliquots() {
         let env = Env::default();
-
-        // Set the time to something substantial first
-        env.ledger().with_mut(|li| li.timestamp = 10000);
-
-        let (_, _, client) = setup_contract_with_admin(&env);
-        let hospital = Address::generate(&env);
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
+        let lab = Address::generate(&env);
 
         env.mock_all_auths();
-        client.register_hospital(&hospital);
+        client.register_blood_bank(&bank);
+        client.register_lab(&lab);
 
-        client.create_request(
-            &hospital,
-            &BloodType::OPositive,
-            &200,
-            &UrgencyLevel::High,
-            &5000, // Now this is safely in the past (5000 < 10000)
-            &String::from_str(&env, "Hosp_1"),
+        let donor = symbol_short!("DONOR9");
+        let parent = client.register_blood(
+            &bank,
+            &BloodType::ONegative,
+            &BloodComponent::RedBloodCells,
+            &300,
+            &(env.ledger().timestamp() + 7 * 86400),
+            &Some(donor.clone()),
+            &None,
         );
+        let report = BytesN::from_array(&env, &[3u8; 32]);
+        client.record_test_result(&lab, &parent, &TestType::Hiv, &report, &true);
+
+        // Volumes must cover the parent exactly and respect the aliquot floor
+        let result = client.try_split_unit(&bank, &parent, &vec![&env, 100, 100]);
+        assert_eq!(result, Err(Ok(Error::InvalidQuantity)));
+        let result = client.try_split_unit(&bank, &parent, &vec![&env, 295, 5]);
+        assert_eq!(result, Err(Ok(Error::InvalidQuantity)));
+        let result = client.try_split_unit(&bank, &parent, &vec![&env, 300]);
+        assert_eq!(result, Err(Ok(Error::InvalidQuantity)));
+
+        let children = client.split_unit(&bank, &parent, &vec![&env, 100, 100, 50, 50]);
+        assert_eq!(children.len(), 4);
+        assert_eq!(client.get_blood_unit(&parent).status, BloodStatus::Split);
+        assert_eq!(client.get_child_units(&parent), children);
+
+        let mut total = 0;
+        for child_id in children.iter() {
+            let child = client.get_blood_unit(&child_id);
+            assert_eq!(child.status, BloodStatus::Available);
+            assert_eq!(child.blood_type, BloodType::ONegative);
+            assert_eq!(child.donor_id, donor);
+            assert_eq!(client.get_parent_unit(&child_id), Some(parent));
+            assert_eq!(client.get_test_results(&child_id).len(), 1);
+            total += child.quantity;
+        }
+        assert_eq!(total, 300);
+
+        // Inventory now counts the aliquots instead of the parent
+        assert_eq!(
+            client.get_available_count(&bank, &BloodType::ONegative, &None),
+            4
+        );
+        let summary = client
+            .get_inventory_summary(&bank)
+            .get(BloodType::ONegative)
+            .unwrap();
+        assert_eq!(summary.available, 4);
+        assert_eq!(summary.total_volume_ml, 300);
+
+        // A split parent cannot be allocated or split again
+        let result = client.try_allocate_blood(&bank, &parent, &hospital, &None, &None);
+        assert_eq!(result, Err(Ok(Error::InvalidStatus)));
+        let result = client.try_split_unit(&bank, &parent, &vec![&env, 150, 150]);
+        assert_eq!(result, Err(Ok(Error::InvalidStatus)));
+
+        // Recalling the parent's lot reaches every aliquot
+        let lot = symbol_short!("LOT9");
+        client.create_lot(&bank, &lot);
+        client.assign_lot(&bank, &parent, &lot);
+        let recalled = client.recall_by_lot(&lot, &RecallReason::DonorTestPositive);
+        assert_eq!(recalled, 4);
+        for child_id in children.iter() {
+            assert_eq!(
+                client.get_blood_unit(&child_id).status,
+                BloodStatus::Recalled
+            );
+        }
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #13)")]
-    fn test_create_request_duplicate_request() {
+    fn test_pool_units_combines_sources_into_one_product() {
         let env = Env::default();
         let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
 
         env.mock_all_auths();
-        let current_time = env.ledger().timestamp();
-        let required_by = current_time + 7200;
-        let address = String::from_str(&env, "Ward D");
+        client.register_blood_bank(&bank);
 
-        client.create_request(
-            &hospital,
-            &BloodType::OPositive,
-            &350,
-            &UrgencyLevel::Urgent,
-            &required_by,
-            &address,
+        let now = env.ledger().timestamp();
+        let register = |blood_type: BloodType, expiry: u64, donor: Symbol| {
+            client.register_blood(
+                &bank,
+                &blood_type,
+                &BloodComponent::Platelets,
+                &60,
+                &expiry,
+                &Some(donor),
+                &None,
+            )
+        };
+        let first = register(BloodType::APositive, now + 4 * 86400, symbol_short!("D1"));
+        let second = register(BloodType::APositive, now + 2 * 86400, symbol_short!("D2"));
+        let third = register(BloodType::APositive, now + 3 * 86400, symbol_short!("D3"));
+        let mismatched = register(BloodType::BPositive, now + 3 * 86400, symbol_short!("D4"));
+
+        let result = client.try_pool_units(&bank, &vec![&env, first]);
+        assert_eq!(result, Err(Ok(Error::InvalidQuantity)));
+        let result = client.try_pool_units(&bank, &vec![&env, first, first]);
+        assert_eq!(result, Err(Ok(Error::DuplicateRequest)));
+        let result = client.try_pool_units(&bank, &vec![&env, first, mismatched]);
+        assert_eq!(result, Err(Ok(Error::InvalidTransition)));
+
+        let sources = vec![&env, first, second, third];
+        let pooled_id = client.pool_units(&bank, &sources);
+
+        let pooled = client.get_blood_unit(&pooled_id);
+        assert_eq!(pooled.status, BloodStatus::Available);
+        assert_eq!(pooled.component, BloodComponent::Platelets);
+        assert_eq!(pooled.quantity, 180);
+        assert_eq!(pooled.expiration_date, now + 2 * 86400);
+        assert_eq!(client.get_pool_sources(&pooled_id), sources);
+        for source_id in sources.iter() {
+            assert_eq!(
+                client.get_blood_unit(&source_id).status,
+                BloodStatus::Pooled
+            );
+            assert_eq!(client.get_pooled_into(&source_id), Some(pooled_id));
+        }
+        assert_eq!(
+            client.get_available_count(&bank, &BloodType::APositive, &None),
+            1
         );
 
-        client.create_request(
-            &hospital,
-            &BloodType::OPositive,
-            &350,
-            &UrgencyLevel::Urgent,
-            &required_by,
-            &address,
+        // Sources are spent; the pool is what gets allocated
+        let result = client.try_allocate_blood(&bank, &second, &hospital, &None, &None);
+        assert_eq!(result, Err(Ok(Error::InvalidStatus)));
+
+        // A positive donor test on any source reaches the pooled product
+        let recalled = client.recall_by_donor(
+            &bank,
+            &symbol_short!("D2"),
+            &RecallReason::DonorTestPositive,
+        );
+        assert_eq!(recalled, 1);
+        assert_eq!(
+            client.get_blood_unit(&pooled_id).status,
+            BloodStatus::Recalled
         );
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #13)")]
-    fn test_create_request_duplicate_request_with_delivery_case_and_spacing_variations() {
+    fn test_component_filter_applies_to_inventory_queries() {
         let env = Env::default();
-        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let (_, _, _, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
 
         env.mock_all_auths();
-        let current_time = env.ledger().timestamp();
-        let required_by = current_time + 7200;
+        client.register_blood_bank(&bank);
 
-        client.create_request(
-            &hospital,
-            &BloodType::APositive,
-            &400,
-            &UrgencyLevel::High,
-            &required_by,
-            &String::from_str(&env, " Ward   7B, ICU "),
+        let now = env.ledger().timestamp();
+        let register = |component: BloodComponent, quantity: u32, expiry: u64| {
+            client.register_blood(
+                &bank,
+                &BloodType::OPositive,
+                &component,
+                &quantity,
+                &expiry,
+                &None,
+                &None,
+            )
+        };
+
+        let whole = register(BloodComponent::WholeBlood, 450, now + 30 * SECONDS_PER_DAY);
+        let platelets = register(BloodComponent::Platelets, 250, now + 5 * SECONDS_PER_DAY);
+        let plasma = register(BloodComponent::Plasma, 200, now + 300 * SECONDS_PER_DAY);
+
+        // Shelf life is validated per component: platelets last at most 5 days
+        let too_long = client.try_register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::Platelets,
+            &250,
+            &(now + 6 * SECONDS_PER_DAY),
+            &None,
+            &None,
         );
+        assert_eq!(too_long, Err(Ok(Error::InvalidExpiration)));
 
-        // Same logical request, delivery text variant only.
-        client.create_request(
-            &hospital,
-            &BloodType::APositive,
-            &400,
-            &UrgencyLevel::High,
-            &required_by,
-            &String::from_str(&env, "ward 7b, icu"),
+        let platelets_only = Some(BloodComponent::Platelets);
+        let results = client.query_by_blood_type(&BloodType::OPositive, &platelets_only, &0, &10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results.get(0).unwrap().id, platelets);
+        assert_eq!(
+            client
+                .query_by_blood_type(&BloodType::OPositive, &None, &0, &10)
+                .len(),
+            3
+        );
+
+        assert!(client.check_availability(&BloodType::OPositive, &None, &900));
+        assert!(!client.check_availability(&BloodType::OPositive, &platelets_only, &300));
+
+        let plasma_only = Some(BloodComponent::Plasma);
+        assert_eq!(
+            client.get_available_volume(&bank, &BloodType::OPositive, &plasma_only),
+            200
+        );
+        assert_eq!(
+            client.get_available_count(&bank, &BloodType::OPositive, &plasma_only),
+            1
+        );
+        assert_eq!(
+            client.get_available_count(&bank, &BloodType::OPositive, &None),
+            3
+        );
+
+        let found = client.find_available(
+            &BloodType::OPositive,
+            &Some(BloodComponent::WholeBlood),
+            &0,
+            &10,
+        );
+        assert_eq!(found.len(), 1);
+        assert_eq!(found.get(0).unwrap().id, whole);
+        assert!(client
+            .find_available(&BloodType::OPositive, &plasma_only, &250, &10)
+            .is_empty());
+        assert_eq!(
+            client
+                .find_available(&BloodType::OPositive, &plasma_only, &0, &10)
+                .get(0)
+                .unwrap()
+                .id,
+            plasma
         );
     }
 
     #[test]
-    fn test_create_request_event_payload() {
+    fn test_get_unit_age_tracks_ledger_time() {
         let env = Env::default();
-        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
 
         env.mock_all_auths();
-        let current_time = env.ledger().timestamp();
-        let required_by = current_time + 7200;
-        let delivery_address = String::from_str(&env, "Ward E, General Hospital");
+        client.register_blood_bank(&bank);
+        env.ledger().set_timestamp(1_000_000);
 
-        let request_id = client.create_request(
-            &hospital,
-            &BloodType::ONegative,
+        let unit_id = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
             &450,
-            &UrgencyLevel::Critical,
-            &required_by,
-            &delivery_address,
+            &(1_000_000 + 7 * 86400),
+            &Some(symbol_short!("donor1")),
+            &None,
         );
+        assert_eq!(client.get_unit_age(&unit_id), 0);
 
-        let events = env.events().all();
-        assert_eq!(events.len(), 1);
-
-        let (event_contract_id, topics, data) = events.get(0).unwrap();
-        assert_eq!(event_contract_id, contract_id);
-        assert_eq!(topics.len(), 3);
-
-        let topic0: Symbol = TryFromVal::try_from_val(&env, &topics.get(0).unwrap()).unwrap();
-        let topic1: Symbol = TryFromVal::try_from_val(&env, &topics.get(1).unwrap()).unwrap();
-        let version_topic: Symbol =
-            TryFromVal::try_from_val(&env, &topics.get(2).unwrap()).unwrap();
-        assert_eq!(topic0, symbol_short!("blood"));
-        assert_eq!(topic1, symbol_short!("request"));
-        assert_eq!(version_topic, symbol_short!("v1"));
+        env.ledger().set_timestamp(1_000_000 + 3600);
+        assert_eq!(client.get_unit_age(&unit_id), 3600);
 
-        let event: RequestCreatedEvent = TryFromVal::try_from_val(&env, &data).unwrap();
-        assert_eq!(event.request_id, request_id);
-        assert_eq!(event.hospital_id, hospital);
-        assert!(event.blood_type == BloodType::ONegative);
-        assert_eq!(event.quantity_ml, 450);
-        assert!(event.urgency == UrgencyLevel::Critical);
-        assert_eq!(event.required_by, required_by);
-        assert_eq!(event.delivery_address, delivery_address);
-        assert_eq!(event.created_at, current_time);
+        assert_eq!(client.try_get_unit_age(&999), Err(Ok(Error::UnitNotFound)));
     }
 
     #[test]
-    fn test_create_request_emits_event() {
+    fn test_get_units_older_than_filters_and_paginates() {
         let env = Env::default();
-        let (contract_id, _, client) = setup_contract_with_admin(&env);
-        let hospital = Address::generate(&env);
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
 
         env.mock_all_auths();
-        client.register_hospital(&hospital);
+        client.register_blood_bank(&bank);
 
-        let req_id = client.create_request(
-            &hospital,
-            &BloodType::BPositive,
-            &300,
-            &UrgencyLevel::Critical,
-            &(env.ledger().timestamp() + 3600),
-            &String::from_str(&env, "ER_Room"),
-        );
+        let base = 1_000_000u64;
+        let mut ids = Vec::new(&env);
+        // Register one unit per day for four days
+        for day in 0..4u64 {
+            env.ledger().set_timestamp(base + day * 86400);
+            ids.push_back(client.register_blood(
+                &bank,
+                &BloodType::OPositive,
+                &BloodComponent::WholeBlood,
+                &450,
+                &(base + 30 * 86400),
+                &Some(symbol_short!("donor1")),
+                &None,
+            ));
+        }
 
-        // Get the last event
-        let last_event = env.events().all().last().unwrap();
+        // At day 4 the units are 4, 3, 2 and 1 days old
+        env.ledger().set_timestamp(base + 4 * 86400);
 
-        // 1. Verify the Contract ID
-        assert_eq!(last_event.0, contract_id);
+        let older = client.get_units_older_than(&bank, &(2 * 86400), &0, &10);
+        assert_eq!(older, vec![&env, ids.get(0).unwrap(), ids.get(1).unwrap()]);
 
-        // 2. Verify the Topics (blood, request, v1)
-        let expected_topics = (
-            symbol_short!("blood"),
-            symbol_short!("request"),
-            symbol_short!("v1"),
-        )
-            .into_val(&env);
-        assert_eq!(last_event.1, expected_topics);
+        let all = client.get_units_older_than(&bank, &0, &0, &10);
+        assert_eq!(all.len(), 4);
 
-        // 3. Verify the Data (Optional: Deserialize it to be sure)
-        // Fixed: Use RequestCreatedEvent instead of legacy BloodRequestEvent which had missing fields
-        let event_data: RequestCreatedEvent = last_event.2.into_val(&env);
-        assert_eq!(event_data.request_id, req_id);
-        assert_eq!(event_data.hospital_id, hospital);
+        let page = client.get_units_older_than(&bank, &0, &1, &2);
+        assert_eq!(page, vec![&env, ids.get(1).unwrap(), ids.get(2).unwrap()]);
+
+        assert_eq!(
+            client
+                .get_units_older_than(&bank, &(10 * 86400), &0, &10)
+                .len(),
+            0
+        );
     }
 
+    /// Test for Issue #125: Donor ID collision across different banks
+    /// Verifies that get_units_by_donor uses composite (bank_id, donor_id) key
+    /// to prevent cross-bank data mixing
     #[test]
-    fn test_event_schema_version_topic_distinguishes_current_from_legacy() {
+    fn test_donor_id_collision_across_banks() {
         let env = Env::default();
-        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let (_, _admin, client) = setup_contract_with_admin(&env);
+
+        // Register two different blood banks
+        let bank_a = Address::generate(&env);
+        let bank_b = Address::generate(&env);
 
         env.mock_all_auths();
-        client.create_request(
-            &hospital,
-            &BloodType::BPositive,
-            &300,
-            &UrgencyLevel::Critical,
-            &(env.ledger().timestamp() + 3600),
-            &String::from_str(&env, "ER_Room"),
+        client.register_blood_bank(&bank_a);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank_b);
+
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (7 * 86400);
+
+        // Bank A registers a unit with donor "001"
+        env.mock_all_auths();
+        let unit_a1 = client.register_blood(
+            &bank_a,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &expiration,
+            &Some(symbol_short!("001")),
+            &None,
         );
 
-        let (_, topics, _) = env.events().all().last().unwrap();
-        let legacy_topics = (symbol_short!("blood"), symbol_short!("request")).into_val(&env);
-        let current_topics = (
-            symbol_short!("blood"),
-            symbol_short!("request"),
-            symbol_short!("v1"),
-        )
-            .into_val(&env);
+        // Bank B also registers a unit with donor "001" (different person, same ID)
+        env.mock_all_auths();
+        let unit_b1 = client.register_blood(
+            &bank_b,
+            &BloodType::APositive,
+            &BloodComponent::WholeBlood,
+            &350,
+            &expiration,
+            &Some(symbol_short!("001")),
+            &None,
+        );
 
-        assert_ne!(topics, legacy_topics);
-        assert_eq!(topics, current_topics);
-        assert_eq!(EVENT_SCHEMA_VERSION, 1);
+        // Get units for donor "001" at Bank A - should only return Bank A's unit
+        let all_donor_units = client.get_units_by_donor(&symbol_short!("001"));
+        let mut bank_a_units = vec![&env];
+        for i in 0..all_donor_units.len() {
+            let unit = all_donor_units.get(i).unwrap();
+            if unit.bank_id == bank_a {
+                bank_a_units.push_back(unit);
+            }
+        }
+        assert_eq!(bank_a_units.len(), 1);
+        assert_eq!(bank_a_units.get(0).unwrap().id, unit_a1);
+        assert_eq!(
+            bank_a_units.get(0).unwrap().blood_type,
+            BloodType::OPositive
+        );
+        assert_eq!(bank_a_units.get(0).unwrap().bank_id, bank_a);
+
+        // Get units for donor "001" at Bank B - should only return Bank B's unit
+        let mut bank_b_units = vec![&env];
+        for i in 0..all_donor_units.len() {
+            let unit = all_donor_units.get(i).unwrap();
+            if unit.bank_id == bank_b {
+                bank_b_units.push_back(unit);
+            }
+        }
+        assert_eq!(bank_b_units.len(), 1);
+        assert_eq!(bank_b_units.get(0).unwrap().id, unit_b1);
+        assert_eq!(
+            bank_b_units.get(0).unwrap().blood_type,
+            BloodType::APositive
+        );
+        assert_eq!(bank_b_units.get(0).unwrap().bank_id, bank_b);
+
+        // Register another unit for donor "001" at Bank A
+        env.mock_all_auths();
+        let unit_a2 = client.register_blood(
+            &bank_a,
+            &BloodType::ONegative,
+            &BloodComponent::WholeBlood,
+            &400,
+            &expiration,
+            &Some(symbol_short!("001")),
+            &None,
+        );
+
+        // Verify Bank A now has 2 units for donor "001"
+        let all_updated = client.get_units_by_donor(&symbol_short!("001"));
+        let mut bank_a_units_updated = vec![&env];
+        for i in 0..all_updated.len() {
+            let unit = all_updated.get(i).unwrap();
+            if unit.bank_id == bank_a {
+                bank_a_units_updated.push_back(unit);
+            }
+        }
+        assert_eq!(bank_a_units_updated.len(), 2);
+
+        // Verify Bank B still has only 1 unit for donor "001"
+        let mut bank_b_units_updated = vec![&env];
+        for i in 0..all_updated.len() {
+            let unit = all_updated.get(i).unwrap();
+            if unit.bank_id == bank_b {
+                bank_b_units_updated.push_back(unit);
+            }
+        }
+        assert_eq!(bank_b_units_updated.len(), 1);
     }
 
+    /// Test get_units_by_donor with non-existent donor
     #[test]
-    fn test_approve_request_reserves_units_and_updates_request() {
+    fn test_get_units_by_donor_nonexistent() {
         let env = Env::default();
-        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
+        let (_, _, client) = setup_contract_with_admin(&env);
 
         let bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
-
-        let unit_id_1 = client.register_blood(
-            &bank,
-            &BloodType::APositive,
-            &BloodComponent::WholeBlood,
-            &300,
-            &expiration,
-            &Some(symbol_short!("donor1")),
-        );
-        let unit_id_2 = client.register_blood(
-            &bank,
-            &BloodType::APositive,
-            &BloodComponent::WholeBlood,
-            &250,
-            &expiration,
-            &Some(symbol_short!("donor2")),
-        );
-
-        let request_id = client.create_request(
-            &hospital,
-            &BloodType::APositive,
-            &500,
-            &UrgencyLevel::Urgent,
-            &(current_time + 3600),
-            &String::from_str(&env, "Ward A"),
-        );
-
-        let unit_ids = vec![&env, unit_id_1, unit_id_2];
-        env.mock_all_auths();
-        client.approve_request(&bank, &request_id, &unit_ids);
-
-        let requests: Map<u64, BloodRequest> = env.as_contract(&contract_id, || {
-            env.storage()
-                .persistent()
-                .get(&REQUESTS)
-                .unwrap_or(Map::new(&env))
-        });
-        let request = requests.get(request_id).unwrap();
-
-        assert_eq!(request.status, RequestStatus::Approved);
-        assert_eq!(request.fulfilled_quantity_ml, 550);
-        assert_eq!(request.reserved_unit_ids, unit_ids);
-
-        let unit1 = client.get_blood_unit(&unit_id_1);
-        let unit2 = client.get_blood_unit(&unit_id_2);
-        assert_eq!(unit1.status, BloodStatus::Reserved);
-        assert_eq!(unit2.status, BloodStatus::Reserved);
-        assert_eq!(unit1.recipient_hospital, Some(hospital.clone()));
-        assert_eq!(unit2.recipient_hospital, Some(hospital));
+        // Query for a donor that doesn't exist
+        let units = client.get_units_by_donor(&symbol_short!("NOEXIST"));
+        assert_eq!(units.len(), 0);
     }
 
+    /// Test get_units_by_donor with anonymous donor
     #[test]
-    fn test_approve_request_partial_sets_in_progress() {
+    fn test_get_units_by_donor_anonymous() {
         let env = Env::default();
-        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
+        let (_, _, client) = setup_contract_with_admin(&env);
 
         let bank = Address::generate(&env);
         env.mock_all_auths();
@@ -5704,66 +12168,83 @@ mod test {
         let current_time = env.ledger().timestamp();
         let expiration = current_time + (7 * 86400);
 
-        let unit_id = client.register_blood(
+        // Register blood without donor_id (anonymous)
+        env.mock_all_auths();
+        client.register_blood(
             &bank,
-            &BloodType::OPositive,
+            &BloodType::ABPositive,
             &BloodComponent::WholeBlood,
-            &200,
+            &300,
             &expiration,
-            &Some(symbol_short!("donor1")),
+            &None,
+            &None,
         );
 
-        let request_id = client.create_request(
-            &hospital,
-            &BloodType::OPositive,
-            &500,
-            &UrgencyLevel::Urgent,
-            &(current_time + 3600),
-            &String::from_str(&env, "Ward B"),
-        );
+        // Anonymous donors are stored as "ANON"
+        let units = client.get_units_by_donor(&symbol_short!("ANON"));
+        assert_eq!(units.len(), 1);
+    }
 
-        let unit_ids = vec![&env, unit_id];
+    /// Per-bank donor lookups only see the queried bank's units
+    #[test]
+    fn test_get_units_by_bank_donor_scopes_to_bank() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+
+        let bank_a = Address::generate(&env);
+        let bank_b = Address::generate(&env);
         env.mock_all_auths();
-        client.approve_request(&bank, &request_id, &unit_ids);
+        client.register_blood_bank(&bank_a);
+        client.register_blood_bank(&bank_b);
 
-        let requests: Map<u64, BloodRequest> = env.as_contract(&contract_id, || {
-            env.storage()
-                .persistent()
-                .get(&REQUESTS)
-                .unwrap_or(Map::new(&env))
-        });
-        let request = requests.get(request_id).unwrap();
+        let expiration = env.ledger().timestamp() + (7 * 86400);
+        let donor = Some(symbol_short!("001"));
+        let register = |bank: &Address, donor: &Option<Symbol>| {
+            client.register_blood(
+                bank,
+                &BloodType::OPositive,
+                &BloodComponent::WholeBlood,
+                &450,
+                &expiration,
+                donor,
+                &None,
+            )
+        };
 
-        assert_eq!(request.status, RequestStatus::InProgress);
-        assert_eq!(request.fulfilled_quantity_ml, 200);
-    }
+        let a1 = register(&bank_a, &donor);
+        let b1 = register(&bank_b, &donor);
+        let a2 = register(&bank_a, &donor);
+        register(&bank_a, &Some(symbol_short!("002")));
 
-    #[test]
-    #[should_panic(expected = "Error(Contract, #1)")]
-    fn test_approve_request_requires_blood_bank() {
-        let env = Env::default();
-        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
-        let non_bank = Address::generate(&env);
+        let ids = |bank: &Address| {
+            let mut ids = Vec::new(&env);
+            for unit in client
+                .get_units_by_bank_donor(bank, &symbol_short!("001"))
+                .iter()
+            {
+                ids.push_back(unit.id);
+            }
+            ids
+        };
 
-        let request_id = client.create_request(
-            &hospital,
-            &BloodType::BPositive,
-            &300,
-            &UrgencyLevel::Routine,
-            &(env.ledger().timestamp() + 3600),
-            &String::from_str(&env, "Ward C"),
+        assert_eq!(ids(&bank_a), vec![&env, a1, a2]);
+        assert_eq!(ids(&bank_b), vec![&env, b1]);
+        assert_eq!(
+            client
+                .get_units_by_bank_donor(&bank_b, &symbol_short!("002"))
+                .len(),
+            0
         );
-
-        let unit_ids = vec![&env, 1u64];
-        env.mock_all_auths();
-        client.approve_request(&non_bank, &request_id, &unit_ids);
     }
 
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Paginated Custody Trail Tests
+    // ─────────────────────────────────────────────────────────────────────────────
+
     #[test]
-    #[should_panic(expected = "Error(Contract, #28)")] // ArithmeticError
-    fn test_approve_request_fails_on_total_quantity_overflow() {
+    fn test_custody_trail_single_event() {
         let env = Env::default();
-        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
 
         let bank = Address::generate(&env);
         env.mock_all_auths();
@@ -5772,56 +12253,42 @@ mod test {
         let current_time = env.ledger().timestamp();
         let expiration = current_time + (7 * 86400);
 
-        let unit_id_1 = client.add_blood_unit(
-            &BloodType::APositive,
-            &500,
-            &expiration,
-            &symbol_short!("donor1"),
-            &symbol_short!("bank"),
-        );
-        let unit_id_2 = client.add_blood_unit(
-            &BloodType::APositive,
-            &500,
+        // Register and allocate blood
+        env.mock_all_auths();
+        let unit_id = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
             &expiration,
-            &symbol_short!("donor2"),
-            &symbol_short!("bank"),
+            &None,
+            &None,
         );
 
-        env.as_contract(&contract_id, || {
-            let mut units: Map<u64, BloodUnit> = env
-                .storage()
-                .persistent()
-                .get(&BLOOD_UNITS)
-                .unwrap_or(Map::new(&env));
-
-            let mut unit_1 = units.get(unit_id_1).unwrap();
-            unit_1.quantity = u32::MAX;
-            units.set(unit_id_1, unit_1);
+        env.mock_all_auths();
+        client.allocate_blood(&bank, &unit_id, &hospital, &None, &None);
 
-            let mut unit_2 = units.get(unit_id_2).unwrap();
-            unit_2.quantity = 1;
-            units.set(unit_id_2, unit_2);
+        // Initiate transfer
+        env.mock_all_auths();
+        let event_id = client.initiate_transfer(&bank, &unit_id, &None, &None, &None);
 
-            env.storage().persistent().set(&BLOOD_UNITS, &units);
-        });
+        // Confirm transfer
+        env.mock_all_auths();
+        client.confirm_transfer(&hospital, &event_id, &None, &None, &None);
 
-        let request_id = client.create_request(
-            &hospital,
-            &BloodType::APositive,
-            &500,
-            &UrgencyLevel::Urgent,
-            &(current_time + 3600),
-            &String::from_str(&env, "Ward O1"),
-        );
+        // Check custody trail
+        let trail = client.get_custody_trail(&unit_id, &0);
+        assert_eq!(trail.len(), 1);
+        assert_eq!(trail.get(0).unwrap(), event_id);
 
-        let unit_ids = vec![&env, unit_id_1, unit_id_2];
-        env.mock_all_auths();
-        client.approve_request(&bank, &request_id, &unit_ids);
+        // Check metadata
+        let metadata = client.get_custody_trail_metadata(&unit_id);
+        assert_eq!(metadata.total_events, 1);
+        assert_eq!(metadata.total_pages, 1);
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #28)")] // ArithmeticError
-    fn test_approve_request_fails_on_fulfillment_percentage_overflow() {
+    fn test_custody_trail_multiple_events_single_page() {
         let env = Env::default();
         let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
 
@@ -5832,300 +12299,320 @@ mod test {
         let current_time = env.ledger().timestamp();
         let expiration = current_time + (7 * 86400);
 
-        let unit_id = client.add_blood_unit(
+        env.mock_all_auths();
+        let unit_id = client.register_blood(
+            &bank,
             &BloodType::OPositive,
-            &500,
+            &BloodComponent::WholeBlood,
+            &450,
             &expiration,
-            &symbol_short!("donor1"),
-            &symbol_short!("bank"),
+            &None,
+            &None,
         );
 
-        env.as_contract(&contract_id, || {
-            let mut units: Map<u64, BloodUnit> = env
-                .storage()
-                .persistent()
-                .get(&BLOOD_UNITS)
-                .unwrap_or(Map::new(&env));
-
-            let mut unit = units.get(unit_id).unwrap();
-            unit.quantity = u32::MAX;
-            units.set(unit_id, unit);
+        let mut event_ids = vec![&env];
 
-            env.storage().persistent().set(&BLOOD_UNITS, &units);
-        });
+        for i in 0..5 {
+            env.as_contract(&contract_id, || {
+                let mut unit = load_unit(&env, unit_id).unwrap();
+                unit.status = BloodStatus::Reserved;
+                unit.recipient_hospital = Some(hospital.clone());
+                save_unit(&env, &unit);
+            });
 
-        let request_id = client.create_request(
-            &hospital,
-            &BloodType::OPositive,
-            &50,
-            &UrgencyLevel::Routine,
-            &(current_time + 3600),
-            &String::from_str(&env, "Ward O2"),
-        );
+            env.mock_all_auths();
+            let event_id = client.initiate_transfer(&bank, &unit_id, &None, &None, &None);
 
-        let unit_ids = vec![&env, unit_id];
-        env.mock_all_auths();
-        client.approve_request(&bank, &request_id, &unit_ids);
-    }
+            env.ledger().set_timestamp(current_time + (i * 100));
 
-    // Request Status Management Tests
+            env.mock_all_auths();
+            client.confirm_transfer(&hospital, &event_id, &None, &None, &None);
 
-    #[test]
-    fn test_update_request_status_pending_to_approved() {
-        let env = Env::default();
-        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+            event_ids.push_back(event_id.clone());
+        }
 
-        env.mock_all_auths();
-        let current_time = env.ledger().timestamp();
-        let required_by = current_time + 3600;
+        let trail = client.get_custody_trail(&unit_id, &0);
+        assert_eq!(trail.len(), 5);
 
-        let request_id = client.create_request(
-            &hospital,
-            &BloodType::OPositive,
-            &500,
-            &UrgencyLevel::Urgent,
-            &required_by,
-            &String::from_str(&env, "Ward A"),
-        );
+        for i in 0..5 {
+            assert_eq!(trail.get(i).unwrap(), event_ids.get(i).unwrap());
+        }
 
-        env.mock_all_auths();
-        client.update_request_status(&request_id, &RequestStatus::Approved);
+        let metadata = client.get_custody_trail_metadata(&unit_id);
+        assert_eq!(metadata.total_events, 5);
+        assert_eq!(metadata.total_pages, 1);
     }
 
     #[test]
-    fn test_update_request_status_approved_to_in_progress() {
+    fn test_custody_trail_pagination_across_pages() {
         let env = Env::default();
-        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
 
+        let bank = Address::generate(&env);
         env.mock_all_auths();
+        client.register_blood_bank(&bank);
+
         let current_time = env.ledger().timestamp();
-        let required_by = current_time + 3600;
+        let expiration = current_time + (30 * 86400);
 
-        let request_id = client.create_request(
-            &hospital,
+        // Register blood
+        env.mock_all_auths();
+        let unit_id = client.register_blood(
+            &bank,
             &BloodType::OPositive,
-            &500,
-            &UrgencyLevel::Urgent,
-            &required_by,
-            &String::from_str(&env, "Ward A"),
+            &BloodComponent::WholeBlood,
+            &450,
+            &expiration,
+            &None,
+            &None,
         );
 
-        env.mock_all_auths();
-        client.update_request_status(&request_id, &RequestStatus::Approved);
-        env.mock_all_auths();
-        client.update_request_status(&request_id, &RequestStatus::InProgress);
-    }
+        let mut all_event_ids = vec![&env];
 
-    #[test]
-    #[should_panic(expected = "Error(Contract, #10)")] // InvalidTransition
-    fn test_update_request_status_invalid_transition_pending_to_fulfilled() {
-        let env = Env::default();
-        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        // Create 25 custody events (should span 2 pages: 20 + 5)
+        for i in 0..25 {
+            // Manually set unit to Reserved state
+            env.as_contract(&contract_id, || {
+                let mut unit = load_unit(&env, unit_id).unwrap();
+                unit.status = BloodStatus::Reserved;
+                unit.recipient_hospital = Some(hospital.clone());
+                save_unit(&env, &unit);
+            });
 
-        env.mock_all_auths();
-        let current_time = env.ledger().timestamp();
-        let required_by = current_time + 3600;
+            env.mock_all_auths();
+            let event_id = client.initiate_transfer(&bank, &unit_id, &None, &None, &None);
 
-        let request_id = client.create_request(
-            &hospital,
-            &BloodType::OPositive,
-            &500,
-            &UrgencyLevel::Urgent,
-            &required_by,
-            &String::from_str(&env, "Ward A"),
-        );
+            // Advance time slightly
+            env.ledger().set_timestamp(current_time + (i * 100));
 
-        // Try to go directly from Pending to Fulfilled (invalid)
-        client.update_request_status(&request_id, &RequestStatus::Fulfilled);
-    }
+            env.mock_all_auths();
+            client.confirm_transfer(&hospital, &event_id, &None, &None, &None);
 
-    #[test]
-    #[should_panic(expected = "Error(Contract, #10)")] // InvalidTransition
-    fn test_update_request_status_no_transition_from_fulfilled() {
-        let env = Env::default();
-        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+            all_event_ids.push_back(event_id);
+        }
 
-        env.mock_all_auths();
-        let current_time = env.ledger().timestamp();
-        let required_by = current_time + 3600;
+        // Check page 0 - should have 20 events
+        let page_0 = client.get_custody_trail(&unit_id, &0);
+        assert_eq!(page_0.len(), 20);
 
-        let request_id = client.create_request(
-            &hospital,
-            &BloodType::OPositive,
-            &500,
-            &UrgencyLevel::Urgent,
-            &required_by,
-            &String::from_str(&env, "Ward A"),
-        );
+        for i in 0..20 {
+            assert_eq!(page_0.get(i).unwrap(), all_event_ids.get(i).unwrap());
+        }
 
-        client.update_request_status(&request_id, &RequestStatus::Approved);
-        client.update_request_status(&request_id, &RequestStatus::InProgress);
+        // Check page 1 - should have 5 events
+        let page_1 = client.get_custody_trail(&unit_id, &1);
+        assert_eq!(page_1.len(), 5);
 
-        // Manually fulfill by creating a dummy fulfilled state
-        // For this test, we'll use cancel and then try to update cancelled
-        client.cancel_request(&request_id, &String::from_str(&env, "Test"));
+        for i in 0..5 {
+            assert_eq!(page_1.get(i).unwrap(), all_event_ids.get(20 + i).unwrap());
+        }
 
-        // Try to update from Cancelled (terminal state)
-        client.update_request_status(&request_id, &RequestStatus::Pending);
+        // Check metadata
+        let metadata = client.get_custody_trail_metadata(&unit_id);
+        assert_eq!(metadata.total_events, 25);
+        assert_eq!(metadata.total_pages, 2);
     }
 
     #[test]
-    fn test_cancel_request_releases_reservations() {
+    fn test_get_custody_trail_page_crosses_storage_pages() {
         let env = Env::default();
-        let (_, admin, hospital, client) = setup_contract_with_hospital(&env);
+        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
 
-        // Register a blood bank
         let bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
-        // Add blood units
         let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
-
-        let unit_id_1 = client.register_blood(
+        let unit_id = client.register_blood(
             &bank,
             &BloodType::OPositive,
             &BloodComponent::WholeBlood,
-            &250,
-            &expiration,
-            &Some(symbol_short!("donor1")),
+            &450,
+            &(current_time + 7 * 86400),
+            &None,
+            &None,
         );
 
-        let unit_id_2 = client.register_blood(
-            &bank,
-            &BloodType::OPositive,
-            &BloodComponent::WholeBlood,
-            &250,
-            &expiration,
-            &Some(symbol_short!("donor2")),
-        );
+        let mut all_event_ids = vec![&env];
+        for i in 0..25 {
+            env.as_contract(&contract_id, || {
+                let mut unit = load_unit(&env, unit_id).unwrap();
+                unit.status = BloodStatus::Reserved;
+                unit.recipient_hospital = Some(hospital.clone());
+                save_unit(&env, &unit);
+            });
 
-        // Allocate units to hospital
-        client.allocate_blood(&bank, &unit_id_1, &hospital);
-        client.allocate_blood(&bank, &unit_id_2, &hospital);
+            let event_id = client.initiate_transfer(&bank, &unit_id, &None, &None, &None);
+            env.ledger().set_timestamp(current_time + (i * 100));
+            client.confirm_transfer(&hospital, &event_id, &None, &None, &None);
+            all_event_ids.push_back(event_id);
+        }
 
-        // Verify units are Reserved
-        let unit1 = client.get_blood_unit(&unit_id_1);
-        assert_eq!(unit1.status, BloodStatus::Reserved);
+        // A page straddling the 20-event storage boundary
+        let page = client.get_custody_trail_page(&unit_id, &15, &8);
+        assert_eq!(page.items.len(), 8);
+        for i in 0..8 {
+            assert_eq!(
+                page.items.get(i).unwrap(),
+                all_event_ids.get(15 + i).unwrap()
+            );
+        }
+        assert_eq!(page.next_cursor, Some(23));
 
-        // Create request
-        let required_by = current_time + 3600;
-        let request_id = client.create_request(
-            &hospital,
-            &BloodType::OPositive,
-            &500,
-            &UrgencyLevel::Urgent,
-            &required_by,
-            &String::from_str(&env, "Ward A"),
-        );
+        let last = client.get_custody_trail_page(&unit_id, &23, &8);
+        assert_eq!(last.items.len(), 2);
+        assert_eq!(last.next_cursor, None);
 
-        // Cancel the request
-        client.cancel_request(&request_id, &String::from_str(&env, "No longer needed"));
+        // Walking with the default limit visits every event exactly once
+        let mut walked = vec![&env];
+        let mut cursor = Some(0u64);
+        while let Some(next) = cursor {
+            let page = client.get_custody_trail_page(&unit_id, &next, &0);
+            for event_id in page.items.iter() {
+                walked.push_back(event_id);
+            }
+            cursor = page.next_cursor;
+        }
+        assert_eq!(walked, all_event_ids);
 
-        // Verify units are back to Available (if they were in the reserved_unit_ids)
-        // Note: In our implementation, cancel_request releases units that were in reserved_unit_ids
-        // Since we didn't add them to the request, they should still be Reserved
-        // But the cancel function works correctly for units that ARE in the list
+        let past_end = client.get_custody_trail_page(&unit_id, &25, &10);
+        assert_eq!(past_end.items.len(), 0);
+        assert_eq!(past_end.next_cursor, None);
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #6)")] // InvalidStatus
-    fn test_cancel_request_already_fulfilled() {
+    fn test_get_custody_events_pages_full_events_with_total() {
         let env = Env::default();
-        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
 
+        let bank = Address::generate(&env);
         env.mock_all_auths();
-        let current_time = env.ledger().timestamp();
-        let required_by = current_time + 3600;
+        client.register_blood_bank(&bank);
 
-        let request_id = client.create_request(
-            &hospital,
+        let current_time = env.ledger().timestamp();
+        let unit_id = client.register_blood(
+            &bank,
             &BloodType::OPositive,
-            &500,
-            &UrgencyLevel::Urgent,
-            &required_by,
-            &String::from_str(&env, "Ward A"),
+            &BloodComponent::WholeBlood,
+            &450,
+            &(current_time + 7 * 86400),
+            &None,
+            &None,
         );
 
-        // Move to Fulfilled
-        client.update_request_status(&request_id, &RequestStatus::Approved);
-        client.update_request_status(&request_id, &RequestStatus::InProgress);
+        let empty = client.get_custody_events(&unit_id, &0, &10);
+        assert_eq!(empty.items.len(), 0);
+        assert_eq!(empty.total, 0);
 
-        // We can't actually fulfill without blood bank, so let's just cancel an already cancelled
-        client.cancel_request(&request_id, &String::from_str(&env, "First cancel"));
+        let mut all_event_ids = vec![&env];
+        for i in 0..12 {
+            env.as_contract(&contract_id, || {
+                let mut unit = load_unit(&env, unit_id).unwrap();
+                unit.status = BloodStatus::Reserved;
+                unit.recipient_hospital = Some(hospital.clone());
+                save_unit(&env, &unit);
+            });
 
-        // Try to cancel again (should fail because it's already Cancelled)
-        client.cancel_request(&request_id, &String::from_str(&env, "Second cancel"));
+            let event_id = client.initiate_transfer(&bank, &unit_id, &None, &None, &None);
+            env.ledger().set_timestamp(current_time + (i * 100));
+            client.confirm_transfer(&hospital, &event_id, &None, &None, &None);
+            all_event_ids.push_back(event_id);
+        }
+
+        let page = client.get_custody_events(&unit_id, &5, &4);
+        assert_eq!(page.total, 12);
+        assert_eq!(page.items.len(), 4);
+        for i in 0..4 {
+            let event = page.items.get(i).unwrap();
+            assert_eq!(event.event_id, all_event_ids.get(5 + i).unwrap());
+            assert_eq!(event.unit_id, unit_id);
+            assert_eq!(event.status, CustodyStatus::Confirmed);
+        }
+
+        let last = client.get_custody_events(&unit_id, &10, &4);
+        assert_eq!(last.items.len(), 2);
+        assert_eq!(last.total, 12);
+
+        let past_end = client.get_custody_events(&unit_id, &12, &4);
+        assert_eq!(past_end.items.len(), 0);
+        assert_eq!(past_end.total, 12);
     }
 
     #[test]
-    fn test_fulfill_request_updates_inventory() {
+    fn test_custody_root_commits_and_verifies_confirmed_chain() {
+        use soroban_sdk::BytesN;
+
         let env = Env::default();
-        let (_, admin, hospital, client) = setup_contract_with_hospital(&env);
+        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
 
-        // Register a blood bank
         let bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
-        // Add blood units
         let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
-
-        let unit_id_1 = client.register_blood(
-            &bank,
-            &BloodType::APositive,
-            &BloodComponent::WholeBlood,
-            &250,
-            &expiration,
-            &Some(symbol_short!("donor1")),
-        );
-
-        let unit_id_2 = client.register_blood(
+        let unit_id = client.register_blood(
             &bank,
-            &BloodType::APositive,
+            &BloodType::OPositive,
             &BloodComponent::WholeBlood,
-            &250,
-            &expiration,
-            &Some(symbol_short!("donor2")),
+            &450,
+            &(current_time + 7 * 86400),
+            &None,
+            &None,
         );
 
-        // Allocate units to hospital
-        client.allocate_blood(&bank, &unit_id_1, &hospital);
-        client.allocate_blood(&bank, &unit_id_2, &hospital);
+        let result = client.try_commit_custody_root(&unit_id);
+        assert_eq!(result, Err(Ok(Error::InvalidStatus)));
+        let result = client.try_commit_custody_root(&(unit_id + 1));
+        assert_eq!(result, Err(Ok(Error::UnitNotFound)));
 
-        // Create request
-        let required_by = current_time + 3600;
-        let request_id = client.create_request(
-            &hospital,
-            &BloodType::APositive,
-            &500,
-            &UrgencyLevel::Urgent,
-            &required_by,
-            &String::from_str(&env, "Ward B"),
-        );
+        let mut leaves = vec![&env];
+        for i in 0..3 {
+            env.as_contract(&contract_id, || {
+                let mut unit = load_unit(&env, unit_id).unwrap();
+                unit.status = BloodStatus::Reserved;
+                unit.recipient_hospital = Some(hospital.clone());
+                save_unit(&env, &unit);
+            });
 
-        // Approve and start progress
-        client.update_request_status(&request_id, &RequestStatus::Approved);
+            env.ledger().with_mut(|li| li.sequence_number += 1);
+            let event_id = client.initiate_transfer(&bank, &unit_id, &None, &None, &None);
+            env.ledger().set_timestamp(current_time + (i * 100));
+            client.confirm_transfer(&hospital, &event_id, &None, &None, &None);
+            let event = client.get_custody_event(&event_id);
+            leaves.push_back(
+                env.as_contract(&contract_id, || custody_proof::leaf_hash(&env, &event)),
+            );
+        }
 
-        // Fulfill the request
-        let unit_ids = vec![&env, unit_id_1, unit_id_2];
-        env.mock_all_auths();
-        client.fulfill_request(&bank, &request_id, &unit_ids);
+        let proof = vec![&env, leaves.get(1).unwrap(), leaves.get(2).unwrap()];
+        let result = client.try_verify_custody_proof(&unit_id, &leaves.get(0).unwrap(), &proof);
+        assert_eq!(result, Err(Ok(Error::CustodyRootNotFound)));
 
-        // Verify units are Delivered
-        let unit1 = client.get_blood_unit(&unit_id_1);
-        assert_eq!(unit1.status, BloodStatus::Delivered);
-        assert!(unit1.delivery_timestamp.is_some());
+        let root = client.commit_custody_root(&unit_id);
+        let committed = client.get_custody_root(&unit_id).unwrap();
+        assert_eq!(committed.root, root);
+        assert_eq!(committed.leaf_count, 3);
 
-        let unit2 = client.get_blood_unit(&unit_id_2);
-        assert_eq!(unit2.status, BloodStatus::Delivered);
-        assert!(unit2.delivery_timestamp.is_some());
+        // Three leaves: the third is carried up and paired with hash(l0, l1)
+        assert!(client.verify_custody_proof(&unit_id, &leaves.get(0).unwrap(), &proof));
+        let pair = env.as_contract(&contract_id, || {
+            custody_proof::merkle_root(
+                &env,
+                &vec![&env, leaves.get(0).unwrap(), leaves.get(1).unwrap()],
+            )
+        });
+        let proof_for_last = vec![&env, pair];
+        assert!(client.verify_custody_proof(&unit_id, &leaves.get(2).unwrap(), &proof_for_last));
+
+        // A leaf not in the chain, or a truncated proof, does not verify
+        let foreign = BytesN::from_array(&env, &[1u8; 32]);
+        assert!(!client.verify_custody_proof(&unit_id, &foreign, &proof));
+        let truncated = vec![&env, leaves.get(1).unwrap()];
+        assert!(!client.verify_custody_proof(&unit_id, &leaves.get(0).unwrap(), &truncated));
     }
 
     #[test]
-    fn test_fulfill_request_rejects_over_delivery_before_mutation() {
+    fn test_custody_trail_100_events() {
         let env = Env::default();
         let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
 
@@ -6134,63 +12621,53 @@ mod test {
         client.register_blood_bank(&bank);
 
         let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
+        let expiration = current_time + (30 * 86400);
 
-        let unit_id_1 = client.register_blood(
-            &bank,
-            &BloodType::APositive,
-            &BloodComponent::WholeBlood,
-            &300,
-            &expiration,
-            &Some(symbol_short!("over1")),
-        );
-        let unit_id_2 = client.register_blood(
+        env.mock_all_auths();
+        let unit_id = client.register_blood(
             &bank,
-            &BloodType::APositive,
+            &BloodType::OPositive,
             &BloodComponent::WholeBlood,
-            &300,
+            &450,
             &expiration,
-            &Some(symbol_short!("over2")),
+            &None,
+            &None,
         );
 
-        client.allocate_blood(&bank, &unit_id_1, &hospital);
-        client.allocate_blood(&bank, &unit_id_2, &hospital);
+        for i in 0..100 {
+            env.as_contract(&contract_id, || {
+                let mut unit = load_unit(&env, unit_id).unwrap();
+                unit.status = BloodStatus::Reserved;
+                unit.recipient_hospital = Some(hospital.clone());
+                save_unit(&env, &unit);
+            });
 
-        let request_id = client.create_request(
-            &hospital,
-            &BloodType::APositive,
-            &500,
-            &UrgencyLevel::Urgent,
-            &(current_time + 3600),
-            &String::from_str(&env, "Ward O"),
-        );
-        client.update_request_status(&request_id, &RequestStatus::Approved);
+            env.mock_all_auths();
+            let event_id = client.initiate_transfer(&bank, &unit_id, &None, &None, &None);
 
-        let unit_ids = vec![&env, unit_id_1, unit_id_2];
-        let result = client.try_fulfill_request(&bank, &request_id, &unit_ids);
-        assert!(matches!(result, Err(Ok(Error::InvalidQuantity))));
+            env.ledger().set_timestamp(current_time + (i * 100));
 
-        let unit1 = client.get_blood_unit(&unit_id_1);
-        let unit2 = client.get_blood_unit(&unit_id_2);
-        assert_eq!(unit1.status, BloodStatus::Reserved);
-        assert_eq!(unit2.status, BloodStatus::Reserved);
-        assert!(unit1.delivery_timestamp.is_none());
-        assert!(unit2.delivery_timestamp.is_none());
+            env.mock_all_auths();
+            client.confirm_transfer(&hospital, &event_id, &None, &None, &None);
+        }
 
-        let request: BloodRequest = env.as_contract(&contract_id, || {
-            let requests: Map<u64, BloodRequest> =
-                env.storage().persistent().get(&REQUESTS).unwrap();
-            requests.get(request_id).unwrap()
-        });
-        assert_eq!(request.status, RequestStatus::Approved);
-        assert_eq!(request.fulfilled_quantity_ml, 0);
-        assert!(request.fulfillment_timestamp.is_none());
+        let metadata = client.get_custody_trail_metadata(&unit_id);
+        assert_eq!(metadata.total_events, 100);
+        assert_eq!(metadata.total_pages, 5);
+
+        for page_num in 0..5 {
+            let page = client.get_custody_trail(&unit_id, &page_num);
+            assert_eq!(page.len(), 20);
+        }
+
+        let result = client.try_get_custody_trail(&unit_id, &5);
+        assert_eq!(result, Err(Error::PageNotFound));
     }
 
     #[test]
-    fn test_fulfill_request_exact_delivery_marks_fulfilled() {
+    fn test_custody_trail_empty_for_new_unit() {
         let env = Env::default();
-        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
+        let (_, _, _, client) = setup_contract_with_hospital(&env);
 
         let bank = Address::generate(&env);
         env.mock_all_auths();
@@ -6199,53 +12676,32 @@ mod test {
         let current_time = env.ledger().timestamp();
         let expiration = current_time + (7 * 86400);
 
-        let unit_id_1 = client.register_blood(
-            &bank,
-            &BloodType::ONegative,
-            &BloodComponent::WholeBlood,
-            &250,
-            &expiration,
-            &Some(symbol_short!("exact1")),
-        );
-        let unit_id_2 = client.register_blood(
+        // Register blood but don't create any custody events
+        env.mock_all_auths();
+        let unit_id = client.register_blood(
             &bank,
-            &BloodType::ONegative,
+            &BloodType::OPositive,
             &BloodComponent::WholeBlood,
-            &250,
+            &450,
             &expiration,
-            &Some(symbol_short!("exact2")),
-        );
-
-        client.allocate_blood(&bank, &unit_id_1, &hospital);
-        client.allocate_blood(&bank, &unit_id_2, &hospital);
-
-        let request_id = client.create_request(
-            &hospital,
-            &BloodType::ONegative,
-            &500,
-            &UrgencyLevel::Urgent,
-            &(current_time + 3600),
-            &String::from_str(&env, "Ward E"),
+            &None,
+            &None,
         );
-        client.update_request_status(&request_id, &RequestStatus::Approved);
 
-        let unit_ids = vec![&env, unit_id_1, unit_id_2];
-        client.fulfill_request(&bank, &request_id, &unit_ids);
+        // Check custody trail - should be empty
+        let trail = client.get_custody_trail(&unit_id, &0);
+        assert_eq!(trail.len(), 0);
 
-        let request: BloodRequest = env.as_contract(&contract_id, || {
-            let requests: Map<u64, BloodRequest> =
-                env.storage().persistent().get(&REQUESTS).unwrap();
-            requests.get(request_id).unwrap()
-        });
-        assert_eq!(request.status, RequestStatus::Fulfilled);
-        assert_eq!(request.fulfilled_quantity_ml, 500);
-        assert!(request.fulfillment_timestamp.is_some());
+        // Check metadata - should show 0 events and 0 pages
+        let metadata = client.get_custody_trail_metadata(&unit_id);
+        assert_eq!(metadata.total_events, 0);
+        assert_eq!(metadata.total_pages, 0);
     }
 
     #[test]
-    fn test_fulfill_request_partial_delivery_remains_in_progress() {
+    fn test_custody_trail_non_existent_page() {
         let env = Env::default();
-        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
 
         let bank = Address::generate(&env);
         env.mock_all_auths();
@@ -6254,102 +12710,104 @@ mod test {
         let current_time = env.ledger().timestamp();
         let expiration = current_time + (7 * 86400);
 
+        // Register and create one custody event
+        env.mock_all_auths();
         let unit_id = client.register_blood(
             &bank,
-            &BloodType::BPositive,
+            &BloodType::OPositive,
             &BloodComponent::WholeBlood,
-            &250,
+            &450,
             &expiration,
-            &Some(symbol_short!("part1")),
+            &None,
+            &None,
         );
 
-        client.allocate_blood(&bank, &unit_id, &hospital);
-
-        let request_id = client.create_request(
-            &hospital,
-            &BloodType::BPositive,
-            &500,
-            &UrgencyLevel::Urgent,
-            &(current_time + 3600),
-            &String::from_str(&env, "Ward P"),
-        );
-        client.update_request_status(&request_id, &RequestStatus::Approved);
+        env.mock_all_auths();
+        client.allocate_blood(&bank, &unit_id, &hospital, &None, &None);
 
-        let unit_ids = vec![&env, unit_id];
-        client.fulfill_request(&bank, &request_id, &unit_ids);
+        env.mock_all_auths();
+        let event_id = client.initiate_transfer(&bank, &unit_id, &None, &None, &None);
 
-        let unit = client.get_blood_unit(&unit_id);
-        assert_eq!(unit.status, BloodStatus::Delivered);
-        assert!(unit.delivery_timestamp.is_some());
+        env.mock_all_auths();
+        client.confirm_transfer(&hospital, &event_id, &None, &None, &None);
 
-        let request: BloodRequest = env.as_contract(&contract_id, || {
-            let requests: Map<u64, BloodRequest> =
-                env.storage().persistent().get(&REQUESTS).unwrap();
-            requests.get(request_id).unwrap()
-        });
-        assert_eq!(request.status, RequestStatus::InProgress);
-        assert_eq!(request.fulfilled_quantity_ml, 250);
-        assert!(request.fulfillment_timestamp.is_none());
+        // Query for page 10 (doesn't exist)
+        let result = client.try_get_custody_trail(&unit_id, &10);
+        assert_eq!(result, Err(Error::PageNotFound));
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #6)")] // InvalidStatus
-    fn test_fulfill_request_invalid_status_pending() {
+    fn test_migrate_trail_index() {
         let env = Env::default();
-        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let (_, admin, _, client) = setup_contract_with_hospital(&env);
 
-        env.mock_all_auths();
         let bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
         let current_time = env.ledger().timestamp();
-        let required_by = current_time + 3600;
+        let expiration = current_time + (7 * 86400);
 
-        let request_id = client.create_request(
-            &hospital,
+        // Register blood
+        env.mock_all_auths();
+        let unit_id = client.register_blood(
+            &bank,
             &BloodType::OPositive,
-            &500,
-            &UrgencyLevel::Urgent,
-            &required_by,
-            &String::from_str(&env, "Ward A"),
+            &BloodComponent::WholeBlood,
+            &450,
+            &expiration,
+            &None,
+            &None,
         );
 
-        let unit_ids = vec![&env, 1u64];
+        // Migrate (should initialize empty metadata)
         env.mock_all_auths();
-        client.fulfill_request(&bank, &request_id, &unit_ids);
+        client.migrate_trail_index(&unit_id);
+
+        // Check metadata was created
+        let metadata = client.get_custody_trail_metadata(&unit_id);
+        assert_eq!(metadata.total_events, 0);
+        assert_eq!(metadata.total_pages, 0);
+
+        // Calling migrate again should be idempotent
+        env.mock_all_auths();
+        client.migrate_trail_index(&unit_id);
+
+        let metadata_after = client.get_custody_trail_metadata(&unit_id);
+        assert_eq!(metadata_after.total_events, 0);
+        assert_eq!(metadata_after.total_pages, 0);
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1)")] // Unauthorized
-    fn test_fulfill_request_unauthorized_non_bank() {
+    fn test_migrate_trail_index_unauthorized() {
         let env = Env::default();
-        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let (_, _, _, client) = setup_contract_with_hospital(&env);
 
+        let bank = Address::generate(&env);
         env.mock_all_auths();
+        client.register_blood_bank(&bank);
+
         let current_time = env.ledger().timestamp();
-        let required_by = current_time + 3600;
+        let expiration = current_time + (7 * 86400);
 
-        let request_id = client.create_request(
-            &hospital,
+        env.mock_all_auths();
+        let unit_id = client.register_blood(
+            &bank,
             &BloodType::OPositive,
-            &500,
-            &UrgencyLevel::Urgent,
-            &required_by,
-            &String::from_str(&env, "Ward A"),
+            &BloodComponent::WholeBlood,
+            &450,
+            &expiration,
+            &None,
+            &None,
         );
 
-        client.update_request_status(&request_id, &RequestStatus::Approved);
-
-        // Try to fulfill as non-bank (hospital cannot fulfill)
-        let unit_ids = vec![&env, 1u64];
-        env.mock_all_auths();
-        client.fulfill_request(&hospital, &request_id, &unit_ids);
+        // With mock_all_auths, this will succeed even without admin
+        // This test documents that behavior
+        client.migrate_trail_index(&unit_id);
     }
 
-    #[test]
-    #[should_panic(expected = "Error(Contract, #28)")] // ArithmeticError
-    fn test_fulfill_request_fails_on_delivered_quantity_overflow() {
+    #[test]
+    fn test_custody_trail_storage_size_within_limits() {
         let env = Env::default();
         let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
 
@@ -6358,453 +12816,557 @@ mod test {
         client.register_blood_bank(&bank);
 
         let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
+        let expiration = current_time + (30 * 86400);
 
-        let unit_id_1 = client.register_blood(
-            &bank,
-            &BloodType::BPositive,
-            &BloodComponent::WholeBlood,
-            &250,
-            &expiration,
-            &Some(symbol_short!("d1")),
-        );
-        let unit_id_2 = client.register_blood(
+        env.mock_all_auths();
+        let unit_id = client.register_blood(
             &bank,
-            &BloodType::BPositive,
+            &BloodType::OPositive,
             &BloodComponent::WholeBlood,
-            &250,
+            &450,
             &expiration,
-            &Some(symbol_short!("d2")),
+            &None,
+            &None,
         );
 
-        // Reserve units for the hospital so fulfill_request can proceed past the
-        // recipient_hospital check and reach the arithmetic overflow guard.
-        client.allocate_blood(&bank, &unit_id_1, &hospital);
-        client.allocate_blood(&bank, &unit_id_2, &hospital);
+        for i in 0..20 {
+            env.as_contract(&contract_id, || {
+                let mut unit = load_unit(&env, unit_id).unwrap();
+                unit.status = BloodStatus::Reserved;
+                unit.recipient_hospital = Some(hospital.clone());
+                save_unit(&env, &unit);
+            });
 
-        let request_id = client.create_request(
-            &hospital,
-            &BloodType::BPositive,
-            &500,
-            &UrgencyLevel::Urgent,
-            &(current_time + 3600),
-            &String::from_str(&env, "Ward F1"),
-        );
+            env.mock_all_auths();
+            let event_id = client.initiate_transfer(&bank, &unit_id, &None, &None, &None);
+
+            env.ledger().set_timestamp(current_time + (i * 100));
+
+            env.mock_all_auths();
+            client.confirm_transfer(&hospital, &event_id, &None, &None, &None);
+        }
+
+        let page = client.get_custody_trail(&unit_id, &0);
+        assert_eq!(page.len(), 20);
+
+        let metadata = client.get_custody_trail_metadata(&unit_id);
+        assert_eq!(metadata.total_events, 20);
+        assert_eq!(metadata.total_pages, 1);
+    }
+
+    // ── SUPER ADMIN NOMINATION TESTS (#111) ────────────────────────────────────────────────────
+
+    #[test]
+    fn test_super_admin_successful_transfer() {
+        let env = Env::default();
+        let (_, admin, client) = setup_contract_with_admin(&env);
+        let new_admin = Address::generate(&env);
 
-        let unit_ids = vec![&env, unit_id_1, unit_id_2];
         env.mock_all_auths();
-        client.update_request_status(&request_id, &RequestStatus::Approved);
+        client.nominate_super_admin(&new_admin);
 
-        env.as_contract(&contract_id, || {
-            let mut units: Map<u64, BloodUnit> = env
-                .storage()
-                .persistent()
-                .get(&BLOOD_UNITS)
-                .unwrap_or(Map::new(&env));
+        env.mock_all_auths();
+        client.accept_super_admin();
 
-            let mut unit_1 = units.get(unit_id_1).unwrap();
-            unit_1.quantity = u32::MAX;
-            units.set(unit_id_1, unit_1);
+        // New admin can now register a blood bank (proves they hold the admin role).
+        let bank = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+        assert!(client.is_blood_bank(&bank));
+    }
 
-            let mut unit_2 = units.get(unit_id_2).unwrap();
-            unit_2.quantity = 1;
-            units.set(unit_id_2, unit_2);
+    #[test]
+    #[should_panic(expected = "Error(Contract, #23)")]
+    fn test_accept_super_admin_after_expiry_returns_nomination_expired() {
+        let env = Env::default();
+        env.ledger().with_mut(|li| li.timestamp = 1_000_000);
 
-            env.storage().persistent().set(&BLOOD_UNITS, &units);
-        });
+        let (_, _admin, client) = setup_contract_with_admin(&env);
+        let new_admin = Address::generate(&env);
 
         env.mock_all_auths();
-        client.fulfill_request(&bank, &request_id, &unit_ids);
+        client.nominate_super_admin(&new_admin);
+
+        // Advance past the 24-hour expiry window.
+        env.ledger()
+            .with_mut(|li| li.timestamp = 1_000_000 + NOMINATION_EXPIRY_SECONDS + 1);
+
+        env.mock_all_auths();
+        client.accept_super_admin();
     }
 
     #[test]
-    fn test_status_transition_pending_to_rejected() {
+    fn test_cancel_nomination_allows_immediate_re_nomination() {
         let env = Env::default();
-        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let (_, _admin, client) = setup_contract_with_admin(&env);
+        let nominee_a = Address::generate(&env);
+        let nominee_b = Address::generate(&env);
 
         env.mock_all_auths();
-        let current_time = env.ledger().timestamp();
-        let required_by = current_time + 3600;
+        client.nominate_super_admin(&nominee_a);
 
-        let request_id = client.create_request(
-            &hospital,
-            &BloodType::OPositive,
-            &500,
-            &UrgencyLevel::Low,
-            &required_by,
-            &String::from_str(&env, "Ward A"),
-        );
+        // Cancel the pending nomination.
+        env.mock_all_auths();
+        client.cancel_nomination();
 
+        // Should be able to nominate a different address immediately.
         env.mock_all_auths();
-        client.update_request_status(&request_id, &RequestStatus::Rejected);
+        client.nominate_super_admin(&nominee_b);
+
+        // nominee_b can accept.
+        env.mock_all_auths();
+        client.accept_super_admin();
     }
 
     #[test]
-    fn test_status_transition_approved_to_cancelled() {
+    fn test_expired_nomination_replaced_by_new_nomination() {
         let env = Env::default();
-        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        env.ledger().with_mut(|li| li.timestamp = 1_000_000);
+
+        let (_, _admin, client) = setup_contract_with_admin(&env);
+        let nominee_a = Address::generate(&env);
+        let nominee_b = Address::generate(&env);
 
         env.mock_all_auths();
-        let current_time = env.ledger().timestamp();
-        let required_by = current_time + 3600;
+        client.nominate_super_admin(&nominee_a);
 
-        let request_id = client.create_request(
-            &hospital,
-            &BloodType::OPositive,
-            &500,
-            &UrgencyLevel::Urgent,
-            &required_by,
-            &String::from_str(&env, "Ward A"),
-        );
+        // Let the nomination expire.
+        env.ledger()
+            .with_mut(|li| li.timestamp = 1_000_000 + NOMINATION_EXPIRY_SECONDS + 1);
 
+        // A new nomination should succeed without error (lazy clear of expired entry).
         env.mock_all_auths();
-        client.update_request_status(&request_id, &RequestStatus::Approved);
+        client.nominate_super_admin(&nominee_b);
 
+        // nominee_b can accept.
         env.mock_all_auths();
-        client.cancel_request(&request_id, &String::from_str(&env, "Changed requirements"));
+        client.accept_super_admin();
     }
 
     #[test]
-    fn test_status_transition_in_progress_to_fulfilled() {
+    #[should_panic(expected = "Error(Contract, #24)")]
+    fn test_second_nomination_while_active_returns_nomination_pending() {
         let env = Env::default();
-        let (_, _admin, hospital, client) = setup_contract_with_hospital(&env);
+        let (_, _admin, client) = setup_contract_with_admin(&env);
+        let nominee_a = Address::generate(&env);
+        let nominee_b = Address::generate(&env);
 
-        let bank = Address::generate(&env);
         env.mock_all_auths();
-        client.register_blood_bank(&bank);
+        client.nominate_super_admin(&nominee_a);
 
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
+        // Second nomination while the first is still active must fail.
+        env.mock_all_auths();
+        client.nominate_super_admin(&nominee_b);
+    }
 
-        let unit_id = client.register_blood(
-            &bank,
-            &BloodType::BPositive,
-            &BloodComponent::WholeBlood,
-            &500,
-            &expiration,
-            &Some(symbol_short!("donor1")),
-        );
+    #[test]
+    fn test_nominate_super_admin_emits_admin_proposed_event() {
+        let env = Env::default();
+        env.ledger().with_mut(|li| li.timestamp = 1_000_000);
+        let (_, _admin, client) = setup_contract_with_admin(&env);
+        let nominee = Address::generate(&env);
 
         env.mock_all_auths();
-        client.allocate_blood(&bank, &unit_id, &hospital);
+        client.nominate_super_admin(&nominee);
 
-        let required_by = current_time + 3600;
-        let request_id = client.create_request(
-            &hospital,
-            &BloodType::BPositive,
-            &500,
-            &UrgencyLevel::Critical,
-            &required_by,
-            &String::from_str(&env, "ER"),
+        let events = env.events().all();
+        // Find the admin.proposed event (last event emitted).
+        let (_, topics, data) = events.last().unwrap();
+        assert_eq!(
+            Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap(),
+            symbol_short!("admin")
         );
+        assert_eq!(
+            Symbol::try_from_val(&env, &topics.get(1).unwrap()).unwrap(),
+            symbol_short!("proposed")
+        );
+        let event = AdminProposedEvent::try_from_val(&env, &data).unwrap();
+        assert_eq!(event.proposed_admin, nominee);
+        assert_eq!(event.nominated_at, 1_000_000);
+        assert_eq!(event.expires_at, 1_000_000 + NOMINATION_EXPIRY_SECONDS);
+    }
+
+    #[test]
+    fn test_accept_super_admin_emits_admin_transferred_event() {
+        let env = Env::default();
+        env.ledger().with_mut(|li| li.timestamp = 1_000_000);
+        let (_, admin, client) = setup_contract_with_admin(&env);
+        let nominee = Address::generate(&env);
 
         env.mock_all_auths();
-        client.update_request_status(&request_id, &RequestStatus::Approved);
-        env.mock_all_auths();
-        client.update_request_status(&request_id, &RequestStatus::InProgress);
+        client.nominate_super_admin(&nominee);
 
-        let unit_ids = vec![&env, unit_id];
         env.mock_all_auths();
-        client.fulfill_request(&bank, &request_id, &unit_ids);
+        client.accept_super_admin();
 
-        let unit = client.get_blood_unit(&unit_id);
-        assert_eq!(unit.status, BloodStatus::Delivered);
+        let events = env.events().all();
+        let (_, topics, data) = events.last().unwrap();
+        assert_eq!(
+            Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap(),
+            symbol_short!("admin")
+        );
+        assert_eq!(
+            Symbol::try_from_val(&env, &topics.get(1).unwrap()).unwrap(),
+            symbol_short!("xfer")
+        );
+        let event = AdminTransferredEvent::try_from_val(&env, &data).unwrap();
+        assert_eq!(event.previous_admin, admin);
+        assert_eq!(event.new_admin, nominee);
+        assert_eq!(event.transferred_at, 1_000_000);
     }
 
     #[test]
-    fn test_cancel_request_emits_event_with_reason() {
+    fn test_cancel_nomination_emits_cancelled_event() {
         let env = Env::default();
-        let (_contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
+        env.ledger().with_mut(|li| li.timestamp = 1_000_000);
+        let (_, _admin, client) = setup_contract_with_admin(&env);
+        let nominee = Address::generate(&env);
 
         env.mock_all_auths();
-        let current_time = env.ledger().timestamp();
-        let required_by = current_time + 3600;
+        client.nominate_super_admin(&nominee);
 
-        let request_id = client.create_request(
-            &hospital,
-            &BloodType::OPositive,
-            &500,
-            &UrgencyLevel::Urgent,
-            &required_by,
-            &String::from_str(&env, "Ward A"),
+        env.mock_all_auths();
+        client.cancel_nomination();
+
+        let events = env.events().all();
+        let (_, topics, data) = events.last().unwrap();
+        assert_eq!(
+            Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap(),
+            symbol_short!("admin")
+        );
+        assert_eq!(
+            Symbol::try_from_val(&env, &topics.get(1).unwrap()).unwrap(),
+            symbol_short!("nom_cxl")
         );
+        let event = AdminNominationCancelledEvent::try_from_val(&env, &data).unwrap();
+        assert_eq!(event.cancelled_nominee, nominee);
+    }
+
+    #[test]
+    fn test_cancel_nomination_no_op_when_no_pending_nomination() {
+        let env = Env::default();
+        let (_, _admin, client) = setup_contract_with_admin(&env);
 
-        let cancel_reason = String::from_str(&env, "Patient condition improved");
         env.mock_all_auths();
-        client.cancel_request(&request_id, &cancel_reason);
+        // Should succeed without error even when nothing is pending.
+        client.cancel_nomination();
     }
 
     #[test]
-    fn test_cancel_request_emits_structured_cancellation_event() {
+    fn test_propose_admin_alias_works_and_emits_event() {
         let env = Env::default();
-        let (_contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
+        env.ledger().with_mut(|li| li.timestamp = 2_000_000);
+        let (_, _admin, client) = setup_contract_with_admin(&env);
+        let new_admin = Address::generate(&env);
 
-        // Register blood bank
-        let bank = Address::generate(&env);
         env.mock_all_auths();
-        client.register_blood_bank(&bank);
-
-        // Create and allocate blood units
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
+        client.propose_admin(&new_admin);
 
-        let unit_id1 = client.register_blood(
-            &bank,
-            &BloodType::OPositive,
-            &BloodComponent::WholeBlood,
-            &450,
-            &expiration,
-            &Some(symbol_short!("donor1")),
+        let events = env.events().all();
+        let (_, topics, _) = events.last().unwrap();
+        assert_eq!(
+            Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap(),
+            symbol_short!("admin")
         );
-
-        let unit_id2 = client.register_blood(
-            &bank,
-            &BloodType::OPositive,
-            &BloodComponent::WholeBlood,
-            &450,
-            &expiration,
-            &Some(symbol_short!("donor2")),
+        assert_eq!(
+            Symbol::try_from_val(&env, &topics.get(1).unwrap()).unwrap(),
+            symbol_short!("proposed")
         );
+    }
 
-        env.mock_all_auths();
-        client.allocate_blood(&bank, &unit_id1, &hospital);
+    #[test]
+    fn test_accept_admin_alias_completes_transfer() {
+        let env = Env::default();
+        let (_, _admin, client) = setup_contract_with_admin(&env);
+        let new_admin = Address::generate(&env);
 
         env.mock_all_auths();
-        client.allocate_blood(&bank, &unit_id2, &hospital);
-
-        // Create request that reserves the units
-        let required_by = current_time + 3600;
-        let request_id = client.create_request(
-            &hospital,
-            &BloodType::OPositive,
-            &900,
-            &UrgencyLevel::Urgent,
-            &required_by,
-            &String::from_str(&env, "Ward A"),
-        );
+        client.propose_admin(&new_admin);
 
-        // Approve request to move to next state
         env.mock_all_auths();
-        client.update_request_status(&request_id, &RequestStatus::Approved);
+        client.accept_admin();
 
-        // Cancel request and verify event is emitted with released units
-        let cancel_reason = String::from_str(&env, "Patient condition improved");
+        // Verify new admin can exercise admin-only functions.
+        let bank = Address::generate(&env);
         env.mock_all_auths();
-        client.cancel_request(&request_id, &cancel_reason);
-
-        // ACCEPTANCE: Event emitted with explicit unit release information
-        // Backend consumers can rebuild inventory state from the event:
-        // - Released units returned to Available status
-        // - No need for separate polling queries
-        // - Full audit context in single event
+        client.register_blood_bank(&bank);
+        assert!(client.is_blood_bank(&bank));
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #7)")] // UnitNotFound (used for request not found)
-    fn test_update_status_nonexistent_request() {
+    fn test_emergency_reinitialize_rotates_admin_via_recovery_address() {
         let env = Env::default();
-        let (_, _, _, client) = setup_contract_with_hospital(&env);
+        let contract_id = env.register(HealthChainContract, ());
+        let client = HealthChainContractClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        let recovery = Address::generate(&env);
+        let new_admin = Address::generate(&env);
 
         env.mock_all_auths();
+        client.initialize_with_recovery(&admin, &recovery);
+        client.nominate_super_admin(&Address::generate(&env));
 
-        // Try to update status of non-existent request
-        client.update_request_status(&999u64, &RequestStatus::Approved);
+        client.emergency_reinitialize(&new_admin, &recovery);
+
+        // Only the rotated admin passes explicit admin checks now.
+        assert_eq!(
+            client.try_set_allow_backdated(&admin, &true),
+            Err(Ok(Error::Unauthorized))
+        );
+        client.set_allow_backdated(&new_admin, &true);
+        assert!(client.is_backdating_allowed());
+
+        // The stale nomination was discarded during recovery.
+        assert_eq!(
+            client.try_accept_super_admin(),
+            Err(Ok(Error::Unauthorized))
+        );
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #7)")] // UnitNotFound
-    fn test_cancel_nonexistent_request() {
+    fn test_emergency_reinitialize_without_recovery_address_reverts() {
         let env = Env::default();
-        let (_, _, _, client) = setup_contract_with_hospital(&env);
+        let (_, admin, client) = setup_contract_with_admin(&env);
+        let attacker = Address::generate(&env);
 
         env.mock_all_auths();
+        let result = client.try_emergency_reinitialize(&attacker, &attacker);
+        assert_eq!(result, Err(Ok(Error::RecoveryNotConfigured)));
 
-        // Try to cancel non-existent request
-        client.cancel_request(&999u64, &String::from_str(&env, "Test"));
+        // Original admin is untouched.
+        client.set_allow_backdated(&admin, &true);
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #7)")] // UnitNotFound
-    fn test_fulfill_nonexistent_request() {
+    fn test_emergency_reinitialize_rejects_wrong_proof() {
         let env = Env::default();
-        let (_, _, _, client) = setup_contract_with_hospital(&env);
+        let contract_id = env.register(HealthChainContract, ());
+        let client = HealthChainContractClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        let recovery = Address::generate(&env);
+        let attacker = Address::generate(&env);
 
-        let bank = Address::generate(&env);
         env.mock_all_auths();
-        client.register_blood_bank(&bank);
+        client.initialize_with_recovery(&admin, &recovery);
 
-        let unit_ids = vec![&env, 1u64];
-        env.mock_all_auths();
-        client.fulfill_request(&bank, &999u64, &unit_ids);
+        let result = client.try_emergency_reinitialize(&attacker, &attacker);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        // Re-initializing cannot swap in the attacker as recovery address
+        let result = client.try_initialize_with_recovery(&attacker, &attacker);
+        assert_eq!(result, Err(Ok(Error::AlreadyInitialized)));
+        let result = client.try_emergency_reinitialize(&attacker, &attacker);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
     }
 
-    // ======================================================
-    // Custodian Check Tests (#101)
-    // ======================================================
+    // ── STALE RESERVATION SWEEP TESTS ────────────────────────────────────────────
 
     #[test]
-    fn test_initiate_transfer_by_current_custodian_succeeds() {
+    fn test_release_stale_reservations_batch_releases_only_stale_units() {
         let env = Env::default();
         let (_, _, hospital, client) = setup_contract_with_hospital(&env);
-
         let bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
-        let unit_id = client.register_blood(
-            &bank,
-            &BloodType::OPositive,
-            &BloodComponent::WholeBlood,
-            &450,
-            &expiration,
-            &None,
-        );
-        client.allocate_blood(&bank, &unit_id, &hospital);
+        let base = 1_000_000u64;
+        let (in_transit, _) = setup_in_transit_unit(&env, &client, &bank, &hospital, base);
 
-        // Current custodian (bank) can initiate transfer
-        let event_id = client.initiate_transfer(&bank, &unit_id);
-        assert!(!event_id.is_empty());
-    }
+        let register = || {
+            client.register_blood(
+                &bank,
+                &BloodType::OPositive,
+                &BloodComponent::WholeBlood,
+                &450,
+                &(base + 7 * 86400),
+                &Some(symbol_short!("donor")),
+                &None,
+            )
+        };
 
-    #[test]
-    #[should_panic(expected = "Error(Contract, #29)")] // NotCurrentCustodian
-    fn test_initiate_transfer_by_non_custodian_authorized_bank_fails() {
-        let env = Env::default();
-        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let stale = register();
+        client.allocate_blood(&bank, &stale, &hospital, &None, &None);
+        let fresh = register();
+        let available = register();
 
-        let bank_a = Address::generate(&env);
-        let bank_b = Address::generate(&env);
-        env.mock_all_auths();
-        client.register_blood_bank(&bank_a);
-        client.register_blood_bank(&bank_b);
+        env.ledger()
+            .set_timestamp(base + RESERVATION_PICKUP_WINDOW_SECONDS - 100);
+        client.allocate_blood(&bank, &fresh, &hospital, &None, &None);
 
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
-        // bank_a registers and allocates the unit — bank_a is the custodian
-        let unit_id = client.register_blood(
-            &bank_a,
-            &BloodType::OPositive,
-            &BloodComponent::WholeBlood,
-            &450,
-            &expiration,
-            &None,
+        env.ledger()
+            .set_timestamp(base + RESERVATION_PICKUP_WINDOW_SECONDS);
+        let released = client.release_stale_reservations_batch(
+            &bank,
+            &vec![&env, stale, fresh, in_transit, available, 999],
         );
-        client.allocate_blood(&bank_a, &unit_id, &hospital);
+        assert_eq!(released, 1);
 
-        // bank_b is authorized but is NOT the custodian — must fail
-        client.initiate_transfer(&bank_b, &unit_id);
+        let stale_unit = client.get_blood_unit(&stale);
+        assert_eq!(stale_unit.status, BloodStatus::Available);
+        assert_eq!(stale_unit.recipient_hospital, None);
+        assert_eq!(stale_unit.allocation_timestamp, None);
+        assert_eq!(client.get_blood_unit(&fresh).status, BloodStatus::Reserved);
+        assert_eq!(
+            client.get_blood_unit(&in_transit).status,
+            BloodStatus::InTransit
+        );
+        assert_eq!(
+            client.get_blood_unit(&available).status,
+            BloodStatus::Available
+        );
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1)")] // Unauthorized
-    fn test_initiate_transfer_by_unregistered_address_fails() {
+    fn test_release_stale_reservations_batch_skips_other_banks_units() {
         let env = Env::default();
         let (_, _, hospital, client) = setup_contract_with_hospital(&env);
-
         let bank = Address::generate(&env);
-        let rogue = Address::generate(&env);
+        let other_bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
+        client.register_blood_bank(&other_bank);
 
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
         let unit_id = client.register_blood(
-            &bank,
+            &other_bank,
             &BloodType::OPositive,
             &BloodComponent::WholeBlood,
             &450,
-            &expiration,
+            &(env.ledger().timestamp() + 7 * 86400),
+            &Some(symbol_short!("donor")),
             &None,
         );
-        client.allocate_blood(&bank, &unit_id, &hospital);
-
-        // Completely unregistered address — must fail with Unauthorized, not NotCurrentCustodian
-        client.initiate_transfer(&rogue, &unit_id);
-    }
-
-    // ======================================================
-    // Transfer Expiry Boundary Tests (#105)
-    // ======================================================
-
-    fn setup_in_transit_unit(
-        env: &Env,
-        client: &HealthChainContractClient<'_>,
-        bank: &Address,
-        hospital: &Address,
-        initiated_at: u64,
-    ) -> (u64, String) {
-        // Ensure deterministic time for registration + allocation.
-        env.ledger().set_timestamp(initiated_at.saturating_sub(10));
+        client.allocate_blood(&other_bank, &unit_id, &hospital, &None, &None);
 
-        let expiration = initiated_at + (7 * 86400);
-        let unit_id = client.register_blood(
-            bank,
-            &BloodType::OPositive,
-            &BloodComponent::WholeBlood,
-            &450,
-            &expiration,
-            &Some(symbol_short!("donor")),
+        env.ledger()
+            .set_timestamp(RESERVATION_PICKUP_WINDOW_SECONDS);
+        let released = client.release_stale_reservations_batch(&bank, &vec![&env, unit_id]);
+        assert_eq!(released, 0);
+        assert_eq!(
+            client.get_blood_unit(&unit_id).status,
+            BloodStatus::Reserved
         );
+    }
 
-        client.allocate_blood(bank, &unit_id, hospital);
+    #[test]
+    fn test_release_stale_reservations_batch_rejects_oversized_batch() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
 
-        // Initiate transfer at exact initiated_at.
-        env.ledger().set_timestamp(initiated_at);
-        let event_id = client.initiate_transfer(bank, &unit_id);
+        let mut ids = Vec::new(&env);
+        for i in 0..(MAX_BATCH_SIZE as u64 + 1) {
+            ids.push_back(i);
+        }
 
-        (unit_id, event_id)
+        let result = client.try_release_stale_reservations_batch(&bank, &ids);
+        assert_eq!(result, Err(Ok(Error::BatchSizeExceeded)));
     }
 
     #[test]
-    fn test_transfer_cancellable_at_exactly_expiry_boundary_succeeds() {
+    fn test_release_expired_reservations_honours_hold_deadlines() {
         let env = Env::default();
-        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
-
-        // Register a blood bank
+        let (_, admin, hospital, client) = setup_contract_with_hospital(&env);
         let bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
-        let initiated_at = 1_000_000u64;
-        let (unit_id, event_id) =
-            setup_in_transit_unit(&env, &client, &bank, &hospital, initiated_at);
+        assert_eq!(
+            client.get_default_hold_duration(),
+            RESERVATION_PICKUP_WINDOW_SECONDS
+        );
+        client.set_default_hold_duration(&admin, &7200);
 
-        // At initiated_at + 1800 => cancellable
-        env.ledger()
-            .set_timestamp(initiated_at + TRANSFER_EXPIRY_SECONDS);
-        client.cancel_transfer(&bank, &event_id);
+        let base = 1_000_000u64;
+        let (in_transit, _) = setup_in_transit_unit(&env, &client, &bank, &hospital, base);
 
-        let unit = client.get_blood_unit(&unit_id);
-        assert_eq!(unit.status, BloodStatus::Reserved);
-        assert_eq!(unit.transfer_timestamp, None);
+        let register = || {
+            client.register_blood(
+                &bank,
+                &BloodType::OPositive,
+                &BloodComponent::WholeBlood,
+                &450,
+                &(base + 7 * 86400),
+                &None,
+                &None,
+            )
+        };
+        let short_hold = register();
+        let default_hold = register();
+        client.allocate_blood(&bank, &short_hold, &hospital, &Some(3600), &None);
+        client.allocate_blood(&bank, &default_hold, &hospital, &None, &None);
+        assert_eq!(client.get_reserved_until(&short_hold), Some(base + 3600));
+        assert_eq!(client.get_reserved_until(&default_hold), Some(base + 7200));
+
+        // Nothing has lapsed yet
+        env.ledger().set_timestamp(base + 3599);
+        assert_eq!(client.release_expired_reservations(&10), 0);
+
+        env.ledger().set_timestamp(base + 3600);
+        assert_eq!(client.release_expired_reservations(&10), 1);
+        let released = client.get_blood_unit(&short_hold);
+        assert_eq!(released.status, BloodStatus::Available);
+        assert_eq!(released.recipient_hospital, None);
+        assert_eq!(client.get_reserved_until(&short_hold), None);
+        assert_eq!(client.get_reservation_duration(&short_hold), Some(3600));
+
+        // The keeper never touches units already in transit
+        env.ledger().set_timestamp(base + 7200);
+        assert_eq!(client.release_expired_reservations(&10), 1);
+        assert_eq!(
+            client.get_blood_unit(&default_hold).status,
+            BloodStatus::Available
+        );
+        assert_eq!(
+            client.get_blood_unit(&in_transit).status,
+            BloodStatus::InTransit
+        );
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #17)")]
-    fn test_transfer_not_cancellable_one_second_before_expiry_fails() {
+    fn test_allocation_hold_duration_is_bounded() {
         let env = Env::default();
-        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let (_, admin, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+
+        let unit_id = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &(env.ledger().timestamp() + 7 * 86400),
+            &None,
+            &None,
+        );
 
-        let bank = Address::generate(&env);
-        env.mock_all_auths();
-        client.register_blood_bank(&bank);
+        let result = client.try_allocate_blood(&bank, &unit_id, &hospital, &Some(0), &None);
+        assert_eq!(result, Err(Ok(Error::InvalidHoldDuration)));
+        let too_long = Some(MAX_RESERVATION_HOLD_SECONDS + 1);
+        let result = client.try_allocate_blood(&bank, &unit_id, &hospital, &too_long, &None);
+        assert_eq!(result, Err(Ok(Error::InvalidHoldDuration)));
 
-        let initiated_at = 1_000_000u64;
-        let (_, event_id) = setup_in_transit_unit(&env, &client, &bank, &hospital, initiated_at);
+        let result = client.try_set_default_hold_duration(&admin, &0);
+        assert_eq!(result, Err(Ok(Error::InvalidHoldDuration)));
+        let result = client.try_set_default_hold_duration(&bank, &3600);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
 
-        // At initiated_at + 1799 => NOT cancellable
-        env.ledger()
-            .set_timestamp(initiated_at + TRANSFER_EXPIRY_SECONDS - 1);
-        client.cancel_transfer(&bank, &event_id);
+        let hold = Some(MAX_RESERVATION_HOLD_SECONDS);
+        client.allocate_blood(&bank, &unit_id, &hospital, &hold, &None);
+        assert_eq!(
+            client.get_reserved_until(&unit_id),
+            Some(env.ledger().timestamp() + MAX_RESERVATION_HOLD_SECONDS)
+        );
     }
 
+    // ── RESERVATION DURATION TESTS ───────────────────────────────────────────────
+
     #[test]
-    fn test_transfer_cancellable_one_second_after_expiry_succeeds() {
+    fn test_reservation_duration_recorded_on_confirmation() {
         let env = Env::default();
         let (_, _, hospital, client) = setup_contract_with_hospital(&env);
-
         let bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
@@ -6813,953 +13375,1717 @@ mod test {
         let (unit_id, event_id) =
             setup_in_transit_unit(&env, &client, &bank, &hospital, initiated_at);
 
-        // At initiated_at + 1801 => cancellable
-        env.ledger()
-            .set_timestamp(initiated_at + TRANSFER_EXPIRY_SECONDS + 1);
-        client.cancel_transfer(&bank, &event_id);
+        // Reservation is still open while the unit is in transit.
+        assert_eq!(client.get_reservation_duration(&unit_id), None);
+        assert_eq!(client.get_avg_reservation_time(&bank).samples, 0);
 
-        let unit = client.get_blood_unit(&unit_id);
-        assert_eq!(unit.status, BloodStatus::Reserved);
+        env.ledger().set_timestamp(initiated_at + 290);
+        client.confirm_transfer(&hospital, &event_id, &None, &None, &None);
+
+        // Reserved 10 seconds before initiation, confirmed 290 seconds after.
+        assert_eq!(client.get_reservation_duration(&unit_id), Some(300));
+        assert_eq!(
+            client.get_avg_reservation_time(&bank),
+            ReservationAverage {
+                samples: 1,
+                average_seconds: 300,
+            }
+        );
     }
 
     #[test]
-    fn test_transfer_confirmation_one_second_before_expiry_succeeds() {
+    fn test_reservation_average_updates_on_each_resolution() {
         let env = Env::default();
         let (_, _, hospital, client) = setup_contract_with_hospital(&env);
-
         let bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
         let initiated_at = 1_000_000u64;
-        let (unit_id, event_id) =
-            setup_in_transit_unit(&env, &client, &bank, &hospital, initiated_at);
+        let (_, event_id) = setup_in_transit_unit(&env, &client, &bank, &hospital, initiated_at);
+        env.ledger().set_timestamp(initiated_at + 290);
+        client.confirm_transfer(&hospital, &event_id, &None, &None, &None);
 
-        // At initiated_at + 1799 => confirm succeeds
-        env.ledger()
-            .set_timestamp(initiated_at + TRANSFER_EXPIRY_SECONDS - 1);
-        client.confirm_transfer(&hospital, &event_id);
+        let released = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &(initiated_at + 7 * 86400),
+            &Some(symbol_short!("donor")),
+            &None,
+        );
+        client.allocate_blood(&bank, &released, &hospital, &None, &None);
+        env.ledger().set_timestamp(initiated_at + 390);
+        client.cancel_allocation(&bank, &released);
 
-        let unit = client.get_blood_unit(&unit_id);
-        assert_eq!(unit.status, BloodStatus::Delivered);
+        assert_eq!(client.get_reservation_duration(&released), Some(100));
+        assert_eq!(
+            client.get_avg_reservation_time(&bank),
+            ReservationAverage {
+                samples: 2,
+                average_seconds: 200,
+            }
+        );
+
+        // Reserving again opens a fresh window.
+        client.allocate_blood(&bank, &released, &hospital, &None, &None);
+        assert_eq!(client.get_reservation_duration(&released), None);
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #16)")]
-    fn test_transfer_confirmation_at_expiry_boundary_fails_with_transfer_expired() {
+    fn test_get_allocations_for_hospital_filters_by_window() {
         let env = Env::default();
         let (_, _, hospital, client) = setup_contract_with_hospital(&env);
-
         let bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
-        let initiated_at = 1_000_000u64;
-        let (_, event_id) = setup_in_transit_unit(&env, &client, &bank, &hospital, initiated_at);
+        let base = 1_000_000u64;
+        env.ledger().set_timestamp(base);
+        let mut ids = vec![&env];
+        for _ in 0..4 {
+            ids.push_back(client.register_blood(
+                &bank,
+                &BloodType::OPositive,
+                &BloodComponent::WholeBlood,
+                &450,
+                &(base + 7 * 86400),
+                &Some(symbol_short!("donor")),
+                &None,
+            ));
+        }
 
-        // At initiated_at + 1800 => confirm fails
-        env.ledger()
-            .set_timestamp(initiated_at + TRANSFER_EXPIRY_SECONDS);
-        client.confirm_transfer(&hospital, &event_id);
+        for (i, unit_id) in ids.iter().enumerate() {
+            env.ledger().set_timestamp(base + 1000 * i as u64);
+            client.allocate_blood(&bank, &unit_id, &hospital, &None, &None);
+        }
+
+        // `from` is inclusive, `to` is exclusive.
+        let found =
+            client.get_allocations_for_hospital(&hospital, &(base + 1000), &(base + 3000), &0, &10);
+        assert_eq!(found, vec![&env, ids.get(1).unwrap(), ids.get(2).unwrap()]);
+
+        let page = client.get_allocations_for_hospital(&hospital, &base, &(base + 4000), &1, &2);
+        assert_eq!(page, vec![&env, ids.get(1).unwrap(), ids.get(2).unwrap()]);
+
+        let other = Address::generate(&env);
+        assert_eq!(
+            client
+                .get_allocations_for_hospital(&other, &base, &(base + 4000), &0, &10)
+                .len(),
+            0
+        );
     }
 
     #[test]
-    fn test_multiple_transfers_track_expiry_independently() {
+    fn test_inventory_checksum_stable_until_inventory_changes() {
         let env = Env::default();
         let (_, _, hospital, client) = setup_contract_with_hospital(&env);
-
         let bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
-        let t1 = 1_000_000u64;
-        let t2 = t1 + 100;
-
-        let (unit_1, event_id_1) = setup_in_transit_unit(&env, &client, &bank, &hospital, t1);
-        let (unit_2, event_id_2) = setup_in_transit_unit(&env, &client, &bank, &hospital, t2);
+        let register = || {
+            client.register_blood(
+                &bank,
+                &BloodType::OPositive,
+                &BloodComponent::WholeBlood,
+                &450,
+                &(env.ledger().timestamp() + 7 * 86400),
+                &Some(symbol_short!("donor")),
+                &None,
+            )
+        };
 
-        // At t1 + 1800: transfer #1 expired, transfer #2 still within window.
-        env.ledger().set_timestamp(t1 + TRANSFER_EXPIRY_SECONDS);
+        let empty = client.inventory_checksum(&bank);
+        let unit_id = register();
+        let registered = client.inventory_checksum(&bank);
+        assert_ne!(registered, empty);
 
-        // Unit 1 can be cancelled.
-        client.cancel_transfer(&bank, &event_id_1);
+        // Reads do not move the checksum.
+        client.get_blood_unit(&unit_id);
+        assert_eq!(client.inventory_checksum(&bank), registered);
+        assert_eq!(client.inventory_checksum(&bank), registered);
 
-        // Unit 2 can still be confirmed at the same ledger time.
-        client.confirm_transfer(&hospital, &event_id_2);
+        client.allocate_blood(&bank, &unit_id, &hospital, &None, &None);
+        let allocated = client.inventory_checksum(&bank);
+        assert_ne!(allocated, registered);
 
-        let u1 = client.get_blood_unit(&unit_1);
-        let u2 = client.get_blood_unit(&unit_2);
+        client.cancel_allocation(&bank, &unit_id);
+        assert_eq!(client.inventory_checksum(&bank), registered);
 
-        assert_eq!(u1.status, BloodStatus::Reserved);
-        assert_eq!(u2.status, BloodStatus::Delivered);
+        register();
+        assert_ne!(client.inventory_checksum(&bank), registered);
     }
 
+    // ── COMPONENT PROFILE TESTS ──────────────────────────────────────────────────
+
     #[test]
-    #[should_panic(expected = "Error(Contract, #9)")]
-    fn test_confirm_transfer_rejects_unregistered_to_custodian() {
+    fn test_platelet_unit_uses_shorter_shelf_life_and_filters_by_component() {
         let env = Env::default();
-        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
-
+        let (_, _, client) = setup_contract_with_admin(&env);
         let bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
-        let initiated_at = 1_000_000u64;
-        let (_, event_id) = setup_in_transit_unit(&env, &client, &bank, &hospital, initiated_at);
+        let now = env.ledger().timestamp();
 
-        let unregistered_hospital = Address::generate(&env);
+        // Seven days is fine for whole blood but too long for platelets.
+        let result = client.try_register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::Platelets,
+            &250,
+            &(now + 7 * SECONDS_PER_DAY),
+            &Some(symbol_short!("donor")),
+            &None,
+        );
+        assert_eq!(result, Err(Ok(Error::InvalidExpiration)));
 
-        // Tamper the custody event in storage to set `to_custodian` to unregistered address
-        env.as_contract(&contract_id, || {
-            let mut custody_events: Map<String, CustodyEvent> = env
-                .storage()
-                .persistent()
-                .get(&CUSTODY_EVENTS)
-                .unwrap();
-            let mut event = custody_events.get(event_id.clone()).unwrap();
-            event.to_custodian = unregistered_hospital.clone();
-            custody_events.set(event_id.clone(), event);
-            env.storage().persistent().set(&CUSTODY_EVENTS, &custody_events);
-        });
+        let platelets = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::Platelets,
+            &250,
+            &(now + PLATELETS_MAX_SHELF_LIFE_DAYS * SECONDS_PER_DAY),
+            &Some(symbol_short!("donor")),
+            &None,
+        );
+        let whole = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &(now + 7 * SECONDS_PER_DAY),
+            &Some(symbol_short!("donor")),
+            &None,
+        );
 
-        // Try to confirm with the unregistered hospital. It should panic with UnauthorizedHospital (error code #9)
-        client.confirm_transfer(&unregistered_hospital, &event_id);
+        let found =
+            client.query_by_component(&BloodType::OPositive, &BloodComponent::Platelets, &0);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found.get(0).unwrap().id, platelets);
+
+        let found =
+            client.query_by_component(&BloodType::OPositive, &BloodComponent::WholeBlood, &0);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found.get(0).unwrap().id, whole);
+
+        let found =
+            client.query_by_component(&BloodType::ONegative, &BloodComponent::Platelets, &0);
+        assert_eq!(found.len(), 0);
     }
 
     #[test]
-    fn test_get_units_by_bank_empty() {
+    fn test_component_profiles_differ_per_component() {
         let env = Env::default();
         let (_, _, client) = setup_contract_with_admin(&env);
-        let empty_bank = Address::generate(&env);
 
-        // This should return an empty Vec and NOT panic
-        let results = client.get_units_by_bank(&empty_bank);
-        assert_eq!(results.len(), 0);
+        let whole = client.get_component_profile(&BloodComponent::WholeBlood);
+        let platelets = client.get_component_profile(&BloodComponent::Platelets);
+        let plasma = client.get_component_profile(&BloodComponent::Plasma);
+
+        assert_eq!(whole.max_shelf_life_days, MAX_SHELF_LIFE_DAYS);
+        assert_eq!(platelets.max_shelf_life_days, PLATELETS_MAX_SHELF_LIFE_DAYS);
+        assert!(platelets.max_shelf_life_days < whole.max_shelf_life_days);
+        assert!(plasma.max_shelf_life_days > whole.max_shelf_life_days);
+
+        assert_eq!(
+            (whole.min_temp_celsius, whole.max_temp_celsius),
+            (REFRIGERATED_MIN_CELSIUS, REFRIGERATED_MAX_CELSIUS)
+        );
+        assert_eq!(
+            (platelets.min_temp_celsius, platelets.max_temp_celsius),
+            (PLATELETS_MIN_CELSIUS, PLATELETS_MAX_CELSIUS)
+        );
+        assert!(plasma.max_temp_celsius < 0);
+
+        // Cryoprecipitate shares plasma's frozen storage but comes in far
+        // smaller bags
+        let cryo = client.get_component_profile(&BloodComponent::Cryoprecipitate);
+        assert_eq!(cryo.max_shelf_life_days, plasma.max_shelf_life_days);
+        assert_eq!(
+            (cryo.min_temp_celsius, cryo.max_temp_celsius),
+            (plasma.min_temp_celsius, plasma.max_temp_celsius)
+        );
+        assert_eq!(
+            (whole.min_volume_ml, whole.max_volume_ml),
+            (MIN_QUANTITY_ML, MAX_QUANTITY_ML)
+        );
+        assert_eq!(
+            (cryo.min_volume_ml, cryo.max_volume_ml),
+            (CRYO_MIN_VOLUME_ML, CRYO_MAX_VOLUME_ML)
+        );
     }
 
-    /// Test for Issue #125: Donor ID collision across different banks
-    /// Verifies that get_units_by_donor uses composite (bank_id, donor_id) key
-    /// to prevent cross-bank data mixing
     #[test]
-    fn test_donor_id_collision_across_banks() {
+    fn test_register_blood_enforces_component_volume_and_shelf_life() {
         let env = Env::default();
-        let (_, _admin, client) = setup_contract_with_admin(&env);
-
-        // Register two different blood banks
-        let bank_a = Address::generate(&env);
-        let bank_b = Address::generate(&env);
-
-        env.mock_all_auths();
-        client.register_blood_bank(&bank_a);
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
         env.mock_all_auths();
-        client.register_blood_bank(&bank_b);
+        client.register_blood_bank(&bank);
 
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
+        let now = 1_000_000u64;
+        env.ledger().set_timestamp(now);
+        let register = |component: BloodComponent, quantity: u32, expiration: u64| {
+            client.try_register_blood(
+                &bank,
+                &BloodType::ABNegative,
+                &component,
+                &quantity,
+                &expiration,
+                &None,
+                &None,
+            )
+        };
+        let week = now + 7 * SECONDS_PER_DAY;
 
-        // Bank A registers a unit with donor "001"
-        env.mock_all_auths();
-        let unit_a1 = client.register_blood(
-            &bank_a,
-            &BloodType::OPositive,
-            &BloodComponent::WholeBlood,
-            &450,
-            &expiration,
-            &Some(symbol_short!("001")),
+        // A 15 ml cryo bag is valid; the same volume of whole blood is not
+        assert!(register(BloodComponent::Cryoprecipitate, 15, week).is_ok());
+        assert_eq!(
+            register(BloodComponent::WholeBlood, 15, week),
+            Err(Ok(Error::InvalidQuantity))
         );
-
-        // Bank B also registers a unit with donor "001" (different person, same ID)
-        env.mock_all_auths();
-        let unit_b1 = client.register_blood(
-            &bank_b,
-            &BloodType::APositive,
-            &BloodComponent::WholeBlood,
-            &350,
-            &expiration,
-            &Some(symbol_short!("001")),
+        assert_eq!(
+            register(
+                BloodComponent::Cryoprecipitate,
+                CRYO_MAX_VOLUME_ML + 1,
+                week
+            ),
+            Err(Ok(Error::InvalidQuantity))
         );
 
-        // Get units for donor "001" at Bank A - should only return Bank A's unit
-        let all_donor_units = client.get_units_by_donor(&symbol_short!("001"));
-        let mut bank_a_units = vec![&env];
-        for i in 0..all_donor_units.len() {
-            let unit = all_donor_units.get(i).unwrap();
-            if unit.bank_id == bank_a {
-                bank_a_units.push_back(unit);
-            }
-        }
-        assert_eq!(bank_a_units.len(), 1);
-        assert_eq!(bank_a_units.get(0).unwrap().id, unit_a1);
+        // Expirations past the component's shelf life are rejected
+        let ten_years = now + 3650 * SECONDS_PER_DAY;
         assert_eq!(
-            bank_a_units.get(0).unwrap().blood_type,
-            BloodType::OPositive
+            register(BloodComponent::Plasma, 250, ten_years),
+            Err(Ok(Error::InvalidExpiration))
         );
-        assert_eq!(bank_a_units.get(0).unwrap().bank_id, bank_a);
-
-        // Get units for donor "001" at Bank B - should only return Bank B's unit
-        let mut bank_b_units = vec![&env];
-        for i in 0..all_donor_units.len() {
-            let unit = all_donor_units.get(i).unwrap();
-            if unit.bank_id == bank_b {
-                bank_b_units.push_back(unit);
-            }
-        }
-        assert_eq!(bank_b_units.len(), 1);
-        assert_eq!(bank_b_units.get(0).unwrap().id, unit_b1);
         assert_eq!(
-            bank_b_units.get(0).unwrap().blood_type,
-            BloodType::APositive
+            register(BloodComponent::Platelets, 250, now + 6 * SECONDS_PER_DAY),
+            Err(Ok(Error::InvalidExpiration))
         );
-        assert_eq!(bank_b_units.get(0).unwrap().bank_id, bank_b);
+        assert_eq!(
+            register(BloodComponent::WholeBlood, 450, now - 1),
+            Err(Ok(Error::AlreadyExpired))
+        );
+    }
 
-        // Register another unit for donor "001" at Bank A
+    // ── DUAL-AUTH HANDOFF TESTS ──────────────────────────────────────────────────
+
+    fn setup_reserved_unit_for_handoff(
+        env: &Env,
+    ) -> (
+        Address,
+        Address,
+        Address,
+        u64,
+        HealthChainContractClient<'_>,
+    ) {
+        let (contract_id, _, hospital, client) = setup_contract_with_hospital(env);
+        let bank = Address::generate(env);
         env.mock_all_auths();
-        let unit_a2 = client.register_blood(
-            &bank_a,
-            &BloodType::ONegative,
+        client.register_blood_bank(&bank);
+
+        let unit_id = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
             &BloodComponent::WholeBlood,
-            &400,
-            &expiration,
-            &Some(symbol_short!("001")),
+            &450,
+            &(env.ledger().timestamp() + 7 * 86400),
+            &Some(symbol_short!("donor")),
+            &None,
         );
+        client.allocate_blood(&bank, &unit_id, &hospital, &None, &None);
 
-        // Verify Bank A now has 2 units for donor "001"
-        let all_updated = client.get_units_by_donor(&symbol_short!("001"));
-        let mut bank_a_units_updated = vec![&env];
-        for i in 0..all_updated.len() {
-            let unit = all_updated.get(i).unwrap();
-            if unit.bank_id == bank_a {
-                bank_a_units_updated.push_back(unit);
-            }
+        (contract_id, bank, hospital, unit_id, client)
+    }
+
+    fn handoff_invoke<'a>(
+        env: &Env,
+        contract_id: &'a Address,
+        from: &Address,
+        to: &Address,
+        unit_id: u64,
+    ) -> MockAuthInvoke<'a> {
+        MockAuthInvoke {
+            contract: contract_id,
+            fn_name: "handoff_with_dual_auth",
+            args: (from, to, unit_id).into_val(env),
+            sub_invokes: &[],
         }
-        assert_eq!(bank_a_units_updated.len(), 2);
+    }
+
+    #[test]
+    fn test_handoff_with_dual_auth_succeeds_when_both_sign() {
+        let env = Env::default();
+        let (contract_id, bank, hospital, unit_id, client) = setup_reserved_unit_for_handoff(&env);
+
+        let invoke = handoff_invoke(&env, &contract_id, &bank, &hospital, unit_id);
+        env.mock_auths(&[
+            MockAuth {
+                address: &bank,
+                invoke: &invoke,
+            },
+            MockAuth {
+                address: &hospital,
+                invoke: &invoke,
+            },
+        ]);
+        let event_id = client.handoff_with_dual_auth(&bank, &hospital, &unit_id);
+
+        let unit = client.get_blood_unit(&unit_id);
+        assert_eq!(unit.status, BloodStatus::Delivered);
+        assert_eq!(
+            client.get_current_custodian(&unit_id),
+            (hospital.clone(), CustodianKind::Hospital)
+        );
 
-        // Verify Bank B still has only 1 unit for donor "001"
-        let mut bank_b_units_updated = vec![&env];
-        for i in 0..all_updated.len() {
-            let unit = all_updated.get(i).unwrap();
-            if unit.bank_id == bank_b {
-                bank_b_units_updated.push_back(unit);
-            }
-        }
-        assert_eq!(bank_b_units_updated.len(), 1);
+        let event = client.get_custody_event(&event_id);
+        assert_eq!(event.from_custodian, bank);
+        assert_eq!(event.to_custodian, hospital);
+        assert_eq!(event.status, CustodyStatus::Confirmed);
     }
 
-    /// Test get_units_by_donor with non-existent donor
     #[test]
-    fn test_get_units_by_donor_nonexistent() {
+    fn test_handoff_with_dual_auth_fails_without_hospital_auth() {
         let env = Env::default();
-        let (_, _, client) = setup_contract_with_admin(&env);
+        let (contract_id, bank, hospital, unit_id, client) = setup_reserved_unit_for_handoff(&env);
+
+        let invoke = handoff_invoke(&env, &contract_id, &bank, &hospital, unit_id);
+        env.mock_auths(&[MockAuth {
+            address: &bank,
+            invoke: &invoke,
+        }]);
+        assert!(client
+            .try_handoff_with_dual_auth(&bank, &hospital, &unit_id)
+            .is_err());
+        assert_eq!(
+            client.get_blood_unit(&unit_id).status,
+            BloodStatus::Reserved
+        );
+    }
+
+    #[test]
+    fn test_handoff_with_dual_auth_fails_without_bank_auth() {
+        let env = Env::default();
+        let (contract_id, bank, hospital, unit_id, client) = setup_reserved_unit_for_handoff(&env);
+
+        let invoke = handoff_invoke(&env, &contract_id, &bank, &hospital, unit_id);
+        env.mock_auths(&[MockAuth {
+            address: &hospital,
+            invoke: &invoke,
+        }]);
+        assert!(client
+            .try_handoff_with_dual_auth(&bank, &hospital, &unit_id)
+            .is_err());
+        assert_eq!(
+            client.get_blood_unit(&unit_id).status,
+            BloodStatus::Reserved
+        );
+    }
+
+    #[test]
+    fn test_confirm_transfer_needs_both_custodians_when_dual_auth_required() {
+        use soroban_sdk::BytesN;
 
+        let env = Env::default();
+        let (contract_id, admin, hospital, client) = setup_contract_with_hospital(&env);
         let bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
+        client.set_dual_auth_required(&admin, &true);
+        assert!(client.is_dual_auth_required());
 
-        // Query for a donor that doesn't exist
-        let units = client.get_units_by_donor(&symbol_short!("NOEXIST"));
-        assert_eq!(units.len(), 0);
+        let (unit_id, event_id) = setup_in_transit_unit(&env, &client, &bank, &hospital, 1_000_000);
+
+        let invoke = MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "confirm_transfer",
+            args: (
+                hospital.clone(),
+                event_id.clone(),
+                None::<BytesN<32>>,
+                None::<Symbol>,
+            )
+                .into_val(&env),
+            sub_invokes: &[],
+        };
+
+        // The receiver alone can no longer confirm
+        env.mock_auths(&[MockAuth {
+            address: &hospital,
+            invoke: &invoke,
+        }]);
+        assert!(client
+            .try_confirm_transfer(&hospital, &event_id, &None, &None, &None)
+            .is_err());
+        assert_eq!(
+            client.get_blood_unit(&unit_id).status,
+            BloodStatus::InTransit
+        );
+
+        env.mock_auths(&[
+            MockAuth {
+                address: &hospital,
+                invoke: &invoke,
+            },
+            MockAuth {
+                address: &bank,
+                invoke: &invoke,
+            },
+        ]);
+        client.confirm_transfer(&hospital, &event_id, &None, &None, &None);
+        assert_eq!(
+            client.get_blood_unit(&unit_id).status,
+            BloodStatus::Delivered
+        );
     }
 
-    /// Test get_units_by_donor with anonymous donor
+    // ── PAGINATION LIMIT TESTS ───────────────────────────────────────────────────
+
     #[test]
-    fn test_get_units_by_donor_anonymous() {
+    fn test_paginated_reads_clamp_limit_and_default_zero() {
         let env = Env::default();
         let (_, _, client) = setup_contract_with_admin(&env);
-
         let bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
+        let base = 1_000_000u64;
+        env.ledger().set_timestamp(base);
+        for _ in 0..(MAX_PAGE_SIZE + 1) {
+            client.register_blood(
+                &bank,
+                &BloodType::OPositive,
+                &BloodComponent::WholeBlood,
+                &450,
+                &(base + 7 * 86400),
+                &Some(symbol_short!("donor")),
+                &None,
+            );
+        }
+        env.ledger().set_timestamp(base + 1);
 
-        // Register blood without donor_id (anonymous)
-        env.mock_all_auths();
-        client.register_blood(
-            &bank,
-            &BloodType::ABPositive,
-            &BloodComponent::WholeBlood,
-            &300,
-            &expiration,
-            &None,
+        assert_eq!(clamp_limit(0), DEFAULT_PAGE_SIZE);
+        assert_eq!(clamp_limit(u32::MAX), MAX_PAGE_SIZE);
+
+        // Over-cap limits are clamped.
+        assert_eq!(
+            client
+                .query_by_status(&BloodStatus::Available, &u32::MAX)
+                .len(),
+            MAX_PAGE_SIZE
+        );
+        assert_eq!(
+            client.get_units_older_than(&bank, &0, &0, &u32::MAX).len(),
+            MAX_PAGE_SIZE
         );
 
-        // Anonymous donors are stored as "ANON"
-        let units = client.get_units_by_donor(&symbol_short!("ANON"));
-        assert_eq!(units.len(), 1);
+        // Zero falls back to the default page size.
+        assert_eq!(
+            client.query_by_status(&BloodStatus::Available, &0).len(),
+            DEFAULT_PAGE_SIZE
+        );
+        assert_eq!(
+            client.get_units_older_than(&bank, &0, &0, &0).len(),
+            DEFAULT_PAGE_SIZE
+        );
+        assert_eq!(
+            client
+                .query_by_blood_type(&BloodType::OPositive, &None, &0, &0)
+                .len(),
+            DEFAULT_PAGE_SIZE
+        );
     }
 
-    // ─────────────────────────────────────────────────────────────────────────────
-    // Paginated Custody Trail Tests
-    // ─────────────────────────────────────────────────────────────────────────────
+    // ── HOSPITAL REGISTRATION CHECK TESTS ────────────────────────────────────────
 
     #[test]
-    fn test_custody_trail_single_event() {
+    fn test_allocate_blood_to_registered_hospital_succeeds() {
         let env = Env::default();
         let (_, _, hospital, client) = setup_contract_with_hospital(&env);
-
         let bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
-
-        // Register and allocate blood
-        env.mock_all_auths();
         let unit_id = client.register_blood(
             &bank,
             &BloodType::OPositive,
             &BloodComponent::WholeBlood,
             &450,
-            &expiration,
+            &(env.ledger().timestamp() + 7 * 86400),
+            &Some(symbol_short!("donor")),
             &None,
         );
+        client.allocate_blood(&bank, &unit_id, &hospital, &None, &None);
 
-        env.mock_all_auths();
-        client.allocate_blood(&bank, &unit_id, &hospital);
+        let unit = client.get_blood_unit(&unit_id);
+        assert_eq!(unit.status, BloodStatus::Reserved);
+        assert_eq!(unit.recipient_hospital, Some(hospital));
+    }
 
-        // Initiate transfer
+    #[test]
+    fn test_allocate_blood_to_unregistered_address_rejected() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
+        let stranger = Address::generate(&env);
         env.mock_all_auths();
-        let event_id = client.initiate_transfer(&bank, &unit_id);
+        client.register_blood_bank(&bank);
 
-        // Confirm transfer
-        env.mock_all_auths();
-        client.confirm_transfer(&hospital, &event_id);
+        let unit_id = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &(env.ledger().timestamp() + 7 * 86400),
+            &Some(symbol_short!("donor")),
+            &None,
+        );
 
-        // Check custody trail
-        let trail = client.get_custody_trail(&unit_id, &0);
-        assert_eq!(trail.len(), 1);
-        assert_eq!(trail.get(0).unwrap(), event_id);
+        let result = client.try_allocate_blood(&bank, &unit_id, &stranger, &None, &None);
+        assert_eq!(result, Err(Ok(Error::HospitalNotRegistered)));
+        assert_eq!(
+            client.get_blood_unit(&unit_id).status,
+            BloodStatus::Available
+        );
 
-        // Check metadata
-        let metadata = client.get_custody_trail_metadata(&unit_id);
-        assert_eq!(metadata.total_events, 1);
-        assert_eq!(metadata.total_pages, 1);
+        let result = client.try_batch_allocate_blood(&bank, &vec![&env, unit_id], &stranger, &None);
+        assert_eq!(result, Err(Ok(Error::HospitalNotRegistered)));
     }
 
     #[test]
-    fn test_custody_trail_multiple_events_single_page() {
+    fn test_allocate_blood_to_deregistered_hospital_rejected() {
         let env = Env::default();
-        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
-
+        let (_, admin, hospital, client) = setup_contract_with_hospital(&env);
         let bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
-
-        env.mock_all_auths();
         let unit_id = client.register_blood(
             &bank,
             &BloodType::OPositive,
             &BloodComponent::WholeBlood,
             &450,
-            &expiration,
+            &(env.ledger().timestamp() + 7 * 86400),
+            &Some(symbol_short!("donor")),
             &None,
         );
 
-        let mut event_ids = vec![&env];
+        client.deactivate_hospital(
+            &admin,
+            &hospital,
+            &String::from_str(&env, "License revoked"),
+        );
 
-        for i in 0..5 {
-            env.as_contract(&contract_id, || {
-                let mut units: Map<u64, BloodUnit> = env
-                    .storage()
-                    .persistent()
-                    .get(&BLOOD_UNITS)
-                    .unwrap_or(Map::new(&env));
-                let mut unit = units.get(unit_id).unwrap();
-                unit.status = BloodStatus::Reserved;
-                unit.recipient_hospital = Some(hospital.clone());
-                units.set(unit_id, unit);
-                env.storage().persistent().set(&BLOOD_UNITS, &units);
-            });
+        let result = client.try_allocate_blood(&bank, &unit_id, &hospital, &None, &None);
+        assert_eq!(result, Err(Ok(Error::HospitalNotRegistered)));
+    }
 
-            env.mock_all_auths();
-            let event_id = client.initiate_transfer(&bank, &unit_id);
+    // ── LAB SCREENING TESTS ──────────────────────────────────────────────────────
 
-            env.ledger().set_timestamp(current_time + (i * 100));
+    #[test]
+    fn test_allocation_requires_passing_lab_results_when_screening_required() {
+        let env = Env::default();
+        let (_, admin, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
+        let lab = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+        client.register_lab(&lab);
+        client.set_screening_required(&admin, &true);
 
-            env.mock_all_auths();
-            client.confirm_transfer(&hospital, &event_id);
+        let unit_id = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &(env.ledger().timestamp() + 7 * 86400),
+            &None,
+            &None,
+        );
+        let report = BytesN::from_array(&env, &[7u8; 32]);
 
-            event_ids.push_back(event_id.clone());
-        }
+        // Only registered labs can record results
+        let result = client.try_record_test_result(&bank, &unit_id, &TestType::Hiv, &report, &true);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
 
-        let trail = client.get_custody_trail(&unit_id, &0);
-        assert_eq!(trail.len(), 5);
+        // Missing and failed tests both block allocation
+        client.record_test_result(&lab, &unit_id, &TestType::Hiv, &report, &true);
+        client.record_test_result(&lab, &unit_id, &TestType::HepatitisB, &report, &true);
+        client.record_test_result(&lab, &unit_id, &TestType::HepatitisC, &report, &false);
+        let result = client.try_allocate_blood(&bank, &unit_id, &hospital, &None, &None);
+        assert_eq!(result, Err(Ok(Error::ScreeningIncomplete)));
 
-        for i in 0..5 {
-            assert_eq!(trail.get(i).unwrap(), event_ids.get(i).unwrap());
-        }
+        client.record_test_result(&lab, &unit_id, &TestType::Syphilis, &report, &true);
+        let result = client.try_batch_allocate_blood(&bank, &vec![&env, unit_id], &hospital, &None);
+        assert_eq!(result, Err(Ok(Error::ScreeningIncomplete)));
 
-        let metadata = client.get_custody_trail_metadata(&unit_id);
-        assert_eq!(metadata.total_events, 5);
-        assert_eq!(metadata.total_pages, 1);
+        // A passing retest replaces the failed result
+        client.record_test_result(&lab, &unit_id, &TestType::HepatitisC, &report, &true);
+        let results = client.get_test_results(&unit_id);
+        assert_eq!(results.len(), 4);
+        assert!(results
+            .iter()
+            .all(|result| result.passed && result.lab == lab));
+
+        client.allocate_blood(&bank, &unit_id, &hospital, &None, &None);
+        assert_eq!(
+            client.get_blood_unit(&unit_id).status,
+            BloodStatus::Reserved
+        );
     }
 
     #[test]
-    fn test_custody_trail_pagination_across_pages() {
+    fn test_screening_not_enforced_until_enabled() {
         let env = Env::default();
-        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
-
+        let (_, admin, hospital, client) = setup_contract_with_hospital(&env);
         let bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (30 * 86400);
+        assert!(!client.is_screening_required());
+        let result = client.try_set_screening_required(&bank, &true);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
 
-        // Register blood
-        env.mock_all_auths();
         let unit_id = client.register_blood(
             &bank,
             &BloodType::OPositive,
             &BloodComponent::WholeBlood,
             &450,
-            &expiration,
+            &(env.ledger().timestamp() + 7 * 86400),
+            &None,
             &None,
         );
+        client.allocate_blood(&bank, &unit_id, &hospital, &None, &None);
 
-        let mut all_event_ids = vec![&env];
+        client.set_screening_required(&admin, &true);
+        assert!(client.is_screening_required());
+    }
 
-        // Create 25 custody events (should span 2 pages: 20 + 5)
-        for i in 0..25 {
-            // Manually set unit to Reserved state
-            env.as_contract(&contract_id, || {
-                let mut units: Map<u64, BloodUnit> = env
-                    .storage()
-                    .persistent()
-                    .get(&BLOOD_UNITS)
-                    .unwrap_or(Map::new(&env));
-                let mut unit = units.get(unit_id).unwrap();
-                unit.status = BloodStatus::Reserved;
-                unit.recipient_hospital = Some(hospital.clone());
-                units.set(unit_id, unit);
-                env.storage().persistent().set(&BLOOD_UNITS, &units);
-            });
+    // ── RECALL TESTS ─────────────────────────────────────────────────────────────
 
-            env.mock_all_auths();
-            let event_id = client.initiate_transfer(&bank, &unit_id);
+    #[test]
+    fn test_recall_by_donor_and_lot_blocks_allocation() {
+        let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
 
-            // Advance time slightly
-            env.ledger().set_timestamp(current_time + (i * 100));
+        let expiry = env.ledger().timestamp() + 7 * 86400;
+        let donor = Some(symbol_short!("DONOR1"));
+        let register = |donor: &Option<Symbol>| {
+            client.register_blood(
+                &bank,
+                &BloodType::OPositive,
+                &BloodComponent::WholeBlood,
+                &450,
+                &expiry,
+                donor,
+                &None,
+            )
+        };
+        let available = register(&donor);
+        let reserved = register(&donor);
+        let used = register(&donor);
+        let other_donor = register(&Some(symbol_short!("DONOR2")));
+        client.allocate_blood(&bank, &reserved, &hospital, &None, &None);
+        client.withdraw_blood(&bank, &used, &WithdrawalReason::Used);
+
+        let recalled = client.recall_by_donor(
+            &bank,
+            &symbol_short!("DONOR1"),
+            &RecallReason::DonorTestPositive,
+        );
+        assert_eq!(recalled, 2);
+        assert_eq!(
+            client.get_blood_unit(&available).status,
+            BloodStatus::Recalled
+        );
+        assert_eq!(
+            client.get_blood_unit(&reserved).status,
+            BloodStatus::Recalled
+        );
+        assert_eq!(client.get_blood_unit(&used).status, BloodStatus::Discarded);
+        assert_eq!(
+            client.get_blood_unit(&other_donor).status,
+            BloodStatus::Available
+        );
 
-            env.mock_all_auths();
-            client.confirm_transfer(&hospital, &event_id);
+        let result = client.try_allocate_blood(&bank, &available, &hospital, &None, &None);
+        assert_eq!(result, Err(Ok(Error::InvalidStatus)));
 
-            all_event_ids.push_back(event_id);
-        }
+        // Recalling again is a no-op
+        let recalled =
+            client.recall_by_donor(&bank, &symbol_short!("DONOR1"), &RecallReason::Other);
+        assert_eq!(recalled, 0);
 
-        // Check page 0 - should have 20 events
-        let page_0 = client.get_custody_trail(&unit_id, &0);
-        assert_eq!(page_0.len(), 20);
+        let lot = symbol_short!("LOT42");
+        client.create_lot(&bank, &lot);
+        let lot_unit = register(&None);
+        client.assign_lot(&bank, &lot_unit, &lot);
+        client.assign_lot(&bank, &available, &lot);
+        let result = client.try_assign_lot(&bank, &lot_unit, &lot);
+        assert_eq!(result, Err(Ok(Error::DuplicateRegistration)));
+        assert_eq!(
+            client.get_units_by_lot(&lot),
+            vec![&env, lot_unit, available]
+        );
 
-        for i in 0..20 {
-            assert_eq!(page_0.get(i).unwrap(), all_event_ids.get(i).unwrap());
-        }
+        let recalled = client.recall_by_lot(&lot, &RecallReason::LotContamination);
+        assert_eq!(recalled, 1);
+        assert_eq!(
+            client.get_blood_unit(&lot_unit).status,
+            BloodStatus::Recalled
+        );
+        let result =
+            client.try_batch_allocate_blood(&bank, &vec![&env, lot_unit], &hospital, &None);
+        assert_eq!(result, Err(Ok(Error::InvalidStatus)));
+    }
 
-        // Check page 1 - should have 5 events
-        let page_1 = client.get_custody_trail(&unit_id, &1);
-        assert_eq!(page_1.len(), 5);
+    #[test]
+    fn test_lot_lifecycle_and_queries() {
+        let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
+        let other_bank = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+        client.register_blood_bank(&other_bank);
 
-        for i in 0..5 {
-            assert_eq!(page_1.get(i).unwrap(), all_event_ids.get(20 + i).unwrap());
-        }
+        let register = |bank: &Address| {
+            client.register_blood(
+                bank,
+                &BloodType::APositive,
+                &BloodComponent::WholeBlood,
+                &450,
+                &(env.ledger().timestamp() + 7 * 86400),
+                &None,
+                &None,
+            )
+        };
+        let first = register(&bank);
+        let second = register(&bank);
+        let foreign = register(&other_bank);
+
+        let lot = symbol_short!("DRIVE1");
+        let result = client.try_assign_lot(&bank, &first, &lot);
+        assert_eq!(result, Err(Ok(Error::LotNotFound)));
+
+        client.create_lot(&bank, &lot);
+        let result = client.try_create_lot(&other_bank, &lot);
+        assert_eq!(result, Err(Ok(Error::DuplicateRegistration)));
+        assert_eq!(client.get_bank_lots(&bank), vec![&env, lot.clone()]);
+
+        client.assign_lot(&bank, &first, &lot);
+        client.assign_lot(&bank, &second, &lot);
+        let result = client.try_assign_lot(&other_bank, &foreign, &lot);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        assert_eq!(client.get_blood_unit(&first).lot_id, Some(lot.clone()));
+
+        // A unit belongs to at most one lot
+        let other_lot = symbol_short!("DRIVE2");
+        client.create_lot(&bank, &other_lot);
+        let result = client.try_assign_lot(&bank, &first, &other_lot);
+        assert_eq!(result, Err(Ok(Error::DuplicateRegistration)));
+
+        client.allocate_blood(&bank, &first, &hospital, &None, &None);
+        assert_eq!(
+            client.get_lot_units_by_status(&lot, &BloodStatus::Available),
+            vec![&env, second]
+        );
+        assert_eq!(
+            client.get_lot_units_by_status(&lot, &BloodStatus::Reserved),
+            vec![&env, first]
+        );
 
-        // Check metadata
-        let metadata = client.get_custody_trail_metadata(&unit_id);
-        assert_eq!(metadata.total_events, 25);
-        assert_eq!(metadata.total_pages, 2);
+        let result = client.try_close_lot(&other_bank, &lot);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        client.close_lot(&bank, &lot);
+        assert!(client.get_lot(&lot).unwrap().closed_at.is_some());
+        let result = client.try_close_lot(&bank, &lot);
+        assert_eq!(result, Err(Ok(Error::LotClosed)));
+
+        let late = register(&bank);
+        let result = client.try_assign_lot(&bank, &late, &lot);
+        assert_eq!(result, Err(Ok(Error::LotClosed)));
+        assert_eq!(client.get_units_by_lot(&lot).len(), 2);
+    }
+
+    // ── CROSSMATCH TESTS ─────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_confirm_transfer_requires_recent_compatible_crossmatch() {
+        use soroban_sdk::BytesN;
+
+        let env = Env::default();
+        let (_, admin, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
+        let other_hospital = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+        client.register_hospital(&other_hospital);
+
+        assert_eq!(client.get_crossmatch_window(), 0);
+        client.set_crossmatch_window(&admin, &3600);
+
+        let base = 1_000_000u64;
+        let (unit_id, event_id) = setup_in_transit_unit(&env, &client, &bank, &hospital, base);
+        let patient = BytesN::from_array(&env, &[9u8; 32]);
+
+        let result = client.try_confirm_transfer(&hospital, &event_id, &None, &None, &None);
+        assert_eq!(result, Err(Ok(Error::CrossmatchRequired)));
+
+        // Only the allocated hospital may record, and not for a future test time
+        let result =
+            client.try_record_crossmatch(&other_hospital, &unit_id, &patient, &true, &base);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        let result =
+            client.try_record_crossmatch(&hospital, &unit_id, &patient, &true, &(base + 1));
+        assert_eq!(result, Err(Ok(Error::InvalidTimestamp)));
+
+        // Incompatible and out-of-window results do not clear the unit
+        client.record_crossmatch(&hospital, &unit_id, &patient, &false, &base);
+        let result = client.try_confirm_transfer(&hospital, &event_id, &None, &None, &None);
+        assert_eq!(result, Err(Ok(Error::CrossmatchRequired)));
+        client.record_crossmatch(&hospital, &unit_id, &patient, &true, &(base - 3601));
+        let result = client.try_confirm_transfer(&hospital, &event_id, &None, &None, &None);
+        assert_eq!(result, Err(Ok(Error::CrossmatchRequired)));
+
+        client.record_crossmatch(&hospital, &unit_id, &patient, &true, &(base - 60));
+        let record = client.get_crossmatch(&unit_id).unwrap();
+        assert!(record.compatible);
+        assert_eq!(record.tested_at, base - 60);
+
+        client.confirm_transfer(&hospital, &event_id, &None, &None, &None);
+        assert_eq!(
+            client.get_blood_unit(&unit_id).status,
+            BloodStatus::Delivered
+        );
     }
 
+    // ── PROCESSING TESTS ─────────────────────────────────────────────────────────
+
     #[test]
-    fn test_custody_trail_100_events() {
+    fn test_processing_steps_are_append_only_and_status_gated() {
         let env = Env::default();
-        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
-
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
         let bank = Address::generate(&env);
+        let other_bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
+        client.register_blood_bank(&other_bank);
 
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (30 * 86400);
-
-        env.mock_all_auths();
+        let base = 1_000_000u64;
+        env.ledger().set_timestamp(base);
         let unit_id = client.register_blood(
             &bank,
             &BloodType::OPositive,
-            &BloodComponent::WholeBlood,
-            &450,
-            &expiration,
+            &BloodComponent::RedBloodCells,
+            &300,
+            &(base + 7 * 86400),
+            &None,
             &None,
         );
+        env.ledger().set_timestamp(base + 600);
 
-        for i in 0..100 {
-            env.as_contract(&contract_id, || {
-                let mut units: Map<u64, BloodUnit> = env
-                    .storage()
-                    .persistent()
-                    .get(&BLOOD_UNITS)
-                    .unwrap_or(Map::new(&env));
-                let mut unit = units.get(unit_id).unwrap();
-                unit.status = BloodStatus::Reserved;
-                unit.recipient_hospital = Some(hospital.clone());
-                units.set(unit_id, unit);
-                env.storage().persistent().set(&BLOOD_UNITS, &units);
-            });
-
-            env.mock_all_auths();
-            let event_id = client.initiate_transfer(&bank, &unit_id);
-
-            env.ledger().set_timestamp(current_time + (i * 100));
-
-            env.mock_all_auths();
-            client.confirm_transfer(&hospital, &event_id);
-        }
-
-        let metadata = client.get_custody_trail_metadata(&unit_id);
-        assert_eq!(metadata.total_events, 100);
-        assert_eq!(metadata.total_pages, 5);
-
-        for page_num in 0..5 {
-            let page = client.get_custody_trail(&unit_id, &page_num);
-            assert_eq!(page.len(), 20);
-        }
+        let result = client.try_add_processing_step(
+            &other_bank,
+            &unit_id,
+            &ProcessingType::Irradiation,
+            &base,
+        );
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        let result = client.try_add_processing_step(
+            &bank,
+            &unit_id,
+            &ProcessingType::Irradiation,
+            &(base + 601),
+        );
+        assert_eq!(result, Err(Ok(Error::InvalidTimestamp)));
+        let result = client.try_add_processing_step(
+            &bank,
+            &unit_id,
+            &ProcessingType::Irradiation,
+            &(base - 1),
+        );
+        assert_eq!(result, Err(Ok(Error::InvalidTimestamp)));
 
-        let result = client.try_get_custody_trail(&unit_id, &5);
-        assert_eq!(result, Err(Error::PageNotFound));
+        client.add_processing_step(
+            &bank,
+            &unit_id,
+            &ProcessingType::Leukoreduction,
+            &(base + 60),
+        );
+        client.add_processing_step(&bank, &unit_id, &ProcessingType::Irradiation, &(base + 300));
+        let result = client.try_add_processing_step(
+            &bank,
+            &unit_id,
+            &ProcessingType::Irradiation,
+            &(base + 600),
+        );
+        assert_eq!(result, Err(Ok(Error::DuplicateRequest)));
+
+        assert!(client.has_processing(&unit_id, &ProcessingType::Irradiation));
+        assert!(!client.has_processing(&unit_id, &ProcessingType::Washing));
+        let steps = client.get_processing_steps(&unit_id);
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps.get(0).unwrap().step, ProcessingType::Leukoreduction);
+        assert_eq!(steps.get(1).unwrap().performed_at, base + 300);
+
+        // Once the unit leaves the bank no further processing is accepted
+        client.allocate_blood(&bank, &unit_id, &hospital, &None, &None);
+        client.initiate_transfer(&bank, &unit_id, &None, &None, &None);
+        let result = client.try_add_processing_step(
+            &bank,
+            &unit_id,
+            &ProcessingType::Washing,
+            &(base + 600),
+        );
+        assert_eq!(result, Err(Ok(Error::InvalidStatus)));
     }
 
+    // ── DONOR DEFERRAL TESTS ─────────────────────────────────────────────────────
+
     #[test]
-    fn test_custody_trail_empty_for_new_unit() {
-        let env = Env::default();
-        let (_, _, _, client) = setup_contract_with_hospital(&env);
+    fn test_deferred_donor_hash_blocks_registration_until_window_lapses() {
+        use soroban_sdk::BytesN;
 
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
         let bank = Address::generate(&env);
+        let stranger = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
-
-        // Register blood but don't create any custody events
-        env.mock_all_auths();
-        let unit_id = client.register_blood(
+        let base = 1_000_000u64;
+        env.ledger().set_timestamp(base);
+        let donor = BytesN::from_array(&env, &[7u8; 32]);
+        let other_donor = BytesN::from_array(&env, &[8u8; 32]);
+
+        let result = client.try_defer_donor(&stranger, &donor, &(base + 86400), &1);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        let result = client.try_defer_donor(&bank, &donor, &base, &1);
+        assert_eq!(result, Err(Ok(Error::InvalidTimestamp)));
+
+        client.defer_donor(&bank, &donor, &(base + 86400), &1);
+        // A shorter deferral never overrides a longer one
+        client.defer_donor(&bank, &donor, &(base + 60), &2);
+        let deferral = client.get_donor_deferral(&donor).unwrap();
+        assert_eq!(deferral.until, base + 86400);
+        assert_eq!(deferral.reason_code, 1);
+        assert!(client.is_deferred(&donor));
+        assert!(!client.is_deferred(&other_donor));
+
+        let expiration = base + 7 * 86400;
+        let result = client.try_register_blood(
             &bank,
             &BloodType::OPositive,
             &BloodComponent::WholeBlood,
             &450,
             &expiration,
             &None,
+            &Some(donor.clone()),
+        );
+        assert_eq!(result, Err(Ok(Error::DonorDeferred)));
+        client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &expiration,
+            &None,
+            &Some(other_donor),
         );
 
-        // Check custody trail - should be empty
-        let trail = client.get_custody_trail(&unit_id, &0);
-        assert_eq!(trail.len(), 0);
-
-        // Check metadata - should show 0 events and 0 pages
-        let metadata = client.get_custody_trail_metadata(&unit_id);
-        assert_eq!(metadata.total_events, 0);
-        assert_eq!(metadata.total_pages, 0);
+        env.ledger().set_timestamp(base + 86400);
+        assert!(!client.is_deferred(&donor));
+        client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &(expiration + 86400),
+            &None,
+            &Some(donor),
+        );
     }
 
+    // ── DONOR CONSENT TESTS ──────────────────────────────────────────────────────
+
     #[test]
-    fn test_custody_trail_non_existent_page() {
-        let env = Env::default();
-        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+    fn test_revoked_consent_hides_donor_linked_units() {
+        use soroban_sdk::BytesN;
 
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
         let bank = Address::generate(&env);
+        let stranger = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
+        let base = 1_000_000u64;
+        env.ledger().set_timestamp(base);
+        let donor_hash = BytesN::from_array(&env, &[7u8; 32]);
+        let form_hash = BytesN::from_array(&env, &[9u8; 32]);
+        let donor_id = symbol_short!("DONOR1");
 
-        // Register and create one custody event
-        env.mock_all_auths();
-        let unit_id = client.register_blood(
+        let result = client.try_record_consent(&stranger, &donor_hash, &form_hash, &1);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        let result = client.try_revoke_consent(&bank, &donor_hash);
+        assert_eq!(result, Err(Ok(Error::ConsentNotFound)));
+
+        client.register_blood(
             &bank,
             &BloodType::OPositive,
             &BloodComponent::WholeBlood,
             &450,
-            &expiration,
-            &None,
+            &(base + 7 * 86400),
+            &Some(donor_id.clone()),
+            &Some(donor_hash.clone()),
+        );
+        // Linked donor without consent on file: nothing is returned
+        assert_eq!(client.get_units_by_donor(&donor_id).len(), 0);
+
+        client.record_consent(&bank, &donor_hash, &form_hash, &1);
+        let consent = client.get_consent(&donor_hash).unwrap();
+        assert_eq!(consent.version, 1);
+        assert_eq!(consent.recorded_by, bank);
+        assert_eq!(consent.revoked_at, None);
+        assert_eq!(client.get_units_by_donor(&donor_id).len(), 1);
+        assert_eq!(client.get_units_by_bank_donor(&bank, &donor_id).len(), 1);
+
+        env.ledger().set_timestamp(base + 60);
+        client.revoke_consent(&bank, &donor_hash);
+        assert_eq!(
+            client.get_consent(&donor_hash).unwrap().revoked_at,
+            Some(base + 60)
         );
+        assert_eq!(client.get_units_by_donor(&donor_id).len(), 0);
+        assert_eq!(client.get_units_by_bank_donor(&bank, &donor_id).len(), 0);
+        let result = client.try_revoke_consent(&bank, &donor_hash);
+        assert_eq!(result, Err(Ok(Error::ConsentNotFound)));
 
-        env.mock_all_auths();
-        client.allocate_blood(&bank, &unit_id, &hospital);
-
-        env.mock_all_auths();
-        let event_id = client.initiate_transfer(&bank, &unit_id);
-
-        env.mock_all_auths();
-        client.confirm_transfer(&hospital, &event_id);
-
-        // Query for page 10 (doesn't exist)
-        let result = client.try_get_custody_trail(&unit_id, &10);
-        assert_eq!(result, Err(Error::PageNotFound));
+        // Re-consenting on a newer form restores access
+        client.record_consent(&bank, &donor_hash, &form_hash, &2);
+        assert_eq!(client.get_units_by_donor(&donor_id).len(), 1);
     }
 
+    // ── PAUSE TESTS ──────────────────────────────────────────────────────────────
+
     #[test]
-    fn test_migrate_trail_index() {
+    fn test_pause_flags_disable_entry_points_individually() {
         let env = Env::default();
-        let (_, admin, _, client) = setup_contract_with_hospital(&env);
-
+        let (_, admin, hospital, client) = setup_contract_with_hospital(&env);
         let bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
-
-        // Register blood
-        env.mock_all_auths();
-        let unit_id = client.register_blood(
-            &bank,
-            &BloodType::OPositive,
-            &BloodComponent::WholeBlood,
-            &450,
-            &expiration,
-            &None,
+        let register = || {
+            client.try_register_blood(
+                &bank,
+                &BloodType::OPositive,
+                &BloodComponent::WholeBlood,
+                &450,
+                &(7 * 86400),
+                &None,
+                &None,
+            )
+        };
+        let unit_id = register().unwrap().unwrap();
+
+        let result = client.try_pause(&bank, &PAUSE_ALLOCATE);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        let result = client.try_pause(&admin, &0);
+        assert_eq!(result, Err(Ok(Error::InvalidPauseFlags)));
+        let result = client.try_pause(&admin, &(PAUSE_ALL + 1));
+        assert_eq!(result, Err(Ok(Error::InvalidPauseFlags)));
+
+        client.pause(&admin, &PAUSE_ALLOCATE);
+        assert_eq!(client.get_paused_flags(), PAUSE_ALLOCATE);
+
+        // Only allocation is stopped; registration and reads stay live
+        let result = client.try_allocate_blood(&bank, &unit_id, &hospital, &None, &None);
+        assert_eq!(result, Err(Ok(Error::ContractPaused)));
+        let result = client.try_allocate_fefo(&bank, &BloodType::OPositive, &1, &hospital);
+        assert_eq!(result, Err(Ok(Error::ContractPaused)));
+        let second = register().unwrap().unwrap();
+        assert_eq!(
+            client.get_blood_unit(&unit_id).status,
+            BloodStatus::Available
         );
 
-        // Migrate (should initialize empty metadata)
-        env.mock_all_auths();
-        client.migrate_trail_index(&unit_id);
+        client.pause(&admin, &(PAUSE_REGISTER | PAUSE_CUSTODY));
+        assert_eq!(client.get_paused_flags(), PAUSE_ALL);
+        assert_eq!(register(), Err(Ok(Error::ContractPaused)));
 
-        // Check metadata was created
-        let metadata = client.get_custody_trail_metadata(&unit_id);
-        assert_eq!(metadata.total_events, 0);
-        assert_eq!(metadata.total_pages, 0);
+        client.unpause(&admin, &PAUSE_ALLOCATE);
+        client.allocate_blood(&bank, &unit_id, &hospital, &None, &None);
+        let result = client.try_initiate_transfer(&bank, &unit_id, &None, &None, &None);
+        assert_eq!(result, Err(Ok(Error::ContractPaused)));
 
-        // Calling migrate again should be idempotent
-        env.mock_all_auths();
-        client.migrate_trail_index(&unit_id);
+        client.unpause(&admin, &PAUSE_ALL);
+        assert_eq!(client.get_paused_flags(), 0);
+        client.initiate_transfer(&bank, &unit_id, &None, &None, &None);
+        client.allocate_blood(&bank, &second, &hospital, &None, &None);
+    }
 
-        let metadata_after = client.get_custody_trail_metadata(&unit_id);
-        assert_eq!(metadata_after.total_events, 0);
-        assert_eq!(metadata_after.total_pages, 0);
+    // ── ABO/RH COMPATIBILITY TESTS ───────────────────────────────────────────────
+
+    #[test]
+    fn test_is_compatible_covers_all_64_pairs() {
+        use BloodType::*;
+
+        let all = [
+            APositive, ANegative, BPositive, BNegative, ABPositive, ABNegative, OPositive,
+            ONegative,
+        ];
+        // Donor types each recipient type can receive red cells from
+        let accepts = |recipient: BloodType| -> &'static [BloodType] {
+            match recipient {
+                APositive => &[APositive, ANegative, OPositive, ONegative],
+                ANegative => &[ANegative, ONegative],
+                BPositive => &[BPositive, BNegative, OPositive, ONegative],
+                BNegative => &[BNegative, ONegative],
+                ABPositive => &[
+                    APositive, ANegative, BPositive, BNegative, ABPositive, ABNegative, OPositive,
+                    ONegative,
+                ],
+                ABNegative => &[ANegative, BNegative, ABNegative, ONegative],
+                OPositive => &[OPositive, ONegative],
+                ONegative => &[ONegative],
+            }
+        };
+
+        let mut checked = 0;
+        for donor in all {
+            for recipient in all {
+                assert_eq!(
+                    is_compatible(donor, recipient),
+                    accepts(recipient).contains(&donor),
+                    "donor {:?} -> recipient {:?}",
+                    donor,
+                    recipient
+                );
+                checked += 1;
+            }
+        }
+        assert_eq!(checked, 64);
     }
 
     #[test]
-    fn test_migrate_trail_index_unauthorized() {
+    fn test_allocation_rejects_incompatible_recipient_type() {
         let env = Env::default();
-        let (_, _, _, client) = setup_contract_with_hospital(&env);
-
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
         let bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
 
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
+        let register = |blood_type: BloodType| {
+            client.register_blood(
+                &bank,
+                &blood_type,
+                &BloodComponent::WholeBlood,
+                &450,
+                &(7 * 86400),
+                &None,
+                &None,
+            )
+        };
+        let a_pos = register(BloodType::APositive);
+        let o_neg = register(BloodType::ONegative);
 
-        env.mock_all_auths();
-        let unit_id = client.register_blood(
+        let result =
+            client.try_allocate_blood(&bank, &a_pos, &hospital, &None, &Some(BloodType::ONegative));
+        assert_eq!(result, Err(Ok(Error::IncompatibleBloodType)));
+        assert_eq!(client.get_blood_unit(&a_pos).status, BloodStatus::Available);
+
+        let result = client.try_batch_allocate_blood(
             &bank,
-            &BloodType::OPositive,
-            &BloodComponent::WholeBlood,
-            &450,
-            &expiration,
-            &None,
+            &vec![&env, o_neg, a_pos],
+            &hospital,
+            &Some(BloodType::BPositive),
         );
+        assert_eq!(result, Err(Ok(Error::IncompatibleBloodType)));
 
-        // With mock_all_auths, this will succeed even without admin
-        // This test documents that behavior
-        client.migrate_trail_index(&unit_id);
+        let outcome = client.allocate_blood_batch(
+            &bank,
+            &vec![&env, o_neg, a_pos],
+            &hospital,
+            &Some(BloodType::ONegative),
+        );
+        assert_eq!(
+            outcome,
+            BatchAllocationOutcome::Rejected(BatchAllocationFailure {
+                index: 1,
+                unit_id: a_pos,
+                error_code: Error::IncompatibleBloodType as u32,
+            })
+        );
+
+        // Compatible recipients and stock moves without a recipient type go through
+        client.allocate_blood(
+            &bank,
+            &a_pos,
+            &hospital,
+            &None,
+            &Some(BloodType::ABPositive),
+        );
+        client.allocate_blood(&bank, &o_neg, &hospital, &None, &None);
+        assert_eq!(client.get_blood_unit(&o_neg).status, BloodStatus::Reserved);
     }
 
+    // ── DISCARD TESTS ────────────────────────────────────────────────────────────
+
     #[test]
-    fn test_custody_trail_storage_size_within_limits() {
+    fn test_discard_unit_enforces_custodian_and_reason_rules() {
         let env = Env::default();
         let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
-
         let bank = Address::generate(&env);
+        let other_bank = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
+        client.register_blood_bank(&other_bank);
 
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (30 * 86400);
-
-        env.mock_all_auths();
-        let unit_id = client.register_blood(
-            &bank,
-            &BloodType::OPositive,
-            &BloodComponent::WholeBlood,
-            &450,
-            &expiration,
-            &None,
-        );
-
-        for i in 0..20 {
-            env.as_contract(&contract_id, || {
-                let mut units: Map<u64, BloodUnit> = env
-                    .storage()
-                    .persistent()
-                    .get(&BLOOD_UNITS)
-                    .unwrap_or(Map::new(&env));
-                let mut unit = units.get(unit_id).unwrap();
-                unit.status = BloodStatus::Reserved;
-                unit.recipient_hospital = Some(hospital.clone());
-                units.set(unit_id, unit);
-                env.storage().persistent().set(&BLOOD_UNITS, &units);
-            });
-
-            env.mock_all_auths();
-            let event_id = client.initiate_transfer(&bank, &unit_id);
+        let expiry = env.ledger().timestamp() + 7 * 86400;
+        let register = || {
+            client.register_blood(
+                &bank,
+                &BloodType::ANegative,
+                &BloodComponent::WholeBlood,
+                &450,
+                &expiry,
+                &None,
+                &None,
+            )
+        };
 
-            env.ledger().set_timestamp(current_time + (i * 100));
+        let damaged = register();
+        let result = client.try_discard_unit(&other_bank, &damaged, &DiscardReason::Damaged);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        let result = client.try_discard_unit(&bank, &damaged, &DiscardReason::Expired);
+        assert_eq!(result, Err(Ok(Error::InvalidStatus)));
+        let result = client.try_discard_unit(&bank, &damaged, &DiscardReason::Recalled);
+        assert_eq!(result, Err(Ok(Error::InvalidStatus)));
 
-            env.mock_all_auths();
-            client.confirm_transfer(&hospital, &event_id);
-        }
+        client.discard_unit(&bank, &damaged, &DiscardReason::Damaged);
+        assert_eq!(
+            client.get_blood_unit(&damaged).status,
+            BloodStatus::Discarded
+        );
 
-        let page = client.get_custody_trail(&unit_id, &0);
-        assert_eq!(page.len(), 20);
+        let last_event = env.events().all().last().unwrap();
+        assert_eq!(last_event.0, contract_id);
+        let event: DiscardEvent = last_event.2.into_val(&env);
+        assert_eq!(event.blood_unit_id, damaged);
+        assert_eq!(event.old_status, BloodStatus::Available);
+        assert_eq!(event.reason, DiscardReason::Damaged);
+        assert_eq!(event.quantity, 450);
+
+        let result = client.try_discard_unit(&bank, &damaged, &DiscardReason::Damaged);
+        assert_eq!(result, Err(Ok(Error::InvalidStatus)));
+
+        // In transit: the transfer must be cancelled first
+        let shipped = register();
+        client.allocate_blood(&bank, &shipped, &hospital, &None, &None);
+        client.initiate_transfer(&bank, &shipped, &None, &None, &None);
+        let result = client.try_discard_unit(&bank, &shipped, &DiscardReason::TemperatureBreach);
+        assert_eq!(result, Err(Ok(Error::InvalidStatus)));
+
+        // Once delivered, only the recipient hospital may discard
+        client.confirm_delivery(&hospital, &shipped, &None, &None, &None);
+        let result = client.try_discard_unit(&bank, &shipped, &DiscardReason::QcFailure);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        client.discard_unit(&hospital, &shipped, &DiscardReason::QcFailure);
+        assert_eq!(
+            client.get_blood_unit(&shipped).status,
+            BloodStatus::Discarded
+        );
 
-        let metadata = client.get_custody_trail_metadata(&unit_id);
-        assert_eq!(metadata.total_events, 20);
-        assert_eq!(metadata.total_pages, 1);
+        // Expired reason is accepted once the unit is past its date
+        let stale = register();
+        env.ledger().with_mut(|li| li.timestamp = expiry);
+        client.discard_unit(&bank, &stale, &DiscardReason::Expired);
+        assert_eq!(client.get_blood_unit(&stale).status, BloodStatus::Discarded);
     }
 
-    // ── SUPER ADMIN NOMINATION TESTS (#111) ────────────────────────────────────────────────────
+    // ── CURRENT CUSTODIAN TESTS ──────────────────────────────────────────────────
 
     #[test]
-    fn test_super_admin_successful_transfer() {
+    fn test_current_custodian_of_available_unit_is_bank() {
         let env = Env::default();
-        let (_, admin, client) = setup_contract_with_admin(&env);
-        let new_admin = Address::generate(&env);
-
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
         env.mock_all_auths();
-        client.nominate_super_admin(&new_admin);
+        client.register_blood_bank(&bank);
 
-        env.mock_all_auths();
-        client.accept_super_admin();
+        let unit_id = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &(env.ledger().timestamp() + 7 * 86400),
+            &Some(symbol_short!("donor")),
+            &None,
+        );
+
+        assert_eq!(
+            client.get_current_custodian(&unit_id),
+            (bank, CustodianKind::Bank)
+        );
+    }
 
-        // New admin can now register a blood bank (proves they hold the admin role).
+    #[test]
+    fn test_current_custodian_of_in_transit_unit_is_rider() {
+        let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
         let bank = Address::generate(&env);
+        let rider = Address::generate(&env);
         env.mock_all_auths();
         client.register_blood_bank(&bank);
-        assert!(client.is_blood_bank(&bank));
+
+        let (unit_id, _) = setup_in_transit_unit(&env, &client, &bank, &hospital, 1_000_000);
+
+        // Until a rider is recorded the bank remains the custodian
+        assert_eq!(
+            client.get_current_custodian(&unit_id),
+            (bank.clone(), CustodianKind::Bank)
+        );
+
+        client.assign_transfer_rider(&bank, &unit_id, &rider);
+        assert_eq!(
+            client.get_current_custodian(&unit_id),
+            (rider, CustodianKind::Rider)
+        );
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #23)")]
-    fn test_accept_super_admin_after_expiry_returns_nomination_expired() {
+    fn test_current_custodian_of_delivered_unit_is_hospital() {
         let env = Env::default();
-        env.ledger().with_mut(|li| li.timestamp = 1_000_000);
-
-        let (_, _admin, client) = setup_contract_with_admin(&env);
-        let new_admin = Address::generate(&env);
-
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
+        let rider = Address::generate(&env);
         env.mock_all_auths();
-        client.nominate_super_admin(&new_admin);
+        client.register_blood_bank(&bank);
 
-        // Advance past the 24-hour expiry window.
-        env.ledger()
-            .with_mut(|li| li.timestamp = 1_000_000 + NOMINATION_EXPIRY_SECONDS + 1);
+        let (unit_id, _) = setup_in_transit_unit(&env, &client, &bank, &hospital, 1_000_000);
+        client.assign_transfer_rider(&bank, &unit_id, &rider);
+        client.confirm_delivery(&hospital, &unit_id, &None, &None, &None);
 
-        env.mock_all_auths();
-        client.accept_super_admin();
+        assert_eq!(
+            client.get_current_custodian(&unit_id),
+            (hospital, CustodianKind::Hospital)
+        );
     }
 
     #[test]
-    fn test_cancel_nomination_allows_immediate_re_nomination() {
+    fn test_assign_transfer_rider_requires_in_transit_unit() {
         let env = Env::default();
-        let (_, _admin, client) = setup_contract_with_admin(&env);
-        let nominee_a = Address::generate(&env);
-        let nominee_b = Address::generate(&env);
-
-        env.mock_all_auths();
-        client.nominate_super_admin(&nominee_a);
-
-        // Cancel the pending nomination.
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
         env.mock_all_auths();
-        client.cancel_nomination();
+        client.register_blood_bank(&bank);
 
-        // Should be able to nominate a different address immediately.
-        env.mock_all_auths();
-        client.nominate_super_admin(&nominee_b);
+        let unit_id = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &(env.ledger().timestamp() + 7 * 86400),
+            &Some(symbol_short!("donor")),
+            &None,
+        );
 
-        // nominee_b can accept.
-        env.mock_all_auths();
-        client.accept_super_admin();
+        let result = client.try_assign_transfer_rider(&bank, &unit_id, &Address::generate(&env));
+        assert_eq!(result, Err(Ok(Error::InvalidStatus)));
     }
 
-    #[test]
-    fn test_expired_nomination_replaced_by_new_nomination() {
-        let env = Env::default();
-        env.ledger().with_mut(|li| li.timestamp = 1_000_000);
-
-        let (_, _admin, client) = setup_contract_with_admin(&env);
-        let nominee_a = Address::generate(&env);
-        let nominee_b = Address::generate(&env);
+    /// Stand-in for the lifebank identity contract: roles are granted directly.
+    #[contract]
+    struct MockIdentity;
 
-        env.mock_all_auths();
-        client.nominate_super_admin(&nominee_a);
+    #[contractimpl]
+    impl MockIdentity {
+        pub fn grant(env: Env, address: Address, role: identity::Role) {
+            env.storage().persistent().set(&(address, role), &true);
+        }
 
-        // Let the nomination expire.
-        env.ledger()
-            .with_mut(|li| li.timestamp = 1_000_000 + NOMINATION_EXPIRY_SECONDS + 1);
+        pub fn has_role(env: Env, address: Address, role: identity::Role) -> bool {
+            env.storage()
+                .persistent()
+                .get(&(address, role))
+                .unwrap_or(false)
+        }
+    }
 
-        // A new nomination should succeed without error (lazy clear of expired entry).
-        env.mock_all_auths();
-        client.nominate_super_admin(&nominee_b);
+    fn setup_reserved_unit_with_riders(
+        env: &Env,
+        client: &HealthChainContractClient<'_>,
+        admin: &Address,
+        bank: &Address,
+        hospital: &Address,
+    ) -> (u64, Address, Address) {
+        let identity = env.register(MockIdentity, ());
+        let identity_client = MockIdentityClient::new(env, &identity);
+        let rider1 = Address::generate(env);
+        let rider2 = Address::generate(env);
+        identity_client.grant(&rider1, &identity::Role::Rider);
+        identity_client.grant(&rider2, &identity::Role::Rider);
+        client.set_identity_contract(admin, &identity);
 
-        // nominee_b can accept.
-        env.mock_all_auths();
-        client.accept_super_admin();
+        let unit_id = client.register_blood(
+            bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &(env.ledger().timestamp() + 7 * 86400),
+            &None,
+            &None,
+        );
+        client.allocate_blood(bank, &unit_id, hospital, &None, &None);
+        (unit_id, rider1, rider2)
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #24)")]
-    fn test_second_nomination_while_active_returns_nomination_pending() {
+    fn test_multi_hop_custody_through_two_riders() {
         let env = Env::default();
-        let (_, _admin, client) = setup_contract_with_admin(&env);
-        let nominee_a = Address::generate(&env);
-        let nominee_b = Address::generate(&env);
-
+        let (_, admin, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
         env.mock_all_auths();
-        client.nominate_super_admin(&nominee_a);
+        client.register_blood_bank(&bank);
 
-        // Second nomination while the first is still active must fail.
-        env.mock_all_auths();
-        client.nominate_super_admin(&nominee_b);
-    }
+        let (unit_id, rider1, rider2) =
+            setup_reserved_unit_with_riders(&env, &client, &admin, &bank, &hospital);
 
-    #[test]
-    fn test_nominate_super_admin_emits_admin_proposed_event() {
-        let env = Env::default();
-        env.ledger().with_mut(|li| li.timestamp = 1_000_000);
-        let (_, _admin, client) = setup_contract_with_admin(&env);
-        let nominee = Address::generate(&env);
+        // Addresses without the Rider role cannot take custody
+        let stranger = Address::generate(&env);
+        let result = client.try_initiate_hop(&bank, &unit_id, &stranger, &None, &None);
+        assert_eq!(result, Err(Ok(Error::InvalidCustodian)));
 
-        env.mock_all_auths();
-        client.nominate_super_admin(&nominee);
+        let hop1 = client.initiate_hop(&bank, &unit_id, &rider1, &None, &None);
+        assert_eq!(
+            client.get_blood_unit(&unit_id).status,
+            BloodStatus::InTransit
+        );
+        // Only one pending hop per unit
+        let result = client.try_initiate_hop(&bank, &unit_id, &rider2, &None, &None);
+        assert_eq!(result, Err(Ok(Error::InvalidStatus)));
+        let result = client.try_confirm_hop(&rider2, &hop1, &None, &None);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        client.confirm_hop(&rider1, &hop1, &None, &None);
+        assert_eq!(
+            client.get_current_custodian(&unit_id),
+            (rider1.clone(), CustodianKind::Rider)
+        );
+
+        // The bank no longer holds the unit
+        let result = client.try_initiate_hop(&bank, &unit_id, &rider2, &None, &None);
+        assert_eq!(result, Err(Ok(Error::NotCurrentCustodian)));
+
+        let hop2 = client.initiate_hop(&rider1, &unit_id, &rider2, &None, &None);
+        client.confirm_hop(&rider2, &hop2, &None, &None);
+        let hop3 = client.initiate_hop(&rider2, &unit_id, &hospital, &None, &None);
+        let event = client.get_custody_event(&hop3);
+        assert_eq!(event.from_kind, CustodianKind::Rider);
+        assert_eq!(event.to_kind, CustodianKind::Hospital);
+        client.confirm_transfer(&hospital, &hop3, &None, &None, &None);
 
-        let events = env.events().all();
-        // Find the admin.proposed event (last event emitted).
-        let (_, topics, data) = events.last().unwrap();
         assert_eq!(
-            Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap(),
-            symbol_short!("admin")
+            client.get_blood_unit(&unit_id).status,
+            BloodStatus::Delivered
         );
         assert_eq!(
-            Symbol::try_from_val(&env, &topics.get(1).unwrap()).unwrap(),
-            symbol_short!("proposed")
+            client.get_current_custodian(&unit_id),
+            (hospital, CustodianKind::Hospital)
         );
-        let event = AdminProposedEvent::try_from_val(&env, &data).unwrap();
-        assert_eq!(event.proposed_admin, nominee);
-        assert_eq!(event.nominated_at, 1_000_000);
-        assert_eq!(event.expires_at, 1_000_000 + NOMINATION_EXPIRY_SECONDS);
+        assert_eq!(client.get_custody_trail_metadata(&unit_id).total_events, 3);
     }
 
     #[test]
-    fn test_accept_super_admin_emits_admin_transferred_event() {
+    fn test_expired_rider_hop_leaves_unit_with_sending_rider() {
         let env = Env::default();
-        env.ledger().with_mut(|li| li.timestamp = 1_000_000);
-        let (_, admin, client) = setup_contract_with_admin(&env);
-        let nominee = Address::generate(&env);
-
+        let (_, admin, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
         env.mock_all_auths();
-        client.nominate_super_admin(&nominee);
+        client.register_blood_bank(&bank);
 
-        env.mock_all_auths();
-        client.accept_super_admin();
+        let (unit_id, rider1, rider2) =
+            setup_reserved_unit_with_riders(&env, &client, &admin, &bank, &hospital);
+
+        let hop1 = client.initiate_hop(&bank, &unit_id, &rider1, &None, &None);
+        client.confirm_hop(&rider1, &hop1, &None, &None);
+        let hop2 = client.initiate_hop(&rider1, &unit_id, &rider2, &None, &None);
+
+        let deadline = client.get_custody_event(&hop2).transfer_deadline;
+        env.ledger().set_timestamp(deadline);
+        client.expire_pending_transfer(&hop2);
 
-        let events = env.events().all();
-        let (_, topics, data) = events.last().unwrap();
         assert_eq!(
-            Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap(),
-            symbol_short!("admin")
+            client.get_blood_unit(&unit_id).status,
+            BloodStatus::InTransit
         );
         assert_eq!(
-            Symbol::try_from_val(&env, &topics.get(1).unwrap()).unwrap(),
-            symbol_short!("xfer")
+            client.get_current_custodian(&unit_id),
+            (rider1.clone(), CustodianKind::Rider)
         );
-        let event = AdminTransferredEvent::try_from_val(&env, &data).unwrap();
-        assert_eq!(event.previous_admin, admin);
-        assert_eq!(event.new_admin, nominee);
-        assert_eq!(event.transferred_at, 1_000_000);
+        // The rider can send the unit on again
+        client.initiate_hop(&rider1, &unit_id, &hospital, &None, &None);
+    }
+
+    /// Stand-in for the lifebank temperature contract.
+    #[contract]
+    struct MockTemperature;
+
+    #[contractimpl]
+    impl MockTemperature {
+        pub fn set_compromised(env: Env, unit_id: u64) {
+            env.storage().persistent().set(&unit_id, &true);
+        }
+
+        pub fn is_compromised(env: Env, unit_id: u64) -> bool {
+            env.storage().persistent().get(&unit_id).unwrap_or(false)
+        }
     }
 
     #[test]
-    fn test_cancel_nomination_emits_cancelled_event() {
+    fn test_temperature_breach_quarantines_delivered_unit() {
         let env = Env::default();
-        env.ledger().with_mut(|li| li.timestamp = 1_000_000);
-        let (_, _admin, client) = setup_contract_with_admin(&env);
-        let nominee = Address::generate(&env);
-
+        let (_, admin, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
         env.mock_all_auths();
-        client.nominate_super_admin(&nominee);
+        client.register_blood_bank(&bank);
 
-        env.mock_all_auths();
-        client.cancel_nomination();
+        let temperature = env.register(MockTemperature, ());
+        let temperature_client = MockTemperatureClient::new(&env, &temperature);
+        let result = client.try_set_temperature_contract(&bank, &temperature);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        client.set_temperature_contract(&admin, &temperature);
 
-        let events = env.events().all();
-        let (_, topics, data) = events.last().unwrap();
+        let (clean_unit, clean_event) =
+            setup_in_transit_unit(&env, &client, &bank, &hospital, 1_000_000);
         assert_eq!(
-            Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap(),
-            symbol_short!("admin")
+            client.confirm_transfer(&hospital, &clean_event, &None, &None, &None),
+            DeliveryOutcome::Delivered
         );
         assert_eq!(
-            Symbol::try_from_val(&env, &topics.get(1).unwrap()).unwrap(),
-            symbol_short!("nom_cxl")
+            client.get_blood_unit(&clean_unit).status,
+            BloodStatus::Delivered
+        );
+
+        let (breached_unit, breached_event) =
+            setup_in_transit_unit(&env, &client, &bank, &hospital, 1_000_100);
+        temperature_client.set_compromised(&breached_unit);
+        assert_eq!(
+            client.confirm_transfer(&hospital, &breached_event, &None, &None, &None),
+            DeliveryOutcome::DeliveredWithExcursion
+        );
+
+        let unit = client.get_blood_unit(&breached_unit);
+        assert_eq!(unit.status, BloodStatus::Quarantined);
+        assert_eq!(unit.recipient_hospital, Some(hospital.clone()));
+        assert_eq!(
+            client.get_custody_event(&breached_event).status,
+            CustodyStatus::Confirmed
         );
-        let event = AdminNominationCancelledEvent::try_from_val(&env, &data).unwrap();
-        assert_eq!(event.cancelled_nominee, nominee);
     }
 
+    // ── EVENT NAMESPACE TESTS ────────────────────────────────────────────────────
+
     #[test]
-    fn test_cancel_nomination_no_op_when_no_pending_nomination() {
+    fn test_events_default_to_no_namespace_prefix() {
         let env = Env::default();
-        let (_, _admin, client) = setup_contract_with_admin(&env);
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
 
         env.mock_all_auths();
-        // Should succeed without error even when nothing is pending.
-        client.cancel_nomination();
+        client.register_blood_bank(&bank);
+
+        assert_eq!(client.get_event_namespace(), Symbol::new(&env, ""));
+        let (_, topics, _) = env.events().all().last().unwrap();
+        assert_eq!(topics.len(), 2);
+        assert_eq!(
+            Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap(),
+            symbol_short!("bank")
+        );
     }
 
     #[test]
-    fn test_propose_admin_alias_works_and_emits_event() {
+    fn test_event_namespace_is_prepended_as_first_topic() {
         let env = Env::default();
-        env.ledger().with_mut(|li| li.timestamp = 2_000_000);
-        let (_, _admin, client) = setup_contract_with_admin(&env);
-        let new_admin = Address::generate(&env);
+        let (_, admin, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
 
         env.mock_all_auths();
-        client.propose_admin(&new_admin);
+        client.set_event_namespace(&admin, &symbol_short!("staging"));
+        assert_eq!(client.get_event_namespace(), symbol_short!("staging"));
 
-        let events = env.events().all();
-        let (_, topics, _) = events.last().unwrap();
+        client.register_blood_bank(&bank);
+        let (_, topics, _) = env.events().all().last().unwrap();
+        assert_eq!(topics.len(), 3);
         assert_eq!(
             Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap(),
-            symbol_short!("admin")
+            symbol_short!("staging")
         );
         assert_eq!(
             Symbol::try_from_val(&env, &topics.get(1).unwrap()).unwrap(),
-            symbol_short!("proposed")
+            symbol_short!("bank")
+        );
+        assert_eq!(
+            Symbol::try_from_val(&env, &topics.get(2).unwrap()).unwrap(),
+            symbol_short!("reg")
+        );
+
+        // Versioned events keep their schema marker as the final topic
+        let expiration = env.ledger().timestamp() + (7 * 86400);
+        client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &expiration,
+            &Some(symbol_short!("donor1")),
+            &None,
+        );
+        let (_, topics, _) = env.events().all().last().unwrap();
+        assert_eq!(topics.len(), 4);
+        assert_eq!(
+            Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap(),
+            symbol_short!("staging")
+        );
+        assert_eq!(
+            Symbol::try_from_val(&env, &topics.get(3).unwrap()).unwrap(),
+            symbol_short!("v1")
         );
     }
 
     #[test]
-    fn test_accept_admin_alias_completes_transfer() {
+    fn test_event_namespace_cleared_with_empty_symbol() {
         let env = Env::default();
-        let (_, _admin, client) = setup_contract_with_admin(&env);
-        let new_admin = Address::generate(&env);
+        let (_, admin, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
 
         env.mock_all_auths();
-        client.propose_admin(&new_admin);
+        client.set_event_namespace(&admin, &symbol_short!("prod"));
+        client.set_event_namespace(&admin, &Symbol::new(&env, ""));
 
-        env.mock_all_auths();
-        client.accept_admin();
+        client.register_blood_bank(&bank);
+        let (_, topics, _) = env.events().all().last().unwrap();
+        assert_eq!(topics.len(), 2);
+    }
+
+    #[test]
+    fn test_set_event_namespace_rejects_non_admin() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let intruder = Address::generate(&env);
 
-        // Verify new admin can exercise admin-only functions.
-        let bank = Address::generate(&env);
         env.mock_all_auths();
-        client.register_blood_bank(&bank);
-        assert!(client.is_blood_bank(&bank));
+        let result = client.try_set_event_namespace(&intruder, &symbol_short!("evil"));
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
     }
 
     // ── ORGANIZATION VERIFICATION TESTS ────────────────────────────────────────────────────
@@ -8017,6 +15343,7 @@ mod test {
             &450,
             &(env.ledger().timestamp() + 86400),
             &Some(Symbol::new(&env, "donor1")),
+            &None,
         );
         assert!(matches!(result, Err(Ok(Error::Unauthorized))));
 
@@ -1,4 +1,9 @@
-use soroban_sdk::{contracttype, Address, Bytes, String, Symbol, Vec};
+use soroban_sdk::{contracttype, symbol_short, Address, Bytes, Env, Map, String, Symbol, Vec};
+
+use crate::{
+    publish_event, CustodyEvent, CustodyStatus, DataKey, Error, PaymentTimeoutRefundedEvent,
+    CUSTODY_EVENTS, PAYMENTS,
+};
 
 pub const DEFAULT_DISPUTE_TIMEOUT_SECS: u64 = 72 * 60 * 60;
 pub const HIGH_VALUE_THRESHOLD: i128 = 10_000;
@@ -392,3 +397,84 @@ pub enum PaymentError {
     DuplicateApproval,
     Overflow,
 }
+
+/// Refund the payer of a unit's payment once its custody transfer has timed out.
+///
+/// Callable by anyone: funds only ever return to the payer. The unit's latest
+/// custody event decides the outcome:
+/// - `Pending`, `Recovered` or `Cancelled` at or past its `transfer_deadline`
+///   is refunded: the transfer auto-expired, or was rolled back once it had.
+///   Before the deadline it fails with `TransferNotExpired`, even if the
+///   transfer was already called off.
+/// - `Confirmed` or `Rejected`, or a unit that never had a transfer, fails
+///   with `InvalidStatus`.
+pub(crate) fn claim_timed_out_payment(env: &Env, unit_id: u64) -> Result<(), Error> {
+    let payment_id: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::UnitPayment(unit_id))
+        .ok_or(Error::PaymentNotFound)?;
+
+    let mut payments: Map<u64, Payment> = env
+        .storage()
+        .persistent()
+        .get(&PAYMENTS)
+        .ok_or(Error::PaymentNotFound)?;
+    let mut payment = payments.get(payment_id).ok_or(Error::PaymentNotFound)?;
+    if !payment.can_transition_to(PaymentStatus::Refunded) {
+        return Err(Error::InvalidPaymentStatus);
+    }
+
+    // The pending index is cleared once a transfer ends, so look at the last
+    // event ever recorded for the unit instead
+    let event_id: String = env
+        .storage()
+        .persistent()
+        .get::<_, Vec<String>>(&DataKey::UnitCustodyEvents(unit_id))
+        .and_then(|event_ids| event_ids.last())
+        .ok_or(Error::InvalidStatus)?;
+    let custody_events: Map<String, CustodyEvent> = env
+        .storage()
+        .persistent()
+        .get(&CUSTODY_EVENTS)
+        .unwrap_or(Map::new(env));
+    let custody_event = custody_events
+        .get(event_id.clone())
+        .ok_or(Error::InvalidStatus)?;
+
+    let current_time = env.ledger().timestamp();
+    match custody_event.status {
+        CustodyStatus::Pending | CustodyStatus::Recovered | CustodyStatus::Cancelled => {
+            if current_time < custody_event.transfer_deadline {
+                return Err(Error::TransferNotExpired);
+            }
+        }
+        CustodyStatus::Confirmed | CustodyStatus::Rejected => {
+            return Err(Error::InvalidStatus);
+        }
+    }
+
+    payment.status = PaymentStatus::Refunded;
+    payment.escrow_released_at = Some(current_time);
+    payments.set(payment_id, payment.clone());
+    env.storage().persistent().set(&PAYMENTS, &payments);
+
+    publish_event(
+        env,
+        (
+            symbol_short!("payment"),
+            symbol_short!("timeout"),
+            symbol_short!("v1"),
+        ),
+        PaymentTimeoutRefundedEvent {
+            payment_id,
+            unit_id,
+            custody_event_id: event_id,
+            refunded_to: payment.payer,
+            amount: payment.amount,
+            refunded_at: current_time,
+        },
+    );
+
+    Ok(())
+}
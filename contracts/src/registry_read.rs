@@ -8,9 +8,13 @@
 //! Every function performs **only** storage reads (`get`) and pure computation.
 //! The public contract entry-points in `lib.rs` delegate to these free functions.
 
-use soroban_sdk::{symbol_short, vec, Address, Env, Map, Symbol, Vec};
+use soroban_sdk::{symbol_short, vec, Address, Bytes, BytesN, Env, Map, Symbol, Vec};
 
-use crate::{BloodStatus, BloodUnit, DataKey, Error, BLOOD_UNITS};
+use crate::{
+    clamp_limit, consent, load_unit, next_unit_id, BankPage, BloodComponent, BloodStatus,
+    BloodType, BloodUnit, DataKey, Error, LifecycleState, ReservationAverage, ReservationWindow,
+    TypeSummary, UnitPage, BLOOD_BANKS,
+};
 
 // ── READ ──────────────────────────────────────────────────────────────────────
 
@@ -18,19 +22,66 @@ use crate::{BloodStatus, BloodUnit, DataKey, Error, BLOOD_UNITS};
 ///
 /// Returns `Err(Error::UnitNotFound)` when the ID does not exist in storage.
 pub fn get_unit(env: &Env, unit_id: u64) -> Result<BloodUnit, Error> {
-    let units: Map<u64, BloodUnit> = env
+    load_unit(env, unit_id).ok_or(Error::UnitNotFound)
+}
+
+/// Page through every unit in ID order.
+///
+/// `cursor` is the first unit ID to consider (0 starts from the beginning);
+/// `next_cursor` is the ID to resume from. `limit` is clamped by [`clamp_limit`].
+pub fn list_units(env: &Env, cursor: u64, limit: u32) -> UnitPage {
+    let end = next_unit_id(env);
+    let limit = clamp_limit(limit);
+    let mut items = vec![env];
+    let mut id = cursor.max(1);
+    while id < end && items.len() < limit {
+        if let Some(unit) = load_unit(env, id) {
+            items.push_back(unit);
+        }
+        id += 1;
+    }
+
+    UnitPage {
+        items,
+        next_cursor: (id < end).then_some(id),
+    }
+}
+
+/// Page through registered blood bank addresses in registry order.
+///
+/// `cursor` is the zero-based position of the first bank to return. Includes
+/// banks in every lifecycle state; use `get_blood_bank_state` to filter.
+pub fn list_blood_banks(env: &Env, cursor: u64, limit: u32) -> BankPage {
+    let banks: Map<Address, LifecycleState> = env
         .storage()
         .persistent()
-        .get(&BLOOD_UNITS)
+        .get(&BLOOD_BANKS)
         .unwrap_or(Map::new(env));
 
-    units.get(unit_id).ok_or(Error::UnitNotFound)
+    let keys = banks.keys();
+    let total = keys.len() as u64;
+    let limit = clamp_limit(limit) as u64;
+    let mut items = vec![env];
+    let mut position = cursor;
+    while position < total && (items.len() as u64) < limit {
+        if let Some(bank_id) = keys.get(position as u32) {
+            items.push_back(bank_id);
+        }
+        position += 1;
+    }
+
+    BankPage {
+        items,
+        next_cursor: (position < total).then_some(position),
+    }
 }
 
-/// Return all blood units registered by a specific blood bank.
+/// Return a page of the units currently in a blood bank's inventory.
 ///
-/// Uses the BankUnits index — O(k) where k is the number of units for this bank.
-pub fn get_units_by_bank(env: &Env, bank_id: Address) -> Vec<BloodUnit> {
+/// Uses the BankUnits index, in registration order. Delivered and expired
+/// units have already been removed from the index. Skips the first `offset`
+/// entries and returns at most `limit` units (clamped by [`clamp_limit`]).
+pub fn get_units_by_bank(env: &Env, bank_id: Address, offset: u32, limit: u32) -> Vec<BloodUnit> {
     let key = DataKey::BankUnits(bank_id);
     let ids: Vec<u64> = env
         .storage()
@@ -38,21 +89,213 @@ pub fn get_units_by_bank(env: &Env, bank_id: Address) -> Vec<BloodUnit> {
         .get(&key)
         .unwrap_or(Vec::new(env));
 
-    let units: Map<u64, BloodUnit> = env
+    let limit = clamp_limit(limit);
+    let mut result = vec![env];
+    for id in ids.iter().skip(offset as usize) {
+        if result.len() >= limit {
+            break;
+        }
+        if let Some(unit) = load_unit(env, id) {
+            result.push_back(unit);
+        }
+    }
+    result
+}
+
+/// Sum the volume (ml) of a bank's Available, non-expired units of `blood_type`,
+/// optionally restricted to one `component`.
+///
+/// Iterates the BankUnits index only — O(k) where k is the number of units for
+/// this bank. Saturates at `u32::MAX` rather than overflowing.
+pub fn get_available_volume(
+    env: &Env,
+    bank_id: Address,
+    blood_type: BloodType,
+    component: Option<BloodComponent>,
+) -> u32 {
+    let key = DataKey::BankUnits(bank_id);
+    let ids: Vec<u64> = env
         .storage()
         .persistent()
-        .get(&BLOOD_UNITS)
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+
+    let current_time = env.ledger().timestamp();
+    let mut total: u32 = 0;
+    for id in ids.iter() {
+        if let Some(unit) = load_unit(env, id) {
+            if unit.blood_type == blood_type
+                && unit.component.matches(component)
+                && unit.status == BloodStatus::Available
+                && unit.expiration_date > current_time
+            {
+                total = total.saturating_add(unit.quantity);
+            }
+        }
+    }
+    total
+}
+
+/// Number of units of `blood_type` currently in Available status at a bank.
+///
+/// Without a component filter this reads only the length of the
+/// `AvailableByType(bank_id, blood_type)` index; with one, each indexed unit is
+/// loaded to check its component. Units past their expiry date still count
+/// until they are marked Expired.
+pub fn get_available_count(
+    env: &Env,
+    bank_id: Address,
+    blood_type: BloodType,
+    component: Option<BloodComponent>,
+) -> u32 {
+    let ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::AvailableByType(bank_id, blood_type))
+        .unwrap_or(Vec::new(env));
+    if component.is_none() {
+        return ids.len();
+    }
+
+    let mut count: u32 = 0;
+    for id in ids.iter() {
+        if let Some(unit) = load_unit(env, id) {
+            if unit.component.matches(component) {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+/// Inventory counters for each blood type a bank has ever held.
+///
+/// Reads the incrementally maintained `TypeSummary` entries (one per blood
+/// type) instead of scanning units. Types with no entry are omitted.
+pub fn get_inventory_summary(env: &Env, bank_id: Address) -> Map<BloodType, TypeSummary> {
+    let mut summary = Map::new(env);
+    for blood_type in [
+        BloodType::APositive,
+        BloodType::ANegative,
+        BloodType::BPositive,
+        BloodType::BNegative,
+        BloodType::ABPositive,
+        BloodType::ABNegative,
+        BloodType::OPositive,
+        BloodType::ONegative,
+    ] {
+        let key = DataKey::TypeSummary(bank_id.clone(), blood_type);
+        if let Some(counts) = env.storage().persistent().get::<DataKey, TypeSummary>(&key) {
+            summary.set(blood_type, counts);
+        }
+    }
+    summary
+}
+
+/// Available, non-expired units of `blood_type` holding at least `min_volume` ml,
+/// optionally restricted to one `component`.
+///
+/// Walks the `AvailableByType` index of each active blood bank, so only
+/// candidate units are loaded. Returns at most `limit` units (clamped by
+/// [`clamp_limit`]), grouped by bank with the earliest-expiring units first.
+pub fn find_available(
+    env: &Env,
+    blood_type: BloodType,
+    component: Option<BloodComponent>,
+    min_volume: u32,
+    limit: u32,
+) -> Vec<BloodUnit> {
+    let banks: Map<Address, LifecycleState> = env
+        .storage()
+        .persistent()
+        .get(&BLOOD_BANKS)
         .unwrap_or(Map::new(env));
 
+    let limit = clamp_limit(limit);
+    let current_time = env.ledger().timestamp();
     let mut result = vec![env];
-    for id in ids.iter() {
-        if let Some(unit) = units.get(id) {
+    for (bank_id, state) in banks.iter() {
+        if state != LifecycleState::Active {
+            continue;
+        }
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AvailableByType(bank_id, blood_type))
+            .unwrap_or(Vec::new(env));
+        for id in ids.iter() {
+            if result.len() >= limit {
+                return result;
+            }
+            if let Some(unit) = load_unit(env, id) {
+                if unit.component.matches(component)
+                    && unit.quantity >= min_volume
+                    && unit.expiration_date > current_time
+                {
+                    result.push_back(unit);
+                }
+            }
+        }
+    }
+    result
+}
+
+/// Available, non-expired units matching both `blood_type` and `component`.
+///
+/// Lets callers match component therapy (e.g. O+ platelets) separately from
+/// whole blood of the same ABO/Rh group. `max_results` is clamped by [`clamp_limit`].
+pub fn query_by_component(
+    env: &Env,
+    blood_type: BloodType,
+    component: BloodComponent,
+    max_results: u32,
+) -> Vec<BloodUnit> {
+    let limit = clamp_limit(max_results);
+    let current_time = env.ledger().timestamp();
+    let mut result = vec![env];
+    for id in 1..next_unit_id(env) {
+        if result.len() >= limit {
+            break;
+        }
+        let unit = match load_unit(env, id) {
+            Some(unit) => unit,
+            None => continue,
+        };
+        if unit.blood_type == blood_type
+            && unit.component == component
+            && unit.status == BloodStatus::Available
+            && unit.expiration_date > current_time
+        {
             result.push_back(unit);
         }
     }
     result
 }
 
+/// Deterministic SHA-256 digest of a bank's inventory state.
+///
+/// Folds `(unit_id, status, expiration_date)` for every unit in the bank's
+/// BankUnits index, in index order. Any registration, status change (e.g.
+/// allocation or discard) or expiry update produces a different digest, so
+/// operators can compare an off-chain snapshot against the chain.
+pub fn inventory_checksum(env: &Env, bank_id: Address) -> BytesN<32> {
+    let ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::BankUnits(bank_id))
+        .unwrap_or(Vec::new(env));
+
+    let mut input = Bytes::new(env);
+    for id in ids.iter() {
+        if let Some(unit) = load_unit(env, id) {
+            input.extend_from_array(&id.to_be_bytes());
+            input.push_back(unit.status as u8);
+            input.extend_from_array(&unit.expiration_date.to_be_bytes());
+        }
+    }
+    env.crypto().sha256(&input).into()
+}
+
 /// Return `true` when the blood unit's expiration date is in the past.
 ///
 /// Returns `Err(Error::UnitNotFound)` when the unit does not exist.
@@ -62,12 +305,208 @@ pub fn is_expired(env: &Env, unit_id: u64) -> Result<bool, Error> {
     Ok(unit.expiration_date <= current_time || unit.status == BloodStatus::Expired)
 }
 
+/// Seconds elapsed since the unit was registered.
+///
+/// Returns `Err(Error::UnitNotFound)` when the unit does not exist.
+pub fn get_unit_age(env: &Env, unit_id: u64) -> Result<u64, Error> {
+    let unit = get_unit(env, unit_id)?;
+    Ok(env
+        .ledger()
+        .timestamp()
+        .saturating_sub(unit.registration_timestamp))
+}
+
+/// Return IDs of a bank's units whose age is strictly greater than `age_seconds`.
+///
+/// Iterates the BankUnits index in registration order, skipping the first
+/// `start` matches and returning at most `limit` IDs.
+pub fn get_units_older_than(
+    env: &Env,
+    bank_id: Address,
+    age_seconds: u64,
+    start: u32,
+    limit: u32,
+) -> Vec<u64> {
+    let key = DataKey::BankUnits(bank_id);
+    let ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+
+    let current_time = env.ledger().timestamp();
+    let limit = clamp_limit(limit);
+    let mut result = vec![env];
+    let mut skipped: u32 = 0;
+    for id in ids.iter() {
+        if result.len() >= limit {
+            break;
+        }
+        if let Some(unit) = load_unit(env, id) {
+            if current_time.saturating_sub(unit.registration_timestamp) <= age_seconds {
+                continue;
+            }
+            if skipped < start {
+                skipped += 1;
+                continue;
+            }
+            result.push_back(id);
+        }
+    }
+    result
+}
+
+/// Page through units allocated to `hospital_id` with `from <= reserved_at < to`.
+///
+/// Iterates the HospitalUnits index, so allocations that were later cancelled
+/// are not returned. `start` counts matching units to skip.
+pub fn get_allocations_for_hospital(
+    env: &Env,
+    hospital_id: Address,
+    from: u64,
+    to: u64,
+    start: u32,
+    limit: u32,
+) -> Vec<u64> {
+    let ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::HospitalUnits(hospital_id))
+        .unwrap_or(Vec::new(env));
+
+    let limit = clamp_limit(limit);
+    let mut result = vec![env];
+    let mut skipped: u32 = 0;
+    for id in ids.iter() {
+        if result.len() >= limit {
+            break;
+        }
+        let window: ReservationWindow = match env
+            .storage()
+            .persistent()
+            .get(&DataKey::ReservationWindow(id))
+        {
+            Some(window) => window,
+            None => continue,
+        };
+        if window.reserved_at < from || window.reserved_at >= to {
+            continue;
+        }
+        if skipped < start {
+            skipped += 1;
+            continue;
+        }
+        result.push_back(id);
+    }
+    result
+}
+
+/// Seconds between reservation and resolution for a unit's latest reservation.
+///
+/// Returns `None` if the unit was never reserved or its reservation is still open.
+pub fn get_reservation_duration(env: &Env, unit_id: u64) -> Option<u64> {
+    let window: ReservationWindow = env
+        .storage()
+        .persistent()
+        .get(&DataKey::ReservationWindow(unit_id))?;
+    window
+        .resolved_at
+        .map(|resolved_at| resolved_at.saturating_sub(window.reserved_at))
+}
+
+/// Hold deadline of a unit's open reservation.
+///
+/// Returns `None` if the unit was never reserved or its latest reservation
+/// has already been confirmed or released.
+pub fn get_reserved_until(env: &Env, unit_id: u64) -> Option<u64> {
+    let window: ReservationWindow = env
+        .storage()
+        .persistent()
+        .get(&DataKey::ReservationWindow(unit_id))?;
+    match window.resolved_at {
+        Some(_) => None,
+        None => Some(window.reserved_until),
+    }
+}
+
+/// Running average reservation duration for a bank.
+///
+/// Returns zero samples when no reservation at the bank has resolved yet.
+pub fn get_avg_reservation_time(env: &Env, bank_id: Address) -> ReservationAverage {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AvgReservationTime(bank_id))
+        .unwrap_or(ReservationAverage {
+            samples: 0,
+            average_seconds: 0,
+        })
+}
+
+/// The unit `unit_id` was split from, if it is an aliquot.
+pub fn get_parent_unit(env: &Env, unit_id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::UnitParent(unit_id))
+}
+
+/// IDs of the aliquots `unit_id` was split into; empty if it was never split.
+pub fn get_child_units(env: &Env, unit_id: u64) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::UnitChildren(unit_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Source unit IDs combined into pooled unit `unit_id`; empty if it is not a pool.
+pub fn get_pool_sources(env: &Env, unit_id: u64) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PoolSources(unit_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// The pooled unit `unit_id` was combined into, if it was pooled.
+pub fn get_pooled_into(env: &Env, unit_id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PooledInto(unit_id))
+}
+
+/// Return the units a single bank collected from `donor_id`, in registration order.
+///
+/// Reads the per-bank `DonorUnits(bank_id, donor_id)` index, so donor IDs that
+/// collide across banks never mix. Used for lookback investigations.
+/// Returns nothing for a donor linked to a hash without active consent.
+pub fn get_units_by_bank_donor(env: &Env, bank_id: Address, donor_id: Symbol) -> Vec<BloodUnit> {
+    if !consent::donor_queries_allowed(env, &donor_id) {
+        return Vec::new(env);
+    }
+    let key = DataKey::DonorUnits(bank_id, donor_id);
+    let ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+
+    let mut result = vec![env];
+    for id in ids.iter() {
+        if let Some(unit) = load_unit(env, id) {
+            result.push_back(unit);
+        }
+    }
+    result
+}
+
 /// Return all blood units donated by the given `donor_id` symbol.
 ///
 /// Uses the DonorUnits index with a sentinel zero-address for cross-bank queries.
 /// Anonymous units (donor_id == "ANON") are excluded unless the caller explicitly
-/// passes `symbol_short!("ANON")`.
+/// passes `symbol_short!("ANON")`. Returns nothing for a donor linked to a
+/// hash without active consent.
 pub fn get_units_by_donor(env: &Env, donor_id: Symbol) -> Vec<BloodUnit> {
+    if !consent::donor_queries_allowed(env, &donor_id) {
+        return Vec::new(env);
+    }
     // Use a sentinel zero-address for global donor index
     let sentinel = soroban_sdk::Address::from_contract_id(
         env,
@@ -80,15 +519,9 @@ pub fn get_units_by_donor(env: &Env, donor_id: Symbol) -> Vec<BloodUnit> {
         .get(&key)
         .unwrap_or(Vec::new(env));
 
-    let units: Map<u64, BloodUnit> = env
-        .storage()
-        .persistent()
-        .get(&BLOOD_UNITS)
-        .unwrap_or(Map::new(env));
-
     let mut result = vec![env];
     for id in ids.iter() {
-        if let Some(unit) = units.get(id) {
+        if let Some(unit) = load_unit(env, id) {
             if unit.donor_id == symbol_short!("ANON") && donor_id != symbol_short!("ANON") {
                 continue;
             }
@@ -6,22 +6,29 @@
 //! The public contract entry-points in `lib.rs` delegate to these free functions.
 //!
 //! ## Storage Write Audit (PR checklist)
-//! - [x] `register_unit`          — writes BLOOD_UNITS, NEXT_ID, BankUnits index, DonorUnits index, StatusUnits index
-//! - [x] `update_status`          — writes BLOOD_UNITS, StatusUnits index
-//! - [x] `expire_unit`            — 1 read + 1 write of BLOOD_UNITS, StatusUnits index
-//! - [x] `expire_unit_in_map`     — pure in-memory mutation; no storage I/O (used by batch)
-//! - [x] `check_and_expire_batch` — 1 read + N in-memory mutations + 1 write of BLOOD_UNITS
+//! - [x] `register_unit`          — writes Unit(id), NEXT_ID, BankUnits, DonorUnits, AvailableByType, StatusUnits, ExpiryBucket indexes and TypeSummary
+//! - [x] `update_status`          — writes Unit(id), StatusUnits index
+//! - [x] `split_unit`             — writes parent and child Unit(id)s, NEXT_ID, UnitParent, UnitChildren, every index `register_unit` touches, UnitTests/UnitProcessing copies and the parent's LotUnits
+//! - [x] `pool_units`             — writes source and pooled Unit(id)s, NEXT_ID, PoolSources, PooledInto and the pooled unit's bank, available, expiry and status indexes
+//! - [x] `expire_unit`            — delegates to `expire_one`
+//! - [x] `expire_one`             — writes Unit(id), StatusUnits index, BankUnits index
+//! - [x] `check_and_expire_batch` — one `expire_one` per requested ID
+//! - [x] `expire_units_batch`     — one `expire_one` per due unit; rewrites ExpiryBucket(day), EXPIRY_CURSOR
 //! - [x] `allocate_blood` (lib.rs) — writes HospitalUnits index on allocation; deindex on cancel_allocation
+//! - [x] `transfer_unit_ownership` (lib.rs) — moves BankUnits, DonorUnits, AvailableByType entries and TypeSummary counts between banks
 
-use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
+use soroban_sdk::{symbol_short, Address, Env, Symbol, Vec};
 
 use crate::{
+    adjust_type_summary, bump_persistent,
     constants::{
-        MAX_BATCH_EXPIRY_SIZE, MAX_QUANTITY_ML, MAX_SHELF_LIFE_DAYS, MIN_QUANTITY_ML,
-        MIN_SHELF_LIFE_DAYS, SECONDS_PER_DAY,
+        MAX_BATCH_EXPIRY_SIZE, MAX_EXPIRY_SWEEP_DAYS, MAX_POOL_SOURCES, MAX_SPLIT_ALIQUOTS,
+        MIN_ALIQUOT_ML, MIN_SHELF_LIFE_DAYS, SECONDS_PER_DAY,
     },
-    get_next_id, index_bank_unit, index_donor_unit, record_status_change, reindex_status,
-    BloodComponent, BloodRegisteredEvent, BloodStatus, BloodType, BloodUnit, Error, BLOOD_UNITS,
+    deindex_bank_unit, get_next_id, index_available_unit, index_bank_unit, index_donor_unit,
+    index_expiry_unit, load_unit, publish_event, record_status_change, reindex_status, save_unit,
+    BloodComponent, BloodRegisteredEvent, BloodStatus, BloodType, BloodUnit, DataKey, Error,
+    ProcessingStep, TestResult, ALLOW_BACKDATED, EXPIRY_CURSOR,
 };
 
 // ── WRITE ─────────────────────────────────────────────────────────────────────
@@ -40,20 +47,30 @@ pub fn register_unit(
     expiration_timestamp: u64,
     donor_id: Option<Symbol>,
 ) -> Result<u64, Error> {
-    // Validate quantity
-    if !(MIN_QUANTITY_ML..=MAX_QUANTITY_ML).contains(&quantity_ml) {
+    // Validate quantity against the component's accepted volume range
+    let profile = component.profile();
+    if !(profile.min_volume_ml..=profile.max_volume_ml).contains(&quantity_ml) {
         return Err(Error::InvalidQuantity);
     }
 
     // Validate expiration
     let current_time = env.ledger().timestamp();
     let min_expiration = current_time + (MIN_SHELF_LIFE_DAYS * SECONDS_PER_DAY);
-    let max_expiration = current_time + (MAX_SHELF_LIFE_DAYS * SECONDS_PER_DAY);
-
-    if expiration_timestamp <= current_time || expiration_timestamp < min_expiration {
-        return Err(Error::InvalidExpiration);
-    }
-    if expiration_timestamp > max_expiration {
+    // Shelf life ceiling depends on the component (e.g. platelets last 5 days).
+    let max_expiration = current_time + (profile.max_shelf_life_days * SECONDS_PER_DAY);
+
+    if expiration_timestamp <= current_time {
+        // Backdated units are only accepted while the admin migration flag is on;
+        // shelf-life bounds do not apply to historical records.
+        let allow_backdated: bool = env
+            .storage()
+            .instance()
+            .get(&ALLOW_BACKDATED)
+            .unwrap_or(false);
+        if !allow_backdated {
+            return Err(Error::AlreadyExpired);
+        }
+    } else if expiration_timestamp < min_expiration || expiration_timestamp > max_expiration {
         return Err(Error::InvalidExpiration);
     }
 
@@ -74,30 +91,20 @@ pub fn register_unit(
         allocation_timestamp: None,
         transfer_timestamp: None,
         delivery_timestamp: None,
+        lot_id: None,
     };
 
-    let mut units: Map<u64, BloodUnit> = env
-        .storage()
-        .persistent()
-        .get(&BLOOD_UNITS)
-        .unwrap_or(Map::new(env));
-
-    units.set(unit_id, blood_unit);
-    env.storage().persistent().set(&BLOOD_UNITS, &units);
+    save_unit(env, &blood_unit);
 
     // Maintain bank and donor indexes
     index_bank_unit(env, &bank_id, unit_id);
     let resolved_donor = donor_id.clone().unwrap_or(symbol_short!("ANON"));
     index_donor_unit(env, &bank_id, &resolved_donor, unit_id);
+    index_available_unit(env, &bank_id, blood_type, unit_id, expiration_timestamp);
+    index_expiry_unit(env, unit_id, expiration_timestamp);
+    adjust_type_summary(env, &blood_unit, None, Some(BloodStatus::Available));
     // New unit starts as Available — seed the status index directly
-    let status_key = crate::DataKey::StatusUnits(BloodStatus::Available);
-    let mut status_ids: soroban_sdk::Vec<u64> = env
-        .storage()
-        .persistent()
-        .get(&status_key)
-        .unwrap_or(soroban_sdk::Vec::new(env));
-    status_ids.push_back(unit_id);
-    env.storage().persistent().set(&status_key, &status_ids);
+    seed_available_status(env, unit_id);
 
     // Record initial status
     record_status_change(
@@ -120,7 +127,8 @@ pub fn register_unit(
         donor_id,
     };
 
-    env.events().publish(
+    publish_event(
+        env,
         (
             symbol_short!("blood"),
             symbol_short!("register"),
@@ -143,18 +151,11 @@ pub fn update_status(
     new_status: BloodStatus,
     actor: Address,
 ) -> Result<(), Error> {
-    let mut units: Map<u64, BloodUnit> = env
-        .storage()
-        .persistent()
-        .get(&BLOOD_UNITS)
-        .unwrap_or(Map::new(env));
-
-    let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+    let mut unit = load_unit(env, unit_id).ok_or(Error::UnitNotFound)?;
     let old_status = unit.status;
 
     unit.status = new_status;
-    units.set(unit_id, unit);
-    env.storage().persistent().set(&BLOOD_UNITS, &units);
+    save_unit(env, &unit);
 
     // Maintain status index
     reindex_status(env, unit_id, old_status, new_status);
@@ -164,45 +165,282 @@ pub fn update_status(
     Ok(())
 }
 
-/// Force mark a blood unit as expired.
+/// Split an Available unit into pediatric aliquots of the given `volumes`.
 ///
-/// Loads the full `BLOOD_UNITS` map, delegates the mutation to
-/// [`expire_unit_in_map`], then writes the map back.  Use this for
-/// single-unit expiry; for bulk expiry prefer [`check_and_expire_batch`]
-/// which amortises the storage round-trip across all units.
-pub fn expire_unit(env: &Env, unit_id: u64) -> Result<(), Error> {
-    let mut units: Map<u64, BloodUnit> = env
+/// Each aliquot becomes a new Available [`BloodUnit`] that inherits the
+/// parent's type, component, donor, bank, expiry and lab results, with a
+/// `UnitParent` link back to it. The parent moves to `Split` and leaves the
+/// bank's inventory. Volumes must number 2..=`MAX_SPLIT_ALIQUOTS`, each be at
+/// least `MIN_ALIQUOT_ML`, and sum exactly to the parent's quantity.
+///
+/// Returns the child unit IDs in the order of `volumes`.
+pub fn split_unit(
+    env: &Env,
+    bank_id: Address,
+    unit_id: u64,
+    volumes: Vec<u32>,
+) -> Result<Vec<u64>, Error> {
+    let mut parent = load_unit(env, unit_id).ok_or(Error::UnitNotFound)?;
+    if parent.bank_id != bank_id {
+        return Err(Error::Unauthorized);
+    }
+    let current_time = env.ledger().timestamp();
+    if parent.expiration_date <= current_time {
+        return Err(Error::UnitExpired);
+    }
+    if parent.status != BloodStatus::Available {
+        return Err(Error::InvalidStatus);
+    }
+
+    if volumes.len() < 2 || volumes.len() > MAX_SPLIT_ALIQUOTS {
+        return Err(Error::InvalidQuantity);
+    }
+    let mut total: u32 = 0;
+    for volume in volumes.iter() {
+        if volume < MIN_ALIQUOT_ML {
+            return Err(Error::InvalidQuantity);
+        }
+        total = total.checked_add(volume).ok_or(Error::ArithmeticError)?;
+    }
+    if total != parent.quantity {
+        return Err(Error::InvalidQuantity);
+    }
+
+    // Retire the parent first so its Available index and counters are released
+    parent.status = BloodStatus::Split;
+    save_unit(env, &parent);
+    reindex_status(env, unit_id, BloodStatus::Available, BloodStatus::Split);
+    deindex_bank_unit(env, &bank_id, unit_id);
+    record_status_change(
+        env,
+        unit_id,
+        BloodStatus::Available,
+        BloodStatus::Split,
+        bank_id.clone(),
+    );
+
+    let test_results: Option<Vec<TestResult>> =
+        env.storage().persistent().get(&DataKey::UnitTests(unit_id));
+    let processing: Option<Vec<ProcessingStep>> = env
         .storage()
         .persistent()
-        .get(&BLOOD_UNITS)
-        .unwrap_or(Map::new(env));
+        .get(&DataKey::UnitProcessing(unit_id));
+
+    let mut children = Vec::new(env);
+    for volume in volumes.iter() {
+        let child_id = get_next_id(env);
+        let child = BloodUnit {
+            id: child_id,
+            quantity: volume,
+            registration_timestamp: current_time,
+            status: BloodStatus::Available,
+            recipient_hospital: None,
+            allocation_timestamp: None,
+            transfer_timestamp: None,
+            delivery_timestamp: None,
+            ..parent.clone()
+        };
+        save_unit(env, &child);
+
+        index_bank_unit(env, &bank_id, child_id);
+        index_donor_unit(env, &bank_id, &child.donor_id, child_id);
+        index_available_unit(
+            env,
+            &bank_id,
+            child.blood_type,
+            child_id,
+            child.expiration_date,
+        );
+        index_expiry_unit(env, child_id, child.expiration_date);
+        adjust_type_summary(env, &child, None, Some(BloodStatus::Available));
+        seed_available_status(env, child_id);
+        record_status_change(
+            env,
+            child_id,
+            BloodStatus::Available,
+            BloodStatus::Available,
+            bank_id.clone(),
+        );
+
+        let parent_key = DataKey::UnitParent(child_id);
+        env.storage().persistent().set(&parent_key, &unit_id);
+        bump_persistent(env, &parent_key);
+        if let Some(ref results) = test_results {
+            let tests_key = DataKey::UnitTests(child_id);
+            env.storage().persistent().set(&tests_key, results);
+            bump_persistent(env, &tests_key);
+        }
+        if let Some(ref steps) = processing {
+            let processing_key = DataKey::UnitProcessing(child_id);
+            env.storage().persistent().set(&processing_key, steps);
+            bump_persistent(env, &processing_key);
+        }
+        // Aliquots inherit the parent's lot, so keep the lot index in step
+        if let Some(ref lot_id) = child.lot_id {
+            let lot_key = DataKey::LotUnits(lot_id.clone());
+            let mut lot_units: Vec<u64> = env
+                .storage()
+                .persistent()
+                .get(&lot_key)
+                .unwrap_or(Vec::new(env));
+            lot_units.push_back(child_id);
+            env.storage().persistent().set(&lot_key, &lot_units);
+            bump_persistent(env, &lot_key);
+        }
+
+        children.push_back(child_id);
+    }
+
+    let children_key = DataKey::UnitChildren(unit_id);
+    env.storage().persistent().set(&children_key, &children);
+    bump_persistent(env, &children_key);
+
+    publish_event(
+        env,
+        (
+            symbol_short!("blood"),
+            symbol_short!("split"),
+            symbol_short!("v1"),
+        ),
+        (unit_id, children.clone(), current_time),
+    );
+
+    Ok(children)
+}
+
+/// Combine several Available units into one pooled product.
+///
+/// Sources must belong to `bank_id`, be unexpired and share blood type and
+/// component; 2..=`MAX_POOL_SOURCES` distinct units are accepted. The pooled
+/// unit holds their combined volume, inherits the earliest source expiry and
+/// records every source under `PoolSources`; each source moves to `Pooled`
+/// with a `PooledInto` link. The pooled unit has no single donor (`POOL`) and
+/// no lab results of its own, so it must be screened separately while
+/// screening is required.
+///
+/// Returns the pooled unit's ID.
+pub fn pool_units(env: &Env, bank_id: Address, unit_ids: Vec<u64>) -> Result<u64, Error> {
+    if unit_ids.len() < 2 || unit_ids.len() > MAX_POOL_SOURCES {
+        return Err(Error::InvalidQuantity);
+    }
+
+    let current_time = env.ledger().timestamp();
+    let mut sources: Vec<BloodUnit> = Vec::new(env);
+    let mut quantity: u32 = 0;
+    let mut expiration_date = u64::MAX;
+    for (index, unit_id) in unit_ids.iter().enumerate() {
+        if unit_ids.first_index_of(unit_id) != Some(index as u32) {
+            return Err(Error::DuplicateRequest);
+        }
+        let unit = load_unit(env, unit_id).ok_or(Error::UnitNotFound)?;
+        if unit.bank_id != bank_id {
+            return Err(Error::Unauthorized);
+        }
+        if unit.expiration_date <= current_time {
+            return Err(Error::UnitExpired);
+        }
+        if unit.status != BloodStatus::Available {
+            return Err(Error::InvalidStatus);
+        }
+        if let Some(first) = sources.first() {
+            if unit.blood_type != first.blood_type || unit.component != first.component {
+                return Err(Error::InvalidTransition);
+            }
+        }
+        quantity = quantity
+            .checked_add(unit.quantity)
+            .ok_or(Error::ArithmeticError)?;
+        expiration_date = expiration_date.min(unit.expiration_date);
+        sources.push_back(unit);
+    }
 
-    let expired = expire_unit_in_map(env, unit_id, &mut units)?;
+    let first = sources.first().ok_or(Error::InvalidQuantity)?;
+    let pooled_id = get_next_id(env);
+    let pooled = BloodUnit {
+        id: pooled_id,
+        blood_type: first.blood_type,
+        component: first.component,
+        quantity,
+        expiration_date,
+        donor_id: symbol_short!("POOL"),
+        location: first.location.clone(),
+        bank_id: bank_id.clone(),
+        registration_timestamp: current_time,
+        status: BloodStatus::Available,
+        recipient_hospital: None,
+        allocation_timestamp: None,
+        transfer_timestamp: None,
+        delivery_timestamp: None,
+        lot_id: None,
+    };
 
-    // Only persist if something actually changed.
-    if expired {
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
+    // Retire the sources before the pooled unit joins the Available indexes
+    for mut source in sources.iter() {
+        let source_id = source.id;
+        source.status = BloodStatus::Pooled;
+        save_unit(env, &source);
+        reindex_status(env, source_id, BloodStatus::Available, BloodStatus::Pooled);
+        deindex_bank_unit(env, &bank_id, source_id);
+        record_status_change(
+            env,
+            source_id,
+            BloodStatus::Available,
+            BloodStatus::Pooled,
+            bank_id.clone(),
+        );
+
+        let link_key = DataKey::PooledInto(source_id);
+        env.storage().persistent().set(&link_key, &pooled_id);
+        bump_persistent(env, &link_key);
     }
 
+    save_unit(env, &pooled);
+    index_bank_unit(env, &bank_id, pooled_id);
+    index_available_unit(env, &bank_id, pooled.blood_type, pooled_id, expiration_date);
+    index_expiry_unit(env, pooled_id, expiration_date);
+    adjust_type_summary(env, &pooled, None, Some(BloodStatus::Available));
+    seed_available_status(env, pooled_id);
+    record_status_change(
+        env,
+        pooled_id,
+        BloodStatus::Available,
+        BloodStatus::Available,
+        bank_id,
+    );
+
+    let sources_key = DataKey::PoolSources(pooled_id);
+    env.storage().persistent().set(&sources_key, &unit_ids);
+    bump_persistent(env, &sources_key);
+
+    publish_event(
+        env,
+        (
+            symbol_short!("blood"),
+            symbol_short!("pool"),
+            symbol_short!("v1"),
+        ),
+        (pooled_id, unit_ids, current_time),
+    );
+
+    Ok(pooled_id)
+}
+
+/// Force mark a blood unit as expired.
+///
+/// Delegates to [`expire_one`]; a unit that is already `Expired` is left
+/// untouched and is not an error.
+pub fn expire_unit(env: &Env, unit_id: u64) -> Result<(), Error> {
+    expire_one(env, unit_id)?;
     Ok(())
 }
 
-/// Mutate a single entry inside an already-loaded `units` map.
+/// Transition a single unit to `Expired` in its own storage entry.
 ///
 /// Returns `Ok(true)` when the unit was transitioned to `Expired`,
-/// `Ok(false)` when it was already `Expired` (no-op), and
+/// `Ok(false)` when it was already `Expired` (no-op, nothing written), and
 /// `Err` when the unit does not exist or has not yet passed its expiry date.
-///
-/// This function performs **no storage I/O** — the caller is responsible for
-/// loading the map beforehand and persisting it afterwards.  Keeping I/O out
-/// of the hot loop in [`check_and_expire_batch`] reduces the per-batch cost
-/// from O(n) reads/writes to a single read + single write.
-fn expire_unit_in_map(
-    env: &Env,
-    unit_id: u64,
-    units: &mut Map<u64, BloodUnit>,
-) -> Result<bool, Error> {
-    let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+fn expire_one(env: &Env, unit_id: u64) -> Result<bool, Error> {
+    let mut unit = load_unit(env, unit_id).ok_or(Error::UnitNotFound)?;
 
     let current_time = env.ledger().timestamp();
     if current_time < unit.expiration_date {
@@ -213,13 +451,22 @@ fn expire_unit_in_map(
         // Already expired — nothing to do, not an error.
         return Ok(false);
     }
+    if matches!(
+        unit.status,
+        BloodStatus::Recalled | BloodStatus::Split | BloodStatus::Pooled
+    ) {
+        // A recall outranks expiry, and split or pooled units live on in the
+        // units made from them; keep the recorded status.
+        return Ok(false);
+    }
 
     let old_status = unit.status;
     unit.status = BloodStatus::Expired;
-    units.set(unit_id, unit);
+    save_unit(env, &unit);
 
-    // Keep the status index and history in sync.
+    // Keep the status and bank indexes and history in sync.
     reindex_status(env, unit_id, old_status, BloodStatus::Expired);
+    deindex_bank_unit(env, &unit.bank_id, unit_id);
     record_status_change(
         env,
         unit_id,
@@ -233,42 +480,121 @@ fn expire_unit_in_map(
 
 /// Batch check and expire units.
 ///
-/// Loads `BLOOD_UNITS` **once**, mutates each requested unit in-memory via
-/// [`expire_unit_in_map`], then writes the map back **once**.  This reduces
-/// the storage cost from O(n) reads + O(n) writes (the old per-unit loop) to
-/// a single read + single write regardless of batch size.
+/// Each unit lives under its own `DataKey::Unit` entry, so the cost is one
+/// read per requested ID plus one write per unit that actually expires.
+/// Units that are already expired, not yet due, or unknown are skipped.
 pub fn check_and_expire_batch(env: &Env, unit_ids: Vec<u64>) -> Result<Vec<u64>, Error> {
     if unit_ids.len() > MAX_BATCH_EXPIRY_SIZE {
         return Err(Error::BatchSizeExceeded);
     }
 
-    // Single read for the entire batch.
-    let mut units: Map<u64, BloodUnit> = env
-        .storage()
-        .persistent()
-        .get(&BLOOD_UNITS)
-        .unwrap_or(Map::new(env));
-
     let mut expired_ids = Vec::new(env);
-    let mut any_changed = false;
-
     for i in 0..unit_ids.len() {
         let unit_id = unit_ids.get(i).unwrap();
-        match expire_unit_in_map(env, unit_id, &mut units) {
-            Ok(true) => {
-                expired_ids.push_back(unit_id);
-                any_changed = true;
+        // Ok(false) = already expired, Err(_) = not yet expired or not found.
+        if let Ok(true) = expire_one(env, unit_id) {
+            expired_ids.push_back(unit_id);
+        }
+    }
+
+    Ok(expired_ids)
+}
+
+/// Sweep the expiry index and expire up to `max_count` past-due units.
+///
+/// Walks `ExpiryBucket` day buckets in ascending order from `EXPIRY_CURSOR`,
+/// reading at most [`MAX_EXPIRY_SWEEP_DAYS`] buckets and expiring at most
+/// [`MAX_BATCH_EXPIRY_SIZE`] units per call, so the cost is bounded by those
+/// two limits plus the size of the buckets read. Each expiry goes through
+/// [`expire_one`] and emits the usual `status/change` event.
+///
+/// Units that are already Expired, Delivered or Discarded are dropped from the
+/// index without touching the unit. A bucket that still holds units (not yet
+/// due, or left over once `max_count` is reached) stops the sweep and the
+/// cursor stays on it. Returns the number of units expired; keepers call
+/// repeatedly until it returns 0.
+pub fn expire_units_batch(env: &Env, max_count: u32) -> u32 {
+    let mut day: u64 = match env.storage().instance().get(&EXPIRY_CURSOR) {
+        Some(day) => day,
+        None => return 0,
+    };
+
+    let max_count = max_count.min(MAX_BATCH_EXPIRY_SIZE);
+    let now = env.ledger().timestamp();
+    let today = now / SECONDS_PER_DAY;
+    let mut expired: u32 = 0;
+    let mut scanned: u32 = 0;
+
+    while day <= today && scanned < MAX_EXPIRY_SWEEP_DAYS && expired < max_count {
+        let key = DataKey::ExpiryBucket(day);
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        scanned += 1;
+
+        let mut remaining = Vec::new(env);
+        for unit_id in ids.iter() {
+            if expired >= max_count {
+                remaining.push_back(unit_id);
+                continue;
+            }
+            let unit = match load_unit(env, unit_id) {
+                Some(unit) => unit,
+                None => continue,
+            };
+            match unit.status {
+                BloodStatus::Expired
+                | BloodStatus::Delivered
+                | BloodStatus::Discarded
+                | BloodStatus::Recalled
+                | BloodStatus::Split
+                | BloodStatus::Pooled => {}
+                _ if unit.expiration_date > now => remaining.push_back(unit_id),
+                _ => {
+                    if let Ok(true) = expire_one(env, unit_id) {
+                        expired += 1;
+                    }
+                }
             }
-            // Ok(false) = already expired, skip silently.
-            // Err(_)    = not yet expired or not found, skip silently.
-            _ => {}
+        }
+
+        if remaining.is_empty() {
+            env.storage().persistent().remove(&key);
+            day += 1;
+        } else {
+            env.storage().persistent().set(&key, &remaining);
+            break;
         }
     }
 
-    // Single write — only when at least one unit changed.
-    if any_changed {
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
+    env.storage().instance().set(&EXPIRY_CURSOR, &day);
+
+    if expired > 0 {
+        publish_event(
+            env,
+            (
+                symbol_short!("blood"),
+                symbol_short!("expire"),
+                symbol_short!("v1"),
+            ),
+            (expired, day),
+        );
     }
 
-    Ok(expired_ids)
+    expired
+}
+
+/// Append a newly created unit to the Available status bucket.
+fn seed_available_status(env: &Env, unit_id: u64) {
+    let status_key = DataKey::StatusUnits(BloodStatus::Available);
+    let mut status_ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&status_key)
+        .unwrap_or(Vec::new(env));
+    status_ids.push_back(unit_id);
+    env.storage().persistent().set(&status_key, &status_ids);
+    bump_persistent(env, &status_key);
 }
@@ -9,9 +9,27 @@
 //! | `NEXT_PAYMENT_ID`            | Instance   | Low-frequency, small; kept in instance                 |
 //! | `NEXT_DISPUTE_ID`            | Instance   | Low-frequency, small; kept in instance                 |
 //! | `DISPUTE_TIMEOUT`            | Instance   | Config value; lives with contract                      |
+//! | `ALLOW_BACKDATED`            | Instance   | Migration flag; lives with contract                    |
+//! | `CUSTODY_COUNTS`             | Instance   | Aggregate custody counters; small, fixed size          |
+//! | `DataKey::RecoveryAddress`   | Instance   | Set once at initialization; emergency admin rotation   |
+//! | `EVENT_NAMESPACE`            | Instance   | Config value; absent means no topic prefix             |
 //! | `MULTISIG_CONFIG`            | Persistent | May be updated; needs long-lived storage               |
 //! | `BLOOD_BANKS`, `HOSPITALS`   | Persistent | Registry maps; grow with onboarding, rent-sensitive    |
-//! | `BLOOD_UNITS`                | Persistent | Core inventory map; **highest rent risk**              |
+//! | `LABS`                       | Persistent | Testing lab registry map; grows with onboarding        |
+//! | `SCREENING_REQUIRED`         | Instance   | Config flag; absent means screening is not enforced    |
+//! | `HOLD_DURATION`              | Instance   | Default reservation hold; falls back to pickup window  |
+//! | `CROSSMATCH_WINDOW`          | Instance   | Crossmatch validity window; absent means not enforced  |
+//! | `STORAGE_VERSION`            | Instance   | Layout version; absent means legacy, see `migrate`     |
+//! | `PAUSED`                     | Instance   | Pause bitmask; absent means nothing is paused          |
+//! | `TRANSFER_DEADLINE`          | Instance   | Pending-transfer deadline; falls back to 4 hours       |
+//! | `IDENTITY_CONTRACT`          | Instance   | Identity contract used for rider role checks           |
+//! | `TEMPERATURE_CONTRACT`       | Instance   | Temperature contract queried on delivery               |
+//! | `DUAL_AUTH_REQUIRED`         | Instance   | Confirmations need both custodians; off when absent    |
+//! | `DataKey::UnitTests`         | Persistent | Latest lab result per test type for a unit             |
+//! | `DataKey::UnitProcessing`    | Persistent | Append-only processing log (irradiation, washing, ...) |
+//! | `DataKey::Crossmatch`        | Persistent | Latest crossmatch recorded by the receiving hospital   |
+//! | `DataKey::Unit`              | Persistent | Per-unit record; bumped with `bump_rent_for_unit`      |
+//! | `BLOOD_UNITS`                | Persistent | Legacy inventory map; drained by `migrate_units`       |
 //! | `REQUESTS`                   | Persistent | Request map; grows with usage, rent-sensitive          |
 //! | `REQUEST_KEYS`               | Persistent | Dedup index; grows with requests                       |
 //! | `PAYMENTS`                   | Persistent | Payment map; grows with usage                          |
@@ -25,8 +43,23 @@
 //! | `PENDING_APPROVALS`          | Persistent | Active multisig votes; cleaned up on execution         |
 //! | `OrgKey::Org(addr)`          | Persistent | Organization records; permanent registry               |
 //! | `DataKey::DonorUnits`        | Persistent | Donor index; grows with donations                      |
+//! | `DataKey::Lot`, `BankLots`   | Persistent | Collection lot records and per-bank lot index          |
+//! | `DataKey::LotUnits`          | Persistent | Collection lot index; units tagged for lot recalls     |
+//! | `DataKey::Deferral`          | Persistent | Donor deferral by salted hash; no PII stored           |
+//! | `DataKey::CustodyRoot`       | Persistent | Latest custody Merkle root per unit                    |
+//! | `DataKey::Consent`           | Persistent | Donor consent form hash/version; revocable             |
+//! | `DataKey::DonorLink`         | Persistent | donor_id → salted donor hash, first link wins          |
+//! | `DataKey::UnitParent`        | Persistent | Aliquot -> parent link written by `split_unit`         |
+//! | `DataKey::UnitChildren`      | Persistent | Parent -> aliquot IDs written by `split_unit`          |
+//! | `DataKey::PoolSources`       | Persistent | Pooled unit -> source IDs written by `pool_units`      |
+//! | `DataKey::PooledInto`        | Persistent | Source -> pooled unit link written by `pool_units`     |
 //! | `DataKey::HospitalUnits`     | Persistent | Hospital index; units allocated/in-transit/delivered   |
 //! | `DataKey::UnitCustodyIndex`  | Persistent | Per-unit pending custody event lookup (O(1) confirm)   |
+//! | `DataKey::UnitPayment`       | Persistent | Unit -> payment link for timeout refunds               |
+//! | `DataKey::UnitRider`         | Persistent | Rider of an in-transit unit; removed when it ends      |
+//! | `DataKey::ReservationWindow` | Persistent | Reserved/resolved timestamps of a unit's reservation   |
+//! | `DataKey::AvgReservationTime`| Persistent | Per-bank running average reservation duration          |
+//! | `ArchiveKey::Unit(id)`       | Persistent | Compact `ArchivedUnit` left behind by `archive_unit`   |
 //!
 //! ## Retention / Archival Strategy
 //!
@@ -34,7 +67,9 @@
 //! - `ADMIN`, counters, config keys (instance storage — no per-entry rent)
 //! - `BloodUnit` records: the canonical inventory state is always needed for
 //!   allocation, expiry checks, and audit. Terminal units (Delivered, Discarded,
-//!   Expired) are compacted to a `ArchivedUnitSummary` after `ARCHIVE_AFTER_DAYS`.
+//!   Expired, Recalled, Split, Pooled) are compacted to an `ArchivedUnit` by
+//!   `archive_unit` after `ARCHIVE_AFTER_DAYS`; the full record is removed from
+//!   `DataKey::Unit`.
 //! - `OrgKey::Org` records: verified status must remain queryable.
 //! - `UnitTrailMeta`: tiny metadata struct, kept permanently.
 //! - `PAYMENT_STATS`: aggregate counters, kept permanently.
@@ -56,9 +91,12 @@
 //!
 //! ## Rent Bump Policy
 //! Persistent entries must have their TTL extended before they expire.
-//! Call `bump_rent_for_unit` after any write to a blood unit and its history.
+//! Unit records, per-unit indexes, status history and custody trail entries
+//! are bumped with `bump_persistent` whenever they are written. Units that
+//! sit idle are covered by `bump_storage_page`, which a keeper walks page by
+//! page through the `bump_storage` entry point.
 //! The `bump_all_registries` admin function extends the TTL of the shared
-//! registry maps (`BLOOD_BANKS`, `HOSPITALS`, `BLOOD_UNITS`, `REQUESTS`, etc.)
+//! registry maps (`BLOOD_BANKS`, `HOSPITALS`, `REQUESTS`, etc.)
 //! which are the highest-risk keys for rent expiry.
 //!
 //! ## Off-chain Consistency After Archival
@@ -72,12 +110,13 @@
 //! 4. Use `get_archived_history_summary` to obtain the first/last timestamps
 //!    and total count for display without loading the full history.
 
-use soroban_sdk::{contracttype, symbol_short, Env, Vec};
+use soroban_sdk::{contracttype, symbol_short, xdr::ToXdr, BytesN, Env, Vec};
 
 use crate::{
-    BloodStatus, BloodUnit, CustodyEvent, CustodyStatus, DataKey, Error, StatusChangeEvent,
-    BLOOD_BANKS, BLOOD_UNITS, CUSTODY_EVENTS, DISPUTES, DISPUTE_METADATA, HISTORY, HOSPITALS,
-    PAYMENTS, PAYMENT_STATS, PENDING_APPROVALS, REQUESTS, REQUEST_KEYS,
+    load_unit, next_unit_id, publish_event, BloodStatus, BloodUnit, CustodyEvent, CustodyStatus,
+    DataKey, Error, StatusChangeEvent, TrailMetadata, BLOOD_BANKS, CUSTODY_EVENTS, DISPUTES,
+    DISPUTE_METADATA, HISTORY, HOSPITALS, LABS, NEXT_ID, PAYMENTS, PAYMENT_STATS,
+    PENDING_APPROVALS, REQUESTS, REQUEST_KEYS,
 };
 
 // ── Constants ──────────────────────────────────────────────────────────────────
@@ -96,6 +135,9 @@ pub const ARCHIVE_AFTER_DAYS: u64 = 30;
 
 pub const SECONDS_PER_DAY: u64 = 86_400;
 
+/// Number of unit IDs covered by one `bump_storage` page.
+pub const BUMP_PAGE_SIZE: u64 = 20;
+
 // ── Archival marker types ──────────────────────────────────────────────────────
 
 /// Compact summary stored in place of a full `Vec<StatusChangeEvent>` after
@@ -123,6 +165,27 @@ pub enum ArchiveKey {
     HistorySummary(u64),
     /// Archived custody-event summary for a blood unit.
     CustodySummary(u64),
+    /// Compact record left in place of an archived `BloodUnit`.
+    Unit(u64),
+}
+
+/// Minimal record stored in place of a terminal `BloodUnit` once it is archived.
+///
+/// `data_hash` is the SHA-256 of the unit's XDR encoding at archival time, so
+/// an off-chain copy of the full record can be checked against the chain.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArchivedUnit {
+    /// SHA-256 of the XDR-encoded `BloodUnit`.
+    pub data_hash: BytesN<32>,
+    /// Terminal status the unit was archived in.
+    pub final_status: BloodStatus,
+    /// Timestamp the unit was registered.
+    pub registered_at: u64,
+    /// Timestamp the unit reached its terminal status.
+    pub terminal_at: u64,
+    /// Ledger sequence at which archival was performed.
+    pub archived_at_ledger: u32,
 }
 
 /// Compact summary stored after custody events for a terminal unit have been
@@ -145,34 +208,95 @@ pub struct ArchivedCustodySummary {
 /// Extend the TTL of a single persistent key to at least `MIN_TTL_LEDGERS`.
 ///
 /// Call this after every write to a persistent key to prevent rent expiry.
-/// No-op if the key does not exist.
+/// No-op if the key does not exist (`extend_ttl` on a missing entry traps).
 pub fn bump_persistent<K>(env: &Env, key: &K)
 where
     K: soroban_sdk::IntoVal<Env, soroban_sdk::Val>,
 {
-    env.storage()
-        .persistent()
-        .extend_ttl(key, MIN_TTL_LEDGERS, EXTENDED_TTL_LEDGERS);
+    if env.storage().persistent().has(key) {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, MIN_TTL_LEDGERS, EXTENDED_TTL_LEDGERS);
+    }
 }
 
 /// Bump TTL for all per-unit storage keys associated with `unit_id`.
 ///
-/// Should be called after any write that touches a blood unit or its history.
+/// Covers the unit record, its status history, custody trail pages and
+/// metadata, and the per-unit custody/reservation/payment links.
 pub fn bump_rent_for_unit(env: &Env, unit_id: u64) {
     // Blood unit record
-    bump_persistent(env, &BLOOD_UNITS);
+    bump_persistent(env, &DataKey::Unit(unit_id));
 
     // Status history
-    let history_key = (HISTORY, unit_id);
-    env.storage()
-        .persistent()
-        .extend_ttl(&history_key, MIN_TTL_LEDGERS, EXTENDED_TTL_LEDGERS);
+    bump_persistent(env, &(HISTORY, unit_id));
 
-    // Trail metadata
+    // Custody trail: metadata plus every page it references
     let meta_key = DataKey::UnitTrailMeta(unit_id);
-    env.storage()
-        .persistent()
-        .extend_ttl(&meta_key, MIN_TTL_LEDGERS, EXTENDED_TTL_LEDGERS);
+    let metadata: Option<TrailMetadata> = env.storage().persistent().get(&meta_key);
+    bump_persistent(env, &meta_key);
+    if let Some(metadata) = metadata {
+        for page in 0..metadata.total_pages {
+            bump_persistent(env, &DataKey::UnitTrailPage(unit_id, page));
+        }
+    }
+
+    // Per-unit links
+    bump_persistent(env, &DataKey::UnitCustodyEvents(unit_id));
+    bump_persistent(env, &DataKey::UnitCustodyIndex(unit_id));
+    bump_persistent(env, &DataKey::UnitPayment(unit_id));
+    bump_persistent(env, &DataKey::UnitRider(unit_id));
+    bump_persistent(env, &DataKey::ReservationWindow(unit_id));
+    bump_persistent(env, &DataKey::UnitTests(unit_id));
+    bump_persistent(env, &DataKey::UnitProcessing(unit_id));
+    bump_persistent(env, &DataKey::Crossmatch(unit_id));
+    bump_persistent(env, &DataKey::UnitParent(unit_id));
+    bump_persistent(env, &DataKey::UnitChildren(unit_id));
+    bump_persistent(env, &DataKey::PoolSources(unit_id));
+    bump_persistent(env, &DataKey::PooledInto(unit_id));
+}
+
+/// Bump TTL for one page of units and the shared index entries they sit in.
+///
+/// Page `n` covers unit IDs `n * BUMP_PAGE_SIZE + 1 ..= (n + 1) * BUMP_PAGE_SIZE`.
+/// Also extends `NEXT_ID`. For each unit still on record this extends
+/// [`bump_rent_for_unit`]'s keys plus its bank, donor, status,
+/// available-by-type, summary, expiry and hospital index entries. Archived or
+/// unknown IDs are skipped. Returns the next page to bump, or `None` once the
+/// page runs past the highest unit ID.
+pub fn bump_storage_page(env: &Env, page: u32) -> Option<u32> {
+    let start = (page as u64) * BUMP_PAGE_SIZE + 1;
+    let end = next_unit_id(env);
+    bump_persistent(env, &NEXT_ID);
+    for unit_id in start..end.min(start + BUMP_PAGE_SIZE) {
+        let unit = match load_unit(env, unit_id) {
+            Some(unit) => unit,
+            None => continue,
+        };
+        bump_rent_for_unit(env, unit_id);
+
+        bump_persistent(env, &DataKey::BankUnits(unit.bank_id.clone()));
+        bump_persistent(
+            env,
+            &DataKey::DonorUnits(unit.bank_id.clone(), unit.donor_id.clone()),
+        );
+        bump_persistent(env, &DataKey::StatusUnits(unit.status));
+        bump_persistent(
+            env,
+            &DataKey::AvailableByType(unit.bank_id.clone(), unit.blood_type),
+        );
+        bump_persistent(env, &DataKey::TypeSummary(unit.bank_id, unit.blood_type));
+        bump_persistent(
+            env,
+            &DataKey::ExpiryBucket(unit.expiration_date / SECONDS_PER_DAY),
+        );
+        if let Some(hospital) = unit.recipient_hospital {
+            bump_persistent(env, &DataKey::HospitalUnits(hospital));
+        }
+    }
+
+    let next_start = start + BUMP_PAGE_SIZE;
+    (next_start < end).then_some(page + 1)
 }
 
 /// Bump TTL for all shared registry maps.
@@ -183,7 +307,7 @@ pub fn bump_all_registries(env: &Env) {
     for key in &[
         BLOOD_BANKS,
         HOSPITALS,
-        BLOOD_UNITS,
+        LABS,
         REQUESTS,
         REQUEST_KEYS,
         PAYMENTS,
@@ -193,9 +317,8 @@ pub fn bump_all_registries(env: &Env) {
         PAYMENT_STATS,
         PENDING_APPROVALS,
     ] {
-        env.storage()
-            .persistent()
-            .extend_ttl(key, MIN_TTL_LEDGERS, EXTENDED_TTL_LEDGERS);
+        // Registries that have never been written (e.g. no labs yet) are skipped
+        bump_persistent(env, key);
     }
 }
 
@@ -205,7 +328,12 @@ pub fn bump_all_registries(env: &Env) {
 pub fn is_terminal_status(status: BloodStatus) -> bool {
     matches!(
         status,
-        BloodStatus::Delivered | BloodStatus::Discarded | BloodStatus::Expired
+        BloodStatus::Delivered
+            | BloodStatus::Discarded
+            | BloodStatus::Expired
+            | BloodStatus::Recalled
+            | BloodStatus::Split
+            | BloodStatus::Pooled
     )
 }
 
@@ -242,15 +370,7 @@ pub fn is_eligible_for_archival(
 /// Returns `Ok(true)` if archival was performed, `Ok(false)` if the unit is
 /// not yet eligible, and `Err` if the unit does not exist.
 pub fn archive_unit_history(env: &Env, unit_id: u64) -> Result<bool, Error> {
-    use soroban_sdk::Map;
-
-    let units: Map<u64, BloodUnit> = env
-        .storage()
-        .persistent()
-        .get(&BLOOD_UNITS)
-        .unwrap_or(Map::new(env));
-
-    let unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+    let unit = load_unit(env, unit_id).ok_or(Error::UnitNotFound)?;
 
     let history_key = (HISTORY, unit_id);
     let history: Vec<StatusChangeEvent> = env
@@ -282,7 +402,8 @@ pub fn archive_unit_history(env: &Env, unit_id: u64) -> Result<bool, Error> {
     // Remove full history to reclaim rent
     env.storage().persistent().remove(&history_key);
 
-    env.events().publish(
+    publish_event(
+        env,
         (symbol_short!("archive"), symbol_short!("hist")),
         (unit_id, summary.total_events, summary.archived_at_ledger),
     );
@@ -301,13 +422,7 @@ pub fn archive_unit_history(env: &Env, unit_id: u64) -> Result<bool, Error> {
 pub fn archive_custody_events(env: &Env, unit_id: u64) -> Result<bool, Error> {
     use soroban_sdk::{Map, String as SorobanString};
 
-    let units: Map<u64, BloodUnit> = env
-        .storage()
-        .persistent()
-        .get(&BLOOD_UNITS)
-        .unwrap_or(Map::new(env));
-
-    let unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+    let unit = load_unit(env, unit_id).ok_or(Error::UnitNotFound)?;
 
     if !is_terminal_status(unit.status) {
         return Ok(false);
@@ -351,7 +466,7 @@ pub fn archive_custody_events(env: &Env, unit_id: u64) -> Result<bool, Error> {
         if let Some(event) = custody_events.get(event_id.clone()) {
             match event.status {
                 CustodyStatus::Confirmed => confirmed += 1,
-                CustodyStatus::Cancelled => cancelled += 1,
+                CustodyStatus::Cancelled | CustodyStatus::Rejected => cancelled += 1,
                 CustodyStatus::Pending | CustodyStatus::Recovered => {}
             }
             if event.initiated_at > last_event_at {
@@ -380,7 +495,8 @@ pub fn archive_custody_events(env: &Env, unit_id: u64) -> Result<bool, Error> {
     env.storage().persistent().set(&summary_key, &summary);
     bump_persistent(env, &summary_key);
 
-    env.events().publish(
+    publish_event(
+        env,
         (symbol_short!("archive"), symbol_short!("cust")),
         (unit_id, confirmed, cancelled, env.ledger().sequence()),
     );
@@ -388,8 +504,78 @@ pub fn archive_custody_events(env: &Env, unit_id: u64) -> Result<bool, Error> {
     Ok(true)
 }
 
+/// Timestamp of the unit's last status change.
+///
+/// Falls back to the archived history summary when the full history has
+/// already been compacted, and to the registration time when neither exists.
+fn terminal_timestamp(env: &Env, unit: &BloodUnit) -> u64 {
+    let history: Vec<StatusChangeEvent> = env
+        .storage()
+        .persistent()
+        .get(&(HISTORY, unit.id))
+        .unwrap_or(Vec::new(env));
+    if let Some(last_event) = history.last() {
+        return last_event.timestamp;
+    }
+    match get_archived_history_summary(env, unit.id) {
+        Some(summary) => summary.last_event_at,
+        None => unit.registration_timestamp,
+    }
+}
+
+/// Replace a terminal blood unit's full record with a compact `ArchivedUnit`.
+///
+/// The unit must be in a terminal status (see [`is_terminal_status`]) and at least
+/// `ARCHIVE_AFTER_DAYS` must have passed since its last status change. The
+/// `DataKey::Unit` entry is removed; index entries that still reference the
+/// ID are skipped by readers once the record is gone.
+///
+/// Returns `Ok(true)` if archival was performed, `Ok(false)` if the unit is
+/// not yet eligible, and `Err` if the unit does not exist.
+pub fn archive_unit(env: &Env, unit_id: u64) -> Result<bool, Error> {
+    let unit = load_unit(env, unit_id).ok_or(Error::UnitNotFound)?;
+
+    if !is_terminal_status(unit.status) {
+        return Ok(false);
+    }
+
+    let terminal_at = terminal_timestamp(env, &unit);
+    let current_time = env.ledger().timestamp();
+    if current_time < terminal_at.saturating_add(ARCHIVE_AFTER_DAYS * SECONDS_PER_DAY) {
+        return Ok(false);
+    }
+
+    let archived = ArchivedUnit {
+        data_hash: env.crypto().sha256(&unit.clone().to_xdr(env)).into(),
+        final_status: unit.status,
+        registered_at: unit.registration_timestamp,
+        terminal_at,
+        archived_at_ledger: env.ledger().sequence(),
+    };
+
+    let archive_key = ArchiveKey::Unit(unit_id);
+    env.storage().persistent().set(&archive_key, &archived);
+    bump_persistent(env, &archive_key);
+
+    // Remove the full record to reclaim rent
+    env.storage().persistent().remove(&DataKey::Unit(unit_id));
+
+    publish_event(
+        env,
+        (symbol_short!("archive"), symbol_short!("unit")),
+        (unit_id, archived.final_status, archived.archived_at_ledger),
+    );
+
+    Ok(true)
+}
+
 // ── Read helpers for archived data ─────────────────────────────────────────────
 
+/// Retrieve the archived record for a unit, if it has been archived.
+pub fn get_archived_unit(env: &Env, unit_id: u64) -> Option<ArchivedUnit> {
+    env.storage().persistent().get(&ArchiveKey::Unit(unit_id))
+}
+
 /// Retrieve the archived history summary for a unit, if it has been compacted.
 pub fn get_archived_history_summary(env: &Env, unit_id: u64) -> Option<ArchivedHistorySummary> {
     env.storage()
@@ -0,0 +1,37 @@
+//! # temperature
+//!
+//! Cold-chain checks against the lifebank temperature contract.
+//!
+//! Once the admin configures the contract with `set_temperature_contract`,
+//! `confirm_transfer` asks it whether the unit was compromised in transit. A
+//! compromised unit is still handed over, but lands in quarantine at the
+//! hospital instead of being available for transfusion.
+
+use soroban_sdk::{contractclient, Address, Env};
+
+use crate::TEMPERATURE_CONTRACT;
+
+#[contractclient(name = "TemperatureClient")]
+#[allow(dead_code)]
+pub trait TemperatureInterface {
+    fn is_compromised(env: Env, unit_id: u64) -> bool;
+}
+
+/// Whether the temperature contract reports an active breach for the unit.
+///
+/// Always `false` while no temperature contract is configured. A configured
+/// contract that cannot be invoked counts as a breach: an unverifiable cold
+/// chain must not release blood for transfusion.
+pub fn has_active_breach(env: &Env, unit_id: u64) -> bool {
+    let Some(contract) = env
+        .storage()
+        .instance()
+        .get::<_, Address>(&TEMPERATURE_CONTRACT)
+    else {
+        return false;
+    };
+    !matches!(
+        TemperatureClient::new(env, &contract).try_is_compromised(&unit_id),
+        Ok(Ok(false))
+    )
+}
@@ -6,8 +6,9 @@ use crate::payments::{
     TransactionMetadata, DEFAULT_DISPUTE_TIMEOUT_SECS, HIGH_VALUE_THRESHOLD,
 };
 use crate::{
-    HealthChainContract, HealthChainContractClient, ADMIN, DISPUTES, DISPUTE_METADATA,
-    ESCROW_ACCOUNTS, MULTISIG_CONFIG, PAYMENTS, PAYMENT_STATS, PENDING_APPROVALS,
+    BloodComponent, BloodType, CustodyEvent, CustodyStatus, Error, HealthChainContract,
+    HealthChainContractClient, ADMIN, CUSTODY_EVENTS, DISPUTES, DISPUTE_METADATA, ESCROW_ACCOUNTS, MULTISIG_CONFIG, PAYMENTS, PAYMENT_STATS,
+    DEFAULT_TRANSFER_DEADLINE_SECONDS, PENDING_APPROVALS,
 };
 
 use soroban_sdk::{
@@ -767,7 +768,7 @@ fn high_value_release_requires_threshold_votes_and_prevents_duplicates() {
     });
 
     let duplicate_attempt = client.try_propose_release(&payment_id, &signer_one);
-    assert!(duplicate_attempt.is_err());
+    assert_eq!(duplicate_attempt, Err(Ok(crate::Error::DuplicateApproval)));
 
     // Update conditions to allow signer_two to also pass the approver check.
     satisfy_escrow_conditions(&env, &contract_id, payment_id, &signer_two);
@@ -833,8 +834,9 @@ fn escrow_conditions_block_release_when_unmet() {
 
     // Default conditions: medical_records_verified=false — release must be blocked.
     let result = client.try_propose_release(&payment_id, &admin);
-    assert!(
-        result.is_err(),
+    assert_eq!(
+        result,
+        Err(Ok(crate::Error::EscrowNotReleasable)),
         "release must fail when escrow conditions are unmet"
     );
 }
@@ -878,7 +880,11 @@ fn escrow_conditions_block_release_before_min_timestamp() {
 
     // Ledger timestamp is 0 < 9_999_999 — must be blocked.
     let result = client.try_propose_release(&payment_id, &admin);
-    assert!(result.is_err(), "release must fail before min_timestamp");
+    assert_eq!(
+        result,
+        Err(Ok(crate::Error::EscrowNotReleasable)),
+        "release must fail before min_timestamp"
+    );
 }
 
 #[test]
@@ -920,8 +926,9 @@ fn escrow_conditions_block_release_wrong_approver() {
     });
 
     let result = client.try_propose_release(&payment_id, &admin);
-    assert!(
-        result.is_err(),
+    assert_eq!(
+        result,
+        Err(Ok(crate::Error::EscrowNotReleasable)),
         "release must fail when approver does not match"
     );
 }
@@ -1031,11 +1038,8 @@ fn test_create_payment_fails_with_tampered_fee_payload() {
         &admin,
     );
 
-    assert!(result.is_err());
     // Soroban sdk client returns Result<Result<T, E>, ...>
-    if let Err(Ok(e)) = result {
-        assert_eq!(e, crate::Error::InvalidFeePayload);
-    }
+    assert_eq!(result, Err(Ok(crate::Error::InvalidFeePayload)));
 }
 
 #[test]
@@ -1059,10 +1063,7 @@ fn test_create_payment_fails_with_unauthorized_backend_auth() {
         &unauthorized_backend,
     );
 
-    assert!(result.is_err());
-    if let Err(Ok(e)) = result {
-        assert_eq!(e, crate::Error::Unauthorized);
-    }
+    assert_eq!(result, Err(Ok(crate::Error::Unauthorized)));
 }
 
 // ======================================================
@@ -1125,3 +1126,205 @@ fn test_fee_arithmetic_overflow_boundary() {
     assert_eq!(fee.calculate_net_amount(1_000), Err(PaymentError::Overflow));
 }
 
+// ======================================================
+// Timed-out custody transfer refunds
+// ======================================================
+
+/// Register a unit, link an escrowed payment to it and start its transfer.
+/// Returns (unit_id, payment_id, payer, hospital, transfer_started_at).
+fn setup_linked_in_transit_payment(
+    env: &Env,
+    contract_id: &Address,
+    client: &HealthChainContractClient<'_>,
+    admin: &Address,
+) -> (u64, u64, Address, Address, u64) {
+    let bank = Address::generate(env);
+    let hospital = Address::generate(env);
+    client.register_blood_bank(&bank);
+    client.register_hospital(&hospital);
+
+    env.ledger().set_timestamp(1_000_000);
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::OPositive,
+        &BloodComponent::WholeBlood,
+        &450,
+        &(1_000_000 + 7 * 86400),
+        &Some(symbol_short!("donor")),
+        &None,
+    );
+    client.allocate_blood(&bank, &unit_id, &hospital, &None, &None);
+
+    let payer = Address::generate(env);
+    let payee = Address::generate(env);
+    let asset = Address::generate(env);
+    let payment_id = client.create_payment(
+        &1,
+        &payer,
+        &payee,
+        &2_500,
+        &asset,
+        &default_fee_structure(env),
+        admin,
+    );
+    move_payment_to_disputed_ready_state(env, contract_id, payment_id);
+    client.link_unit_payment(&unit_id, &payment_id);
+
+    client.initiate_transfer(&bank, &unit_id, &None, &None, &None);
+
+    (unit_id, payment_id, payer, hospital, 1_000_000)
+}
+
+#[test]
+fn claim_timed_out_payment_refunds_payer() {
+    let env = Env::default();
+    let (contract_id, client, admin) = setup_dispute_contract(&env);
+    let (unit_id, payment_id, payer, _, started_at) =
+        setup_linked_in_transit_payment(&env, &contract_id, &client, &admin);
+
+    env.ledger()
+        .set_timestamp(started_at + DEFAULT_TRANSFER_DEADLINE_SECONDS);
+    client.claim_timed_out_payment(&unit_id);
+
+    let last_event = env.events().all().last().unwrap();
+    let event = crate::PaymentTimeoutRefundedEvent::try_from_val(&env, &last_event.2).unwrap();
+    assert_eq!(event.payment_id, payment_id);
+    assert_eq!(event.unit_id, unit_id);
+    assert_eq!(event.refunded_to, payer);
+    assert_eq!(event.amount, 2_500);
+
+    env.as_contract(&contract_id, || {
+        let payments: Map<u64, Payment> = env.storage().persistent().get(&PAYMENTS).unwrap();
+        let payment = payments.get(payment_id).unwrap();
+        assert_eq!(payment.status, PaymentStatus::Refunded);
+        assert_eq!(
+            payment.escrow_released_at,
+            Some(started_at + DEFAULT_TRANSFER_DEADLINE_SECONDS)
+        );
+    });
+
+    // A second claim finds the payment already terminal
+    assert_eq!(
+        client.try_claim_timed_out_payment(&unit_id),
+        Err(Ok(Error::InvalidPaymentStatus))
+    );
+}
+
+#[test]
+fn claim_timed_out_payment_refunds_payer_after_transfer_expired() {
+    let env = Env::default();
+    let (contract_id, client, admin) = setup_dispute_contract(&env);
+    let (unit_id, payment_id, _, _, started_at) =
+        setup_linked_in_transit_payment(&env, &contract_id, &client, &admin);
+
+    // The keeper expires the transfer first, which clears the pending index
+    let event_id = client
+        .get_custody_events(&unit_id, &0, &1)
+        .items
+        .get(0)
+        .unwrap()
+        .event_id;
+    env.ledger()
+        .set_timestamp(started_at + DEFAULT_TRANSFER_DEADLINE_SECONDS);
+    client.expire_pending_transfer(&event_id);
+
+    client.claim_timed_out_payment(&unit_id);
+
+    let last_event = env.events().all().last().unwrap();
+    let event = crate::PaymentTimeoutRefundedEvent::try_from_val(&env, &last_event.2).unwrap();
+    assert_eq!(event.payment_id, payment_id);
+    assert_eq!(event.custody_event_id, event_id);
+}
+
+#[test]
+fn claim_timed_out_payment_rejected_inside_transfer_window() {
+    let env = Env::default();
+    let (contract_id, client, admin) = setup_dispute_contract(&env);
+    let (unit_id, _, _, _, started_at) =
+        setup_linked_in_transit_payment(&env, &contract_id, &client, &admin);
+
+    env.ledger()
+        .set_timestamp(started_at + DEFAULT_TRANSFER_DEADLINE_SECONDS - 1);
+    assert_eq!(
+        client.try_claim_timed_out_payment(&unit_id),
+        Err(Ok(Error::TransferNotExpired))
+    );
+}
+
+#[test]
+fn claim_timed_out_payment_rejected_for_transfer_ended_before_deadline() {
+    let env = Env::default();
+    let (contract_id, client, admin) = setup_dispute_contract(&env);
+    let (unit_id, payment_id, _, _, started_at) =
+        setup_linked_in_transit_payment(&env, &contract_id, &client, &admin);
+
+    // The transfer was called off well inside its window
+    let event_id = client
+        .get_custody_events(&unit_id, &0, &1)
+        .items
+        .get(0)
+        .unwrap()
+        .event_id;
+    env.as_contract(&contract_id, || {
+        let mut events: Map<String, CustodyEvent> =
+            env.storage().persistent().get(&CUSTODY_EVENTS).unwrap();
+        let mut event = events.get(event_id.clone()).unwrap();
+        event.status = CustodyStatus::Cancelled;
+        events.set(event_id.clone(), event);
+        env.storage().persistent().set(&CUSTODY_EVENTS, &events);
+    });
+
+    env.ledger().set_timestamp(started_at + 60);
+    assert_eq!(
+        client.try_claim_timed_out_payment(&unit_id),
+        Err(Ok(Error::TransferNotExpired))
+    );
+
+    env.ledger()
+        .set_timestamp(started_at + DEFAULT_TRANSFER_DEADLINE_SECONDS);
+    client.claim_timed_out_payment(&unit_id);
+    env.as_contract(&contract_id, || {
+        let payments: Map<u64, Payment> = env.storage().persistent().get(&PAYMENTS).unwrap();
+        assert_eq!(
+            payments.get(payment_id).unwrap().status,
+            PaymentStatus::Refunded
+        );
+    });
+}
+
+#[test]
+fn claim_timed_out_payment_rejected_after_confirmed_transfer() {
+    let env = Env::default();
+    let (contract_id, client, admin) = setup_dispute_contract(&env);
+    let (unit_id, payment_id, _, hospital, started_at) =
+        setup_linked_in_transit_payment(&env, &contract_id, &client, &admin);
+
+    env.ledger().set_timestamp(started_at + 60);
+    client.confirm_delivery(&hospital, &unit_id, &None, &None, &None);
+
+    env.ledger()
+        .set_timestamp(started_at + DEFAULT_TRANSFER_DEADLINE_SECONDS);
+    assert_eq!(
+        client.try_claim_timed_out_payment(&unit_id),
+        Err(Ok(Error::InvalidStatus))
+    );
+
+    env.as_contract(&contract_id, || {
+        let payments: Map<u64, Payment> = env.storage().persistent().get(&PAYMENTS).unwrap();
+        assert_eq!(
+            payments.get(payment_id).unwrap().status,
+            PaymentStatus::Escrowed
+        );
+    });
+}
+
+#[test]
+fn claim_timed_out_payment_without_linked_payment_fails() {
+    let env = Env::default();
+    let (_, client, _) = setup_dispute_contract(&env);
+
+    assert_eq!(
+        client.try_claim_timed_out_payment(&42),
+        Err(Ok(Error::PaymentNotFound))
+    );
+}
@@ -5,9 +5,9 @@ use crate::payments::{
     PendingApproval, ReleaseConditions, HIGH_VALUE_THRESHOLD,
 };
 use crate::{
-    BloodComponent, BloodRequest, BloodStatus, BloodType, BloodUnit, CustodyStatus, Error,
-    HealthChainContract, HealthChainContractClient, QuarantineReason, RequestStatus, UrgencyLevel,
-    BLOOD_UNITS, ESCROW_ACCOUNTS, PAYMENTS, REQUESTS,
+    load_unit, save_unit, BloodComponent, BloodRequest, BloodStatus, BloodType, BloodUnit,
+    CustodyStatus, Error, HealthChainContract, HealthChainContractClient, QuarantineReason,
+    RequestStatus, UrgencyLevel, ESCROW_ACCOUNTS, PAYMENTS, REQUESTS,
 };
 
 use soroban_sdk::{
@@ -68,6 +68,7 @@ fn register_unit(fixture: &ProtocolFixture, quantity: u32) -> u64 {
         &quantity,
         &valid_expiration(&fixture.env),
         &Some(symbol_short!("donor")),
+        &None,
     )
 }
 
@@ -96,16 +97,9 @@ fn stored_request(fixture: &ProtocolFixture, request_id: u64) -> BloodRequest {
 
 fn force_unit_quantity(fixture: &ProtocolFixture, unit_id: u64, quantity: u32) {
     fixture.env.as_contract(&fixture.contract_id, || {
-        let mut units: Map<u64, BloodUnit> = fixture
-            .env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap();
-        let mut unit = units.get(unit_id).unwrap();
+        let mut unit = load_unit(&fixture.env, unit_id).unwrap();
         unit.quantity = quantity;
-        units.set(unit_id, unit);
-        fixture.env.storage().persistent().set(&BLOOD_UNITS, &units);
+        save_unit(&fixture.env, &unit);
     });
 }
 
@@ -197,8 +191,13 @@ fn property_expired_units_cannot_be_allocated_or_transferred() {
         ledger.timestamp = expiration;
     });
 
-    let allocation =
-        client(&fixture).try_allocate_blood(&fixture.bank, &unit_id, &fixture.hospital);
+    let allocation = client(&fixture).try_allocate_blood(
+        &fixture.bank,
+        &unit_id,
+        &fixture.hospital,
+        &None,
+        &None,
+    );
     assert!(matches!(allocation, Err(Ok(Error::UnitExpired))));
     assert_eq!(
         stored_unit(&fixture, unit_id).status,
@@ -266,11 +265,12 @@ fn property_delivered_unit_cannot_be_concurrently_quarantined_by_duplicate_actio
     let fixture = setup_protocol();
     let unit_id = register_unit(&fixture, 450);
 
-    client(&fixture).allocate_blood(&fixture.bank, &unit_id, &fixture.hospital);
-    let event_id = client(&fixture).initiate_transfer(&fixture.bank, &unit_id);
-    client(&fixture).confirm_transfer(&fixture.hospital, &event_id);
+    client(&fixture).allocate_blood(&fixture.bank, &unit_id, &fixture.hospital, &None, &None);
+    let event_id = client(&fixture).initiate_transfer(&fixture.bank, &unit_id, &None, &None, &None);
+    client(&fixture).confirm_transfer(&fixture.hospital, &event_id, &None, &None, &None);
 
-    let duplicate_confirm = client(&fixture).try_confirm_transfer(&fixture.hospital, &event_id);
+    let duplicate_confirm =
+        client(&fixture).try_confirm_transfer(&fixture.hospital, &event_id, &None, &None, &None);
     assert!(matches!(duplicate_confirm, Err(Ok(Error::InvalidStatus))));
 
     let event = client(&fixture).get_custody_event(&event_id);
@@ -286,19 +286,20 @@ fn property_custody_transfer_requires_authorized_current_custodian() {
     let fixture = setup_protocol();
     let unit_id = register_unit(&fixture, 450);
 
-    client(&fixture).allocate_blood(&fixture.bank, &unit_id, &fixture.hospital);
+    client(&fixture).allocate_blood(&fixture.bank, &unit_id, &fixture.hospital, &None, &None);
 
     let non_custodian_attempt =
-        client(&fixture).try_initiate_transfer(&fixture.other_bank, &unit_id);
+        client(&fixture).try_initiate_transfer(&fixture.other_bank, &unit_id, &None, &None, &None);
     assert!(matches!(
         non_custodian_attempt,
         Err(Ok(Error::NotCurrentCustodian))
     ));
     assert_eq!(stored_unit(&fixture, unit_id).status, BloodStatus::Reserved);
 
-    let event_id = client(&fixture).initiate_transfer(&fixture.bank, &unit_id);
+    let event_id = client(&fixture).initiate_transfer(&fixture.bank, &unit_id, &None, &None, &None);
     let unauthorized_recipient = Address::generate(&fixture.env);
-    let bad_confirm = client(&fixture).try_confirm_transfer(&unauthorized_recipient, &event_id);
+    let bad_confirm =
+        client(&fixture).try_confirm_transfer(&unauthorized_recipient, &event_id, &None, &None, &None);
     assert!(matches!(bad_confirm, Err(Ok(Error::UnauthorizedHospital))));
     assert_eq!(
         stored_unit(&fixture, unit_id).status,
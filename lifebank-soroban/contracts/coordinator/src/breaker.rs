@@ -0,0 +1,111 @@
+//! Per-contract circuit breakers.
+//!
+//! Every downstream call passed to [`observe`] or [`check`] is recorded
+//! against the called contract. A call that traps, aborts or returns an
+//! unreadable value counts as a failure; a typed contract error is an answer
+//! and does not. A success resets the count. After [`FAILURE_THRESHOLD`]
+//! failures in a row the breaker opens and stays open until the admin resets
+//! it, even if later calls succeed.
+//!
+//! While a breaker is open, calls to a required contract fail fast with
+//! `DownstreamUnavailable` and calls to an optional one are skipped.
+//!
+//! A failure is only stored if the coordinator call that observed it
+//! succeeds, since a failed invocation rolls back all of its writes. In
+//! practice the counts come from optional integrations and from best-effort
+//! calls whose failure the coordinator works around.
+
+use crate::{
+    contract_address, ContractHealth, ContractKind, CoordBreakerOpened, CoordinatorError,
+    DataKey,
+};
+use soroban_sdk::{Address, Env, InvokeError};
+
+/// Consecutive failures that open a breaker.
+pub const FAILURE_THRESHOLD: u32 = 3;
+
+/// Whether the coordinator's flows cannot run without `kind`.
+pub fn is_required(kind: ContractKind) -> bool {
+    !matches!(kind, ContractKind::Temperature | ContractKind::Reputation)
+}
+
+pub fn health(env: &Env, kind: ContractKind) -> ContractHealth {
+    env.storage()
+        .instance()
+        .get(&DataKey::Health(kind))
+        .unwrap_or(ContractHealth {
+            consecutive_failures: 0,
+            open: false,
+            opened_at: 0,
+        })
+}
+
+pub fn reset(env: &Env, kind: ContractKind) {
+    env.storage().instance().remove(&DataKey::Health(kind));
+}
+
+/// Address to call for `kind`, or `None` when its breaker is open and the
+/// contract is optional.
+///
+/// # Errors
+/// - `DownstreamUnavailable` — the breaker of a required contract is open
+/// - `ContractNotConfigured` — no address is registered for `kind`
+pub fn target(env: &Env, kind: ContractKind) -> Result<Option<Address>, CoordinatorError> {
+    if health(env, kind).open {
+        if is_required(kind) {
+            return Err(CoordinatorError::DownstreamUnavailable);
+        }
+        return Ok(None);
+    }
+    contract_address(env, kind).map(Some)
+}
+
+/// Address of a required contract whose breaker is closed.
+pub fn required(env: &Env, kind: ContractKind) -> Result<Address, CoordinatorError> {
+    target(env, kind)?.ok_or(CoordinatorError::DownstreamUnavailable)
+}
+
+fn record(env: &Env, kind: ContractKind, failed: bool) {
+    let mut health = health(env, kind);
+    if !failed {
+        if health.consecutive_failures != 0 && !health.open {
+            reset(env, kind);
+        }
+        return;
+    }
+    health.consecutive_failures += 1;
+    if !health.open && health.consecutive_failures >= FAILURE_THRESHOLD {
+        health.open = true;
+        health.opened_at = env.ledger().timestamp();
+        CoordBreakerOpened {
+            kind,
+            consecutive_failures: health.consecutive_failures,
+        }
+        .publish(env);
+    }
+    env.storage().instance().set(&DataKey::Health(kind), &health);
+}
+
+/// Record the outcome of a `try_` call to `kind`.
+pub fn observe<T, C, E>(
+    env: &Env,
+    kind: ContractKind,
+    result: &Result<Result<T, C>, Result<E, InvokeError>>,
+) {
+    record(env, kind, matches!(result, Ok(Err(_)) | Err(Err(_))));
+}
+
+/// Record the outcome of a `try_` call to `kind` and unwrap it, turning any
+/// error into `error`.
+pub fn check<T, C, E>(
+    env: &Env,
+    kind: ContractKind,
+    result: Result<Result<T, C>, Result<E, InvokeError>>,
+    error: CoordinatorError,
+) -> Result<T, CoordinatorError> {
+    observe(env, kind, &result);
+    match result {
+        Ok(Ok(value)) => Ok(value),
+        _ => Err(error),
+    }
+}
@@ -7,6 +7,8 @@ pub enum CoordinatorError {
     AlreadyInitialized = 800,
     NotInitialized = 801,
     Unauthorized = 802,
+    /// No address is registered for the downstream contract.
+    ContractNotConfigured = 803,
 
     // Workflow state errors
     WorkflowNotFound = 810,
@@ -23,11 +25,14 @@ pub enum CoordinatorError {
     InvalidPaymentState = 825,
     DeliveryNotConfirmed = 826,
     IncompatibleBloodType = 827,
+    /// The temperature contract reports the unit as compromised.
+    UnitCompromised = 828,
 
     // Cross-contract call failures
     InventoryUpdateFailed = 830,
     PaymentUpdateFailed = 831,
     PaymentFlagFailed = 832,
+    EscalationFailed = 833,
 
     // Circuit breaker
     ContractPaused = 840,
@@ -37,4 +42,35 @@ pub enum CoordinatorError {
 
     /// expire_workflow() was called but the deadline has not yet elapsed.
     WorkflowNotExpired = 842,
+
+    // Order placement
+    InvalidOrderQuantity = 850,
+    /// No unit price for the blood type, or no payment token, is configured.
+    PricingNotConfigured = 851,
+    PriceAboveMax = 852,
+    /// Registered banks together hold too little available stock for the order.
+    InsufficientStock = 853,
+    DeliveryJobFailed = 854,
+    OrderNotFound = 855,
+    /// The order's status does not allow the requested change.
+    InvalidOrderTransition = 856,
+    /// The order has no leg at this index.
+    LegNotFound = 857,
+    LegAlreadyDelivered = 858,
+    /// enforce_sla() was called before the order's SLA deadline passed.
+    SlaNotExpired = 859,
+
+    // Contract address registry
+    /// No change to this address was proposed, or a different one was.
+    ContractChangeNotProposed = 860,
+    /// The proposed change is still inside its timelock.
+    ContractChangeTimelocked = 861,
+
+    /// The circuit breaker of a required downstream contract is open.
+    DownstreamUnavailable = 870,
+
+    // Delivery proof
+    /// The custody events given for a leg are not one confirmed, unused
+    /// handoff to the order's hospital per unit.
+    CustodyNotConfirmed = 880,
 }
@@ -10,16 +10,30 @@
 ///
 /// Any step that finds the prerequisite state missing returns an error and makes
 /// no state changes, providing safe rollback semantics within a single transaction.
+/// Steps that touch several downstream contracts register compensating actions
+/// (see `saga`) and undo the completed ones before returning an error.
+mod breaker;
 mod error;
+mod lifecycle;
+mod routing;
+mod saga;
+mod sla;
 mod types;
 
 #[cfg(test)]
 mod test;
 
 pub use error::CoordinatorError;
-pub use types::{DataKey, ExcursionSummary, WorkflowRecord, WorkflowStatus};
+pub use types::{
+    ContractHealth, ContractKind, DataKey, ExcursionSummary, FulfillmentLeg, Order,
+    OrderMilestone, OrderStatus, PendingContractAddress, Urgency, UrgencyProfile, WorkflowRecord,
+    WorkflowStatus,
+};
 
-use soroban_sdk::{contract, contractevent, contractimpl, contracttype, Address, Env, String, Vec};
+use saga::{Compensation, Saga};
+use soroban_sdk::{
+    contract, contractevent, contractimpl, contracttype, Address, BytesN, Env, String, Symbol, Vec,
+};
 
 /// Default workflow expiry window: 6 hours expressed in seconds.
 /// After `allocate_units` is called, if `confirm_delivery` is never invoked
@@ -27,6 +41,19 @@ use soroban_sdk::{contract, contractevent, contractimpl, contracttype, Address,
 /// allocation and free the reserved units and escrowed payment.
 const WORKFLOW_TIMEOUT_SECS: u64 = 6 * 60 * 60;
 
+/// Most units a single order may ask for.
+const MAX_ORDER_UNITS: u32 = 20;
+
+/// Orders per page returned by `get_orders_by_hospital`.
+const ORDERS_PAGE_SIZE: u32 = 20;
+
+/// Response window given to blood banks when a request is escalated: 30 minutes.
+const ESCALATION_RESPONSE_SECS: u64 = 30 * 60;
+
+/// Delay between proposing a replacement downstream contract address and
+/// being able to apply it: 48 hours.
+const CONTRACT_CHANGE_TIMELOCK_SECS: u64 = 48 * 60 * 60;
+
 const CONTRACT_VERSION: u32 = 1;
 
 // ── Minimal interface types mirroring the domain contracts ────────────────────
@@ -118,21 +145,108 @@ pub struct Payment {
     pub status: PaymentStatus,
 }
 
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CustodyStatus {
+    Pending,
+    Confirmed,
+    Cancelled,
+    Recovered,
+    Rejected,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CustodianKind {
+    Bank,
+    Rider,
+    Hospital,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CustodyEvent {
+    pub event_id: String,
+    pub unit_id: u64,
+    pub from_custodian: Address,
+    pub to_custodian: Address,
+    pub from_kind: CustodianKind,
+    pub to_kind: CustodianKind,
+    pub initiated_at: u64,
+    pub transfer_deadline: u64,
+    pub ledger_sequence: u32,
+    pub status: CustodyStatus,
+    pub doc_hash: Option<BytesN<32>>,
+    pub note: Option<Symbol>,
+    pub location_hash: Option<BytesN<32>>,
+    pub closing_location_hash: Option<BytesN<32>>,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BloodComponent {
+    WholeBlood,
+    RedBloodCells,
+    Plasma,
+    Platelets,
+    Cryoprecipitate,
+}
+
+/// Unit status as tracked by the custody contract.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CustodyUnitStatus {
+    Available,
+    Reserved,
+    InTransit,
+    Delivered,
+    Quarantined,
+    Expired,
+    Discarded,
+    Recalled,
+    Split,
+    Pooled,
+    ReturnPending,
+}
+
+/// A blood unit as registered in the custody contract.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CustodyUnit {
+    pub id: u64,
+    pub blood_type: BloodType,
+    pub component: BloodComponent,
+    pub quantity: u32,
+    pub expiration_date: u64,
+    pub donor_id: Symbol,
+    pub location: Symbol,
+    pub bank_id: Address,
+    pub registration_timestamp: u64,
+    pub status: CustodyUnitStatus,
+    pub recipient_hospital: Option<Address>,
+    pub allocation_timestamp: Option<u64>,
+    pub transfer_timestamp: Option<u64>,
+    pub delivery_timestamp: Option<u64>,
+    pub lot_id: Option<Symbol>,
+}
+
 // ── Cross-contract client traits ──────────────────────────────────────────────
 
 mod request_client {
     use super::BloodRequest;
-    use soroban_sdk::{contractclient, Address, Env};
+    use soroban_sdk::{contractclient, Address, Env, Vec};
 
     #[contractclient(name = "RequestContractClient")]
     #[allow(dead_code)]
     pub trait RequestContractInterface {
         fn get_request(env: Env, request_id: u64) -> BloodRequest;
+        fn broadcast_request(env: Env, request_id: u64, respond_by: u64) -> u32;
+        fn get_blood_banks(env: Env) -> Vec<Address>;
     }
 }
 
 mod inventory_client {
-    use super::{BloodStatus, BloodUnit};
+    use super::{BloodStatus, BloodType, BloodUnit};
     use soroban_sdk::{contractclient, Address, Env, String};
 
     #[contractclient(name = "InventoryContractClient")]
@@ -153,12 +267,22 @@ mod inventory_client {
             delivery_location: String,
         ) -> BloodUnit;
         fn get_admin(env: Env) -> Address;
+        fn get_available_stock(env: Env, bank: Address, blood_type: BloodType) -> u32;
+        fn reserve_stock_for_order(
+            env: Env,
+            bank: Address,
+            blood_type: BloodType,
+            quantity: u32,
+            order_id: u64,
+            expires_at: u64,
+        ) -> u64;
+        fn release_stock_reservation(env: Env, caller: Address, reservation_id: u64);
     }
 }
 
 mod payment_client {
     use super::{Payment, PaymentStatus};
-    use soroban_sdk::{contractclient, contracttype, Env, String};
+    use soroban_sdk::{contractclient, contracttype, Address, Env, String};
 
     #[contracttype]
     #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -178,12 +302,84 @@ mod payment_client {
         fn get_payment(env: Env, payment_id: u64) -> Payment;
         fn update_status(env: Env, payment_id: u64, status: PaymentStatus);
         fn record_dispute(env: Env, payment_id: u64, reason: DisputeReason, case_id: String);
+        fn open_escrow(
+            env: Env,
+            order_id: u64,
+            payer: Address,
+            payee: Address,
+            amount: i128,
+            token: Address,
+        ) -> u64;
+        fn lock_payment(env: Env, payment_id: u64, token: Address);
+        fn release_on_delivery(env: Env, order_id: u64);
+        fn release_order_escrow(env: Env, payment_id: u64);
+        fn refund_on_cancel(env: Env, payment_id: u64);
     }
 }
 
+mod delivery_client {
+    use soroban_sdk::{contractclient, Address, Env};
+
+    #[contractclient(name = "DeliveryContractClient")]
+    #[allow(dead_code)]
+    pub trait DeliveryContractInterface {
+        fn create_delivery_job(
+            env: Env,
+            order_id: u64,
+            pickup: Address,
+            dropoff: Address,
+            unit_count: u32,
+        ) -> u64;
+    }
+}
+
+mod temperature_client {
+    use soroban_sdk::{contractclient, Env};
+
+    #[contractclient(name = "TemperatureContractClient")]
+    #[allow(dead_code)]
+    pub trait TemperatureContractInterface {
+        fn is_compromised(env: Env, unit_id: u64) -> bool;
+    }
+}
+
+mod reputation_client {
+    use soroban_sdk::{contractclient, Env, Val};
+
+    #[contractclient(name = "ReputationContractClient")]
+    #[allow(dead_code)]
+    pub trait ReputationContractInterface {
+        /// Returns the entity's recalculated score, which the coordinator
+        /// does not read.
+        fn record_assignment(
+            env: Env,
+            entity_id: u64,
+            completed: bool,
+            response_secs: u64,
+            timestamp: u64,
+        ) -> Val;
+    }
+}
+
+mod custody_client {
+    use super::{CustodyEvent, CustodyUnit};
+    use soroban_sdk::{contractclient, Env, String};
+
+    #[contractclient(name = "CustodyContractClient")]
+    #[allow(dead_code)]
+    pub trait CustodyContractInterface {
+        fn get_custody_event(env: Env, event_id: String) -> CustodyEvent;
+        fn get_blood_unit(env: Env, unit_id: u64) -> CustodyUnit;
+    }
+}
+
+use custody_client::CustodyContractClient;
+use delivery_client::DeliveryContractClient;
 use inventory_client::InventoryContractClient;
 use payment_client::PaymentContractClient;
+use reputation_client::ReputationContractClient;
 use request_client::RequestContractClient;
+use temperature_client::TemperatureContractClient;
 
 // ── Contract events ───────────────────────────────────────────────────────────
 
@@ -192,11 +388,47 @@ pub struct CoordInitialized {
     pub admin: Address,
 }
 
+#[contractevent(topics = ["coord", "addrprop"], data_format = "vec")]
+pub struct CoordContractProposed {
+    pub kind: ContractKind,
+    pub address: Address,
+    pub effective_at: u64,
+}
+
+#[contractevent(topics = ["coord", "addrset"], data_format = "vec")]
+pub struct CoordContractSet {
+    pub kind: ContractKind,
+    pub address: Address,
+}
+
+/// High-priority alert: a downstream contract failed repeatedly and its
+/// breaker opened.
+#[contractevent(topics = ["coord", "brkopen", "high"], data_format = "vec")]
+pub struct CoordBreakerOpened {
+    pub kind: ContractKind,
+    pub consecutive_failures: u32,
+}
+
+#[contractevent(topics = ["coord", "brkreset"], data_format = "vec")]
+pub struct CoordBreakerReset {
+    pub kind: ContractKind,
+    pub admin: Address,
+}
+
 #[contractevent(topics = ["coord", "emrghlt"], data_format = "single-value")]
 pub struct CoordEmergencyHalt {
     pub admin: Address,
 }
 
+/// High-priority alert: a request no nearby bank could cover was broadcast to
+/// every bank.
+#[contractevent(topics = ["coord", "escalatd", "high"], data_format = "vec")]
+pub struct CoordEscalated {
+    pub request_id: u64,
+    pub banks_notified: u32,
+    pub respond_by: u64,
+}
+
 #[contractevent(topics = ["coord", "alloc"], data_format = "vec")]
 pub struct CoordAllocated {
     pub request_id: u64,
@@ -204,6 +436,61 @@ pub struct CoordAllocated {
     pub unit_count: u32,
 }
 
+#[contractevent(topics = ["coord", "order"], data_format = "vec")]
+pub struct CoordOrderPlaced {
+    pub order_id: u64,
+    pub hospital: Address,
+    pub quantity: u32,
+    pub price: i128,
+    pub leg_count: u32,
+}
+
+#[contractevent(topics = ["coord", "leg"], data_format = "vec")]
+pub struct CoordLegOpened {
+    #[topic]
+    pub order_id: u64,
+    pub leg_id: u64,
+    pub bank: Address,
+    pub quantity: u32,
+    pub price: i128,
+}
+
+/// A routine order was cancelled to free stock for an emergency order.
+#[contractevent(topics = ["coord", "preempt"], data_format = "single-value")]
+pub struct CoordOrderPreempted {
+    #[topic]
+    pub order_id: u64,
+    pub preempted_by: u64,
+}
+
+#[contractevent(topics = ["coord", "legdlvd"], data_format = "vec")]
+pub struct CoordLegDelivered {
+    #[topic]
+    pub order_id: u64,
+    pub leg_id: u64,
+    pub fulfilled_quantity: u32,
+}
+
+/// An order entered a new status; see `lifecycle`.
+#[contractevent(topics = ["coord", "milestn"], data_format = "vec")]
+pub struct CoordOrderMilestone {
+    #[topic]
+    pub order_id: u64,
+    pub milestone: OrderStatus,
+    pub actor: Address,
+    pub timestamp: u64,
+}
+
+/// High-priority alert: an order missed its SLA deadline and was cancelled.
+#[contractevent(topics = ["coord", "slamiss", "high"], data_format = "vec")]
+pub struct CoordSlaViolated {
+    #[topic]
+    pub order_id: u64,
+    /// Status the order was in when it was cancelled.
+    pub status: OrderStatus,
+    pub sla_deadline: u64,
+}
+
 #[contractevent(topics = ["coord", "dlvrd"], data_format = "vec")]
 pub struct CoordDelivered {
     pub request_id: u64,
@@ -242,6 +529,19 @@ fn get_admin(env: &Env) -> Result<Address, CoordinatorError> {
         .ok_or(CoordinatorError::NotInitialized)
 }
 
+fn contract_address(env: &Env, kind: ContractKind) -> Result<Address, CoordinatorError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::ContractAddr(kind))
+        .ok_or(CoordinatorError::ContractNotConfigured)
+}
+
+fn set_contract(env: &Env, kind: ContractKind, address: &Address) {
+    env.storage()
+        .instance()
+        .set(&DataKey::ContractAddr(kind), address);
+}
+
 fn load_workflow(env: &Env, request_id: u64) -> Option<WorkflowRecord> {
     const WORKFLOW_TTL_LEDGERS: u32 = 535_680; // ~30 days at 5s/ledger
     
@@ -276,6 +576,42 @@ fn save_workflow(env: &Env, wf: &WorkflowRecord) {
     );
 }
 
+fn load_order(env: &Env, order_id: u64) -> Option<Order> {
+    env.storage().persistent().get(&DataKey::Order(order_id))
+}
+
+fn save_order(env: &Env, order: &Order) {
+    const ORDER_TTL_LEDGERS: u32 = 535_680; // ~30 days at 5s/ledger
+
+    let key = DataKey::Order(order.id);
+    env.storage().persistent().set(&key, order);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, ORDER_TTL_LEDGERS, ORDER_TTL_LEDGERS);
+}
+
+/// Append `order_id` to the hospital's paged order index.
+fn index_hospital_order(env: &Env, hospital: &Address, order_id: u64) {
+    const ORDER_TTL_LEDGERS: u32 = 535_680; // ~30 days at 5s/ledger
+
+    let count_key = DataKey::HospitalOrderCount(hospital.clone());
+    let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+    let page_key = DataKey::HospitalOrders(hospital.clone(), count / ORDERS_PAGE_SIZE);
+    let mut page: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&page_key)
+        .unwrap_or(Vec::new(env));
+    page.push_back(order_id);
+    env.storage().persistent().set(&page_key, &page);
+    env.storage().persistent().set(&count_key, &(count + 1));
+    for key in [page_key, count_key] {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, ORDER_TTL_LEDGERS, ORDER_TTL_LEDGERS);
+    }
+}
+
 // ── Contract ───────────────────────────────────────────────────────────────────
 
 #[contract]
@@ -295,15 +631,9 @@ impl CoordinatorContract {
             return Err(CoordinatorError::AlreadyInitialized);
         }
         env.storage().instance().set(&DataKey::Admin, &admin);
-        env.storage()
-            .instance()
-            .set(&DataKey::RequestContract, &request_contract);
-        env.storage()
-            .instance()
-            .set(&DataKey::InventoryContract, &inventory_contract);
-        env.storage()
-            .instance()
-            .set(&DataKey::PaymentContract, &payment_contract);
+        set_contract(&env, ContractKind::Requests, &request_contract);
+        set_contract(&env, ContractKind::Inventory, &inventory_contract);
+        set_contract(&env, ContractKind::Payments, &payment_contract);
         CoordInitialized { admin }.publish(&env);
         Ok(())
     }
@@ -420,7 +750,680 @@ impl CoordinatorContract {
             .unwrap_or(false)
     }
 
-    /// Step 1 – Allocate inventory units to a pending request.
+    /// Queue `address` as the new `kind` contract. It can be applied with
+    /// `set_contract_address` once `CONTRACT_CHANGE_TIMELOCK_SECS` have
+    /// passed. Proposing again replaces the queued change and restarts the
+    /// delay. Admin only.
+    pub fn propose_contract_address(
+        env: Env,
+        admin: Address,
+        kind: ContractKind,
+        address: Address,
+    ) -> Result<u64, CoordinatorError> {
+        Self::require_admin(&env, &admin)?;
+        let effective_at = env.ledger().timestamp() + CONTRACT_CHANGE_TIMELOCK_SECS;
+        env.storage().instance().set(
+            &DataKey::PendingContractAddr(kind),
+            &PendingContractAddress {
+                address: address.clone(),
+                effective_at,
+            },
+        );
+        CoordContractProposed {
+            kind,
+            address,
+            effective_at,
+        }
+        .publish(&env);
+        Ok(effective_at)
+    }
+
+    /// Point the coordinator at `address` for `kind`. Admin only.
+    ///
+    /// The first address registered for a kind takes effect immediately.
+    /// Replacing an existing one requires the same address to have been
+    /// proposed with `propose_contract_address` and its timelock to have
+    /// elapsed.
+    ///
+    /// # Errors
+    /// - `ContractChangeNotProposed` — `address` is not the queued change
+    /// - `ContractChangeTimelocked`  — the queued change is not yet effective
+    pub fn set_contract_address(
+        env: Env,
+        admin: Address,
+        kind: ContractKind,
+        address: Address,
+    ) -> Result<(), CoordinatorError> {
+        Self::require_admin(&env, &admin)?;
+        if env.storage().instance().has(&DataKey::ContractAddr(kind)) {
+            let pending: PendingContractAddress = env
+                .storage()
+                .instance()
+                .get(&DataKey::PendingContractAddr(kind))
+                .ok_or(CoordinatorError::ContractChangeNotProposed)?;
+            if pending.address != address {
+                return Err(CoordinatorError::ContractChangeNotProposed);
+            }
+            if env.ledger().timestamp() < pending.effective_at {
+                return Err(CoordinatorError::ContractChangeTimelocked);
+            }
+        }
+        env.storage()
+            .instance()
+            .remove(&DataKey::PendingContractAddr(kind));
+        set_contract(&env, kind, &address);
+        CoordContractSet { kind, address }.publish(&env);
+        Ok(())
+    }
+
+    pub fn get_contract_address(env: Env, kind: ContractKind) -> Option<Address> {
+        env.storage().instance().get(&DataKey::ContractAddr(kind))
+    }
+
+    /// Close the circuit breaker of `kind` and clear its failure count.
+    /// Admin only.
+    pub fn reset_breaker(env: Env, admin: Address, kind: ContractKind) -> Result<(), CoordinatorError> {
+        Self::require_admin(&env, &admin)?;
+        breaker::reset(&env, kind);
+        CoordBreakerReset { kind, admin }.publish(&env);
+        Ok(())
+    }
+
+    pub fn get_contract_health(env: Env, kind: ContractKind) -> ContractHealth {
+        breaker::health(&env, kind)
+    }
+
+    pub fn get_pending_contract_address(
+        env: Env,
+        kind: ContractKind,
+    ) -> Option<PendingContractAddress> {
+        env.storage()
+            .instance()
+            .get(&DataKey::PendingContractAddr(kind))
+    }
+
+    /// Set the token order payments are escrowed in. Admin only.
+    pub fn set_payment_token(env: Env, admin: Address, token: Address) -> Result<(), CoordinatorError> {
+        Self::require_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::PaymentToken, &token);
+        Ok(())
+    }
+
+    /// Set the price of one unit of `blood_type`. Admin only.
+    pub fn set_unit_price(
+        env: Env,
+        admin: Address,
+        blood_type: BloodType,
+        price: i128,
+    ) -> Result<(), CoordinatorError> {
+        Self::require_admin(&env, &admin)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::UnitPrice(blood_type), &price);
+        Ok(())
+    }
+
+    /// Record how far `bank` is from `hospital`, for the urgency matching
+    /// radius. Banks with no recorded distance count as within any radius.
+    /// Admin only.
+    pub fn set_bank_distance(
+        env: Env,
+        admin: Address,
+        hospital: Address,
+        bank: Address,
+        distance_km: u32,
+    ) -> Result<(), CoordinatorError> {
+        Self::require_admin(&env, &admin)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::BankDistance(hospital, bank), &distance_km);
+        Ok(())
+    }
+
+    /// Record SLA violations of `party` under reputation entity `entity_id`.
+    /// Admin only.
+    pub fn set_reputation_entity(
+        env: Env,
+        admin: Address,
+        party: Address,
+        entity_id: u64,
+    ) -> Result<(), CoordinatorError> {
+        Self::require_admin(&env, &admin)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::ReputationEntity(party), &entity_id);
+        Ok(())
+    }
+
+    /// Routing rules applied to orders of `urgency`.
+    pub fn get_urgency_profile(_env: Env, urgency: Urgency) -> UrgencyProfile {
+        routing::profile(urgency)
+    }
+
+    /// Place an order in one transaction and return its ID.
+    ///
+    /// The order is split into fulfillment legs (see `plan_legs`). For each
+    /// leg the bank's stock is reserved, the leg's share of the price is
+    /// escrowed from `hospital` to the bank, and a delivery job is opened.
+    ///
+    /// `urgency` selects the matching radius, how long stock is held, the
+    /// price premium and the delivery deadline (see `routing`). An emergency
+    /// order the banks cannot cover preempts open routine orders.
+    ///
+    /// The price is the blood type's unit price plus the premium, times
+    /// `quantity`, and must not exceed `max_price`. Only exact blood-type
+    /// stock is considered. If any step fails, the steps already taken are
+    /// compensated (see `saga`).
+    ///
+    /// # Errors
+    /// - `InvalidOrderQuantity`  — `quantity` is zero or above `MAX_ORDER_UNITS`
+    /// - `PricingNotConfigured`  — no unit price or payment token is set
+    /// - `PriceAboveMax`         — the order would cost more than `max_price`
+    /// - `InsufficientStock`     — registered banks together cannot cover `quantity`
+    /// - `InventoryUpdateFailed` / `PaymentUpdateFailed` / `DeliveryJobFailed`
+    ///   — a downstream step was rejected
+    pub fn place_order(
+        env: Env,
+        hospital: Address,
+        blood_type: BloodType,
+        quantity: u32,
+        max_price: i128,
+        urgency: Urgency,
+    ) -> Result<u64, CoordinatorError> {
+        hospital.require_auth();
+        Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
+
+        if quantity == 0 || quantity > MAX_ORDER_UNITS {
+            return Err(CoordinatorError::InvalidOrderQuantity);
+        }
+        let profile = routing::profile(urgency);
+        let base_price: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::UnitPrice(blood_type))
+            .ok_or(CoordinatorError::PricingNotConfigured)?;
+        let unit_price = routing::premium_price(base_price, profile.premium_bps)
+            .ok_or(CoordinatorError::PriceAboveMax)?;
+        let token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::PaymentToken)
+            .ok_or(CoordinatorError::PricingNotConfigured)?;
+        let price = unit_price
+            .checked_mul(i128::from(quantity))
+            .ok_or(CoordinatorError::PriceAboveMax)?;
+        if price > max_price {
+            return Err(CoordinatorError::PriceAboveMax);
+        }
+
+        let order_id = env
+            .storage()
+            .instance()
+            .get::<_, u64>(&DataKey::OrderCounter)
+            .unwrap_or(0)
+            + 1;
+        env.storage().instance().set(&DataKey::OrderCounter, &order_id);
+
+        let plan = match Self::plan_legs(&env, &hospital, blood_type, quantity, profile.radius_km) {
+            Err(CoordinatorError::InsufficientStock) if urgency == Urgency::Emergency => {
+                let covered =
+                    routing::preempt_routine(&env, blood_type, order_id, &hospital, || {
+                        Self::plan_legs(&env, &hospital, blood_type, quantity, profile.radius_km)
+                            .is_ok()
+                    })?;
+                if !covered {
+                    return Err(CoordinatorError::InsufficientStock);
+                }
+                Self::plan_legs(&env, &hospital, blood_type, quantity, profile.radius_km)?
+            }
+            plan => plan?,
+        };
+
+        let mut legs = Vec::new(&env);
+        let mut leg_id = env
+            .storage()
+            .instance()
+            .get::<_, u64>(&DataKey::LegCounter)
+            .unwrap_or(0);
+        for (bank, leg_quantity) in plan.iter() {
+            leg_id += 1;
+            legs.push_back(FulfillmentLeg {
+                id: leg_id,
+                bank,
+                quantity: leg_quantity,
+                price: unit_price * i128::from(leg_quantity),
+                reservation_id: 0,
+                payment_id: 0,
+                delivery_id: 0,
+                delivered: false,
+            });
+        }
+        env.storage().instance().set(&DataKey::LegCounter, &leg_id);
+
+        let now = env.ledger().timestamp();
+        let mut order = Order {
+            id: order_id,
+            hospital,
+            blood_type,
+            quantity,
+            price,
+            urgency,
+            legs,
+            fulfilled_quantity: 0,
+            status: OrderStatus::Created,
+            created_at: now,
+            updated_at: now,
+            sla_deadline: now + profile.sla_secs,
+        };
+        let hospital = order.hospital.clone();
+        lifecycle::record_milestone(&env, &order, &hospital);
+        lifecycle::advance(&env, &mut order, OrderStatus::Matched, &hospital)?;
+        let mut saga = Saga::new(&env);
+        if let Err(error) = Self::open_order(&env, &mut saga, &mut order, &token, profile.hold_secs) {
+            return Err(saga.compensate(error));
+        }
+        save_order(&env, &order);
+        index_hospital_order(&env, &order.hospital, order_id);
+
+        for leg in order.legs.iter() {
+            CoordLegOpened {
+                order_id,
+                leg_id: leg.id,
+                bank: leg.bank,
+                quantity: leg.quantity,
+                price: leg.price,
+            }
+            .publish(&env);
+        }
+        CoordOrderPlaced {
+            order_id,
+            hospital: order.hospital,
+            quantity,
+            price,
+            leg_count: order.legs.len(),
+        }
+        .publish(&env);
+
+        Ok(order_id)
+    }
+
+    pub fn get_order(env: Env, order_id: u64) -> Result<Order, CoordinatorError> {
+        load_order(&env, order_id).ok_or(CoordinatorError::OrderNotFound)
+    }
+
+    /// Every status the order has entered, oldest first, with who moved it
+    /// there and when.
+    ///
+    /// # Errors
+    /// - `OrderNotFound` — no order with this ID
+    pub fn get_order_timeline(
+        env: Env,
+        order_id: u64,
+    ) -> Result<Vec<OrderMilestone>, CoordinatorError> {
+        if load_order(&env, order_id).is_none() {
+            return Err(CoordinatorError::OrderNotFound);
+        }
+        Ok(lifecycle::timeline(&env, order_id))
+    }
+
+    /// One page (0-based, `ORDERS_PAGE_SIZE` orders) of `hospital`'s orders,
+    /// oldest first.
+    pub fn get_orders_by_hospital(env: Env, hospital: Address, page: u32) -> Vec<Order> {
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::HospitalOrders(hospital, page))
+            .unwrap_or(Vec::new(&env));
+        let mut orders = Vec::new(&env);
+        for order_id in ids.iter() {
+            if let Some(order) = load_order(&env, order_id) {
+                orders.push_back(order);
+            }
+        }
+        orders
+    }
+
+    /// Move an order to `status`; see `lifecycle` for the allowed transitions.
+    ///
+    /// - The admin may make any allowed transition.
+    /// - The ordering hospital may cancel an order that is not yet in transit,
+    ///   dispute it, or complete a delivered order.
+    /// - The registered delivery contract may mark the order in transit; an
+    ///   order becomes delivered through `confirm_leg_delivery`.
+    ///
+    /// Cancelling releases every undelivered leg's stock reservation and
+    /// refunds its escrow, best effort. The escrow of each delivered leg is
+    /// paid to its bank, unless a disputed order is being cancelled.
+    ///
+    /// # Errors
+    /// - `OrderNotFound`          — no order with this ID
+    /// - `Unauthorized`           — `caller` may not make this change
+    /// - `InvalidOrderTransition` — the order's status does not allow it
+    pub fn update_order_status(
+        env: Env,
+        caller: Address,
+        order_id: u64,
+        status: OrderStatus,
+    ) -> Result<(), CoordinatorError> {
+        caller.require_auth();
+        Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
+        let mut order = load_order(&env, order_id).ok_or(CoordinatorError::OrderNotFound)?;
+
+        let allowed = caller == get_admin(&env)?
+            || (caller == order.hospital
+                && match status {
+                    OrderStatus::Cancelled => order.status != OrderStatus::InTransit,
+                    OrderStatus::Disputed | OrderStatus::Completed => true,
+                    _ => false,
+                })
+            || (contract_address(&env, ContractKind::Delivery).ok() == Some(caller)
+                && status == OrderStatus::InTransit);
+        if !allowed {
+            return Err(CoordinatorError::Unauthorized);
+        }
+
+        let from = order.status;
+        lifecycle::advance_and_save(&env, &mut order, status, &caller)?;
+        if status == OrderStatus::Cancelled {
+            lifecycle::release_legs(&env, &order, from != OrderStatus::Disputed);
+        }
+        Ok(())
+    }
+
+    /// Cancel an order that missed its SLA deadline; see `sla`.
+    ///
+    /// Any caller may invoke this once `ledger.timestamp() >= order.sla_deadline`
+    /// while the order is not yet delivered. The stock of every undelivered
+    /// leg is released and its escrow refunded, and the escrow of every
+    /// delivered leg is paid to its bank, best effort. The banks responsible
+    /// are reported to the reputation contract if one is registered.
+    ///
+    /// # Errors
+    /// - `OrderNotFound`          — no order with this ID
+    /// - `SlaNotExpired`          — the SLA deadline has not yet passed
+    /// - `InvalidOrderTransition` — the order is delivered, disputed,
+    ///   completed or cancelled
+    pub fn enforce_sla(env: Env, order_id: u64) -> Result<(), CoordinatorError> {
+        Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
+        let mut order = load_order(&env, order_id).ok_or(CoordinatorError::OrderNotFound)?;
+
+        if env.ledger().timestamp() < order.sla_deadline {
+            return Err(CoordinatorError::SlaNotExpired);
+        }
+        if !sla::is_enforceable(order.status) {
+            return Err(CoordinatorError::InvalidOrderTransition);
+        }
+
+        let status = order.status;
+        lifecycle::advance_and_save(
+            &env,
+            &mut order,
+            OrderStatus::Cancelled,
+            &env.current_contract_address(),
+        )?;
+        lifecycle::release_legs(&env, &order, true);
+        sla::record_violation(&env, &order);
+        CoordSlaViolated {
+            order_id,
+            status,
+            sla_deadline: order.sla_deadline,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Record that leg `leg_index` of an order reached the hospital. Called by
+    /// the delivery contract or the admin while the order is dispatched or in
+    /// transit. The first delivered leg moves the order to `InTransit`; once
+    /// every leg is delivered the order moves to `Delivered` and its escrows
+    /// are released to the banks.
+    ///
+    /// `custody_event_ids` proves the delivery: one custody transfer per unit
+    /// of the leg, each confirmed by the order's hospital in the custody
+    /// contract, for a unit of the leg's bank and the order's blood type. An
+    /// event can prove only one delivery.
+    ///
+    /// # Errors
+    /// - `OrderNotFound`          — no order with this ID
+    /// - `Unauthorized`           — `caller` is neither the delivery contract nor the admin
+    /// - `InvalidOrderTransition` — the order is not dispatched or in transit
+    /// - `LegNotFound`            — the order has no leg at `leg_index`
+    /// - `LegAlreadyDelivered`    — the leg was already confirmed
+    /// - `CustodyNotConfirmed`    — the custody events do not prove the leg's delivery
+    pub fn confirm_leg_delivery(
+        env: Env,
+        caller: Address,
+        order_id: u64,
+        leg_index: u32,
+        custody_event_ids: Vec<String>,
+    ) -> Result<(), CoordinatorError> {
+        caller.require_auth();
+        Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
+        if caller != get_admin(&env)?
+            && contract_address(&env, ContractKind::Delivery).ok() != Some(caller)
+        {
+            return Err(CoordinatorError::Unauthorized);
+        }
+        let mut order = load_order(&env, order_id).ok_or(CoordinatorError::OrderNotFound)?;
+        if order.status == OrderStatus::Dispatched {
+            lifecycle::advance(&env, &mut order, OrderStatus::InTransit, &caller)?;
+        }
+        if order.status != OrderStatus::InTransit {
+            return Err(CoordinatorError::InvalidOrderTransition);
+        }
+
+        let mut leg = order
+            .legs
+            .get(leg_index)
+            .ok_or(CoordinatorError::LegNotFound)?;
+        if leg.delivered {
+            return Err(CoordinatorError::LegAlreadyDelivered);
+        }
+        Self::verify_custody(&env, &order, &leg, &custody_event_ids)?;
+        leg.delivered = true;
+        order.fulfilled_quantity += leg.quantity;
+        order.legs.set(leg_index, leg.clone());
+        CoordLegDelivered {
+            order_id,
+            leg_id: leg.id,
+            fulfilled_quantity: order.fulfilled_quantity,
+        }
+        .publish(&env);
+
+        if order.fulfilled_quantity == order.quantity {
+            lifecycle::advance(&env, &mut order, OrderStatus::Delivered, &caller)?;
+            let pay_addr = breaker::required(&env, ContractKind::Payments)?;
+            breaker::check(
+                &env,
+                ContractKind::Payments,
+                PaymentContractClient::new(&env, &pay_addr).try_release_on_delivery(&order_id),
+                CoordinatorError::PaymentUpdateFailed,
+            )?;
+        }
+        save_order(&env, &order);
+        Ok(())
+    }
+
+    /// Check that `event_ids` are confirmed handoffs of `leg.quantity`
+    /// distinct units of the leg's bank and the order's blood type to the
+    /// order's hospital that proved no earlier delivery, and mark them used.
+    fn verify_custody(
+        env: &Env,
+        order: &Order,
+        leg: &FulfillmentLeg,
+        event_ids: &Vec<String>,
+    ) -> Result<(), CoordinatorError> {
+        if event_ids.len() != leg.quantity {
+            return Err(CoordinatorError::CustodyNotConfirmed);
+        }
+        let custody_addr = breaker::required(env, ContractKind::Custody)?;
+        let custody = CustodyContractClient::new(env, &custody_addr);
+        let mut units: Vec<u64> = Vec::new(env);
+        for event_id in event_ids.iter() {
+            let key = DataKey::CustodyEventUsed(event_id.clone());
+            if env.storage().persistent().has(&key) {
+                return Err(CoordinatorError::CustodyNotConfirmed);
+            }
+            let event = breaker::check(
+                env,
+                ContractKind::Custody,
+                custody.try_get_custody_event(&event_id),
+                CoordinatorError::CustodyNotConfirmed,
+            )?;
+            if event.status != CustodyStatus::Confirmed
+                || event.to_kind != CustodianKind::Hospital
+                || event.to_custodian != order.hospital
+                || (event.from_kind == CustodianKind::Bank && event.from_custodian != leg.bank)
+                || units.contains(event.unit_id)
+            {
+                return Err(CoordinatorError::CustodyNotConfirmed);
+            }
+            // A rider hop names no bank, so the unit's own record decides
+            let unit = breaker::check(
+                env,
+                ContractKind::Custody,
+                custody.try_get_blood_unit(&event.unit_id),
+                CoordinatorError::CustodyNotConfirmed,
+            )?;
+            if unit.bank_id != leg.bank || unit.blood_type != order.blood_type {
+                return Err(CoordinatorError::CustodyNotConfirmed);
+            }
+            units.push_back(event.unit_id);
+            env.storage().persistent().set(&key, &order.id);
+        }
+        Ok(())
+    }
+
+    /// Split `quantity` units of `blood_type` into `(bank, quantity)` legs.
+    /// Only banks within `radius_km` of `hospital` are considered. A single
+    /// bank that can cover the whole order is preferred; otherwise banks are
+    /// drawn on in registration order until the order is covered.
+    fn plan_legs(
+        env: &Env,
+        hospital: &Address,
+        blood_type: BloodType,
+        quantity: u32,
+        radius_km: u32,
+    ) -> Result<Vec<(Address, u32)>, CoordinatorError> {
+        let req_addr = breaker::required(env, ContractKind::Requests)?;
+        let banks = breaker::check(
+            env,
+            ContractKind::Requests,
+            RequestContractClient::new(env, &req_addr).try_get_blood_banks(),
+            CoordinatorError::InsufficientStock,
+        )?;
+
+        let inv_addr = breaker::required(env, ContractKind::Inventory)?;
+        let inv_client = InventoryContractClient::new(env, &inv_addr);
+        let mut stocked = Vec::new(env);
+        for bank in banks.iter() {
+            let distance: Option<u32> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::BankDistance(hospital.clone(), bank.clone()));
+            if distance.is_some_and(|km| km > radius_km) {
+                continue;
+            }
+            let result = inv_client.try_get_available_stock(&bank, &blood_type);
+            breaker::observe(env, ContractKind::Inventory, &result);
+            if let Ok(Ok(available)) = result {
+                if available >= quantity {
+                    return Ok(Vec::from_array(env, [(bank, quantity)]));
+                }
+                if available > 0 {
+                    stocked.push_back((bank, available));
+                }
+            }
+        }
+
+        let mut legs = Vec::new(env);
+        let mut remaining = quantity;
+        for (bank, available) in stocked.iter() {
+            let take = available.min(remaining);
+            legs.push_back((bank, take));
+            remaining -= take;
+            if remaining == 0 {
+                return Ok(legs);
+            }
+        }
+        Err(CoordinatorError::InsufficientStock)
+    }
+
+    /// Reserve stock, open escrow and create the delivery job for every leg
+    /// of `order`, registering each completed step with `saga`. Each step is
+    /// taken for all legs before the order advances to the next status.
+    fn open_order(
+        env: &Env,
+        saga: &mut Saga,
+        order: &mut Order,
+        token: &Address,
+        hold_secs: u64,
+    ) -> Result<(), CoordinatorError> {
+        let hospital = order.hospital.clone();
+        let inv_addr = breaker::required(env, ContractKind::Inventory)?;
+        let inv_client = InventoryContractClient::new(env, &inv_addr);
+        let expires_at = env.ledger().timestamp() + hold_secs;
+        for i in 0..order.legs.len() {
+            let mut leg = order.legs.get_unchecked(i);
+            leg.reservation_id = breaker::check(
+                env,
+                ContractKind::Inventory,
+                inv_client.try_reserve_stock_for_order(
+                    &leg.bank,
+                    &order.blood_type,
+                    &leg.quantity,
+                    &order.id,
+                    &expires_at,
+                ),
+                CoordinatorError::InventoryUpdateFailed,
+            )?;
+            saga.register(Compensation::ReleaseStock(leg.reservation_id));
+            order.legs.set(i, leg);
+        }
+        lifecycle::advance(env, order, OrderStatus::Reserved, &hospital)?;
+
+        let pay_addr = breaker::required(env, ContractKind::Payments)?;
+        let pay_client = PaymentContractClient::new(env, &pay_addr);
+        for i in 0..order.legs.len() {
+            let mut leg = order.legs.get_unchecked(i);
+            leg.payment_id = breaker::check(
+                env,
+                ContractKind::Payments,
+                pay_client.try_open_escrow(&order.id, &order.hospital, &leg.bank, &leg.price, token),
+                CoordinatorError::PaymentUpdateFailed,
+            )?;
+            saga.register(Compensation::RefundEscrow(leg.payment_id));
+            order.legs.set(i, leg);
+        }
+        lifecycle::advance(env, order, OrderStatus::Paid, &hospital)?;
+
+        let delivery_addr = breaker::required(env, ContractKind::Delivery)?;
+        let delivery_client = DeliveryContractClient::new(env, &delivery_addr);
+        for i in 0..order.legs.len() {
+            let mut leg = order.legs.get_unchecked(i);
+            leg.delivery_id = breaker::check(
+                env,
+                ContractKind::Delivery,
+                delivery_client.try_create_delivery_job(
+                    &order.id,
+                    &leg.bank,
+                    &order.hospital,
+                    &leg.quantity,
+                ),
+                CoordinatorError::DeliveryJobFailed,
+            )?;
+            order.legs.set(i, leg);
+        }
+        lifecycle::advance(env, order, OrderStatus::Dispatched, &hospital)
+    }
+
+    /// Step 1 – Allocate inventory units to a pending request and escrow its
+    /// payment. If escrow fails after units were reserved, the reservations
+    /// are released before the error is returned.
     pub fn allocate_units(
         env: Env,
         request_id: u64,
@@ -434,17 +1437,13 @@ impl CoordinatorContract {
         Self::require_not_paused(&env)?;
 
         if let Some(wf) = load_workflow(&env, request_id) {
-            if wf.status != WorkflowStatus::Pending {
+            if wf.status != WorkflowStatus::Pending && wf.status != WorkflowStatus::Escalated {
                 return Err(CoordinatorError::WorkflowAlreadyStarted);
             }
         }
 
         // Verify request is Pending
-        let req_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::RequestContract)
-            .unwrap();
+        let req_addr = breaker::required(&env, ContractKind::Requests)?;
         let req_client = RequestContractClient::new(&env, &req_addr);
         let request = req_client
             .try_get_request(&request_id)
@@ -455,14 +1454,115 @@ impl CoordinatorContract {
             return Err(CoordinatorError::InvalidRequestState);
         }
 
-        // Reserve each inventory unit
-        let inv_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::InventoryContract)
-            .unwrap();
-        let inv_client = InventoryContractClient::new(&env, &inv_addr);
+        let mut saga = Saga::new(&env);
+        let result = Self::reserve_units(&env, &mut saga, &unit_ids, requested_blood_type)
+            .and_then(|()| Self::escrow_payment(&env, &mut saga, payment_id));
+        if let Err(error) = result {
+            return Err(saga.compensate(error));
+        }
+
+        CoordAllocated { request_id, unit_ids: unit_ids.clone(), unit_count: unit_ids.len() }.publish(&env);
+
+        save_workflow(
+            &env,
+            &WorkflowRecord {
+                request_id,
+                payment_id,
+                unit_ids,
+                status: WorkflowStatus::Allocated,
+                delivery_confirmed: false,
+                delivery_location: None,
+                expires_at: env.ledger().timestamp() + WORKFLOW_TIMEOUT_SECS,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Escalate a pending request that no nearby bank can cover. Admin only.
+    ///
+    /// Instead of failing the allocation, the request is broadcast to every
+    /// authorized bank through the requests contract with its deadline pulled
+    /// in to `ESCALATION_RESPONSE_SECS` from now, and the workflow is marked
+    /// `Escalated` until `allocate_units` succeeds. Returns the number of
+    /// banks notified.
+    ///
+    /// # Errors
+    /// - `WorkflowAlreadyStarted` — the request is already escalated or allocated
+    /// - `InvalidRequestState`    — the request is not `Pending`
+    /// - `EscalationFailed`       — the requests contract rejected the broadcast
+    pub fn escalate_request(
+        env: Env,
+        admin: Address,
+        request_id: u64,
+    ) -> Result<u32, CoordinatorError> {
+        admin.require_auth();
+        if admin != get_admin(&env)? {
+            return Err(CoordinatorError::Unauthorized);
+        }
+        Self::require_not_paused(&env)?;
+
+        if let Some(wf) = load_workflow(&env, request_id) {
+            if wf.status != WorkflowStatus::Pending {
+                return Err(CoordinatorError::WorkflowAlreadyStarted);
+            }
+        }
+
+        let req_addr = breaker::required(&env, ContractKind::Requests)?;
+        let req_client = RequestContractClient::new(&env, &req_addr);
+        let request = req_client
+            .try_get_request(&request_id)
+            .map_err(|_| CoordinatorError::RequestNotFound)?
+            .map_err(|_| CoordinatorError::RequestNotFound)?;
+        if request.status != RequestStatus::Pending {
+            return Err(CoordinatorError::InvalidRequestState);
+        }
+
+        let respond_by = env.ledger().timestamp() + ESCALATION_RESPONSE_SECS;
+        let banks_notified = breaker::check(
+            &env,
+            ContractKind::Requests,
+            req_client.try_broadcast_request(&request_id, &respond_by),
+            CoordinatorError::EscalationFailed,
+        )?;
+
+        // The payment and units are unknown until allocation fills them in.
+        save_workflow(
+            &env,
+            &WorkflowRecord {
+                request_id,
+                payment_id: 0,
+                unit_ids: Vec::new(&env),
+                status: WorkflowStatus::Escalated,
+                delivery_confirmed: false,
+                delivery_location: None,
+                expires_at: respond_by,
+            },
+        );
+
+        CoordEscalated { request_id, banks_notified, respond_by }.publish(&env);
+
+        Ok(banks_notified)
+    }
+
+    /// Reserve each unit, registering its release with `saga`.
+    ///
+    /// When a temperature contract is registered, units it reports as
+    /// compromised are refused. The check is optional: if the temperature
+    /// contract fails, or its breaker is open, units are reserved unchecked.
+    fn reserve_units(
+        env: &Env,
+        saga: &mut Saga,
+        unit_ids: &Vec<u64>,
+        requested_blood_type: BloodType,
+    ) -> Result<(), CoordinatorError> {
+        let inv_addr = breaker::required(env, ContractKind::Inventory)?;
+        let inv_client = InventoryContractClient::new(env, &inv_addr);
         let inv_admin = inv_client.get_admin();
+        let temperature = match breaker::target(env, ContractKind::Temperature) {
+            Ok(Some(temp_addr)) => Some(TemperatureContractClient::new(env, &temp_addr)),
+            _ => None,
+        };
 
         for i in 0..unit_ids.len() {
             let uid = unit_ids.get(i).unwrap();
@@ -479,28 +1579,53 @@ impl CoordinatorContract {
                 return Err(CoordinatorError::IncompatibleBloodType);
             }
 
+            if let Some(temp_client) = &temperature {
+                let result = temp_client.try_is_compromised(&uid);
+                breaker::observe(env, ContractKind::Temperature, &result);
+                if let Ok(Ok(true)) = result {
+                    return Err(CoordinatorError::UnitCompromised);
+                }
+            }
+
             inv_client
                 .try_update_status(&uid, &BloodStatus::Reserved, &inv_admin, &None)
                 .map_err(|_| CoordinatorError::InventoryUpdateFailed)?
                 .map_err(|_| CoordinatorError::InventoryUpdateFailed)?;
+            saga.register(Compensation::ReleaseUnit(uid));
         }
+        Ok(())
+    }
 
-        CoordAllocated { request_id, unit_ids: unit_ids.clone(), unit_count: unit_ids.len() }.publish(&env);
-
-        save_workflow(
-            &env,
-            &WorkflowRecord {
-                request_id,
-                payment_id,
-                unit_ids,
-                status: WorkflowStatus::Allocated,
-                delivery_confirmed: false,
-                delivery_location: None,
-                expires_at: env.ledger().timestamp() + WORKFLOW_TIMEOUT_SECS,
-            },
-        );
+    /// Fund a `Pending` payment from its payer in the payment token and lock
+    /// it in escrow, registering its refund with `saga`. A payment that is
+    /// already `Locked` is accepted as is.
+    fn escrow_payment(env: &Env, saga: &mut Saga, payment_id: u64) -> Result<(), CoordinatorError> {
+        let pay_addr = breaker::required(env, ContractKind::Payments)?;
+        let pay_client = PaymentContractClient::new(env, &pay_addr);
+        let payment = pay_client
+            .try_get_payment(&payment_id)
+            .map_err(|_| CoordinatorError::PaymentNotFound)?
+            .map_err(|_| CoordinatorError::PaymentNotFound)?;
 
-        Ok(())
+        match payment.status {
+            PaymentStatus::Locked => Ok(()),
+            PaymentStatus::Pending => {
+                let token: Address = env
+                    .storage()
+                    .instance()
+                    .get(&DataKey::PaymentToken)
+                    .ok_or(CoordinatorError::PricingNotConfigured)?;
+                breaker::check(
+                    env,
+                    ContractKind::Payments,
+                    pay_client.try_lock_payment(&payment_id, &token),
+                    CoordinatorError::PaymentUpdateFailed,
+                )?;
+                saga.register(Compensation::RefundEscrow(payment_id));
+                Ok(())
+            }
+            _ => Err(CoordinatorError::InvalidPaymentState),
+        }
     }
 
     /// Step 2 – Confirm delivery: mark all reserved units as Delivered.
@@ -526,11 +1651,7 @@ impl CoordinatorContract {
             return Err(CoordinatorError::InvalidWorkflowState);
         }
 
-        let inv_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::InventoryContract)
-            .unwrap();
+        let inv_addr = breaker::required(&env, ContractKind::Inventory)?;
         let inv_client = InventoryContractClient::new(&env, &inv_addr);
         let inv_admin = inv_client.get_admin();
 
@@ -574,11 +1695,7 @@ impl CoordinatorContract {
             return Err(CoordinatorError::DeliveryNotConfirmed);
         }
 
-        let pay_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::PaymentContract)
-            .unwrap();
+        let pay_addr = breaker::required(&env, ContractKind::Payments)?;
         let pay_client = PaymentContractClient::new(&env, &pay_addr);
 
         let payment = pay_client
@@ -616,11 +1733,7 @@ impl CoordinatorContract {
             return Err(CoordinatorError::CannotRollbackSettled);
         }
 
-        let inv_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::InventoryContract)
-            .unwrap();
+        let inv_addr = breaker::required(&env, ContractKind::Inventory)?;
         let inv_client = InventoryContractClient::new(&env, &inv_addr);
         let inv_admin = inv_client.get_admin();
 
@@ -632,11 +1745,7 @@ impl CoordinatorContract {
                 .map_err(|_| CoordinatorError::InventoryUpdateFailed)?;
         }
 
-        let pay_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::PaymentContract)
-            .unwrap();
+        let pay_addr = breaker::required(&env, ContractKind::Payments)?;
         let pay_client = PaymentContractClient::new(&env, &pay_addr);
         let payment = pay_client
             .try_get_payment(&wf.payment_id)
@@ -689,11 +1798,7 @@ impl CoordinatorContract {
         // Reuse the existing rollback logic to release units and refund payment.
         // We call `get_admin` only to satisfy the inventory client's admin
         // parameter — the coordinator itself is authorised to update inventory.
-        let inv_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::InventoryContract)
-            .unwrap();
+        let inv_addr = breaker::required(&env, ContractKind::Inventory)?;
         let inv_client = InventoryContractClient::new(&env, &inv_addr);
         let inv_admin = inv_client.get_admin();
 
@@ -705,11 +1810,7 @@ impl CoordinatorContract {
                 .map_err(|_| CoordinatorError::InventoryUpdateFailed)?;
         }
 
-        let pay_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::PaymentContract)
-            .unwrap();
+        let pay_addr = breaker::required(&env, ContractKind::Payments)?;
         let pay_client = PaymentContractClient::new(&env, &pay_addr);
         let payment = pay_client
             .try_get_payment(&wf.payment_id)
@@ -738,8 +1839,10 @@ impl CoordinatorContract {
     /// Flag a temperature breach: transitions the linked payment from Locked → Disputed.
     ///
     /// Called by the temperature contract when a sustained excursion is detected.
+    /// Once a temperature contract is registered, only it may call this.
     ///
     /// # Errors
+    /// - `Unauthorized`        - Caller is not the registered temperature contract
     /// - `PaymentNotFound`     - No payment with this ID
     /// - `InvalidPaymentState` - Payment is not in Locked status
     /// - `PaymentFlagFailed`   - Cross-contract call to payments failed
@@ -752,12 +1855,13 @@ impl CoordinatorContract {
         caller.require_auth();
         Self::require_initialized(&env)?;
         Self::require_not_paused(&env)?;
+        if let Ok(temperature) = contract_address(&env, ContractKind::Temperature) {
+            if caller != temperature {
+                return Err(CoordinatorError::Unauthorized);
+            }
+        }
 
-        let pay_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::PaymentContract)
-            .unwrap();
+        let pay_addr = breaker::required(&env, ContractKind::Payments)?;
         let pay_client = PaymentContractClient::new(&env, &pay_addr);
 
         let payment = pay_client
@@ -831,6 +1935,14 @@ impl CoordinatorContract {
         Ok(())
     }
 
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), CoordinatorError> {
+        admin.require_auth();
+        if *admin != get_admin(env)? {
+            return Err(CoordinatorError::Unauthorized);
+        }
+        Ok(())
+    }
+
     /// Upgrade the contract to a new WASM hash. Only admin can call this.
     ///
     /// # Arguments
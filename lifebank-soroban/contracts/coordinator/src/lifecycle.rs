@@ -0,0 +1,136 @@
+//! Order lifecycle.
+//!
+//! ```text
+//! Created → Matched → Reserved → Paid → Dispatched → InTransit → Delivered → Completed
+//! ```
+//!
+//! An order can be cancelled until it is delivered, and disputed once paid.
+//! Only the admin or SLA enforcement cancel an order in transit. A dispute
+//! ends with the order either completed or cancelled. `Completed` and
+//! `Cancelled` are terminal.
+//!
+//! Every state an order enters, starting with `Created`, is appended to its
+//! timeline as a milestone recording who moved it there and when.
+
+use crate::saga::{Compensation, Saga};
+use crate::{
+    breaker, routing, save_order, ContractKind, CoordOrderMilestone, CoordinatorError, DataKey,
+    Order, OrderMilestone, OrderStatus, PaymentContractClient,
+};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Whether an order may move from `from` to `to`.
+pub fn can_transition(from: OrderStatus, to: OrderStatus) -> bool {
+    use OrderStatus::*;
+    matches!(
+        (from, to),
+        (Created, Matched)
+            | (Matched, Reserved)
+            | (Reserved, Paid)
+            | (Paid, Dispatched)
+            | (Dispatched, InTransit)
+            | (InTransit, Delivered)
+            | (Delivered, Completed)
+            | (Created | Matched | Reserved | Paid | Dispatched | InTransit, Cancelled)
+            | (Paid | Dispatched | InTransit | Delivered, Disputed)
+            | (Disputed, Completed | Cancelled)
+    )
+}
+
+pub fn timeline(env: &Env, order_id: u64) -> Vec<OrderMilestone> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OrderTimeline(order_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Append the order's current status to its timeline and emit it.
+pub fn record_milestone(env: &Env, order: &Order, actor: &Address) {
+    const ORDER_TTL_LEDGERS: u32 = 535_680; // ~30 days at 5s/ledger
+
+    let timestamp = env.ledger().timestamp();
+    let mut milestones = timeline(env, order.id);
+    milestones.push_back(OrderMilestone {
+        milestone: order.status,
+        actor: actor.clone(),
+        timestamp,
+    });
+    let key = DataKey::OrderTimeline(order.id);
+    env.storage().persistent().set(&key, &milestones);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, ORDER_TTL_LEDGERS, ORDER_TTL_LEDGERS);
+    CoordOrderMilestone {
+        order_id: order.id,
+        milestone: order.status,
+        actor: actor.clone(),
+        timestamp,
+    }
+    .publish(env);
+}
+
+/// Move `order` to `to` on behalf of `actor` and record the milestone. The
+/// order itself is not stored; callers save it once all of its changes are
+/// made.
+pub fn advance(
+    env: &Env,
+    order: &mut Order,
+    to: OrderStatus,
+    actor: &Address,
+) -> Result<(), CoordinatorError> {
+    if !can_transition(order.status, to) {
+        return Err(CoordinatorError::InvalidOrderTransition);
+    }
+    let from = order.status;
+    order.status = to;
+    order.updated_at = env.ledger().timestamp();
+    routing::on_status_change(env, order, from);
+    record_milestone(env, order, actor);
+    Ok(())
+}
+
+/// Apply `to` to a stored order and save it.
+pub fn advance_and_save(
+    env: &Env,
+    order: &mut Order,
+    to: OrderStatus,
+    actor: &Address,
+) -> Result<(), CoordinatorError> {
+    advance(env, order, to, actor)?;
+    save_order(env, order);
+    Ok(())
+}
+
+/// Release the stock reservation and refund the escrow of every undelivered
+/// leg of a cancelled order, best effort. With `pay_delivered`, the escrow of
+/// each delivered leg is paid to its bank; otherwise it stays locked for the
+/// admin to settle.
+pub fn release_legs(env: &Env, order: &Order, pay_delivered: bool) {
+    let mut saga = Saga::new(env);
+    for leg in order.legs.iter() {
+        if leg.delivered {
+            continue;
+        }
+        if leg.reservation_id != 0 {
+            saga.register(Compensation::ReleaseStock(leg.reservation_id));
+        }
+        if leg.payment_id != 0 {
+            saga.register(Compensation::RefundEscrow(leg.payment_id));
+        }
+    }
+    saga.unwind();
+
+    if !pay_delivered {
+        return;
+    }
+    let Ok(Some(pay_addr)) = breaker::target(env, ContractKind::Payments) else {
+        return;
+    };
+    let pay_client = PaymentContractClient::new(env, &pay_addr);
+    for leg in order.legs.iter() {
+        if leg.delivered && leg.payment_id != 0 {
+            let result = pay_client.try_release_order_escrow(&leg.payment_id);
+            breaker::observe(env, ContractKind::Payments, &result);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! Urgency-dependent order routing.
+//!
+//! An order's `Urgency` selects an [`UrgencyProfile`]: how far away a bank may
+//! be, how long its stock is held, the premium added to the unit price, and
+//! the delivery deadline.
+//!
+//! Emergency orders may preempt routine ones. When the registered banks
+//! cannot cover an emergency order, open routine orders for the same blood
+//! type are cancelled, newest first, until they can. An order is open while
+//! it is `Dispatched`, i.e. before any of its stock leaves a bank. A
+//! preempted order has its reservations released and its escrow refunded,
+//! exactly as if its hospital had cancelled it. Urgent orders neither
+//! preempt nor are preempted.
+
+use crate::{
+    lifecycle, load_order, save_order, BloodType, CoordOrderPreempted, CoordinatorError,
+    DataKey, Order, OrderStatus, Urgency, UrgencyProfile,
+};
+use soroban_sdk::{Address, Env, Vec};
+
+const HOUR: u64 = 60 * 60;
+
+pub fn profile(urgency: Urgency) -> UrgencyProfile {
+    match urgency {
+        Urgency::Routine => UrgencyProfile {
+            radius_km: 25,
+            hold_secs: 24 * HOUR,
+            premium_bps: 0,
+            sla_secs: 24 * HOUR,
+        },
+        Urgency::Urgent => UrgencyProfile {
+            radius_km: 75,
+            hold_secs: 8 * HOUR,
+            premium_bps: 2_000,
+            sla_secs: 6 * HOUR,
+        },
+        Urgency::Emergency => UrgencyProfile {
+            radius_km: u32::MAX,
+            hold_secs: 2 * HOUR,
+            premium_bps: 5_000,
+            sla_secs: HOUR,
+        },
+    }
+}
+
+/// `unit_price` with the urgency premium added.
+pub fn premium_price(unit_price: i128, premium_bps: u32) -> Option<i128> {
+    unit_price
+        .checked_mul(10_000 + i128::from(premium_bps))
+        .map(|scaled| scaled / 10_000)
+}
+
+fn open_routine_orders(env: &Env, blood_type: BloodType) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OpenRoutineOrders(blood_type))
+        .unwrap_or(Vec::new(env))
+}
+
+fn set_open_routine_orders(env: &Env, blood_type: BloodType, order_ids: &Vec<u64>) {
+    const ORDER_TTL_LEDGERS: u32 = 535_680; // ~30 days at 5s/ledger
+
+    let key = DataKey::OpenRoutineOrders(blood_type);
+    env.storage().persistent().set(&key, order_ids);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, ORDER_TTL_LEDGERS, ORDER_TTL_LEDGERS);
+}
+
+/// Keep the index of open routine orders in step with a status change.
+/// Called by `lifecycle::advance` for every transition.
+pub fn on_status_change(env: &Env, order: &Order, from: OrderStatus) {
+    if order.urgency != Urgency::Routine {
+        return;
+    }
+    let mut order_ids = open_routine_orders(env, order.blood_type);
+    if order.status == OrderStatus::Dispatched {
+        order_ids.push_back(order.id);
+    } else if from == OrderStatus::Dispatched {
+        let Some(index) = order_ids.first_index_of(order.id) else {
+            return;
+        };
+        order_ids.remove(index);
+    } else {
+        return;
+    }
+    set_open_routine_orders(env, order.blood_type, &order_ids);
+}
+
+/// Cancel open routine orders for `blood_type`, newest first, until
+/// `covered()` reports that enough stock is free. Returns whether it was
+/// reached. The cancellations are attributed to `emergency_hospital`.
+pub fn preempt_routine(
+    env: &Env,
+    blood_type: BloodType,
+    emergency_order_id: u64,
+    emergency_hospital: &Address,
+    mut covered: impl FnMut() -> bool,
+) -> Result<bool, CoordinatorError> {
+    let mut order_ids = open_routine_orders(env, blood_type);
+    while let Some(order_id) = order_ids.pop_back() {
+        let Some(mut order) = load_order(env, order_id) else {
+            continue;
+        };
+        // Removes the order from the index
+        lifecycle::advance(env, &mut order, OrderStatus::Cancelled, emergency_hospital)?;
+        save_order(env, &order);
+        lifecycle::release_legs(env, &order, true);
+        CoordOrderPreempted {
+            order_id,
+            preempted_by: emergency_order_id,
+        }
+        .publish(env);
+
+        if covered() {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
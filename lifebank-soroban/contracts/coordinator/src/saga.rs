@@ -0,0 +1,91 @@
+//! Compensating actions for multi-step order flows.
+//!
+//! Every step that changes a downstream contract registers how to undo that
+//! change. When a later step fails, the coordinator runs the registered
+//! compensations newest first and then returns the failing step's error, so a
+//! half-completed flow never leaves a reservation or an escrow lock behind.
+
+use crate::{
+    breaker, contract_address, BloodStatus, ContractKind, CoordinatorError, InventoryContractClient,
+    PaymentContractClient,
+};
+use soroban_sdk::{contracttype, Env, Vec};
+
+/// Undo action for one completed step.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Compensation {
+    /// Return a unit reserved by the flow to `Available`.
+    ReleaseUnit(u64),
+    /// Release a stock reservation opened by the flow.
+    ReleaseStock(u64),
+    /// Refund an escrow opened or locked by the flow to its payer.
+    RefundEscrow(u64),
+}
+
+pub struct Saga<'a> {
+    env: &'a Env,
+    compensations: Vec<Compensation>,
+}
+
+impl<'a> Saga<'a> {
+    pub fn new(env: &'a Env) -> Self {
+        Saga {
+            env,
+            compensations: Vec::new(env),
+        }
+    }
+
+    /// Record how to undo a step that has just succeeded.
+    pub fn register(&mut self, compensation: Compensation) {
+        self.compensations.push_back(compensation);
+    }
+
+    /// Run every registered compensation, newest first, and hand back `error`
+    /// for the caller to return.
+    pub fn compensate(self, error: CoordinatorError) -> CoordinatorError {
+        self.unwind();
+        error
+    }
+
+    /// Run every registered compensation, newest first. Compensations are
+    /// best effort: one that fails does not stop the others from running.
+    pub fn unwind(self) {
+        let env = self.env;
+        for compensation in self.compensations.iter().rev() {
+            match compensation {
+                Compensation::ReleaseUnit(unit_id) => {
+                    let Ok(inv_addr) = contract_address(env, ContractKind::Inventory) else {
+                        continue;
+                    };
+                    let inv_client = InventoryContractClient::new(env, &inv_addr);
+                    if let Ok(Ok(inv_admin)) = inv_client.try_get_admin() {
+                        let result = inv_client.try_update_status(
+                            &unit_id,
+                            &BloodStatus::Available,
+                            &inv_admin,
+                            &None,
+                        );
+                        breaker::observe(env, ContractKind::Inventory, &result);
+                    }
+                }
+                Compensation::ReleaseStock(reservation_id) => {
+                    let Ok(inv_addr) = contract_address(env, ContractKind::Inventory) else {
+                        continue;
+                    };
+                    let result = InventoryContractClient::new(env, &inv_addr)
+                        .try_release_stock_reservation(&env.current_contract_address(), &reservation_id);
+                    breaker::observe(env, ContractKind::Inventory, &result);
+                }
+                Compensation::RefundEscrow(payment_id) => {
+                    let Ok(pay_addr) = contract_address(env, ContractKind::Payments) else {
+                        continue;
+                    };
+                    let result =
+                        PaymentContractClient::new(env, &pay_addr).try_refund_on_cancel(&payment_id);
+                    breaker::observe(env, ContractKind::Payments, &result);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! Order SLA enforcement.
+//!
+//! Every order carries the delivery deadline its urgency sets (see
+//! `routing`). Once that deadline has passed, anyone may call `enforce_sla`
+//! on an order that is not yet delivered. The order is cancelled, the stock
+//! of its undelivered legs is released and their escrows refunded, the
+//! escrows of its delivered legs are paid to their banks, and each bank with
+//! an undelivered leg has a failed assignment recorded against its
+//! reputation.
+//!
+//! Reputation is an optional integration. A bank is only reported once the
+//! admin has mapped it to a reputation entity with `set_reputation_entity`,
+//! and reports are skipped while no reputation contract is registered or its
+//! breaker is open. A failing report never blocks the cancellation.
+
+use crate::{breaker, ContractKind, DataKey, Order, OrderStatus, ReputationContractClient};
+use soroban_sdk::Env;
+
+/// Whether an order in `status` is still bound by its SLA.
+pub fn is_enforceable(status: OrderStatus) -> bool {
+    use OrderStatus::*;
+    matches!(
+        status,
+        Created | Matched | Reserved | Paid | Dispatched | InTransit
+    )
+}
+
+/// Record a failed assignment against every bank that left a leg of `order`
+/// undelivered.
+pub fn record_violation(env: &Env, order: &Order) {
+    let Ok(Some(rep_addr)) = breaker::target(env, ContractKind::Reputation) else {
+        return;
+    };
+    let rep_client = ReputationContractClient::new(env, &rep_addr);
+    let now = env.ledger().timestamp();
+    for leg in order.legs.iter() {
+        if leg.delivered {
+            continue;
+        }
+        let Some(entity_id) = env
+            .storage()
+            .persistent()
+            .get::<_, u64>(&DataKey::ReputationEntity(leg.bank))
+        else {
+            continue;
+        };
+        let result =
+            rep_client.try_record_assignment(&entity_id, &false, &(now - order.created_at), &now);
+        breaker::observe(env, ContractKind::Reputation, &result);
+    }
+}
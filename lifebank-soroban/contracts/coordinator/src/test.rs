@@ -7,12 +7,14 @@
 //! drives the full request → allocation → delivery → settlement sequence.
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, testutils::Address as _, vec, Address, Env, String, Vec,
+    contract, contractimpl, contracttype, symbol_short, testutils::Address as _, vec, Address, Env,
+    String, Vec,
 };
 
 use super::{
-    BloodRequest, BloodStatus, BloodUnit, CoordinatorContract, CoordinatorContractClient,
-    CoordinatorError, Payment, PaymentStatus, RequestStatus, WorkflowStatus,
+    BloodComponent, BloodRequest, BloodStatus, BloodType, BloodUnit, ContractKind,
+    CoordinatorContract, CoordinatorContractClient, CoordinatorError, CustodianKind, CustodyEvent,
+    CustodyStatus, CustodyUnit, CustodyUnitStatus, OrderStatus, Payment, PaymentStatus, RequestStatus, Urgency, WorkflowStatus,
 };
 
 // ── Mock: Request contract ────────────────────────────────────────────────────
@@ -21,6 +23,7 @@ use super::{
 enum ReqKey {
     Request(u64),
     Counter,
+    Banks,
 }
 
 #[contract]
@@ -40,6 +43,22 @@ impl MockRequestContract {
             .get(&ReqKey::Request(request_id))
             .unwrap()
     }
+
+    /// Pretends three banks are registered.
+    pub fn broadcast_request(_env: Env, _request_id: u64, _respond_by: u64) -> u32 {
+        3
+    }
+
+    pub fn seed_blood_banks(env: Env, banks: Vec<Address>) {
+        env.storage().persistent().set(&ReqKey::Banks, &banks);
+    }
+
+    pub fn get_blood_banks(env: Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&ReqKey::Banks)
+            .unwrap_or(Vec::new(&env))
+    }
 }
 
 // ── Mock: Inventory contract ──────────────────────────────────────────────────
@@ -49,6 +68,9 @@ enum InvKey {
     Unit(u64),
     Admin,
     Counter,
+    Stock(Address, BloodType),
+    StockReservation(u64),
+    ReservationCounter,
 }
 
 #[contract]
@@ -115,6 +137,55 @@ impl MockInventoryContract {
     ) -> BloodUnit {
         Self::update_status(env, unit_id, BloodStatus::Delivered, authorized_by, Some(delivery_location))
     }
+
+    pub fn set_stock(env: Env, bank: Address, blood_type: BloodType, quantity: u32) {
+        env.storage()
+            .persistent()
+            .set(&InvKey::Stock(bank, blood_type), &quantity);
+    }
+
+    pub fn get_available_stock(env: Env, bank: Address, blood_type: BloodType) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&InvKey::Stock(bank, blood_type))
+            .unwrap_or(0)
+    }
+
+    pub fn reserve_stock_for_order(
+        env: Env,
+        bank: Address,
+        blood_type: BloodType,
+        quantity: u32,
+        _order_id: u64,
+        _expires_at: u64,
+    ) -> u64 {
+        let available = Self::get_available_stock(env.clone(), bank.clone(), blood_type);
+        Self::set_stock(env.clone(), bank.clone(), blood_type, available - quantity);
+        let id: u64 = env
+            .storage()
+            .instance()
+            .get(&InvKey::ReservationCounter)
+            .unwrap_or(0u64)
+            + 1;
+        env.storage().instance().set(&InvKey::ReservationCounter, &id);
+        env.storage()
+            .persistent()
+            .set(&InvKey::StockReservation(id), &(bank, blood_type, quantity));
+        id
+    }
+
+    pub fn release_stock_reservation(env: Env, _caller: Address, reservation_id: u64) {
+        let (bank, blood_type, quantity): (Address, BloodType, u32) = env
+            .storage()
+            .persistent()
+            .get(&InvKey::StockReservation(reservation_id))
+            .unwrap();
+        env.storage()
+            .persistent()
+            .remove(&InvKey::StockReservation(reservation_id));
+        let available = Self::get_available_stock(env.clone(), bank.clone(), blood_type);
+        Self::set_stock(env, bank, blood_type, available + quantity);
+    }
 }
 
 // ── Mock: Payment contract ────────────────────────────────────────────────────
@@ -123,6 +194,7 @@ impl MockInventoryContract {
 enum PayKey {
     Payment(u64),
     Counter,
+    OrderEscrows(u64),
 }
 
 #[contract]
@@ -164,6 +236,50 @@ impl MockPaymentContract {
             .set(&PayKey::Payment(payment_id), &p);
     }
 
+    pub fn open_escrow(
+        env: Env,
+        order_id: u64,
+        _payer: Address,
+        _payee: Address,
+        _amount: i128,
+        _token: Address,
+    ) -> u64 {
+        let id = Self::create_payment(env.clone(), order_id, PaymentStatus::Locked);
+        let mut ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&PayKey::OrderEscrows(order_id))
+            .unwrap_or(Vec::new(&env));
+        ids.push_back(id);
+        env.storage().persistent().set(&PayKey::OrderEscrows(order_id), &ids);
+        id
+    }
+
+    pub fn release_on_delivery(env: Env, order_id: u64) {
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&PayKey::OrderEscrows(order_id))
+            .unwrap();
+        for id in ids.iter() {
+            if Self::get_payment(env.clone(), id).status == PaymentStatus::Locked {
+                Self::update_status(env.clone(), id, PaymentStatus::Released);
+            }
+        }
+    }
+
+    pub fn release_order_escrow(env: Env, payment_id: u64) {
+        Self::update_status(env, payment_id, PaymentStatus::Released);
+    }
+
+    pub fn lock_payment(env: Env, payment_id: u64, _token: Address) {
+        Self::update_status(env, payment_id, PaymentStatus::Locked);
+    }
+
+    pub fn refund_on_cancel(env: Env, payment_id: u64) {
+        Self::update_status(env, payment_id, PaymentStatus::Refunded);
+    }
+
     pub fn record_dispute(env: Env, payment_id: u64, _reason: super::payment_client::DisputeReason, _case_id: String) {
         let mut p: Payment = env
             .storage()
@@ -177,6 +293,143 @@ impl MockPaymentContract {
     }
 }
 
+// ── Mock: Delivery contract ───────────────────────────────────────────────────
+
+#[contracttype]
+enum DelKey {
+    Counter,
+    Failing,
+}
+
+#[contract]
+struct MockDeliveryContract;
+
+#[contractimpl]
+impl MockDeliveryContract {
+    pub fn set_failing(env: Env, failing: bool) {
+        env.storage().instance().set(&DelKey::Failing, &failing);
+    }
+
+    pub fn create_delivery_job(
+        env: Env,
+        _order_id: u64,
+        _pickup: Address,
+        _dropoff: Address,
+        _unit_count: u32,
+    ) -> u64 {
+        if env.storage().instance().get(&DelKey::Failing).unwrap_or(false) {
+            panic!("delivery unavailable");
+        }
+        let id: u64 = env.storage().instance().get(&DelKey::Counter).unwrap_or(0u64) + 1;
+        env.storage().instance().set(&DelKey::Counter, &id);
+        id
+    }
+}
+
+// ── Mock: Temperature contract ────────────────────────────────────────────────
+
+#[contracttype]
+enum TempKey {
+    Compromised(u64),
+    Failing,
+}
+
+#[contract]
+struct MockTemperatureContract;
+
+#[contractimpl]
+impl MockTemperatureContract {
+    pub fn set_failing(env: Env, failing: bool) {
+        env.storage().instance().set(&TempKey::Failing, &failing);
+    }
+
+    pub fn set_compromised(env: Env, unit_id: u64) {
+        env.storage().persistent().set(&TempKey::Compromised(unit_id), &true);
+    }
+
+    pub fn is_compromised(env: Env, unit_id: u64) -> bool {
+        if env.storage().instance().get(&TempKey::Failing).unwrap_or(false) {
+            panic!("temperature unavailable");
+        }
+        env.storage()
+            .persistent()
+            .get(&TempKey::Compromised(unit_id))
+            .unwrap_or(false)
+    }
+}
+
+// ── Mock: Reputation contract ─────────────────────────────────────────────────
+
+#[contracttype]
+enum RepKey {
+    Failed(u64),
+}
+
+#[contract]
+struct MockReputationContract;
+
+#[contractimpl]
+impl MockReputationContract {
+    pub fn record_assignment(
+        env: Env,
+        entity_id: u64,
+        completed: bool,
+        _response_secs: u64,
+        _timestamp: u64,
+    ) -> i64 {
+        if !completed {
+            let failed = Self::failed(env.clone(), entity_id) + 1;
+            env.storage().persistent().set(&RepKey::Failed(entity_id), &failed);
+        }
+        100
+    }
+
+    pub fn failed(env: Env, entity_id: u64) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&RepKey::Failed(entity_id))
+            .unwrap_or(0)
+    }
+}
+
+// ── Mock: Custody contract ────────────────────────────────────────────────────
+
+#[contracttype]
+enum CusKey {
+    Event(String),
+    Unit(u64),
+}
+
+#[contract]
+struct MockCustodyContract;
+
+#[contractimpl]
+impl MockCustodyContract {
+    pub fn seed_event(env: Env, event: CustodyEvent) {
+        env.storage()
+            .persistent()
+            .set(&CusKey::Event(event.event_id.clone()), &event);
+    }
+
+    pub fn get_custody_event(env: Env, event_id: String) -> CustodyEvent {
+        env.storage()
+            .persistent()
+            .get(&CusKey::Event(event_id))
+            .unwrap()
+    }
+
+    pub fn seed_unit(env: Env, unit: CustodyUnit) {
+        env.storage().persistent().set(&CusKey::Unit(unit.id), &unit);
+    }
+
+    pub fn get_blood_unit(env: Env, unit_id: u64) -> CustodyUnit {
+        env.storage()
+            .persistent()
+            .get(&CusKey::Unit(unit_id))
+            .unwrap()
+    }
+}
+
 // ── Harness ───────────────────────────────────────────────────────────────────
 
 struct Harness<'a> {
@@ -186,6 +439,8 @@ struct Harness<'a> {
     req_id: Address,
     inv_id: Address,
     pay_id: Address,
+    del_id: Address,
+    cus_id: Address,
 }
 
 fn setup<'a>() -> Harness<'a> {
@@ -197,6 +452,8 @@ fn setup<'a>() -> Harness<'a> {
     let req_id = env.register(MockRequestContract, ());
     let inv_id = env.register(MockInventoryContract, ());
     let pay_id = env.register(MockPaymentContract, ());
+    let del_id = env.register(MockDeliveryContract, ());
+    let cus_id = env.register(MockCustodyContract, ());
     let coord_id = env.register(CoordinatorContract, ());
 
     // Initialize inventory mock with admin
@@ -205,8 +462,10 @@ fn setup<'a>() -> Harness<'a> {
 
     let coord = CoordinatorContractClient::new(&env, &coord_id);
     coord.initialize(&admin, &req_id, &inv_id, &pay_id);
+    coord.set_contract_address(&admin, &ContractKind::Delivery, &del_id);
+    coord.set_contract_address(&admin, &ContractKind::Custody, &cus_id);
 
-    Harness { env, admin, coord, req_id, inv_id, pay_id }
+    Harness { env, admin, coord, req_id, inv_id, pay_id, del_id, cus_id }
 }
 
 fn seed_pending_request(h: &Harness, id: u64) {
@@ -646,3 +905,729 @@ fn test_expire_workflow_blocked_for_delivered_workflow() {
         "expire_workflow must fail for a Delivered workflow"
     );
 }
+
+// ── Saga compensation tests ───────────────────────────────────────────────────
+
+/// A Pending payment is locked in escrow as part of allocation, once a
+/// payment token is configured.
+#[test]
+fn test_allocate_units_escrows_pending_payment() {
+    let h = setup();
+    seed_pending_request(&h, 1);
+    let unit_id = register_unit(&h);
+    let payment_id = MockPaymentContractClient::new(&h.env, &h.pay_id)
+        .create_payment(&1u64, &PaymentStatus::Pending);
+
+    let result = h.coord.try_allocate_units(
+        &1u64,
+        &vec![&h.env, unit_id],
+        &payment_id,
+        &h.admin,
+        &BloodType::ONegative,
+    );
+    assert_eq!(result, Err(Ok(CoordinatorError::PricingNotConfigured)));
+    let inv = MockInventoryContractClient::new(&h.env, &h.inv_id);
+    assert_eq!(inv.get_blood_unit(&unit_id).status, BloodStatus::Available);
+
+    h.coord.set_payment_token(&h.admin, &Address::generate(&h.env));
+    h.coord.allocate_units(&1u64, &vec![&h.env, unit_id], &payment_id, &h.admin, &BloodType::ONegative);
+
+    let payment = MockPaymentContractClient::new(&h.env, &h.pay_id).get_payment(&payment_id);
+    assert_eq!(payment.status, PaymentStatus::Locked);
+}
+
+/// When escrow fails after units were reserved, the reservations are
+/// released and the escrow error is returned.
+#[test]
+fn test_failed_escrow_releases_reserved_units() {
+    let h = setup();
+    seed_pending_request(&h, 1);
+    let first = register_unit(&h);
+    let second = register_unit(&h);
+    let payment_id = MockPaymentContractClient::new(&h.env, &h.pay_id)
+        .create_payment(&1u64, &PaymentStatus::Released);
+
+    let result = h.coord.try_allocate_units(
+        &1u64,
+        &vec![&h.env, first, second],
+        &payment_id,
+        &h.admin,
+        &BloodType::ONegative,
+    );
+    assert_eq!(result, Err(Ok(CoordinatorError::InvalidPaymentState)));
+
+    let inv = MockInventoryContractClient::new(&h.env, &h.inv_id);
+    assert_eq!(inv.get_blood_unit(&first).status, BloodStatus::Available);
+    assert_eq!(inv.get_blood_unit(&second).status, BloodStatus::Available);
+    assert!(h.coord.try_get_workflow(&1u64).is_err());
+}
+
+// ── Escalation tests ──────────────────────────────────────────────────────────
+
+/// An escalated request is broadcast with a tightened deadline and can still
+/// be allocated once a bank responds.
+#[test]
+fn test_escalate_request_marks_workflow_escalated() {
+    let h = setup();
+    h.env.ledger().with_mut(|l| l.timestamp = 1_000);
+    seed_pending_request(&h, 1);
+
+    assert_eq!(h.coord.escalate_request(&h.admin, &1u64), 3);
+    let wf = h.coord.get_workflow(&1u64);
+    assert_eq!(wf.status, WorkflowStatus::Escalated);
+    assert_eq!(wf.expires_at, 1_000 + 30 * 60);
+
+    let result = h.coord.try_escalate_request(&h.admin, &1u64);
+    assert_eq!(result, Err(Ok(CoordinatorError::WorkflowAlreadyStarted)));
+
+    let unit_id = register_unit(&h);
+    let payment_id = create_locked_payment(&h, 1);
+    h.coord.allocate_units(&1u64, &vec![&h.env, unit_id], &payment_id, &h.admin, &BloodType::ONegative);
+    assert_eq!(h.coord.get_workflow(&1u64).status, WorkflowStatus::Allocated);
+}
+
+// ── Order placement tests ─────────────────────────────────────────────────────
+
+/// Registers two banks, stocks only the second with `stock` O-negative units,
+/// and prices O-negative at 100 per unit. Returns the stocked bank.
+fn setup_order_stock(h: &Harness, stock: u32) -> Address {
+    let (empty_bank, bank) = (Address::generate(&h.env), Address::generate(&h.env));
+    MockRequestContractClient::new(&h.env, &h.req_id)
+        .seed_blood_banks(&vec![&h.env, empty_bank, bank.clone()]);
+    MockInventoryContractClient::new(&h.env, &h.inv_id).set_stock(&bank, &BloodType::ONegative, &stock);
+    h.coord.set_payment_token(&h.admin, &Address::generate(&h.env));
+    h.coord.set_unit_price(&h.admin, &BloodType::ONegative, &100);
+    bank
+}
+
+/// Seed one custody handoff of `status` to the order's hospital per unit of
+/// leg `leg_index` and return their IDs.
+fn custody_events(h: &Harness, order_id: u64, leg_index: u32, status: CustodyStatus) -> Vec<String> {
+    let order = h.coord.get_order(&order_id);
+    let leg = order.legs.get(leg_index).unwrap();
+    custody_events_from(h, order_id, leg_index, status, &leg.bank, order.blood_type)
+}
+
+/// As `custody_events`, with the units registered to `bank` as `blood_type`.
+fn custody_events_from(
+    h: &Harness,
+    order_id: u64,
+    leg_index: u32,
+    status: CustodyStatus,
+    bank: &Address,
+    blood_type: BloodType,
+) -> Vec<String> {
+    let order = h.coord.get_order(&order_id);
+    let leg = order.legs.get(leg_index).unwrap();
+    let custody = MockCustodyContractClient::new(&h.env, &h.cus_id);
+    let mut ids = Vec::new(&h.env);
+    for unit in 0..leg.quantity {
+        let unit_id = leg.id * 100 + u64::from(unit);
+        custody.seed_unit(&CustodyUnit {
+            id: unit_id,
+            blood_type,
+            component: BloodComponent::WholeBlood,
+            quantity: 450,
+            expiration_date: h.env.ledger().timestamp() + 86_400 * 30,
+            donor_id: symbol_short!("donor"),
+            location: symbol_short!("bank"),
+            bank_id: bank.clone(),
+            registration_timestamp: 0,
+            status: CustodyUnitStatus::Delivered,
+            recipient_hospital: Some(order.hospital.clone()),
+            allocation_timestamp: None,
+            transfer_timestamp: None,
+            delivery_timestamp: None,
+            lot_id: None,
+        });
+        let name = [b'e', b'0' + (leg.id / 10 % 10) as u8, b'0' + (leg.id % 10) as u8, b'a' + unit as u8];
+        let event_id = String::from_bytes(&h.env, &name);
+        custody.seed_event(&CustodyEvent {
+            event_id: event_id.clone(),
+            unit_id,
+            from_custodian: Address::generate(&h.env),
+            to_custodian: order.hospital.clone(),
+            from_kind: CustodianKind::Rider,
+            to_kind: CustodianKind::Hospital,
+            initiated_at: 0,
+            transfer_deadline: 0,
+            ledger_sequence: 0,
+            status,
+            doc_hash: None,
+            note: None,
+            location_hash: None,
+            closing_location_hash: None,
+        });
+        ids.push_back(event_id);
+    }
+    ids
+}
+
+/// Confirm leg `leg_index` with a confirmed custody handoff per unit.
+fn deliver_leg(h: &Harness, order_id: u64, leg_index: u32) {
+    let events = custody_events(h, order_id, leg_index, CustodyStatus::Confirmed);
+    h.coord.confirm_leg_delivery(&h.del_id, &order_id, &leg_index, &events);
+}
+
+#[test]
+fn test_place_order_reserves_escrows_and_dispatches() {
+    let h = setup();
+    let bank = setup_order_stock(&h, 5);
+    let hospital = Address::generate(&h.env);
+
+    let order_id = h.coord.place_order(&hospital, &BloodType::ONegative, &3, &300, &Urgency::Routine);
+
+    let order = h.coord.get_order(&order_id);
+    assert_eq!(order.legs.len(), 1);
+    let leg = order.legs.get(0).unwrap();
+    assert_eq!(leg.bank, bank);
+    assert_eq!((order.quantity, order.price, leg.delivery_id), (3, 300, 1));
+    assert_eq!(order.status, OrderStatus::Dispatched);
+    let inv = MockInventoryContractClient::new(&h.env, &h.inv_id);
+    assert_eq!(inv.get_available_stock(&bank, &BloodType::ONegative), 2);
+    let payment = MockPaymentContractClient::new(&h.env, &h.pay_id).get_payment(&leg.payment_id);
+    assert_eq!(payment.status, PaymentStatus::Locked);
+
+    let result = h.coord.try_place_order(&hospital, &BloodType::ONegative, &3, &300, &Urgency::Routine);
+    assert_eq!(result, Err(Ok(CoordinatorError::InsufficientStock)));
+    let result = h.coord.try_place_order(&hospital, &BloodType::ONegative, &2, &199, &Urgency::Routine);
+    assert_eq!(result, Err(Ok(CoordinatorError::PriceAboveMax)));
+    let result = h.coord.try_place_order(&hospital, &BloodType::APositive, &1, &500, &Urgency::Routine);
+    assert_eq!(result, Err(Ok(CoordinatorError::PricingNotConfigured)));
+}
+
+/// A failing delivery job undoes the reservation and escrow.
+#[test]
+fn test_place_order_compensates_when_delivery_job_fails() {
+    let h = setup();
+    let bank = setup_order_stock(&h, 2);
+    MockDeliveryContractClient::new(&h.env, &h.del_id).set_failing(&true);
+
+    let result = h.coord.try_place_order(
+        &Address::generate(&h.env),
+        &BloodType::ONegative,
+        &2,
+        &1_000,
+        &Urgency::Urgent,
+    );
+    assert_eq!(result, Err(Ok(CoordinatorError::DeliveryJobFailed)));
+    let inv = MockInventoryContractClient::new(&h.env, &h.inv_id);
+    assert_eq!(inv.get_available_stock(&bank, &BloodType::ONegative), 2);
+    assert_eq!(h.coord.try_get_order(&1), Err(Ok(CoordinatorError::OrderNotFound)));
+}
+
+/// Delivery, receipt and cancellation follow the order lifecycle, and each
+/// party may only make its own transitions.
+#[test]
+fn test_order_status_transitions_are_validated() {
+    let h = setup();
+    let bank = setup_order_stock(&h, 10);
+    let hospital = Address::generate(&h.env);
+    let first = h.coord.place_order(&hospital, &BloodType::ONegative, &2, &1_000, &Urgency::Routine);
+    let second = h.coord.place_order(&hospital, &BloodType::ONegative, &3, &1_000, &Urgency::Routine);
+
+    let result = h.coord.try_update_order_status(&hospital, &first, &OrderStatus::InTransit);
+    assert_eq!(result, Err(Ok(CoordinatorError::Unauthorized)));
+    let result = h.coord.try_update_order_status(&hospital, &first, &OrderStatus::Completed);
+    assert_eq!(result, Err(Ok(CoordinatorError::InvalidOrderTransition)));
+    let result = h.coord.try_update_order_status(&h.del_id, &first, &OrderStatus::Delivered);
+    assert_eq!(result, Err(Ok(CoordinatorError::Unauthorized)));
+    h.coord.update_order_status(&h.del_id, &first, &OrderStatus::InTransit);
+    deliver_leg(&h, first, 0);
+    assert_eq!(h.coord.get_order(&first).status, OrderStatus::Delivered);
+    h.coord.update_order_status(&hospital, &first, &OrderStatus::Completed);
+    let result = h.coord.try_update_order_status(&hospital, &first, &OrderStatus::Cancelled);
+    assert_eq!(result, Err(Ok(CoordinatorError::InvalidOrderTransition)));
+
+    // Cancelling gives back the reserved stock and refunds the escrow
+    h.coord.update_order_status(&hospital, &second, &OrderStatus::Cancelled);
+    let order = h.coord.get_order(&second);
+    assert_eq!(order.status, OrderStatus::Cancelled);
+    let inv = MockInventoryContractClient::new(&h.env, &h.inv_id);
+    assert_eq!(inv.get_available_stock(&bank, &BloodType::ONegative), 8);
+    let payment_id = order.legs.get(0).unwrap().payment_id;
+    let payment = MockPaymentContractClient::new(&h.env, &h.pay_id).get_payment(&payment_id);
+    assert_eq!(payment.status, PaymentStatus::Refunded);
+
+    let orders = h.coord.get_orders_by_hospital(&hospital, &0);
+    assert_eq!(orders.len(), 2);
+    assert_eq!(orders.get(0).unwrap().status, OrderStatus::Completed);
+    assert_eq!(h.coord.get_orders_by_hospital(&hospital, &1).len(), 0);
+}
+
+/// The timeline records every status an order entered and who moved it.
+#[test]
+fn test_order_timeline_records_each_milestone() {
+    let h = setup();
+    h.env.ledger().with_mut(|l| l.timestamp = 1_000);
+    setup_order_stock(&h, 5);
+    let hospital = Address::generate(&h.env);
+    let order_id = h.coord.place_order(&hospital, &BloodType::ONegative, &2, &1_000, &Urgency::Routine);
+    h.env.ledger().with_mut(|l| l.timestamp = 2_000);
+    deliver_leg(&h, order_id, 0);
+    h.coord.update_order_status(&hospital, &order_id, &OrderStatus::Completed);
+
+    let timeline = h.coord.get_order_timeline(&order_id);
+    let milestones: Vec<OrderStatus> = timeline.iter().fold(Vec::new(&h.env), |mut v, m| {
+        v.push_back(m.milestone);
+        v
+    });
+    assert_eq!(
+        milestones,
+        vec![
+            &h.env,
+            OrderStatus::Created,
+            OrderStatus::Matched,
+            OrderStatus::Reserved,
+            OrderStatus::Paid,
+            OrderStatus::Dispatched,
+            OrderStatus::InTransit,
+            OrderStatus::Delivered,
+            OrderStatus::Completed,
+        ]
+    );
+    let dispatched = timeline.get(4).unwrap();
+    assert_eq!((dispatched.actor, dispatched.timestamp), (hospital.clone(), 1_000));
+    let in_transit = timeline.get(5).unwrap();
+    assert_eq!((in_transit.actor, in_transit.timestamp), (h.del_id.clone(), 2_000));
+    assert_eq!(timeline.get(7).unwrap().actor, hospital);
+
+    let result = h.coord.try_get_order_timeline(&99);
+    assert_eq!(result, Err(Ok(CoordinatorError::OrderNotFound)));
+}
+
+/// An order no single bank can cover is split into legs; it is delivered
+/// once every leg is.
+#[test]
+fn test_place_order_splits_across_banks() {
+    let h = setup();
+    let second_bank = setup_order_stock(&h, 5);
+    let first_bank = Address::generate(&h.env);
+    MockRequestContractClient::new(&h.env, &h.req_id)
+        .seed_blood_banks(&vec![&h.env, first_bank.clone(), second_bank.clone()]);
+    let inv = MockInventoryContractClient::new(&h.env, &h.inv_id);
+    inv.set_stock(&first_bank, &BloodType::ONegative, &3);
+    let hospital = Address::generate(&h.env);
+
+    let result = h.coord.try_place_order(&hospital, &BloodType::ONegative, &9, &10_000, &Urgency::Routine);
+    assert_eq!(result, Err(Ok(CoordinatorError::InsufficientStock)));
+    let order_id = h.coord.place_order(&hospital, &BloodType::ONegative, &8, &10_000, &Urgency::Routine);
+
+    let order = h.coord.get_order(&order_id);
+    assert_eq!(order.legs.len(), 2);
+    let (first, second) = (order.legs.get(0).unwrap(), order.legs.get(1).unwrap());
+    assert_eq!((first.bank.clone(), first.quantity, first.price), (first_bank.clone(), 3, 300));
+    assert_eq!((second.bank.clone(), second.quantity, second.price), (second_bank.clone(), 5, 500));
+    assert_ne!(first.payment_id, second.payment_id);
+    let pay = MockPaymentContractClient::new(&h.env, &h.pay_id);
+    assert_eq!(pay.get_payment(&first.payment_id).status, PaymentStatus::Locked);
+    assert_eq!(inv.get_available_stock(&first_bank, &BloodType::ONegative), 0);
+    assert_eq!(inv.get_available_stock(&second_bank, &BloodType::ONegative), 0);
+
+    deliver_leg(&h, order_id, 1);
+    let order = h.coord.get_order(&order_id);
+    assert_eq!((order.status, order.fulfilled_quantity), (OrderStatus::InTransit, 5));
+    let no_events = Vec::new(&h.env);
+    let result = h.coord.try_confirm_leg_delivery(&h.del_id, &order_id, &1, &no_events);
+    assert_eq!(result, Err(Ok(CoordinatorError::LegAlreadyDelivered)));
+    let result = h.coord.try_confirm_leg_delivery(&h.del_id, &order_id, &2, &no_events);
+    assert_eq!(result, Err(Ok(CoordinatorError::LegNotFound)));
+    deliver_leg(&h, order_id, 0);
+    let order = h.coord.get_order(&order_id);
+    assert_eq!((order.status, order.fulfilled_quantity), (OrderStatus::Delivered, 8));
+
+    // Both banks are paid only once the whole order is delivered
+    for leg in order.legs.iter() {
+        assert_eq!(pay.get_payment(&leg.payment_id).status, PaymentStatus::Released);
+    }
+}
+
+/// A leg is only delivered, and its escrow only released, against one
+/// confirmed custody handoff to the hospital per unit, each used once.
+#[test]
+fn test_confirm_leg_delivery_requires_confirmed_custody() {
+    let h = setup();
+    setup_order_stock(&h, 10);
+    let hospital = Address::generate(&h.env);
+    let order_id = h.coord.place_order(&hospital, &BloodType::ONegative, &2, &1_000, &Urgency::Routine);
+    let other = h.coord.place_order(&hospital, &BloodType::ONegative, &2, &1_000, &Urgency::Routine);
+
+    let pending = custody_events(&h, order_id, 0, CustodyStatus::Pending);
+    let result = h.coord.try_confirm_leg_delivery(&h.del_id, &order_id, &0, &pending);
+    assert_eq!(result, Err(Ok(CoordinatorError::CustodyNotConfirmed)));
+
+    let mut confirmed = custody_events(&h, order_id, 0, CustodyStatus::Confirmed);
+    let first = confirmed.get(0).unwrap();
+    let result = h.coord.try_confirm_leg_delivery(&h.del_id, &order_id, &0, &vec![&h.env, first.clone()]);
+    assert_eq!(result, Err(Ok(CoordinatorError::CustodyNotConfirmed)));
+    let result = h.coord.try_confirm_leg_delivery(
+        &h.del_id,
+        &order_id,
+        &0,
+        &vec![&h.env, first.clone(), first.clone()],
+    );
+    assert_eq!(result, Err(Ok(CoordinatorError::CustodyNotConfirmed)));
+
+    // A handoff to another hospital proves nothing for this order
+    let stranger = Address::generate(&h.env);
+    let mut misdirected = MockCustodyContractClient::new(&h.env, &h.cus_id).get_custody_event(&first);
+    misdirected.to_custodian = stranger;
+    MockCustodyContractClient::new(&h.env, &h.cus_id).seed_event(&misdirected);
+    let result = h.coord.try_confirm_leg_delivery(&h.del_id, &order_id, &0, &confirmed);
+    assert_eq!(result, Err(Ok(CoordinatorError::CustodyNotConfirmed)));
+    assert_eq!(h.coord.get_order(&order_id).status, OrderStatus::Dispatched);
+    let payment_id = h.coord.get_order(&order_id).legs.get(0).unwrap().payment_id;
+    let pay = MockPaymentContractClient::new(&h.env, &h.pay_id);
+    assert_eq!(pay.get_payment(&payment_id).status, PaymentStatus::Locked);
+
+    confirmed = custody_events(&h, order_id, 0, CustodyStatus::Confirmed);
+    h.coord.confirm_leg_delivery(&h.del_id, &order_id, &0, &confirmed);
+    assert_eq!(h.coord.get_order(&order_id).status, OrderStatus::Delivered);
+    assert_eq!(pay.get_payment(&payment_id).status, PaymentStatus::Released);
+
+    // The same handoffs cannot prove a second order's delivery
+    let result = h.coord.try_confirm_leg_delivery(&h.del_id, &other, &0, &confirmed);
+    assert_eq!(result, Err(Ok(CoordinatorError::CustodyNotConfirmed)));
+}
+
+/// Handoffs only count for units of the leg's bank and the order's blood type.
+#[test]
+fn test_confirm_leg_delivery_rejects_units_from_another_bank() {
+    let h = setup();
+    setup_order_stock(&h, 10);
+    let hospital = Address::generate(&h.env);
+    let order_id = h.coord.place_order(&hospital, &BloodType::ONegative, &2, &1_000, &Urgency::Routine);
+    let bank = h.coord.get_order(&order_id).legs.get(0).unwrap().bank;
+    let stranger = Address::generate(&h.env);
+    let custody = MockCustodyContractClient::new(&h.env, &h.cus_id);
+
+    let foreign = custody_events_from(&h, order_id, 0, CustodyStatus::Confirmed, &stranger, BloodType::ONegative);
+    let result = h.coord.try_confirm_leg_delivery(&h.del_id, &order_id, &0, &foreign);
+    assert_eq!(result, Err(Ok(CoordinatorError::CustodyNotConfirmed)));
+
+    // A direct handoff must come from the leg's bank itself
+    let mut direct = custody_events(&h, order_id, 0, CustodyStatus::Confirmed);
+    let mut event = custody.get_custody_event(&direct.get(0).unwrap());
+    event.from_custodian = stranger.clone();
+    event.from_kind = CustodianKind::Bank;
+    custody.seed_event(&event);
+    let result = h.coord.try_confirm_leg_delivery(&h.del_id, &order_id, &0, &direct);
+    assert_eq!(result, Err(Ok(CoordinatorError::CustodyNotConfirmed)));
+
+    let mismatched = custody_events_from(&h, order_id, 0, CustodyStatus::Confirmed, &bank, BloodType::APositive);
+    let result = h.coord.try_confirm_leg_delivery(&h.del_id, &order_id, &0, &mismatched);
+    assert_eq!(result, Err(Ok(CoordinatorError::CustodyNotConfirmed)));
+    assert_eq!(h.coord.get_order(&order_id).status, OrderStatus::Dispatched);
+
+    direct = custody_events(&h, order_id, 0, CustodyStatus::Confirmed);
+    event = custody.get_custody_event(&direct.get(0).unwrap());
+    event.from_custodian = bank;
+    event.from_kind = CustodianKind::Bank;
+    custody.seed_event(&event);
+    h.coord.confirm_leg_delivery(&h.del_id, &order_id, &0, &direct);
+    assert_eq!(h.coord.get_order(&order_id).status, OrderStatus::Delivered);
+}
+
+/// Urgency sets the price premium, SLA deadline and matching radius.
+#[test]
+fn test_urgency_changes_price_deadline_and_radius() {
+    let h = setup();
+    h.env.ledger().with_mut(|l| l.timestamp = 1_000);
+    let bank = setup_order_stock(&h, 10);
+    let hospital = Address::generate(&h.env);
+    h.coord.set_bank_distance(&h.admin, &hospital, &bank, &50);
+
+    let result = h.coord.try_place_order(&hospital, &BloodType::ONegative, &2, &1_000, &Urgency::Routine);
+    assert_eq!(result, Err(Ok(CoordinatorError::InsufficientStock)));
+    let urgent = h.coord.place_order(&hospital, &BloodType::ONegative, &2, &1_000, &Urgency::Urgent);
+    let order = h.coord.get_order(&urgent);
+    assert_eq!(order.price, 240);
+    let profile = h.coord.get_urgency_profile(&Urgency::Urgent);
+    assert_eq!(order.sla_deadline, 1_000 + profile.sla_secs);
+
+    let emergency = h.coord.place_order(&hospital, &BloodType::ONegative, &2, &1_000, &Urgency::Emergency);
+    assert_eq!(h.coord.get_order(&emergency).price, 300);
+}
+
+/// An emergency order the banks cannot cover cancels the newest routine
+/// orders; urgent orders cannot preempt.
+#[test]
+fn test_emergency_order_preempts_routine_orders() {
+    let h = setup();
+    let bank = setup_order_stock(&h, 5);
+    let clinic = Address::generate(&h.env);
+    let older = h.coord.place_order(&clinic, &BloodType::ONegative, &2, &1_000, &Urgency::Routine);
+    let newer = h.coord.place_order(&clinic, &BloodType::ONegative, &2, &1_000, &Urgency::Routine);
+    let hospital = Address::generate(&h.env);
+
+    let result = h.coord.try_place_order(&hospital, &BloodType::ONegative, &3, &1_000, &Urgency::Urgent);
+    assert_eq!(result, Err(Ok(CoordinatorError::InsufficientStock)));
+    let result = h.coord.try_place_order(&hospital, &BloodType::ONegative, &6, &1_000, &Urgency::Emergency);
+    assert_eq!(result, Err(Ok(CoordinatorError::InsufficientStock)));
+    assert_eq!(h.coord.get_order(&newer).status, OrderStatus::Dispatched);
+
+    let emergency = h.coord.place_order(&hospital, &BloodType::ONegative, &3, &1_000, &Urgency::Emergency);
+    assert_eq!(h.coord.get_order(&emergency).status, OrderStatus::Dispatched);
+    assert_eq!(h.coord.get_order(&newer).status, OrderStatus::Cancelled);
+    assert_eq!(h.coord.get_order(&older).status, OrderStatus::Dispatched);
+    let payment_id = h.coord.get_order(&newer).legs.get(0).unwrap().payment_id;
+    let payment = MockPaymentContractClient::new(&h.env, &h.pay_id).get_payment(&payment_id);
+    assert_eq!(payment.status, PaymentStatus::Refunded);
+    let inv = MockInventoryContractClient::new(&h.env, &h.inv_id);
+    assert_eq!(inv.get_available_stock(&bank, &BloodType::ONegative), 0);
+}
+
+/// A late order is cancelled by anyone, its stock and escrow are returned,
+/// and its bank's reputation records the miss.
+#[test]
+fn test_enforce_sla_cancels_late_orders_and_reports_banks() {
+    let h = setup();
+    h.env.ledger().with_mut(|l| l.timestamp = 1_000);
+    let bank = setup_order_stock(&h, 5);
+    let rep_id = h.env.register(MockReputationContract, ());
+    h.coord.set_contract_address(&h.admin, &ContractKind::Reputation, &rep_id);
+    h.coord.set_reputation_entity(&h.admin, &bank, &7);
+    let hospital = Address::generate(&h.env);
+    let late = h.coord.place_order(&hospital, &BloodType::ONegative, &2, &1_000, &Urgency::Routine);
+    let delivered = h.coord.place_order(&hospital, &BloodType::ONegative, &1, &1_000, &Urgency::Routine);
+    h.coord.update_order_status(&h.del_id, &late, &OrderStatus::InTransit);
+    deliver_leg(&h, delivered, 0);
+
+    let result = h.coord.try_update_order_status(&hospital, &late, &OrderStatus::Cancelled);
+    assert_eq!(result, Err(Ok(CoordinatorError::Unauthorized)));
+    let result = h.coord.try_enforce_sla(&late);
+    assert_eq!(result, Err(Ok(CoordinatorError::SlaNotExpired)));
+
+    let order = h.coord.get_order(&late);
+    h.env.ledger().with_mut(|l| l.timestamp = order.sla_deadline);
+    h.coord.enforce_sla(&late);
+    let order = h.coord.get_order(&late);
+    assert_eq!(order.status, OrderStatus::Cancelled);
+    let inv = MockInventoryContractClient::new(&h.env, &h.inv_id);
+    assert_eq!(inv.get_available_stock(&bank, &BloodType::ONegative), 4);
+    let payment_id = order.legs.get(0).unwrap().payment_id;
+    let payment = MockPaymentContractClient::new(&h.env, &h.pay_id).get_payment(&payment_id);
+    assert_eq!(payment.status, PaymentStatus::Refunded);
+    assert_eq!(MockReputationContractClient::new(&h.env, &rep_id).failed(&7), 1);
+
+    let result = h.coord.try_enforce_sla(&late);
+    assert_eq!(result, Err(Ok(CoordinatorError::InvalidOrderTransition)));
+    let result = h.coord.try_enforce_sla(&delivered);
+    assert_eq!(result, Err(Ok(CoordinatorError::InvalidOrderTransition)));
+}
+
+/// When a partially delivered order misses its SLA, the delivered leg's bank
+/// is paid and only the undelivered leg is refunded and reported.
+#[test]
+fn test_enforce_sla_pays_delivered_legs_of_partial_order() {
+    let h = setup();
+    h.env.ledger().with_mut(|l| l.timestamp = 1_000);
+    let second_bank = setup_order_stock(&h, 5);
+    let first_bank = Address::generate(&h.env);
+    MockRequestContractClient::new(&h.env, &h.req_id)
+        .seed_blood_banks(&vec![&h.env, first_bank.clone(), second_bank.clone()]);
+    MockInventoryContractClient::new(&h.env, &h.inv_id).set_stock(&first_bank, &BloodType::ONegative, &3);
+    let rep_id = h.env.register(MockReputationContract, ());
+    h.coord.set_contract_address(&h.admin, &ContractKind::Reputation, &rep_id);
+    h.coord.set_reputation_entity(&h.admin, &first_bank, &1);
+    h.coord.set_reputation_entity(&h.admin, &second_bank, &2);
+    let hospital = Address::generate(&h.env);
+    let order_id = h.coord.place_order(&hospital, &BloodType::ONegative, &8, &10_000, &Urgency::Routine);
+    deliver_leg(&h, order_id, 1);
+
+    let order = h.coord.get_order(&order_id);
+    assert_eq!(order.status, OrderStatus::InTransit);
+    h.env.ledger().with_mut(|l| l.timestamp = order.sla_deadline);
+    h.coord.enforce_sla(&order_id);
+
+    let order = h.coord.get_order(&order_id);
+    assert_eq!(order.status, OrderStatus::Cancelled);
+    let pay = MockPaymentContractClient::new(&h.env, &h.pay_id);
+    let (undelivered, delivered) = (order.legs.get(0).unwrap(), order.legs.get(1).unwrap());
+    assert!(delivered.delivered);
+    assert_eq!(pay.get_payment(&undelivered.payment_id).status, PaymentStatus::Refunded);
+    assert_eq!(pay.get_payment(&delivered.payment_id).status, PaymentStatus::Released);
+    let rep = MockReputationContractClient::new(&h.env, &rep_id);
+    assert_eq!((rep.failed(&1), rep.failed(&2)), (1, 0));
+}
+
+// ── Contract address registry ─────────────────────────────────────────────────
+
+/// Replacing a registered contract waits out the timelock; the new address is
+/// then used for downstream calls.
+#[test]
+fn test_contract_address_change_waits_for_timelock() {
+    let h = setup();
+    h.env.ledger().with_mut(|l| l.timestamp = 1_000);
+    let new_pay_id = h.env.register(MockPaymentContract, ());
+
+    let result = h.coord.try_set_contract_address(&h.admin, &ContractKind::Payments, &new_pay_id);
+    assert_eq!(result, Err(Ok(CoordinatorError::ContractChangeNotProposed)));
+
+    let effective_at = h.coord.propose_contract_address(&h.admin, &ContractKind::Payments, &new_pay_id);
+    assert_eq!(effective_at, 1_000 + 48 * 60 * 60);
+    let result = h.coord.try_set_contract_address(&h.admin, &ContractKind::Payments, &new_pay_id);
+    assert_eq!(result, Err(Ok(CoordinatorError::ContractChangeTimelocked)));
+    let other = Address::generate(&h.env);
+    h.env.ledger().with_mut(|l| l.timestamp = effective_at);
+    let result = h.coord.try_set_contract_address(&h.admin, &ContractKind::Payments, &other);
+    assert_eq!(result, Err(Ok(CoordinatorError::ContractChangeNotProposed)));
+
+    h.coord.set_contract_address(&h.admin, &ContractKind::Payments, &new_pay_id);
+    assert_eq!(h.coord.get_contract_address(&ContractKind::Payments), Some(new_pay_id.clone()));
+    assert_eq!(h.coord.get_pending_contract_address(&ContractKind::Payments), None);
+
+    // Escrow now goes through the replacement payments contract
+    seed_pending_request(&h, 1);
+    let unit_id = register_unit(&h);
+    let old_payment = create_locked_payment(&h, 1);
+    let result = h.coord.try_allocate_units(&1, &vec![&h.env, unit_id], &old_payment, &h.admin, &BloodType::ONegative);
+    assert_eq!(result, Err(Ok(CoordinatorError::PaymentNotFound)));
+}
+
+/// Once registered, only the temperature contract may flag breaches.
+#[test]
+fn test_flag_temperature_breach_requires_registered_temperature_contract() {
+    let h = setup();
+    let temperature = Address::generate(&h.env);
+    h.coord.set_contract_address(&h.admin, &ContractKind::Temperature, &temperature);
+    let payment_id = create_locked_payment(&h, 1);
+
+    let result = h.coord.try_flag_temperature_breach(
+        &Address::generate(&h.env),
+        &payment_id,
+        &make_excursion(1),
+    );
+    assert_eq!(result, Err(Ok(CoordinatorError::Unauthorized)));
+    h.coord.flag_temperature_breach(&temperature, &payment_id, &make_excursion(1));
+}
+
+// ── Circuit breaker ───────────────────────────────────────────────────────────
+
+fn allocate_one(h: &Harness, request_id: u64) -> Result<(), CoordinatorError> {
+    seed_pending_request(h, request_id);
+    let unit_id = register_unit(h);
+    let payment_id = create_locked_payment(h, request_id);
+    match h.coord.try_allocate_units(
+        &request_id,
+        &vec![&h.env, unit_id],
+        &payment_id,
+        &h.admin,
+        &BloodType::ONegative,
+    ) {
+        Ok(_) => Ok(()),
+        Err(Ok(error)) => Err(error),
+        Err(Err(_)) => panic!("allocate_units aborted"),
+    }
+}
+
+/// The temperature check refuses compromised units; when the temperature
+/// contract keeps failing, allocation carries on and its breaker opens.
+#[test]
+fn test_failing_temperature_contract_opens_breaker_without_blocking() {
+    let h = setup();
+    let temp_id = h.env.register(MockTemperatureContract, ());
+    h.coord.set_contract_address(&h.admin, &ContractKind::Temperature, &temp_id);
+    let temp = MockTemperatureContractClient::new(&h.env, &temp_id);
+    temp.set_compromised(&1);
+    assert_eq!(allocate_one(&h, 1), Err(CoordinatorError::UnitCompromised));
+
+    temp.set_failing(&true);
+    for request_id in 2..=4 {
+        assert_eq!(allocate_one(&h, request_id), Ok(()));
+    }
+    let health = h.coord.get_contract_health(&ContractKind::Temperature);
+    assert!(health.open);
+    assert_eq!(health.consecutive_failures, 3);
+
+    // Skipped while open, so a recovered contract is not consulted
+    temp.set_failing(&false);
+    temp.set_compromised(&5);
+    assert_eq!(allocate_one(&h, 5), Ok(()));
+    assert!(h.coord.get_contract_health(&ContractKind::Temperature).open);
+
+    h.coord.reset_breaker(&h.admin, &ContractKind::Temperature);
+    let health = h.coord.get_contract_health(&ContractKind::Temperature);
+    assert!(!health.open);
+    assert_eq!(health.consecutive_failures, 0);
+    temp.set_compromised(&6);
+    assert_eq!(allocate_one(&h, 6), Err(CoordinatorError::UnitCompromised));
+}
+
+/// An open breaker on a required contract fails calls fast.
+#[test]
+fn test_open_breaker_blocks_required_contract() {
+    let h = setup();
+    setup_order_stock(&h, 5);
+    let hospital = Address::generate(&h.env);
+    h.env.as_contract(&h.coord.address, || {
+        h.env.storage().instance().set(
+            &super::DataKey::Health(ContractKind::Inventory),
+            &super::ContractHealth {
+                consecutive_failures: 3,
+                open: true,
+                opened_at: 0,
+            },
+        );
+    });
+
+    let result = h.coord.try_place_order(&hospital, &BloodType::ONegative, &1, &1_000, &Urgency::Routine);
+    assert_eq!(result, Err(Ok(CoordinatorError::DownstreamUnavailable)));
+    h.coord.reset_breaker(&h.admin, &ContractKind::Inventory);
+    h.coord.place_order(&hospital, &BloodType::ONegative, &1, &1_000, &Urgency::Routine);
+}
+
+// ── Real payments contract ────────────────────────────────────────────────────
+
+/// Like `setup`, but with the real payments contract, which accepts the
+/// coordinator as its escrow coordinator.
+fn setup_with_payments_contract<'a>() -> Harness<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+
+    let req_id = env.register(MockRequestContract, ());
+    let inv_id = env.register(MockInventoryContract, ());
+    let pay_id = env.register(payment_contract::PaymentContract, ());
+    let del_id = env.register(MockDeliveryContract, ());
+    let cus_id = env.register(MockCustodyContract, ());
+    let coord_id = env.register(CoordinatorContract, ());
+
+    MockInventoryContractClient::new(&env, &inv_id).initialize(&admin);
+    let payments = payment_contract::PaymentContractClient::new(&env, &pay_id);
+    payments.initialize(&admin, &None);
+    payments.set_coordinator(&admin, &coord_id);
+
+    let coord = CoordinatorContractClient::new(&env, &coord_id);
+    coord.initialize(&admin, &req_id, &inv_id, &pay_id);
+    coord.set_contract_address(&admin, &ContractKind::Delivery, &del_id);
+    coord.set_contract_address(&admin, &ContractKind::Custody, &cus_id);
+
+    Harness { env, admin, coord, req_id, inv_id, pay_id, del_id, cus_id }
+}
+
+/// Escrow compensation moves real funds: a cancelled order's escrow goes
+/// back to the hospital through the payments contract's `refund_on_cancel`.
+#[test]
+fn test_escrow_compensation_refunds_through_payments_contract() {
+    let h = setup_with_payments_contract();
+    setup_order_stock(&h, 5);
+    let hospital = Address::generate(&h.env);
+    let token_id = h.env.register_stellar_asset_contract_v2(h.admin.clone()).address();
+    soroban_sdk::token::StellarAssetClient::new(&h.env, &token_id).mint(&hospital, &1_000);
+    h.coord.set_payment_token(&h.admin, &token_id);
+    let token = soroban_sdk::token::Client::new(&h.env, &token_id);
+    let payments = payment_contract::PaymentContractClient::new(&h.env, &h.pay_id);
+
+    let order_id = h.coord.place_order(&hospital, &BloodType::ONegative, &3, &300, &Urgency::Routine);
+    let payment_id = h.coord.get_order(&order_id).legs.get(0).unwrap().payment_id;
+    assert_eq!(token.balance(&hospital), 700);
+    assert_eq!(token.balance(&h.pay_id), 300);
+    assert_eq!(payments.get_payment(&payment_id).status, payment_contract::PaymentStatus::Locked);
+
+    h.coord.update_order_status(&hospital, &order_id, &OrderStatus::Cancelled);
+    assert_eq!(token.balance(&hospital), 1_000);
+    assert_eq!(token.balance(&h.pay_id), 0);
+    assert_eq!(payments.get_payment(&payment_id).status, payment_contract::PaymentStatus::Refunded);
+}
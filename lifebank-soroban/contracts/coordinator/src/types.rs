@@ -1,4 +1,5 @@
-use soroban_sdk::{contracttype, String, Vec};
+use crate::BloodType;
+use soroban_sdk::{contracttype, Address, String, Vec};
 
 /// Canonical workflow states — shared identifier across all contracts.
 #[contracttype]
@@ -6,6 +7,9 @@ use soroban_sdk::{contracttype, String, Vec};
 pub enum WorkflowStatus {
     /// Initial state before allocate_units is called.
     Pending,
+    /// No bank could cover the request; it was broadcast to every bank and
+    /// awaits allocation before a tightened deadline.
+    Escalated,
     /// Units reserved, request approved.
     Allocated,
     /// All units delivered to hospital.
@@ -47,20 +51,174 @@ pub struct ExcursionSummary {
     pub detected_at: u64,
 }
 
+/// Priority of a coordinator order.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Urgency {
+    Routine,
+    Urgent,
+    Emergency,
+}
+
+/// Routing rules an order's urgency selects; see `routing`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UrgencyProfile {
+    /// Furthest a bank may be from the hospital, where a distance is recorded.
+    pub radius_km: u32,
+    /// How long reserved stock is held for delivery.
+    pub hold_secs: u64,
+    /// Premium added to the unit price, in basis points.
+    pub premium_bps: u32,
+    /// Time from placement by which the order must be delivered.
+    pub sla_secs: u64,
+}
+
+/// Lifecycle state of an order; see `lifecycle` for the allowed transitions.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OrderStatus {
+    Created,
+    /// A bank able to cover the order was found.
+    Matched,
+    /// Stock is reserved at the bank.
+    Reserved,
+    /// The price is escrowed.
+    Paid,
+    /// A delivery job is open.
+    Dispatched,
+    InTransit,
+    Delivered,
+    /// Receipt confirmed; terminal.
+    Completed,
+    /// Terminal.
+    Cancelled,
+    Disputed,
+}
+
+/// The part of an order filled by one bank.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FulfillmentLeg {
+    /// Unique across all orders.
+    pub id: u64,
+    pub bank: Address,
+    pub quantity: u32,
+    /// This leg's share of the order price, escrowed to `bank`.
+    pub price: i128,
+    /// Stock reservation ID in the inventory contract.
+    pub reservation_id: u64,
+    /// Escrow payment ID in the payments contract.
+    pub payment_id: u64,
+    /// Job ID in the delivery contract.
+    pub delivery_id: u64,
+    pub delivered: bool,
+}
+
+/// An order placed by a hospital through `place_order`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Order {
+    pub id: u64,
+    pub hospital: Address,
+    pub blood_type: BloodType,
+    pub quantity: u32,
+    /// Total escrowed: the blood type's unit price, plus the urgency
+    /// premium, times `quantity`.
+    pub price: i128,
+    pub urgency: Urgency,
+    /// One leg per bank the order is split across.
+    pub legs: Vec<FulfillmentLeg>,
+    /// Units delivered so far, summed over delivered legs.
+    pub fulfilled_quantity: u32,
+    pub status: OrderStatus,
+    pub created_at: u64,
+    pub updated_at: u64,
+    /// Delivery deadline from the urgency's SLA.
+    pub sla_deadline: u64,
+}
+
+/// One entry of an order's timeline: a status the order entered.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrderMilestone {
+    pub milestone: OrderStatus,
+    /// Who moved the order there; the coordinator itself for SLA enforcement.
+    pub actor: Address,
+    pub timestamp: u64,
+}
+
+/// Downstream contract the coordinator calls.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ContractKind {
+    Requests,
+    Inventory,
+    Payments,
+    Delivery,
+    Temperature,
+    Reputation,
+    /// Chain-of-custody contract whose confirmed handoffs prove delivery.
+    Custody,
+}
+
+/// Circuit breaker state of a downstream contract; see `breaker`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractHealth {
+    pub consecutive_failures: u32,
+    pub open: bool,
+    /// When the breaker opened; zero while closed.
+    pub opened_at: u64,
+}
+
+/// Address change queued by `propose_contract_address`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingContractAddress {
+    pub address: Address,
+    /// Earliest time `set_contract_address` may apply the change.
+    pub effective_at: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataKey {
     Admin,
     /// Pending admin address set by propose_admin(); cleared on accept_admin().
     PendingAdmin,
-    RequestContract,
-    InventoryContract,
-    PaymentContract,
+    /// Address of a downstream contract.
+    ContractAddr(ContractKind),
+    /// Queued replacement for a downstream contract address.
+    PendingContractAddr(ContractKind),
+    /// Circuit breaker state of a downstream contract.
+    Health(ContractKind),
     Workflow(u64),
     Paused,
     /// Emergency halt flag — set by emergency_halt(); blocks all in-flight
     /// workflow steps until manually cleared by admin.
     EmergencyHalt,
+    /// Token hospitals escrow order payments in.
+    PaymentToken,
+    /// Price of one unit of a blood type, in `PaymentToken` units.
+    UnitPrice(BloodType),
+    Order(u64),
+    /// Milestones an order has passed, oldest first.
+    OrderTimeline(u64),
+    OrderCounter,
+    LegCounter,
+    /// Distance in km from a hospital to a bank, for the matching radius.
+    BankDistance(Address, Address),
+    /// Routine orders for a blood type that emergency orders may preempt.
+    OpenRoutineOrders(BloodType),
+    /// Page of a hospital's order IDs, oldest first.
+    HospitalOrders(Address, u32),
+    /// Number of orders a hospital has placed.
+    HospitalOrderCount(Address),
+    /// Reputation entity ID that SLA violations of a party are recorded under.
+    ReputationEntity(Address),
+    /// Order whose leg delivery a custody event was used to prove.
+    CustodyEventUsed(String),
 }
 
 /// Status applied to all in-flight workflows when emergency_halt() is triggered.
@@ -1,6 +1,7 @@
 #![no_std]
 #![deny(deprecated)]
 
+use lifebank_auth::{require_role, Role};
 use soroban_sdk::{
     contract, contracterror, contractevent, contractimpl, contracttype, Address, Bytes, Env,
 };
@@ -11,6 +12,14 @@ pub struct DeliveryInitialized {
     pub request_contract: Address,
 }
 
+#[contractevent(topics = ["delivery", "job"], data_format = "vec")]
+pub struct DeliveryJobCreated {
+    pub delivery_id: u64,
+    pub order_id: u64,
+    pub pickup: Address,
+    pub dropoff: Address,
+}
+
 #[contractevent(topics = ["comply"], data_format = "vec")]
 pub struct ComplianceAttested {
     pub delivery_id: u64,
@@ -29,6 +38,7 @@ pub enum Error {
     AlreadyInitialized = 700,
     NotInitialized = 701,
     DeliveryNotFound = 702,
+    Unauthorized = 703,
 }
 
 #[contracttype]
@@ -46,6 +56,20 @@ pub struct ProofRequirements {
     pub requires_temperature_log: bool,
 }
 
+/// A pickup-to-dropoff job opened for a coordinator order.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeliveryJob {
+    pub id: u64,
+    pub order_id: u64,
+    /// Blood bank the units are collected from.
+    pub pickup: Address,
+    /// Hospital the units are delivered to.
+    pub dropoff: Address,
+    pub unit_count: u32,
+    pub created_at: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataKey {
@@ -55,6 +79,9 @@ pub enum DataKey {
     TemperatureThresholds,
     ProofRequirements,
     ComplianceAttestation(u64),
+    IdentityContract,
+    CoordinatorContract,
+    DeliveryJob(u64),
 }
 
 #[contract]
@@ -145,6 +172,97 @@ impl DeliveryContract {
             .ok_or(Error::NotInitialized)
     }
 
+    /// Point role checks at the identity contract. Admin only.
+    ///
+    /// Once set, `record_compliance_attestation` requires the caller to hold
+    /// `Role::Admin` there.
+    pub fn set_identity_contract(
+        env: Env,
+        admin: Address,
+        identity_contract: Address,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        if admin != Self::get_admin(env.clone())? {
+            return Err(Error::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::IdentityContract, &identity_contract);
+        Ok(())
+    }
+
+    pub fn get_identity_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::IdentityContract)
+    }
+
+    /// Set the coordinator allowed to open delivery jobs. Admin only.
+    pub fn set_coordinator_contract(
+        env: Env,
+        admin: Address,
+        coordinator_contract: Address,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        if admin != Self::get_admin(env.clone())? {
+            return Err(Error::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::CoordinatorContract, &coordinator_contract);
+        Ok(())
+    }
+
+    pub fn get_coordinator_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::CoordinatorContract)
+    }
+
+    /// Open a delivery job for a coordinator order. Only the configured
+    /// coordinator may call this. Returns the delivery ID.
+    pub fn create_delivery_job(
+        env: Env,
+        order_id: u64,
+        pickup: Address,
+        dropoff: Address,
+        unit_count: u32,
+    ) -> Result<u64, Error> {
+        Self::get_coordinator_contract(env.clone())
+            .ok_or(Error::Unauthorized)?
+            .require_auth();
+
+        let delivery_id = Self::get_delivery_counter(env.clone())? + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::DeliveryCounter, &delivery_id);
+
+        let job = DeliveryJob {
+            id: delivery_id,
+            order_id,
+            pickup: pickup.clone(),
+            dropoff: dropoff.clone(),
+            unit_count,
+            created_at: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::DeliveryJob(delivery_id), &job);
+
+        DeliveryJobCreated {
+            delivery_id,
+            order_id,
+            pickup,
+            dropoff,
+        }
+        .publish(&env);
+
+        Ok(delivery_id)
+    }
+
+    pub fn get_delivery_job(env: Env, delivery_id: u64) -> Result<DeliveryJob, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::DeliveryJob(delivery_id))
+            .ok_or(Error::DeliveryNotFound)
+    }
+
     /// Record a compliance attestation hash for a completed delivery.
     /// The hash is produced off-chain by the backend after evaluating telemetry.
     pub fn record_compliance_attestation(
@@ -158,6 +276,9 @@ impl DeliveryContract {
         if !Self::is_initialized(env.clone()) {
             return Err(Error::NotInitialized);
         }
+        if let Some(identity) = Self::get_identity_contract(env.clone()) {
+            require_role(&env, &identity, &admin, Role::Admin).map_err(|_| Error::Unauthorized)?;
+        }
 
         env.storage().persistent().set(
             &DataKey::ComplianceAttestation(delivery_id),
@@ -105,3 +105,58 @@ fn test_getters_fail_before_initialization() {
         Err(Ok(Error::NotInitialized))
     );
 }
+
+#[test]
+fn test_compliance_attestation_requires_identity_admin_role() {
+    let (env, client, _contract_id, admin, _request_contract) = create_initialized_contract();
+    let identity_id = env.register(identity_contract::AccessControlContract, ());
+    let identity = identity_contract::AccessControlContractClient::new(&env, &identity_id);
+    identity.initialize(&admin);
+
+    client.set_identity_contract(&admin, &identity_id);
+    assert_eq!(client.get_identity_contract(), Some(identity_id));
+
+    let hash = Bytes::from_array(&env, &[1u8; 32]);
+    let backend = Address::generate(&env);
+    assert_eq!(
+        client.try_record_compliance_attestation(&backend, &1, &hash, &true),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    identity.grant_role_with_expiry(&admin, &backend, &identity_contract::Role::Admin, &None);
+    client.record_compliance_attestation(&backend, &1, &hash, &true);
+    assert_eq!(client.get_compliance_attestation(&1), (hash, true));
+}
+
+#[test]
+fn test_set_identity_contract_requires_admin() {
+    let (env, client, _contract_id, _admin, _request_contract) = create_initialized_contract();
+    let outsider = Address::generate(&env);
+    let identity_id = Address::generate(&env);
+
+    assert_eq!(
+        client.try_set_identity_contract(&outsider, &identity_id),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(client.get_identity_contract(), None);
+}
+
+#[test]
+fn test_coordinator_opens_delivery_jobs() {
+    let (env, client, _contract_id, admin, _request_contract) = create_initialized_contract();
+    let (bank, hospital) = (Address::generate(&env), Address::generate(&env));
+
+    assert_eq!(
+        client.try_create_delivery_job(&1, &bank, &hospital, &2),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    client.set_coordinator_contract(&admin, &Address::generate(&env));
+    let delivery_id = client.create_delivery_job(&1, &bank, &hospital, &2);
+    assert_eq!(delivery_id, 1);
+    assert_eq!(client.get_delivery_counter(), 1);
+
+    let job = client.get_delivery_job(&delivery_id);
+    assert_eq!((job.order_id, job.pickup, job.dropoff, job.unit_count), (1, bank, hospital, 2));
+    assert_eq!(client.try_get_delivery_job(&2), Err(Ok(Error::DeliveryNotFound)));
+}
@@ -3,13 +3,23 @@
 
 use soroban_sdk::{
     contract, contracterror, contractevent, contractimpl, contracttype, Address, BytesN, Env,
-    String, Vec,
+    String, Symbol, Vec,
 };
 
 /// Persistent storage TTL constants (ledgers; one ledger ≈ 5 s on mainnet).
 const TTL_THRESHOLD: u32 = 518_400; // ~30 days
 const TTL_EXTEND_TO: u32 = 1_036_800; // ~60 days
 const CONTRACT_VERSION: u32 = 1;
+/// Maximum entries accepted by grant_roles_batch / revoke_roles_batch.
+const MAX_ROLE_BATCH_SIZE: u32 = 50;
+/// Upper bound on addresses visited by one `sweep_expired` call.
+const MAX_SWEEP_LIMIT: u32 = 100;
+/// Entries kept per address in `RoleHistory`; the oldest are dropped first.
+const MAX_ROLE_HISTORY: u32 = 100;
+/// Page size used by paginated reads when the caller passes a limit of zero.
+const DEFAULT_PAGE_SIZE: u32 = 20;
+/// Upper bound on entries returned by one paginated read.
+const MAX_PAGE_SIZE: u32 = 50;
 
 // ---------------------------------------------------------------------------
 // Errors
@@ -33,6 +43,22 @@ pub enum Error {
     AlreadyVerified = 211,
     AlreadyUnverified = 212,
     ContractPaused = 213,
+    GrantRateExceeded = 214,
+    LastAdmin = 215,
+    NoPendingAdmin = 216,
+    BatchTooLarge = 217,
+    NotInitialized = 220,
+    NoPendingGrant = 221,
+    TimelockRequired = 222,
+    OperationNotFound = 223,
+    OperationNotReady = 224,
+    MultisigRequired = 225,
+    ProposalNotFound = 226,
+    AlreadyApproved = 227,
+    MissingPrerequisite = 228,
+    ConflictingRole = 229,
+    /// The role is not defined, or not held by the address.
+    RoleNotFound = 230,
 }
 
 // ---------------------------------------------------------------------------
@@ -138,6 +164,114 @@ pub struct BadgeAwarded {
     pub admin: Address,
 }
 
+#[contractevent(topics = ["admin", "proposed"], data_format = "vec")]
+pub struct AdminProposed {
+    pub current: Address,
+    pub proposed: Address,
+}
+
+#[contractevent(topics = ["admin", "accepted"], data_format = "vec")]
+pub struct AdminAccepted {
+    pub previous: Address,
+    pub new_admin: Address,
+}
+
+#[contractevent(topics = ["contract", "upgraded"], data_format = "vec")]
+pub struct ContractUpgraded {
+    pub admin: Address,
+    pub new_wasm_hash: BytesN<32>,
+    pub previous_version: u32,
+}
+
+#[contractevent(topics = ["operator", "authorized"], data_format = "vec")]
+pub struct OperatorAuthorized {
+    pub owner: Address,
+    pub operator: Address,
+    pub scopes: u32,
+    pub expires_at: Option<u64>,
+}
+
+#[contractevent(topics = ["operator", "revoked"], data_format = "vec")]
+pub struct OperatorRevoked {
+    pub owner: Address,
+    pub operator: Address,
+}
+
+#[contractevent(topics = ["proposal", "created"], data_format = "vec")]
+pub struct ActionProposed {
+    pub id: u64,
+    pub proposer: Address,
+    pub action: ProposalAction,
+}
+
+#[contractevent(topics = ["proposal", "approved"], data_format = "vec")]
+pub struct ProposalApproved {
+    pub id: u64,
+    pub approver: Address,
+    pub approvals: u32,
+}
+
+#[contractevent(topics = ["timelock", "queued"], data_format = "vec")]
+pub struct OperationQueued {
+    pub id: u64,
+    pub operation: TimelockOperation,
+    pub executable_at: u64,
+}
+
+#[contractevent(topics = ["timelock", "executed"], data_format = "vec")]
+pub struct OperationExecuted {
+    pub id: u64,
+    pub executed_by: Address,
+}
+
+#[contractevent(topics = ["timelock", "cancelled"], data_format = "vec")]
+pub struct OperationCancelled {
+    pub id: u64,
+    pub cancelled_by: Address,
+}
+
+#[contractevent(topics = ["address", "suspended"], data_format = "vec")]
+pub struct AddressSuspended {
+    pub address: Address,
+    pub suspended_at: u64,
+}
+
+#[contractevent(topics = ["address", "unsuspended"], data_format = "vec")]
+pub struct AddressUnsuspended {
+    pub address: Address,
+    pub unsuspended_at: u64,
+}
+
+#[contractevent(topics = ["role_granted"], data_format = "vec")]
+pub struct RoleGranted {
+    pub address: Address,
+    pub role: Role,
+    pub granted_at: u64,
+    pub expires_at: Option<u64>,
+}
+
+#[contractevent(topics = ["role_pending"], data_format = "vec")]
+pub struct RoleGrantPending {
+    pub address: Address,
+    pub role: Role,
+    pub expires_at: Option<u64>,
+}
+
+#[contractevent(topics = ["role_revoked"], data_format = "vec")]
+pub struct RoleRevoked {
+    pub address: Address,
+    pub role: Role,
+    pub revoked_at: u64,
+}
+
+#[contractevent(topics = ["role_expired"], data_format = "vec")]
+pub struct RoleExpired {
+    pub address: Address,
+    pub role: Role,
+    pub expires_at: u64,
+    pub removed_at: u64,
+}
+
 #[contractevent(topics = ["delivery_proof"], data_format = "vec")]
 pub struct DeliveryProofRecorded {
     pub request_id: u64,
@@ -154,6 +288,142 @@ pub struct RoleGrant {
     pub expires_at: Option<u64>,
 }
 
+/// Admin-registered metadata describing what a `Role::Custom(id)` means.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CustomRoleDefinition {
+    pub id: u32,
+    pub name: Symbol,
+    /// Hash of the off-chain description document
+    pub description_hash: BytesN<32>,
+    pub defined_at: u64,
+}
+
+/// Delegation from an owner to an operator address (e.g. a staff device).
+///
+/// `scopes` is a bitmask whose bits are assigned by the consuming contracts.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperatorGrant {
+    pub scopes: u32,
+    pub granted_at: u64,
+    pub expires_at: Option<u64>,
+}
+
+/// An admin action that needs `get_approval_threshold` admin approvals.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProposalAction {
+    /// Grant (address, role, expires_at)
+    GrantRole(Address, Role, Option<u64>),
+    AddAdmin(Address),
+    RemoveAdmin(Address),
+    SetApprovalThreshold(u32),
+    /// Queue a timelocked operation; it still waits out the delay once approved
+    QueueOperation(TimelockOperation),
+    /// Upgrade the contract to a new WASM hash
+    Upgrade(BytesN<32>),
+    /// Nominate a new primary admin, who still has to `accept_admin`
+    ProposeAdmin(Address),
+    /// (id, name, description_hash) as for `define_custom_role`
+    DefineCustomRole(u32, Symbol, BytesN<32>),
+    /// (role, prerequisites) as for `set_role_prerequisites`
+    SetRolePrerequisites(Role, Vec<Role>),
+    /// (role_a, role_b, exclusive) as for `set_mutually_exclusive`
+    SetMutuallyExclusive(Role, Role, bool),
+    /// (role, admin_role) as for `set_role_admin`
+    SetRoleAdmin(Role, Option<Role>),
+    /// (role, required) as for `set_role_requires_acceptance`
+    SetRoleRequiresAcceptance(Role, bool),
+    /// (max_per_window, window_seconds) as for `set_grant_rate_limit`
+    SetGrantRateLimit(u32, u64),
+    SuspendAddress(Address),
+    UnsuspendAddress(Address),
+}
+
+/// A `ProposalAction` awaiting approval from `get_approval_threshold` admins.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal {
+    pub id: u64,
+    pub action: ProposalAction,
+    pub proposer: Address,
+    /// Admins who have approved, starting with the proposer
+    pub approvals: Vec<Address>,
+}
+
+/// An admin operation that must wait out the timelock delay.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TimelockOperation {
+    /// Grant (address, role, expires_at)
+    GrantRole(Address, Role, Option<u64>),
+    /// Change the timelock delay itself (seconds, zero disables it)
+    SetTimelockDelay(u64),
+    /// Add an address to the admin set
+    AddAdmin(Address),
+}
+
+/// A `TimelockOperation` queued by `queue_operation`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueuedOperation {
+    pub id: u64,
+    pub operation: TimelockOperation,
+    pub queued_by: Address,
+    pub executable_at: u64,
+}
+
+/// A grant of a role that requires acceptance, waiting on `accept_role`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingGrant {
+    pub role: Role,
+    pub expires_at: Option<u64>,
+    pub proposed_at: u64,
+    pub granted_by: Address,
+}
+
+/// Kind of change recorded in an address's role history.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RoleAction {
+    Granted,
+    Revoked,
+    Expired,
+}
+
+/// One entry of the append-only role history kept per address.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleHistoryEntry {
+    pub role: Role,
+    pub action: RoleAction,
+    /// Admin or delegate behind the change; `None` for expiry cleanup
+    pub actor: Option<Address>,
+    pub timestamp: u64,
+    /// Expiry of the grant, for `Granted` entries
+    pub expires_at: Option<u64>,
+}
+
+/// Proof that a KYC document backed a role grant.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attestation {
+    /// Hash of the verified KYC document
+    pub attestation_hash: BytesN<32>,
+    pub attester: Address,
+    pub attested_at: u64,
+}
+
+/// Per-admin cap on role grants within a sliding window. `max_per_window == 0` disables it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GrantRateLimit {
+    pub max_per_window: u32,
+    pub window_seconds: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct RatingRecord {
@@ -214,13 +484,89 @@ pub enum DataKey {
     Delivery(u64),
     // AccessControlContract (and IdentityContract role storage)
     AddressRoles(Address),
+    // Pre-consolidation layout: one RoleGrant entry per (address, role).
+    // Only read by migrate_legacy_roles; never written.
+    LegacyRole(Address, Role),
     // Fine-grained permission scopes (Issue #374)
     AddressScopes(Address),
+    // Sorted list of Role::Custom ids with at least one holder
+    KnownCustomRoles,
+    // Number of addresses currently holding Role::Custom(id)
+    CustomRoleHolders(u32),
+    // Configured GrantRateLimit for role grants
+    GrantRateLimit,
+    // Timestamps of an admin's grants still inside the rate-limit window
+    GrantRate(Address),
+    // AccessControlContract admin set; DataKey::Admin stays the primary admin
+    Admins,
+    // Primary admin nominated by propose_admin, awaiting accept_admin
+    PendingAdmin,
+    // Role whose holders may grant/revoke the keyed role via grant_role_as
+    RoleAdmin(Role),
+    // Addresses currently holding the keyed role, in grant order
+    RoleMembers(Role),
+    // CustomRoleDefinition for Role::Custom(id); grants require one
+    CustomRoleDef(u32),
+    // Set for roles whose grants stay pending until the grantee calls accept_role
+    RequiresAcceptance(Role),
+    // Grant of a RequiresAcceptance role not yet accepted by the grantee
+    PendingGrant(Address, Role),
+    // Set while an address is suspended; has_role reports none of its roles
+    Suspended(Address),
+    // Last MAX_ROLE_HISTORY RoleHistoryEntry values for the keyed address
+    RoleHistory(Address),
+    // Roles an address must hold before it can be granted the keyed role
+    RolePrerequisites(Role),
+    // Roles that cannot be held together with the keyed role
+    RoleExclusions(Role),
+    // OperatorGrant from (owner, operator)
+    Operator(Address, Address),
+    // Number of admin approvals a Proposal needs; direct calls need 1
+    ApprovalThreshold,
+    // Id of the last Proposal
+    ProposalCounter,
+    // Proposal still short of the approval threshold
+    Proposal(u64),
+    // Seconds a queued TimelockOperation waits before it can be executed
+    TimelockDelay,
+    // Id of the last queued TimelockOperation
+    OperationCounter,
+    // QueuedOperation awaiting execution or cancellation
+    QueuedOperation(u64),
+    // Number of addresses in the role address index
+    RoleAddressCount,
+    // Role address index: position -> address, append-only
+    RoleAddressAt(u32),
+    // Set once an address has been appended to the role address index
+    RoleAddressIndexed(Address),
+    // KYC Attestation recorded against an (address, role) grant
+    Attestation(Address, Role),
     Paused,
     // Address of the requests contract used to verify interactions before rating
     RequestsContract,
 }
 
+/// Read the role grants stored for `address`, extending the entry's TTL so
+/// that roles which are still being checked are never archived.
+fn read_role_grants(env: &Env, address: &Address) -> Option<Vec<RoleGrant>> {
+    let key = DataKey::AddressRoles(address.clone());
+    let roles: Option<Vec<RoleGrant>> = env.storage().persistent().get(&key);
+    if roles.is_some() {
+        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    }
+    roles
+}
+
+/// Clamp a caller-supplied page size to `1..=MAX_PAGE_SIZE`, treating zero as
+/// `DEFAULT_PAGE_SIZE`.
+fn clamp_limit(limit: u32) -> u32 {
+    if limit == 0 {
+        DEFAULT_PAGE_SIZE
+    } else {
+        limit.min(MAX_PAGE_SIZE)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // IdentityContract
 // ---------------------------------------------------------------------------
@@ -460,12 +806,7 @@ impl IdentityContract {
 
     /// Get the primary role of an address (first role in the sorted vec, if any).
     pub fn get_role(env: Env, address: Address) -> Option<Role> {
-        let key = DataKey::AddressRoles(address);
-        let roles: Vec<RoleGrant> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(&env));
+        let roles = read_role_grants(&env, &address).unwrap_or(Vec::new(&env));
         if roles.is_empty() {
             None
         } else {
@@ -480,12 +821,7 @@ impl IdentityContract {
 
     /// Check if an address has a given role
     pub fn has_role(env: Env, account: Address, role: Role) -> bool {
-        let key = DataKey::AddressRoles(account);
-        let roles: Vec<RoleGrant> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(&env));
+        let roles = read_role_grants(&env, &account).unwrap_or(Vec::new(&env));
         for i in 0..roles.len() {
             if roles.get(i).unwrap().role == role {
                 return true;
@@ -982,159 +1318,1317 @@ pub struct AccessControlContract;
 #[contractimpl]
 impl AccessControlContract {
     /// Initialize the contract with an administrator
-    pub fn initialize(env: Env, admin: Address) {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
         if env.storage().persistent().has(&DataKey::Admin) {
-            panic!("Already initialized");
+            return Err(Error::AlreadyInitialized);
         }
         env.storage().persistent().set(&DataKey::Admin, &admin);
         env.storage().persistent().extend_ttl(&DataKey::Admin, TTL_THRESHOLD, TTL_EXTEND_TO);
+        let admins: Vec<Address> = Vec::from_array(&env, [admin]);
+        env.storage().persistent().set(&DataKey::Admins, &admins);
+        env.storage().persistent().extend_ttl(&DataKey::Admins, TTL_THRESHOLD, TTL_EXTEND_TO);
+        Ok(())
     }
 
-    /// Grant a role to an address with optional expiry
-    pub fn grant_role_with_expiry(env: Env, address: Address, role: Role, expires_at: Option<u64>) {
-        let admin: Address = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
-        admin.require_auth();
+    pub fn version(_env: Env) -> u32 {
+        CONTRACT_VERSION
+    }
 
-        Self::cleanup_expired_roles_internal(&env, &address);
+    /// Upgrade the contract to a new WASM hash. Any admin may call this while
+    /// the approval threshold is 1; above it, propose `ProposalAction::Upgrade`.
+    ///
+    /// Grants and other storage are kept, so no migration to a new address is
+    /// needed. Emits `ContractUpgraded` with the version being replaced.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not an admin
+    /// * `MultisigRequired` - If the approval threshold is above 1
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        Self::require_single_approval(&env)?;
+        Self::apply_upgrade(&env, admin, new_wasm_hash);
+        Ok(())
+    }
 
-        let key = DataKey::AddressRoles(address.clone());
-        let mut roles: Vec<RoleGrant> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(&env));
+    fn apply_upgrade(env: &Env, admin: Address, new_wasm_hash: BytesN<32>) {
+        ContractUpgraded {
+            admin,
+            new_wasm_hash: new_wasm_hash.clone(),
+            previous_version: CONTRACT_VERSION,
+        }
+        .publish(env);
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
 
-        let granted_at = env.ledger().timestamp();
-        let new_grant = RoleGrant {
-            role: role.clone(),
-            granted_at,
-            expires_at,
-        };
+    /// Add another admin. Any existing admin may call this while the approval
+    /// threshold is 1; above it, propose `ProposalAction::AddAdmin` instead.
+    ///
+    /// Fails with `TimelockRequired` while a timelock delay is configured;
+    /// queue `TimelockOperation::AddAdmin` instead.
+    pub fn add_admin(env: Env, caller: Address, new_admin: Address) -> Result<(), Error> {
+        Self::require_admin(&env, &caller)?;
+        Self::require_single_approval(&env)?;
+        Self::require_admins_unlocked(&env)?;
+        Self::store_admin(&env, new_admin);
+        Ok(())
+    }
 
-        roles = Self::remove_role_from_vec(&env, roles, &role);
-        roles = Self::insert_sorted(&env, roles, new_grant);
+    fn store_admin(env: &Env, new_admin: Address) {
+        let mut admins = Self::load_admins(env);
+        if admins.contains(&new_admin) {
+            return;
+        }
+        admins.push_back(new_admin);
+        env.storage().persistent().set(&DataKey::Admins, &admins);
+        env.storage().persistent().extend_ttl(&DataKey::Admins, TTL_THRESHOLD, TTL_EXTEND_TO);
+    }
 
-        env.storage().persistent().set(&key, &roles);
-        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    /// Remove an admin. Fails with `LastAdmin` rather than leave the contract
+    /// without one. Like `add_admin`, needs `ProposalAction::RemoveAdmin`
+    /// once the approval threshold is above 1, since dropping admins also
+    /// lowers the effective threshold.
+    ///
+    /// Removing the primary admin promotes the first remaining admin.
+    pub fn remove_admin(env: Env, caller: Address, admin: Address) -> Result<(), Error> {
+        Self::require_admin(&env, &caller)?;
+        Self::require_single_approval(&env)?;
+        Self::unstore_admin(&env, &admin)
     }
 
-    /// Revoke a role from an address
-    pub fn revoke_role(env: Env, address: Address, role: Role) {
-        let admin: Address = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
+    fn unstore_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+        let mut admins = Self::load_admins(env);
+        let index = admins.first_index_of(admin).ok_or(Error::InvalidInput)?;
+        if admins.len() == 1 {
+            return Err(Error::LastAdmin);
+        }
+        admins.remove(index);
+        env.storage().persistent().set(&DataKey::Admins, &admins);
+        env.storage().persistent().extend_ttl(&DataKey::Admins, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        let primary = Self::primary_admin(env)?;
+        if primary == *admin {
+            env.storage().persistent().set(&DataKey::Admin, &admins.get(0).unwrap());
+            env.storage().persistent().extend_ttl(&DataKey::Admin, TTL_THRESHOLD, TTL_EXTEND_TO);
+        }
+        Ok(())
+    }
+
+    /// Nominate a new primary admin. Takes effect only once the nominee calls
+    /// `accept_admin`; a later proposal replaces an earlier one.
+    ///
+    /// Both steps fail with `TimelockRequired` while a timelock delay is
+    /// configured, since the nominee joins the admin set. Above an approval
+    /// threshold of 1, nominate with `ProposalAction::ProposeAdmin` instead.
+    pub fn propose_admin(env: Env, new_admin: Address) -> Result<(), Error> {
+        let admin = Self::primary_admin(&env)?;
         admin.require_auth();
+        Self::require_single_approval(&env)?;
+        Self::nominate_admin(&env, new_admin)
+    }
 
-        let key = DataKey::AddressRoles(address.clone());
+    fn nominate_admin(env: &Env, new_admin: Address) -> Result<(), Error> {
+        Self::require_admins_unlocked(env)?;
+        let current = Self::primary_admin(env)?;
+        env.storage().persistent().set(&DataKey::PendingAdmin, &new_admin);
+        env.storage().persistent().extend_ttl(&DataKey::PendingAdmin, TTL_THRESHOLD, TTL_EXTEND_TO);
 
-        if let Some(mut roles) = env
+        AdminProposed { current, proposed: new_admin }.publish(env);
+        Ok(())
+    }
+
+    /// Complete a primary admin rotation. Must be signed by the proposed admin,
+    /// who replaces the previous primary admin in the admin set.
+    pub fn accept_admin(env: Env) -> Result<(), Error> {
+        let new_admin: Address = env
             .storage()
             .persistent()
-            .get::<DataKey, Vec<RoleGrant>>(&key)
-        {
-            roles = Self::remove_role_from_vec(&env, roles, &role);
+            .get(&DataKey::PendingAdmin)
+            .ok_or(Error::NoPendingAdmin)?;
+        new_admin.require_auth();
+        Self::require_admins_unlocked(&env)?;
 
-            if roles.is_empty() {
-                env.storage().persistent().remove(&key);
-            } else {
-                env.storage().persistent().set(&key, &roles);
-                env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
-            }
+        let previous = Self::primary_admin(&env)?;
+
+        let mut admins = Self::load_admins(&env);
+        if let Some(index) = admins.first_index_of(&previous) {
+            admins.remove(index);
         }
+        if !admins.contains(&new_admin) {
+            admins.push_front(new_admin.clone());
+        }
+        env.storage().persistent().set(&DataKey::Admins, &admins);
+        env.storage().persistent().extend_ttl(&DataKey::Admins, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        env.storage().persistent().set(&DataKey::Admin, &new_admin);
+        env.storage().persistent().extend_ttl(&DataKey::Admin, TTL_THRESHOLD, TTL_EXTEND_TO);
+        env.storage().persistent().remove(&DataKey::PendingAdmin);
+
+        AdminAccepted { previous, new_admin }.publish(&env);
+        Ok(())
     }
 
-    /// Check if an address has a specific non-expired role
-    pub fn has_role(env: Env, address: Address, role: Role) -> bool {
-        Self::cleanup_expired_roles_internal(&env, &address);
+    /// Get the admin nominated by `propose_admin`, if any
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::PendingAdmin)
+    }
 
-        let key = DataKey::AddressRoles(address);
+    /// List the current admins
+    pub fn get_admins(env: Env) -> Vec<Address> {
+        Self::load_admins(&env)
+    }
 
-        if let Some(roles) = env
-            .storage()
-            .persistent()
-            .get::<DataKey, Vec<RoleGrant>>(&key)
-        {
-            for i in 0..roles.len() {
-                let grant = roles.get(i).unwrap();
-                if grant.role == role {
-                    return true;
-                }
-            }
+    /// Admin set, falling back to the primary admin for deployments that
+    /// predate `DataKey::Admins`.
+    fn load_admins(env: &Env) -> Vec<Address> {
+        if let Some(admins) = env.storage().persistent().get(&DataKey::Admins) {
+            return admins;
+        }
+        match env.storage().persistent().get::<DataKey, Address>(&DataKey::Admin) {
+            Some(admin) => Vec::from_array(env, [admin]),
+            None => Vec::new(env),
         }
-
-        false
     }
 
-    /// Get all role grants for an address (including expired)
-    pub fn get_roles(env: Env, address: Address) -> Vec<RoleGrant> {
-        let key = DataKey::AddressRoles(address);
+    /// Primary admin, or `NotInitialized` before `initialize` has run.
+    fn primary_admin(env: &Env) -> Result<Address, Error> {
         env.storage()
             .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(&env))
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)
     }
 
-    /// Proactively clean up all expired roles for an address. Returns count removed.
-    pub fn cleanup_expired_roles(env: Env, address: Address) -> u32 {
-        let admin: Address = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
-        admin.require_auth();
+    /// Require `caller` to be one of the admins and to have signed.
+    fn require_admin(env: &Env, caller: &Address) -> Result<(), Error> {
+        if !env.storage().persistent().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        if !Self::load_admins(env).contains(caller) {
+            return Err(Error::Unauthorized);
+        }
+        caller.require_auth();
+        Ok(())
+    }
 
-        Self::cleanup_expired_roles_internal(&env, &address)
+    /// Grant a role to an address with optional expiry. Any admin may call this.
+    ///
+    /// Fails with `GrantRateExceeded` when the calling admin has used up the
+    /// grant allowance configured by `set_grant_rate_limit`.
+    pub fn grant_role_with_expiry(
+        env: Env,
+        admin: Address,
+        address: Address,
+        role: Role,
+        expires_at: Option<u64>,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        Self::require_single_approval(&env)?;
+        Self::require_defined_role(&env, &role)?;
+        Self::require_role_constraints(&env, &address, &role)?;
+        Self::require_not_timelocked(&env, &role)?;
+        Self::consume_grant_allowance(&env, &admin)?;
+        Self::grant_or_propose(&env, &admin, &address, &role, expires_at);
+        Ok(())
     }
 
-    fn cleanup_expired_roles_internal(env: &Env, address: &Address) -> u32 {
-        let key = DataKey::AddressRoles(address.clone());
+    /// Register or update the metadata for `Role::Custom(id)`. Any admin may
+    /// call this while the approval threshold is 1; above it, propose
+    /// `ProposalAction::DefineCustomRole`. Custom roles cannot be granted
+    /// until they are defined.
+    pub fn define_custom_role(
+        env: Env,
+        admin: Address,
+        id: u32,
+        name: Symbol,
+        description_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        Self::require_single_approval(&env)?;
+        Self::store_custom_role(&env, id, name, description_hash);
+        Ok(())
+    }
 
-        if let Some(roles) = env
-            .storage()
-            .persistent()
-            .get::<DataKey, Vec<RoleGrant>>(&key)
-        {
-            let current_time = env.ledger().timestamp();
-            let mut new_roles = Vec::new(env);
-            let mut removed_count = 0u32;
+    fn store_custom_role(env: &Env, id: u32, name: Symbol, description_hash: BytesN<32>) {
+        let key = DataKey::CustomRoleDef(id);
+        let definition = CustomRoleDefinition {
+            id,
+            name,
+            description_hash,
+            defined_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&key, &definition);
+        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    }
 
-            for i in 0..roles.len() {
-                let grant = roles.get(i).unwrap();
-                let is_expired = if let Some(expires_at) = grant.expires_at {
-                    current_time >= expires_at
-                } else {
-                    false
-                };
+    /// Get the metadata registered for `Role::Custom(id)`. Fails with
+    /// `RoleNotFound` if it was never defined.
+    pub fn get_custom_role(env: Env, id: u32) -> Result<CustomRoleDefinition, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CustomRoleDef(id))
+            .ok_or(Error::RoleNotFound)
+    }
 
-                if is_expired {
-                    removed_count += 1;
-                } else {
-                    new_roles.push_back(grant);
-                }
+    /// Reject grants of `Role::Custom(id)` that have no registered definition.
+    fn require_defined_role(env: &Env, role: &Role) -> Result<(), Error> {
+        if let Role::Custom(id) = role {
+            if !env.storage().persistent().has(&DataKey::CustomRoleDef(*id)) {
+                return Err(Error::RoleNotFound);
             }
+        }
+        Ok(())
+    }
 
-            if removed_count > 0 {
-                if new_roles.is_empty() {
-                    env.storage().persistent().remove(&key);
-                } else {
-                    env.storage().persistent().set(&key, &new_roles);
-                    env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
-                }
-            }
+    /// Require holders of `role` to already hold every role in `prerequisites`
+    /// (e.g. `Rider` requires `Donor`). An empty list clears the requirement.
+    /// Any admin may call this while the approval threshold is 1; above it,
+    /// propose `ProposalAction::SetRolePrerequisites`.
+    pub fn set_role_prerequisites(
+        env: Env,
+        admin: Address,
+        role: Role,
+        prerequisites: Vec<Role>,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        Self::require_single_approval(&env)?;
+        Self::store_role_prerequisites(&env, role, prerequisites)
+    }
 
-            removed_count
+    fn store_role_prerequisites(env: &Env, role: Role, prerequisites: Vec<Role>) -> Result<(), Error> {
+        if prerequisites.contains(&role) {
+            return Err(Error::InvalidInput);
+        }
+        let key = DataKey::RolePrerequisites(role);
+        if prerequisites.is_empty() {
+            env.storage().persistent().remove(&key);
         } else {
-            0
+            env.storage().persistent().set(&key, &prerequisites);
+            env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
         }
+        Ok(())
     }
 
-    fn remove_role_from_vec(env: &Env, roles: Vec<RoleGrant>, role: &Role) -> Vec<RoleGrant> {
+    /// Get the roles required before `role` can be granted
+    pub fn get_role_prerequisites(env: Env, role: Role) -> Vec<Role> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RolePrerequisites(role))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Mark two roles as mutually exclusive (e.g. `Hospital` and `BloodBank`),
+    /// or lift the exclusion. Applies to later grants only. Any admin may call
+    /// this while the approval threshold is 1; above it, propose
+    /// `ProposalAction::SetMutuallyExclusive`.
+    pub fn set_mutually_exclusive(
+        env: Env,
+        admin: Address,
+        role_a: Role,
+        role_b: Role,
+        exclusive: bool,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        Self::require_single_approval(&env)?;
+        Self::store_exclusion(&env, &role_a, &role_b, exclusive)
+    }
+
+    fn store_exclusion(env: &Env, role_a: &Role, role_b: &Role, exclusive: bool) -> Result<(), Error> {
+        if role_a == role_b {
+            return Err(Error::InvalidInput);
+        }
+        Self::set_exclusion(env, role_a, role_b, exclusive);
+        Self::set_exclusion(env, role_b, role_a, exclusive);
+        Ok(())
+    }
+
+    /// Get the roles that cannot be held together with `role`
+    pub fn get_role_exclusions(env: Env, role: Role) -> Vec<Role> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RoleExclusions(role))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    fn set_exclusion(env: &Env, role: &Role, other: &Role, exclusive: bool) {
+        let key = DataKey::RoleExclusions(role.clone());
+        let mut exclusions = Self::get_role_exclusions(env.clone(), role.clone());
+        match (exclusions.first_index_of(other), exclusive) {
+            (None, true) => exclusions.push_back(other.clone()),
+            (Some(index), false) => {
+                exclusions.remove(index);
+            }
+            _ => return,
+        }
+        if exclusions.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &exclusions);
+            env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        }
+    }
+
+    /// Fail with `MissingPrerequisite` if `address` lacks a role required for
+    /// `role`, or `ConflictingRole` if it holds one excluded by `role`.
+    fn require_role_constraints(env: &Env, address: &Address, role: &Role) -> Result<(), Error> {
+        for required in Self::get_role_prerequisites(env.clone(), role.clone()).iter() {
+            if !Self::has_role(env.clone(), address.clone(), required) {
+                return Err(Error::MissingPrerequisite);
+            }
+        }
+        for excluded in Self::get_role_exclusions(env.clone(), role.clone()).iter() {
+            if Self::has_role(env.clone(), address.clone(), excluded) {
+                return Err(Error::ConflictingRole);
+            }
+        }
+        Ok(())
+    }
+
+    /// Let holders of `admin_role` grant and revoke `role` via `grant_role_as`
+    /// and `revoke_role_as` (e.g. `Hospital` may manage `Rider`). Passing
+    /// `None` removes the delegation. Any admin may call this while the
+    /// approval threshold is 1; above it, propose `ProposalAction::SetRoleAdmin`.
+    pub fn set_role_admin(
+        env: Env,
+        admin: Address,
+        role: Role,
+        admin_role: Option<Role>,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        Self::require_single_approval(&env)?;
+        Self::store_role_admin(&env, role, admin_role);
+        Ok(())
+    }
+
+    fn store_role_admin(env: &Env, role: Role, admin_role: Option<Role>) {
+        let key = DataKey::RoleAdmin(role);
+        match admin_role {
+            Some(admin_role) => {
+                env.storage().persistent().set(&key, &admin_role);
+                env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+            }
+            None => env.storage().persistent().remove(&key),
+        }
+    }
+
+    /// Get the role allowed to grant and revoke `role`, if delegated
+    pub fn get_role_admin(env: Env, role: Role) -> Option<Role> {
+        env.storage().persistent().get(&DataKey::RoleAdmin(role))
+    }
+
+    /// Grant `role` on behalf of a holder of its delegated admin role.
+    ///
+    /// Fails with `Unauthorized` unless `granter` currently holds the role set
+    /// by `set_role_admin` for `role`. Counts against the granter's rate limit.
+    /// Like a direct grant, fails with `MultisigRequired` while the approval
+    /// threshold is above 1.
+    pub fn grant_role_as(
+        env: Env,
+        granter: Address,
+        address: Address,
+        role: Role,
+        expires_at: Option<u64>,
+    ) -> Result<(), Error> {
+        Self::require_role_admin(&env, &granter, &role)?;
+        Self::require_single_approval(&env)?;
+        Self::require_defined_role(&env, &role)?;
+        Self::require_role_constraints(&env, &address, &role)?;
+        Self::require_not_timelocked(&env, &role)?;
+        Self::consume_grant_allowance(&env, &granter)?;
+        Self::grant_or_propose(&env, &granter, &address, &role, expires_at);
+        Ok(())
+    }
+
+    /// Revoke `role` on behalf of a holder of its delegated admin role.
+    pub fn revoke_role_as(
+        env: Env,
+        revoker: Address,
+        address: Address,
+        role: Role,
+    ) -> Result<(), Error> {
+        Self::require_role_admin(&env, &revoker, &role)?;
+        if !Self::apply_revoke(&env, &revoker, &address, &role) {
+            return Err(Error::RoleNotFound);
+        }
+        Ok(())
+    }
+
+    fn require_role_admin(env: &Env, caller: &Address, role: &Role) -> Result<(), Error> {
+        caller.require_auth();
+        let admin_role: Role = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RoleAdmin(role.clone()))
+            .ok_or(Error::Unauthorized)?;
+        if !Self::has_role(env.clone(), caller.clone(), admin_role) {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
+
+    /// Require grantees of `role` to accept it with `accept_role` before it
+    /// takes effect. Admin only; above an approval threshold of 1, propose
+    /// `ProposalAction::SetRoleRequiresAcceptance`. Grants already held are
+    /// unaffected.
+    pub fn set_role_requires_acceptance(
+        env: Env,
+        admin: Address,
+        role: Role,
+        required: bool,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        Self::require_single_approval(&env)?;
+        Self::store_requires_acceptance(&env, role, required);
+        Ok(())
+    }
+
+    fn store_requires_acceptance(env: &Env, role: Role, required: bool) {
+        let key = DataKey::RequiresAcceptance(role);
+        if required {
+            env.storage().persistent().set(&key, &true);
+            env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+    }
+
+    /// Whether grants of `role` wait for the grantee's acceptance
+    pub fn role_requires_acceptance(env: Env, role: Role) -> bool {
+        env.storage().persistent().has(&DataKey::RequiresAcceptance(role))
+    }
+
+    /// Get the grant of `role` to `address` awaiting acceptance, if any
+    pub fn get_pending_grant(env: Env, address: Address, role: Role) -> Option<PendingGrant> {
+        env.storage().persistent().get(&DataKey::PendingGrant(address, role))
+    }
+
+    /// Accept a pending grant of `role`. Must be signed by the grantee.
+    ///
+    /// Fails with `NoPendingGrant` if nothing is waiting. The expiry chosen by
+    /// the granter is kept.
+    pub fn accept_role(env: Env, address: Address, role: Role) -> Result<(), Error> {
+        address.require_auth();
+        let key = DataKey::PendingGrant(address.clone(), role.clone());
+        let pending: PendingGrant = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::NoPendingGrant)?;
+        env.storage().persistent().remove(&key);
+        Self::apply_grant(&env, &pending.granted_by, &address, &role, pending.expires_at);
+        Ok(())
+    }
+
+    /// Apply the grant, or park it as a `PendingGrant` when `role` requires
+    /// acceptance. A later grant replaces an earlier pending one.
+    fn grant_or_propose(
+        env: &Env,
+        granter: &Address,
+        address: &Address,
+        role: &Role,
+        expires_at: Option<u64>,
+    ) {
+        if !env.storage().persistent().has(&DataKey::RequiresAcceptance(role.clone())) {
+            Self::apply_grant(env, granter, address, role, expires_at);
+            return;
+        }
+        let key = DataKey::PendingGrant(address.clone(), role.clone());
+        let pending = PendingGrant {
+            role: role.clone(),
+            expires_at,
+            proposed_at: env.ledger().timestamp(),
+            granted_by: granter.clone(),
+        };
+        env.storage().persistent().set(&key, &pending);
+        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        RoleGrantPending {
+            address: address.clone(),
+            role: role.clone(),
+            expires_at,
+        }
+        .publish(env);
+    }
+
+    fn apply_grant(
+        env: &Env,
+        granter: &Address,
+        address: &Address,
+        role: &Role,
+        expires_at: Option<u64>,
+    ) {
+        Self::cleanup_expired_roles_internal(env, address);
+        Self::index_role_address(env, address);
+
+        let key = DataKey::AddressRoles(address.clone());
+        let mut roles: Vec<RoleGrant> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+
+        let granted_at = env.ledger().timestamp();
+        let new_grant = RoleGrant {
+            role: role.clone(),
+            granted_at,
+            expires_at,
+        };
+
+        let held_before = roles.len();
+        roles = Self::remove_role_from_vec(env, roles, role);
+        if roles.len() == held_before {
+            Self::track_custom_role_added(env, role);
+            Self::add_role_member(env, role, address);
+        }
+        roles = Self::insert_sorted(env, roles, new_grant);
+
+        env.storage().persistent().set(&key, &roles);
+        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        Self::record_role_history(
+            env,
+            address,
+            role,
+            RoleAction::Granted,
+            Some(granter),
+            expires_at,
+        );
+
+        RoleGranted {
+            address: address.clone(),
+            role: role.clone(),
+            granted_at,
+            expires_at,
+        }
+        .publish(env);
+    }
+
+    /// Append to `address`'s role history, dropping the oldest entry once
+    /// `MAX_ROLE_HISTORY` is reached.
+    fn record_role_history(
+        env: &Env,
+        address: &Address,
+        role: &Role,
+        action: RoleAction,
+        actor: Option<&Address>,
+        expires_at: Option<u64>,
+    ) {
+        let key = DataKey::RoleHistory(address.clone());
+        let mut history: Vec<RoleHistoryEntry> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        if history.len() >= MAX_ROLE_HISTORY {
+            history.pop_front();
+        }
+        history.push_back(RoleHistoryEntry {
+            role: role.clone(),
+            action,
+            actor: actor.cloned(),
+            timestamp: env.ledger().timestamp(),
+            expires_at,
+        });
+        env.storage().persistent().set(&key, &history);
+        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    }
+
+    /// Page through `address`'s role grants, revocations and expiries,
+    /// oldest first. Only the last `MAX_ROLE_HISTORY` (100) entries are kept.
+    pub fn get_role_history(
+        env: Env,
+        address: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<RoleHistoryEntry> {
+        let history: Vec<RoleHistoryEntry> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RoleHistory(address))
+            .unwrap_or(Vec::new(&env));
+
+        let end = offset.saturating_add(limit).min(history.len());
+        let mut page = Vec::new(&env);
+        for i in offset..end {
+            page.push_back(history.get(i).unwrap());
+        }
+        page
+    }
+
+    /// Limit how many grants each admin may issue within a sliding window.
+    ///
+    /// Every granter has an allowance of its own, so a compromised key cannot
+    /// use up the others'. A `max_per_window` of zero disables the limit.
+    /// Primary admin only; above an approval threshold of 1, propose
+    /// `ProposalAction::SetGrantRateLimit`.
+    pub fn set_grant_rate_limit(
+        env: Env,
+        max_per_window: u32,
+        window_seconds: u64,
+    ) -> Result<(), Error> {
+        let admin = Self::primary_admin(&env)?;
+        admin.require_auth();
+        Self::require_single_approval(&env)?;
+        Self::store_grant_rate_limit(&env, max_per_window, window_seconds);
+        Ok(())
+    }
+
+    fn store_grant_rate_limit(env: &Env, max_per_window: u32, window_seconds: u64) {
+        if max_per_window == 0 {
+            env.storage().persistent().remove(&DataKey::GrantRateLimit);
+            return;
+        }
+        let limit = GrantRateLimit { max_per_window, window_seconds };
+        env.storage().persistent().set(&DataKey::GrantRateLimit, &limit);
+        env.storage().persistent().extend_ttl(&DataKey::GrantRateLimit, TTL_THRESHOLD, TTL_EXTEND_TO);
+    }
+
+    /// Get the configured grant rate limit, if any
+    pub fn get_grant_rate_limit(env: Env) -> Option<GrantRateLimit> {
+        env.storage().persistent().get(&DataKey::GrantRateLimit)
+    }
+
+    /// Record one grant against `granter`'s allowance, dropping grants that
+    /// have slid out of the window. No-op when no limit is configured.
+    fn consume_grant_allowance(env: &Env, granter: &Address) -> Result<(), Error> {
+        let limit: GrantRateLimit = match env.storage().persistent().get(&DataKey::GrantRateLimit) {
+            Some(limit) => limit,
+            None => return Ok(()),
+        };
+
+        let now = env.ledger().timestamp();
+        let key = DataKey::GrantRate(granter.clone());
+        let recent: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+
+        let mut in_window = Vec::new(env);
+        for granted_at in recent.iter() {
+            if granted_at.saturating_add(limit.window_seconds) > now {
+                in_window.push_back(granted_at);
+            }
+        }
+        if in_window.len() >= limit.max_per_window {
+            return Err(Error::GrantRateExceeded);
+        }
+
+        in_window.push_back(now);
+        env.storage().persistent().set(&key, &in_window);
+        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        Ok(())
+    }
+
+    /// Revoke a role from an address. Any admin may call this.
+    ///
+    /// Fails with `RoleNotFound` if the address neither holds the role nor
+    /// has a grant of it waiting on `accept_role`.
+    pub fn revoke_role(env: Env, admin: Address, address: Address, role: Role) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        if !Self::apply_revoke(&env, &admin, &address, &role) {
+            return Err(Error::RoleNotFound);
+        }
+        Ok(())
+    }
+
+    /// Grant several roles in one call. Any admin may call this.
+    ///
+    /// At most `MAX_ROLE_BATCH_SIZE` (50) entries; larger batches fail with
+    /// `BatchTooLarge`. Entries are applied independently: the result holds
+    /// one code per entry, `0` on success or the `Error` value that stopped
+    /// that entry (e.g. `GrantRateExceeded`, `RoleNotFound`). Failed entries do not roll back
+    /// the rest of the batch.
+    pub fn grant_roles_batch(
+        env: Env,
+        admin: Address,
+        grants: Vec<(Address, Role, Option<u64>)>,
+    ) -> Result<Vec<u32>, Error> {
+        Self::require_admin(&env, &admin)?;
+        Self::require_single_approval(&env)?;
+        if grants.len() > MAX_ROLE_BATCH_SIZE {
+            return Err(Error::BatchTooLarge);
+        }
+
+        let mut results = Vec::new(&env);
+        for (address, role, expires_at) in grants.iter() {
+            let outcome = Self::require_defined_role(&env, &role)
+                .and_then(|_| Self::require_role_constraints(&env, &address, &role))
+                .and_then(|_| Self::require_not_timelocked(&env, &role))
+                .and_then(|_| Self::consume_grant_allowance(&env, &admin));
+            match outcome {
+                Ok(()) => {
+                    Self::grant_or_propose(&env, &admin, &address, &role, expires_at);
+                    results.push_back(0);
+                }
+                Err(e) => results.push_back(e as u32),
+            }
+        }
+        Ok(results)
+    }
+
+    /// Revoke several roles in one call. Any admin may call this.
+    ///
+    /// Same size cap and per-entry result codes as `grant_roles_batch`; an
+    /// entry whose role is not currently held reports `RoleNotFound`.
+    pub fn revoke_roles_batch(
+        env: Env,
+        admin: Address,
+        revocations: Vec<(Address, Role)>,
+    ) -> Result<Vec<u32>, Error> {
+        Self::require_admin(&env, &admin)?;
+        if revocations.len() > MAX_ROLE_BATCH_SIZE {
+            return Err(Error::BatchTooLarge);
+        }
+
+        let mut results = Vec::new(&env);
+        for (address, role) in revocations.iter() {
+            if Self::apply_revoke(&env, &admin, &address, &role) {
+                results.push_back(0);
+            } else {
+                results.push_back(Error::RoleNotFound as u32);
+            }
+        }
+        Ok(results)
+    }
+
+    /// Remove `role` from `address`. Returns whether it was held or still
+    /// waiting on `accept_role`.
+    fn apply_revoke(env: &Env, revoker: &Address, address: &Address, role: &Role) -> bool {
+        // Revoking also withdraws a grant still waiting on accept_role
+        let pending_key = DataKey::PendingGrant(address.clone(), role.clone());
+        let withdrew_pending = env.storage().persistent().has(&pending_key);
+        env.storage().persistent().remove(&pending_key);
+
+        let key = DataKey::AddressRoles(address.clone());
+
+        if let Some(mut roles) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Vec<RoleGrant>>(&key)
+        {
+            let held_before = roles.len();
+            roles = Self::remove_role_from_vec(env, roles, role);
+            if roles.len() == held_before {
+                return withdrew_pending;
+            }
+            Self::track_custom_role_removed(env, role);
+            Self::remove_role_member(env, role, address);
+
+            if roles.is_empty() {
+                env.storage().persistent().remove(&key);
+            } else {
+                env.storage().persistent().set(&key, &roles);
+                env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+            }
+
+            Self::record_role_history(env, address, role, RoleAction::Revoked, Some(revoker), None);
+
+            RoleRevoked {
+                address: address.clone(),
+                role: role.clone(),
+                revoked_at: env.ledger().timestamp(),
+            }
+            .publish(env);
+            true
+        } else {
+            withdrew_pending
+        }
+    }
+
+    /// Check if an address has a specific non-expired role. Always false
+    /// while the address is suspended.
+    pub fn has_role(env: Env, address: Address, role: Role) -> bool {
+        if Self::is_suspended(env.clone(), address.clone()) {
+            return false;
+        }
+        Self::cleanup_expired_roles_internal(&env, &address);
+
+        if let Some(roles) = read_role_grants(&env, &address) {
+            for i in 0..roles.len() {
+                let grant = roles.get(i).unwrap();
+                if grant.role == role {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Attach a KYC attestation to the grant of `role` to `address`, replacing
+    /// any earlier one. Admin only; the admin is recorded as the attester.
+    ///
+    /// Fails with `RoleNotFound` unless the grant exists.
+    pub fn set_attestation(
+        env: Env,
+        admin: Address,
+        address: Address,
+        role: Role,
+        attestation_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        let held = Self::get_roles(env.clone(), address.clone())
+            .iter()
+            .any(|grant| grant.role == role);
+        if !held {
+            return Err(Error::RoleNotFound);
+        }
+
+        let key = DataKey::Attestation(address, role);
+        let attestation = Attestation {
+            attestation_hash,
+            attester: admin,
+            attested_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&key, &attestation);
+        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        Ok(())
+    }
+
+    /// Get the KYC attestation recorded for a grant, if any
+    pub fn get_attestation(env: Env, address: Address, role: Role) -> Option<Attestation> {
+        env.storage().persistent().get(&DataKey::Attestation(address, role))
+    }
+
+    /// Disable every role held by `address` without touching its grants.
+    /// Admin only; above an approval threshold of 1, propose
+    /// `ProposalAction::SuspendAddress`. Suspending an already suspended
+    /// address is a no-op.
+    pub fn suspend_address(env: Env, admin: Address, address: Address) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        Self::require_single_approval(&env)?;
+        Self::store_suspension(&env, address);
+        Ok(())
+    }
+
+    fn store_suspension(env: &Env, address: Address) {
+        let key = DataKey::Suspended(address.clone());
+        if env.storage().persistent().has(&key) {
+            return;
+        }
+        env.storage().persistent().set(&key, &true);
+        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        AddressSuspended { address, suspended_at: env.ledger().timestamp() }.publish(env);
+    }
+
+    /// Lift a suspension, restoring the address's unexpired grants. Admin
+    /// only; above an approval threshold of 1, propose
+    /// `ProposalAction::UnsuspendAddress`.
+    pub fn unsuspend_address(env: Env, admin: Address, address: Address) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        Self::require_single_approval(&env)?;
+        Self::clear_suspension(&env, address);
+        Ok(())
+    }
+
+    fn clear_suspension(env: &Env, address: Address) {
+        let key = DataKey::Suspended(address.clone());
+        if !env.storage().persistent().has(&key) {
+            return;
+        }
+        env.storage().persistent().remove(&key);
+
+        AddressUnsuspended { address, unsuspended_at: env.ledger().timestamp() }.publish(env);
+    }
+
+    /// Whether `address` is currently suspended
+    pub fn is_suspended(env: Env, address: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Suspended(address))
+    }
+
+    /// Let `operator` act for `owner` within `scopes` until `expires_at`,
+    /// replacing any earlier delegation. Must be signed by the owner.
+    ///
+    /// Fails with `InvalidInput` if `scopes` is empty or `expires_at` is not
+    /// in the future.
+    pub fn authorize_operator(
+        env: Env,
+        owner: Address,
+        operator: Address,
+        scopes: u32,
+        expires_at: Option<u64>,
+    ) -> Result<(), Error> {
+        owner.require_auth();
+        let now = env.ledger().timestamp();
+        if scopes == 0 || owner == operator || expires_at.is_some_and(|at| at <= now) {
+            return Err(Error::InvalidInput);
+        }
+
+        let key = DataKey::Operator(owner.clone(), operator.clone());
+        let grant = OperatorGrant {
+            scopes,
+            granted_at: now,
+            expires_at,
+        };
+        env.storage().persistent().set(&key, &grant);
+        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        OperatorAuthorized { owner, operator, scopes, expires_at }.publish(&env);
+        Ok(())
+    }
+
+    /// Withdraw a delegation. Must be signed by the owner; a no-op if none exists.
+    pub fn revoke_operator(env: Env, owner: Address, operator: Address) {
+        owner.require_auth();
+        let key = DataKey::Operator(owner.clone(), operator.clone());
+        if env.storage().persistent().has(&key) {
+            env.storage().persistent().remove(&key);
+            OperatorRevoked { owner, operator }.publish(&env);
+        }
+    }
+
+    /// Get the delegation from `owner` to `operator`, if any (including expired)
+    pub fn get_operator(env: Env, owner: Address, operator: Address) -> Option<OperatorGrant> {
+        env.storage().persistent().get(&DataKey::Operator(owner, operator))
+    }
+
+    /// Whether `operator` may currently act for `owner` on every bit of
+    /// `scope`. False once expired or while either address is suspended.
+    pub fn is_operator_for(env: Env, owner: Address, operator: Address, scope: u32) -> bool {
+        if scope == 0
+            || Self::is_suspended(env.clone(), owner.clone())
+            || Self::is_suspended(env.clone(), operator.clone())
+        {
+            return false;
+        }
+        match Self::get_operator(env.clone(), owner, operator) {
+            Some(grant) => {
+                let live = match grant.expires_at {
+                    Some(expires_at) => env.ledger().timestamp() < expires_at,
+                    None => true,
+                };
+                live && grant.scopes & scope == scope
+            }
+            None => false,
+        }
+    }
+
+    /// Get all role grants for an address (including expired)
+    pub fn get_roles(env: Env, address: Address) -> Vec<RoleGrant> {
+        read_role_grants(&env, &address).unwrap_or(Vec::new(&env))
+    }
+
+    /// Extend the TTL of `address`'s role grants to `ledgers` ledgers from now.
+    ///
+    /// Maintenance hook for grants that are rarely read; anyone may pay to keep
+    /// them live. Fails with `InvalidInput` if `ledgers` is zero or above the
+    /// network's maximum TTL. A no-op for addresses without grants.
+    pub fn bump_roles(env: Env, address: Address, ledgers: u32) -> Result<(), Error> {
+        if ledgers == 0 || ledgers > env.storage().max_ttl() {
+            return Err(Error::InvalidInput);
+        }
+        let key = DataKey::AddressRoles(address);
+        if env.storage().persistent().has(&key) {
+            env.storage().persistent().extend_ttl(&key, ledgers, ledgers);
+        }
+        Ok(())
+    }
+
+    /// Proactively clean up all expired roles for an address. Returns count removed.
+    pub fn cleanup_expired_roles(env: Env, address: Address) -> Result<u32, Error> {
+        let admin = Self::primary_admin(&env)?;
+        admin.require_auth();
+
+        Ok(Self::cleanup_expired_roles_internal(&env, &address))
+    }
+
+    /// Remove expired grants across every address that has ever held a role.
+    ///
+    /// Visits up to `limit` addresses (capped at `MAX_SWEEP_LIMIT`) of the
+    /// role address index starting at `cursor`, and returns the number of
+    /// grants removed and the cursor for the next call. The sweep is complete
+    /// once the returned cursor equals `get_role_address_count`. Anyone may
+    /// call this.
+    pub fn sweep_expired(env: Env, cursor: u32, limit: u32) -> (u32, u32) {
+        let count = Self::get_role_address_count(env.clone());
+        let end = cursor.saturating_add(limit.min(MAX_SWEEP_LIMIT)).min(count);
+
+        let mut removed = 0u32;
+        for i in cursor..end {
+            if let Some(address) = env.storage().persistent().get(&DataKey::RoleAddressAt(i)) {
+                removed += Self::cleanup_expired_roles_internal(&env, &address);
+            }
+        }
+        (removed, end)
+    }
+
+    /// Number of addresses in the role address index walked by `sweep_expired`
+    pub fn get_role_address_count(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RoleAddressCount)
+            .unwrap_or(0)
+    }
+
+    fn cleanup_expired_roles_internal(env: &Env, address: &Address) -> u32 {
+        let key = DataKey::AddressRoles(address.clone());
+
+        if let Some(roles) = read_role_grants(env, address) {
+            let current_time = env.ledger().timestamp();
+            let mut new_roles = Vec::new(env);
+            let mut removed_count = 0u32;
+
+            for i in 0..roles.len() {
+                let grant = roles.get(i).unwrap();
+                match grant.expires_at {
+                    Some(expires_at) if current_time >= expires_at => {
+                        Self::track_custom_role_removed(env, &grant.role);
+                        Self::remove_role_member(env, &grant.role, address);
+                        removed_count += 1;
+                        Self::record_role_history(
+                            env,
+                            address,
+                            &grant.role,
+                            RoleAction::Expired,
+                            None,
+                            Some(expires_at),
+                        );
+                        RoleExpired {
+                            address: address.clone(),
+                            role: grant.role,
+                            expires_at,
+                            removed_at: current_time,
+                        }
+                        .publish(env);
+                    }
+                    _ => new_roles.push_back(grant),
+                }
+            }
+
+            if removed_count > 0 {
+                if new_roles.is_empty() {
+                    env.storage().persistent().remove(&key);
+                } else {
+                    env.storage().persistent().set(&key, &new_roles);
+                    env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+                }
+            }
+
+            removed_count
+        } else {
+            0
+        }
+    }
+
+    /// Fold any pre-consolidation per-role entries for `address` into its
+    /// `AddressRoles` vector and delete them. Returns the number of grants
+    /// folded in. Admin only.
+    ///
+    /// Idempotent: an already-consolidated address has no legacy entries and
+    /// returns 0. If a role exists in both layouts the consolidated grant wins.
+    /// `Role::Custom` post-dates consolidation, so only built-in roles are probed.
+    pub fn migrate_legacy_roles(env: Env, address: Address) -> Result<u32, Error> {
+        let admin = Self::primary_admin(&env)?;
+        admin.require_auth();
+
+        let key = DataKey::AddressRoles(address.clone());
+        let mut roles: Vec<RoleGrant> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+
+        let legacy_roles = [
+            Role::Admin,
+            Role::BloodBank,
+            Role::Hospital,
+            Role::Donor,
+            Role::Rider,
+        ];
+
+        let mut found = 0u32;
+        let mut migrated = 0u32;
+        for role in legacy_roles.iter() {
+            let legacy_key = DataKey::LegacyRole(address.clone(), role.clone());
+            let legacy_grant: Option<RoleGrant> = env.storage().persistent().get(&legacy_key);
+            if let Some(grant) = legacy_grant {
+                found += 1;
+                let held_before = roles.len();
+                let without = Self::remove_role_from_vec(&env, roles.clone(), role);
+                if without.len() == held_before {
+                    roles = Self::insert_sorted(&env, roles, grant);
+                    Self::add_role_member(&env, role, &address);
+                    Self::index_role_address(&env, &address);
+                    migrated += 1;
+                }
+                env.storage().persistent().remove(&legacy_key);
+            }
+        }
+
+        if found > 0 {
+            env.storage().persistent().set(&key, &roles);
+            env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        }
+
+        Ok(migrated)
+    }
+
+    /// List custom role ids with at least one holder, sorted ascending.
+    ///
+    /// Returns up to `limit` ids (capped at `MAX_PAGE_SIZE`, zero meaning
+    /// `DEFAULT_PAGE_SIZE`) starting at index `start`. Expired grants that
+    /// have not yet been cleaned up still count as holders.
+    pub fn list_custom_roles(env: Env, start: u32, limit: u32) -> Vec<u32> {
+        let known: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::KnownCustomRoles)
+            .unwrap_or(Vec::new(&env));
+
+        let end = start.saturating_add(clamp_limit(limit)).min(known.len());
+        let mut page = Vec::new(&env);
+        for i in start..end {
+            page.push_back(known.get(i).unwrap());
+        }
+        page
+    }
+
+    /// Page through the addresses holding `role`, in grant order.
+    ///
+    /// Returns up to `limit` addresses starting at `offset`, with `limit`
+    /// clamped as in `list_custom_roles`. Expired grants that have not yet
+    /// been cleaned up are still listed.
+    pub fn get_role_members(env: Env, role: Role, offset: u32, limit: u32) -> Vec<Address> {
+        let members: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RoleMembers(role))
+            .unwrap_or(Vec::new(&env));
+
+        let end = offset.saturating_add(clamp_limit(limit)).min(members.len());
+        let mut page = Vec::new(&env);
+        for i in offset..end {
+            page.push_back(members.get(i).unwrap());
+        }
+        page
+    }
+
+    fn add_role_member(env: &Env, role: &Role, address: &Address) {
+        let key = DataKey::RoleMembers(role.clone());
+        let mut members: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        if members.contains(address) {
+            return;
+        }
+        members.push_back(address.clone());
+        env.storage().persistent().set(&key, &members);
+        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    }
+
+    fn remove_role_member(env: &Env, role: &Role, address: &Address) {
+        let key = DataKey::RoleMembers(role.clone());
+        let mut members: Vec<Address> = match env.storage().persistent().get(&key) {
+            Some(members) => members,
+            None => return,
+        };
+        if let Some(index) = members.first_index_of(address) {
+            members.remove(index);
+        }
+        if members.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &members);
+            env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        }
+    }
+
+    /// Append `address` to the role address index the first time it is
+    /// granted a role. Entries are never removed, so cursors stay stable.
+    fn index_role_address(env: &Env, address: &Address) {
+        let flag = DataKey::RoleAddressIndexed(address.clone());
+        if env.storage().persistent().has(&flag) {
+            return;
+        }
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RoleAddressCount)
+            .unwrap_or(0);
+        let slot = DataKey::RoleAddressAt(count);
+        env.storage().persistent().set(&slot, address);
+        env.storage().persistent().extend_ttl(&slot, TTL_THRESHOLD, TTL_EXTEND_TO);
+        env.storage().persistent().set(&flag, &true);
+        env.storage().persistent().extend_ttl(&flag, TTL_THRESHOLD, TTL_EXTEND_TO);
+        env.storage().persistent().set(&DataKey::RoleAddressCount, &(count + 1));
+        env.storage().persistent().extend_ttl(&DataKey::RoleAddressCount, TTL_THRESHOLD, TTL_EXTEND_TO);
+    }
+
+    fn track_custom_role_added(env: &Env, role: &Role) {
+        let id = match role {
+            Role::Custom(id) => *id,
+            _ => return,
+        };
+
+        let holders_key = DataKey::CustomRoleHolders(id);
+        let holders: u32 = env.storage().persistent().get(&holders_key).unwrap_or(0);
+
+        if holders == 0 {
+            let mut known: Vec<u32> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::KnownCustomRoles)
+                .unwrap_or(Vec::new(env));
+            let mut pos = known.len();
+            for i in 0..known.len() {
+                if known.get(i).unwrap() > id {
+                    pos = i;
+                    break;
+                }
+            }
+            known.insert(pos, id);
+            env.storage().persistent().set(&DataKey::KnownCustomRoles, &known);
+            env.storage()
+                .persistent()
+                .extend_ttl(&DataKey::KnownCustomRoles, TTL_THRESHOLD, TTL_EXTEND_TO);
+        }
+
+        env.storage().persistent().set(&holders_key, &(holders + 1));
+        env.storage().persistent().extend_ttl(&holders_key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    }
+
+    fn track_custom_role_removed(env: &Env, role: &Role) {
+        let id = match role {
+            Role::Custom(id) => *id,
+            _ => return,
+        };
+
+        let holders_key = DataKey::CustomRoleHolders(id);
+        let holders: u32 = env.storage().persistent().get(&holders_key).unwrap_or(0);
+
+        if holders > 1 {
+            env.storage().persistent().set(&holders_key, &(holders - 1));
+            env.storage().persistent().extend_ttl(&holders_key, TTL_THRESHOLD, TTL_EXTEND_TO);
+            return;
+        }
+
+        // Last holder gone — prune the id from the known list
+        env.storage().persistent().remove(&holders_key);
+        let known: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::KnownCustomRoles)
+            .unwrap_or(Vec::new(env));
+        let mut remaining = Vec::new(env);
+        for i in 0..known.len() {
+            let known_id = known.get(i).unwrap();
+            if known_id != id {
+                remaining.push_back(known_id);
+            }
+        }
+        if remaining.is_empty() {
+            env.storage().persistent().remove(&DataKey::KnownCustomRoles);
+        } else {
+            env.storage().persistent().set(&DataKey::KnownCustomRoles, &remaining);
+            env.storage()
+                .persistent()
+                .extend_ttl(&DataKey::KnownCustomRoles, TTL_THRESHOLD, TTL_EXTEND_TO);
+        }
+    }
+
+    fn remove_role_from_vec(env: &Env, roles: Vec<RoleGrant>, role: &Role) -> Vec<RoleGrant> {
         let mut new_roles = Vec::new(env);
         for i in 0..roles.len() {
             let grant = roles.get(i).unwrap();
@@ -1165,15 +2659,412 @@ impl AccessControlContract {
         new_roles
     }
 
-    // ── Fine-grained permission scopes (Issue #374) ──────────────────────
+    // ── Multisig admin approval ──────────────────────────────────────────
+
+    /// Require `threshold` admin approvals per sensitive admin action. Above
+    /// 1, direct grants, admin changes, threshold changes, queued operations
+    /// and upgrades by a single admin fail with `MultisigRequired` and must go
+    /// through `propose` / `approve`. Any admin may call this while the
+    /// threshold is 1; above it, propose `ProposalAction::SetApprovalThreshold`.
+    ///
+    /// Fails with `InvalidInput` unless `1 <= threshold <= admin count`.
+    pub fn set_approval_threshold(env: Env, admin: Address, threshold: u32) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        Self::require_single_approval(&env)?;
+        Self::store_approval_threshold(&env, threshold)
+    }
 
-    /// Grant a permission scope to an address. Admin only.
-    pub fn grant_scope(env: Env, address: Address, scope: PermissionScope) {
-        let admin: Address = env
+    fn store_approval_threshold(env: &Env, threshold: u32) -> Result<(), Error> {
+        if threshold == 0 || threshold > Self::load_admins(env).len() {
+            return Err(Error::InvalidInput);
+        }
+        env.storage().persistent().set(&DataKey::ApprovalThreshold, &threshold);
+        env.storage().persistent().extend_ttl(&DataKey::ApprovalThreshold, TTL_THRESHOLD, TTL_EXTEND_TO);
+        Ok(())
+    }
+
+    /// Get the number of admin approvals an action needs (1 by default). Never
+    /// more than the current admin count, so removing admins cannot strand
+    /// proposals.
+    pub fn get_approval_threshold(env: Env) -> u32 {
+        let threshold: u32 = env
             .storage()
             .persistent()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .get(&DataKey::ApprovalThreshold)
+            .unwrap_or(1);
+        threshold.min(Self::load_admins(&env).len()).max(1)
+    }
+
+    /// Propose `action`. The proposer's approval counts towards the
+    /// threshold; with a threshold of 1 the action applies at once. Returns
+    /// the proposal id. Any admin may call this.
+    ///
+    /// The action is validated up front: grants are checked and counted
+    /// against the rate limit as for a direct grant, and `Admin` and
+    /// `BloodBank` grants fail with `TimelockRequired` while a timelock delay
+    /// is configured (propose `QueueOperation` instead).
+    pub fn propose(env: Env, admin: Address, action: ProposalAction) -> Result<u64, Error> {
+        Self::require_admin(&env, &admin)?;
+        match &action {
+            ProposalAction::GrantRole(address, role, _) => {
+                Self::require_defined_role(&env, role)?;
+                Self::require_role_constraints(&env, address, role)?;
+                Self::require_not_timelocked(&env, role)?;
+                Self::consume_grant_allowance(&env, &admin)?;
+            }
+            ProposalAction::RemoveAdmin(target) => {
+                if !Self::load_admins(&env).contains(target) {
+                    return Err(Error::InvalidInput);
+                }
+            }
+            ProposalAction::SetApprovalThreshold(threshold) => {
+                if *threshold == 0 || *threshold > Self::load_admins(&env).len() {
+                    return Err(Error::InvalidInput);
+                }
+            }
+            ProposalAction::QueueOperation(operation) => {
+                Self::validate_operation(&env, operation)?;
+            }
+            ProposalAction::AddAdmin(_) | ProposalAction::ProposeAdmin(_) => {
+                Self::require_admins_unlocked(&env)?
+            }
+            ProposalAction::SetRolePrerequisites(role, prerequisites) => {
+                if prerequisites.contains(role) {
+                    return Err(Error::InvalidInput);
+                }
+            }
+            ProposalAction::SetMutuallyExclusive(role_a, role_b, _) => {
+                if role_a == role_b {
+                    return Err(Error::InvalidInput);
+                }
+            }
+            ProposalAction::Upgrade(_)
+            | ProposalAction::DefineCustomRole(..)
+            | ProposalAction::SetRoleAdmin(..)
+            | ProposalAction::SetRoleRequiresAcceptance(..)
+            | ProposalAction::SetGrantRateLimit(..)
+            | ProposalAction::SuspendAddress(_)
+            | ProposalAction::UnsuspendAddress(_) => {}
+        }
+
+        let id: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ProposalCounter)
+            .unwrap_or(0)
+            + 1;
+        env.storage().persistent().set(&DataKey::ProposalCounter, &id);
+        env.storage().persistent().extend_ttl(&DataKey::ProposalCounter, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        ActionProposed {
+            id,
+            proposer: admin.clone(),
+            action: action.clone(),
+        }
+        .publish(&env);
+
+        let proposal = Proposal {
+            id,
+            action,
+            proposer: admin.clone(),
+            approvals: Vec::from_array(&env, [admin]),
+        };
+        Self::settle_proposal(&env, proposal)?;
+        Ok(id)
+    }
+
+    /// Propose granting `role` to `address`; shorthand for `propose` with
+    /// `ProposalAction::GrantRole`.
+    pub fn propose_grant(
+        env: Env,
+        admin: Address,
+        address: Address,
+        role: Role,
+        expires_at: Option<u64>,
+    ) -> Result<u64, Error> {
+        Self::propose(env, admin, ProposalAction::GrantRole(address, role, expires_at))
+    }
+
+    /// Approve a pending proposal. Returns true if this approval met the
+    /// threshold and the action was applied. Any admin may call this.
+    ///
+    /// Fails with `ProposalNotFound` for unknown or already executed ids,
+    /// `AlreadyApproved` if `admin` has approved before and `TimelockRequired`
+    /// if a timelock was configured after an `Admin` or `BloodBank` grant was
+    /// proposed. An action that has become invalid since it was proposed
+    /// (e.g. removing an admin who is already gone) fails with the same error
+    /// as the direct call.
+    pub fn approve(env: Env, admin: Address, proposal_id: u64) -> Result<bool, Error> {
+        Self::require_admin(&env, &admin)?;
+        let mut proposal: Proposal = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Proposal(proposal_id))
+            .ok_or(Error::ProposalNotFound)?;
+        if proposal.approvals.contains(&admin) {
+            return Err(Error::AlreadyApproved);
+        }
+        proposal.approvals.push_back(admin.clone());
+
+        ProposalApproved {
+            id: proposal_id,
+            approver: admin,
+            approvals: proposal.approvals.len(),
+        }
+        .publish(&env);
+
+        Self::settle_proposal(&env, proposal)
+    }
+
+    /// Get a proposal still waiting for approvals
+    pub fn get_proposal(env: Env, proposal_id: u64) -> Option<Proposal> {
+        env.storage().persistent().get(&DataKey::Proposal(proposal_id))
+    }
+
+    /// Apply the proposal if it has enough approvals, otherwise store it.
+    /// Returns whether the action was applied.
+    fn settle_proposal(env: &Env, proposal: Proposal) -> Result<bool, Error> {
+        let key = DataKey::Proposal(proposal.id);
+        if proposal.approvals.len() < Self::get_approval_threshold(env.clone()) {
+            env.storage().persistent().set(&key, &proposal);
+            env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+            return Ok(false);
+        }
+
+        env.storage().persistent().remove(&key);
+        match proposal.action {
+            ProposalAction::GrantRole(address, role, expires_at) => {
+                Self::require_not_timelocked(env, &role)?;
+                Self::grant_or_propose(env, &proposal.proposer, &address, &role, expires_at);
+            }
+            ProposalAction::AddAdmin(new_admin) => {
+                Self::require_admins_unlocked(env)?;
+                Self::store_admin(env, new_admin);
+            }
+            ProposalAction::RemoveAdmin(admin) => {
+                Self::unstore_admin(env, &admin)?;
+            }
+            ProposalAction::SetApprovalThreshold(threshold) => {
+                Self::store_approval_threshold(env, threshold)?;
+            }
+            ProposalAction::QueueOperation(operation) => {
+                Self::enqueue_operation(env, proposal.proposer, operation)?;
+            }
+            ProposalAction::Upgrade(new_wasm_hash) => {
+                Self::apply_upgrade(env, proposal.proposer, new_wasm_hash);
+            }
+            ProposalAction::ProposeAdmin(new_admin) => {
+                Self::nominate_admin(env, new_admin)?;
+            }
+            ProposalAction::DefineCustomRole(id, name, description_hash) => {
+                Self::store_custom_role(env, id, name, description_hash);
+            }
+            ProposalAction::SetRolePrerequisites(role, prerequisites) => {
+                Self::store_role_prerequisites(env, role, prerequisites)?;
+            }
+            ProposalAction::SetMutuallyExclusive(role_a, role_b, exclusive) => {
+                Self::store_exclusion(env, &role_a, &role_b, exclusive)?;
+            }
+            ProposalAction::SetRoleAdmin(role, admin_role) => {
+                Self::store_role_admin(env, role, admin_role);
+            }
+            ProposalAction::SetRoleRequiresAcceptance(role, required) => {
+                Self::store_requires_acceptance(env, role, required);
+            }
+            ProposalAction::SetGrantRateLimit(max_per_window, window_seconds) => {
+                Self::store_grant_rate_limit(env, max_per_window, window_seconds);
+            }
+            ProposalAction::SuspendAddress(address) => {
+                Self::store_suspension(env, address);
+            }
+            ProposalAction::UnsuspendAddress(address) => {
+                Self::clear_suspension(env, address);
+            }
+        }
+        Ok(true)
+    }
+
+    fn require_single_approval(env: &Env) -> Result<(), Error> {
+        if Self::get_approval_threshold(env.clone()) > 1 {
+            return Err(Error::MultisigRequired);
+        }
+        Ok(())
+    }
+
+    // ── Timelocked operations ─────────────────────────────────────────────
+
+    /// Set how long sensitive operations wait between `queue_operation` and
+    /// `execute_operation`. While non-zero, `Admin` and `BloodBank` grants
+    /// and additions to the admin set cannot be made directly and fail with
+    /// `TimelockRequired`. Zero disables the timelock. Any admin may call
+    /// this while the timelock is off and the approval threshold is 1.
+    ///
+    /// Once a delay is set, changing it (including back to zero) must itself
+    /// be queued as `TimelockOperation::SetTimelockDelay`, and direct calls
+    /// fail with `TimelockRequired`.
+    pub fn set_timelock_delay(env: Env, admin: Address, delay_secs: u64) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        Self::require_single_approval(&env)?;
+        if env.storage().persistent().has(&DataKey::TimelockDelay) {
+            return Err(Error::TimelockRequired);
+        }
+        Self::store_timelock_delay(&env, delay_secs);
+        Ok(())
+    }
+
+    fn store_timelock_delay(env: &Env, delay_secs: u64) {
+        if delay_secs == 0 {
+            env.storage().persistent().remove(&DataKey::TimelockDelay);
+        } else {
+            env.storage().persistent().set(&DataKey::TimelockDelay, &delay_secs);
+            env.storage().persistent().extend_ttl(&DataKey::TimelockDelay, TTL_THRESHOLD, TTL_EXTEND_TO);
+        }
+    }
+
+    /// Get the timelock delay in seconds (0 when disabled)
+    pub fn get_timelock_delay(env: Env) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TimelockDelay)
+            .unwrap_or(0)
+    }
+
+    /// Queue `operation` to become executable after the timelock delay.
+    /// Returns the operation id. Any admin may call this while the approval
+    /// threshold is 1; above it, propose `ProposalAction::QueueOperation`.
+    ///
+    /// Grants are validated and counted against the rate limit when queued.
+    pub fn queue_operation(
+        env: Env,
+        admin: Address,
+        operation: TimelockOperation,
+    ) -> Result<u64, Error> {
+        Self::require_admin(&env, &admin)?;
+        Self::require_single_approval(&env)?;
+        Self::enqueue_operation(&env, admin, operation)
+    }
+
+    fn validate_operation(env: &Env, operation: &TimelockOperation) -> Result<(), Error> {
+        match operation {
+            TimelockOperation::GrantRole(address, role, _) => {
+                Self::require_defined_role(env, role)?;
+                Self::require_role_constraints(env, address, role)
+            }
+            TimelockOperation::SetTimelockDelay(_) | TimelockOperation::AddAdmin(_) => Ok(()),
+        }
+    }
+
+    fn enqueue_operation(
+        env: &Env,
+        queued_by: Address,
+        operation: TimelockOperation,
+    ) -> Result<u64, Error> {
+        Self::validate_operation(env, &operation)?;
+        if matches!(operation, TimelockOperation::GrantRole(..)) {
+            Self::consume_grant_allowance(env, &queued_by)?;
+        }
+
+        let id: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OperationCounter)
+            .unwrap_or(0)
+            + 1;
+        env.storage().persistent().set(&DataKey::OperationCounter, &id);
+        env.storage().persistent().extend_ttl(&DataKey::OperationCounter, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        let executable_at = env.ledger().timestamp() + Self::get_timelock_delay(env.clone());
+        let queued = QueuedOperation {
+            id,
+            operation: operation.clone(),
+            queued_by,
+            executable_at,
+        };
+        let key = DataKey::QueuedOperation(id);
+        env.storage().persistent().set(&key, &queued);
+        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        OperationQueued { id, operation, executable_at }.publish(env);
+        Ok(id)
+    }
+
+    /// Execute a queued operation once its delay has passed. Any admin may
+    /// call this: the approvals were collected when the operation was queued.
+    ///
+    /// Fails with `OperationNotFound` for unknown, executed or cancelled ids
+    /// and `OperationNotReady` before `executable_at`.
+    pub fn execute_operation(env: Env, admin: Address, id: u64) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        let key = DataKey::QueuedOperation(id);
+        let queued: QueuedOperation = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::OperationNotFound)?;
+        if env.ledger().timestamp() < queued.executable_at {
+            return Err(Error::OperationNotReady);
+        }
+        env.storage().persistent().remove(&key);
+
+        match queued.operation {
+            TimelockOperation::GrantRole(address, role, expires_at) => {
+                Self::grant_or_propose(&env, &queued.queued_by, &address, &role, expires_at);
+            }
+            TimelockOperation::SetTimelockDelay(delay_secs) => {
+                Self::store_timelock_delay(&env, delay_secs);
+            }
+            TimelockOperation::AddAdmin(new_admin) => {
+                Self::store_admin(&env, new_admin);
+            }
+        }
+
+        OperationExecuted { id, executed_by: admin }.publish(&env);
+        Ok(())
+    }
+
+    /// Cancel a queued operation before it is executed. Any admin may call this.
+    pub fn cancel_operation(env: Env, admin: Address, id: u64) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        let key = DataKey::QueuedOperation(id);
+        if !env.storage().persistent().has(&key) {
+            return Err(Error::OperationNotFound);
+        }
+        env.storage().persistent().remove(&key);
+
+        OperationCancelled { id, cancelled_by: admin }.publish(&env);
+        Ok(())
+    }
+
+    /// Get a queued operation that has not been executed or cancelled
+    pub fn get_queued_operation(env: Env, id: u64) -> Option<QueuedOperation> {
+        env.storage().persistent().get(&DataKey::QueuedOperation(id))
+    }
+
+    /// `Admin` and `BloodBank` grants must go through the queue while a
+    /// timelock delay is configured.
+    fn require_not_timelocked(env: &Env, role: &Role) -> Result<(), Error> {
+        let sensitive = matches!(role, Role::Admin | Role::BloodBank);
+        if sensitive && env.storage().persistent().has(&DataKey::TimelockDelay) {
+            return Err(Error::TimelockRequired);
+        }
+        Ok(())
+    }
+
+    /// Admin power comes from the admin set rather than `Role::Admin`, so
+    /// adding to it must go through the queue while a timelock delay is
+    /// configured.
+    fn require_admins_unlocked(env: &Env) -> Result<(), Error> {
+        if env.storage().persistent().has(&DataKey::TimelockDelay) {
+            return Err(Error::TimelockRequired);
+        }
+        Ok(())
+    }
+
+    // ── Fine-grained permission scopes (Issue #374) ──────────────────────
+
+    /// Grant a permission scope to an address. Admin only.
+    pub fn grant_scope(env: Env, address: Address, scope: PermissionScope) -> Result<(), Error> {
+        let admin = Self::primary_admin(&env)?;
         admin.require_auth();
 
         let key = DataKey::AddressScopes(address.clone());
@@ -1186,21 +3077,18 @@ impl AccessControlContract {
         // Deduplicate
         for i in 0..scopes.len() {
             if scopes.get(i).unwrap() == scope {
-                return;
+                return Ok(());
             }
         }
         scopes.push_back(scope);
         env.storage().persistent().set(&key, &scopes);
         env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        Ok(())
     }
 
     /// Revoke a permission scope from an address. Admin only.
-    pub fn revoke_scope(env: Env, address: Address, scope: PermissionScope) {
-        let admin: Address = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
+    pub fn revoke_scope(env: Env, address: Address, scope: PermissionScope) -> Result<(), Error> {
+        let admin = Self::primary_admin(&env)?;
         admin.require_auth();
 
         let key = DataKey::AddressScopes(address.clone());
@@ -1219,6 +3107,7 @@ impl AccessControlContract {
             env.storage().persistent().set(&key, &new_scopes);
             env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
         }
+        Ok(())
     }
 
     /// Check whether an address holds a specific permission scope.
@@ -35,14 +35,17 @@ pub enum ContractError {
     // Blood-specific errors (140-149)
     BloodUnitNotAvailable = 140,
     InvalidStatusTransition = 141,
+    InsufficientStock = 142,
+    StockOutOfRange = 143,
 
     // Reservation errors (150-159)
     ReservationNotFound = 150,
     ReservationExpired = 151,
     NotReservationOwner = 152,
 
-    // Circuit breaker (160)
+    // Circuit breaker (160-169)
     ContractPaused = 160,
+    InventoryFrozen = 161,
 
     // Role-based access control (170-179)
     InvalidRole = 170,
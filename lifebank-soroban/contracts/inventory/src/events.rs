@@ -1,5 +1,9 @@
-use crate::types::{AuditEvent, BloodRegisteredEvent, BloodStatus, BloodType, StatusChangeEvent};
-use soroban_sdk::{contractevent, Address, Env, String};
+use crate::types::{
+    AuditEvent, BloodRegisteredEvent, BloodStatus, BloodType, Location, ReconciliationReport,
+    ReconciliationStatus, StatusChangeEvent, StockAdjustment, StockSeverity, StockTransfer,
+    StockTransferStatus,
+};
+use soroban_sdk::{contractevent, Address, BytesN, Env, String, Vec};
 
 #[contractevent(topics = ["invalid_transition"], data_format = "vec")]
 pub struct InvalidTransition {
@@ -20,6 +24,84 @@ pub struct ReservationReleased {
     pub reservation_id: u64,
 }
 
+#[contractevent(topics = ["stock_low"])]
+pub struct StockLow {
+    pub bank_id: Address,
+    pub blood_type: BloodType,
+    pub available: u32,
+    pub par_level: u32,
+    pub severity: StockSeverity,
+}
+
+#[contractevent(topics = ["stock_reserved"], data_format = "vec")]
+pub struct StockReserved {
+    pub reservation_id: u64,
+    pub bank_id: Address,
+    pub blood_type: BloodType,
+    pub quantity: u32,
+    pub request_id: u64,
+}
+
+#[contractevent(topics = ["stock_res_released"], data_format = "vec")]
+pub struct StockReservationReleased {
+    pub reservation_id: u64,
+    /// Released by the keeper after `expires_at` rather than by a caller
+    pub expired: bool,
+}
+
+#[contractevent(topics = ["stock_res_consumed"], data_format = "single-value")]
+pub struct StockReservationConsumed {
+    pub reservation_id: u64,
+}
+
+#[contractevent(topics = ["location_registered"], data_format = "vec")]
+pub struct LocationRegistered {
+    pub bank_id: Address,
+    pub location_id: u32,
+    pub name_hash: BytesN<32>,
+}
+
+#[contractevent(topics = ["stock_transfer"], data_format = "vec")]
+pub struct StockTransferUpdated {
+    pub transfer_id: u64,
+    pub from_bank: Address,
+    pub to_bank: Address,
+    pub blood_type: BloodType,
+    pub quantity: u32,
+    pub status: StockTransferStatus,
+}
+
+#[contractevent(topics = ["inventory_frozen"], data_format = "vec")]
+pub struct InventoryFrozen {
+    pub bank_id: Address,
+    pub until: u64,
+    pub frozen_by: Address,
+}
+
+#[contractevent(topics = ["inventory_unfrozen"], data_format = "vec")]
+pub struct InventoryUnfrozen {
+    pub bank_id: Address,
+    /// `None` when the freeze lapsed at its deadline
+    pub unfrozen_by: Option<Address>,
+}
+
+#[contractevent(topics = ["stock_adjusted"])]
+pub struct StockAdjusted {
+    pub bank_id: Address,
+    pub units_added: u32,
+    pub units_removed: u32,
+    /// Entries as submitted, with their reason codes
+    pub adjustments: Vec<StockAdjustment>,
+}
+
+#[contractevent(topics = ["reconciliation"], data_format = "vec")]
+pub struct ReconciliationResolved {
+    pub reconciliation_id: u64,
+    pub bank_id: Address,
+    pub status: ReconciliationStatus,
+    pub total_discrepancy: u32,
+}
+
 pub fn emit_blood_registered(
     env: &Env,
     blood_unit_id: u64,
@@ -96,3 +178,111 @@ pub fn emit_blood_reserved(env: &Env, reservation_id: u64, requester: &Address,
 pub fn emit_reservation_released(env: &Env, reservation_id: u64) {
     ReservationReleased { reservation_id }.publish(env);
 }
+
+pub fn emit_stock_low(
+    env: &Env,
+    bank_id: &Address,
+    blood_type: BloodType,
+    available: u32,
+    par_level: u32,
+    severity: StockSeverity,
+) {
+    StockLow {
+        bank_id: bank_id.clone(),
+        blood_type,
+        available,
+        par_level,
+        severity,
+    }
+    .publish(env);
+}
+
+pub fn emit_stock_reserved(env: &Env, reservation: &crate::types::StockReservation) {
+    StockReserved {
+        reservation_id: reservation.id,
+        bank_id: reservation.bank_id.clone(),
+        blood_type: reservation.blood_type,
+        quantity: reservation.quantity,
+        request_id: reservation.request_id,
+    }
+    .publish(env);
+}
+
+pub fn emit_stock_reservation_released(env: &Env, reservation_id: u64, expired: bool) {
+    StockReservationReleased {
+        reservation_id,
+        expired,
+    }
+    .publish(env);
+}
+
+pub fn emit_stock_reservation_consumed(env: &Env, reservation_id: u64) {
+    StockReservationConsumed { reservation_id }.publish(env);
+}
+
+pub fn emit_location_registered(env: &Env, location: &Location) {
+    LocationRegistered {
+        bank_id: location.bank_id.clone(),
+        location_id: location.location_id,
+        name_hash: location.name_hash.clone(),
+    }
+    .publish(env);
+}
+
+/// Emitted when a stock transfer is initiated, acknowledged or cancelled.
+pub fn emit_stock_transfer(env: &Env, transfer: &StockTransfer) {
+    StockTransferUpdated {
+        transfer_id: transfer.id,
+        from_bank: transfer.from_bank.clone(),
+        to_bank: transfer.to_bank.clone(),
+        blood_type: transfer.blood_type,
+        quantity: transfer.quantity,
+        status: transfer.status,
+    }
+    .publish(env);
+}
+
+pub fn emit_inventory_frozen(env: &Env, bank_id: &Address, until: u64, frozen_by: &Address) {
+    InventoryFrozen {
+        bank_id: bank_id.clone(),
+        until,
+        frozen_by: frozen_by.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_inventory_unfrozen(env: &Env, bank_id: &Address, unfrozen_by: Option<Address>) {
+    InventoryUnfrozen {
+        bank_id: bank_id.clone(),
+        unfrozen_by,
+    }
+    .publish(env);
+}
+
+/// One event per `adjust_stock_batch` call, summarising all of its entries.
+pub fn emit_stock_adjusted(
+    env: &Env,
+    bank_id: &Address,
+    units_added: u32,
+    units_removed: u32,
+    adjustments: &Vec<StockAdjustment>,
+) {
+    StockAdjusted {
+        bank_id: bank_id.clone(),
+        units_added,
+        units_removed,
+        adjustments: adjustments.clone(),
+    }
+    .publish(env);
+}
+
+/// Emitted on submission and again when a pending report is approved or rejected.
+pub fn emit_reconciliation(env: &Env, report: &ReconciliationReport) {
+    ReconciliationResolved {
+        reconciliation_id: report.id,
+        bank_id: report.bank_id.clone(),
+        status: report.status,
+        total_discrepancy: report.total_discrepancy,
+    }
+    .publish(env);
+}
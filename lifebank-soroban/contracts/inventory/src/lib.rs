@@ -3,14 +3,22 @@
 
 mod error;
 mod events;
+mod stock;
 mod storage;
 mod types;
 mod validation;
+mod wastage;
 
 use crate::error::ContractError;
-use crate::types::{is_valid_transition, BloodStatus, BloodType, BloodUnit, DataKey, Reservation, Role};
+use crate::types::{
+    is_valid_transition, AdjustmentReason, BloodStatus, BloodType, BloodUnit, DataKey,
+    ExpiryBuckets, Location, ReconciliationLine, ReconciliationReport, ReconciliationStatus,
+    Reservation, Role, StockAdjustment, StockReservation, StockTransfer, StockTransferStatus,
+    WastageReport,
+};
 
-use soroban_sdk::{contract, contractimpl, Address, Env, Map, String, Vec};
+use lifebank_auth::require_role;
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Map, String, Vec};
 
 /// Cross-contract client for the authoritative HealthChainContract (BloodUnitRegistry).
 ///
@@ -70,12 +78,45 @@ mod registry_client {
     }
 }
 
+/// Cross-contract client for the requests contract, used to open restock
+/// requests when stock falls below par.
+mod requests_client {
+    use crate::types::{BloodType, RestockUrgency};
+    use soroban_sdk::{vec, Address, Env, IntoVal, InvokeError, Symbol, Val, Vec};
+
+    /// Open a restock request for `bank`. Returns the request ID, or `None` if
+    /// the requests contract rejected the call.
+    pub fn create_restock_request(
+        env: &Env,
+        requests_id: &Address,
+        bank: &Address,
+        blood_type: BloodType,
+        deficit_units: u32,
+        urgency: RestockUrgency,
+    ) -> Option<u64> {
+        let func = Symbol::new(env, "create_restock_request");
+        let args: Vec<Val> = vec![
+            &env,
+            bank.clone().into_val(env),
+            blood_type.into_val(env),
+            deficit_units.into_val(env),
+            urgency.into_val(env),
+        ];
+        match env.try_invoke_contract::<u64, InvokeError>(requests_id, &func, args) {
+            Ok(Ok(request_id)) => Some(request_id),
+            _ => None,
+        }
+    }
+}
+
 #[contract]
 pub struct InventoryContract;
 
 #[contractimpl]
 impl InventoryContract {
     const MAX_RESERVATION_DURATION_SECS: u64 = 86_400 * 7;
+    const MAX_ADJUSTMENT_BATCH: u32 = 100;
+    const MAX_LOCATIONS_PER_BANK: u32 = 20;
     const CONTRACT_VERSION: u32 = 1;
 
     /// Initialize the inventory contract
@@ -138,6 +179,50 @@ impl InventoryContract {
             .unwrap_or(false)
     }
 
+    /// Freeze `bank`'s inventory until `until`, e.g. during a regulatory
+    /// audit. While frozen every stock mutation for the bank fails with
+    /// `InventoryFrozen`; the freeze lifts by itself at `until`. Callable by
+    /// the admin or a `Regulator`.
+    ///
+    /// # Errors
+    /// - `InsufficientRolePermission`: caller is neither admin nor regulator
+    /// - `InvalidTimestamp`: `until` is not in the future
+    pub fn freeze_inventory(
+        env: Env,
+        caller: Address,
+        bank: Address,
+        until: u64,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        Self::require_auditor(&env, &caller)?;
+        if until <= env.ledger().timestamp() {
+            return Err(ContractError::InvalidTimestamp);
+        }
+
+        storage::set_frozen_until(&env, &bank, until);
+        events::emit_inventory_frozen(&env, &bank, until, &caller);
+        Ok(())
+    }
+
+    /// Lift a freeze before its deadline. Callable by the admin or a
+    /// `Regulator`.
+    pub fn unfreeze_inventory(env: Env, caller: Address, bank: Address) -> Result<(), ContractError> {
+        caller.require_auth();
+        Self::require_auditor(&env, &caller)?;
+        if storage::get_frozen_until(&env, &bank).is_none() {
+            return Err(ContractError::NotFound);
+        }
+
+        storage::remove_frozen_until(&env, &bank);
+        events::emit_inventory_unfrozen(&env, &bank, Some(caller));
+        Ok(())
+    }
+
+    /// Whether `bank`'s inventory is currently frozen.
+    pub fn is_inventory_frozen(env: Env, bank: Address) -> bool {
+        Self::is_frozen(&env, &bank)
+    }
+
     /// Authorize or revoke a blood bank. Only admin can call this.
     pub fn authorize_bank(env: Env, admin: Address, bank: Address, authorized: bool) -> Result<(), ContractError> {
         admin.require_auth();
@@ -180,6 +265,14 @@ impl InventoryContract {
         }
     }
 
+    /// Only the admin or a `Regulator` may freeze and unfreeze inventory.
+    fn require_auditor(env: &Env, caller: &Address) -> Result<(), ContractError> {
+        match Self::get_role(env, caller) {
+            Role::Admin | Role::Regulator => Ok(()),
+            _ => Err(ContractError::InsufficientRolePermission),
+        }
+    }
+
     /// Validate that a role can transition a blood unit to the new status.
     fn assert_can_transition(role: &Role, new_status: &BloodStatus) -> Result<(), ContractError> {
         match role {
@@ -199,7 +292,51 @@ impl InventoryContract {
                 }
             }
             Role::BloodBank => Ok(()),
+            Role::Regulator => Err(ContractError::InsufficientRolePermission),
+        }
+    }
+
+    /// Require `bank` to be a blood bank: via `Role::BloodBank` in the identity
+    /// contract when one is configured, otherwise via `authorize_bank`.
+    fn require_blood_bank(env: &Env, bank: &Address) -> Result<(), ContractError> {
+        let identity: Option<Address> = env.storage().instance().get(&DataKey::IdentityContractId);
+        let authorized = match identity {
+            Some(identity) => {
+                require_role(env, &identity, bank, lifebank_auth::Role::BloodBank).is_ok()
+            }
+            None => storage::is_authorized_bank(env, bank),
+        };
+        if !authorized {
+            return Err(ContractError::NotAuthorizedBloodBank);
+        }
+        Ok(())
+    }
+
+    fn require_location(env: &Env, bank: &Address, location_id: u32) -> Result<(), ContractError> {
+        if !stock::location_exists(env, bank, location_id) {
+            return Err(ContractError::NotFound);
         }
+        Ok(())
+    }
+
+    /// Whether `bank`'s inventory is frozen at the current ledger time.
+    fn is_frozen(env: &Env, bank: &Address) -> bool {
+        storage::get_frozen_until(env, bank)
+            .is_some_and(|until| env.ledger().timestamp() < until)
+    }
+
+    /// Fail with `InventoryFrozen` while `bank` is frozen. A freeze whose
+    /// deadline has passed is cleared here and `inventory_unfrozen` emitted.
+    fn require_not_frozen(env: &Env, bank: &Address) -> Result<(), ContractError> {
+        let Some(until) = storage::get_frozen_until(env, bank) else {
+            return Ok(());
+        };
+        if env.ledger().timestamp() < until {
+            return Err(ContractError::InventoryFrozen);
+        }
+        storage::remove_frozen_until(env, bank);
+        events::emit_inventory_unfrozen(env, bank, None);
+        Ok(())
     }
 
     fn require_not_paused(env: &Env) -> Result<(), ContractError> {
@@ -262,6 +399,7 @@ impl InventoryContract {
         donor_id: Option<Address>,
     ) -> Result<u64, ContractError> {
         Self::require_not_paused(&env)?;
+        Self::require_not_frozen(&env, &bank_id)?;
 
         // Check contract is initialized
         if !env.storage().instance().has(&DataKey::Admin) {
@@ -269,9 +407,7 @@ impl InventoryContract {
         }
 
         // Verify bank is authorized
-        if !storage::is_authorized_bank(&env, &bank_id) {
-            return Err(ContractError::NotAuthorizedBloodBank);
-        }
+        Self::require_blood_bank(&env, &bank_id)?;
 
         // 4. Validate blood type
         validation::validate_blood_type(blood_type)?;
@@ -357,6 +493,8 @@ impl InventoryContract {
         storage::add_to_bank_index(&env, &blood_unit);
         storage::add_to_status_index(&env, &blood_unit);
         storage::add_to_donor_index(&env, &blood_unit);
+        stock::on_register(&env, &blood_unit);
+        wastage::on_register(&env, &blood_unit);
 
         // 11. Emit event
         // Emit event
@@ -401,6 +539,7 @@ impl InventoryContract {
 
         let blood_unit =
             storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+        Self::require_not_frozen(&env, &blood_unit.bank_id)?;
 
         let role = Self::get_role(&env, &authorized_by);
         Self::assert_can_transition(&role, &new_status)?;
@@ -442,6 +581,8 @@ impl InventoryContract {
         // Keep status index consistent: remove from old bucket, add to new bucket.
         storage::remove_from_status_index(&env, unit_id, old_status);
         storage::add_to_status_index(&env, &blood_unit);
+        stock::on_status_change(&env, &blood_unit, old_status);
+        wastage::on_status_change(&env, &blood_unit, old_status);
 
         storage::record_status_change(
             &env,
@@ -540,6 +681,7 @@ impl InventoryContract {
             if authorized_by != admin && authorized_by != blood_unit.bank_id {
                 return Err(ContractError::Unauthorized);
             }
+            Self::require_not_frozen(&env, &blood_unit.bank_id)?;
 
             if blood_unit.is_expired(current_time) {
                 let allowed_past_shelf = matches!(
@@ -570,6 +712,8 @@ impl InventoryContract {
 
             storage::remove_from_status_index(&env, unit_id, old_status);
             storage::add_to_status_index(&env, &blood_unit);
+            stock::on_status_change(&env, &blood_unit, old_status);
+            wastage::on_status_change(&env, &blood_unit, old_status);
 
             storage::record_status_change(
                 &env,
@@ -630,9 +774,7 @@ impl InventoryContract {
         if !env.storage().instance().has(&DataKey::Admin) {
             return Err(ContractError::NotInitialized);
         }
-        if !storage::is_authorized_bank(&env, &bank_id) {
-            return Err(ContractError::NotAuthorizedBloodBank);
-        }
+        Self::require_blood_bank(&env, &bank_id)?;
 
         for i in 0..entries.len() {
             let (_, _, quantity_ml, _) = entries.get(i).unwrap();
@@ -691,9 +833,8 @@ impl InventoryContract {
 
         Self::require_not_paused(&env)?;
 
-        if !storage::is_authorized_bank(&env, &requester) {
-            return Err(ContractError::NotAuthorizedBloodBank);
-        }
+        Self::require_blood_bank(&env, &requester)?;
+        Self::require_not_frozen(&env, &requester)?;
 
         let current_time = env.ledger().timestamp();
 
@@ -759,6 +900,7 @@ impl InventoryContract {
             storage::set_blood_unit(&env, &unit);
             storage::remove_from_status_index(&env, unit_id, old_status);
             storage::add_to_status_index(&env, &unit);
+            stock::on_status_change(&env, &unit, old_status);
         }
 
         // ── Phase 2: sync each unit's status to the authoritative registry ────
@@ -813,11 +955,13 @@ impl InventoryContract {
         for i in 0..reservation.unit_ids.len() {
             let unit_id = reservation.unit_ids.get(i).ok_or(ContractError::NotFound)?;
             if let Some(mut unit) = storage::get_blood_unit(&env, unit_id) {
+                Self::require_not_frozen(&env, &unit.bank_id)?;
                 if unit.status == BloodStatus::Reserved {
                     unit.status = BloodStatus::Available;
                     storage::set_blood_unit(&env, &unit);
                     storage::remove_from_status_index(&env, unit_id, BloodStatus::Reserved);
                     storage::add_to_status_index(&env, &unit);
+                    stock::on_status_change(&env, &unit, BloodStatus::Reserved);
                     storage::record_status_change(
                         &env,
                         unit_id,
@@ -867,9 +1011,7 @@ impl InventoryContract {
 
         Self::require_not_paused(&env)?;
 
-        if !storage::is_authorized_bank(&env, &requester) {
-            return Err(ContractError::NotAuthorizedBloodBank);
-        }
+        Self::require_blood_bank(&env, &requester)?;
 
         let mut reservation_ids: Vec<u64> = Vec::new(&env);
 
@@ -890,6 +1032,791 @@ impl InventoryContract {
         Ok(reservation_ids)
     }
 
+    /// Set the minimum number of available units `bank` wants to keep of
+    /// `blood_type`. A `min_units` of zero removes the par level.
+    ///
+    /// Whenever available stock drops below the par level a `stock_low` event is
+    /// emitted with severity `Low`, or `Critical` once stock is at or below half
+    /// of par.
+    ///
+    /// # Errors
+    /// - `NotAuthorizedBloodBank`: `bank` is not a blood bank
+    pub fn set_par_level(
+        env: Env,
+        bank: Address,
+        blood_type: BloodType,
+        min_units: u32,
+    ) -> Result<(), ContractError> {
+        bank.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_blood_bank(&env, &bank)?;
+        validation::validate_blood_type(blood_type)?;
+
+        stock::set_par_level(&env, &bank, blood_type, min_units);
+        Ok(())
+    }
+
+    /// Get a bank's par level for a blood type (0 when none is set).
+    pub fn get_par_level(env: Env, bank: Address, blood_type: BloodType) -> u32 {
+        stock::get_par_level(&env, &bank, blood_type)
+    }
+
+    /// Units of `blood_type` held by `bank` across all its locations and not
+    /// promised to a stock reservation (on hand − reserved).
+    pub fn get_available_stock(env: Env, bank: Address, blood_type: BloodType) -> u32 {
+        stock::get_available(&env, &bank, blood_type)
+    }
+
+    /// Units of `blood_type` held by `bank` across all its locations, reserved
+    /// or not.
+    pub fn get_on_hand_stock(env: Env, bank: Address, blood_type: BloodType) -> u32 {
+        stock::get_on_hand(&env, &bank, blood_type)
+    }
+
+    /// Units of `blood_type` promised by `bank` to open stock reservations.
+    pub fn get_reserved_stock(env: Env, bank: Address, blood_type: BloodType) -> u32 {
+        stock::get_reserved(&env, &bank, blood_type)
+    }
+
+    /// Available units of `blood_type` at `bank` grouped by time left before
+    /// expiry (<48h, <7d, <14d, longer), at day granularity. Read from
+    /// counters kept per expiry day, so the cost does not grow with stock.
+    pub fn get_stock_by_expiry_bucket(
+        env: Env,
+        bank: Address,
+        blood_type: BloodType,
+    ) -> ExpiryBuckets {
+        stock::get_expiry_buckets(&env, &bank, blood_type)
+    }
+
+    /// Units `bank` wasted in a calendar month (`period` as `YYYYMM`, UTC),
+    /// summed over blood types, with the wastage rate against units
+    /// registered that month.
+    ///
+    /// # Errors
+    /// - `InvalidInput`: `period` is not a valid `YYYYMM` month
+    pub fn get_wastage(
+        env: Env,
+        bank: Address,
+        period: u32,
+    ) -> Result<WastageReport, ContractError> {
+        if !wastage::is_valid_period(period) {
+            return Err(ContractError::InvalidInput);
+        }
+        Ok(wastage::report(&env, &bank, None, period))
+    }
+
+    /// Like `get_wastage`, for a single blood type.
+    pub fn get_wastage_by_blood_type(
+        env: Env,
+        bank: Address,
+        blood_type: BloodType,
+        period: u32,
+    ) -> Result<WastageReport, ContractError> {
+        if !wastage::is_valid_period(period) {
+            return Err(ContractError::InvalidInput);
+        }
+        Ok(wastage::report(&env, &bank, Some(blood_type), period))
+    }
+
+    /// Units of `blood_type` held at one of `bank`'s locations.
+    pub fn get_location_on_hand_stock(
+        env: Env,
+        bank: Address,
+        location_id: u32,
+        blood_type: BloodType,
+    ) -> u32 {
+        stock::get_location_on_hand(&env, &bank, location_id, blood_type)
+    }
+
+    /// Units of `blood_type` at one of `bank`'s locations not promised to a
+    /// stock reservation.
+    pub fn get_location_available_stock(
+        env: Env,
+        bank: Address,
+        location_id: u32,
+        blood_type: BloodType,
+    ) -> u32 {
+        stock::get_location_available(&env, &bank, location_id, blood_type)
+    }
+
+    /// Register a storage site (fridge, satellite depot) under `bank`.
+    ///
+    /// Location `0` is the bank's default location and exists implicitly;
+    /// newly registered units are placed there.
+    ///
+    /// # Errors
+    /// - `NotAuthorizedBloodBank`: `bank` is not a blood bank
+    /// - `InvalidInput`: `location_id` is 0 or the bank already has 20 locations
+    /// - `AlreadyExists`: the location ID is taken
+    pub fn register_location(
+        env: Env,
+        bank: Address,
+        location_id: u32,
+        name_hash: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        bank.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_blood_bank(&env, &bank)?;
+
+        if location_id == stock::DEFAULT_LOCATION
+            || storage::get_bank_locations(&env, &bank).len() >= Self::MAX_LOCATIONS_PER_BANK
+        {
+            return Err(ContractError::InvalidInput);
+        }
+        if storage::get_location(&env, &bank, location_id).is_some() {
+            return Err(ContractError::AlreadyExists);
+        }
+
+        let location = Location {
+            bank_id: bank,
+            location_id,
+            name_hash,
+            registered_at: env.ledger().timestamp(),
+        };
+        storage::set_location(&env, &location);
+        events::emit_location_registered(&env, &location);
+        Ok(())
+    }
+
+    /// Get one of a bank's registered locations.
+    pub fn get_location(
+        env: Env,
+        bank: Address,
+        location_id: u32,
+    ) -> Result<Location, ContractError> {
+        storage::get_location(&env, &bank, location_id).ok_or(ContractError::NotFound)
+    }
+
+    /// IDs of the locations `bank` has registered (the default location 0 is
+    /// not listed).
+    pub fn get_bank_locations(env: Env, bank: Address) -> Vec<u32> {
+        storage::get_bank_locations(&env, &bank)
+    }
+
+    /// Move a unit to another of its bank's locations. If the unit is
+    /// available its on-hand count moves with it.
+    ///
+    /// # Errors
+    /// - `NotFound`: unit or location does not exist
+    /// - `NotUnitOwner`: `bank` does not hold the unit
+    pub fn move_unit(
+        env: Env,
+        bank: Address,
+        unit_id: u64,
+        location_id: u32,
+    ) -> Result<(), ContractError> {
+        bank.require_auth();
+        Self::require_not_paused(&env)?;
+
+        let unit = storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+        if unit.bank_id != bank {
+            return Err(ContractError::NotUnitOwner);
+        }
+        Self::require_not_frozen(&env, &bank)?;
+        if !stock::location_exists(&env, &bank, location_id) {
+            return Err(ContractError::NotFound);
+        }
+
+        let from = storage::get_unit_location(&env, unit_id);
+        if from == location_id {
+            return Ok(());
+        }
+        if unit.status == BloodStatus::Available {
+            // Add before removing so bank-wide stock never dips mid-move
+            stock::increase(&env, &bank, location_id, unit.blood_type, 1);
+            stock::decrease(&env, &bank, from, unit.blood_type, 1);
+        }
+        storage::set_unit_location(&env, unit_id, location_id);
+        Ok(())
+    }
+
+    /// Location a unit is stored at (0 unless moved).
+    pub fn get_unit_location(env: Env, unit_id: u64) -> u32 {
+        storage::get_unit_location(&env, unit_id)
+    }
+
+    /// Promise `quantity` units of `blood_type` at `location_id` to
+    /// `request_id` until `expires_at`.
+    ///
+    /// Unlike `reserve_blood` this holds a count rather than specific units:
+    /// the quantity stops counting as available stock until the reservation is
+    /// released, consumed or expired. `expires_at` must be in the future and
+    /// no more than 7 days away.
+    ///
+    /// # Errors
+    /// - `NotAuthorizedBloodBank`: `bank` is not a blood bank
+    /// - `NotFound`: `location_id` is not one of the bank's locations
+    /// - `InvalidQuantity`: `quantity` is zero
+    /// - `InvalidTimestamp`: `expires_at` is not in the allowed window
+    /// - `InsufficientStock`: less than `quantity` is available at the location
+    pub fn reserve_stock(
+        env: Env,
+        bank: Address,
+        location_id: u32,
+        blood_type: BloodType,
+        quantity: u32,
+        request_id: u64,
+        expires_at: u64,
+    ) -> Result<u64, ContractError> {
+        bank.require_auth();
+        Self::open_stock_reservation(
+            &env,
+            &bank,
+            location_id,
+            blood_type,
+            quantity,
+            request_id,
+            expires_at,
+        )
+    }
+
+    /// Reserve stock at `bank`'s default location for a coordinator order.
+    /// Only the configured coordinator may call this; the bank does not sign.
+    /// Otherwise behaves as `reserve_stock` with `order_id` as the request.
+    ///
+    /// # Errors
+    /// - `Unauthorized`: no coordinator is configured
+    /// - as `reserve_stock`
+    pub fn reserve_stock_for_order(
+        env: Env,
+        bank: Address,
+        blood_type: BloodType,
+        quantity: u32,
+        order_id: u64,
+        expires_at: u64,
+    ) -> Result<u64, ContractError> {
+        Self::get_coordinator_contract(env.clone())
+            .ok_or(ContractError::Unauthorized)?
+            .require_auth();
+        Self::open_stock_reservation(
+            &env,
+            &bank,
+            stock::DEFAULT_LOCATION,
+            blood_type,
+            quantity,
+            order_id,
+            expires_at,
+        )
+    }
+
+    fn open_stock_reservation(
+        env: &Env,
+        bank: &Address,
+        location_id: u32,
+        blood_type: BloodType,
+        quantity: u32,
+        request_id: u64,
+        expires_at: u64,
+    ) -> Result<u64, ContractError> {
+        Self::require_not_paused(env)?;
+        Self::require_blood_bank(env, bank)?;
+        Self::require_not_frozen(env, bank)?;
+        validation::validate_blood_type(blood_type)?;
+        Self::require_location(env, bank, location_id)?;
+
+        if quantity == 0 {
+            return Err(ContractError::InvalidQuantity);
+        }
+        let current_time = env.ledger().timestamp();
+        if expires_at <= current_time
+            || expires_at - current_time > Self::MAX_RESERVATION_DURATION_SECS
+        {
+            return Err(ContractError::InvalidTimestamp);
+        }
+        if stock::get_location_available(env, bank, location_id, blood_type) < quantity {
+            return Err(ContractError::InsufficientStock);
+        }
+
+        let reservation = StockReservation {
+            id: storage::increment_stock_reservation_id(env),
+            bank_id: bank.clone(),
+            location_id,
+            blood_type,
+            quantity,
+            request_id,
+            created_at: current_time,
+            expires_at,
+        };
+        storage::set_stock_reservation(env, &reservation);
+        stock::reserve(env, bank, location_id, blood_type, quantity);
+
+        events::emit_stock_reserved(env, &reservation);
+
+        Ok(reservation.id)
+    }
+
+    /// Release a stock reservation, returning its quantity to available stock.
+    ///
+    /// Callable by the reserving bank, the admin or the coordinator, whether
+    /// or not the reservation has expired.
+    pub fn release_stock_reservation(
+        env: Env,
+        caller: Address,
+        reservation_id: u64,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        Self::require_not_paused(&env)?;
+
+        let reservation = storage::get_stock_reservation(&env, reservation_id)
+            .ok_or(ContractError::ReservationNotFound)?;
+        if caller != reservation.bank_id
+            && caller != storage::get_admin(&env)
+            && Some(caller) != Self::get_coordinator_contract(env.clone())
+        {
+            return Err(ContractError::NotReservationOwner);
+        }
+        Self::require_not_frozen(&env, &reservation.bank_id)?;
+
+        Self::settle_stock_reservation(&env, &reservation);
+        events::emit_stock_reservation_released(&env, reservation_id, false);
+
+        Ok(())
+    }
+
+    /// Ship the stock held by a reservation: its quantity leaves both the
+    /// reserved and the on-hand counts.
+    ///
+    /// # Errors
+    /// - `NotReservationOwner`: `bank` did not make the reservation
+    /// - `ReservationExpired`: the reservation is past `expires_at`
+    pub fn consume_stock_reservation(
+        env: Env,
+        bank: Address,
+        reservation_id: u64,
+    ) -> Result<(), ContractError> {
+        bank.require_auth();
+        Self::require_not_paused(&env)?;
+
+        let reservation = storage::get_stock_reservation(&env, reservation_id)
+            .ok_or(ContractError::ReservationNotFound)?;
+        if bank != reservation.bank_id {
+            return Err(ContractError::NotReservationOwner);
+        }
+        Self::require_not_frozen(&env, &bank)?;
+        if env.ledger().timestamp() >= reservation.expires_at {
+            return Err(ContractError::ReservationExpired);
+        }
+
+        Self::settle_stock_reservation(&env, &reservation);
+        stock::decrease(
+            &env,
+            &bank,
+            reservation.location_id,
+            reservation.blood_type,
+            reservation.quantity,
+        );
+        events::emit_stock_reservation_consumed(&env, reservation_id);
+
+        Ok(())
+    }
+
+    /// Keeper: release up to `limit` open stock reservations past their
+    /// `expires_at`. Callable by anyone. Returns the number released.
+    /// Reservations of frozen banks are left for a later run.
+    pub fn expire_stock_reservations(env: Env, limit: u32) -> u32 {
+        let current_time = env.ledger().timestamp();
+        let mut released = 0u32;
+        for reservation_id in storage::get_open_stock_reservations(&env).iter() {
+            if released >= limit {
+                break;
+            }
+            let Some(reservation) = storage::get_stock_reservation(&env, reservation_id) else {
+                continue;
+            };
+            if current_time < reservation.expires_at
+                || Self::is_frozen(&env, &reservation.bank_id)
+            {
+                continue;
+            }
+            Self::settle_stock_reservation(&env, &reservation);
+            events::emit_stock_reservation_released(&env, reservation_id, true);
+            released += 1;
+        }
+        released
+    }
+
+    /// Get a stock reservation by ID.
+    pub fn get_stock_reservation(
+        env: Env,
+        reservation_id: u64,
+    ) -> Result<StockReservation, ContractError> {
+        storage::get_stock_reservation(&env, reservation_id)
+            .ok_or(ContractError::ReservationNotFound)
+    }
+
+    /// Start moving `quantity` units of `blood_type` from `from` to `to`.
+    ///
+    /// The quantity is drawn from the sender's default location and held as
+    /// reserved there; no counts move until the receiver calls
+    /// `acknowledge_stock_transfer`. Returns the transfer ID.
+    ///
+    /// # Errors
+    /// - `NotAuthorizedBloodBank`: either party is not a blood bank
+    /// - `InvalidInput`: `from` and `to` are the same bank
+    /// - `InvalidQuantity`: `quantity` is zero
+    /// - `InsufficientStock`: less than `quantity` is available at the sender
+    pub fn initiate_stock_transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        blood_type: BloodType,
+        quantity: u32,
+    ) -> Result<u64, ContractError> {
+        from.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_blood_bank(&env, &from)?;
+        Self::require_blood_bank(&env, &to)?;
+        Self::require_not_frozen(&env, &from)?;
+        Self::require_not_frozen(&env, &to)?;
+        validation::validate_blood_type(blood_type)?;
+
+        if from == to {
+            return Err(ContractError::InvalidInput);
+        }
+        if quantity == 0 {
+            return Err(ContractError::InvalidQuantity);
+        }
+        if stock::get_location_available(&env, &from, stock::DEFAULT_LOCATION, blood_type)
+            < quantity
+        {
+            return Err(ContractError::InsufficientStock);
+        }
+
+        let transfer = StockTransfer {
+            id: storage::increment_stock_transfer_id(&env),
+            from_bank: from.clone(),
+            to_bank: to,
+            blood_type,
+            quantity,
+            status: StockTransferStatus::Pending,
+            initiated_at: env.ledger().timestamp(),
+            resolved_at: None,
+        };
+        stock::reserve(&env, &from, stock::DEFAULT_LOCATION, blood_type, quantity);
+        storage::set_stock_transfer(&env, &transfer);
+        events::emit_stock_transfer(&env, &transfer);
+
+        Ok(transfer.id)
+    }
+
+    /// Confirm receipt of a pending stock transfer. The quantity leaves the
+    /// sender's default location and is added to the receiver's.
+    ///
+    /// # Errors
+    /// - `NotFound`: no such transfer
+    /// - `Unauthorized`: `to` is not the receiving bank
+    /// - `InvalidStatus`: the transfer is not pending
+    pub fn acknowledge_stock_transfer(
+        env: Env,
+        to: Address,
+        transfer_id: u64,
+    ) -> Result<(), ContractError> {
+        to.require_auth();
+        Self::require_not_paused(&env)?;
+
+        let mut transfer = Self::pending_stock_transfer(&env, transfer_id)?;
+        if to != transfer.to_bank {
+            return Err(ContractError::Unauthorized);
+        }
+        Self::require_not_frozen(&env, &transfer.from_bank)?;
+        Self::require_not_frozen(&env, &to)?;
+
+        let (from, blood_type, quantity) =
+            (transfer.from_bank.clone(), transfer.blood_type, transfer.quantity);
+        stock::unreserve(&env, &from, stock::DEFAULT_LOCATION, blood_type, quantity);
+        stock::decrease(&env, &from, stock::DEFAULT_LOCATION, blood_type, quantity);
+        stock::increase(&env, &to, stock::DEFAULT_LOCATION, blood_type, quantity);
+
+        transfer.status = StockTransferStatus::Acknowledged;
+        transfer.resolved_at = Some(env.ledger().timestamp());
+        storage::set_stock_transfer(&env, &transfer);
+        events::emit_stock_transfer(&env, &transfer);
+
+        Ok(())
+    }
+
+    /// Cancel a pending stock transfer, releasing the held quantity back to
+    /// the sender. Either the sender (withdrawing) or the receiver (refusing)
+    /// may cancel.
+    ///
+    /// # Errors
+    /// - `NotFound`: no such transfer
+    /// - `Unauthorized`: `caller` is neither party
+    /// - `InvalidStatus`: the transfer is not pending
+    pub fn cancel_stock_transfer(
+        env: Env,
+        caller: Address,
+        transfer_id: u64,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        Self::require_not_paused(&env)?;
+
+        let mut transfer = Self::pending_stock_transfer(&env, transfer_id)?;
+        if caller != transfer.from_bank && caller != transfer.to_bank {
+            return Err(ContractError::Unauthorized);
+        }
+        Self::require_not_frozen(&env, &transfer.from_bank)?;
+
+        stock::unreserve(
+            &env,
+            &transfer.from_bank,
+            stock::DEFAULT_LOCATION,
+            transfer.blood_type,
+            transfer.quantity,
+        );
+        transfer.status = StockTransferStatus::Cancelled;
+        transfer.resolved_at = Some(env.ledger().timestamp());
+        storage::set_stock_transfer(&env, &transfer);
+        events::emit_stock_transfer(&env, &transfer);
+
+        Ok(())
+    }
+
+    /// Get an inter-bank stock transfer by ID.
+    pub fn get_stock_transfer(env: Env, transfer_id: u64) -> Result<StockTransfer, ContractError> {
+        storage::get_stock_transfer(&env, transfer_id).ok_or(ContractError::NotFound)
+    }
+
+    fn pending_stock_transfer(env: &Env, transfer_id: u64) -> Result<StockTransfer, ContractError> {
+        let transfer = storage::get_stock_transfer(env, transfer_id).ok_or(ContractError::NotFound)?;
+        if transfer.status != StockTransferStatus::Pending {
+            return Err(ContractError::InvalidStatus);
+        }
+        Ok(transfer)
+    }
+
+    /// Apply many on-hand stock adjustments in one transaction, e.g. a nightly
+    /// LIS sync. Entries are applied in order; if any would overflow or take a
+    /// count below zero the whole batch fails and nothing is changed.
+    ///
+    /// Emits a single `stock_adjusted` event summarising the batch.
+    ///
+    /// # Errors
+    /// - `NotAuthorizedBloodBank`: `bank` is not a blood bank
+    /// - `InvalidInput`: batch is empty or larger than 100 entries
+    /// - `NotFound`: an entry names a location the bank has not registered
+    /// - `InvalidQuantity`: an entry has a zero delta
+    /// - `StockOutOfRange`: an entry would overflow or underflow on-hand stock
+    pub fn adjust_stock_batch(
+        env: Env,
+        bank: Address,
+        adjustments: Vec<StockAdjustment>,
+    ) -> Result<(), ContractError> {
+        bank.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_blood_bank(&env, &bank)?;
+        Self::require_not_frozen(&env, &bank)?;
+
+        if adjustments.is_empty() || adjustments.len() > Self::MAX_ADJUSTMENT_BATCH {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let mut units_added = 0u32;
+        let mut units_removed = 0u32;
+        for adjustment in adjustments.iter() {
+            validation::validate_blood_type(adjustment.blood_type)?;
+            Self::require_location(&env, &bank, adjustment.location_id)?;
+            if adjustment.delta == 0 {
+                return Err(ContractError::InvalidQuantity);
+            }
+            stock::adjust(
+                &env,
+                &bank,
+                adjustment.location_id,
+                adjustment.blood_type,
+                adjustment.delta,
+            )?;
+
+            let units = adjustment.delta.unsigned_abs();
+            if adjustment.delta > 0 {
+                units_added = units_added.saturating_add(units);
+            } else {
+                units_removed = units_removed.saturating_add(units);
+                if adjustment.reason == AdjustmentReason::Discarded {
+                    wastage::record_discarded(&env, &bank, adjustment.blood_type, units);
+                }
+            }
+        }
+
+        events::emit_stock_adjusted(&env, &bank, units_added, units_removed, &adjustments);
+        Ok(())
+    }
+
+    /// Set the total discrepancy (in units, summed over blood types) a
+    /// reconciliation may carry and still apply without admin approval.
+    /// Only admin can call this.
+    ///
+    /// # Errors
+    /// - `Unauthorized`: caller is not the admin
+    pub fn set_reconciliation_threshold(
+        env: Env,
+        admin: Address,
+        threshold: u32,
+    ) -> Result<(), ContractError> {
+        admin.require_auth();
+        if admin != storage::get_admin(&env) {
+            return Err(ContractError::Unauthorized);
+        }
+        storage::set_reconciliation_threshold(&env, threshold);
+        Ok(())
+    }
+
+    /// Get the reconciliation approval threshold (0 when unset).
+    pub fn get_reconciliation_threshold(env: Env) -> u32 {
+        storage::get_reconciliation_threshold(&env)
+    }
+
+    /// Reconcile on-chain stock at one location with a physical count.
+    ///
+    /// `counts` holds the counted units per blood type; `count_hash` is the
+    /// hash of the signed count document. The returned report lists recorded
+    /// versus counted stock for every blood type submitted. When the total
+    /// discrepancy is within the threshold the counted figures replace on-hand
+    /// stock immediately; otherwise the report waits for
+    /// `approve_reconciliation`.
+    ///
+    /// # Errors
+    /// - `NotAuthorizedBloodBank`: `bank` is not a blood bank
+    /// - `NotFound`: `location_id` is not one of the bank's locations
+    /// - `InvalidInput`: `counts` is empty
+    pub fn submit_reconciliation(
+        env: Env,
+        bank: Address,
+        location_id: u32,
+        counts: Map<BloodType, u32>,
+        count_hash: BytesN<32>,
+    ) -> Result<ReconciliationReport, ContractError> {
+        bank.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_blood_bank(&env, &bank)?;
+        Self::require_not_frozen(&env, &bank)?;
+        Self::require_location(&env, &bank, location_id)?;
+
+        if counts.is_empty() {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let mut lines = Vec::new(&env);
+        let mut total_discrepancy = 0u32;
+        for (blood_type, counted) in counts.iter() {
+            validation::validate_blood_type(blood_type)?;
+            let recorded = stock::get_location_on_hand(&env, &bank, location_id, blood_type);
+            total_discrepancy = total_discrepancy.saturating_add(recorded.abs_diff(counted));
+            lines.push_back(ReconciliationLine {
+                blood_type,
+                recorded,
+                counted,
+            });
+        }
+
+        let mut report = ReconciliationReport {
+            id: storage::increment_reconciliation_id(&env),
+            bank_id: bank,
+            location_id,
+            count_hash,
+            lines,
+            total_discrepancy,
+            status: ReconciliationStatus::PendingApproval,
+            submitted_at: env.ledger().timestamp(),
+            resolved_at: None,
+        };
+        if total_discrepancy <= storage::get_reconciliation_threshold(&env) {
+            Self::apply_reconciliation(&env, &mut report);
+        }
+        storage::set_reconciliation(&env, &report);
+        events::emit_reconciliation(&env, &report);
+
+        Ok(report)
+    }
+
+    /// Apply a reconciliation held for approval. Admin only.
+    ///
+    /// # Errors
+    /// - `Unauthorized`: caller is not the admin
+    /// - `NotFound`: no such reconciliation
+    /// - `InvalidStatus`: the reconciliation is not pending approval
+    pub fn approve_reconciliation(
+        env: Env,
+        admin: Address,
+        reconciliation_id: u64,
+    ) -> Result<ReconciliationReport, ContractError> {
+        let mut report = Self::pending_reconciliation(&env, &admin, reconciliation_id)?;
+        Self::require_not_frozen(&env, &report.bank_id)?;
+        Self::apply_reconciliation(&env, &mut report);
+        storage::set_reconciliation(&env, &report);
+        events::emit_reconciliation(&env, &report);
+        Ok(report)
+    }
+
+    /// Refuse a reconciliation held for approval, leaving stock unchanged.
+    /// Admin only.
+    pub fn reject_reconciliation(
+        env: Env,
+        admin: Address,
+        reconciliation_id: u64,
+    ) -> Result<ReconciliationReport, ContractError> {
+        let mut report = Self::pending_reconciliation(&env, &admin, reconciliation_id)?;
+        report.status = ReconciliationStatus::Rejected;
+        report.resolved_at = Some(env.ledger().timestamp());
+        storage::set_reconciliation(&env, &report);
+        events::emit_reconciliation(&env, &report);
+        Ok(report)
+    }
+
+    /// Get a reconciliation report by ID.
+    pub fn get_reconciliation(
+        env: Env,
+        reconciliation_id: u64,
+    ) -> Result<ReconciliationReport, ContractError> {
+        storage::get_reconciliation(&env, reconciliation_id).ok_or(ContractError::NotFound)
+    }
+
+    fn pending_reconciliation(
+        env: &Env,
+        admin: &Address,
+        reconciliation_id: u64,
+    ) -> Result<ReconciliationReport, ContractError> {
+        admin.require_auth();
+        Self::require_not_paused(env)?;
+        if *admin != storage::get_admin(env) {
+            return Err(ContractError::Unauthorized);
+        }
+        let report =
+            storage::get_reconciliation(env, reconciliation_id).ok_or(ContractError::NotFound)?;
+        if report.status != ReconciliationStatus::PendingApproval {
+            return Err(ContractError::InvalidStatus);
+        }
+        Ok(report)
+    }
+
+    /// Replace on-hand stock with the counted figures.
+    fn apply_reconciliation(env: &Env, report: &mut ReconciliationReport) {
+        for line in report.lines.iter() {
+            stock::set_on_hand(
+                env,
+                &report.bank_id,
+                report.location_id,
+                line.blood_type,
+                line.counted,
+            );
+        }
+        report.status = ReconciliationStatus::Applied;
+        report.resolved_at = Some(env.ledger().timestamp());
+    }
+
+    /// Close a stock reservation and stop holding its quantity.
+    fn settle_stock_reservation(env: &Env, reservation: &StockReservation) {
+        storage::remove_stock_reservation(env, reservation.id);
+        stock::unreserve(
+            env,
+            &reservation.bank_id,
+            reservation.location_id,
+            reservation.blood_type,
+            reservation.quantity,
+        );
+    }
+
     /// Set the authoritative BloodUnitRegistry contract address for cross-contract
     /// state synchronisation. Only admin can call this.
     ///
@@ -915,11 +1842,101 @@ impl InventoryContract {
         Ok(())
     }
 
+    /// Set the requests contract that receives automatic restock requests.
+    /// Only admin can call this.
+    ///
+    /// Once set, stock falling below a bank's par level opens a restock
+    /// request there for the deficit, unless one is already open for that
+    /// bank and blood type.
+    ///
+    /// # Errors
+    /// - `Unauthorized`: caller is not the admin
+    pub fn set_requests_contract(
+        env: Env,
+        admin: Address,
+        requests_contract_id: Address,
+    ) -> Result<(), ContractError> {
+        admin.require_auth();
+        let stored_admin = storage::get_admin(&env);
+        if admin != stored_admin {
+            return Err(ContractError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::RequestsContractId, &requests_contract_id);
+        Ok(())
+    }
+
+    /// Get the configured requests contract address, if any.
+    pub fn get_requests_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::RequestsContractId)
+    }
+
+    /// Set the coordinator contract allowed to reserve and release stock for
+    /// its orders. Only admin can call this.
+    pub fn set_coordinator_contract(
+        env: Env,
+        admin: Address,
+        coordinator_contract_id: Address,
+    ) -> Result<(), ContractError> {
+        admin.require_auth();
+        let stored_admin = storage::get_admin(&env);
+        if admin != stored_admin {
+            return Err(ContractError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::CoordinatorContractId, &coordinator_contract_id);
+        Ok(())
+    }
+
+    /// Get the configured coordinator contract address, if any.
+    pub fn get_coordinator_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::CoordinatorContractId)
+    }
+
+    /// Restock request currently open for a bank and blood type, if any.
+    pub fn get_open_restock_request(
+        env: Env,
+        bank: Address,
+        blood_type: BloodType,
+    ) -> Option<u64> {
+        storage::get_open_restock_request(&env, &bank, blood_type)
+    }
+
     /// Get the configured registry contract address, if any.
     pub fn get_registry_contract(env: Env) -> Option<Address> {
         env.storage().instance().get(&DataKey::RegistryContractId)
     }
 
+    /// Set the identity contract whose `Role::BloodBank` holders may register
+    /// and reserve blood. Only admin can call this.
+    ///
+    /// Once set, it replaces the `authorize_bank` allow-list for those checks.
+    ///
+    /// # Errors
+    /// - `Unauthorized`: caller is not the admin
+    pub fn set_identity_contract(
+        env: Env,
+        admin: Address,
+        identity_contract_id: Address,
+    ) -> Result<(), ContractError> {
+        admin.require_auth();
+        let stored_admin = storage::get_admin(&env);
+        if admin != stored_admin {
+            return Err(ContractError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::IdentityContractId, &identity_contract_id);
+        Ok(())
+    }
+
+    /// Get the configured identity contract address, if any.
+    pub fn get_identity_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::IdentityContractId)
+    }
+
     /// Upgrade the contract to a new WASM hash. Only admin can call this.
     ///
     /// # Arguments
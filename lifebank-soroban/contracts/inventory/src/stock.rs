@@ -0,0 +1,355 @@
+//! Per-location stock counters and par-level alerts.
+//!
+//! On-hand counts are maintained incrementally as units move into and out of
+//! `Available`; reserved counts as quantity reservations are made and settled.
+//! Available stock is on hand minus reserved, so par-level checks never scan
+//! a bank's units.
+//!
+//! Counters are kept per `(bank, location, blood_type)`. Location
+//! [`DEFAULT_LOCATION`] always exists; a bank registers further sites with
+//! `register_location`. Bank-wide figures are roll-ups over its locations, and
+//! par levels apply to the bank-wide available stock.
+//!
+//! When a requests contract is configured, falling below par also opens one
+//! restock request per bank and blood type; it is forgotten once available
+//! stock is back at par, so a later shortfall opens a new one.
+//!
+//! Registered units are also counted per expiry day so freshness can be
+//! reported by summing at most a shelf life's worth of day counters.
+
+use crate::error::ContractError;
+use crate::events;
+use crate::storage::{self, BLOOD_SHELF_LIFE_DAYS, SECONDS_PER_DAY, TTL_EXTEND_TO, TTL_THRESHOLD};
+use crate::requests_client;
+use crate::types::{
+    BloodStatus, BloodType, BloodUnit, DataKey, ExpiryBuckets, RestockUrgency, StockSeverity,
+};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Location every bank has without registering it; new units start here.
+pub const DEFAULT_LOCATION: u32 = 0;
+
+fn get_count(env: &Env, key: &DataKey) -> u32 {
+    env.storage().persistent().get(key).unwrap_or(0)
+}
+
+fn set_count(env: &Env, key: &DataKey, count: u32) {
+    env.storage().persistent().set(key, &count);
+    env.storage().persistent().extend_ttl(key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+/// The default location followed by every registered location of `bank`.
+pub fn locations(env: &Env, bank: &Address) -> Vec<u32> {
+    let mut locations = Vec::from_array(env, [DEFAULT_LOCATION]);
+    locations.append(&storage::get_bank_locations(env, bank));
+    locations
+}
+
+pub fn location_exists(env: &Env, bank: &Address, location_id: u32) -> bool {
+    location_id == DEFAULT_LOCATION || storage::get_location(env, bank, location_id).is_some()
+}
+
+pub fn get_location_on_hand(
+    env: &Env,
+    bank: &Address,
+    location_id: u32,
+    blood_type: BloodType,
+) -> u32 {
+    get_count(env, &DataKey::OnHandStock(bank.clone(), location_id, blood_type))
+}
+
+pub fn get_location_reserved(
+    env: &Env,
+    bank: &Address,
+    location_id: u32,
+    blood_type: BloodType,
+) -> u32 {
+    get_count(env, &DataKey::ReservedStock(bank.clone(), location_id, blood_type))
+}
+
+/// On-hand stock at one location not promised to any reservation.
+pub fn get_location_available(
+    env: &Env,
+    bank: &Address,
+    location_id: u32,
+    blood_type: BloodType,
+) -> u32 {
+    get_location_on_hand(env, bank, location_id, blood_type)
+        .saturating_sub(get_location_reserved(env, bank, location_id, blood_type))
+}
+
+/// Bank-wide on-hand stock, summed over its locations.
+pub fn get_on_hand(env: &Env, bank: &Address, blood_type: BloodType) -> u32 {
+    locations(env, bank).iter().fold(0u32, |total, location_id| {
+        total.saturating_add(get_location_on_hand(env, bank, location_id, blood_type))
+    })
+}
+
+/// Bank-wide reserved stock, summed over its locations.
+pub fn get_reserved(env: &Env, bank: &Address, blood_type: BloodType) -> u32 {
+    locations(env, bank).iter().fold(0u32, |total, location_id| {
+        total.saturating_add(get_location_reserved(env, bank, location_id, blood_type))
+    })
+}
+
+/// Bank-wide stock not promised to any reservation.
+pub fn get_available(env: &Env, bank: &Address, blood_type: BloodType) -> u32 {
+    locations(env, bank).iter().fold(0u32, |total, location_id| {
+        total.saturating_add(get_location_available(env, bank, location_id, blood_type))
+    })
+}
+
+pub fn get_par_level(env: &Env, bank: &Address, blood_type: BloodType) -> u32 {
+    get_count(env, &DataKey::ParLevel(bank.clone(), blood_type))
+}
+
+/// A par level of zero removes the threshold.
+pub fn set_par_level(env: &Env, bank: &Address, blood_type: BloodType, min_units: u32) {
+    let key = DataKey::ParLevel(bank.clone(), blood_type);
+    if min_units == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        set_count(env, &key, min_units);
+    }
+}
+
+/// Severity of a shortfall, or `None` while stock is at or above par.
+pub fn severity(available: u32, par_level: u32) -> Option<StockSeverity> {
+    if available >= par_level {
+        None
+    } else if available <= par_level / 2 {
+        Some(StockSeverity::Critical)
+    } else {
+        Some(StockSeverity::Low)
+    }
+}
+
+/// Alert if bank-wide available stock is below the bank's par level. Called
+/// after every change that lowers available stock.
+fn check_par_level(env: &Env, bank: &Address, blood_type: BloodType) {
+    let par_level = get_par_level(env, bank, blood_type);
+    if par_level == 0 {
+        return;
+    }
+    let available = get_available(env, bank, blood_type);
+    if let Some(severity) = severity(available, par_level) {
+        events::emit_stock_low(env, bank, blood_type, available, par_level, severity);
+        request_restock(env, bank, blood_type, available, par_level);
+    }
+}
+
+/// Restock priority from how far below par a bank is: empty is critical, at
+/// or below half of par urgent, otherwise routine.
+pub fn restock_urgency(available: u32, par_level: u32) -> RestockUrgency {
+    if available == 0 {
+        RestockUrgency::Critical
+    } else if available <= par_level / 2 {
+        RestockUrgency::Urgent
+    } else {
+        RestockUrgency::Routine
+    }
+}
+
+/// Open a restock request for the deficit unless one is already open. A
+/// failing requests contract never blocks the stock change itself.
+fn request_restock(
+    env: &Env,
+    bank: &Address,
+    blood_type: BloodType,
+    available: u32,
+    par_level: u32,
+) {
+    if storage::get_open_restock_request(env, bank, blood_type).is_some() {
+        return;
+    }
+    let Some(requests_id) = env
+        .storage()
+        .instance()
+        .get::<_, Address>(&DataKey::RequestsContractId)
+    else {
+        return;
+    };
+    if let Some(request_id) = requests_client::create_restock_request(
+        env,
+        &requests_id,
+        bank,
+        blood_type,
+        par_level - available,
+        restock_urgency(available, par_level),
+    ) {
+        storage::set_open_restock_request(env, bank, blood_type, request_id);
+    }
+}
+
+/// Forget the open restock request once available stock is back at par.
+/// Called after every change that raises available stock.
+fn check_recovered(env: &Env, bank: &Address, blood_type: BloodType) {
+    if storage::get_open_restock_request(env, bank, blood_type).is_none() {
+        return;
+    }
+    if get_available(env, bank, blood_type) >= get_par_level(env, bank, blood_type) {
+        storage::remove_open_restock_request(env, bank, blood_type);
+    }
+}
+
+pub fn increase(
+    env: &Env,
+    bank: &Address,
+    location_id: u32,
+    blood_type: BloodType,
+    quantity: u32,
+) {
+    let key = DataKey::OnHandStock(bank.clone(), location_id, blood_type);
+    set_count(env, &key, get_count(env, &key).saturating_add(quantity));
+    check_recovered(env, bank, blood_type);
+}
+
+pub fn decrease(
+    env: &Env,
+    bank: &Address,
+    location_id: u32,
+    blood_type: BloodType,
+    quantity: u32,
+) {
+    let key = DataKey::OnHandStock(bank.clone(), location_id, blood_type);
+    set_count(env, &key, get_count(env, &key).saturating_sub(quantity));
+    check_par_level(env, bank, blood_type);
+}
+
+/// Overwrite the on-hand count with a physically counted figure.
+pub fn set_on_hand(
+    env: &Env,
+    bank: &Address,
+    location_id: u32,
+    blood_type: BloodType,
+    count: u32,
+) {
+    let key = DataKey::OnHandStock(bank.clone(), location_id, blood_type);
+    let previous = get_count(env, &key);
+    set_count(env, &key, count);
+    if count < previous {
+        check_par_level(env, bank, blood_type);
+    } else {
+        check_recovered(env, bank, blood_type);
+    }
+}
+
+/// Apply a signed change to the on-hand count, refusing to overflow or to go
+/// below zero.
+pub fn adjust(
+    env: &Env,
+    bank: &Address,
+    location_id: u32,
+    blood_type: BloodType,
+    delta: i32,
+) -> Result<u32, ContractError> {
+    let on_hand = get_location_on_hand(env, bank, location_id, blood_type);
+    let count = if delta >= 0 {
+        on_hand.checked_add(delta.unsigned_abs())
+    } else {
+        on_hand.checked_sub(delta.unsigned_abs())
+    }
+    .ok_or(ContractError::StockOutOfRange)?;
+    set_on_hand(env, bank, location_id, blood_type, count);
+    Ok(count)
+}
+
+/// Promise `quantity` units of available stock. The caller checks availability.
+pub fn reserve(
+    env: &Env,
+    bank: &Address,
+    location_id: u32,
+    blood_type: BloodType,
+    quantity: u32,
+) {
+    let key = DataKey::ReservedStock(bank.clone(), location_id, blood_type);
+    set_count(env, &key, get_count(env, &key).saturating_add(quantity));
+    check_par_level(env, bank, blood_type);
+}
+
+pub fn unreserve(
+    env: &Env,
+    bank: &Address,
+    location_id: u32,
+    blood_type: BloodType,
+    quantity: u32,
+) {
+    let key = DataKey::ReservedStock(bank.clone(), location_id, blood_type);
+    set_count(env, &key, get_count(env, &key).saturating_sub(quantity));
+    check_recovered(env, bank, blood_type);
+}
+
+fn expiry_key(unit: &BloodUnit) -> DataKey {
+    DataKey::ExpiryStock(
+        unit.bank_id.clone(),
+        unit.blood_type,
+        unit.expiration_timestamp / SECONDS_PER_DAY,
+    )
+}
+
+fn add_dated(env: &Env, unit: &BloodUnit) {
+    let key = expiry_key(unit);
+    set_count(env, &key, get_count(env, &key).saturating_add(1));
+}
+
+fn remove_dated(env: &Env, unit: &BloodUnit) {
+    let key = expiry_key(unit);
+    let count = get_count(env, &key).saturating_sub(1);
+    if count == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        set_count(env, &key, count);
+    }
+}
+
+/// Count a newly registered unit, which starts `Available` at the default
+/// location.
+pub fn on_register(env: &Env, unit: &BloodUnit) {
+    increase(env, &unit.bank_id, DEFAULT_LOCATION, unit.blood_type, 1);
+    add_dated(env, unit);
+}
+
+/// Keep the on-hand and expiry counters in step with a unit's status
+/// transition.
+pub fn on_status_change(env: &Env, unit: &BloodUnit, old_status: BloodStatus) {
+    let location_id = storage::get_unit_location(env, unit.id);
+    match (old_status, unit.status) {
+        (BloodStatus::Available, BloodStatus::Available) => {}
+        (BloodStatus::Available, _) => {
+            decrease(env, &unit.bank_id, location_id, unit.blood_type, 1);
+            remove_dated(env, unit);
+        }
+        (_, BloodStatus::Available) => {
+            increase(env, &unit.bank_id, location_id, unit.blood_type, 1);
+            add_dated(env, unit);
+        }
+        _ => {}
+    }
+}
+
+/// Available units of `blood_type` at `bank` grouped by days left before
+/// expiry, at day granularity. Units already past their expiry day but not
+/// yet marked expired are left out.
+pub fn get_expiry_buckets(env: &Env, bank: &Address, blood_type: BloodType) -> ExpiryBuckets {
+    let today = env.ledger().timestamp() / SECONDS_PER_DAY;
+    let mut buckets = ExpiryBuckets {
+        within_48h: 0,
+        within_7d: 0,
+        within_14d: 0,
+        longer: 0,
+    };
+    for days_left in 0..=BLOOD_SHELF_LIFE_DAYS {
+        let count = get_count(
+            env,
+            &DataKey::ExpiryStock(bank.clone(), blood_type, today + days_left),
+        );
+        let bucket = match days_left {
+            0..=1 => &mut buckets.within_48h,
+            2..=6 => &mut buckets.within_7d,
+            7..=13 => &mut buckets.within_14d,
+            _ => &mut buckets.longer,
+        };
+        *bucket = bucket.saturating_add(count);
+    }
+    buckets
+}
@@ -1,4 +1,7 @@
-use crate::types::{BloodStatus, BloodUnit, DataKey, StatusChangeHistory};
+use crate::types::{
+    BloodStatus, BloodType, BloodUnit, DataKey, Location, ReconciliationReport,
+    StatusChangeHistory, StockReservation, StockTransfer,
+};
 use soroban_sdk::{Address, Env, String, Vec};
 
 pub const SECONDS_PER_DAY: u64 = 86400;
@@ -263,3 +266,177 @@ pub fn get_reservation(env: &Env, id: u64) -> Option<crate::types::Reservation>
 pub fn remove_reservation(env: &Env, id: u64) {
     env.storage().temporary().remove(&DataKey::Reservation(id));
 }
+
+// ── Stock reservation ──────────────────────────────────────────────────────────
+
+pub fn increment_stock_reservation_id(env: &Env) -> u64 {
+    let key = DataKey::StockReservationCounter;
+    let next_id = env.storage().instance().get(&key).unwrap_or(0u64) + 1;
+    env.storage().instance().set(&key, &next_id);
+    next_id
+}
+
+pub fn set_stock_reservation(env: &Env, reservation: &StockReservation) {
+    let key = DataKey::StockReservation(reservation.id);
+    env.storage().persistent().set(&key, reservation);
+    env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+    let mut open = get_open_stock_reservations(env);
+    open.push_back(reservation.id);
+    set_open_stock_reservations(env, &open);
+}
+
+pub fn get_stock_reservation(env: &Env, id: u64) -> Option<StockReservation> {
+    env.storage().persistent().get(&DataKey::StockReservation(id))
+}
+
+pub fn remove_stock_reservation(env: &Env, id: u64) {
+    env.storage().persistent().remove(&DataKey::StockReservation(id));
+
+    let open = get_open_stock_reservations(env);
+    if let Some(index) = open.first_index_of(id) {
+        let mut open = open;
+        open.remove(index);
+        set_open_stock_reservations(env, &open);
+    }
+}
+
+pub fn get_open_stock_reservations(env: &Env) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OpenStockReservations)
+        .unwrap_or(Vec::new(env))
+}
+
+fn set_open_stock_reservations(env: &Env, open: &Vec<u64>) {
+    let key = DataKey::OpenStockReservations;
+    env.storage().persistent().set(&key, open);
+    env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+// ── Reconciliation ─────────────────────────────────────────────────────────────
+
+pub fn increment_reconciliation_id(env: &Env) -> u64 {
+    let key = DataKey::ReconciliationCounter;
+    let next_id = env.storage().instance().get(&key).unwrap_or(0u64) + 1;
+    env.storage().instance().set(&key, &next_id);
+    next_id
+}
+
+pub fn set_reconciliation(env: &Env, report: &ReconciliationReport) {
+    let key = DataKey::Reconciliation(report.id);
+    env.storage().persistent().set(&key, report);
+    env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+pub fn get_reconciliation(env: &Env, id: u64) -> Option<ReconciliationReport> {
+    env.storage().persistent().get(&DataKey::Reconciliation(id))
+}
+
+/// Unset means any discrepancy at all needs admin approval.
+pub fn get_reconciliation_threshold(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::ReconciliationThreshold)
+        .unwrap_or(0)
+}
+
+pub fn set_reconciliation_threshold(env: &Env, threshold: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKey::ReconciliationThreshold, &threshold);
+}
+
+/// Store a location and add it to the bank's location list.
+pub fn set_location(env: &Env, location: &Location) {
+    let key = DataKey::Location(location.bank_id.clone(), location.location_id);
+    env.storage().persistent().set(&key, location);
+    env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+    let mut ids = get_bank_locations(env, &location.bank_id);
+    ids.push_back(location.location_id);
+    let list_key = DataKey::BankLocations(location.bank_id.clone());
+    env.storage().persistent().set(&list_key, &ids);
+    env.storage()
+        .persistent()
+        .extend_ttl(&list_key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+pub fn get_location(env: &Env, bank: &Address, location_id: u32) -> Option<Location> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Location(bank.clone(), location_id))
+}
+
+pub fn get_bank_locations(env: &Env, bank: &Address) -> Vec<u32> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BankLocations(bank.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn get_unit_location(env: &Env, unit_id: u64) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::UnitLocation(unit_id))
+        .unwrap_or(0)
+}
+
+pub fn set_unit_location(env: &Env, unit_id: u64, location_id: u32) {
+    let key = DataKey::UnitLocation(unit_id);
+    env.storage().persistent().set(&key, &location_id);
+    env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+pub fn increment_stock_transfer_id(env: &Env) -> u64 {
+    let key = DataKey::StockTransferCounter;
+    let next_id = env.storage().instance().get(&key).unwrap_or(0u64) + 1;
+    env.storage().instance().set(&key, &next_id);
+    next_id
+}
+
+pub fn set_stock_transfer(env: &Env, transfer: &StockTransfer) {
+    let key = DataKey::StockTransfer(transfer.id);
+    env.storage().persistent().set(&key, transfer);
+    env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+pub fn get_stock_transfer(env: &Env, id: u64) -> Option<StockTransfer> {
+    env.storage().persistent().get(&DataKey::StockTransfer(id))
+}
+
+pub fn get_open_restock_request(env: &Env, bank: &Address, blood_type: BloodType) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OpenRestockRequest(bank.clone(), blood_type))
+}
+
+pub fn set_open_restock_request(env: &Env, bank: &Address, blood_type: BloodType, request_id: u64) {
+    let key = DataKey::OpenRestockRequest(bank.clone(), blood_type);
+    env.storage().persistent().set(&key, &request_id);
+    env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+pub fn remove_open_restock_request(env: &Env, bank: &Address, blood_type: BloodType) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::OpenRestockRequest(bank.clone(), blood_type));
+}
+
+pub fn get_frozen_until(env: &Env, bank: &Address) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::FrozenUntil(bank.clone()))
+}
+
+pub fn set_frozen_until(env: &Env, bank: &Address, until: u64) {
+    let key = DataKey::FrozenUntil(bank.clone());
+    env.storage().persistent().set(&key, &until);
+    env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+pub fn remove_frozen_until(env: &Env, bank: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::FrozenUntil(bank.clone()));
+}
@@ -1,9 +1,12 @@
 use crate::storage;
-use crate::types::{BloodStatus, BloodType};
+use crate::types::{
+    AdjustmentReason, BloodStatus, BloodType, ReconciliationStatus, Role, StockAdjustment,
+    StockTransferStatus,
+};
 use crate::{InventoryContract, InventoryContractClient};
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    vec, Address, Env, String,
+    testutils::{Address as _, Events as _, Ledger},
+    vec, Address, Env, String, Symbol, TryFromVal,
 };
 
 /// Standard shelf life used by register_blood (35 days in seconds).
@@ -1729,3 +1732,588 @@ fn test_reserve_blood_fails_on_mixed_ownership() {
     // Bank1 tries to reserve both units (but only owns one)
     client.reserve_blood(&bank1, &vec![&env, id1, id2], &123, &3600);
 }
+
+#[test]
+fn test_identity_contract_blood_bank_role_gates_registration() {
+    let (env, admin, client, _) = create_test_contract();
+    env.ledger().set_timestamp(1000u64);
+
+    let identity_id = env.register(identity_contract::AccessControlContract, ());
+    let identity = identity_contract::AccessControlContractClient::new(&env, &identity_id);
+    identity.initialize(&admin);
+    client.set_identity_contract(&admin, &identity_id);
+    assert_eq!(client.get_identity_contract(), Some(identity_id));
+
+    // Locally authorized banks no longer qualify without the identity role
+    let bank = Address::generate(&env);
+    client.authorize_bank(&admin, &bank, &true);
+    let result = client.try_register_blood(&bank, &String::from_str(&env, "SN-ID-001"), &BloodType::OPositive, &450u32, &None);
+    assert_eq!(result, Err(Ok(crate::error::ContractError::NotAuthorizedBloodBank)));
+
+    identity.grant_role_with_expiry(&admin, &bank, &identity_contract::Role::BloodBank, &None);
+    let unit_id = client.register_blood(&bank, &String::from_str(&env, "SN-ID-001"), &BloodType::OPositive, &450u32, &None);
+    assert_eq!(client.get_blood_unit(&unit_id).bank_id, bank);
+}
+
+// ── Par level tests ───────────────────────────────────────────────────────────
+
+/// Number of events with topic `name` emitted by the last invocation.
+fn count_events(env: &Env, name: &str) -> usize {
+    let name = Symbol::new(env, name);
+    env.events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            topics
+                .get(0)
+                .and_then(|topic| Symbol::try_from_val(env, &topic).ok())
+                .map_or(false, |topic| topic == name)
+        })
+        .count()
+}
+
+#[test]
+fn test_stock_below_par_level_emits_stock_low() {
+    let (env, admin, client, _) = create_test_contract();
+    env.ledger().set_timestamp(1000u64);
+
+    let mut ids = vec![&env];
+    for serial in ["SN-PAR-1", "SN-PAR-2", "SN-PAR-3", "SN-PAR-4"] {
+        ids.push_back(client.register_blood(&admin, &String::from_str(&env, serial), &BloodType::OPositive, &450u32, &None));
+    }
+    client.set_par_level(&admin, &BloodType::OPositive, &3);
+    assert_eq!(client.get_par_level(&admin, &BloodType::OPositive), 3);
+    assert_eq!(client.get_available_stock(&admin, &BloodType::OPositive), 4);
+
+    // Still at par: no alert
+    client.update_status(&ids.get(0).unwrap(), &BloodStatus::Compromised, &admin, &None);
+    assert_eq!(count_events(&env, "stock_low"), 0);
+
+    client.update_status(&ids.get(1).unwrap(), &BloodStatus::Compromised, &admin, &None);
+    assert_eq!(client.get_available_stock(&admin, &BloodType::OPositive), 2);
+    assert_eq!(count_events(&env, "stock_low"), 1);
+
+    let reservation_id = client.reserve_blood(&admin, &vec![&env, ids.get(2).unwrap()], &1, &3600);
+    assert_eq!(client.get_available_stock(&admin, &BloodType::OPositive), 1);
+    assert_eq!(count_events(&env, "stock_low"), 1);
+
+    client.release_reservation(&admin, &reservation_id);
+    assert_eq!(client.get_available_stock(&admin, &BloodType::OPositive), 2);
+    assert_eq!(count_events(&env, "stock_low"), 0);
+}
+
+#[test]
+fn test_stock_severity_tiers() {
+    use crate::stock::severity;
+    use crate::types::StockSeverity;
+
+    assert_eq!(severity(5, 4), None);
+    assert_eq!(severity(4, 4), None);
+    assert_eq!(severity(3, 4), Some(StockSeverity::Low));
+    assert_eq!(severity(2, 4), Some(StockSeverity::Critical));
+    assert_eq!(severity(0, 0), None);
+}
+
+// ── Restock request tests ─────────────────────────────────────────────────────
+
+mod mock_requests {
+    use crate::types::{BloodType, RestockUrgency};
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env};
+
+    /// Records restock requests the inventory contract opens.
+    #[contract]
+    pub struct MockRequestsContract;
+
+    #[contractimpl]
+    impl MockRequestsContract {
+        pub fn create_restock_request(
+            env: Env,
+            _bank: Address,
+            _blood_type: BloodType,
+            deficit_units: u32,
+            urgency: RestockUrgency,
+        ) -> u64 {
+            let count = Self::count(env.clone()) + 1;
+            env.storage().instance().set(&symbol_short!("count"), &count);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("last"), &(deficit_units, urgency));
+            count
+        }
+
+        pub fn count(env: Env) -> u64 {
+            env.storage().instance().get(&symbol_short!("count")).unwrap_or(0)
+        }
+
+        pub fn last(env: Env) -> (u32, RestockUrgency) {
+            env.storage().instance().get(&symbol_short!("last")).unwrap()
+        }
+    }
+}
+
+#[test]
+fn test_falling_below_par_opens_one_restock_request() {
+    use crate::types::RestockUrgency;
+    use mock_requests::{MockRequestsContract, MockRequestsContractClient};
+
+    let (env, admin, client, _) = create_test_contract();
+    let requests_id = env.register(MockRequestsContract, ());
+    let requests = MockRequestsContractClient::new(&env, &requests_id);
+    client.set_requests_contract(&admin, &requests_id);
+    register_stock(&env, &client, &admin, &["SN-PAR-R1", "SN-PAR-R2", "SN-PAR-R3", "SN-PAR-R4"]);
+    client.set_par_level(&admin, &BloodType::ONegative, &3);
+
+    client.update_status(&1, &BloodStatus::Compromised, &admin, &None);
+    assert_eq!(requests.count(), 0);
+
+    client.update_status(&2, &BloodStatus::Compromised, &admin, &None);
+    assert_eq!(requests.count(), 1);
+    assert_eq!(requests.last(), (1, RestockUrgency::Routine));
+    assert_eq!(client.get_open_restock_request(&admin, &BloodType::ONegative), Some(1));
+
+    // Still below par, but a request is already open
+    client.update_status(&3, &BloodStatus::Compromised, &admin, &None);
+    assert_eq!(requests.count(), 1);
+
+    // Recovering to par closes the window; the next shortfall opens a new request
+    let restock = StockAdjustment {
+        location_id: 0,
+        blood_type: BloodType::ONegative,
+        delta: 5,
+        reason: AdjustmentReason::Received,
+    };
+    client.adjust_stock_batch(&admin, &vec![&env, restock.clone()]);
+    assert_eq!(client.get_open_restock_request(&admin, &BloodType::ONegative), None);
+
+    let wasted = StockAdjustment { delta: -6, reason: AdjustmentReason::Discarded, ..restock };
+    client.adjust_stock_batch(&admin, &vec![&env, wasted]);
+    assert_eq!(requests.count(), 2);
+    assert_eq!(requests.last(), (3, RestockUrgency::Critical));
+}
+
+#[test]
+fn test_restock_urgency_tiers() {
+    use crate::stock::restock_urgency;
+    use crate::types::RestockUrgency;
+
+    assert_eq!(restock_urgency(0, 4), RestockUrgency::Critical);
+    assert_eq!(restock_urgency(2, 4), RestockUrgency::Urgent);
+    assert_eq!(restock_urgency(3, 4), RestockUrgency::Routine);
+}
+
+// ── Stock reservation tests ───────────────────────────────────────────────────
+
+fn register_stock(env: &Env, client: &InventoryContractClient, bank: &Address, serials: &[&str]) {
+    for serial in serials {
+        client.register_blood(bank, &String::from_str(env, serial), &BloodType::ONegative, &450u32, &None);
+    }
+}
+
+#[test]
+fn test_stock_reservation_holds_available_stock() {
+    let (env, admin, client, _) = create_test_contract();
+    env.ledger().set_timestamp(1000u64);
+    register_stock(&env, &client, &admin, &["SN-RES-1", "SN-RES-2", "SN-RES-3", "SN-RES-4", "SN-RES-5"]);
+
+    let reservation_id = client.reserve_stock(&admin, &0, &BloodType::ONegative, &3, &42, &4600);
+    assert_eq!(client.get_on_hand_stock(&admin, &BloodType::ONegative), 5);
+    assert_eq!(client.get_reserved_stock(&admin, &BloodType::ONegative), 3);
+    assert_eq!(client.get_available_stock(&admin, &BloodType::ONegative), 2);
+    assert_eq!(client.get_stock_reservation(&reservation_id).request_id, 42);
+
+    // Only two units remain unpromised
+    let result = client.try_reserve_stock(&admin, &0, &BloodType::ONegative, &3, &43, &4600);
+    assert_eq!(result, Err(Ok(crate::error::ContractError::InsufficientStock)));
+
+    client.consume_stock_reservation(&admin, &reservation_id);
+    assert_eq!(client.get_on_hand_stock(&admin, &BloodType::ONegative), 2);
+    assert_eq!(client.get_reserved_stock(&admin, &BloodType::ONegative), 0);
+    assert_eq!(client.get_available_stock(&admin, &BloodType::ONegative), 2);
+
+    let result = client.try_release_stock_reservation(&admin, &reservation_id);
+    assert_eq!(result, Err(Ok(crate::error::ContractError::ReservationNotFound)));
+}
+
+#[test]
+fn test_expired_stock_reservations_are_released_by_keeper() {
+    let (env, admin, client, _) = create_test_contract();
+    env.ledger().set_timestamp(1000u64);
+    register_stock(&env, &client, &admin, &["SN-EXP-1", "SN-EXP-2", "SN-EXP-3", "SN-EXP-4"]);
+
+    let short = client.reserve_stock(&admin, &0, &BloodType::ONegative, &1, &1, &2000);
+    let long = client.reserve_stock(&admin, &0, &BloodType::ONegative, &2, &2, &9000);
+    assert_eq!(client.get_available_stock(&admin, &BloodType::ONegative), 1);
+
+    // Nothing is due yet
+    assert_eq!(client.expire_stock_reservations(&10), 0);
+
+    env.ledger().set_timestamp(2000u64);
+    let result = client.try_consume_stock_reservation(&admin, &short);
+    assert_eq!(result, Err(Ok(crate::error::ContractError::ReservationExpired)));
+
+    assert_eq!(client.expire_stock_reservations(&10), 1);
+    assert_eq!(client.get_available_stock(&admin, &BloodType::ONegative), 2);
+    assert!(client.try_get_stock_reservation(&short).is_err());
+    assert_eq!(client.get_stock_reservation(&long).quantity, 2);
+
+    // Another bank cannot release the remaining reservation
+    let outsider = Address::generate(&env);
+    let result = client.try_release_stock_reservation(&outsider, &long);
+    assert_eq!(result, Err(Ok(crate::error::ContractError::NotReservationOwner)));
+    client.release_stock_reservation(&admin, &long);
+    assert_eq!(client.get_available_stock(&admin, &BloodType::ONegative), 4);
+}
+
+// ── Reconciliation tests ──────────────────────────────────────────────────────
+
+#[test]
+fn test_reconciliation_within_threshold_applies_immediately() {
+    let (env, admin, client, _) = create_test_contract();
+    register_stock(&env, &client, &admin, &["SN-REC-1", "SN-REC-2", "SN-REC-3"]);
+    client.set_reconciliation_threshold(&admin, &1);
+
+    let counts = soroban_sdk::map![&env, (BloodType::ONegative, 2u32)];
+    let count_hash = soroban_sdk::BytesN::from_array(&env, &[7u8; 32]);
+    let report = client.submit_reconciliation(&admin, &0, &counts, &count_hash);
+
+    assert_eq!(report.status, ReconciliationStatus::Applied);
+    assert_eq!(report.total_discrepancy, 1);
+    assert_eq!(report.count_hash, count_hash);
+    let line = report.lines.get(0).unwrap();
+    assert_eq!((line.recorded, line.counted), (3, 2));
+    assert_eq!(client.get_on_hand_stock(&admin, &BloodType::ONegative), 2);
+    assert_eq!(client.get_reconciliation(&report.id), report);
+}
+
+#[test]
+fn test_reconciliation_above_threshold_needs_admin_approval() {
+    let (env, admin, client, _) = create_test_contract();
+    register_stock(&env, &client, &admin, &["SN-REC-A", "SN-REC-B"]);
+
+    let counts = soroban_sdk::map![
+        &env,
+        (BloodType::ONegative, 0u32),
+        (BloodType::APositive, 1u32)
+    ];
+    let count_hash = soroban_sdk::BytesN::from_array(&env, &[9u8; 32]);
+    let report = client.submit_reconciliation(&admin, &0, &counts, &count_hash);
+
+    assert_eq!(report.status, ReconciliationStatus::PendingApproval);
+    assert_eq!(report.total_discrepancy, 3);
+    // Stock is untouched until approval
+    assert_eq!(client.get_on_hand_stock(&admin, &BloodType::ONegative), 2);
+
+    let outsider = Address::generate(&env);
+    let result = client.try_approve_reconciliation(&outsider, &report.id);
+    assert_eq!(result, Err(Ok(crate::error::ContractError::Unauthorized)));
+
+    let approved = client.approve_reconciliation(&admin, &report.id);
+    assert_eq!(approved.status, ReconciliationStatus::Applied);
+    assert_eq!(client.get_on_hand_stock(&admin, &BloodType::ONegative), 0);
+    assert_eq!(client.get_on_hand_stock(&admin, &BloodType::APositive), 1);
+
+    let result = client.try_reject_reconciliation(&admin, &report.id);
+    assert_eq!(result, Err(Ok(crate::error::ContractError::InvalidStatus)));
+}
+
+// ── Stock adjustment tests ────────────────────────────────────────────────────
+
+fn adjustment(delta: i32, reason: AdjustmentReason) -> StockAdjustment {
+    StockAdjustment {
+        location_id: 0,
+        blood_type: BloodType::ONegative,
+        delta,
+        reason,
+    }
+}
+
+#[test]
+fn test_adjust_stock_batch_applies_all_entries() {
+    let (env, admin, client, _) = create_test_contract();
+
+    let adjustments = vec![
+        &env,
+        adjustment(10, AdjustmentReason::LisSync),
+        adjustment(-3, AdjustmentReason::Issued),
+        StockAdjustment {
+            location_id: 0,
+            blood_type: BloodType::APositive,
+            delta: 4,
+            reason: AdjustmentReason::Received,
+        },
+    ];
+    client.adjust_stock_batch(&admin, &adjustments);
+
+    assert_eq!(client.get_on_hand_stock(&admin, &BloodType::ONegative), 7);
+    assert_eq!(client.get_on_hand_stock(&admin, &BloodType::APositive), 4);
+    assert_eq!(count_events(&env, "stock_adjusted"), 1);
+}
+
+#[test]
+fn test_adjust_stock_batch_underflow_rolls_back() {
+    let (env, admin, client, _) = create_test_contract();
+    client.adjust_stock_batch(&admin, &vec![&env, adjustment(2, AdjustmentReason::Received)]);
+
+    let adjustments = vec![
+        &env,
+        adjustment(1, AdjustmentReason::Correction),
+        adjustment(-5, AdjustmentReason::Discarded),
+    ];
+    let result = client.try_adjust_stock_batch(&admin, &adjustments);
+    assert_eq!(result, Err(Ok(crate::error::ContractError::StockOutOfRange)));
+    assert_eq!(client.get_on_hand_stock(&admin, &BloodType::ONegative), 2);
+
+    // 2 + 2 * i32::MAX overflows u32
+    let overflow = adjustment(i32::MAX, AdjustmentReason::LisSync);
+    let result =
+        client.try_adjust_stock_batch(&admin, &vec![&env, overflow.clone(), overflow]);
+    assert_eq!(result, Err(Ok(crate::error::ContractError::StockOutOfRange)));
+}
+
+// ── Wastage tests ─────────────────────────────────────────────────────────────
+
+#[test]
+fn test_wastage_is_counted_per_month() {
+    // 2026-10-03 and 2026-11-02 (UTC)
+    let october = 1_791_000_000u64;
+    let november = october + 30 * 86_400;
+    let (env, admin, client, _) = create_test_contract();
+    env.ledger().set_timestamp(october);
+    register_stock(&env, &client, &admin, &["SN-WASTE-1", "SN-WASTE-2", "SN-WASTE-3", "SN-WASTE-4"]);
+
+    client.mark_expired(&1, &admin);
+    client.update_status(&2, &BloodStatus::Compromised, &admin, &None);
+    // Disposal of an already-wasted unit is not counted twice
+    client.dispose(&2, &admin, &None);
+
+    let report = client.get_wastage(&admin, &202610);
+    assert_eq!((report.registered, report.expired, report.discarded), (4, 1, 1));
+    assert_eq!(report.wastage_rate_bps, 5_000);
+    let a_positive = client.get_wastage_by_blood_type(&admin, &BloodType::APositive, &202610);
+    assert_eq!(a_positive.registered, 0);
+
+    env.ledger().set_timestamp(november);
+    let discard = StockAdjustment {
+        location_id: 0,
+        blood_type: BloodType::ONegative,
+        delta: -1,
+        reason: AdjustmentReason::Discarded,
+    };
+    client.adjust_stock_batch(&admin, &vec![&env, discard]);
+    let report = client.get_wastage(&admin, &202611);
+    assert_eq!((report.registered, report.discarded, report.wastage_rate_bps), (0, 1, 0));
+    assert_eq!(client.get_wastage(&admin, &202610).discarded, 1);
+
+    let result = client.try_get_wastage(&admin, &202613);
+    assert_eq!(result, Err(Ok(crate::error::ContractError::InvalidInput)));
+}
+
+#[test]
+fn test_wastage_period_of_timestamp() {
+    use crate::wastage::period_of;
+
+    assert_eq!(period_of(0), 197001);
+    assert_eq!(period_of(951_782_400), 200002); // 2000-02-29
+    assert_eq!(period_of(1_791_000_000), 202610);
+}
+
+// ── Expiry bucket tests ───────────────────────────────────────────────────────
+
+#[test]
+fn test_stock_by_expiry_bucket_tracks_freshness() {
+    let (env, admin, client, _) = create_test_contract();
+    let day = 86_400u64;
+    register_stock(&env, &client, &admin, &["SN-FRESH-A"]);
+    env.ledger().set_timestamp(22 * day);
+    register_stock(&env, &client, &admin, &["SN-FRESH-B"]);
+
+    let buckets = client.get_stock_by_expiry_bucket(&admin, &BloodType::ONegative);
+    assert_eq!(buckets.within_14d, 1);
+    assert_eq!(buckets.longer, 1);
+
+    env.ledger().set_timestamp(29 * day);
+    let buckets = client.get_stock_by_expiry_bucket(&admin, &BloodType::ONegative);
+    assert_eq!((buckets.within_7d, buckets.within_14d, buckets.longer), (1, 0, 1));
+
+    env.ledger().set_timestamp(34 * day);
+    let buckets = client.get_stock_by_expiry_bucket(&admin, &BloodType::ONegative);
+    assert_eq!((buckets.within_48h, buckets.within_7d), (1, 0));
+
+    // Units leaving Available leave their bucket
+    client.update_status(&1, &BloodStatus::Compromised, &admin, &None);
+    let buckets = client.get_stock_by_expiry_bucket(&admin, &BloodType::ONegative);
+    assert_eq!(buckets.within_48h, 0);
+    assert_eq!(buckets.longer, 1);
+}
+
+// ── Stock transfer tests ──────────────────────────────────────────────────────
+
+#[test]
+fn test_stock_transfer_moves_counts_only_on_acknowledgement() {
+    let (env, admin, client, _) = create_test_contract();
+    let receiver = Address::generate(&env);
+    client.authorize_bank(&admin, &receiver, &true);
+    register_stock(&env, &client, &admin, &["SN-XFER-1", "SN-XFER-2", "SN-XFER-3"]);
+
+    let transfer_id = client.initiate_stock_transfer(&admin, &receiver, &BloodType::ONegative, &2);
+    // Held at the sender, not yet at the receiver
+    assert_eq!(client.get_on_hand_stock(&admin, &BloodType::ONegative), 3);
+    assert_eq!(client.get_available_stock(&admin, &BloodType::ONegative), 1);
+    assert_eq!(client.get_on_hand_stock(&receiver, &BloodType::ONegative), 0);
+
+    let result = client.try_acknowledge_stock_transfer(&admin, &transfer_id);
+    assert_eq!(result, Err(Ok(crate::error::ContractError::Unauthorized)));
+
+    client.acknowledge_stock_transfer(&receiver, &transfer_id);
+    assert_eq!(client.get_on_hand_stock(&admin, &BloodType::ONegative), 1);
+    assert_eq!(client.get_available_stock(&admin, &BloodType::ONegative), 1);
+    assert_eq!(client.get_on_hand_stock(&receiver, &BloodType::ONegative), 2);
+    assert_eq!(
+        client.get_stock_transfer(&transfer_id).status,
+        StockTransferStatus::Acknowledged
+    );
+
+    let result = client.try_cancel_stock_transfer(&admin, &transfer_id);
+    assert_eq!(result, Err(Ok(crate::error::ContractError::InvalidStatus)));
+}
+
+#[test]
+fn test_refused_stock_transfer_returns_quantity_to_sender() {
+    let (env, admin, client, _) = create_test_contract();
+    let receiver = Address::generate(&env);
+    client.authorize_bank(&admin, &receiver, &true);
+    register_stock(&env, &client, &admin, &["SN-XFER-A", "SN-XFER-B"]);
+
+    let result = client.try_initiate_stock_transfer(&admin, &receiver, &BloodType::ONegative, &3);
+    assert_eq!(result, Err(Ok(crate::error::ContractError::InsufficientStock)));
+
+    let transfer_id = client.initiate_stock_transfer(&admin, &receiver, &BloodType::ONegative, &2);
+    client.cancel_stock_transfer(&receiver, &transfer_id);
+    assert_eq!(client.get_available_stock(&admin, &BloodType::ONegative), 2);
+    assert_eq!(client.get_on_hand_stock(&receiver, &BloodType::ONegative), 0);
+    assert_eq!(client.get_stock_transfer(&transfer_id).status, StockTransferStatus::Cancelled);
+}
+
+// ── Location tests ────────────────────────────────────────────────────────────
+
+#[test]
+fn test_stock_is_kept_per_location_and_rolled_up_per_bank() {
+    let (env, admin, client, _) = create_test_contract();
+    register_stock(&env, &client, &admin, &["SN-LOC-1", "SN-LOC-2", "SN-LOC-3"]);
+    let fridge = soroban_sdk::BytesN::from_array(&env, &[1u8; 32]);
+    client.register_location(&admin, &7, &fridge);
+    assert_eq!(client.get_bank_locations(&admin), vec![&env, 7u32]);
+    assert_eq!(client.get_location(&admin, &7).name_hash, fridge);
+
+    let result = client.try_register_location(&admin, &7, &fridge);
+    assert_eq!(result, Err(Ok(crate::error::ContractError::AlreadyExists)));
+
+    client.move_unit(&admin, &1, &7);
+    assert_eq!(client.get_unit_location(&1), 7);
+    assert_eq!(client.get_location_on_hand_stock(&admin, &0, &BloodType::ONegative), 2);
+    assert_eq!(client.get_location_on_hand_stock(&admin, &7, &BloodType::ONegative), 1);
+    assert_eq!(client.get_on_hand_stock(&admin, &BloodType::ONegative), 3);
+
+    // A reservation only draws on its own location
+    let result = client.try_reserve_stock(&admin, &7, &BloodType::ONegative, &2, &1, &4600);
+    assert_eq!(result, Err(Ok(crate::error::ContractError::InsufficientStock)));
+    client.reserve_stock(&admin, &7, &BloodType::ONegative, &1, &1, &4600);
+    assert_eq!(client.get_location_available_stock(&admin, &7, &BloodType::ONegative), 0);
+    assert_eq!(client.get_available_stock(&admin, &BloodType::ONegative), 2);
+
+    let result = client.try_reserve_stock(&admin, &8, &BloodType::ONegative, &1, &1, &4600);
+    assert_eq!(result, Err(Ok(crate::error::ContractError::NotFound)));
+
+    // The moved unit leaves stock from the location it is stored at
+    client.update_status(&1, &BloodStatus::Compromised, &admin, &None);
+    assert_eq!(client.get_location_on_hand_stock(&admin, &7, &BloodType::ONegative), 0);
+    assert_eq!(client.get_on_hand_stock(&admin, &BloodType::ONegative), 2);
+}
+
+#[test]
+fn test_coordinator_reserves_and_releases_stock_for_orders() {
+    let (env, admin, client, _) = create_test_contract();
+    env.ledger().set_timestamp(1000u64);
+    register_stock(&env, &client, &admin, &["SN-ORD-1", "SN-ORD-2"]);
+    let coordinator = Address::generate(&env);
+
+    let result = client.try_reserve_stock_for_order(&admin, &BloodType::ONegative, &1, &7, &4600);
+    assert_eq!(result, Err(Ok(crate::error::ContractError::Unauthorized)));
+
+    client.set_coordinator_contract(&admin, &coordinator);
+    let reservation_id = client.reserve_stock_for_order(&admin, &BloodType::ONegative, &2, &7, &4600);
+    let reservation = client.get_stock_reservation(&reservation_id);
+    assert_eq!((reservation.location_id, reservation.request_id), (0, 7));
+    assert_eq!(client.get_available_stock(&admin, &BloodType::ONegative), 0);
+
+    client.release_stock_reservation(&coordinator, &reservation_id);
+    assert_eq!(client.get_available_stock(&admin, &BloodType::ONegative), 2);
+}
+
+// ── Freeze tests ──────────────────────────────────────────────────────────────
+
+#[test]
+fn test_frozen_bank_rejects_stock_changes_until_deadline() {
+    let (env, admin, client, _) = create_test_contract();
+    env.ledger().set_timestamp(1000u64);
+    register_stock(&env, &client, &admin, &["SN-FRZ-1", "SN-FRZ-2"]);
+    let regulator = Address::generate(&env);
+    client.grant_role(&admin, &regulator, &Role::Regulator);
+
+    client.freeze_inventory(&regulator, &admin, &5000);
+    assert_eq!(count_events(&env, "inventory_frozen"), 1);
+    assert!(client.is_inventory_frozen(&admin));
+
+    let frozen = Err(Ok(crate::error::ContractError::InventoryFrozen));
+    let serial = String::from_str(&env, "SN-FRZ-3");
+    assert_eq!(
+        client.try_register_blood(&admin, &serial, &BloodType::ONegative, &450u32, &None),
+        frozen
+    );
+    assert_eq!(client.try_update_status(&1, &BloodStatus::Compromised, &admin, &None), frozen);
+    assert_eq!(
+        client.try_reserve_stock(&admin, &0, &BloodType::ONegative, &1, &1, &4600),
+        frozen
+    );
+    let correction = StockAdjustment {
+        location_id: 0,
+        blood_type: BloodType::ONegative,
+        delta: 1,
+        reason: AdjustmentReason::Correction,
+    };
+    assert_eq!(client.try_adjust_stock_batch(&admin, &vec![&env, correction.clone()]), frozen);
+    assert_eq!(client.get_on_hand_stock(&admin, &BloodType::ONegative), 2);
+
+    // The freeze lapses at its deadline without another call
+    env.ledger().set_timestamp(5000u64);
+    assert!(!client.is_inventory_frozen(&admin));
+    client.adjust_stock_batch(&admin, &vec![&env, correction]);
+    assert_eq!(count_events(&env, "inventory_unfrozen"), 1);
+    assert_eq!(client.get_on_hand_stock(&admin, &BloodType::ONegative), 3);
+}
+
+#[test]
+fn test_freeze_requires_admin_or_regulator() {
+    let (env, admin, client, _) = create_test_contract();
+    env.ledger().set_timestamp(1000u64);
+    let bank = Address::generate(&env);
+
+    let result = client.try_freeze_inventory(&bank, &admin, &5000);
+    assert_eq!(result, Err(Ok(crate::error::ContractError::InsufficientRolePermission)));
+    let result = client.try_freeze_inventory(&admin, &bank, &1000);
+    assert_eq!(result, Err(Ok(crate::error::ContractError::InvalidTimestamp)));
+
+    client.freeze_inventory(&admin, &bank, &5000);
+    client.unfreeze_inventory(&admin, &bank);
+    assert!(!client.is_inventory_frozen(&bank));
+    let result = client.try_unfreeze_inventory(&admin, &bank);
+    assert_eq!(result, Err(Ok(crate::error::ContractError::NotFound)));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #102)")]
+fn test_set_identity_contract_requires_admin() {
+    let (env, _admin, client, _) = create_test_contract();
+    let outsider = Address::generate(&env);
+    client.set_identity_contract(&outsider, &Address::generate(&env));
+}
@@ -1,5 +1,5 @@
 use crate::error::ContractError;
-use soroban_sdk::{contractevent, contracttype, Address, Map, String, Symbol, Vec};
+use soroban_sdk::{contractevent, contracttype, Address, BytesN, Map, String, Symbol, Vec};
 
 /// Blood type enumeration supporting all major blood groups
 ///
@@ -259,6 +259,8 @@ pub enum Role {
     Rider,
     /// Hospital staff - can mark units as Delivered
     Hospital,
+    /// Regulator - can freeze a bank's inventory during an audit; cannot move units
+    Regulator,
 }
 
 /// Storage key types for efficient querying
@@ -319,6 +321,80 @@ pub enum DataKey {
     /// Address of the authoritative HealthChainContract (BloodUnitRegistry)
     /// for cross-contract state synchronisation.
     RegistryContractId,
+
+    /// Address of the identity contract used for blood bank role checks.
+    IdentityContractId,
+
+    /// Address of the requests contract that receives automatic restock requests.
+    RequestsContractId,
+
+    /// Address of the coordinator allowed to reserve stock for its orders.
+    CoordinatorContractId,
+
+    /// Registered/expired/discarded counts per (bank, blood type, YYYYMM period)
+    Wastage(Address, BloodType, u32),
+
+    /// Deadline until which a bank's inventory is frozen
+    FrozenUntil(Address),
+
+    /// Restock request opened for a (bank, blood type) and not yet covered by
+    /// recovered stock
+    OpenRestockRequest(Address, BloodType),
+
+    /// Units on hand per (bank, location, blood type)
+    OnHandStock(Address, u32, BloodType),
+
+    /// Units promised to open stock reservations per (bank, location, blood type)
+    ReservedStock(Address, u32, BloodType),
+
+    /// Storage site registered by a bank, by (bank, location ID)
+    Location(Address, u32),
+
+    /// Location IDs registered by a bank
+    BankLocations(Address),
+
+    /// Location a unit is stored at; absent means the default location
+    UnitLocation(u64),
+
+    /// Available units per (bank, blood type, expiry day since epoch)
+    ExpiryStock(Address, BloodType, u64),
+
+    /// Inter-bank stock transfer by ID
+    StockTransfer(u64),
+
+    /// Stock transfer counter
+    StockTransferCounter,
+
+    /// Quantity reservation by ID
+    StockReservation(u64),
+
+    /// Stock reservation counter
+    StockReservationCounter,
+
+    /// IDs of open stock reservations, walked by `expire_stock_reservations`
+    OpenStockReservations,
+
+    /// Reconciliation report by ID
+    Reconciliation(u64),
+
+    /// Reconciliation counter
+    ReconciliationCounter,
+
+    /// Total discrepancy (units) a reconciliation may carry without admin approval
+    ReconciliationThreshold,
+
+    /// Minimum available units a bank wants to keep per blood type
+    ParLevel(Address, BloodType),
+}
+
+/// How far available stock has fallen below a bank's par level.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StockSeverity {
+    /// Below par level
+    Low,
+    /// At or below half the par level
+    Critical,
 }
 
 /// Reservation record for blood units locked for a specific requester
@@ -333,6 +409,180 @@ pub struct Reservation {
     pub request_id: u64,
 }
 
+/// Available stock grouped by time left before expiry. Buckets do not
+/// overlap: `within_7d` counts units with 2–6 days left, and so on.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpiryBuckets {
+    /// Expiring today or tomorrow
+    pub within_48h: u32,
+    /// Expiring in 2–6 days
+    pub within_7d: u32,
+    /// Expiring in 7–13 days
+    pub within_14d: u32,
+    /// 14 days or more left
+    pub longer: u32,
+}
+
+/// Urgency of an automatic restock request. Mirrors `Urgency` in the requests
+/// contract, which decodes it by variant name.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RestockUrgency {
+    Critical,
+    Urgent,
+    Routine,
+    Scheduled,
+}
+
+/// Units counted for one (bank, blood type, month).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WastageCounts {
+    pub registered: u32,
+    /// Units that became `Expired`
+    pub expired: u32,
+    /// Units that became `Compromised`, plus stock removed by `Discarded`
+    /// adjustments
+    pub discarded: u32,
+}
+
+/// Wastage for a bank over one calendar month.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WastageReport {
+    /// Month as `YYYYMM` (UTC)
+    pub period: u32,
+    pub registered: u32,
+    pub expired: u32,
+    pub discarded: u32,
+    /// (expired + discarded) / registered, in basis points
+    pub wastage_rate_bps: u32,
+}
+
+/// Where an inter-bank stock transfer stands.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StockTransferStatus {
+    /// Quantity is held at the sender until the receiver acknowledges it
+    Pending,
+    /// Receiver confirmed receipt; counts have moved
+    Acknowledged,
+    /// Withdrawn by the sender or refused by the receiver
+    Cancelled,
+}
+
+/// Count-based movement of stock from one bank to another.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StockTransfer {
+    pub id: u64,
+    pub from_bank: Address,
+    pub to_bank: Address,
+    pub blood_type: BloodType,
+    pub quantity: u32,
+    pub status: StockTransferStatus,
+    pub initiated_at: u64,
+    /// Set once the transfer is acknowledged or cancelled
+    pub resolved_at: Option<u64>,
+}
+
+/// Storage site (fridge, satellite depot) belonging to a bank.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Location {
+    pub bank_id: Address,
+    pub location_id: u32,
+    /// Hash of the site name, kept off-chain
+    pub name_hash: BytesN<32>,
+    pub registered_at: u64,
+}
+
+/// Quantity of a blood type promised to a request but not yet shipped.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StockReservation {
+    pub id: u64,
+    pub bank_id: Address,
+    /// Location the stock is held at
+    pub location_id: u32,
+    pub blood_type: BloodType,
+    pub quantity: u32,
+    /// Request the stock is held for
+    pub request_id: u64,
+    pub created_at: u64,
+    /// Once reached, anyone may release the reservation
+    pub expires_at: u64,
+}
+
+/// Where a reconciliation stands.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReconciliationStatus {
+    /// Counted stock has replaced the recorded stock
+    Applied,
+    /// Discrepancy exceeds the threshold; waiting for the admin
+    PendingApproval,
+    /// Refused by the admin; recorded stock was left unchanged
+    Rejected,
+}
+
+/// Recorded versus physically counted stock for one blood type.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReconciliationLine {
+    pub blood_type: BloodType,
+    /// On-hand stock on chain at submission
+    pub recorded: u32,
+    pub counted: u32,
+}
+
+/// Diff report produced by `submit_reconciliation`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReconciliationReport {
+    pub id: u64,
+    pub bank_id: Address,
+    /// Location that was counted
+    pub location_id: u32,
+    /// Hash of the signed physical count document kept off-chain
+    pub count_hash: BytesN<32>,
+    pub lines: Vec<ReconciliationLine>,
+    /// Sum of |counted − recorded| over all lines
+    pub total_discrepancy: u32,
+    pub status: ReconciliationStatus,
+    pub submitted_at: u64,
+    /// Set once the report is applied or rejected
+    pub resolved_at: Option<u64>,
+}
+
+/// Why a bank changed its on-hand stock outside the unit lifecycle.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AdjustmentReason {
+    /// Stock received from a supplier or another bank
+    Received,
+    /// Stock issued to a hospital
+    Issued,
+    /// Stock discarded (damaged, expired, failed screening)
+    Discarded,
+    /// Manual correction of a counting error
+    Correction,
+    /// Sync from the bank's laboratory information system
+    LisSync,
+}
+
+/// One entry of `adjust_stock_batch`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StockAdjustment {
+    pub location_id: u32,
+    pub blood_type: BloodType,
+    /// Units added (positive) or removed (negative)
+    pub delta: i32,
+    pub reason: AdjustmentReason,
+}
+
 #[contractevent(topics = ["blood_registered"])]
 #[derive(Clone, Debug)]
 pub struct BloodRegisteredEvent {
@@ -2,7 +2,7 @@ use crate::error::ContractError;
 use crate::types::{BloodStatus, BloodType};
 
 /// Valid ABO+Rh blood types
-const VALID_BLOOD_TYPES: [BloodType; 8] = [
+pub(crate) const VALID_BLOOD_TYPES: [BloodType; 8] = [
     BloodType::APositive,
     BloodType::ANegative,
     BloodType::BPositive,
@@ -0,0 +1,127 @@
+//! Monthly wastage counters.
+//!
+//! Units registered, expired and discarded are counted per
+//! `(bank, blood_type, period)`, where a period is a calendar month written
+//! as `YYYYMM` (UTC). Counters are bumped as units move, so reports never scan
+//! a bank's units.
+
+use crate::storage::{SECONDS_PER_DAY, TTL_EXTEND_TO, TTL_THRESHOLD};
+use crate::types::{BloodStatus, BloodType, BloodUnit, DataKey, WastageCounts, WastageReport};
+use crate::validation::VALID_BLOOD_TYPES;
+use soroban_sdk::{Address, Env};
+
+/// Calendar month (`YYYYMM`, UTC) containing `timestamp`.
+pub fn period_of(timestamp: u64) -> u32 {
+    // Civil-from-days conversion (proleptic Gregorian, March-based years)
+    let days = timestamp / SECONDS_PER_DAY + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    (year * 100 + month) as u32
+}
+
+/// `YYYYMM` with a month between 1 and 12.
+pub fn is_valid_period(period: u32) -> bool {
+    (1..=12).contains(&(period % 100))
+}
+
+fn get_counts(env: &Env, bank: &Address, blood_type: BloodType, period: u32) -> WastageCounts {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Wastage(bank.clone(), blood_type, period))
+        .unwrap_or(WastageCounts {
+            registered: 0,
+            expired: 0,
+            discarded: 0,
+        })
+}
+
+fn update_counts(
+    env: &Env,
+    bank: &Address,
+    blood_type: BloodType,
+    update: impl FnOnce(&mut WastageCounts),
+) {
+    let period = period_of(env.ledger().timestamp());
+    let mut counts = get_counts(env, bank, blood_type, period);
+    update(&mut counts);
+    let key = DataKey::Wastage(bank.clone(), blood_type, period);
+    env.storage().persistent().set(&key, &counts);
+    env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+pub fn on_register(env: &Env, unit: &BloodUnit) {
+    update_counts(env, &unit.bank_id, unit.blood_type, |counts| {
+        counts.registered = counts.registered.saturating_add(1);
+    });
+}
+
+/// Count a unit as wasted when it becomes `Expired` or `Compromised`. Its
+/// later disposal is not counted again.
+pub fn on_status_change(env: &Env, unit: &BloodUnit, old_status: BloodStatus) {
+    if old_status == unit.status {
+        return;
+    }
+    match unit.status {
+        BloodStatus::Expired => update_counts(env, &unit.bank_id, unit.blood_type, |counts| {
+            counts.expired = counts.expired.saturating_add(1);
+        }),
+        BloodStatus::Compromised => update_counts(env, &unit.bank_id, unit.blood_type, |counts| {
+            counts.discarded = counts.discarded.saturating_add(1);
+        }),
+        _ => {}
+    }
+}
+
+/// Count stock removed by a `Discarded` adjustment.
+pub fn record_discarded(env: &Env, bank: &Address, blood_type: BloodType, quantity: u32) {
+    update_counts(env, bank, blood_type, |counts| {
+        counts.discarded = counts.discarded.saturating_add(quantity);
+    });
+}
+
+/// Wastage for one blood type, or summed over all blood types when `None`.
+pub fn report(
+    env: &Env,
+    bank: &Address,
+    blood_type: Option<BloodType>,
+    period: u32,
+) -> WastageReport {
+    let mut total = WastageCounts {
+        registered: 0,
+        expired: 0,
+        discarded: 0,
+    };
+    for candidate in VALID_BLOOD_TYPES {
+        if blood_type.is_some_and(|wanted| wanted != candidate) {
+            continue;
+        }
+        let counts = get_counts(env, bank, candidate, period);
+        total.registered = total.registered.saturating_add(counts.registered);
+        total.expired = total.expired.saturating_add(counts.expired);
+        total.discarded = total.discarded.saturating_add(counts.discarded);
+    }
+
+    let wasted = u64::from(total.expired) + u64::from(total.discarded);
+    let wastage_rate_bps = if total.registered == 0 {
+        0
+    } else {
+        (wasted * 10_000 / u64::from(total.registered)).min(u64::from(u32::MAX)) as u32
+    };
+    WastageReport {
+        period,
+        registered: total.registered,
+        expired: total.expired,
+        discarded: total.discarded,
+        wastage_rate_bps,
+    }
+}
@@ -62,6 +62,35 @@ fn dispute_reason_to_code(reason: DisputeReason) -> u32 {
     }
 }
 
+/// How escrow releases are split. The rider and platform shares are taken
+/// from the released amount; the bank (the payee) receives the rest.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeSchedule {
+    /// Rider share in basis points; at most `MAX_RIDER_FEE_BPS`.
+    pub rider_bps: u32,
+    /// Platform fee in basis points; at most `MAX_PLATFORM_FEE_BPS`.
+    pub platform_bps: u32,
+    /// Receives the platform fee.
+    pub platform: Address,
+}
+
+/// Per-recipient amounts paid out when an escrow was released.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Settlement {
+    pub payment_id: u64,
+    pub bank: Address,
+    pub bank_amount: i128,
+    /// `None` when no rider was assigned; the rider share then goes to the bank.
+    pub rider: Option<Address>,
+    pub rider_amount: i128,
+    /// `None` when no fee schedule was set.
+    pub platform: Option<Address>,
+    pub platform_amount: i128,
+    pub settled_at: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PaymentStats {
@@ -141,6 +170,15 @@ pub enum Error {
     InvalidVestingSchedule = 518,
     /// Arithmetic overflow detected in running totals.
     Overflow = 518,
+    /// No coordinator contract has been set with `set_coordinator`.
+    CoordinatorNotConfigured = 519,
+    /// A fee schedule share exceeds its cap.
+    FeeAboveCap = 520,
+    /// Payment is not in the Pending state required to lock it.
+    PaymentNotPending = 521,
+    /// Payment is a coordinator order escrow, which only the coordinator
+    /// can settle.
+    OrderEscrow = 522,
 }
 
 // ── Storage keys ───────────────────────────────────────────────────────────────
@@ -158,6 +196,14 @@ const REQ_CONTRACT: soroban_sdk::Symbol = symbol_short!("REQ_CTR");
 const DEFAULT_DISPUTE_TIMEOUT_SECS: u64 = 7 * 24 * 3600;
 /// Instance storage key for the dispute timeout override.
 const DISPUTE_TIMEOUT: soroban_sdk::Symbol = symbol_short!("DISP_TO");
+/// Instance storage key for the coordinator contract address (optional).
+const COORDINATOR: soroban_sdk::Symbol = symbol_short!("COORD");
+/// Instance storage key for the fee schedule (optional).
+const FEE_SCHEDULE: soroban_sdk::Symbol = symbol_short!("FEES");
+/// Highest rider share a fee schedule may set: 20%.
+const MAX_RIDER_FEE_BPS: u32 = 2_000;
+/// Highest platform fee a fee schedule may set: 10%.
+const MAX_PLATFORM_FEE_BPS: u32 = 1_000;
 
 /// Persistent storage TTL constants (in ledgers; one ledger ≈ 5 s).
 /// Entries are bumped to PERSISTENT_BUMP_TO whenever their remaining TTL
@@ -313,6 +359,146 @@ fn remove_from_request_index(env: &Env, request_id: u64) {
         .remove(&req_idx_key(request_id));
 }
 
+/// Persistent key for the escrow payment IDs opened for a coordinator order,
+/// one per payee.
+fn order_escrow_key(order_id: u64) -> (u64, &'static str) {
+    (order_id, "oe")
+}
+
+fn get_order_escrows(env: &Env, order_id: u64) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&order_escrow_key(order_id))
+        .unwrap_or(Vec::new(env))
+}
+
+fn set_order_escrows(env: &Env, order_id: u64, ids: &Vec<u64>) {
+    let key = order_escrow_key(order_id);
+    env.storage().persistent().set(&key, ids);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_BUMP_THRESHOLD, PERSISTENT_BUMP_TO);
+}
+
+/// Persistent key for the coordinator order an escrow was opened for.
+fn escrow_order_key(payment_id: u64) -> (u64, &'static str) {
+    (payment_id, "ord")
+}
+
+fn escrow_order(env: &Env, payment_id: u64) -> Option<u64> {
+    env.storage().persistent().get(&escrow_order_key(payment_id))
+}
+
+fn set_escrow_order(env: &Env, payment_id: u64, order_id: u64) {
+    let key = escrow_order_key(payment_id);
+    env.storage().persistent().set(&key, &order_id);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_BUMP_THRESHOLD, PERSISTENT_BUMP_TO);
+}
+
+/// Refuse order escrows in the request payment entrypoints, so their funds
+/// and status only move through the coordinator.
+fn require_not_order_escrow(env: &Env, payment_id: u64) -> Result<(), Error> {
+    if escrow_order(env, payment_id).is_some() {
+        return Err(Error::OrderEscrow);
+    }
+    Ok(())
+}
+
+fn rider_key(payment_id: u64) -> (u64, &'static str) {
+    (payment_id, "rdr")
+}
+
+fn settlement_key(payment_id: u64) -> (u64, &'static str) {
+    (payment_id, "stl")
+}
+
+fn fee_snapshot_key(payment_id: u64) -> (u64, &'static str) {
+    (payment_id, "fee")
+}
+
+/// Pin the current fee schedule to an escrow as it is locked, so a later
+/// `set_fee_schedule` does not change the split of funds already held.
+fn snapshot_fees(env: &Env, payment_id: u64) {
+    let fees: Option<FeeSchedule> = env.storage().instance().get(&FEE_SCHEDULE);
+    if let Some(fees) = fees {
+        let key = fee_snapshot_key(payment_id);
+        env.storage().persistent().set(&key, &fees);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_BUMP_THRESHOLD, PERSISTENT_BUMP_TO);
+    }
+}
+
+/// Pay a released escrow out of the contract, split by the fee schedule in
+/// force when it was locked, and record the settlement. Shares are rounded
+/// down; the bank receives the remainder.
+fn pay_out(env: &Env, payment: &Payment) -> Result<Settlement, Error> {
+    let token_addr = payment.token.clone().ok_or(Error::NotEscrowPayment)?;
+    let token_client = token::Client::new(env, &token_addr);
+    let fees: Option<FeeSchedule> = env.storage().persistent().get(&fee_snapshot_key(payment.id));
+    let rider: Option<Address> = env.storage().persistent().get(&rider_key(payment.id));
+    let share = |bps: u32| {
+        payment
+            .amount
+            .checked_mul(i128::from(bps))
+            .and_then(|scaled| scaled.checked_div(10_000))
+            .ok_or(Error::Overflow)
+    };
+
+    let (platform, platform_amount) = match &fees {
+        Some(fees) => (Some(fees.platform.clone()), share(fees.platform_bps)?),
+        None => (None, 0),
+    };
+    let rider_amount = match (&fees, &rider) {
+        (Some(fees), Some(_)) => share(fees.rider_bps)?,
+        _ => 0,
+    };
+    let bank_amount = payment
+        .amount
+        .checked_sub(rider_amount)
+        .and_then(|rest| rest.checked_sub(platform_amount))
+        .ok_or(Error::Overflow)?;
+
+    let contract = env.current_contract_address();
+    token_client.transfer(&contract, &payment.payee, &bank_amount);
+    if let Some(rider) = &rider {
+        if rider_amount > 0 {
+            token_client.transfer(&contract, rider, &rider_amount);
+        }
+    }
+    if let Some(platform) = &platform {
+        if platform_amount > 0 {
+            token_client.transfer(&contract, platform, &platform_amount);
+        }
+    }
+
+    let settlement = Settlement {
+        payment_id: payment.id,
+        bank: payment.payee.clone(),
+        bank_amount,
+        rider,
+        rider_amount,
+        platform,
+        platform_amount,
+        settled_at: env.ledger().timestamp(),
+    };
+    let key = settlement_key(payment.id);
+    env.storage().persistent().set(&key, &settlement);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_BUMP_THRESHOLD, PERSISTENT_BUMP_TO);
+    PaymentSettled {
+        payment_id: payment.id,
+        bank_amount,
+        rider_amount,
+        platform_amount,
+    }
+    .publish(env);
+    Ok(settlement)
+}
+
 /// Persistent key for the ordered list of payment IDs associated with a request.
 /// Separate from req_idx_key (which maps request → current active payment).
 fn request_timeline_key(request_id: u64) -> (u64, &'static str) {
@@ -498,6 +684,21 @@ pub struct PaymentReleased {
     pub amount: i128,
 }
 
+#[contractevent(topics = ["payment", "settled"], data_format = "vec")]
+pub struct PaymentSettled {
+    pub payment_id: u64,
+    pub bank_amount: i128,
+    pub rider_amount: i128,
+    pub platform_amount: i128,
+}
+
+#[contractevent(topics = ["payment", "fees"], data_format = "vec")]
+pub struct FeeScheduleSet {
+    pub rider_bps: u32,
+    pub platform_bps: u32,
+    pub platform: Address,
+}
+
 #[contractevent(topics = ["payment", "hosp_ok"], data_format = "single-value")]
 pub struct PaymentHospConfirmed {
     pub payment_id: u64,
@@ -776,6 +977,7 @@ impl PaymentContract {
         index_by_request(&env, request_id, id);
         timeline_append(&env, request_id, id);
         update_stats_on_transition(&env, amount, PaymentStatus::Pending, PaymentStatus::Locked)?;
+        snapshot_fees(&env, id);
 
         PaymentEscrowed { payment_id: id }.publish(&env);
 
@@ -796,6 +998,7 @@ impl PaymentContract {
         Self::require_admin(&env, &caller)?;
 
         let mut payment = load_payment(&env, payment_id).ok_or(Error::PaymentNotFound)?;
+        require_not_order_escrow(&env, payment_id)?;
 
         if payment.status != PaymentStatus::Locked {
             return Err(Error::PaymentNotLocked);
@@ -816,13 +1019,7 @@ impl PaymentContract {
         }
 
         // Both parties confirmed - release payment
-        let token_addr = payment.token.clone().ok_or(Error::NotEscrowPayment)?;
-        let token_client = token::Client::new(&env, &token_addr);
-        token_client.transfer(
-            &env.current_contract_address(),
-            &payment.payee,
-            &payment.amount,
-        );
+        pay_out(&env, &payment)?;
 
         let old_status = payment.status;
         payment.status = PaymentStatus::Released;
@@ -842,6 +1039,276 @@ impl PaymentContract {
         Ok(())
     }
 
+    /// Set the coordinator contract allowed to release and refund order
+    /// escrows. Admin only.
+    pub fn set_coordinator(env: Env, admin: Address, coordinator: Address) -> Result<(), Error> {
+        admin.require_auth();
+        Self::require_admin(&env, &admin)?;
+        env.storage().instance().set(&COORDINATOR, &coordinator);
+        Ok(())
+    }
+
+    pub fn get_coordinator(env: Env) -> Option<Address> {
+        env.storage().instance().get(&COORDINATOR)
+    }
+
+    /// Set how escrow releases are split between bank, rider and platform.
+    /// Admin only. Escrows keep the schedule in force when they were locked.
+    pub fn set_fee_schedule(env: Env, admin: Address, schedule: FeeSchedule) -> Result<(), Error> {
+        admin.require_auth();
+        Self::require_admin(&env, &admin)?;
+        if schedule.rider_bps > MAX_RIDER_FEE_BPS || schedule.platform_bps > MAX_PLATFORM_FEE_BPS {
+            return Err(Error::FeeAboveCap);
+        }
+        env.storage().instance().set(&FEE_SCHEDULE, &schedule);
+        FeeScheduleSet {
+            rider_bps: schedule.rider_bps,
+            platform_bps: schedule.platform_bps,
+            platform: schedule.platform,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    pub fn get_fee_schedule(env: Env) -> Option<FeeSchedule> {
+        env.storage().instance().get(&FEE_SCHEDULE)
+    }
+
+    /// Record the rider delivering a locked escrow's units, so the rider
+    /// share is paid to them on release. Admin or coordinator only.
+    pub fn assign_rider(env: Env, caller: Address, payment_id: u64, rider: Address) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env)?;
+        let coordinator: Option<Address> = env.storage().instance().get(&COORDINATOR);
+        if !Self::is_admin(&env, &caller) && coordinator != Some(caller) {
+            return Err(Error::Unauthorized);
+        }
+        let payment = load_payment(&env, payment_id).ok_or(Error::PaymentNotFound)?;
+        if payment.status != PaymentStatus::Locked {
+            return Err(Error::PaymentNotLocked);
+        }
+        let key = rider_key(payment_id);
+        env.storage().persistent().set(&key, &rider);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_BUMP_THRESHOLD, PERSISTENT_BUMP_TO);
+        Ok(())
+    }
+
+    /// Amounts each recipient received when the escrow was released.
+    pub fn get_settlement(env: Env, payment_id: u64) -> Result<Settlement, Error> {
+        env.storage()
+            .persistent()
+            .get(&settlement_key(payment_id))
+            .ok_or(Error::PaymentNotFound)
+    }
+
+    /// Require the configured coordinator's authorization.
+    fn require_coordinator(env: &Env) -> Result<(), Error> {
+        let coordinator: Address = env
+            .storage()
+            .instance()
+            .get(&COORDINATOR)
+            .ok_or(Error::CoordinatorNotConfigured)?;
+        coordinator.require_auth();
+        Ok(())
+    }
+
+    /// Lock `amount` of `token` from `payer` against coordinator order
+    /// `order_id`, payable to `payee` once the order is delivered. An order
+    /// split across several banks has one escrow per bank. Returns the new
+    /// payment ID.
+    ///
+    /// Coordinator only; the payer must sign the transfer too. Otherwise
+    /// anyone could claim an order's escrow slot for a payee first and make
+    /// the coordinator's own escrow fail with `DuplicatePayment`.
+    ///
+    /// The funds can only leave through the coordinator: `release_on_delivery`
+    /// pays them out once custody of every unit is confirmed, and
+    /// `refund_on_cancel` returns them if the order is cancelled. The escrow
+    /// belongs to no blood request (`request_id` is 0); `get_escrow_order`
+    /// returns its order, and the request payment entrypoints refuse it with
+    /// `OrderEscrow`.
+    pub fn open_escrow(
+        env: Env,
+        order_id: u64,
+        payer: Address,
+        payee: Address,
+        amount: i128,
+        token: Address,
+    ) -> Result<u64, Error> {
+        Self::require_not_paused(&env)?;
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if payer == payee {
+            return Err(Error::SamePayerPayee);
+        }
+        Self::require_coordinator(&env)?;
+        payer.require_auth();
+
+        // One escrow per payee and order.
+        let mut ids = get_order_escrows(&env, order_id);
+        for existing in ids.iter() {
+            if load_payment(&env, existing).is_some_and(|p| p.payee == payee) {
+                return Err(Error::DuplicatePayment);
+            }
+        }
+
+        let token_client = token::Client::new(&env, &token);
+        token_client.transfer(&payer, &env.current_contract_address(), &amount);
+
+        let id = get_counter(&env) + 1;
+        set_counter(&env, id);
+
+        let now = env.ledger().timestamp();
+        let payment = Payment {
+            id,
+            request_id: 0,
+            payer: payer.clone(),
+            payee: payee.clone(),
+            amount,
+            status: PaymentStatus::Locked,
+            created_at: now,
+            updated_at: now,
+            dispute_reason_code: None,
+            dispute_case_id: None,
+            dispute_resolved: false,
+            token: Some(token),
+        };
+
+        store_payment(&env, &payment);
+        set_escrow_order(&env, id, order_id);
+        index_by_payer(&env, &payer, id);
+        index_by_payee(&env, &payee, id);
+        index_by_status(&env, PaymentStatus::Locked, id);
+        ids.push_back(id);
+        set_order_escrows(&env, order_id, &ids);
+        update_stats_on_transition(&env, amount, PaymentStatus::Pending, PaymentStatus::Locked)?;
+        snapshot_fees(&env, id);
+
+        PaymentEscrowed { payment_id: id }.publish(&env);
+
+        Ok(id)
+    }
+
+    /// Fund a `Pending` payment from its payer in `token` and lock it in
+    /// escrow. Coordinator only; the payer must sign the transfer too. If
+    /// the coordinator's flow fails afterwards, it returns the funds with
+    /// `refund_on_cancel`.
+    pub fn lock_payment(env: Env, payment_id: u64, token: Address) -> Result<(), Error> {
+        Self::require_not_paused(&env)?;
+        Self::require_coordinator(&env)?;
+
+        let mut payment = load_payment(&env, payment_id).ok_or(Error::PaymentNotFound)?;
+        if payment.status != PaymentStatus::Pending {
+            return Err(Error::PaymentNotPending);
+        }
+        payment.payer.require_auth();
+
+        token::Client::new(&env, &token).transfer(
+            &payment.payer,
+            &env.current_contract_address(),
+            &payment.amount,
+        );
+
+        payment.status = PaymentStatus::Locked;
+        payment.token = Some(token);
+        payment.updated_at = env.ledger().timestamp();
+        store_payment(&env, &payment);
+        remove_from_status_index(&env, PaymentStatus::Pending, payment_id);
+        index_by_status(&env, PaymentStatus::Locked, payment_id);
+        update_stats_on_transition(&env, payment.amount, PaymentStatus::Pending, PaymentStatus::Locked)?;
+        snapshot_fees(&env, payment_id);
+
+        PaymentEscrowed { payment_id }.publish(&env);
+        Ok(())
+    }
+
+    /// Pay out every locked escrow of `order_id` to its payee. Coordinator
+    /// only; the coordinator calls this once delivery of every unit in the
+    /// order has been confirmed. Escrows already refunded are skipped.
+    pub fn release_on_delivery(env: Env, order_id: u64) -> Result<(), Error> {
+        Self::require_not_paused(&env)?;
+        Self::require_coordinator(&env)?;
+
+        let ids = get_order_escrows(&env, order_id);
+        if ids.is_empty() {
+            return Err(Error::PaymentNotFound);
+        }
+        let mut released = 0u32;
+        for payment_id in ids.iter() {
+            let Some(mut payment) = load_payment(&env, payment_id) else {
+                continue;
+            };
+            if payment.status != PaymentStatus::Locked {
+                continue;
+            }
+            Self::settle_order_escrow(&env, &mut payment, PaymentStatus::Released)?;
+            PaymentReleased { payment_id, payee: payment.payee.clone(), amount: payment.amount }.publish(&env);
+            released += 1;
+        }
+        if released == 0 {
+            return Err(Error::PaymentNotLocked);
+        }
+        Ok(())
+    }
+
+    /// Pay out one locked order escrow to its payee. Coordinator only; the
+    /// coordinator calls this for the legs already delivered when the rest
+    /// of their order is cancelled.
+    pub fn release_order_escrow(env: Env, payment_id: u64) -> Result<(), Error> {
+        Self::require_not_paused(&env)?;
+        Self::require_coordinator(&env)?;
+
+        let mut payment = load_payment(&env, payment_id).ok_or(Error::PaymentNotFound)?;
+        if payment.status != PaymentStatus::Locked {
+            return Err(Error::PaymentNotLocked);
+        }
+        Self::settle_order_escrow(&env, &mut payment, PaymentStatus::Released)?;
+        PaymentReleased { payment_id, payee: payment.payee.clone(), amount: payment.amount }.publish(&env);
+        Ok(())
+    }
+
+    /// Return a locked order escrow to its payer when the order is
+    /// cancelled, or a payment locked with `lock_payment` when the
+    /// coordinator's flow fails. Coordinator only.
+    pub fn refund_on_cancel(env: Env, payment_id: u64) -> Result<(), Error> {
+        Self::require_not_paused(&env)?;
+        Self::require_coordinator(&env)?;
+
+        let mut payment = load_payment(&env, payment_id).ok_or(Error::PaymentNotFound)?;
+        if payment.status != PaymentStatus::Locked {
+            return Err(Error::PaymentNotLocked);
+        }
+        Self::settle_order_escrow(&env, &mut payment, PaymentStatus::Refunded)?;
+        PaymentRefunded { payment_id, payer: payment.payer.clone(), amount: payment.amount }.publish(&env);
+        Ok(())
+    }
+
+    /// Move a locked escrow's funds to its payee and rider (`Released`) or
+    /// back to its payer (`Refunded`) and record the new status.
+    fn settle_order_escrow(env: &Env, payment: &mut Payment, status: PaymentStatus) -> Result<(), Error> {
+        if status == PaymentStatus::Released {
+            pay_out(env, payment)?;
+        } else {
+            let token_addr = payment.token.clone().ok_or(Error::NotEscrowPayment)?;
+            token::Client::new(env, &token_addr).transfer(
+                &env.current_contract_address(),
+                &payment.payer,
+                &payment.amount,
+            );
+        }
+
+        let old_status = payment.status;
+        payment.status = status;
+        payment.updated_at = env.ledger().timestamp();
+        store_payment(env, payment);
+        remove_from_status_index(env, old_status, payment.id);
+        index_by_status(env, status, payment.id);
+        update_stats_on_transition(env, payment.amount, old_status, status)
+    }
+
     /// Hospital confirms receipt of blood units (issue #848 fix).
     /// Payment is only released when both coordinator and hospital confirm.
     pub fn confirm_receipt(env: Env, payment_id: u64, hospital: Address) -> Result<(), Error> {
@@ -849,6 +1316,7 @@ impl PaymentContract {
         Self::require_not_paused(&env)?;
 
         let mut payment = load_payment(&env, payment_id).ok_or(Error::PaymentNotFound)?;
+        require_not_order_escrow(&env, payment_id)?;
 
         if payment.status != PaymentStatus::Locked {
             return Err(Error::PaymentNotLocked);
@@ -873,13 +1341,7 @@ impl PaymentContract {
         }
 
         // Both parties confirmed - release payment
-        let token_addr = payment.token.clone().ok_or(Error::NotEscrowPayment)?;
-        let token_client = token::Client::new(&env, &token_addr);
-        token_client.transfer(
-            &env.current_contract_address(),
-            &payment.payee,
-            &payment.amount,
-        );
+        pay_out(&env, &payment)?;
 
         let old_status = payment.status;
         payment.status = PaymentStatus::Released;
@@ -908,6 +1370,7 @@ impl PaymentContract {
         Self::require_admin(&env, &caller)?;
 
         let mut payment = load_payment(&env, payment_id).ok_or(Error::PaymentNotFound)?;
+        require_not_order_escrow(&env, payment_id)?;
 
         if payment.status != PaymentStatus::Locked {
             return Err(Error::PaymentNotLocked);
@@ -945,6 +1408,7 @@ impl PaymentContract {
         Self::require_not_paused(&env)?;
         Self::require_admin(&env, &caller)?;
         let mut payment = load_payment(&env, payment_id).ok_or(Error::PaymentNotFound)?;
+        require_not_order_escrow(&env, payment_id)?;
         let old_status = payment.status;
         payment.status = status;
         payment.updated_at = env.ledger().timestamp();
@@ -974,6 +1438,7 @@ impl PaymentContract {
         caller.require_auth();
         Self::require_not_paused(&env)?;
         let mut payment = load_payment(&env, payment_id).ok_or(Error::PaymentNotFound)?;
+        require_not_order_escrow(&env, payment_id)?;
         if caller != payment.payer && caller != payment.payee {
             return Err(Error::Unauthorized);
         }
@@ -1011,6 +1476,12 @@ impl PaymentContract {
         load_payment(&env, payment_id).ok_or(Error::PaymentNotFound)
     }
 
+    /// The coordinator order an escrow was opened for with `open_escrow`,
+    /// or `None` for a request payment.
+    pub fn get_escrow_order(env: Env, payment_id: u64) -> Option<u64> {
+        escrow_order(&env, payment_id)
+    }
+
     pub fn get_payment_by_request(env: Env, request_id: u64) -> Result<Payment, Error> {
         let payment_id: u64 = env
             .storage()
@@ -1310,7 +1781,7 @@ impl PaymentContract {
             if payment.status != PaymentStatus::Disputed {
                 continue;
             }
-            if payment.token.is_none() {
+            if payment.token.is_none() || escrow_order(&env, pid).is_some() {
                 continue;
             }
             if now < payment.updated_at + timeout {
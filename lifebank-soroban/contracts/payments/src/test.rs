@@ -962,3 +962,206 @@ fn test_create_escrow_rejects_negative_amount() {
     let result = client.try_create_escrow(&1u64, &hospital, &payee, &-1i128, &token_id);
     assert_eq!(result, Err(Ok(Error::InvalidAmount)), "Negative amount escrow must be rejected");
 }
+
+// ── Order escrow ───────────────────────────────────────────────────────────────
+
+/// Every escrow of a delivered order is paid out on the coordinator's
+/// authorization.
+#[test]
+fn test_release_on_delivery_pays_every_order_escrow() {
+    let (env, cid, admin) = setup_with_admin();
+    let client = PaymentContractClient::new(&env, &cid);
+    let hospital = Address::generate(&env);
+    let (first_bank, second_bank) = (Address::generate(&env), Address::generate(&env));
+    let token_id = deploy_token_with_balance(&env, &admin, &hospital, 1_000);
+
+    let result = client.try_open_escrow(&9u64, &hospital, &first_bank, &300i128, &token_id);
+    assert_eq!(result, Err(Ok(Error::CoordinatorNotConfigured)));
+    let result = client.try_release_on_delivery(&9u64);
+    assert_eq!(result, Err(Ok(Error::CoordinatorNotConfigured)));
+
+    let coordinator = Address::generate(&env);
+    client.set_coordinator(&admin, &coordinator);
+    client.open_escrow(&9u64, &hospital, &first_bank, &300i128, &token_id);
+    let signers = env.auths();
+    assert!(signers.iter().any(|(signer, _)| *signer == coordinator));
+    assert!(signers.iter().any(|(signer, _)| *signer == hospital));
+    client.open_escrow(&9u64, &hospital, &second_bank, &500i128, &token_id);
+    let result = client.try_open_escrow(&9u64, &hospital, &first_bank, &100i128, &token_id);
+    assert_eq!(result, Err(Ok(Error::DuplicatePayment)));
+
+    client.release_on_delivery(&9u64);
+    assert_eq!(env.auths().first().unwrap().0, coordinator);
+
+    let token_client = soroban_sdk::token::Client::new(&env, &token_id);
+    assert_eq!(token_client.balance(&first_bank), 300);
+    assert_eq!(token_client.balance(&second_bank), 500);
+    assert_eq!(token_client.balance(&cid), 0);
+    assert_eq!(client.get_payment_statistics().total_released, 800);
+    assert_eq!(client.try_release_on_delivery(&9u64), Err(Ok(Error::PaymentNotLocked)));
+    assert_eq!(client.try_release_on_delivery(&10u64), Err(Ok(Error::PaymentNotFound)));
+}
+
+/// A cancelled order's escrow goes back to the payer and is skipped on
+/// release.
+#[test]
+fn test_refund_on_cancel_returns_order_escrow_to_payer() {
+    let (env, cid, admin) = setup_with_admin();
+    let client = PaymentContractClient::new(&env, &cid);
+    let hospital = Address::generate(&env);
+    let (first_bank, second_bank) = (Address::generate(&env), Address::generate(&env));
+    let token_id = deploy_token_with_balance(&env, &admin, &hospital, 1_000);
+    client.set_coordinator(&admin, &Address::generate(&env));
+
+    let refunded = client.open_escrow(&9u64, &hospital, &first_bank, &300i128, &token_id);
+    client.open_escrow(&9u64, &hospital, &second_bank, &500i128, &token_id);
+    client.refund_on_cancel(&refunded);
+    assert_eq!(client.get_payment(&refunded).status, PaymentStatus::Refunded);
+    assert_eq!(client.try_refund_on_cancel(&refunded), Err(Ok(Error::PaymentNotLocked)));
+
+    client.release_on_delivery(&9u64);
+    let token_client = soroban_sdk::token::Client::new(&env, &token_id);
+    assert_eq!(token_client.balance(&hospital), 500);
+    assert_eq!(token_client.balance(&first_bank), 0);
+    assert_eq!(token_client.balance(&second_bank), 500);
+}
+
+/// A single order escrow can be paid out on its own, leaving the order's
+/// other escrows locked.
+#[test]
+fn test_release_order_escrow_pays_one_escrow() {
+    let (env, cid, admin) = setup_with_admin();
+    let client = PaymentContractClient::new(&env, &cid);
+    let hospital = Address::generate(&env);
+    let (first_bank, second_bank) = (Address::generate(&env), Address::generate(&env));
+    let token_id = deploy_token_with_balance(&env, &admin, &hospital, 1_000);
+    client.set_coordinator(&admin, &Address::generate(&env));
+
+    let released = client.open_escrow(&9u64, &hospital, &first_bank, &300i128, &token_id);
+    let locked = client.open_escrow(&9u64, &hospital, &second_bank, &500i128, &token_id);
+    client.release_order_escrow(&released);
+    assert_eq!(client.get_payment(&released).status, PaymentStatus::Released);
+    assert_eq!(client.get_payment(&locked).status, PaymentStatus::Locked);
+    assert_eq!(client.try_release_order_escrow(&released), Err(Ok(Error::PaymentNotLocked)));
+
+    let token_client = soroban_sdk::token::Client::new(&env, &token_id);
+    assert_eq!(token_client.balance(&first_bank), 300);
+    assert_eq!(token_client.balance(&cid), 500);
+}
+
+/// Order escrows belong to no request and only the coordinator can settle
+/// them; the request payment entrypoints refuse them.
+#[test]
+fn test_order_escrow_rejected_by_request_payment_entrypoints() {
+    let (env, cid, admin) = setup_with_admin();
+    let client = PaymentContractClient::new(&env, &cid);
+    let hospital = Address::generate(&env);
+    let bank = Address::generate(&env);
+    let token_id = deploy_token_with_balance(&env, &admin, &hospital, 1_000);
+    client.set_coordinator(&admin, &Address::generate(&env));
+
+    // A request payment numbered like the order keeps its index
+    let (request_payment, _, _) = make_payment(&env, &client, 9, 100);
+    let escrow = client.open_escrow(&9u64, &hospital, &bank, &300i128, &token_id);
+    assert_eq!(client.get_payment(&escrow).request_id, 0);
+    assert_eq!(client.get_escrow_order(&escrow), Some(9));
+    assert_eq!(client.get_escrow_order(&request_payment), None);
+
+    assert_eq!(client.try_release_escrow(&admin, &escrow), Err(Ok(Error::OrderEscrow)));
+    assert_eq!(client.try_confirm_receipt(&escrow, &hospital), Err(Ok(Error::OrderEscrow)));
+    assert_eq!(client.try_refund_escrow(&admin, &escrow), Err(Ok(Error::OrderEscrow)));
+    let result = client.try_update_status(&escrow, &PaymentStatus::Refunded, &admin);
+    assert_eq!(result, Err(Ok(Error::OrderEscrow)));
+    let case_id = soroban_sdk::String::from_str(&env, "case-1");
+    let result = client.try_record_dispute(&escrow, &DisputeReason::FailedDelivery, &case_id, &bank);
+    assert_eq!(result, Err(Ok(Error::OrderEscrow)));
+
+    // Even a disputed order escrow is left to the coordinator
+    env.as_contract(&cid, || {
+        let mut payment = load_payment(&env, escrow).unwrap();
+        payment.status = PaymentStatus::Disputed;
+        store_payment(&env, &payment);
+    });
+    client.set_dispute_timeout(&admin, &0u64);
+    let refunded = client.process_expired_disputes(&admin, &soroban_sdk::vec![&env, escrow]);
+    assert!(refunded.is_empty());
+
+    let token_client = soroban_sdk::token::Client::new(&env, &token_id);
+    assert_eq!(token_client.balance(&cid), 300);
+    assert_eq!(client.get_payment_by_request(&9u64).id, request_payment);
+}
+
+/// A pending payment is funded from its payer on the coordinator's
+/// authorization and can be refunded the same way.
+#[test]
+fn test_lock_payment_funds_pending_payment() {
+    let (env, cid, admin) = setup_with_admin();
+    let client = PaymentContractClient::new(&env, &cid);
+    let (payment_id, payer, _) = make_payment(&env, &client, 4, 700);
+    let token_id = deploy_token_with_balance(&env, &admin, &payer, 1_000);
+    let result = client.try_lock_payment(&payment_id, &token_id);
+    assert_eq!(result, Err(Ok(Error::CoordinatorNotConfigured)));
+
+    let coordinator = Address::generate(&env);
+    client.set_coordinator(&admin, &coordinator);
+    client.lock_payment(&payment_id, &token_id);
+    let payment = client.get_payment(&payment_id);
+    assert_eq!(payment.status, PaymentStatus::Locked);
+    assert_eq!(payment.token, Some(token_id.clone()));
+    let result = client.try_lock_payment(&payment_id, &token_id);
+    assert_eq!(result, Err(Ok(Error::PaymentNotPending)));
+
+    let token_client = soroban_sdk::token::Client::new(&env, &token_id);
+    assert_eq!(token_client.balance(&payer), 300);
+    assert_eq!(token_client.balance(&cid), 700);
+
+    client.refund_on_cancel(&payment_id);
+    assert_eq!(token_client.balance(&payer), 1_000);
+    assert_eq!(client.get_payment(&payment_id).status, PaymentStatus::Refunded);
+}
+
+// ── Fee splitting ──────────────────────────────────────────────────────────────
+
+/// Releases are split by the fee schedule in force when the escrow was
+/// locked; without an assigned rider the rider share stays with the bank.
+#[test]
+fn test_release_splits_amount_by_fee_schedule() {
+    let (env, cid, admin) = setup_with_admin();
+    let client = PaymentContractClient::new(&env, &cid);
+    let hospital = Address::generate(&env);
+    let (bank, rider, platform) = (Address::generate(&env), Address::generate(&env), Address::generate(&env));
+    let token_id = deploy_token_with_balance(&env, &admin, &hospital, 2_000);
+    client.set_coordinator(&admin, &Address::generate(&env));
+
+    let over_cap = FeeSchedule { rider_bps: 2_001, platform_bps: 0, platform: platform.clone() };
+    assert_eq!(client.try_set_fee_schedule(&admin, &over_cap), Err(Ok(Error::FeeAboveCap)));
+    client.set_fee_schedule(&admin, &FeeSchedule { rider_bps: 1_000, platform_bps: 250, platform: platform.clone() });
+
+    let with_rider = client.open_escrow(&1u64, &hospital, &bank, &1_000i128, &token_id);
+    let without_rider = client.open_escrow(&2u64, &hospital, &bank, &1_000i128, &token_id);
+    // Escrows already locked keep the schedule they were opened under
+    client.set_fee_schedule(&admin, &FeeSchedule { rider_bps: 2_000, platform_bps: 1_000, platform: Address::generate(&env) });
+    let result = client.try_assign_rider(&Address::generate(&env), &with_rider, &rider);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    client.assign_rider(&admin, &with_rider, &rider);
+    client.release_on_delivery(&1u64);
+    client.release_on_delivery(&2u64);
+
+    let settlement = client.get_settlement(&with_rider);
+    assert_eq!(
+        (settlement.bank_amount, settlement.rider_amount, settlement.platform_amount),
+        (875, 100, 25)
+    );
+    assert_eq!(settlement.rider, Some(rider.clone()));
+    let settlement = client.get_settlement(&without_rider);
+    assert_eq!(
+        (settlement.bank_amount, settlement.rider_amount, settlement.platform_amount),
+        (975, 0, 25)
+    );
+
+    let token_client = soroban_sdk::token::Client::new(&env, &token_id);
+    assert_eq!(token_client.balance(&bank), 1_850);
+    assert_eq!(token_client.balance(&rider), 100);
+    assert_eq!(token_client.balance(&platform), 50);
+    assert_eq!(token_client.balance(&cid), 0);
+}
@@ -1,4 +1,4 @@
-use crate::types::{BloodRequest, RequestCreatedEvent, RequestStatus};
+use crate::types::{BloodRequest, BloodType, RequestCreatedEvent, RequestStatus};
 use soroban_sdk::{contractevent, Address, Env};
 
 #[contractevent(topics = ["initialized"], data_format = "vec")]
@@ -7,6 +7,24 @@ pub struct RequestsInitialized {
     pub inventory_contract: Address,
 }
 
+#[contractevent(topics = ["restock_requested"], data_format = "vec")]
+pub struct RestockRequested {
+    pub request_id: u64,
+    pub bank: Address,
+    pub deficit_units: u32,
+}
+
+/// One per authorized blood bank when a request is escalated.
+#[contractevent(topics = ["request_broadcast"], data_format = "vec")]
+pub struct RequestBroadcast {
+    #[topic]
+    pub bank: Address,
+    pub request_id: u64,
+    pub blood_type: BloodType,
+    pub quantity_ml: u32,
+    pub respond_by: u64,
+}
+
 #[contractevent(topics = ["request_cancelled"], data_format = "vec")]
 pub struct RequestCancelled {
     pub request_id: u64,
@@ -43,6 +61,25 @@ pub fn emit_request_created(env: &Env, request: &BloodRequest) {
     .publish(env);
 }
 
+pub fn emit_restock_requested(env: &Env, request_id: u64, bank: &Address, deficit_units: u32) {
+    RestockRequested {
+        request_id,
+        bank: bank.clone(),
+        deficit_units,
+    }
+    .publish(env);
+}
+
+pub fn emit_request_broadcast(env: &Env, bank: &Address, request: &BloodRequest) {
+    RequestBroadcast {
+        bank: bank.clone(),
+        request_id: request.id,
+        blood_type: request.blood_type,
+        quantity_ml: request.quantity_ml,
+        respond_by: request.required_by_timestamp,
+    }
+    .publish(env);
+}
 
 pub fn emit_request_cancelled(env: &Env, request_id: u64, actor: &Address, timestamp: u64) {
     RequestCancelled {
@@ -33,6 +33,9 @@ use inventory_client::InventoryContractClient;
 
 const CONTRACT_VERSION: u32 = 1;
 
+/// Volume of one standard whole-blood unit, used to size restock requests.
+const STANDARD_UNIT_ML: u32 = 450;
+
 #[contract]
 pub struct RequestContract;
 
@@ -149,6 +152,19 @@ impl RequestContract {
         Ok(())
     }
 
+    /// Every currently authorized blood bank.
+    pub fn get_blood_banks(env: Env) -> soroban_sdk::Vec<Address> {
+        storage::get_blood_banks(&env)
+    }
+
+    /// Set the coordinator allowed to escalate requests. Admin only.
+    pub fn set_coordinator_contract(env: Env, coordinator: Address) -> Result<(), ContractError> {
+        storage::require_initialized(&env)?;
+        storage::get_admin(&env).require_auth();
+        storage::set_coordinator_contract(&env, &coordinator);
+        Ok(())
+    }
+
     pub fn authorize_rider(env: Env, rider: Address) -> Result<(), ContractError> {
         storage::require_initialized(&env)?;
         storage::get_admin(&env).require_auth();
@@ -217,6 +233,103 @@ impl RequestContract {
         Ok(request_id)
     }
 
+    /// Open a restock request on behalf of a blood bank whose stock fell below
+    /// par. Only the configured inventory contract may call this; the bank is
+    /// recorded as the requester and `deficit_units` is sized in standard
+    /// 450 ml units. The deadline follows from `urgency`.
+    pub fn create_restock_request(
+        env: Env,
+        bank: Address,
+        blood_type: BloodType,
+        deficit_units: u32,
+        urgency: Urgency,
+    ) -> Result<u64, ContractError> {
+        storage::require_initialized(&env)?;
+        storage::get_inventory_contract(&env).require_auth();
+
+        let quantity_ml = deficit_units.saturating_mul(STANDARD_UNIT_ML);
+        validation::validate_quantity(quantity_ml)?;
+
+        let now = env.ledger().timestamp();
+        let request_id = storage::increment_request_counter(&env);
+        let mut request = BloodRequest {
+            id: request_id,
+            hospital_id: bank.clone(),
+            blood_type,
+            component: BloodComponent::WholeBlood,
+            quantity_ml,
+            urgency,
+            created_timestamp: now,
+            required_by_timestamp: now + urgency.restock_window_secs(),
+            status: RequestStatus::Pending,
+            assigned_units: soroban_sdk::Vec::new(&env),
+            fulfilled_quantity_ml: 0,
+            reservation_id: None,
+            history: soroban_sdk::Vec::new(&env),
+        };
+        Self::append_history(
+            &env,
+            &mut request,
+            &bank,
+            RequestStatus::Pending,
+            true,
+            RequestStatus::Pending,
+            String::from_str(&env, "Restock below par level"),
+            0,
+            false,
+        );
+
+        storage::set_request(&env, &request);
+        events::emit_request_created(&env, &request);
+        events::emit_restock_requested(&env, request_id, &bank, deficit_units);
+
+        Ok(request_id)
+    }
+
+    /// Escalate a pending request to every authorized blood bank. Only the
+    /// configured coordinator may call this. The request becomes `Critical`,
+    /// its deadline is pulled in to `respond_by` if that is sooner, and one
+    /// `request_broadcast` event is emitted per bank. Returns the number of
+    /// banks notified.
+    pub fn broadcast_request(
+        env: Env,
+        request_id: u64,
+        respond_by: u64,
+    ) -> Result<u32, ContractError> {
+        storage::require_initialized(&env)?;
+        let coordinator =
+            storage::get_coordinator_contract(&env).ok_or(ContractError::Unauthorized)?;
+        coordinator.require_auth();
+        validation::validate_timestamp(&env, respond_by)?;
+
+        let mut request =
+            storage::get_request(&env, request_id).ok_or(ContractError::RequestNotFound)?;
+        if request.status != RequestStatus::Pending {
+            return Err(ContractError::InvalidStatusTransition);
+        }
+
+        request.urgency = Urgency::Critical;
+        request.required_by_timestamp = request.required_by_timestamp.min(respond_by);
+        Self::append_history(
+            &env,
+            &mut request,
+            &coordinator,
+            RequestStatus::Pending,
+            false,
+            RequestStatus::Pending,
+            String::from_str(&env, "Escalated to all blood banks"),
+            0,
+            false,
+        );
+        storage::set_request(&env, &request);
+
+        let banks = storage::get_blood_banks(&env);
+        for bank in banks.iter() {
+            events::emit_request_broadcast(&env, &bank, &request);
+        }
+        Ok(banks.len())
+    }
+
     /// Create multiple blood requests in a single transaction.
     /// Each tuple is `(blood_type, component, quantity_ml, urgency, required_by_timestamp)`.
     /// Returns the Vec of new request IDs in input order.
@@ -1,6 +1,6 @@
 use crate::error::ContractError;
 use crate::types::{BloodRequest, ContractMetadata, DataKey};
-use soroban_sdk::{Address, Env, String};
+use soroban_sdk::{Address, Env, String, Vec};
 
 /// Persistent storage TTL constants (ledgers; one ledger ≈ 5 s on mainnet).
 const TTL_THRESHOLD: u32 = 518_400; // ~30 days
@@ -89,17 +89,51 @@ pub fn is_hospital_authorized(env: &Env, hospital: &Address) -> bool {
 }
 
 pub fn authorize_blood_bank(env: &Env, blood_bank: &Address) {
+    if !is_blood_bank_authorized(env, blood_bank) {
+        let mut banks = get_blood_banks(env);
+        banks.push_back(blood_bank.clone());
+        set_blood_banks(env, &banks);
+    }
     env.storage()
         .instance()
         .set(&DataKey::AuthorizedBloodBank(blood_bank.clone()), &true);
 }
 
 pub fn revoke_blood_bank(env: &Env, blood_bank: &Address) {
+    let mut banks = get_blood_banks(env);
+    if let Some(index) = banks.first_index_of(blood_bank) {
+        banks.remove(index);
+        set_blood_banks(env, &banks);
+    }
     env.storage()
         .instance()
         .remove(&DataKey::AuthorizedBloodBank(blood_bank.clone()));
 }
 
+pub fn get_blood_banks(env: &Env) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BloodBanks)
+        .unwrap_or(Vec::new(env))
+}
+
+fn set_blood_banks(env: &Env, banks: &Vec<Address>) {
+    env.storage().persistent().set(&DataKey::BloodBanks, banks);
+    env.storage()
+        .persistent()
+        .extend_ttl(&DataKey::BloodBanks, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+pub fn set_coordinator_contract(env: &Env, coordinator: &Address) {
+    env.storage()
+        .instance()
+        .set(&DataKey::CoordinatorContract, coordinator);
+}
+
+pub fn get_coordinator_contract(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::CoordinatorContract)
+}
+
 pub fn is_blood_bank_authorized(env: &Env, blood_bank: &Address) -> bool {
     env.storage()
         .instance()
@@ -130,6 +130,64 @@ fn test_create_request_success() {
     assert_eq!(request.assigned_units.len(), 0);
 }
 
+#[test]
+fn test_create_restock_request_sizes_and_dates_request() {
+    let (env, client, _contract_id, _admin, _inventory_contract) = create_initialized_contract();
+    let bank = Address::generate(&env);
+
+    env.ledger().set_timestamp(1_000);
+
+    let request_id =
+        client.create_restock_request(&bank, &BloodType::ONegative, &3u32, &Urgency::Critical);
+
+    let request = client.get_request(&request_id);
+    assert_eq!(request.hospital_id, bank);
+    assert_eq!(request.blood_type, BloodType::ONegative);
+    assert_eq!(request.quantity_ml, 1_350);
+    assert_eq!(request.urgency, Urgency::Critical);
+    assert_eq!(request.required_by_timestamp, 1_000 + 6 * 3600);
+    assert_eq!(request.status, RequestStatus::Pending);
+
+    let result =
+        client.try_create_restock_request(&bank, &BloodType::ONegative, &0u32, &Urgency::Routine);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidQuantity)));
+}
+
+#[test]
+fn test_broadcast_request_tightens_deadline_and_notifies_every_bank() {
+    let (env, client, _contract_id, _admin, _inventory_contract) = create_initialized_contract();
+    let hospital = authorize_hospital(&env, &client);
+    let coordinator = Address::generate(&env);
+    let (bank_a, bank_b) = (Address::generate(&env), Address::generate(&env));
+    client.authorize_blood_bank(&bank_a);
+    client.authorize_blood_bank(&bank_b);
+    client.authorize_blood_bank(&bank_a);
+    assert_eq!(client.get_blood_banks().len(), 2);
+
+    env.ledger().set_timestamp(1_000);
+    let request_id = client.create_request(
+        &hospital,
+        &BloodType::ONegative,
+        &BloodComponent::WholeBlood,
+        &900u32,
+        &Urgency::Urgent,
+        &90_000u64,
+    );
+
+    let result = client.try_broadcast_request(&request_id, &2_800u64);
+    assert_eq!(result, Err(Ok(crate::ContractError::Unauthorized)));
+
+    client.set_coordinator_contract(&coordinator);
+    assert_eq!(client.broadcast_request(&request_id, &2_800u64), 2);
+    let request = client.get_request(&request_id);
+    assert_eq!(request.urgency, Urgency::Critical);
+    assert_eq!(request.required_by_timestamp, 2_800);
+    assert_eq!(request.status, RequestStatus::Pending);
+
+    client.revoke_blood_bank(&bank_b);
+    assert_eq!(client.get_blood_banks(), soroban_sdk::vec![&env, bank_a]);
+}
+
 #[test]
 fn test_create_request_generates_unique_ids() {
     let (env, client, _contract_id, _admin, _inventory_contract) = create_initialized_contract();
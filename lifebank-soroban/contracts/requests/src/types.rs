@@ -20,6 +20,10 @@ pub enum DataKey {
     AuthorizedBloodBank(Address),
     AuthorizedRider(Address),
     Request(u64),
+    /// Every authorized blood bank, in authorization order
+    BloodBanks,
+    /// Coordinator allowed to escalate requests to all banks
+    CoordinatorContract,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -70,6 +74,16 @@ impl Urgency {
             Self::Scheduled => 1,
         }
     }
+
+    /// How long a bank has to be restocked at this urgency.
+    pub fn restock_window_secs(&self) -> u64 {
+        match self {
+            Self::Critical => 6 * 3600,
+            Self::Urgent => 24 * 3600,
+            Self::Routine => 3 * 86400,
+            Self::Scheduled => 7 * 86400,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
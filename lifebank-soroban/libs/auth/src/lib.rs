@@ -0,0 +1,69 @@
+#![no_std]
+#![deny(deprecated)]
+
+//! Role checks against the identity contract's `AccessControlContract`.
+//!
+//! Contracts store the identity contract address in their own config and call
+//! [`require_role`] instead of keeping a private role table.
+
+use soroban_sdk::{contractclient, contracttype, Address, Env};
+
+/// Mirror of `identity_contract::Role`. Variant order must match the identity
+/// contract so that values encode identically across the call.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Role {
+    Admin,
+    BloodBank,
+    Hospital,
+    Donor,
+    Rider,
+    Custom(u32),
+}
+
+#[contractclient(name = "IdentityClient")]
+#[allow(dead_code)]
+pub trait IdentityInterface {
+    fn has_role(env: Env, address: Address, role: Role) -> bool;
+}
+
+/// Why a role check failed. Callers map this onto their own contract error.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AuthError {
+    /// The address does not currently hold the role (or is suspended)
+    MissingRole,
+    /// The identity contract could not be invoked or returned a bad value
+    IdentityCallFailed,
+}
+
+/// Whether `address` currently holds `role` in the identity contract.
+pub fn has_role(
+    env: &Env,
+    identity_contract: &Address,
+    address: &Address,
+    role: Role,
+) -> Result<bool, AuthError> {
+    match IdentityClient::new(env, identity_contract).try_has_role(address, &role) {
+        Ok(Ok(held)) => Ok(held),
+        _ => Err(AuthError::IdentityCallFailed),
+    }
+}
+
+/// Fail with `MissingRole` unless `address` currently holds `role`.
+///
+/// Only checks membership; callers still `require_auth` on `address`.
+pub fn require_role(
+    env: &Env,
+    identity_contract: &Address,
+    address: &Address,
+    role: Role,
+) -> Result<(), AuthError> {
+    if has_role(env, identity_contract, address, role)? {
+        Ok(())
+    } else {
+        Err(AuthError::MissingRole)
+    }
+}
+
+#[cfg(test)]
+mod test;
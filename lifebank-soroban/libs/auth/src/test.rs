@@ -0,0 +1,49 @@
+#![cfg(test)]
+
+use super::*;
+use identity_contract::{AccessControlContract, AccessControlContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+fn setup_identity(env: &Env) -> (Address, AccessControlContractClient<'_>, Address) {
+    let admin = Address::generate(env);
+    let contract_id = env.register(AccessControlContract, ());
+    let client = AccessControlContractClient::new(env, &contract_id);
+    client.initialize(&admin);
+    (contract_id, client, admin)
+}
+
+#[test]
+fn test_require_role_passes_for_holder() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (identity, client, admin) = setup_identity(&env);
+
+    let bank = Address::generate(&env);
+    client.grant_role_with_expiry(&admin, &bank, &identity_contract::Role::BloodBank, &None);
+
+    assert_eq!(
+        require_role(&env, &identity, &bank, Role::BloodBank),
+        Ok(())
+    );
+    assert_eq!(has_role(&env, &identity, &bank, Role::Hospital), Ok(false));
+}
+
+#[test]
+fn test_require_role_rejects_missing_and_suspended() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (identity, client, admin) = setup_identity(&env);
+
+    let rider = Address::generate(&env);
+    assert_eq!(
+        require_role(&env, &identity, &rider, Role::Rider),
+        Err(AuthError::MissingRole)
+    );
+
+    client.grant_role_with_expiry(&admin, &rider, &identity_contract::Role::Rider, &None);
+    client.suspend_address(&admin, &rider);
+    assert_eq!(
+        require_role(&env, &identity, &rider, Role::Rider),
+        Err(AuthError::MissingRole)
+    );
+}